@@ -37,6 +37,13 @@ export const BUILDER_ENTRIES: RegistryEntry[] = [
         description: "Blend mode selection namespace.",
         readonly: true,
       },
+      {
+        name: "mask",
+        path: "FeedbackBuilder.mask",
+        type: "MaskBuilder",
+        description: "Feedback region namespace.",
+        readonly: true,
+      },
     ],
     methods: [
       {
@@ -56,6 +63,27 @@ export const BUILDER_ENTRIES: RegistryEntry[] = [
         example: ".opacity(0.9)",
         notes: "Default is 0.8.",
       },
+      {
+        name: "offset",
+        path: "FeedbackBuilder.offset",
+        description: "Translate the previous frame by a fixed UV offset each frame (echo trails).",
+        params: [
+          {
+            name: "dx",
+            type: "float | Signal",
+            description: "Horizontal offset per frame in UV units (positive = right).",
+          },
+          {
+            name: "dy",
+            type: "float | Signal",
+            description: "Vertical offset per frame in UV units (positive = down).",
+          },
+        ],
+        returns: "FeedbackBuilder",
+        chainsTo: "FeedbackBuilder",
+        example: ".offset(0.002, 0.0)",
+        notes: "Applied after the warp chain. Default is (0, 0).",
+      },
       {
         name: "build",
         path: "FeedbackBuilder.build",
@@ -254,8 +282,27 @@ export const BUILDER_ENTRIES: RegistryEntry[] = [
         ],
         returns: "FeedbackBuilder",
         chainsTo: "FeedbackBuilder",
+        overloadId: "2-arg",
         example: ".warp.noise(0.5, 2.0)",
       },
+      {
+        name: "noise",
+        path: "WarpBuilder.noise",
+        description: "Add a time-animated noise displacement warp.",
+        params: [
+          {
+            name: "options",
+            type: "Map",
+            description:
+              "#{ strength (float | Signal, default 0.5), scale (float | Signal, default 4.0), speed (float | Signal, default 0.0), seed (int, default 0) }.",
+          },
+        ],
+        returns: "FeedbackBuilder",
+        chainsTo: "FeedbackBuilder",
+        overloadId: "options",
+        example: ".warp.noise(#{ strength: 0.4, scale: 6.0, speed: 0.3 })",
+        notes: "The field is seeded and driven by frame time, so renders are reproducible.",
+      },
       {
         name: "shear",
         path: "WarpBuilder.shear",
@@ -362,6 +409,31 @@ export const BUILDER_ENTRIES: RegistryEntry[] = [
         chainsTo: "FeedbackBuilder",
         example: ".color.channel_offset(0.5, 0.0)",
       },
+      {
+        name: "invert",
+        path: "ColorBuilder.invert",
+        description: "Add a color inversion effect (1 - rgb each frame).",
+        params: [],
+        returns: "FeedbackBuilder",
+        chainsTo: "FeedbackBuilder",
+        example: ".color.invert()",
+      },
+      {
+        name: "hueRotate",
+        path: "ColorBuilder.hueRotate",
+        description: "Add a hue rotation effect.",
+        params: [
+          {
+            name: "angle",
+            type: "float | Signal",
+            description: "Rotation per frame in radians (red -> green -> blue).",
+          },
+        ],
+        returns: "FeedbackBuilder",
+        chainsTo: "FeedbackBuilder",
+        example: ".color.hueRotate(0.05)",
+        notes: "2π is a full turn (no change); π gives the complementary hue.",
+      },
     ],
   },
 
@@ -441,4 +513,78 @@ export const BUILDER_ENTRIES: RegistryEntry[] = [
       },
     ],
   },
+
+  // ============================================================================
+  // MaskBuilder - Feedback region
+  // ============================================================================
+  {
+    kind: "builder",
+    name: "MaskBuilder",
+    path: "MaskBuilder",
+    description:
+      "Feedback region (returned by builder.mask). Outside the region the current frame shows without trails.",
+    parent: "FeedbackBuilder",
+    properties: [],
+    methods: [
+      {
+        name: "circle",
+        path: "MaskBuilder.circle",
+        description: "Apply feedback only inside a circle.",
+        params: [
+          {
+            name: "center",
+            type: "Vec2",
+            description: "Circle center in UV coordinates; x and y may be Signals.",
+          },
+          {
+            name: "radius",
+            type: "float | Signal",
+            description: "Radius in units of frame height.",
+          },
+          {
+            name: "softness",
+            type: "float | Signal",
+            description: "Width of the soft edge inside the boundary (0 = hard).",
+          },
+        ],
+        returns: "FeedbackBuilder",
+        chainsTo: "FeedbackBuilder",
+        example: ".mask.circle(#{ x: 0.5, y: 0.5 }, 0.3, 0.05)",
+      },
+      {
+        name: "rect",
+        path: "MaskBuilder.rect",
+        description: "Apply feedback only inside a rectangle.",
+        params: [
+          {
+            name: "center",
+            type: "Vec2",
+            description: "Rectangle center in UV coordinates; x and y may be Signals.",
+          },
+          {
+            name: "size",
+            type: "Vec2",
+            description: "Width and height in UV units; x and y may be Signals.",
+          },
+          {
+            name: "softness",
+            type: "float | Signal",
+            description: "Width of the soft edge inside the boundary (0 = hard).",
+          },
+        ],
+        returns: "FeedbackBuilder",
+        chainsTo: "FeedbackBuilder",
+        example: ".mask.rect(#{ x: 0.5, y: 0.5 }, #{ x: 0.5, y: 1.0 }, 0.02)",
+      },
+      {
+        name: "full",
+        path: "MaskBuilder.full",
+        description: "Apply feedback to the whole frame (default).",
+        params: [],
+        returns: "FeedbackBuilder",
+        chainsTo: "FeedbackBuilder",
+        example: ".mask.full()",
+      },
+    ],
+  },
 ];
@@ -95,6 +95,27 @@ export const CONFIG_MAP_ENTRIES: RegistryEntry[] = [
         description: "Color tint applied to the image.",
         default: { r: 1.0, g: 1.0, b: 1.0, a: 1.0 },
       },
+      {
+        name: "exposure",
+        type: "float | Signal",
+        description: "Exposure in stops, applied before the other adjustments (+1 doubles).",
+        default: 0.0,
+        range: { min: -5.0, max: 5.0 },
+      },
+      {
+        name: "temperature",
+        type: "float | Signal",
+        description: "White balance temperature: negative cools, positive warms.",
+        default: 0.0,
+        range: { min: -1.0, max: 1.0 },
+      },
+      {
+        name: "tint_shift",
+        type: "float | Signal",
+        description: "White balance tint: negative towards green, positive towards magenta.",
+        default: 0.0,
+        range: { min: -1.0, max: 1.0 },
+      },
     ],
   },
 
@@ -119,7 +140,8 @@ export const CONFIG_MAP_ENTRIES: RegistryEntry[] = [
       {
         name: "smoothness",
         type: "float | Signal",
-        description: "Edge smoothness (how gradual the falloff is).",
+        description:
+          "Edge smoothness (how gradual the falloff is). Without inner_radius, the soft edge starts smoothness / 2 inside outer_radius.",
         default: 0.5,
         range: { min: 0.0, max: 1.0 },
       },
@@ -129,6 +151,33 @@ export const CONFIG_MAP_ENTRIES: RegistryEntry[] = [
         description: "Vignette color.",
         default: { r: 0.0, g: 0.0, b: 0.0, a: 1.0 },
       },
+      {
+        name: "roundness",
+        type: "float | Signal",
+        description: "Shape (1 = ellipse, 0 = rounded rectangle).",
+        default: 1.0,
+        range: { min: 0.0, max: 1.0 },
+      },
+      {
+        name: "aspect",
+        type: "float | Signal",
+        description: "Width/height of the shape; above 1 is wider.",
+        default: 1.0,
+        range: { min: 0.25, max: 4.0 },
+      },
+      {
+        name: "inner_radius",
+        type: "float | Signal",
+        description: "Distance from center where darkening starts (default: outer_radius - smoothness / 2).",
+        range: { min: 0.0, max: 1.5 },
+      },
+      {
+        name: "outer_radius",
+        type: "float | Signal",
+        description: "Distance from center where darkening is full.",
+        default: 0.7,
+        range: { min: 0.0, max: 1.5 },
+      },
     ],
   },
 
@@ -159,6 +208,40 @@ export const CONFIG_MAP_ENTRIES: RegistryEntry[] = [
     ],
   },
 
+  // ============================================================================
+  // fx.radialBlur - Radial blur effect options
+  // ============================================================================
+  {
+    kind: "config-map",
+    name: "fx.radialBlur",
+    path: "fx.radialBlur",
+    description: "Create a radial (zoom) blur centered on a point.",
+    properties: [],
+    methods: [],
+    configMapKeys: [
+      {
+        name: "strength",
+        type: "float | Signal",
+        description: "Blur strength.",
+        default: 0.0,
+        range: { min: 0.0, max: 1.0 },
+      },
+      {
+        name: "center",
+        type: "Vec2",
+        description: "Blur center in normalized coordinates; x and y may be Signals.",
+        default: { x: 0.5, y: 0.5 },
+      },
+      {
+        name: "samples",
+        type: "int | Signal",
+        description: "Number of blur samples (clamped to the range).",
+        default: 8,
+        range: { min: 2, max: 32 },
+      },
+    ],
+  },
+
   // ============================================================================
   // line.strip - Line strip options
   // ============================================================================
@@ -649,6 +732,19 @@ export const CONFIG_MAP_ENTRIES: RegistryEntry[] = [
         description: "Position spread (x, y, z).",
         default: { x: 0.0, y: 0.0, z: 0.0 },
       },
+      {
+        name: "velocity",
+        type: "Vec3",
+        description: "Particle velocity in units per second. Orients stretchBillboards; does not move particles.",
+        default: { x: 0.0, y: 0.0, z: 0.0 },
+      },
+      {
+        name: "soft_fade",
+        type: "float",
+        description:
+          "Soft-particle fade distance: billboards fade out within this distance of geometry behind them. Requires a scene depth buffer; until then it has no visible effect.",
+        default: 0.0,
+      },
       {
         name: "scale_variation",
         type: "float",
@@ -757,6 +853,19 @@ export const CONFIG_MAP_ENTRIES: RegistryEntry[] = [
         description: "Position spread (x, y, z).",
         default: { x: 0.0, y: 0.0, z: 0.0 },
       },
+      {
+        name: "velocity",
+        type: "Vec3",
+        description: "Particle velocity in units per second. Orients stretchBillboards; does not move particles.",
+        default: { x: 0.0, y: 0.0, z: 0.0 },
+      },
+      {
+        name: "soft_fade",
+        type: "float",
+        description:
+          "Soft-particle fade distance: billboards fade out within this distance of geometry behind them. Requires a scene depth buffer; until then it has no visible effect.",
+        default: 0.0,
+      },
       {
         name: "seed",
         type: "int",
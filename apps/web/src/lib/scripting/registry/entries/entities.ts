@@ -40,6 +40,13 @@ export const ENTITY_ENTRIES: RegistryEntry[] = [
         type: "bool",
         description: "Visibility flag.",
       },
+      {
+        name: "layer",
+        path: "MeshEntity.layer",
+        type: "int",
+        description:
+          "Draw order layer (default 0). Lower layers draw first; within a layer, further meshes draw before nearer ones.",
+      },
       {
         name: "material",
         path: "MeshEntity.material",
@@ -86,6 +93,103 @@ export const ENTITY_ENTRIES: RegistryEntry[] = [
       },
     ],
     methods: [
+      {
+        name: "lookAt",
+        path: "MeshEntity.lookAt",
+        description:
+          "Keep the entity's forward (+Z) axis pointed at a target, overriding its rotation each frame.",
+        params: [
+          {
+            name: "target",
+            type: "Entity | Camera | Vec3 | ()",
+            description: "Entity, camera, or world-space point to face; () removes the constraint.",
+          },
+        ],
+        returns: "void",
+        example: "cube.lookAt(camera);",
+        notes:
+          "Evaluated after positions are resolved. When the entity and target coincide, the rotation is left unchanged.",
+      },
+      {
+        name: "followPath",
+        path: "MeshEntity.followPath",
+        description: "Move the entity along a waypoint path, overriding its position each frame.",
+        params: [
+          {
+            name: "points",
+            type: "Array<Vec3>",
+            description: "Waypoints, in the same space as position. Components may be Signals.",
+          },
+          {
+            name: "progress",
+            type: "Signal | f32",
+            description: "Distance along the path, 0 (start) to 1 (end).",
+          },
+          {
+            name: "options",
+            type: "Map",
+            description: "spline: true smooths the path into a Catmull-Rom spline.",
+            optional: true,
+          },
+        ],
+        returns: "void",
+        example:
+          "cube.followPath([#{ x: 0.0, y: 0.0, z: 0.0 }, #{ x: 2.0, y: 1.0, z: 0.0 }], timing.time.scale(0.1));",
+        notes:
+          "Progress is arc-length based, so the entity moves at an even speed however the waypoints are spaced. followPath(()) stops following.",
+      },
+      {
+        name: "morph",
+        path: "MeshEntity.morph",
+        description: "Blend one of a loaded mesh's morph targets (shape keys) by name.",
+        params: [
+          {
+            name: "name",
+            type: "string",
+            description: "Morph target name, from the glTF targetNames, or its index as a string.",
+          },
+          {
+            name: "amount",
+            type: "Signal | f32",
+            description: "Blend weight: 0 is the base shape, 1 the full target.",
+          },
+        ],
+        returns: "void",
+        example: 'face.morph("mouthOpen", inputs.mix.energy.normalise.robust());',
+        notes:
+          "Only for mesh.load() entities. Several targets blend additively, before deformations. Unknown names are ignored.",
+      },
+      {
+        name: "playAnimation",
+        path: "MeshEntity.playAnimation",
+        description: "Play one of a loaded mesh's skeletal animation clips by name.",
+        params: [
+          {
+            name: "name",
+            type: "string",
+            description: "Animation clip name from the glTF file.",
+          },
+          {
+            name: "options",
+            type: "Map",
+            description:
+              "speed (Signal | f32, default 1.0), looping (bool, default true), and time (Signal, seconds) to scrub the clip instead of advancing it.",
+            optional: true,
+          },
+        ],
+        returns: "void",
+        example: 'dancer.playAnimation("dance", #{ time: timing.beatPhase });',
+        notes:
+          'Only for mesh.load() entities with a skin. One animation plays at a time; playing another clip restarts from 0. loop is a Rhai keyword, so write looping (or a quoted "loop" key).',
+      },
+      {
+        name: "stopAnimation",
+        path: "MeshEntity.stopAnimation",
+        description: "Stop skeletal animation playback, returning the mesh to its rest pose.",
+        params: [],
+        returns: "void",
+        example: "dancer.stopAnimation();",
+      },
       {
         name: "instance",
         path: "MeshEntity.instance",
@@ -214,6 +318,13 @@ scene.add(copy);`,
         type: "bool",
         description: "Visibility flag.",
       },
+      {
+        name: "layer",
+        path: "LineStripEntity.layer",
+        type: "int",
+        description:
+          "Draw order layer (default 0). Lower layers draw first; within a layer, lines keep their creation order.",
+      },
       {
         name: "color",
         path: "LineStripEntity.color",
@@ -278,6 +389,13 @@ scene.add(copy);`,
         type: "bool",
         description: "Visibility flag.",
       },
+      {
+        name: "layer",
+        path: "LineTraceEntity.layer",
+        type: "int",
+        description:
+          "Draw order layer (default 0). Lower layers draw first; within a layer, lines keep their creation order.",
+      },
       {
         name: "color",
         path: "LineTraceEntity.color",
@@ -438,6 +556,13 @@ scene.add(copy);`,
         type: "bool",
         description: "Visibility flag.",
       },
+      {
+        name: "layer",
+        path: "PointCloudEntity.layer",
+        type: "int",
+        description:
+          "Draw order layer (default 0). Lower layers draw first; within a layer, further clouds draw before nearer ones.",
+      },
       {
         name: "color",
         path: "PointCloudEntity.color",
@@ -532,6 +657,116 @@ scene.add(copy);`,
         returns: "void",
         example: "particles.reset();",
       },
+      {
+        name: "rotation",
+        path: "ParticleSystemHandle.rotation",
+        description: "Give each particle an initial spin angle and angular velocity.",
+        params: [
+          {
+            name: "options",
+            type: "Map",
+            description:
+              "{ initial: radians, velocity: radians per second, randomize: 0-1 per-particle jitter }.",
+          },
+        ],
+        returns: "void",
+        example: "sparks.rotation(#{ initial: 0.0, velocity: 3.0, randomize: 0.5 });",
+        notes:
+          "Billboards spin in screen space; mesh particles spin about their local Z axis. Randomization is seeded by the system seed. Call before scene.add().",
+      },
+      {
+        name: "sizeOverLife",
+        path: "ParticleSystemHandle.sizeOverLife",
+        description: "Scale particles over their lifetime with a keyframe curve.",
+        params: [
+          {
+            name: "stops",
+            type: "Array",
+            description: "Array of #{ t, size } stops; t is normalized age (0-1), size multiplies the particle scale.",
+          },
+        ],
+        returns: "void",
+        example: "puffs.sizeOverLife([#{ t: 0.0, size: 0.2 }, #{ t: 0.3, size: 1.0 }, #{ t: 1.0, size: 0.0 }]);",
+        notes:
+          "Stops are sorted by t and linearly interpolated. A single stop gives a constant size; ages outside the stops hold the nearest value.",
+      },
+      {
+        name: "blend",
+        path: "ParticleSystemHandle.blend",
+        description: "Choose how particles composite onto the scene.",
+        params: [
+          {
+            name: "mode",
+            type: "string",
+            description: '"alpha" (default) for smoke and dust, "add" for sparks and fire.',
+          },
+        ],
+        returns: "void",
+        example: 'sparks.blend("add");',
+        notes:
+          "Alpha-blended billboards from every system and sprite are sorted back-to-front together each frame; additive particles are order-independent and skip sorting.",
+      },
+      {
+        name: "maxParticles",
+        path: "ParticleSystemHandle.maxParticles",
+        description: "Cap the number of live particles in this system.",
+        params: [{ name: "n", type: "int", description: "Maximum live particles (1-262144)." }],
+        returns: "void",
+        example: "sparks.maxParticles(200);",
+        notes: "When the cap is reached the overflow policy decides which particles are dropped.",
+      },
+      {
+        name: "overflow",
+        path: "ParticleSystemHandle.overflow",
+        description: "Choose what happens when spawning would exceed maxParticles.",
+        params: [
+          {
+            name: "policy",
+            type: "string",
+            description: '"oldest" evicts the oldest particles (default); "new" discards new spawns.',
+          },
+        ],
+        returns: "void",
+        example: 'sparks.overflow("new");',
+      },
+      {
+        name: "stretchBillboards",
+        path: "ParticleSystemHandle.stretchBillboards",
+        description: "Stretch billboards along their on-screen velocity (sparks, rain).",
+        params: [
+          {
+            name: "factor",
+            type: "float",
+            description: "Extra length per unit of speed. 0 keeps billboards camera-facing.",
+          },
+        ],
+        returns: "void",
+        example: "sparks.stretchBillboards(0.5);",
+        notes:
+          "Velocity comes from the velocity option. Particles with near-zero on-screen velocity fall back to camera-facing quads.",
+      },
+      {
+        name: "sprite",
+        path: "ParticleSystemHandle.sprite",
+        description: "Texture billboard particles with an image or an animated sprite sheet.",
+        params: [
+          {
+            name: "path",
+            type: "string",
+            description: "Image path (relative to the script on native renders; registered by the host in the browser).",
+          },
+          {
+            name: "sheet",
+            type: "map",
+            description: "Optional sheet layout #{ frames, rows, cols, fps }.",
+            optional: true,
+          },
+        ],
+        returns: "void",
+        example: 'smoke.sprite("smoke_sheet.png", #{ frames: 16, rows: 4, cols: 4 });',
+        notes:
+          "Frames read left-to-right, top-to-bottom and play once over each particle's lifetime, or loop at fps when given. The sprite is tinted by the particle color; sprites that fail to load fall back to soft circles.",
+      },
     ],
   },
 
@@ -547,6 +782,55 @@ scene.add(copy);`,
     methods: [],
   },
 
+  // ============================================================================
+  // UiRectEntity - Pixel-space UI rectangle
+  // ============================================================================
+  {
+    kind: "type",
+    name: "UiRectEntity",
+    path: "UiRectEntity",
+    description: "A pixel-space rectangle created by ui.rect(options).",
+    properties: [
+      {
+        name: "position",
+        path: "UiRectEntity.position",
+        type: "Vec3",
+        description: "Top-left corner in pixels (z is ignored).",
+      },
+      {
+        name: "scale",
+        path: "UiRectEntity.scale",
+        type: "float",
+        description: "Size multiplier, growing from the top-left corner.",
+      },
+      {
+        name: "visible",
+        path: "UiRectEntity.visible",
+        type: "bool",
+        description: "Visibility flag.",
+      },
+      {
+        name: "width",
+        path: "UiRectEntity.width",
+        type: "float | Signal",
+        description: "Width in pixels.",
+      },
+      {
+        name: "height",
+        path: "UiRectEntity.height",
+        type: "float | Signal",
+        description: "Height in pixels.",
+      },
+      {
+        name: "color",
+        path: "UiRectEntity.color",
+        type: "Color",
+        description: "Fill color; alpha blends over the frame.",
+      },
+    ],
+    methods: [],
+  },
+
   // ============================================================================
   // MaterialParams - Material parameters map
   // ============================================================================
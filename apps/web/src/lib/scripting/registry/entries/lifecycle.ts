@@ -93,6 +93,8 @@ export const LIFECYCLE_ENTRIES: RegistryEntry[] = [
         ],
         returns: "string",
         example: "log.info(help(mesh));",
+        notes:
+          'help("signals") lists every Signal operator, including smooth/normalise/gate builders.',
       },
     ],
   },
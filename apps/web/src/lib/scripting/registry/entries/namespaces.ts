@@ -2,7 +2,7 @@
  * Namespace entries for the API registry.
  *
  * These are the top-level global objects available in Rhai scripts:
- * mesh, line, scene, log, dbg, gen, inputs, feedback, fx, post, camera
+ * mesh, line, scene, log, dbg, gen, inputs, feedback, fx, post, camera, math, vec, ease, preset
  */
 
 import type { RegistryEntry } from "../types";
@@ -15,7 +15,7 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
     kind: "namespace",
     name: "mesh",
     path: "mesh",
-    description: "Mesh factory namespace. Create mesh entities (cube, plane).",
+    description: "Mesh factory namespace. Create mesh entities (cube, plane, sphere).",
     properties: [],
     methods: [
       {
@@ -31,10 +31,36 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         name: "plane",
         path: "mesh.plane",
         description: "Create a plane mesh entity.",
-        params: [],
+        params: [
+          {
+            name: "options",
+            type: "PlaneOptions",
+            description: "Options map (optional keys: subdivisions).",
+            optional: true,
+          },
+        ],
+        returns: "MeshEntity",
+        chainsTo: "MeshEntity",
+        example: "let ground = mesh.plane(#{ subdivisions: 32 });",
+        notes: "Defaults: subdivisions=1 (grid cells per side, max 255).",
+      },
+      {
+        name: "sphere",
+        path: "mesh.sphere",
+        description: "Create a UV sphere mesh entity.",
+        params: [
+          {
+            name: "options",
+            type: "SphereOptions",
+            description: "Options map (optional keys: segments).",
+            optional: true,
+          },
+        ],
         returns: "MeshEntity",
         chainsTo: "MeshEntity",
-        example: "let ground = mesh.plane();",
+        example: "let ball = mesh.sphere(#{ segments: 12 });",
+        notes:
+          "Defaults: segments=32 (longitude segments, 4-360; latitude rings are half as many).",
       },
     ],
   },
@@ -220,7 +246,36 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
     name: "scene",
     path: "scene",
     description: "Scene management namespace. Add/remove entities for rendering.",
-    properties: [],
+    properties: [
+      {
+        name: "blendMode",
+        path: "scene.blendMode",
+        type: "string",
+        description:
+          'Scene-wide blending: "normal" (default) or "add" to blend all meshes, lines and point clouds additively.',
+      },
+      {
+        name: "exposure",
+        path: "scene.exposure",
+        type: "float | Signal",
+        description:
+          "Exposure multiplier applied before tonemapping (default 1.0). Raise to brighten, lower to recover highlights.",
+      },
+      {
+        name: "tonemap",
+        path: "scene.tonemap",
+        type: "string",
+        description:
+          'Curve mapping the HDR scene to the output: "none" (default) clips at 1.0, "aces" rolls highlights off with a filmic curve.',
+      },
+      {
+        name: "background",
+        path: "scene.background",
+        type: "Color | Map | ()",
+        description:
+          'Scene background: a colour, #{ top, bottom } colours for a vertical gradient, or #{ skybox: "image" } for an equirectangular image that turns with the camera. () (default) clears to dark grey.',
+      },
+    ],
     methods: [
       {
         name: "add",
@@ -250,6 +305,65 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         returns: "void",
         example: "scene.remove(cube);",
       },
+      {
+        name: "grid",
+        path: "scene.grid",
+        description:
+          "Create a cols x rows grid of entities from a factory, laid out on the XZ plane and added to the scene.",
+        params: [
+          {
+            name: "factory",
+            type: "Fn() -> Entity",
+            description: "Called once per cell to create its entity.",
+          },
+          {
+            name: "options",
+            type: "Map",
+            description:
+              "cols (default 1), rows (default 1) and spacing between cell centres (default 1.0).",
+          },
+        ],
+        returns: "Array<Entity>",
+        example: "let cells = scene.grid(|| mesh.cube(), #{ cols: 4, rows: 4, spacing: 1.5 });",
+        notes:
+          "The grid is centred on the origin. Cells are returned row by row: cell (col, row) is cells[row * cols + col].",
+      },
+      {
+        name: "spectrumBars",
+        path: "scene.spectrumBars",
+        description:
+          "Create a group of bars whose heights track the track audio's spectrum, updated automatically each frame.",
+        params: [
+          {
+            name: "options",
+            type: "Map",
+            description:
+              "count (default 32), spacing between bars (default 0.25), full height (default 3.0) and optional bar color.",
+          },
+        ],
+        returns: "Entity",
+        example: "let bars = scene.spectrumBars(#{ count: 48, height: 4.0 });",
+        notes:
+          "Bars run along X, lowest frequency on the left, centred on the group's origin and growing upwards. The group and bars are added to the scene; move, rotate or scale the returned group to place them. Needs raw track audio (the CLI's `--input`); interpretation packages only carry analysis envelopes, so without it the spectrum stays flat and a warning is logged.",
+      },
+      {
+        name: "fog",
+        path: "scene.fog",
+        description:
+          "Fade meshes towards a fog colour with distance from the camera. Pass () to turn fog off.",
+        params: [
+          {
+            name: "options",
+            type: "Map | ()",
+            description:
+              "Fog color, the camera distance where fog begins (start, default 0) and where meshes are fully fogged (end, default start + 1). All may be Signals.",
+          },
+        ],
+        returns: "()",
+        example: "scene.fog(#{ color: #{ r: 0.1, g: 0.1, b: 0.15 }, start: 5.0, end: 30.0 });",
+        notes:
+          "Applies to primitives and loaded models alike. Match the fog colour to scene.background so distant meshes dissolve into it.",
+      },
     ],
   },
 
@@ -311,6 +425,48 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
     ],
   },
 
+  // ============================================================================
+  // color - Colormap namespace
+  // ============================================================================
+  {
+    kind: "namespace",
+    name: "color",
+    path: "color",
+    description: "Named colormaps for data visualization.",
+    properties: [],
+    methods: [
+      {
+        name: "map",
+        path: "color.map",
+        description: "Look up the color for a value in a named colormap.",
+        params: [
+          {
+            name: "name",
+            type: "string",
+            description:
+              'Colormap name: "viridis", "magma", "inferno", "turbo" or "grayscale".',
+          },
+          {
+            name: "value",
+            type: "float",
+            description: "Position in the colormap, clamped to 0-1.",
+          },
+        ],
+        returns: "Map",
+        example: 'let c = color.map("viridis", 0.25);',
+        notes: "Returns #{r, g, b, a} with components in 0-1 and a = 1.",
+      },
+      {
+        name: "maps",
+        path: "color.maps",
+        description: "List the available colormap names.",
+        params: [],
+        returns: "Array",
+        example: "for name in color.maps() { log.info(name); }",
+      },
+    ],
+  },
+
   // ============================================================================
   // dbg - Debug namespace
   // ============================================================================
@@ -385,7 +541,176 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         ],
         returns: "Map",
         example: 'log.info(dbg.describeEffect("bloom"));',
-        notes: "Returns {name, description, params: [{name, type, description}]}.",
+        notes:
+          "Returns {name, description, params: [{name, type, description, default, min?, max?}]}.",
+      },
+      {
+        name: "describeEntity",
+        path: "dbg.describeEntity",
+        description:
+          "Describe an entity's rendered scene-graph state, with Signal properties evaluated.",
+        params: [
+          {
+            name: "entity",
+            type: "Entity",
+            description: "Entity to describe.",
+          },
+        ],
+        returns: "Map",
+        example: "log.info(dbg.describeEntity(cube));",
+        notes:
+          "Returns {id, type, world_position: {x, y, z}, visible, in_scene, parent, mesh_type?, render_mode?} as of the last synced frame (unit before the first frame). visible includes ancestors.",
+      },
+      {
+        name: "plot",
+        path: "dbg.plot",
+        description:
+          "Plot a value as a small sparkline in the top-right corner, sampled every frame.",
+        params: [
+          {
+            name: "name",
+            type: "string",
+            description:
+              "Plot name. Calling again with the same name replaces the plotted value but keeps its history.",
+          },
+          { name: "signal", type: "Signal | float", description: "Value to plot." },
+        ],
+        returns: "void",
+        example: 'dbg.plot("energy", inputs.mix.energy.smooth.exponential(0.2, 0.2));',
+        notes:
+          "Shows roughly the last 3 seconds, auto-ranged, labelled with its name. Up to 6 plots are shown; further names are ignored with a warning. Plots persist until the script is reloaded.",
+      },
+      {
+        name: "plotParam",
+        path: "dbg.plotParam",
+        description:
+          'Plot an evaluated post-effect or feedback parameter as a sparkline named "effect.param", sampled every frame after Signals are resolved.',
+        params: [
+          {
+            name: "effect",
+            type: "string",
+            description: 'Effect ID in the post chain (e.g. "bloom"), or "feedback".',
+          },
+          {
+            name: "param",
+            type: "string",
+            description:
+              "Float parameter name. For feedback: opacity, offsetX, offsetY, strength, scale, rotation (first warp step) or decay.",
+          },
+        ],
+        returns: "void",
+        example: 'dbg.plotParam("bloom", "intensity");',
+        notes:
+          "Uses the first effect with that ID. Nothing is plotted while the effect is missing or feedback is disabled.",
+      },
+      {
+        name: "showWaveform",
+        path: "dbg.showWaveform",
+        description:
+          "Draw the full-track waveform across the bottom of the screen with a playhead at the current time.",
+        params: [],
+        returns: "void",
+        example: "dbg.showWaveform();",
+        notes:
+          "Stays on until the script is reloaded. Draws nothing when no track audio is available.",
+      },
+      {
+        name: "showSpectrogram",
+        path: "dbg.showSpectrogram",
+        description:
+          "Draw a scrolling spectrogram of the track audio, one column per frame, newest on the right.",
+        params: [
+          {
+            name: "options",
+            type: "Map",
+            description:
+              'rect: #{x, y, w, h} as fractions of the screen from the top-left (default #{x: 0.02, y: 0.02, w: 0.4, h: 0.2}); colormap: a name from color.maps() (default "magma").',
+          },
+        ],
+        returns: "void",
+        example:
+          'dbg.showSpectrogram(#{ rect: #{ x: 0.6, y: 0.05, w: 0.35, h: 0.2 }, colormap: "viridis" });',
+        notes:
+          "Frequencies are log-spaced, lowest at the bottom. Calling again replaces the options. Stays on until the script is reloaded. Needs raw track audio (the CLI's `--input`); interpretation packages only carry analysis envelopes, so without it the spectrum stays flat and a warning is logged.",
+      },
+      {
+        name: "showGrid",
+        path: "dbg.showGrid",
+        description:
+          "Overlay a screen-space reference grid (or checkerboard) to help line up and frame the scene.",
+        params: [
+          {
+            name: "options",
+            type: "Map",
+            description:
+              "spacing: cell size as a fraction of the screen height (default 0.1); color: #{r, g, b, a} (default translucent white); checker: fill alternate cells instead of drawing lines (default false).",
+          },
+        ],
+        returns: "void",
+        example: "dbg.showGrid(#{ spacing: 0.125, color: #{ r: 0.0, g: 1.0, b: 1.0, a: 0.5 } });",
+        notes:
+          "Cells are measured from the screen centre; the centre lines are drawn brighter. Offline renders leave the grid out unless run with `render --show-grid`.",
+      },
+      {
+        name: "hideGrid",
+        path: "dbg.hideGrid",
+        description: "Remove the reference grid overlay.",
+        params: [],
+        returns: "void",
+        example: "dbg.hideGrid();",
+      },
+      {
+        name: "showAxes",
+        path: "dbg.showAxes",
+        description:
+          "Draw an RGB axis gizmo (X red, Y green, Z blue) at the world origin or at an entity, to check camera and rotation.",
+        params: [
+          {
+            name: "target",
+            type: "bool | Entity",
+            description:
+              "true/false toggles the gizmo at the world origin; an entity adds a gizmo that follows its world transform.",
+          },
+        ],
+        returns: "void",
+        example: "dbg.showAxes(cube);",
+        notes:
+          "Axes are one unit long in the target's space. dbg.showAxes(false) removes all gizmos. Stays on until the script is reloaded.",
+      },
+      {
+        name: "captureReplay",
+        path: "dbg.captureReplay",
+        description:
+          "Export the instant-replay buffer (the last few seconds of frames, ending with this one) as a video.",
+        params: [],
+        returns: "void",
+        example: "if frame == 600 { dbg.captureReplay(); }",
+        notes:
+          "Only has an effect when rendering with `render --replay-seconds N`; each call writes the next replay_NN video. Does nothing elsewhere.",
+      },
+      {
+        name: "soloEffect",
+        path: "dbg.soloEffect",
+        description:
+          "Render only this post effect, bypassing the rest of the chain, to tune it in isolation.",
+        params: [
+          {
+            name: "effect",
+            type: "PostEffect | string",
+            description: 'An effect from fx.*, or an effect ID such as "bloom".',
+          },
+        ],
+        returns: "void",
+        example: "dbg.soloEffect(bloom);",
+        notes: "Nothing is applied if the effect isn't enabled in the chain. Feedback still runs.",
+      },
+      {
+        name: "clearSolo",
+        path: "dbg.clearSolo",
+        description: "Stop soloing a post effect and render the whole chain again.",
+        params: [],
+        returns: "void",
+        example: "dbg.clearSolo();",
       },
     ],
   },
@@ -570,6 +895,53 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         chainsTo: "Signal",
         example: "let n = gen.perlin(1.0, 42);",
       },
+      {
+        name: "sequence",
+        path: "gen.sequence",
+        description:
+          "Step sequencer that outputs one array value per step, advancing every step_beats beats.",
+        params: [
+          {
+            name: "values",
+            type: "array<float>",
+            description: "Step values, played in order.",
+          },
+          {
+            name: "options",
+            type: "map",
+            description: "step_beats: beats per step (default 1.0).",
+          },
+        ],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "let pattern = gen.sequence([1.0, 0.0, 0.5, 0.0], #{ step_beats: 0.5 });",
+        notes: "Wraps back to the first value after the last step. An empty array outputs 0.",
+      },
+      {
+        name: "adsr",
+        path: "gen.adsr",
+        description:
+          "ADSR envelope retriggered by each event: attack to the event weight, decay to the sustain level, then release.",
+        params: [
+          {
+            name: "events",
+            type: "EventStream",
+            description: "Trigger events.",
+          },
+          {
+            name: "options",
+            type: "map",
+            description:
+              "attack, decay, release: beats (defaults 0.1, 0.5, 0.3); sustain: level 0-1 (default 0.7).",
+          },
+        ],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example:
+          "let env = gen.adsr(inputs.mix.onset.pick.events(#{}), #{ attack: 0.05, decay: 0.25, sustain: 0.4, release: 1.0 });",
+        notes:
+          "Events have no duration, so each trigger is also a release. A trigger during an active envelope restarts the attack from the current level.",
+      },
       {
         name: "constant",
         path: "gen.constant",
@@ -632,6 +1004,28 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         description: "Phase within current beat (0-1).",
         readonly: true,
       },
+      {
+        name: "barPosition",
+        path: "timing.barPosition",
+        type: "Signal",
+        description:
+          "Continuous bar position, counted from the first downbeat (phase offset) in the segment's time signature (default 4/4).",
+        readonly: true,
+      },
+      {
+        name: "barIndex",
+        path: "timing.barIndex",
+        type: "Signal",
+        description: "Current bar index (integer-valued, negative before the first downbeat).",
+        readonly: true,
+      },
+      {
+        name: "barPhase",
+        path: "timing.barPhase",
+        type: "Signal",
+        description: "Phase within current bar (0-1), resetting on each downbeat.",
+        readonly: true,
+      },
       {
         name: "bpm",
         path: "timing.bpm",
@@ -657,14 +1051,16 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         name: "mix",
         path: "inputs.mix",
         type: "MixSignals",
-        description: "Mixdown audio signals (rms, energy, centroid, flux, onset).",
+        description:
+          "Mixdown audio signals (rms, energy, centroid, flux, onset, loudness). loudness is short-term LUFS (K-weighted, 3 s window), consistent across master levels.",
         readonly: true,
       },
       {
         name: "stems",
         path: "inputs.stems",
         type: "Stems",
-        description: 'Stem-scoped signal accessors: inputs.stems["Drums"].energy.',
+        description:
+          'Stem-scoped signal accessors: inputs.stems["Drums"].energy. Per-stem band signals: inputs.stems["Drums"].bands["Hi"].energy.',
         readonly: true,
       },
       {
@@ -678,7 +1074,7 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         name: "customEvents",
         path: "inputs.customEvents",
         type: "CustomEvents",
-        description: 'User-authored event streams: inputs.customEvents["beats"].',
+        description: 'User-authored event streams: inputs.customEvents["beats"]. The CLI loads them from a JSON file with --events.',
         readonly: true,
       },
       {
@@ -689,6 +1085,14 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
           'Human-authored interpretation curves (keyframe envelopes): inputs.composedSignals["intensity"].',
         readonly: true,
       },
+      {
+        name: "midi",
+        path: "inputs.midi",
+        type: "Midi",
+        description:
+          "Notes from a MIDI file (CLI --midi), per channel 1-16: inputs.midi.ch1.note36 is the velocity while held, inputs.midi.ch1.events / .note36Events the note-ons.",
+        readonly: true,
+      },
     ],
     methods: [],
   },
@@ -910,7 +1314,8 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         returns: "PostEffect",
         chainsTo: "PostEffect",
         example: "let grade = fx.colorGrade(#{ contrast: 1.1, saturation: 1.2 });",
-        notes: "Parameters: brightness, contrast, saturation, gamma, tint.",
+        notes:
+          "Parameters: exposure, temperature, tint_shift, brightness, contrast, saturation, gamma, tint.",
       },
       {
         name: "vignette",
@@ -963,7 +1368,7 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
       {
         name: "radialBlur",
         path: "fx.radialBlur",
-        description: "Create a radial motion blur effect.",
+        description: "Create a radial (zoom) blur effect.",
         params: [
           {
             name: "options",
@@ -973,7 +1378,7 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         ],
         returns: "PostEffect",
         chainsTo: "PostEffect",
-        example: "let blur = fx.radialBlur(#{ strength: 0.3, samples: 16 });",
+        example: "let blur = fx.radialBlur(#{ strength: 0.3, center: #{ x: 0.5, y: 0.4 } });",
         notes: "Parameters: strength (0-1), center {x, y}, samples (2-32).",
       },
       {
@@ -1293,6 +1698,23 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
         returns: "void",
         example: "camera.pan(1.0, 0.5);",
       },
+      {
+        name: "shake",
+        path: "camera.shake",
+        description: "Add noise-driven shake on top of the camera position and orientation.",
+        params: [
+          {
+            name: "options",
+            type: "Map",
+            description:
+              "amount (Signal | f32, world units, default 0.1), frequency (Hz, default 12), rotation (radians per unit of amount, default 0.1), trigger (EventStream), decay (seconds, default 0.25), seed (int, default 0). Pass () to stop shaking.",
+          },
+        ],
+        returns: "void",
+        example: "camera.shake(#{ amount: 0.3, trigger: kicks });",
+        notes:
+          "Noise depends only on time and seed, so offline renders reproduce it. With a trigger, each event kicks the shake to amount times the event weight, decaying exponentially with time constant decay.",
+      },
     ],
   },
 
@@ -1362,4 +1784,847 @@ export const NAMESPACE_ENTRIES: RegistryEntry[] = [
     ],
     methods: [],
   },
+
+  // ============================================================================
+  // math - Transform math namespace
+  // ============================================================================
+  {
+    kind: "namespace",
+    name: "math",
+    path: "math",
+    description:
+      "Transform math namespace. Build 4x4 transforms and compose them with `*` (`a * b` applies `b` first). Arguments are plain numbers (not Signals).",
+    properties: [],
+    methods: [
+      {
+        name: "identity",
+        path: "math.identity",
+        description: "Identity transform.",
+        params: [],
+        returns: "Mat4",
+        example: "let m = math.identity();",
+      },
+      {
+        name: "translate",
+        path: "math.translate",
+        description: "Translation transform.",
+        params: [
+          {
+            name: "x",
+            type: "f32",
+            description: "X component.",
+          },
+          {
+            name: "y",
+            type: "f32",
+            description: "Y component.",
+          },
+          {
+            name: "z",
+            type: "f32",
+            description: "Z component.",
+          },
+        ],
+        returns: "Mat4",
+        example: "let m = math.translate(1.0, 0.0, 0.0);",
+      },
+      {
+        name: "scale",
+        path: "math.scale",
+        description: "Per-axis scale transform.",
+        params: [
+          {
+            name: "x",
+            type: "f32",
+            description: "X component.",
+          },
+          {
+            name: "y",
+            type: "f32",
+            description: "Y component.",
+          },
+          {
+            name: "z",
+            type: "f32",
+            description: "Z component.",
+          },
+        ],
+        returns: "Mat4",
+        example: "let m = math.scale(2.0, 1.0, 1.0);",
+      },
+      {
+        name: "rotateX",
+        path: "math.rotateX",
+        description: "Rotation about the X axis.",
+        params: [
+          {
+            name: "radians",
+            type: "f32",
+            description: "Angle in radians.",
+          },
+        ],
+        returns: "Mat4",
+        example: "let m = math.rotateX(PI() / 4.0);",
+      },
+      {
+        name: "rotateY",
+        path: "math.rotateY",
+        description: "Rotation about the Y axis.",
+        params: [
+          {
+            name: "radians",
+            type: "f32",
+            description: "Angle in radians.",
+          },
+        ],
+        returns: "Mat4",
+        example: "let m = math.rotateY(PI() / 4.0);",
+      },
+      {
+        name: "rotateZ",
+        path: "math.rotateZ",
+        description: "Rotation about the Z axis.",
+        params: [
+          {
+            name: "radians",
+            type: "f32",
+            description: "Angle in radians.",
+          },
+        ],
+        returns: "Mat4",
+        example: "let m = math.rotateZ(PI() / 4.0);",
+      },
+    ],
+  },
+
+  // ============================================================================
+  // vec - Vector math namespace
+  // ============================================================================
+  {
+    kind: "namespace",
+    name: "vec",
+    path: "vec",
+    description:
+      "Vector math on #{ x, y, z } maps (missing components read as 0). Arguments are plain numbers (not Signals).",
+    properties: [],
+    methods: [
+      {
+        name: "dot",
+        path: "vec.dot",
+        description: "Dot product.",
+        params: [
+          {
+            name: "a",
+            type: "Vec3",
+            description: "First vector.",
+          },
+          {
+            name: "b",
+            type: "Vec3",
+            description: "Second vector.",
+          },
+        ],
+        returns: "float",
+        example: "let facing = vec.dot(normal, dir);",
+      },
+      {
+        name: "cross",
+        path: "vec.cross",
+        description: "Cross product (perpendicular to both).",
+        params: [
+          {
+            name: "a",
+            type: "Vec3",
+            description: "First vector.",
+          },
+          {
+            name: "b",
+            type: "Vec3",
+            description: "Second vector.",
+          },
+        ],
+        returns: "Vec3",
+        example: "let up = vec.cross(forward, right);",
+      },
+      {
+        name: "length",
+        path: "vec.length",
+        description: "Vector length.",
+        params: [
+          {
+            name: "a",
+            type: "Vec3",
+            description: "Vector.",
+          },
+        ],
+        returns: "float",
+        example: "let d = vec.length(vec.sub(target, cube.position));",
+      },
+      {
+        name: "normalize",
+        path: "vec.normalize",
+        description: "Unit vector in the same direction.",
+        params: [
+          {
+            name: "a",
+            type: "Vec3",
+            description: "Vector.",
+          },
+        ],
+        returns: "Vec3",
+        example: "let dir = vec.normalize(velocity);",
+        notes: "A zero vector normalizes to zero.",
+      },
+      {
+        name: "add",
+        path: "vec.add",
+        description: "Component-wise sum.",
+        params: [
+          {
+            name: "a",
+            type: "Vec3",
+            description: "First vector.",
+          },
+          {
+            name: "b",
+            type: "Vec3",
+            description: "Second vector.",
+          },
+        ],
+        returns: "Vec3",
+        example: "let p = vec.add(center, offset);",
+      },
+      {
+        name: "sub",
+        path: "vec.sub",
+        description: "Component-wise difference (a - b).",
+        params: [
+          {
+            name: "a",
+            type: "Vec3",
+            description: "First vector.",
+          },
+          {
+            name: "b",
+            type: "Vec3",
+            description: "Second vector.",
+          },
+        ],
+        returns: "Vec3",
+        example: "let offset = vec.sub(target, cube.position);",
+      },
+      {
+        name: "scale",
+        path: "vec.scale",
+        description: "Multiply every component by a factor.",
+        params: [
+          {
+            name: "a",
+            type: "Vec3",
+            description: "Vector.",
+          },
+          {
+            name: "factor",
+            type: "f32",
+            description: "Scale factor.",
+          },
+        ],
+        returns: "Vec3",
+        example: "let half = vec.scale(offset, 0.5);",
+      },
+      {
+        name: "lerp",
+        path: "vec.lerp",
+        description: "Linear interpolation from a (t = 0) to b (t = 1).",
+        params: [
+          {
+            name: "a",
+            type: "Vec3",
+            description: "First vector.",
+          },
+          {
+            name: "b",
+            type: "Vec3",
+            description: "Second vector.",
+          },
+          {
+            name: "t",
+            type: "f32",
+            description: "Interpolation amount.",
+          },
+        ],
+        returns: "Vec3",
+        example: "cube.position = vec.lerp(start, end, 0.25);",
+      },
+    ],
+  },
+
+  // ============================================================================
+  // ease - Easing curves
+  // ============================================================================
+  {
+    kind: "namespace",
+    name: "ease",
+    path: "ease",
+    description:
+      "Easing curves: quad, cubic, quart, quint, sine, expo, circ, back, elastic and bounce, each as in/out/inOut. Every curve maps 0 to 0 and 1 to 1; back and elastic overshoot in between.",
+    properties: [],
+    methods: [
+      {
+        name: "linear",
+        path: "ease.linear",
+        description: "`linear` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.linear(progress);",
+      },
+      {
+        name: "smoothstep",
+        path: "ease.smoothstep",
+        description: "`smoothstep` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.smoothstep(progress);",
+      },
+      {
+        name: "inQuad",
+        path: "ease.inQuad",
+        description: "`inQuad` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inQuad(progress);",
+      },
+      {
+        name: "outQuad",
+        path: "ease.outQuad",
+        description: "`outQuad` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outQuad(progress);",
+      },
+      {
+        name: "inOutQuad",
+        path: "ease.inOutQuad",
+        description: "`inOutQuad` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutQuad(progress);",
+      },
+      {
+        name: "inCubic",
+        path: "ease.inCubic",
+        description: "`inCubic` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inCubic(progress);",
+      },
+      {
+        name: "outCubic",
+        path: "ease.outCubic",
+        description: "`outCubic` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outCubic(progress);",
+      },
+      {
+        name: "inOutCubic",
+        path: "ease.inOutCubic",
+        description: "`inOutCubic` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutCubic(progress);",
+      },
+      {
+        name: "inQuart",
+        path: "ease.inQuart",
+        description: "`inQuart` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inQuart(progress);",
+      },
+      {
+        name: "outQuart",
+        path: "ease.outQuart",
+        description: "`outQuart` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outQuart(progress);",
+      },
+      {
+        name: "inOutQuart",
+        path: "ease.inOutQuart",
+        description: "`inOutQuart` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutQuart(progress);",
+      },
+      {
+        name: "inQuint",
+        path: "ease.inQuint",
+        description: "`inQuint` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inQuint(progress);",
+      },
+      {
+        name: "outQuint",
+        path: "ease.outQuint",
+        description: "`outQuint` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outQuint(progress);",
+      },
+      {
+        name: "inOutQuint",
+        path: "ease.inOutQuint",
+        description: "`inOutQuint` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutQuint(progress);",
+      },
+      {
+        name: "inSine",
+        path: "ease.inSine",
+        description: "`inSine` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inSine(progress);",
+      },
+      {
+        name: "outSine",
+        path: "ease.outSine",
+        description: "`outSine` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outSine(progress);",
+      },
+      {
+        name: "inOutSine",
+        path: "ease.inOutSine",
+        description: "`inOutSine` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutSine(progress);",
+      },
+      {
+        name: "inExpo",
+        path: "ease.inExpo",
+        description: "`inExpo` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inExpo(progress);",
+      },
+      {
+        name: "outExpo",
+        path: "ease.outExpo",
+        description: "`outExpo` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outExpo(progress);",
+      },
+      {
+        name: "inOutExpo",
+        path: "ease.inOutExpo",
+        description: "`inOutExpo` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutExpo(progress);",
+      },
+      {
+        name: "inCirc",
+        path: "ease.inCirc",
+        description: "`inCirc` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inCirc(progress);",
+      },
+      {
+        name: "outCirc",
+        path: "ease.outCirc",
+        description: "`outCirc` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outCirc(progress);",
+      },
+      {
+        name: "inOutCirc",
+        path: "ease.inOutCirc",
+        description: "`inOutCirc` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutCirc(progress);",
+      },
+      {
+        name: "inBack",
+        path: "ease.inBack",
+        description: "`inBack` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inBack(progress);",
+      },
+      {
+        name: "outBack",
+        path: "ease.outBack",
+        description: "`outBack` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outBack(progress);",
+      },
+      {
+        name: "inOutBack",
+        path: "ease.inOutBack",
+        description: "`inOutBack` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutBack(progress);",
+      },
+      {
+        name: "inElastic",
+        path: "ease.inElastic",
+        description: "`inElastic` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inElastic(progress);",
+      },
+      {
+        name: "outElastic",
+        path: "ease.outElastic",
+        description: "`outElastic` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outElastic(progress);",
+      },
+      {
+        name: "inOutElastic",
+        path: "ease.inOutElastic",
+        description: "`inOutElastic` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutElastic(progress);",
+      },
+      {
+        name: "inBounce",
+        path: "ease.inBounce",
+        description: "`inBounce` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inBounce(progress);",
+      },
+      {
+        name: "outBounce",
+        path: "ease.outBounce",
+        description: "`outBounce` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.outBounce(progress);",
+      },
+      {
+        name: "inOutBounce",
+        path: "ease.inOutBounce",
+        description: "`inOutBounce` easing curve.",
+        params: [
+          {
+            name: "t",
+            type: "float | Signal",
+            description: "Progress, clamped to 0-1.",
+          },
+        ],
+        returns: "float | Signal",
+        example: "cube.scale = 1.0 + ease.inOutBounce(progress);",
+      },
+    ],
+  },
+
+  // ============================================================================
+  // preset - Named parameter presets
+  // ============================================================================
+  {
+    kind: "namespace",
+    name: "preset",
+    path: "preset",
+    description:
+      "Named parameter sets (numbers, Signals, nested maps such as colours) that can be crossfaded, e.g. from an ambient to an energetic look.",
+    properties: [],
+    methods: [
+      {
+        name: "define",
+        path: "preset.define",
+        description: "Store a named parameter set. Redefining a name replaces it.",
+        params: [
+          {
+            name: "name",
+            type: "string",
+            description: "Preset name.",
+          },
+          {
+            name: "params",
+            type: "Map",
+            description: "Parameter values: numbers, Signals, nested maps, strings or bools.",
+          },
+        ],
+        returns: "void",
+        example:
+          'preset.define("ambient", #{ bloom: 0.2, speed: 0.5, tint: #{ r: 0.2, g: 0.4, b: 1.0 } });',
+      },
+      {
+        name: "get",
+        path: "preset.get",
+        description: "Get a copy of a defined preset.",
+        params: [
+          {
+            name: "name",
+            type: "string",
+            description: "Preset name.",
+          },
+        ],
+        returns: "Map",
+        example: 'let p = preset.get("ambient");',
+      },
+      {
+        name: "blend",
+        path: "preset.blend",
+        description:
+          "Crossfade two presets: 0 is all `a`, 1 is all `b`. Returns a map with the keys of both.",
+        params: [
+          {
+            name: "a",
+            type: "string",
+            description: "First preset name.",
+          },
+          {
+            name: "b",
+            type: "string",
+            description: "Second preset name.",
+          },
+          {
+            name: "amount",
+            type: "float | Signal",
+            description: "Blend amount, 0-1.",
+          },
+        ],
+        returns: "Map",
+        example:
+          'let p = preset.blend("ambient", "energetic", inputs.mix.energy.normalise.robust());',
+        notes:
+          "Numbers are interpolated; with a Signal value or amount the result is a Signal. Nested maps blend key by key. Other values switch from `a` to `b` at 0.5 (a Signal amount keeps `a`). Keys in one preset keep its value.",
+      },
+    ],
+  },
+
+  // ============================================================================
+  // ui - Pixel-space UI overlay
+  // ============================================================================
+  {
+    kind: "namespace",
+    name: "ui",
+    path: "ui",
+    description:
+      "Pixel-space UI overlay. Positions are in pixels from (0, 0) at the top-left of the frame; UI entities are drawn after post-processing, over everything else.",
+    properties: [],
+    methods: [
+      {
+        name: "rect",
+        path: "ui.rect",
+        description: "Create a filled rectangle. Add it to the scene with `scene.add()`.",
+        params: [
+          {
+            name: "options",
+            type: "Map",
+            description:
+              "x, y (top-left corner in pixels), width, height (pixels, default 100), color (default white).",
+          },
+        ],
+        returns: "UiRectEntity",
+        example: "let panel = ui.rect(#{ x: 20.0, y: 20.0, width: 200.0, height: 40.0 });",
+        notes:
+          "UI entities aren't affected by bloom, feedback or other effects. They draw in the order they were added.",
+      },
+    ],
+  },
 ];
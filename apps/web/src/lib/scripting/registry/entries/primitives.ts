@@ -1,7 +1,7 @@
 /**
  * Primitive type entries for the API registry.
  *
- * Basic value types: Vec2, Vec3, Color
+ * Basic value types: Vec2, Vec3, Color, Mat4
  */
 
 import type { RegistryEntry } from "../types";
@@ -106,4 +106,70 @@ export const PRIMITIVE_ENTRIES: RegistryEntry[] = [
     methods: [],
     example: "cube.color.r = inputs.amplitude;",
   },
+
+  // ============================================================================
+  // Mat4 - 4x4 transform matrix
+  // ============================================================================
+  {
+    kind: "type",
+    name: "Mat4",
+    path: "Mat4",
+    description:
+      "4x4 transform matrix from the math namespace. `a * b` applies `b` first, then `a`.",
+    properties: [],
+    methods: [
+      {
+        name: "mul",
+        path: "Mat4.mul",
+        description:
+          "Compose transforms: applies `other` first, then this one. Same as `this * other`.",
+        params: [
+          {
+            name: "other",
+            type: "Mat4",
+            description: "Transform applied first.",
+          },
+        ],
+        returns: "Mat4",
+        example: "let m = math.rotateY(1.0).mul(math.translate(1.0, 0.0, 0.0));",
+      },
+      {
+        name: "inverse",
+        path: "Mat4.inverse",
+        description: "Inverse transform.",
+        params: [],
+        returns: "Mat4",
+        example: "let back = m.inverse();",
+      },
+      {
+        name: "transformPoint",
+        path: "Mat4.transformPoint",
+        description: "Transform a position (translation applies).",
+        params: [
+          {
+            name: "point",
+            type: "Vec3",
+            description: "Position to transform.",
+          },
+        ],
+        returns: "Vec3",
+        example: "cube.position = m.transformPoint(#{ x: 0.0, y: 0.0, z: 0.0 });",
+      },
+      {
+        name: "transformVector",
+        path: "Mat4.transformVector",
+        description: "Transform a direction (translation ignored).",
+        params: [
+          {
+            name: "vector",
+            type: "Vec3",
+            description: "Direction to transform.",
+          },
+        ],
+        returns: "Vec3",
+        example: "let dir = m.transformVector(#{ x: 0.0, y: 0.0, z: 1.0 });",
+      },
+    ],
+    example: "let m = math.rotateY(PI() / 2.0) * math.translate(1.0, 0.0, 0.0);",
+  },
 ];
@@ -154,6 +154,52 @@ export const SIGNAL_ENTRIES: RegistryEntry[] = [
         example: "inputs.amplitude.normalise.robust().sigmoid(10.0)",
         notes: "Normalize or clamp to 0-1 first for predictable results.",
       },
+      {
+        name: "step",
+        path: "Signal.step",
+        description: "Hard threshold: 0 below the edge, 1 at or above it.",
+        params: [{ name: "edge", type: "float | Signal", description: "Threshold." }],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "inputs.mix.energy.normalise.robust().step(0.6)",
+      },
+      {
+        name: "smoothstep",
+        path: "Signal.smoothstep",
+        description: "Hermite ramp from 0 at edge0 to 1 at edge1.",
+        params: [
+          {
+            name: "edge0",
+            type: "float | Signal",
+            description: "Value where the output starts rising.",
+          },
+          {
+            name: "edge1",
+            type: "float | Signal",
+            description: "Value where the output reaches 1.",
+          },
+        ],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "inputs.mix.energy.normalise.robust().smoothstep(0.4, 0.7)",
+        notes: "Equal edges behave like step(edge0).",
+      },
+      {
+        name: "ease",
+        path: "Signal.ease",
+        description: "Reshape a 0-1 signal with a named easing curve.",
+        params: [
+          {
+            name: "name",
+            type: "string",
+            description: 'Easing name, e.g. "outCubic", "inOutSine", "outBounce".',
+          },
+        ],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: 'inputs.mix.energy.normalise.robust().ease("outCubic")',
+        notes: "Input is clamped to 0-1. Same curves as the `ease` namespace.",
+      },
       // Rate and accumulation
       {
         name: "diff",
@@ -180,6 +226,48 @@ export const SIGNAL_ENTRIES: RegistryEntry[] = [
         chainsTo: "Signal",
         example: "inputs.onsetEnvelope.integrate(0.5)",
       },
+      {
+        name: "windowMax",
+        path: "Signal.windowMax",
+        description: "Largest value over the last N seconds.",
+        params: [{ name: "seconds", type: "float", description: "Window length in seconds." }],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "inputs.mix.energy.windowMax(4.0)",
+        notes: "Live and local: only frames evaluated so far count (unlike normalise statistics).",
+      },
+      {
+        name: "windowMin",
+        path: "Signal.windowMin",
+        description: "Smallest value over the last N seconds.",
+        params: [{ name: "seconds", type: "float", description: "Window length in seconds." }],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "inputs.mix.energy.windowMin(4.0)",
+        notes: "Live and local: only frames evaluated so far count (unlike normalise statistics).",
+      },
+      {
+        name: "windowAvg",
+        path: "Signal.windowAvg",
+        description: "Average value over the last N seconds.",
+        params: [{ name: "seconds", type: "float", description: "Window length in seconds." }],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "inputs.mix.energy.windowAvg(2.0)",
+        notes: "Live and local: only frames evaluated so far count (unlike normalise statistics).",
+      },
+      {
+        name: "normalizeWindow",
+        path: "Signal.normalizeWindow",
+        description:
+          "Normalise to 0-1 against the min/max of the last N seconds (automatic gain control).",
+        params: [{ name: "seconds", type: "float", description: "Window length in seconds." }],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "inputs.mix.energy.normalizeWindow(8.0)",
+        notes:
+          "Computed live, so unlike normalise.global()/robust() it needs no precomputed statistics.",
+      },
       // Time shifting
       {
         name: "delay",
@@ -248,6 +336,16 @@ export const SIGNAL_ENTRIES: RegistryEntry[] = [
         example: 'inputs.onsetEnvelope.probe("onset")',
         notes: "Use with analysis mode / host evaluation; this does not print.",
       },
+      {
+        name: "value",
+        path: "Signal.value",
+        description: "Evaluate the signal at the current frame and return the number.",
+        params: [],
+        returns: "float",
+        example: "if inputs.mix.energy.value() > 0.8 { cube.visible = true; }",
+        notes:
+          "Only available inside update(); repeated calls within a frame return the cached value.",
+      },
       // === Comparison operations (Boolean signals) ===
       {
         name: "lt",
@@ -346,6 +444,20 @@ export const SIGNAL_ENTRIES: RegistryEntry[] = [
         chainsTo: "Signal",
         example: "isSilent.not()",
       },
+      {
+        name: "select",
+        path: "Signal.select",
+        description: "If-then-else: returns a when this > 0.5, else b.",
+        params: [
+          { name: "a", type: "Signal", description: "Value when the condition is true." },
+          { name: "b", type: "Signal", description: "Value when the condition is false." },
+        ],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "energy.gt(0.8).select(gen.sin(2.0, 0.0), gen.constant(0.0))",
+        notes:
+          "Only the chosen branch is evaluated, so stateful nodes in the inactive branch pause.",
+      },
     ],
   },
 
@@ -825,6 +937,18 @@ export const SIGNAL_ENTRIES: RegistryEntry[] = [
         example: "let phase = events.beat_phase_between();",
         notes: "Useful for smooth animations that reset at each event.",
       },
+      // === Payload Method ===
+      {
+        name: "held_weight",
+        path: "EventStream.held_weight",
+        description:
+          "Weight (velocity) of the most recent event, held until the next one. 0 before the first event.",
+        params: [],
+        returns: "Signal",
+        chainsTo: "Signal",
+        example: "let burst = strobe * kicks.held_weight();",
+        notes: "For authored and MIDI event streams the weight is the hit velocity.",
+      },
       {
         name: "filter_time",
         path: "EventStream.filter_time",
@@ -855,6 +979,17 @@ export const SIGNAL_ENTRIES: RegistryEntry[] = [
         chainsTo: "EventStream",
         example: "let first = events.limit(10);",
       },
+      {
+        name: "shift",
+        path: "EventStream.shift",
+        description:
+          "Offset all event times by a number of seconds. Negative values anticipate events (lookahead).",
+        params: [{ name: "seconds", type: "float", description: "Offset in seconds (positive = later)." }],
+        returns: "EventStream",
+        chainsTo: "EventStream",
+        example: "let early = kicks.shift(-0.05);",
+        notes: "Events shifted before 0 are dropped, not clamped.",
+      },
       {
         name: "probe",
         path: "EventStream.probe",
@@ -5,7 +5,26 @@
  */
 
 export type ScriptDiagnosticKind =
-  "parse_error" | "runtime_error" | "host_api_misuse" | "host_error" | "warning";
+  | "parse_error"
+  | "runtime_error"
+  | "host_api_misuse"
+  | "host_error"
+  | "resource_limit"
+  | "warning";
+/** Stable machine-readable codes; messages may change, codes do not. */
+export type ScriptDiagnosticCode =
+  | "E_PARSE"
+  | "E_RUNTIME"
+  | "E_HOST_API"
+  | "E_UNKNOWN_VARIABLE"
+  | "E_UNKNOWN_PROPERTY"
+  | "E_UNKNOWN_FUNCTION"
+  | "E_HOST"
+  | "E_RESOURCE_LIMIT"
+  | "E_TIMEOUT"
+  | "W_SCENE_ADD_OUTSIDE_INIT"
+  | "W_UNKNOWN_BAND";
+export type ScriptSeverity = "info" | "warning" | "error";
 export type ScriptPhase = "compile" | "init" | "update";
 
 export interface ScriptLocation {
@@ -15,6 +34,8 @@ export interface ScriptLocation {
 
 export interface ScriptDiagnostic {
   kind: ScriptDiagnosticKind;
+  code: ScriptDiagnosticCode;
+  severity: ScriptSeverity;
   phase: ScriptPhase;
   message: string;
   location?: ScriptLocation | null;
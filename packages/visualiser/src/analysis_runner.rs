@@ -5,6 +5,7 @@
 
 use std::collections::HashMap;
 
+use crate::beat_tracker::{detect_tempo, TempoEstimate};
 use crate::debug_collector::{
     install_collector, remove_collector, set_collector_time, DebugCollector, DebugSignal,
 };
@@ -63,12 +64,36 @@ pub struct AnalysisResult {
     pub step_count: usize,
     /// Total duration analyzed.
     pub duration: f32,
+    /// Tempo detected from the onset envelope when no musical time was given.
+    pub detected_tempo: Option<TempoEstimate>,
+}
+
+/// Onset envelope signal names used for tempo detection, in preference order.
+const ONSET_SIGNAL_NAMES: &[&str] = &["onsetEnvelope", "onset"];
+
+/// Detect tempo and beat phase from the onset envelope in `signals`, if any.
+///
+/// Used by the analysis runs when no musical time is supplied, so `timing.*`
+/// and beat-based features follow the track instead of the 120 BPM default.
+pub fn detect_signal_tempo(signals: &SignalMap) -> Option<TempoEstimate> {
+    let onset = ONSET_SIGNAL_NAMES
+        .iter()
+        .find_map(|name| signals.get(*name))?;
+    let estimate = detect_tempo(onset.samples(), onset.sample_rate())?;
+    log::info!(
+        "Detected tempo {:.1} BPM (phase {:.3}s, confidence {:.2})",
+        estimate.bpm,
+        estimate.phase_offset,
+        estimate.confidence
+    );
+    Some(estimate)
 }
 
 /// Run script in analysis mode, collecting debug.emit() calls.
 ///
 /// This function:
-/// 1. Creates a fresh script engine (no shared state)
+/// 1. Creates a fresh script engine (no shared state), detecting tempo from
+///    the onset envelope when no musical time is given
 /// 2. Loads the script
 /// 3. Installs a debug collector
 /// 4. Calls init(ctx) at time=0
@@ -105,6 +130,15 @@ pub fn run_analysis_with_bands(
         return Err("Time step must be positive".to_string());
     }
 
+    // Without authored musical time, fall back to a detected beat grid
+    let detected_tempo = if musical_time.is_none() {
+        detect_signal_tempo(signals)
+    } else {
+        None
+    };
+    let detected_musical_time = detected_tempo.map(|t| t.to_musical_time(config.duration));
+    let musical_time = musical_time.or(detected_musical_time.as_ref());
+
     // Create fresh script engine
     let mut engine = ScriptEngine::new();
 
@@ -202,6 +236,7 @@ pub fn run_analysis_with_bands(
         debug_signals,
         step_count,
         duration: config.duration,
+        detected_tempo,
     })
 }
 
@@ -218,6 +253,8 @@ pub struct ExtendedAnalysisResult {
     pub step_count: usize,
     /// Total duration analyzed.
     pub duration: f32,
+    /// Tempo detected from the onset envelope when no musical time was given.
+    pub detected_tempo: Option<TempoEstimate>,
 }
 
 /// Run script in analysis mode with event extraction support.
@@ -270,6 +307,15 @@ pub fn run_analysis_with_events_and_bands(
     clear_pending_extractions();
     clear_extracted_streams();
 
+    // Without authored musical time, fall back to a detected beat grid
+    let detected_tempo = if musical_time.is_none() {
+        detect_signal_tempo(signals)
+    } else {
+        None
+    };
+    let detected_musical_time = detected_tempo.map(|t| t.to_musical_time(config.duration));
+    let musical_time = musical_time.or(detected_musical_time.as_ref());
+
     // Create fresh script engine
     let mut engine = ScriptEngine::new();
 
@@ -406,6 +452,7 @@ pub fn run_analysis_with_events_and_bands(
         event_debug,
         step_count,
         duration: config.duration,
+        detected_tempo,
     })
 }
 
@@ -457,6 +504,35 @@ mod tests {
         assert!((energy.emissions[50].value - 1.0).abs() < 0.1);
     }
 
+    #[test]
+    fn test_analysis_detects_tempo_without_musical_time() {
+        let script = r#"
+            fn init(ctx) {}
+
+            fn update(dt, frame) {
+                dbg.emit("bpm", timing.bpm.value());
+            }
+        "#;
+
+        // Onset envelope of a 128 BPM click track: a decaying spike per beat.
+        let period = 60.0 / 128.0;
+        let onset: Vec<f32> = (0..1000)
+            .map(|i| (-(i as f32 / 100.0).rem_euclid(period) / 0.02).exp())
+            .collect();
+        let mut signals = HashMap::new();
+        signals.insert("onsetEnvelope".to_string(), make_test_signal(onset, 100.0));
+
+        let config = AnalysisConfig::new(10.0, 0.05);
+        let result = run_analysis(script, &signals, config).unwrap();
+
+        let detected = result.detected_tempo.expect("tempo detected");
+        assert!((detected.bpm - 128.0).abs() < 1.0, "bpm {}", detected.bpm);
+
+        // Beat-aware features see the detected tempo, not the 120 default.
+        let bpm = result.debug_signals.get("bpm").unwrap();
+        assert!((bpm.emissions[100].value - detected.bpm).abs() < 0.01);
+    }
+
     #[test]
     fn test_analysis_with_varying_signal() {
         let script = r#"
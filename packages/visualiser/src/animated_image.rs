@@ -0,0 +1,372 @@
+//! Animated GIF and WebP output.
+//!
+//! Short loops are easier to share as an animated image than as a video. The
+//! encoder takes the RGBA frames read back from the GPU one at a time and
+//! writes them straight into the chosen format, so no FFmpeg is needed.
+//!
+//! Limits:
+//! - GIF: at most 65535x65535 pixels and 256 colours per frame (each frame gets
+//!   its own NeuQuant palette). Frame delays are in 1/100 s and browsers slow
+//!   down anything under 2/100 s, so GIF output is capped at 50 fps. Delays are
+//!   quantized with a running total so the average rate matches `fps`.
+//! - WebP: at most 16384x16384 pixels. Frames are lossless (VP8L), which keeps
+//!   quality but grows quickly with resolution and frame count; the encoded
+//!   frames are held in memory until `finish`.
+//!
+//! Frames are written fully opaque; any alpha from the renderer is dropped.
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::{Path, PathBuf};
+
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::codecs::webp::WebPEncoder;
+use image::{Delay, ExtendedColorType, Frame, RgbaImage};
+
+/// Highest frame rate GIF output supports (2/100 s per frame).
+pub const GIF_MAX_FPS: f32 = 50.0;
+
+/// Largest width or height of a GIF.
+pub const GIF_MAX_DIMENSION: u32 = u16::MAX as u32;
+
+/// Largest width or height of an animated WebP canvas.
+pub const WEBP_MAX_DIMENSION: u32 = 1 << 14;
+
+/// Animated image format, chosen by the output file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationFormat {
+    Gif,
+    WebP,
+}
+
+impl AnimationFormat {
+    /// Detect the format from a `.gif` or `.webp` extension (case-insensitive).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "gif" => Some(AnimationFormat::Gif),
+            "webp" => Some(AnimationFormat::WebP),
+            _ => None,
+        }
+    }
+
+    /// Check that the format can hold frames of this size at this rate.
+    pub fn validate(self, width: u32, height: u32, fps: f32) -> Result<(), String> {
+        match self {
+            AnimationFormat::Gif => {
+                if width > GIF_MAX_DIMENSION || height > GIF_MAX_DIMENSION {
+                    return Err(format!(
+                        "GIF output is limited to {0}x{0} pixels",
+                        GIF_MAX_DIMENSION
+                    ));
+                }
+                if fps > GIF_MAX_FPS {
+                    return Err(format!(
+                        "GIF output supports at most {} fps; lower --fps",
+                        GIF_MAX_FPS
+                    ));
+                }
+            }
+            AnimationFormat::WebP => {
+                if width > WEBP_MAX_DIMENSION || height > WEBP_MAX_DIMENSION {
+                    return Err(format!(
+                        "Animated WebP output is limited to {0}x{0} pixels",
+                        WEBP_MAX_DIMENSION
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Integer frame delays whose running total tracks the exact frame times.
+///
+/// At 30 fps in 1/100 s units this yields 3, 4, 3, 3, 4, 3, ... instead of
+/// truncating every delay to 3 and playing 10% fast.
+#[derive(Debug, Clone)]
+struct FrameTiming {
+    units_per_second: f64,
+    fps: f64,
+    frames: u64,
+    elapsed_units: u64,
+}
+
+impl FrameTiming {
+    fn new(units_per_second: u32, fps: f32) -> Self {
+        Self {
+            units_per_second: f64::from(units_per_second),
+            fps: f64::from(fps),
+            frames: 0,
+            elapsed_units: 0,
+        }
+    }
+
+    /// Delay of the next frame, in units. Never zero.
+    fn next_delay(&mut self) -> u32 {
+        self.frames += 1;
+        let target = (self.frames as f64 * self.units_per_second / self.fps).round() as u64;
+        let delay = target.saturating_sub(self.elapsed_units).max(1);
+        self.elapsed_units += delay;
+        delay as u32
+    }
+}
+
+enum Inner {
+    Gif(GifEncoder<BufWriter<File>>),
+    /// Encoded VP8L bitstreams and their durations in ms, muxed on `finish`.
+    WebP(Vec<(Vec<u8>, u32)>),
+}
+
+/// Writes rendered frames to an animated GIF or WebP file.
+pub struct AnimationEncoder {
+    path: PathBuf,
+    width: u32,
+    height: u32,
+    loops: u32,
+    timing: FrameTiming,
+    frame_count: usize,
+    inner: Inner,
+}
+
+impl AnimationEncoder {
+    /// Create an encoder writing to `path`.
+    ///
+    /// `loops` is how many times the animation plays; 0 loops forever.
+    pub fn new(
+        format: AnimationFormat,
+        path: &Path,
+        width: u32,
+        height: u32,
+        fps: f32,
+        loops: u32,
+    ) -> Result<Self, String> {
+        format.validate(width, height, fps)?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create output directory: {}", e))?;
+        }
+
+        let (inner, timing) = match format {
+            AnimationFormat::Gif => {
+                let file = File::create(path)
+                    .map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+                let mut encoder = GifEncoder::new_with_speed(BufWriter::new(file), 10);
+                // GIF counts repeats after the first play; no extension plays once
+                let repeat = match loops {
+                    0 => Some(Repeat::Infinite),
+                    1 => None,
+                    n => Some(Repeat::Finite(u16::try_from(n - 1).unwrap_or(u16::MAX))),
+                };
+                if let Some(repeat) = repeat {
+                    encoder
+                        .set_repeat(repeat)
+                        .map_err(|e| format!("Failed to start GIF: {}", e))?;
+                }
+                (Inner::Gif(encoder), FrameTiming::new(100, fps))
+            }
+            AnimationFormat::WebP => (Inner::WebP(Vec::new()), FrameTiming::new(1000, fps)),
+        };
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            width,
+            height,
+            loops,
+            timing,
+            frame_count: 0,
+            inner,
+        })
+    }
+
+    /// Append a frame of tightly packed RGBA8 rows, top row first.
+    pub fn add_frame(&mut self, rgba: &[u8]) -> Result<(), String> {
+        let expected = self.width as usize * self.height as usize * 4;
+        if rgba.len() != expected {
+            return Err(format!(
+                "Frame is {} bytes, expected {} for {}x{}",
+                rgba.len(),
+                expected,
+                self.width,
+                self.height
+            ));
+        }
+
+        let delay = self.timing.next_delay();
+        match &mut self.inner {
+            Inner::Gif(encoder) => {
+                let mut pixels = rgba.to_vec();
+                for pixel in pixels.chunks_exact_mut(4) {
+                    pixel[3] = 255;
+                }
+                let image = RgbaImage::from_raw(self.width, self.height, pixels)
+                    .expect("frame size checked above");
+                // The encoder converts ms back to 1/100 s, so pass exact multiples of 10
+                let delay = Delay::from_numer_denom_ms(delay * 10, 1);
+                encoder
+                    .encode_frame(Frame::from_parts(image, 0, 0, delay))
+                    .map_err(|e| format!("Failed to encode GIF frame: {}", e))?;
+            }
+            Inner::WebP(frames) => {
+                let rgb: Vec<u8> = rgba
+                    .chunks_exact(4)
+                    .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+                    .collect();
+                let mut still = Vec::new();
+                WebPEncoder::new_lossless(&mut still)
+                    .encode(&rgb, self.width, self.height, ExtendedColorType::Rgb8)
+                    .map_err(|e| format!("Failed to encode WebP frame: {}", e))?;
+                frames.push((vp8l_chunk(&still)?, delay));
+            }
+        }
+        self.frame_count += 1;
+        Ok(())
+    }
+
+    /// Finish the file and return the number of frames written.
+    pub fn finish(self) -> Result<usize, String> {
+        match self.inner {
+            Inner::Gif(encoder) => {
+                // Dropping the encoder writes the GIF trailer
+                drop(encoder);
+            }
+            Inner::WebP(frames) => {
+                let data = mux_animated_webp(self.width, self.height, self.loops, &frames);
+                std::fs::write(&self.path, data)
+                    .map_err(|e| format!("Failed to write {:?}: {}", self.path, e))?;
+            }
+        }
+        Ok(self.frame_count)
+    }
+}
+
+/// Extract the VP8L chunk (header included) from a simple-format WebP file.
+fn vp8l_chunk(still: &[u8]) -> Result<Vec<u8>, String> {
+    match still.get(12..16) {
+        Some(b"VP8L") => Ok(still[12..].to_vec()),
+        _ => Err("Unexpected WebP encoder output".to_string()),
+    }
+}
+
+/// Append a RIFF chunk, padded to an even length.
+fn write_chunk(out: &mut Vec<u8>, fourcc: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(fourcc);
+    out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    out.extend_from_slice(data);
+    if data.len() % 2 == 1 {
+        out.push(0);
+    }
+}
+
+/// Little-endian 24-bit field, as used by the VP8X, ANIM and ANMF chunks.
+fn u24(value: u32) -> [u8; 3] {
+    let bytes = value.to_le_bytes();
+    [bytes[0], bytes[1], bytes[2]]
+}
+
+/// Build an extended-format WebP file with one full-canvas ANMF chunk per frame.
+fn mux_animated_webp(width: u32, height: u32, loops: u32, frames: &[(Vec<u8>, u32)]) -> Vec<u8> {
+    let mut body = Vec::new();
+
+    // VP8X: animation flag, then canvas size minus one
+    let mut vp8x = vec![0x02, 0, 0, 0];
+    vp8x.extend_from_slice(&u24(width - 1));
+    vp8x.extend_from_slice(&u24(height - 1));
+    write_chunk(&mut body, b"VP8X", &vp8x);
+
+    // ANIM: opaque black background, loop count (0 = forever)
+    let mut anim = vec![0, 0, 0, 255];
+    anim.extend_from_slice(&u16::try_from(loops).unwrap_or(u16::MAX).to_le_bytes());
+    write_chunk(&mut body, b"ANIM", &anim);
+
+    for (bitstream, duration_ms) in frames {
+        let mut anmf = Vec::with_capacity(16 + bitstream.len());
+        anmf.extend_from_slice(&u24(0)); // x offset / 2
+        anmf.extend_from_slice(&u24(0)); // y offset / 2
+        anmf.extend_from_slice(&u24(width - 1));
+        anmf.extend_from_slice(&u24(height - 1));
+        anmf.extend_from_slice(&u24((*duration_ms).min(0xFF_FFFF)));
+        anmf.push(0b10); // no blending, no disposal
+        anmf.extend_from_slice(bitstream);
+        write_chunk(&mut body, b"ANMF", &anmf);
+    }
+
+    let mut out = Vec::with_capacity(12 + body.len());
+    out.extend_from_slice(b"RIFF");
+    out.extend_from_slice(&(body.len() as u32 + 4).to_le_bytes());
+    out.extend_from_slice(b"WEBP");
+    out.extend_from_slice(&body);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::AnimationDecoder;
+
+    fn test_frames(count: usize, width: u32, height: u32) -> Vec<Vec<u8>> {
+        (0..count)
+            .map(|i| {
+                let shade = (i * 50) as u8;
+                (0..width * height)
+                    .flat_map(|_| [shade, 255 - shade, 128, 255])
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("octoseq-{}-{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(
+            AnimationFormat::from_path(Path::new("out/loop.GIF")),
+            Some(AnimationFormat::Gif)
+        );
+        assert_eq!(
+            AnimationFormat::from_path(Path::new("loop.webp")),
+            Some(AnimationFormat::WebP)
+        );
+        assert_eq!(AnimationFormat::from_path(Path::new("render.mp4")), None);
+        assert!(AnimationFormat::Gif.validate(64, 64, 60.0).is_err());
+        assert!(AnimationFormat::WebP.validate(64, 64, 60.0).is_ok());
+    }
+
+    #[test]
+    fn test_gif_delays_track_frame_rate() {
+        let mut timing = FrameTiming::new(100, 30.0);
+        let delays: Vec<u32> = (0..6).map(|_| timing.next_delay()).collect();
+        assert_eq!(delays, [3, 4, 3, 3, 4, 3]);
+    }
+
+    #[test]
+    fn test_webp_round_trips_frames() {
+        let path = temp_path("anim.webp");
+        let mut encoder =
+            AnimationEncoder::new(AnimationFormat::WebP, &path, 8, 6, 20.0, 0).unwrap();
+        for frame in test_frames(3, 8, 6) {
+            encoder.add_frame(&frame).unwrap();
+        }
+        assert_eq!(encoder.finish().unwrap(), 3);
+
+        let file = std::io::BufReader::new(File::open(&path).unwrap());
+        let decoder = image::codecs::webp::WebPDecoder::new(file).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[1].delay().numer_denom_ms(), (50, 1));
+        assert_eq!(frames[2].buffer().get_pixel(0, 0).0, [100, 155, 128, 255]);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_wrong_frame_size() {
+        let path = temp_path("bad.gif");
+        let mut encoder =
+            AnimationEncoder::new(AnimationFormat::Gif, &path, 4, 4, 10.0, 0).unwrap();
+        assert!(encoder.add_frame(&[0; 12]).is_err());
+        drop(encoder);
+        let _ = std::fs::remove_file(&path);
+    }
+}
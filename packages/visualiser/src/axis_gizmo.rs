@@ -0,0 +1,49 @@
+//! World-space axis gizmo for orienting 3D scenes (`dbg.showAxes()`).
+//!
+//! Three coloured segments from a frame's origin along its X (red), Y (green)
+//! and Z (blue) axes. The frame is either the world origin or an entity's
+//! world transform, so an entity's gizmo shows where its local axes point
+//! after rotation and parenting.
+
+/// Length of each axis in the frame's local units.
+pub const AXIS_LENGTH: f32 = 1.0;
+
+/// Axis colours, X/Y/Z.
+pub const AXIS_COLORS: [[f32; 4]; 3] = [
+    [1.0, 0.2, 0.2, 1.0],
+    [0.2, 1.0, 0.2, 1.0],
+    [0.3, 0.5, 1.0, 1.0],
+];
+
+/// One world-space gizmo line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AxisSegment {
+    pub start: glam::Vec3,
+    pub end: glam::Vec3,
+    pub color: [f32; 4],
+}
+
+impl AxisSegment {
+    /// Segment endpoints in the polyline vertex layout.
+    pub fn to_gpu_data(&self) -> [f32; 6] {
+        [
+            self.start.x,
+            self.start.y,
+            self.start.z,
+            self.end.x,
+            self.end.y,
+            self.end.z,
+        ]
+    }
+}
+
+/// The X, Y and Z segments of a gizmo at `frame` (a local-to-world matrix).
+pub fn axis_segments(frame: glam::Mat4) -> [AxisSegment; 3] {
+    let origin = frame.transform_point3(glam::Vec3::ZERO);
+    let axes = [glam::Vec3::X, glam::Vec3::Y, glam::Vec3::Z];
+    std::array::from_fn(|i| AxisSegment {
+        start: origin,
+        end: frame.transform_point3(axes[i] * AXIS_LENGTH),
+        color: AXIS_COLORS[i],
+    })
+}
@@ -0,0 +1,103 @@
+//! Scene background (`scene.background`).
+//!
+//! The scene pass clears to dark grey unless the script sets a background,
+//! which is drawn as a full-screen pass before anything else:
+//!
+//! ```rhai
+//! scene.background = #{ r: 0.0, g: 0.0, b: 0.1 };                     // solid colour
+//! scene.background = #{ top: #{ r: 0.2, g: 0.3, b: 0.8 },
+//!                       bottom: #{ r: 0.0, g: 0.0, b: 0.0 } };         // vertical gradient
+//! scene.background = #{ skybox: "sky.jpg" };                           // equirectangular image
+//! ```
+//!
+//! Colour components can be Signals. Skybox images are sampled by view
+//! direction, so they turn with the camera; they are loaded like particle
+//! sprites (see [`crate::sprite_asset`]).
+
+use rhai::Dynamic;
+
+/// Clear colour of the scene pass when no background is set.
+pub const DEFAULT_CLEAR_COLOR: [f32; 4] = [0.1, 0.1, 0.1, 1.0];
+
+/// An evaluated `scene.background`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SceneBackground {
+    /// Linear RGBA colours at the top and bottom edges of the screen.
+    Gradient { top: [f32; 4], bottom: [f32; 4] },
+    /// Equirectangular image, by sprite id.
+    Skybox { asset_id: String },
+}
+
+impl SceneBackground {
+    /// Parse `scene.background`, evaluating colour components with `eval`.
+    /// Returns None for `()` or an unrecognised value.
+    pub fn from_script(
+        value: &Dynamic,
+        eval: &mut impl FnMut(&Dynamic) -> Option<f32>,
+    ) -> Option<Self> {
+        let map = value.read_lock::<rhai::Map>()?;
+        if let Some(asset_id) = map.get("skybox") {
+            return Some(Self::Skybox {
+                asset_id: asset_id.clone().into_string().ok()?,
+            });
+        }
+        if let (Some(top), Some(bottom)) = (map.get("top"), map.get("bottom")) {
+            return Some(Self::Gradient {
+                top: color(top, eval)?,
+                bottom: color(bottom, eval)?,
+            });
+        }
+        let solid = color(value, eval)?;
+        Some(Self::Gradient {
+            top: solid,
+            bottom: solid,
+        })
+    }
+}
+
+/// An `#{ r, g, b, a }` colour; alpha defaults to 1.
+fn color(value: &Dynamic, eval: &mut impl FnMut(&Dynamic) -> Option<f32>) -> Option<[f32; 4]> {
+    let map = value.read_lock::<rhai::Map>()?;
+    let mut component = |key: &str| map.get(key).and_then(&mut *eval);
+    Some([
+        component("r")?,
+        component("g")?,
+        component("b")?,
+        component("a").unwrap_or(1.0),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::headless_or_skip;
+
+    #[test]
+    fn test_gradient_background_spans_top_to_bottom() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        let script = r#"
+            fn init(ctx) {
+                scene.background = #{
+                    top: #{ r: gen.constant(1.0), g: 0.5, b: 0.0 },
+                    bottom: #{ r: 0.0, g: 0.25, b: 1.0 },
+                };
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        headless.render_script(script);
+        let scene = headless.read_scene_hdr();
+        let close = |pixel: [f32; 4], expected: [f32; 3]| {
+            (0..3).all(|c| (pixel[c] - expected[c]).abs() < 0.02)
+        };
+        let top = scene[32];
+        let bottom = scene[63 * 64 + 32];
+        assert!(close(top, [1.0, 0.5, 0.0]), "top edge {:?}", top);
+        assert!(close(bottom, [0.0, 0.25, 1.0]), "bottom edge {:?}", bottom);
+        // Halfway down is the average of the two
+        let middle = scene[32 * 64 + 32];
+        assert!(close(middle, [0.5, 0.375, 0.5]), "middle {:?}", middle);
+    }
+}
@@ -0,0 +1,208 @@
+//! Built-in tempo and beat-phase estimation from an onset envelope.
+//!
+//! Used when no authored musical time is available, so beat-aware features
+//! follow the track instead of falling back to [`DEFAULT_BPM`]:
+//!
+//! 1. Tempo: the mean-removed onset envelope is autocorrelated at every
+//!    candidate period between [`MIN_BPM`] and [`MAX_BPM`] (fractional lags,
+//!    0.1 BPM steps). Each score is weighted by a log-normal prior centred on
+//!    [`DEFAULT_BPM`] to resolve octave ambiguity (64 vs 128 vs 256).
+//! 2. Phase: a comb of beat-spaced taps is slid across one period; the offset
+//!    whose taps collect the most onset energy is the first beat.
+//!
+//! The result converts to a single-segment [`MusicalTimeStructure`].
+
+use crate::musical_time::{MusicalTimeStructure, DEFAULT_BPM};
+
+/// Slowest tempo considered.
+pub const MIN_BPM: f32 = 60.0;
+
+/// Fastest tempo considered.
+pub const MAX_BPM: f32 = 200.0;
+
+/// Tempo search resolution in BPM.
+const BPM_STEP: f32 = 0.1;
+
+/// Width of the tempo prior in octaves (one standard deviation).
+const TEMPO_PRIOR_OCTAVES: f32 = 1.0;
+
+/// Minimum envelope length in beats at [`MIN_BPM`] before a tempo is estimated.
+const MIN_BEATS: f32 = 4.0;
+
+/// Provenance source recorded on detected segments.
+pub const DETECTED_TEMPO_SOURCE: &str = "auto";
+
+/// A detected tempo and beat grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TempoEstimate {
+    /// Tempo in beats per minute.
+    pub bpm: f32,
+    /// Time of the first beat in seconds (within the first beat period).
+    pub phase_offset: f32,
+    /// Normalised autocorrelation at the detected period (0-1).
+    pub confidence: f32,
+}
+
+impl TempoEstimate {
+    /// Build a single-segment musical time structure covering `[0, duration)`.
+    pub fn to_musical_time(&self, duration: f32) -> MusicalTimeStructure {
+        let mut structure = MusicalTimeStructure::constant(
+            self.bpm,
+            self.phase_offset,
+            None,
+            duration,
+            DETECTED_TEMPO_SOURCE,
+        );
+        structure.segments[0].confidence = Some(self.confidence);
+        structure
+    }
+}
+
+/// Estimate tempo and beat phase from an onset envelope sampled at
+/// `sample_rate` Hz.
+///
+/// Returns None when the envelope is shorter than a few beats at
+/// [`MIN_BPM`], or has no variation to correlate.
+pub fn detect_tempo(onset: &[f32], sample_rate: f32) -> Option<TempoEstimate> {
+    if sample_rate <= 0.0 || (onset.len() as f32) < MIN_BEATS * 60.0 / MIN_BPM * sample_rate {
+        return None;
+    }
+
+    let mean = onset.iter().sum::<f32>() / onset.len() as f32;
+    let centred: Vec<f32> = onset.iter().map(|v| v - mean).collect();
+    let energy = autocorrelation(&centred, 0.0);
+    if energy <= f32::EPSILON {
+        return None;
+    }
+
+    // Tempo: best prior-weighted autocorrelation over the BPM range.
+    let steps = ((MAX_BPM - MIN_BPM) / BPM_STEP).round() as usize;
+    let (bpm, correlation) = (0..=steps)
+        .map(|i| {
+            let bpm = MIN_BPM + i as f32 * BPM_STEP;
+            (bpm, autocorrelation(&centred, 60.0 * sample_rate / bpm))
+        })
+        .max_by(|(a_bpm, a), (b_bpm, b)| {
+            (a * tempo_prior(*a_bpm)).total_cmp(&(b * tempo_prior(*b_bpm)))
+        })?;
+    if correlation <= 0.0 {
+        return None;
+    }
+
+    // Phase: slide a beat-spaced comb over one period (in 0.1-sample steps).
+    let period = 60.0 * sample_rate / bpm;
+    let phase_steps = (period * 10.0).ceil() as usize;
+    let phase = (0..phase_steps)
+        .map(|i| i as f32 * 0.1)
+        .max_by(|a, b| comb_energy(onset, *a, period).total_cmp(&comb_energy(onset, *b, period)))
+        .unwrap_or(0.0);
+
+    Some(TempoEstimate {
+        bpm,
+        phase_offset: phase / sample_rate,
+        confidence: (correlation / energy).clamp(0.0, 1.0),
+    })
+}
+
+/// Log-normal tempo prior centred on [`DEFAULT_BPM`].
+fn tempo_prior(bpm: f32) -> f32 {
+    let octaves = (bpm / DEFAULT_BPM).log2() / TEMPO_PRIOR_OCTAVES;
+    (-0.5 * octaves * octaves).exp()
+}
+
+/// Mean of `x[n] * x[n + lag]` over the overlap, with `x` linearly
+/// interpolated at the fractional lag.
+fn autocorrelation(x: &[f32], lag: f32) -> f32 {
+    let overlap = x.len() as f32 - lag - 1.0;
+    if overlap < 1.0 {
+        return 0.0;
+    }
+    let count = overlap as usize;
+    let sum: f32 = (0..count)
+        .map(|n| x[n] * sample_linear(x, n as f32 + lag))
+        .sum();
+    sum / count as f32
+}
+
+/// Mean envelope value at `phase, phase + period, phase + 2 * period, ...`.
+fn comb_energy(x: &[f32], phase: f32, period: f32) -> f32 {
+    let taps = ((x.len() as f32 - 1.0 - phase) / period).floor().max(0.0) as usize + 1;
+    let sum: f32 = (0..taps)
+        .map(|k| sample_linear(x, phase + k as f32 * period))
+        .sum();
+    sum / taps as f32
+}
+
+fn sample_linear(x: &[f32], pos: f32) -> f32 {
+    let i = pos.floor() as usize;
+    let frac = pos - i as f32;
+    let a = x.get(i).copied().unwrap_or(0.0);
+    let b = x.get(i + 1).copied().unwrap_or(0.0);
+    a + (b - a) * frac
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Onset envelope of a click track: a short decaying spike per beat.
+    fn click_envelope(bpm: f32, first_beat: f32, seconds: f32, sample_rate: f32) -> Vec<f32> {
+        let period = 60.0 / bpm;
+        (0..(seconds * sample_rate) as usize)
+            .map(|n| {
+                let t = n as f32 / sample_rate - first_beat;
+                if t < 0.0 {
+                    return 0.0;
+                }
+                let since_beat = t.rem_euclid(period);
+                (-since_beat / 0.02).exp()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detects_128_bpm_click_track() {
+        let onset = click_envelope(128.0, 0.25, 30.0, 100.0);
+        let estimate = detect_tempo(&onset, 100.0).unwrap();
+        assert!((estimate.bpm - 128.0).abs() < 1.0, "bpm {}", estimate.bpm);
+        assert!(
+            (estimate.phase_offset - 0.25).abs() < 0.02,
+            "phase {}",
+            estimate.phase_offset
+        );
+        assert!(estimate.confidence > 0.5);
+
+        let musical_time = estimate.to_musical_time(30.0);
+        let beat = musical_time
+            .beat_position_at(0.25 + 8.5 * 60.0 / 128.0)
+            .unwrap();
+        assert_eq!(beat.beat_index, 8);
+    }
+
+    #[test]
+    fn test_prefers_beat_level_over_accented_half_tempo() {
+        // Accenting every other click makes the 64 BPM lag correlate best;
+        // the tempo prior keeps the beat level at 128.
+        let period = 60.0 / 128.0;
+        let onset: Vec<f32> = click_envelope(128.0, 0.0, 30.0, 100.0)
+            .iter()
+            .enumerate()
+            .map(|(n, v)| {
+                let beat = (n as f32 / 100.0 / period).floor() as i32;
+                if beat % 2 == 0 {
+                    *v
+                } else {
+                    0.5 * v
+                }
+            })
+            .collect();
+        let estimate = detect_tempo(&onset, 100.0).unwrap();
+        assert!((estimate.bpm - 128.0).abs() < 1.0, "bpm {}", estimate.bpm);
+    }
+
+    #[test]
+    fn test_flat_or_short_envelope_has_no_tempo() {
+        assert!(detect_tempo(&[0.5; 3000], 100.0).is_none());
+        assert!(detect_tempo(&click_envelope(128.0, 0.0, 1.0, 100.0), 100.0).is_none());
+    }
+}
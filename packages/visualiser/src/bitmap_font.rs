@@ -0,0 +1,156 @@
+//! Tiny 3x5 bitmap font for debug labels and contact sheet timestamps.
+//!
+//! Glyphs cover digits, letters (lowercase is drawn as uppercase) and common
+//! punctuation. Contact sheets fill the glyph pixels directly; GPU overlays
+//! draw [`text_strokes`] with the polyline pipeline.
+
+/// Glyph size in font pixels.
+pub const GLYPH_WIDTH: u32 = 3;
+pub const GLYPH_HEIGHT: u32 = 5;
+
+/// Horizontal distance between glyph origins, including one pixel of spacing.
+pub const GLYPH_ADVANCE: u32 = GLYPH_WIDTH + 1;
+
+/// 3x5 bitmap for a character; each row's low three bits, left to right.
+/// Characters without a glyph (including space) draw nothing.
+pub fn glyph(c: char) -> Option<[u8; 5]> {
+    Some(match c.to_ascii_uppercase() {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        'A' => [0b010, 0b101, 0b111, 0b101, 0b101],
+        'B' => [0b110, 0b101, 0b110, 0b101, 0b110],
+        'C' => [0b011, 0b100, 0b100, 0b100, 0b011],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'F' => [0b111, 0b100, 0b110, 0b100, 0b100],
+        'G' => [0b011, 0b100, 0b101, 0b101, 0b011],
+        'H' => [0b101, 0b101, 0b111, 0b101, 0b101],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'J' => [0b001, 0b001, 0b001, 0b101, 0b010],
+        'K' => [0b101, 0b101, 0b110, 0b101, 0b101],
+        'L' => [0b100, 0b100, 0b100, 0b100, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'N' => [0b110, 0b101, 0b101, 0b101, 0b101],
+        'O' => [0b010, 0b101, 0b101, 0b101, 0b010],
+        'P' => [0b110, 0b101, 0b110, 0b100, 0b100],
+        'Q' => [0b010, 0b101, 0b101, 0b110, 0b011],
+        'R' => [0b110, 0b101, 0b110, 0b101, 0b101],
+        'S' => [0b011, 0b100, 0b010, 0b001, 0b110],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        'U' => [0b101, 0b101, 0b101, 0b101, 0b111],
+        'V' => [0b101, 0b101, 0b101, 0b101, 0b010],
+        'W' => [0b101, 0b101, 0b111, 0b111, 0b101],
+        'X' => [0b101, 0b101, 0b010, 0b101, 0b101],
+        'Y' => [0b101, 0b101, 0b010, 0b010, 0b010],
+        'Z' => [0b111, 0b001, 0b010, 0b100, 0b111],
+        ':' => [0b000, 0b010, 0b000, 0b010, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        ',' => [0b000, 0b000, 0b000, 0b010, 0b100],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '_' => [0b000, 0b000, 0b000, 0b000, 0b111],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '=' => [0b000, 0b111, 0b000, 0b111, 0b000],
+        '/' => [0b001, 0b001, 0b010, 0b100, 0b100],
+        '%' => [0b101, 0b001, 0b010, 0b100, 0b101],
+        '#' => [0b101, 0b111, 0b101, 0b111, 0b101],
+        '(' => [0b010, 0b100, 0b100, 0b100, 0b010],
+        ')' => [0b010, 0b001, 0b001, 0b001, 0b010],
+        '[' => [0b110, 0b100, 0b100, 0b100, 0b110],
+        ']' => [0b011, 0b001, 0b001, 0b001, 0b011],
+        '!' => [0b010, 0b010, 0b010, 0b000, 0b010],
+        '?' => [0b110, 0b001, 0b010, 0b000, 0b010],
+        '\'' => [0b010, 0b010, 0b000, 0b000, 0b000],
+        _ => return None,
+    })
+}
+
+/// Width of `text` in font pixels (no trailing spacing).
+pub fn text_width(text: &str) -> u32 {
+    (text.chars().count() as u32 * GLYPH_ADVANCE).saturating_sub(1)
+}
+
+/// Line segments `[x0, y0, x1, y1]` tracing `text`, in font pixels with the
+/// origin at the top-left and y pointing down.
+///
+/// Strokes run through pixel centres, joining neighbouring lit pixels
+/// horizontally, vertically and (where no orthogonal path exists)
+/// diagonally. A pixel with no lit neighbours becomes a short dash.
+pub fn text_strokes(text: &str) -> Vec<[f32; 4]> {
+    let mut strokes = Vec::new();
+    for (index, c) in text.chars().enumerate() {
+        let Some(rows) = glyph(c) else { continue };
+        let lit = |x: i32, y: i32| {
+            (0..GLYPH_WIDTH as i32).contains(&x)
+                && (0..GLYPH_HEIGHT as i32).contains(&y)
+                && rows[y as usize] & (0b100 >> x) != 0
+        };
+        let origin = (index as u32 * GLYPH_ADVANCE) as f32 + 0.5;
+        let mut push = |x0: f32, y0: f32, x1: f32, y1: f32| {
+            strokes.push([origin + x0, y0 + 0.5, origin + x1, y1 + 0.5]);
+        };
+
+        for y in 0..GLYPH_HEIGHT as i32 {
+            for x in 0..GLYPH_WIDTH as i32 {
+                if !lit(x, y) {
+                    continue;
+                }
+                let (fx, fy) = (x as f32, y as f32);
+                let mut joined = false;
+                if lit(x + 1, y) {
+                    push(fx, fy, fx + 1.0, fy);
+                    joined = true;
+                }
+                if lit(x, y + 1) {
+                    push(fx, fy, fx, fy + 1.0);
+                    joined = true;
+                }
+                for dx in [-1, 1] {
+                    if lit(x + dx, y + 1) && !lit(x + dx, y) && !lit(x, y + 1) {
+                        push(fx, fy, fx + dx as f32, fy + 1.0);
+                        joined = true;
+                    }
+                }
+                let has_neighbour = (-1..=1)
+                    .flat_map(|dy| (-1..=1).map(move |dx| (dx, dy)))
+                    .any(|(dx, dy)| (dx, dy) != (0, 0) && lit(x + dx, y + dy));
+                if !joined && !has_neighbour {
+                    push(fx - 0.25, fy, fx + 0.25, fy);
+                }
+            }
+        }
+    }
+    strokes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_strokes_trace_glyphs() {
+        let strokes = text_strokes("L");
+        // Vertical stem (4 links) and base (2 links)
+        assert_eq!(strokes.len(), 6);
+        assert!(strokes.contains(&[0.5, 0.5, 0.5, 1.5]));
+        assert!(strokes.contains(&[1.5, 4.5, 2.5, 4.5]));
+
+        // An isolated pixel still draws something
+        assert_eq!(text_strokes(".").len(), 1);
+        // Lowercase uses the uppercase glyph; unknown characters are skipped
+        assert_eq!(text_strokes("l"), strokes);
+        assert!(text_strokes(" ~").is_empty());
+
+        // Glyphs advance by four pixels
+        let second = text_strokes(" L");
+        assert_eq!(second[0][0], strokes[0][0] + GLYPH_ADVANCE as f32);
+        assert_eq!(text_width("ab"), 7);
+    }
+}
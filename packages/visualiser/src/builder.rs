@@ -0,0 +1,413 @@
+//! Builder-style API for embedding the visualiser in Rust programs.
+//!
+//! `VisualiserBuilder` collects the same inputs the `render` CLI command takes
+//! (script, audio signal or interpretation package, resolution, fps, seed,
+//! bands, loop range, outputs) and either renders offline with
+//! [`VisualiserBuilder::run`] or hands back an [`AttachedVisualiser`] that
+//! draws into a host-owned surface, such as a window's swapchain, with
+//! [`VisualiserBuilder::attach`]. Both set up the render with the CLI's
+//! [`PreparedRender`], and `run` draws it with the CLI's [`render_frames`].
+//!
+//! ```no_run
+//! use visualiser::builder::{AudioSource, VisualiserBuilder};
+//!
+//! let summary = VisualiserBuilder::new()
+//!     .script_file("scene.rhai")
+//!     .audio(AudioSource::SignalFile {
+//!         path: "amplitude.json".into(),
+//!         sample_rate: 100.0,
+//!     })
+//!     .resolution(1280, 720)
+//!     .fps(30.0)
+//!     .seed(42)
+//!     .output_dir("frames")
+//!     .on_progress(|progress| eprintln!("{}/{}", progress.frame + 1, progress.total_frames))
+//!     .run()?;
+//! println!("Rendered {} frames", summary.frame_count);
+//! # Ok::<(), visualiser::error::VisualiserError>(())
+//! ```
+
+use std::path::PathBuf;
+
+use crate::cli::{render_frames, FrameSinks, PreparedRender, RenderSources};
+use crate::contact_sheet::ContactSheetGrid;
+use crate::error::{Result, VisualiserError};
+use crate::gpu::headless;
+use crate::gpu::renderer::Renderer;
+use crate::input::InputSignal;
+use crate::render_job::{RenderError, RenderJobSpec, RenderPhase};
+use crate::video_encode::VideoEncodingOptions;
+use crate::visualiser::VisualiserState;
+
+/// Where the per-frame input signals come from.
+#[derive(Debug, Clone, Default)]
+pub enum AudioSource {
+    /// No input: the script only sees time-based signals.
+    #[default]
+    None,
+    /// An in-memory amplitude signal.
+    Samples { samples: Vec<f32>, sample_rate: f32 },
+    /// A file with a JSON array of floats, or whitespace separated floats
+    /// (the CLI's `--input`).
+    SignalFile { path: PathBuf, sample_rate: f32 },
+    /// An interpretation package (the CLI's `--package`). Supplies the signal
+    /// maps, event streams, default duration and, if none is set, the script.
+    Package(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+enum ScriptSource {
+    Inline(String),
+    File(PathBuf),
+}
+
+/// Progress reported by [`VisualiserBuilder::run`] after each frame.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderProgress {
+    /// Index of the frame that just finished.
+    pub frame: usize,
+    pub total_frames: usize,
+    /// Wall-clock seconds since rendering started.
+    pub elapsed_secs: f64,
+}
+
+/// A rendered frame passed to the per-frame callback.
+#[derive(Debug)]
+pub struct RenderedFrame<'a> {
+    pub index: usize,
+    /// Timeline position of the frame in seconds.
+    pub time: f32,
+    pub width: u32,
+    pub height: u32,
+    /// Tightly packed RGBA8 rows, top row first.
+    pub pixels: &'a [u8],
+}
+
+impl RenderedFrame<'_> {
+    /// The RGBA value at (x, y), with (0, 0) at the top left.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+}
+
+/// Result of a completed offline render.
+#[derive(Debug, Clone)]
+pub struct RenderSummary {
+    pub frame_count: usize,
+    pub render_duration_secs: f64,
+    /// Directory the PNG frames were written to, if any.
+    pub output_dir: Option<PathBuf>,
+    /// The encoded video or animated image, if one was written.
+    pub video_path: Option<PathBuf>,
+    /// Outputs that were skipped, e.g. video without FFmpeg.
+    pub warnings: Vec<String>,
+}
+
+type ProgressCallback = Box<dyn FnMut(&RenderProgress)>;
+type FrameCallback = Box<dyn FnMut(&RenderedFrame)>;
+
+/// Configures and runs the visualiser programmatically.
+pub struct VisualiserBuilder {
+    script: Option<ScriptSource>,
+    audio: AudioSource,
+    /// Every other setting, in the CLI's terms.
+    job: RenderJobSpec,
+    output_dir: Option<PathBuf>,
+    on_progress: Option<ProgressCallback>,
+    on_frame: Option<FrameCallback>,
+}
+
+impl Default for VisualiserBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VisualiserBuilder {
+    /// Create a builder with the CLI defaults: 1920x1080 at 60 fps, seed 0, no output.
+    pub fn new() -> Self {
+        Self {
+            script: None,
+            audio: AudioSource::None,
+            job: RenderJobSpec {
+                input_path: None,
+                script_path: None,
+                ..RenderJobSpec::new(PathBuf::new(), PathBuf::new(), PathBuf::new())
+            },
+            output_dir: None,
+            on_progress: None,
+            on_frame: None,
+        }
+    }
+
+    /// Use this Rhai source as the script.
+    pub fn script(mut self, source: impl Into<String>) -> Self {
+        self.script = Some(ScriptSource::Inline(source.into()));
+        self
+    }
+
+    /// Load the script from a file. Relative sprite and mesh paths resolve against its directory.
+    pub fn script_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.script = Some(ScriptSource::File(path.into()));
+        self
+    }
+
+    pub fn audio(mut self, audio: AudioSource) -> Self {
+        self.audio = audio;
+        self
+    }
+
+    pub fn resolution(mut self, width: u32, height: u32) -> Self {
+        self.job.width = width;
+        self.job.height = height;
+        self
+    }
+
+    pub fn fps(mut self, fps: f32) -> Self {
+        self.job.fps = fps;
+        self
+    }
+
+    /// Global seed for deterministic randomness. 0 leaves the engine default.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.job.seed = seed;
+        self
+    }
+
+    /// Render length in seconds. Defaults to the package or audio duration.
+    pub fn duration(mut self, seconds: f32) -> Self {
+        self.job.duration = Some(seconds);
+        self
+    }
+
+    /// Custom frequency bands computed from the audio signal (the CLI's
+    /// `--bands`): a band config file, inline JSON, or `mel:N`.
+    pub fn bands(mut self, spec: impl Into<String>) -> Self {
+        self.job.bands = Some(spec.into());
+        self
+    }
+
+    /// Play `[start, end)` seconds on repeat for the whole render.
+    pub fn loop_range(mut self, start: f32, end: f32) -> Self {
+        self.job.loop_start = Some(start);
+        self.job.loop_end = Some(end);
+        self
+    }
+
+    /// Cap on rendered particle instances per frame, shared across systems.
+    pub fn max_particles(mut self, max: usize) -> Self {
+        self.job.max_particles = Some(max);
+        self
+    }
+
+    /// Encode the render to `path`. `.gif` and `.webp` are written directly;
+    /// other formats are encoded with FFmpeg from the PNG frames, so they
+    /// need an [`output_dir`](Self::output_dir).
+    pub fn video(mut self, path: impl Into<PathBuf>) -> Self {
+        self.job.output_video = true;
+        self.job.video_path = Some(path.into());
+        self
+    }
+
+    /// Codec and quality settings for [`video`](Self::video).
+    pub fn video_options(mut self, options: VideoEncodingOptions) -> Self {
+        self.job.video_options = options;
+        self
+    }
+
+    /// Draw sampled frames onto a single `contact_sheet.png` in the output
+    /// directory instead of saving every frame.
+    pub fn contact_sheet(mut self, grid: ContactSheetGrid) -> Self {
+        self.job.contact_sheet = Some(grid);
+        self
+    }
+
+    /// Write a manifest of the settings, inputs and effect chain beside the
+    /// video, or in the output directory.
+    pub fn manifest(mut self, enabled: bool) -> Self {
+        self.job.manifest = enabled;
+        self
+    }
+
+    /// Write frames to `dir` as `frame_00000.png`, `frame_00001.png`, ...
+    pub fn output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    /// Called after every frame rendered by [`run`](Self::run).
+    pub fn on_progress(mut self, callback: impl FnMut(&RenderProgress) + 'static) -> Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Called with the pixels of every frame rendered by [`run`](Self::run).
+    pub fn on_frame(mut self, callback: impl FnMut(&RenderedFrame) + 'static) -> Self {
+        self.on_frame = Some(Box::new(callback));
+        self
+    }
+
+    /// Render every frame offscreen, without a window, through the same
+    /// render path as the CLI.
+    ///
+    /// Frames go to the output directory (if set) and the per-frame callback.
+    pub fn run(mut self) -> Result<RenderSummary> {
+        let (job, mut prepared) = self.prepare()?;
+        if prepared.duration.is_none() {
+            return Err(RenderError::new(
+                RenderPhase::Initialization,
+                "No duration available from duration(), package, or audio signal",
+            )
+            .into());
+        }
+
+        let (device, queue) =
+            headless::request_device().map_err(|e| RenderError::new(RenderPhase::GpuSetup, e))?;
+
+        let mut warnings = Vec::new();
+        let outcome = render_frames(
+            &job,
+            &mut prepared,
+            device,
+            queue,
+            FrameSinks {
+                write_files: self.output_dir.is_some(),
+                quiet: true,
+                on_frame: self.on_frame.as_deref_mut().map(|f| f as _),
+                on_progress: self.on_progress.as_deref_mut().map(|f| f as _),
+            },
+            &mut warnings,
+        )?;
+
+        Ok(RenderSummary {
+            frame_count: outcome.frame_count,
+            render_duration_secs: outcome.render_duration_secs,
+            output_dir: self.output_dir,
+            video_path: outcome.video_path,
+            warnings,
+        })
+    }
+
+    /// Build a visualiser that renders with the host's device, e.g. into a window surface.
+    ///
+    /// The host drives the frame loop: call [`AttachedVisualiser::update`] and
+    /// [`AttachedVisualiser::render`] once per frame. `format` is the format of
+    /// the views passed to `render`. The outputs and callbacks only apply to
+    /// [`run`](Self::run).
+    pub fn attach(
+        self,
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        format: wgpu::TextureFormat,
+    ) -> Result<AttachedVisualiser> {
+        let (job, prepared) = self.prepare()?;
+        let renderer = Renderer::new(device, queue, format, job.width, job.height);
+        Ok(AttachedVisualiser { renderer, prepared })
+    }
+
+    /// Validate the settings, then load the inputs and script and set up the
+    /// state as the CLI does.
+    fn prepare(&self) -> Result<(RenderJobSpec, PreparedRender)> {
+        let mut job = self.job.clone();
+        job.output_dir = self.output_dir.clone().unwrap_or_default();
+        let mut sources = RenderSources::default();
+        match &self.script {
+            Some(ScriptSource::Inline(source)) => sources.script = Some(source.clone()),
+            Some(ScriptSource::File(path)) => job.script_path = Some(path.clone()),
+            None => {}
+        }
+        match &self.audio {
+            AudioSource::None => {}
+            AudioSource::Samples {
+                samples,
+                sample_rate,
+            } => {
+                sources.audio = Some(std::rc::Rc::new(InputSignal::new(
+                    samples.clone(),
+                    *sample_rate,
+                )))
+            }
+            AudioSource::SignalFile { path, sample_rate } => {
+                job.input_path = Some(path.clone());
+                job.input_sample_rate = *sample_rate;
+            }
+            AudioSource::Package(path) => job.package_path = Some(path.clone()),
+        }
+
+        job.validate_settings()?;
+        if matches!(job.duration, Some(d) if d <= 0.0) {
+            return Err(VisualiserError::InvalidConfig(
+                "Duration must be positive".to_string(),
+            ));
+        }
+        if self.script.is_none() && job.package_path.is_none() {
+            return Err(VisualiserError::InvalidConfig(
+                "No script: set one with script() or script_file()".to_string(),
+            ));
+        }
+        if job.bands.is_some() && job.input_path.is_none() && sources.audio.is_none() {
+            return Err(VisualiserError::InvalidConfig(
+                "bands() needs audio samples or a signal file".to_string(),
+            ));
+        }
+        let needs_output_dir = job.contact_sheet.is_some()
+            || (job.output_video && job.animation_format().is_none())
+            || (job.manifest && !job.output_video);
+        if needs_output_dir && self.output_dir.is_none() {
+            return Err(VisualiserError::InvalidConfig(
+                "Contact sheets, manifests and FFmpeg video are written to the output \
+                 directory: set output_dir()"
+                    .to_string(),
+            ));
+        }
+
+        let prepared = PreparedRender::load(&job, sources, &mut Vec::new())?;
+        Ok((job, prepared))
+    }
+}
+
+/// A visualiser rendering with a host-owned device. Created by [`VisualiserBuilder::attach`].
+pub struct AttachedVisualiser {
+    renderer: Renderer,
+    prepared: PreparedRender,
+}
+
+impl AttachedVisualiser {
+    /// Advance the script and inputs by `dt` seconds.
+    pub fn update(&mut self, dt: f32) {
+        self.prepared.update(dt);
+    }
+
+    /// Draw the current state into `view`.
+    pub fn render(&mut self, view: &wgpu::TextureView) {
+        self.renderer.render(view, &self.prepared.state);
+    }
+
+    /// Resize the render targets, e.g. after the window was resized.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.renderer.resize(width, height, &self.prepared.state);
+    }
+
+    /// Render length in seconds, if known from the settings or inputs.
+    pub fn duration(&self) -> Option<f32> {
+        self.prepared.duration
+    }
+
+    pub fn state(&self) -> &VisualiserState {
+        &self.prepared.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut VisualiserState {
+        &mut self.prepared.state
+    }
+
+    pub fn renderer(&self) -> &Renderer {
+        &self.renderer
+    }
+}
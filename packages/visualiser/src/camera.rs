@@ -9,13 +9,27 @@
 //! - **LookAt mode**: Uses position + target (auto-derives orientation)
 //!
 //! Mode is determined automatically: if `target` is set, LookAt is used.
+//!
+//! `near`/`far` set the clip planes. Unusable values (non-finite, near <= 0,
+//! far <= near) are sanitised rather than producing a degenerate projection.
+//!
+//! `camera.shake()` adds positional and rotational noise on top of the
+//! evaluated transform. The noise is a function of time and seed only, so
+//! offline renders and seeks reproduce the same shake.
+
+use std::sync::Arc;
 
 use bytemuck::{Pod, Zeroable};
 
+use crate::deformation::deterministic_noise_3d;
+use crate::event_stream::EventStream;
 use crate::feedback::SignalOrF32;
 use crate::signal::Signal;
 use crate::signal_eval::EvalContext;
 
+/// Smallest usable near plane; closer values are clamped.
+pub const MIN_NEAR: f32 = 1e-4;
+
 // ============================================================================
 // Vec3 with Signal Support
 // ============================================================================
@@ -82,6 +96,82 @@ impl Default for Vec3Signal {
     }
 }
 
+// ============================================================================
+// Camera Shake
+// ============================================================================
+
+/// Noise-driven camera shake added on top of the camera transform.
+///
+/// Without a trigger the shake strength is `amount`. With one, each event
+/// kicks the strength up to `amount` times the event weight, and it decays
+/// exponentially from there.
+#[derive(Clone, Debug)]
+pub struct CameraShake {
+    /// Positional shake in world units at full strength.
+    pub amount: SignalOrF32,
+
+    /// Noise frequency in Hz.
+    pub frequency: f32,
+
+    /// Rotational shake in radians per unit of strength.
+    pub rotation: f32,
+
+    /// Seconds for a triggered shake to fall to 1/e of its peak.
+    pub decay: f32,
+
+    /// Seed selecting the noise pattern.
+    pub seed: u32,
+
+    /// Events that kick the shake, if any.
+    pub trigger: Option<Arc<EventStream>>,
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self {
+            amount: SignalOrF32::Scalar(0.1),
+            frequency: 12.0,
+            rotation: 0.1,
+            decay: 0.25,
+            seed: 0,
+            trigger: None,
+        }
+    }
+}
+
+impl CameraShake {
+    /// Triggered shakes are ignored once they decay below this fraction.
+    const SETTLED: f32 = 1e-4;
+
+    /// Envelope of the trigger events at `time` (1 without a trigger).
+    ///
+    /// Computed from event times rather than accumulated per frame, so it
+    /// is the same however the timeline is stepped.
+    pub fn envelope(&self, time: f32) -> f32 {
+        let Some(trigger) = &self.trigger else {
+            return 1.0;
+        };
+        let decay = self.decay.max(1e-3);
+        let window = decay * -Self::SETTLED.ln();
+        trigger
+            .events_in_range(time - window, f32::INFINITY)
+            .take_while(|event| event.time <= time)
+            .map(|event| event.weight * (-(time - event.time) / decay).exp())
+            .fold(0.0, f32::max)
+    }
+
+    /// Shake offsets at `time` for a strength: a positional offset along the
+    /// camera's (right, up, forward) axes and (pitch, yaw, roll) in radians.
+    pub fn offsets(&self, time: f32, strength: f32) -> (glam::Vec3, glam::Vec3) {
+        let phase = time * self.frequency;
+        // One noise row per axis
+        let noise = |channel: u32| deterministic_noise_3d(phase, channel as f32, 0.0, self.seed);
+        let position = glam::Vec3::new(noise(0), noise(1), noise(2)) * strength;
+        let rotation = glam::Vec3::new(noise(3), noise(4), noise(5)) * strength * self.rotation;
+        (position, rotation)
+    }
+}
+
 // ============================================================================
 // Camera Configuration
 // ============================================================================
@@ -114,6 +204,9 @@ pub struct CameraConfig {
 
     /// Far clip plane distance.
     pub far: SignalOrF32,
+
+    /// Camera shake, if any.
+    pub shake: Option<CameraShake>,
 }
 
 impl Default for CameraConfig {
@@ -128,6 +221,7 @@ impl Default for CameraConfig {
             fov: SignalOrF32::Scalar(45.0),
             near: SignalOrF32::Scalar(0.1),
             far: SignalOrF32::Scalar(100.0),
+            shake: None,
         }
     }
 }
@@ -161,7 +255,7 @@ impl CameraConfig {
             ([0.0, 0.0, 0.0], 0) // Euler mode
         };
 
-        CameraUniforms {
+        let uniforms = CameraUniforms {
             position: [position[0], position[1], position[2], 1.0],
             rotation: [rotation[0], rotation[1], rotation[2], 0.0],
             target: [target[0], target[1], target[2], 1.0],
@@ -170,7 +264,17 @@ impl CameraConfig {
             near,
             far,
             mode,
+        };
+
+        let Some(shake) = &self.shake else {
+            return uniforms;
+        };
+        let strength = shake.amount.evaluate(ctx) * shake.envelope(ctx.time);
+        if strength == 0.0 {
+            return uniforms;
         }
+        let (offset, angles) = shake.offsets(ctx.time, strength);
+        uniforms.shaken(offset, angles)
     }
 
     /// Check if any parameter is signal-driven.
@@ -186,6 +290,11 @@ impl CameraConfig {
             || !self.fov.is_scalar()
             || !self.near.is_scalar()
             || !self.far.is_scalar()
+            || self
+                .shake
+                .as_ref()
+                .map(|s| !s.amount.is_scalar())
+                .unwrap_or(false)
     }
 
     /// Collect all Signal values from this config.
@@ -210,6 +319,13 @@ impl CameraConfig {
         if let SignalOrF32::Signal(s) = &self.far {
             signals.push(s.clone());
         }
+        if let Some(CameraShake {
+            amount: SignalOrF32::Signal(s),
+            ..
+        }) = &self.shake
+        {
+            signals.push(s.clone());
+        }
 
         signals
     }
@@ -315,9 +431,26 @@ impl CameraUniforms {
         }
     }
 
+    /// Near and far planes, sanitised: near is at least [`MIN_NEAR`] and far
+    /// is beyond near.
+    pub fn clip_planes(&self) -> (f32, f32) {
+        let near = if self.near.is_finite() {
+            self.near.max(MIN_NEAR)
+        } else {
+            MIN_NEAR
+        };
+        let far = if self.far.is_finite() {
+            self.far.max(near + MIN_NEAR)
+        } else {
+            near + MIN_NEAR
+        };
+        (near, far)
+    }
+
     /// Compute the projection matrix from camera parameters.
     pub fn projection_matrix(&self, aspect: f32) -> glam::Mat4 {
-        glam::Mat4::perspective_rh(self.fov.to_radians(), aspect, self.near, self.far)
+        let (near, far) = self.clip_planes();
+        glam::Mat4::perspective_rh(self.fov.to_radians(), aspect, near, far)
     }
 
     /// Compute the combined view-projection matrix.
@@ -354,6 +487,40 @@ impl CameraUniforms {
     pub fn camera_up(&self) -> glam::Vec3 {
         self.right().cross(self.forward())
     }
+
+    /// Apply a shake: `offset` moves the camera along its right/up/forward
+    /// axes, and `angles` (pitch, yaw, roll) turn it in place.
+    pub fn shaken(&self, offset: glam::Vec3, angles: glam::Vec3) -> Self {
+        let mut forward = self.forward();
+        let mut right = self.right();
+        if !forward.is_finite() || !right.is_finite() {
+            // Degenerate orientation (camera on its target, or looking along up)
+            forward = glam::Vec3::NEG_Z;
+            right = glam::Vec3::X;
+        }
+        let up = right.cross(forward);
+
+        let mut shaken = *self;
+        let eye = self.position_vec3() + right * offset.x + up * offset.y + forward * offset.z;
+        shaken.position = [eye.x, eye.y, eye.z, 1.0];
+
+        if self.is_look_at() {
+            // Swing the view direction and roll the up vector around it
+            let turn = glam::Quat::from_axis_angle(up, angles.y)
+                * glam::Quat::from_axis_angle(right, angles.x);
+            let direction = turn * (self.target_vec3() - self.position_vec3());
+            let roll = glam::Quat::from_axis_angle(turn * forward, angles.z);
+            let target = eye + direction;
+            let world_up = roll * turn * self.up_vec3();
+            shaken.target = [target.x, target.y, target.z, 1.0];
+            shaken.up = [world_up.x, world_up.y, world_up.z, 0.0];
+        } else {
+            for (rotation, angle) in shaken.rotation.iter_mut().zip(angles.to_array()) {
+                *rotation += angle;
+            }
+        }
+        shaken
+    }
 }
 
 // ============================================================================
@@ -464,6 +631,7 @@ mod tests {
             fov: SignalOrF32::Scalar(60.0),
             near: SignalOrF32::Scalar(0.5),
             far: SignalOrF32::Scalar(50.0),
+            shake: None,
         };
 
         let uniforms = config.to_uniforms(&mut ctx);
@@ -510,6 +678,7 @@ mod tests {
             fov: SignalOrF32::Scalar(45.0),
             near: SignalOrF32::Scalar(0.1),
             far: SignalOrF32::Scalar(100.0),
+            shake: None,
         };
 
         let uniforms = config.to_uniforms(&mut ctx);
@@ -520,6 +689,121 @@ mod tests {
         assert_eq!(uniforms.mode, 1); // LookAt mode
     }
 
+    /// Evaluate a camera config at a time without any signal inputs.
+    fn uniforms_at(config: &CameraConfig, time: f32) -> CameraUniforms {
+        let (mut state, stats) = test_eval_ctx();
+        let inputs = HashMap::new();
+        let bands = HashMap::new();
+        let stems = HashMap::new();
+        let custom_signals = HashMap::new();
+        let composed_signals = HashMap::new();
+        let mut ctx = EvalContext::new(
+            time,
+            0.016,
+            0,
+            None,
+            &inputs,
+            &bands,
+            &stems,
+            &custom_signals,
+            &composed_signals,
+            &stats,
+            &mut state,
+            None,
+        );
+        config.to_uniforms(&mut ctx)
+    }
+
+    /// How far the shaken camera sits from the unshaken one.
+    fn shake_offset(config: &CameraConfig, time: f32) -> f32 {
+        let still = CameraConfig {
+            shake: None,
+            ..config.clone()
+        };
+        let shaken = uniforms_at(config, time);
+        let still = uniforms_at(&still, time);
+        shaken.position_vec3().distance(still.position_vec3())
+            + shaken.target_vec3().distance(still.target_vec3())
+    }
+
+    #[test]
+    fn test_zero_shake_leaves_camera_unshaken() {
+        let config = CameraConfig {
+            shake: Some(CameraShake {
+                amount: SignalOrF32::Scalar(0.0),
+                ..CameraShake::default()
+            }),
+            ..CameraConfig::default()
+        };
+        let still = uniforms_at(&CameraConfig::default(), 1.3);
+        let shaken = uniforms_at(&config, 1.3);
+        assert_eq!(bytemuck::bytes_of(&shaken), bytemuck::bytes_of(&still));
+    }
+
+    #[test]
+    fn test_shake_is_reproducible_from_time_and_seed() {
+        let config = CameraConfig {
+            shake: Some(CameraShake::default()),
+            ..CameraConfig::default()
+        };
+        let first = uniforms_at(&config, 0.7);
+        let again = uniforms_at(&config, 0.7);
+        assert_eq!(bytemuck::bytes_of(&first), bytemuck::bytes_of(&again));
+        assert!(shake_offset(&config, 0.7) > 0.0);
+
+        // Noise is continuous in time, and the seed picks another pattern
+        assert!(
+            first
+                .position_vec3()
+                .distance(uniforms_at(&config, 0.701).position_vec3())
+                < 0.01
+        );
+        let reseeded = CameraConfig {
+            shake: Some(CameraShake {
+                seed: 7,
+                ..CameraShake::default()
+            }),
+            ..CameraConfig::default()
+        };
+        assert_ne!(
+            bytemuck::bytes_of(&uniforms_at(&reseeded, 0.7)),
+            bytemuck::bytes_of(&first)
+        );
+    }
+
+    #[test]
+    fn test_triggered_shake_spikes_then_settles() {
+        use crate::event_stream::{Event, PickEventsOptions};
+
+        let trigger = EventStream::new(
+            vec![Event::new(1.0, 1.0)],
+            String::new(),
+            PickEventsOptions::default(),
+        );
+        let config = CameraConfig {
+            shake: Some(CameraShake {
+                amount: SignalOrF32::Scalar(1.0),
+                trigger: Some(Arc::new(trigger)),
+                ..CameraShake::default()
+            }),
+            ..CameraConfig::default()
+        };
+
+        // Still before the event
+        assert_eq!(shake_offset(&config, 0.9), 0.0);
+
+        // The event kicks the shake to full strength
+        let spike = (0..10)
+            .map(|frame| shake_offset(&config, 1.0 + frame as f32 * 0.01))
+            .fold(0.0, f32::max);
+        assert!(spike > 0.2, "spike {spike}");
+
+        // Then it decays away
+        let late = shake_offset(&config, 2.5);
+        assert!(late < spike * 0.01, "late {late} vs spike {spike}");
+        assert_eq!(shake_offset(&config, 5.0), 0.0);
+    }
+
     #[test]
     fn test_uniform_size() {
         // Ensure proper alignment for GPU
@@ -544,4 +828,35 @@ mod tests {
         let origin_in_view = view.transform_point3(glam::Vec3::ZERO);
         assert!(origin_in_view.z < 0.0); // Origin should be in front (negative Z in view space)
     }
+
+    #[test]
+    fn test_clip_planes_clip_outside_objects() {
+        let mut uniforms = CameraUniforms {
+            position: [0.0, 0.0, 20.0, 1.0],
+            target: [0.0, 0.0, 0.0, 1.0],
+            near: 1.0,
+            far: 10.0,
+            mode: 1,
+            ..CameraUniforms::new()
+        };
+        // Is an object this far in front of the camera inside the clip volume?
+        let drawn = |uniforms: &CameraUniforms, distance: f32| {
+            let point = glam::Vec3::new(0.0, 0.0, 20.0 - distance);
+            let clip = uniforms.view_projection_matrix(1.0) * point.extend(1.0);
+            (0.0..=clip.w).contains(&clip.z)
+        };
+
+        assert!(!drawn(&uniforms, 0.5));
+        assert!(drawn(&uniforms, 1.5));
+        assert!(drawn(&uniforms, 9.5));
+        assert!(!drawn(&uniforms, 10.5));
+        assert!(!drawn(&uniforms, 50.0));
+
+        // Unusable planes are sanitised rather than producing NaNs.
+        uniforms.near = 0.0;
+        uniforms.far = -1.0;
+        let (near, far) = uniforms.clip_planes();
+        assert!(near > 0.0 && far > near);
+        assert!(uniforms.view_projection_matrix(1.0).is_finite());
+    }
 }
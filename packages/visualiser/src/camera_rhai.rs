@@ -3,9 +3,12 @@
 //! The camera is exposed as a global Map in Rhai scripts, with properties
 //! that can be set to either numeric literals or Signal graphs.
 
+use std::sync::Arc;
+
 use rhai::Dynamic;
 
-use crate::camera::{CameraConfig, CameraUniforms, Vec3Signal};
+use crate::camera::{CameraConfig, CameraShake, CameraUniforms, Vec3Signal};
+use crate::event_stream::EventStream;
 use crate::feedback::SignalOrF32;
 use crate::signal::Signal;
 use crate::signal_eval::EvalContext;
@@ -41,6 +44,9 @@ camera.fov = 45.0;
 camera.near = 0.1;
 camera.far = 100.0;
 
+// Camera shake options (set by camera.shake)
+camera.__shake = ();
+
 // === Camera Helper Methods ===
 
 // lookAt(target) - Set the camera to look at a target position
@@ -108,6 +114,14 @@ camera.pan = |dx, dy| {
         this.target.y = this.target.y + dy;
     }
 };
+
+// shake(options) - Add noise on top of the camera position and orientation
+// options: #{ amount, frequency, rotation, decay, seed, trigger }
+// amount can be a Signal; trigger is an EventStream whose events kick a
+// decaying shake. shake(()) stops shaking.
+camera.shake = |options| {
+    this.__shake = options;
+};
 "#
     .to_string()
 }
@@ -155,6 +169,36 @@ fn parse_vec3_from_map(map: &rhai::Map) -> Option<Vec3Signal> {
     Some(Vec3Signal { x, y, z })
 }
 
+/// Parse `camera.shake()` options, keeping defaults for missing keys.
+fn parse_shake_from_map(map: &rhai::Map) -> CameraShake {
+    let mut shake = CameraShake::default();
+    // Only the amount can follow a Signal
+    let scalar = |key: &str| match map.get(key).and_then(to_signal_or_f32) {
+        Some(SignalOrF32::Scalar(value)) => Some(value),
+        _ => None,
+    };
+
+    if let Some(amount) = map.get("amount").and_then(to_signal_or_f32) {
+        shake.amount = amount;
+    }
+    if let Some(frequency) = scalar("frequency") {
+        shake.frequency = frequency;
+    }
+    if let Some(rotation) = scalar("rotation") {
+        shake.rotation = rotation;
+    }
+    if let Some(decay) = scalar("decay") {
+        shake.decay = decay;
+    }
+    if let Some(seed) = map.get("seed").and_then(|d| d.as_int().ok()) {
+        shake.seed = seed as u32;
+    }
+    shake.trigger = map
+        .get("trigger")
+        .and_then(|d| d.clone().try_cast::<Arc<EventStream>>());
+    shake
+}
+
 /// Sync camera configuration from the Rhai scope.
 ///
 /// Reads the `camera` Map from scope and converts it to CameraConfig.
@@ -232,6 +276,12 @@ pub fn sync_camera_from_scope(
         config.far = far;
     }
 
+    // Parse shake (unit when not shaking)
+    config.shake = camera_map
+        .get("__shake")
+        .and_then(|d| d.clone().try_cast::<rhai::Map>())
+        .map(|options| parse_shake_from_map(&options));
+
     // Evaluate signals to produce uniforms
     let uniforms = config.to_uniforms(eval_ctx);
 
@@ -251,5 +301,6 @@ mod tests {
         assert!(ns.contains("camera.lookAt"));
         assert!(ns.contains("camera.orbit"));
         assert!(ns.contains("camera.dolly"));
+        assert!(ns.contains("camera.shake"));
     }
 }
@@ -4,21 +4,49 @@
 //! - Single job rendering with deterministic output
 //! - Batch rendering of multiple presets
 //! - Configuration validation
+//! - Listing available post effects and materials
 
-use anyhow::Result;
 use chrono::Utc;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use std::collections::HashMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
 
+use crate::animated_image::AnimationEncoder;
+use crate::builder::{RenderProgress, RenderedFrame};
+use crate::contact_sheet::{ContactSheet, ContactSheetGrid};
+use crate::error::{Result, VisualiserError};
+use crate::event_files::{apply_event_streams, load_events_file};
+use crate::event_stream::EventStream;
+use crate::frequency_band::{
+    band_config_ids, check_band_resolution, compute_band_energies, parse_band_configs,
+    parse_band_spec, BandConfig, BAND_ENERGY_FEATURE,
+};
+use crate::gpu::headless;
 use crate::gpu::renderer::Renderer;
 use crate::input::{BandSignalMap, InputSignal, SharedSignal, SignalMap};
-use crate::interpretation_package::{apply_to_state, load_package, LoadedPackage};
-use crate::render_job::{BatchJobSpec, RenderJobSpec, RenderMetadata, RenderPhase};
-use crate::video_encode::{check_ffmpeg, encode_video_with_ffmpeg, FfmpegStatus};
-use crate::visualiser::VisualiserState;
+use crate::instant_replay::FrameRingBuffer;
+use crate::interpretation_package::{
+    apply_stem_signals, apply_to_state, load_package, LoadedPackage,
+};
+use crate::loudness::{integrated_loudness, short_term_loudness, LOUDNESS_FEATURE};
+use crate::midi_files::{apply_midi, load_midi_file, LoadedMidi};
+use crate::musical_time::MusicalTimeStructure;
+use crate::render_job::{
+    BatchJobSpec, ManifestEventStream, RenderError, RenderJobSpec, RenderManifest, RenderMetadata,
+    RenderPhase,
+};
+use crate::scripting::SandboxConfig;
+use crate::stem_files::{apply_stems, load_stem_dir, LoadedStem};
+use crate::video_encode::{
+    check_ffmpeg, encode_video_with_options, FfmpegStatus, PixelFormat, VideoCodec,
+    VideoEncodingOptions,
+};
+use crate::visualiser::{LoopRange, VisualiserState};
+
+/// Texture format of rendered frames (saved as sRGB PNGs).
+const FRAME_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
 
 #[derive(Parser)]
 #[command(author, version, about = "Octoseq offline rendering CLI", long_about = None)]
@@ -27,6 +55,52 @@ struct Cli {
     command: Commands,
 }
 
+/// Video encoding flags for `render`.
+#[derive(Args)]
+struct VideoArgs {
+    /// Video codec: h264, h265, vp9 or prores. h264/h265/vp9 need an FFmpeg
+    /// built with libx264/libx265/libvpx; prores uses the built-in encoder
+    #[arg(long, default_value_t = VideoCodec::H264)]
+    codec: VideoCodec,
+
+    /// Constant Rate Factor, lower is better (default: 18, or 31 for vp9).
+    /// Not supported by prores
+    #[arg(long, conflicts_with = "bitrate")]
+    crf: Option<u32>,
+
+    /// Target bitrate instead of CRF, e.g. 8M or 2500k. Not supported by prores
+    #[arg(long)]
+    bitrate: Option<String>,
+
+    /// Pixel format: yuv420p or yuv444p (default: yuv420p, or yuv444p for prores)
+    #[arg(long)]
+    pix_fmt: Option<PixelFormat>,
+
+    /// Encode in two passes for a more accurate --bitrate
+    #[arg(long, requires = "bitrate")]
+    two_pass: bool,
+
+    /// Put the MP4/MOV index at the front of the file for faster web playback
+    #[arg(long)]
+    faststart: bool,
+}
+
+impl VideoArgs {
+    fn options(self) -> VideoEncodingOptions {
+        VideoEncodingOptions {
+            codec: self.codec,
+            pixel_format: self.pix_fmt,
+            crf: self.crf,
+            bitrate: self.bitrate,
+            two_pass: self.two_pass,
+            faststart: self.faststart,
+            ..Default::default()
+        }
+    }
+}
+
+// Parsed once per process, so the size of the render variant doesn't matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     /// Render frames to disk (single job)
@@ -49,7 +123,8 @@ enum Commands {
         #[arg(long, required_unless_present = "package")]
         script: Option<PathBuf>,
 
-        /// Frames per second
+        /// Output frames per second. Independent of the analysis rate: signals
+        /// are sampled at each frame's time
         #[arg(long, default_value_t = 60.0)]
         fps: f32,
 
@@ -77,10 +152,116 @@ enum Commands {
         #[arg(long)]
         output_video: bool,
 
-        /// Video output path (default: {out}/render.mp4)
+        /// Video output path (default: {out}/render.mp4, .webm for vp9, .mov for prores).
+        /// A .gif or .webp path writes an animated image instead, without FFmpeg
+        /// (GIF: at most 50 fps, 256 colours per frame; WebP: lossless, at most 16384 px)
         #[arg(long)]
         video_path: Option<PathBuf>,
 
+        /// Times a GIF/WebP animation plays (0 = loop forever)
+        #[arg(long = "loop", default_value_t = 0)]
+        loop_count: u32,
+
+        /// Write contact_sheet.png, a <cols>x<rows> grid of timestamped
+        /// thumbnails at evenly spaced times, instead of individual frames
+        #[arg(long, conflicts_with = "output_video")]
+        contact_sheet: Option<ContactSheetGrid>,
+
+        /// Write a JSON manifest (script/audio hashes, seed, fps, resolution,
+        /// effect chain, event streams) next to the output
+        #[arg(long)]
+        manifest: bool,
+
+        #[command(flatten)]
+        video: VideoArgs,
+
+        /// Custom frequency bands: a JSON band config file, an inline JSON array
+        /// (e.g. '[{"id":"sub","lo":20,"hi":60}]'), or mel:N for N mel-spaced
+        /// bands from 20 Hz up to 20 kHz or Nyquist. Treats --input as audio (at --sample-rate) and computes
+        /// per-band energy signals from it. Every band must span at least one
+        /// FFT bin at that sample rate
+        #[arg(long, requires = "input")]
+        bands: Option<String>,
+
+        /// Directory of pre-separated stem .wav files (e.g. drums.wav,
+        /// bass.wav). Each file becomes inputs.stems["<name>"] with energy
+        /// and amplitude signals, plus per-band energy with --bands
+        #[arg(long)]
+        stems: Option<PathBuf>,
+
+        /// JSON file of authored event streams: {"kicks": [0.5, 1.0], "drops":
+        /// [{"time": 32.0, "velocity": 0.8, "label": "drop"}]}. Each becomes
+        /// inputs.customEvents["<name>"]
+        #[arg(long)]
+        events: Option<PathBuf>,
+
+        /// Standard MIDI file (.mid). Note-ons become event streams
+        /// inputs.customEvents["midi:ch1"] / ["midi:ch1:36"], and held notes
+        /// velocity signals inputs.midi.ch1.note36 (channels 1-16)
+        #[arg(long)]
+        midi: Option<PathBuf>,
+
+        /// Fixed tempo in BPM. Overrides the package's musical time (and the
+        /// 120 BPM default) for timing.* and beat-based features
+        #[arg(long)]
+        bpm: Option<f32>,
+
+        /// Time of the first downbeat in seconds (requires --bpm)
+        #[arg(long, allow_hyphen_values = true)]
+        beat_offset: Option<f32>,
+
+        /// Time signature, e.g. 3/4 or 6/8 (requires --bpm). Sets beats per
+        /// bar for timing.barPosition
+        #[arg(long)]
+        time_signature: Option<String>,
+
+        /// Loop start in seconds: playback wraps from --loop-end back to here
+        #[arg(long, requires = "loop_end")]
+        loop_start: Option<f32>,
+
+        /// Loop end in seconds (clamped to the track length)
+        #[arg(long, requires = "loop_start")]
+        loop_end: Option<f32>,
+
+        /// Global cap on rendered particle instances per frame, shared
+        /// proportionally across particle systems
+        #[arg(long)]
+        max_particles: Option<usize>,
+
+        /// Script operation limit per update() call. Raise it for heavy
+        /// per-frame loops [default: 100000]
+        #[arg(long)]
+        max_operations: Option<u64>,
+
+        /// Script operation limit for loading and init() [default: 1000000]
+        #[arg(long)]
+        max_init_operations: Option<u64>,
+
+        /// Draw the track waveform with a playhead along the bottom of each frame
+        #[arg(long)]
+        show_waveform: bool,
+
+        /// Draw the script's dbg.showGrid() reference grid (left out of
+        /// renders by default)
+        #[arg(long)]
+        show_grid: bool,
+
+        /// Exposure multiplier applied before tonemapping (multiplies the
+        /// script's scene.exposure)
+        #[arg(long, default_value_t = 1.0)]
+        exposure: f32,
+
+        /// Keep the last N seconds of frames in memory and export them to
+        /// replay_NN.<ext> whenever the script calls dbg.captureReplay().
+        /// Costs width*height*4 bytes per frame (5s of 1080p60 is ~2.5 GB)
+        #[arg(long, conflicts_with = "contact_sheet")]
+        replay_seconds: Option<f32>,
+
+        /// Re-analyse --input and --stems when they change on disk during the
+        /// render (e.g. stems re-separated externally)
+        #[arg(long)]
+        watch_audio: bool,
+
         /// Preset name (for metadata tracking)
         #[arg(long)]
         preset: Option<String>,
@@ -118,6 +299,20 @@ enum Commands {
         /// Path to job/batch config JSON file
         config: PathBuf,
     },
+
+    /// List available post-processing effects and their parameters
+    ListEffects {
+        /// Print as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// List available materials and their parameters
+    ListMaterials {
+        /// Print as JSON instead of a table
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 pub fn run() -> Result<()> {
@@ -137,6 +332,27 @@ pub fn run() -> Result<()> {
             sample_rate,
             output_video,
             video_path,
+            loop_count,
+            contact_sheet,
+            manifest,
+            video,
+            bands,
+            stems,
+            events,
+            midi,
+            bpm,
+            beat_offset,
+            time_signature,
+            loop_start,
+            loop_end,
+            max_particles,
+            max_operations,
+            max_init_operations,
+            show_waveform,
+            show_grid,
+            exposure,
+            replay_seconds,
+            watch_audio,
             preset,
             no_metadata,
             quiet,
@@ -155,6 +371,27 @@ pub fn run() -> Result<()> {
                 preset_name: preset,
                 output_video,
                 video_path,
+                video_options: video.options(),
+                loop_count,
+                contact_sheet,
+                manifest,
+                bands,
+                stems,
+                events,
+                midi,
+                bpm,
+                beat_offset,
+                time_signature,
+                loop_start,
+                loop_end,
+                max_particles,
+                max_operations,
+                max_init_operations,
+                show_waveform,
+                show_grid,
+                exposure,
+                replay_seconds,
+                watch_audio,
             };
 
             pollster::block_on(execute_render_job(&job, !no_metadata, quiet))?;
@@ -165,11 +402,17 @@ pub fn run() -> Result<()> {
             continue_on_error,
             quiet,
         } => {
-            pollster::block_on(run_batch(&config, out, continue_on_error, quiet))?;
+            run_batch(&config, out, continue_on_error, quiet)?;
         }
         Commands::Validate { config } => {
             validate_config(&config)?;
         }
+        Commands::ListEffects { json } => {
+            print!("{}", format_effects(json));
+        }
+        Commands::ListMaterials { json } => {
+            print!("{}", format_materials(json));
+        }
     }
     Ok(())
 }
@@ -184,133 +427,13 @@ pub async fn execute_render_job(
     quiet: bool,
 ) -> Result<()> {
     let start_time = Utc::now();
-    let render_start = std::time::Instant::now();
     let mut warnings: Vec<String> = Vec::new();
 
     // Validate job
     job.validate()
-        .map_err(|e| anyhow::anyhow!("[{}] {}", RenderPhase::Initialization, e))?;
-
-    // Load interpretation package, if given. It supplies the script (unless an
-    // explicit --script overrides it), the default duration, and all signal
-    // maps / event streams / state configuration.
-    let package: Option<LoadedPackage> = match &job.package_path {
-        Some(package_path) => {
-            let json = std::fs::read_to_string(package_path).map_err(|e| {
-                anyhow::anyhow!(
-                    "[{}] Failed to read package {:?}: {}",
-                    RenderPhase::InputLoading,
-                    package_path,
-                    e
-                )
-            })?;
-            let pkg = load_package(&json).map_err(|e| {
-                anyhow::anyhow!(
-                    "[{}] Failed to load package {:?}: {:#}",
-                    RenderPhase::InputLoading,
-                    package_path,
-                    e
-                )
-            })?;
-            Some(pkg)
-        }
-        None => None,
-    };
+        .map_err(|e| e.with_phase(RenderPhase::Initialization))?;
 
-    // Load script: an explicit --script wins; otherwise the package supplies it.
-    let script_content = if let Some(script_path) = &job.script_path {
-        let mut script_file = File::open(script_path).map_err(|e| {
-            anyhow::anyhow!(
-                "[{}] Failed to open script {:?}: {}",
-                RenderPhase::ScriptLoading,
-                script_path,
-                e
-            )
-        })?;
-        let mut content = String::new();
-        script_file.read_to_string(&mut content).map_err(|e| {
-            anyhow::anyhow!(
-                "[{}] Failed to read script: {}",
-                RenderPhase::ScriptLoading,
-                e
-            )
-        })?;
-        content
-    } else if let Some(script) = package.as_ref().and_then(|pkg| pkg.script.clone()) {
-        script
-    } else {
-        return Err(anyhow::anyhow!(
-            "[{}] No script: the package does not embed one; pass --script",
-            RenderPhase::ScriptLoading
-        ));
-    };
-
-    // Load input signal (legacy single-signal path only)
-    let legacy_signal: Option<SharedSignal> = match &job.input_path {
-        Some(input_path) => {
-            let mut file = File::open(input_path).map_err(|e| {
-                anyhow::anyhow!(
-                    "[{}] Failed to open input {:?}: {}",
-                    RenderPhase::InputLoading,
-                    input_path,
-                    e
-                )
-            })?;
-            let mut contents = String::new();
-            file.read_to_string(&mut contents).map_err(|e| {
-                anyhow::anyhow!(
-                    "[{}] Failed to read input: {}",
-                    RenderPhase::InputLoading,
-                    e
-                )
-            })?;
-
-            let samples: Vec<f32> = serde_json::from_str(&contents)
-                .or_else(|_| {
-                    contents
-                        .split_whitespace()
-                        .map(|s| s.parse::<f32>())
-                        .collect::<Result<Vec<_>, _>>()
-                })
-                .map_err(|_| {
-                    anyhow::anyhow!(
-                        "[{}] Failed to parse input file as JSON list of floats or whitespace separated floats",
-                        RenderPhase::InputLoading
-                    )
-                })?;
-
-            Some(std::rc::Rc::new(InputSignal::new(
-                samples,
-                job.input_sample_rate,
-            )))
-        }
-        None => None,
-    };
-
-    // Calculate frame count: explicit --duration wins, then the package's
-    // durationSec, then the legacy input signal duration.
-    let render_duration = job
-        .duration
-        .or_else(|| package.as_ref().map(|pkg| pkg.duration_sec))
-        .or_else(|| legacy_signal.as_ref().map(|sig| sig.get_duration()))
-        .ok_or_else(|| {
-            anyhow::anyhow!(
-                "[{}] No duration available from --duration, package, or input signal",
-                RenderPhase::Initialization
-            )
-        })?;
-    let total_frames = (render_duration * job.fps).ceil() as usize;
-    let dt = 1.0 / job.fps;
-
-    // Create output directory
-    std::fs::create_dir_all(&job.output_dir).map_err(|e| {
-        anyhow::anyhow!(
-            "[{}] Failed to create output directory {:?}: {}",
-            RenderPhase::Initialization,
-            job.output_dir,
-            e
-        )
-    })?;
+    let mut prepared = PreparedRender::load(job, RenderSources::default(), &mut warnings)?;
 
     // WGPU Init
     let instance = wgpu::Instance::new(wgpu::InstanceDescriptor::default());
@@ -321,7 +444,7 @@ pub async fn execute_render_job(
             force_fallback_adapter: false,
         })
         .await
-        .ok_or_else(|| anyhow::anyhow!("[{}] No GPU adapter found", RenderPhase::GpuSetup))?;
+        .ok_or_else(|| RenderError::new(RenderPhase::GpuSetup, "No GPU adapter found"))?;
 
     let adapter_info = adapter.get_info();
     let gpu_adapter_str = format!("{} ({:?})", adapter_info.name, adapter_info.backend);
@@ -330,215 +453,541 @@ pub async fn execute_render_job(
         .request_device(&wgpu::DeviceDescriptor::default(), None)
         .await
         .map_err(|e| {
-            anyhow::anyhow!("[{}] Failed to create device: {}", RenderPhase::GpuSetup, e)
+            RenderError::new(
+                RenderPhase::GpuSetup,
+                format!("Failed to create device: {}", e),
+            )
         })?;
 
-    let texture_desc = wgpu::TextureDescriptor {
-        label: Some("Target Texture"),
-        size: wgpu::Extent3d {
-            width: job.width,
-            height: job.height,
-            depth_or_array_layers: 1,
+    let outcome = render_frames(
+        job,
+        &mut prepared,
+        device,
+        queue,
+        FrameSinks {
+            write_files: true,
+            quiet,
+            ..FrameSinks::default()
         },
-        mip_level_count: 1,
-        sample_count: 1,
-        dimension: wgpu::TextureDimension::D2,
-        format: wgpu::TextureFormat::Rgba8UnormSrgb,
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
-        view_formats: &[],
-    };
+        &mut warnings,
+    )?;
 
-    let texture = device.create_texture(&texture_desc);
-    let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    // Save metadata
+    if save_metadata {
+        let end_time = Utc::now();
+        let (script_hash, input_hash) = render_hashes(job, &prepared.script);
 
-    // Buffer for reading back data
-    let u32_size = std::mem::size_of::<u32>() as u32;
-    let unpadded_bytes_per_row = u32_size * job.width;
-    let align = 256;
-    let padded_bytes_per_row_padding = (align - unpadded_bytes_per_row % align) % align;
-    let padded_bytes_per_row = unpadded_bytes_per_row + padded_bytes_per_row_padding;
-
-    let output_buffer_size = (padded_bytes_per_row * job.height) as wgpu::BufferAddress;
-    let output_buffer_desc = wgpu::BufferDescriptor {
-        label: Some("Output Buffer"),
-        size: output_buffer_size,
-        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
-        mapped_at_creation: false,
-    };
-    let output_buffer = device.create_buffer(&output_buffer_desc);
+        let metadata = RenderMetadata {
+            job: job.clone(),
+            started_at: start_time,
+            completed_at: end_time,
+            render_duration_secs: outcome.render_duration_secs,
+            frame_count: outcome.frame_count,
+            average_render_fps: outcome.frame_count as f64 / outcome.render_duration_secs,
+            script_hash,
+            input_hash,
+            octoseq_version: env!("CARGO_PKG_VERSION").to_string(),
+            gpu_adapter: gpu_adapter_str,
+            video_path: outcome.video_path,
+            warnings,
+        };
 
-    // Create renderer and state
-    let mut renderer = Renderer::new(device, queue, texture_desc.format, job.width, job.height);
-    let mut state = VisualiserState::new();
+        let metadata_path = job.output_dir.join("metadata.json");
+        metadata
+            .save(&metadata_path)
+            .map_err(|e| RenderError::new(RenderPhase::MetadataSave, e.to_string()))?;
 
-    // Set global seed for deterministic particle systems
-    if job.seed != 0 {
-        state.set_global_seed(job.seed);
-    }
-
-    // Apply package inputs that live on the state (stem signals, event streams,
-    // available stems, and the script namespace configuration), mirroring the
-    // wasm push layer. Must happen before load_script.
-    if let Some(pkg) = package.as_ref() {
-        apply_to_state(pkg, &mut state);
-    }
-
-    // Load script
-    if !state.load_script(&script_content) {
-        let error_msg = state
-            .get_script_error()
-            .unwrap_or("Unknown script error")
-            .to_string();
-        return Err(anyhow::anyhow!(
-            "[{}] {}",
-            RenderPhase::ScriptLoading,
-            error_msg
-        ));
+        if !quiet {
+            println!("  Metadata saved to {:?}", metadata_path);
+        }
     }
 
     if !quiet {
-        println!(
-            "Rendering {} frames at {}x{} @ {} fps...",
-            total_frames, job.width, job.height, job.fps
-        );
+        println!("Done.");
+    }
+
+    Ok(())
+}
+
+/// Render inputs held in memory rather than read from the job's paths.
+#[derive(Clone, Default)]
+pub struct RenderSources {
+    /// Script source. Wins over `--script` and the package's script.
+    pub script: Option<String>,
+    /// Input signal, in place of `--input`.
+    pub audio: Option<SharedSignal>,
+}
+
+/// A render's inputs and its state with the script loaded: everything set
+/// up before the first frame.
+///
+/// Shared by [`execute_render_job`] and the embedding API
+/// (`VisualiserBuilder`), which render it with [`render_frames`] or step it
+/// themselves with [`PreparedRender::update`].
+pub struct PreparedRender {
+    pub state: VisualiserState,
+    pub package: Option<LoadedPackage>,
+    pub audio: AudioAnalysis,
+    /// Authored event streams from `--events`.
+    pub event_streams: Vec<(String, EventStream)>,
+    /// Notes and velocity signals from `--midi`.
+    pub midi: Option<LoadedMidi>,
+    pub script: String,
+    /// Render length: `--duration`, then the package's, then the input signal's.
+    pub duration: Option<f32>,
+    /// Constant musical time from `--bpm`, replacing the package's.
+    tempo_override: Option<MusicalTimeStructure>,
+    rotation_signal: Option<SharedSignal>,
+}
+
+impl PreparedRender {
+    /// Load the job's inputs and script and set up the state. Call after
+    /// validating the job. Warnings about the inputs are added to `warnings`.
+    pub fn load(
+        job: &RenderJobSpec,
+        sources: RenderSources,
+        warnings: &mut Vec<String>,
+    ) -> Result<Self> {
+        // Load interpretation package, if given. It supplies the script (unless an
+        // explicit --script overrides it), the default duration, and all signal
+        // maps / event streams / state configuration.
+        let package: Option<LoadedPackage> = match &job.package_path {
+            Some(package_path) => {
+                let json = std::fs::read_to_string(package_path).map_err(|e| {
+                    RenderError::new(
+                        RenderPhase::InputLoading,
+                        format!("Failed to read package {:?}: {}", package_path, e),
+                    )
+                })?;
+                let pkg = load_package(&json).map_err(|e| {
+                    RenderError::new(
+                        RenderPhase::InputLoading,
+                        format!("Failed to load package {:?}: {:#}", package_path, e),
+                    )
+                })?;
+                Some(pkg)
+            }
+            None => None,
+        };
+
+        // Load script: in-memory source, then an explicit --script, then the package.
+        let script = if let Some(script) = sources.script {
+            script
+        } else if let Some(script_path) = &job.script_path {
+            let mut script_file = File::open(script_path).map_err(|e| {
+                RenderError::new(
+                    RenderPhase::ScriptLoading,
+                    format!("Failed to open script {:?}: {}", script_path, e),
+                )
+            })?;
+            let mut content = String::new();
+            script_file.read_to_string(&mut content).map_err(|e| {
+                RenderError::new(
+                    RenderPhase::ScriptLoading,
+                    format!("Failed to read script: {}", e),
+                )
+            })?;
+            content
+        } else if let Some(script) = package.as_ref().and_then(|pkg| pkg.script.clone()) {
+            script
+        } else {
+            return Err(RenderError::new(
+                RenderPhase::ScriptLoading,
+                "No script: the package does not embed one; pass --script",
+            )
+            .into());
+        };
+
+        // Audio analysis: the --input signal, its loudness, --bands and --stems
+        let audio = match sources.audio {
+            Some(signal) => AudioAnalysis::analyse(job, Some(signal))?,
+            None => AudioAnalysis::load(job)?,
+        };
+
+        // Authored event streams from --events.
+        let event_streams = match &job.events {
+            Some(path) => load_events_file(path)
+                .map_err(|e| RenderError::new(RenderPhase::InputLoading, e))?,
+            None => Vec::new(),
+        };
+
+        // MIDI note events and velocity signals from --midi.
+        let midi = match &job.midi {
+            Some(path) => Some(
+                load_midi_file(path).map_err(|e| RenderError::new(RenderPhase::InputLoading, e))?,
+            ),
+            None => None,
+        };
+
+        // Render length: explicit --duration wins, then the package's
+        // durationSec, then the legacy input signal duration.
+        let track_duration = package
+            .as_ref()
+            .map(|pkg| pkg.duration_sec)
+            .or_else(|| audio.audio.as_ref().map(|sig| sig.get_duration()));
+        let duration = job.duration.or(track_duration);
+
+        let mut state = VisualiserState::new();
+
+        // Set global seed for deterministic particle systems
         if job.seed != 0 {
-            println!("  Seed: {}", job.seed);
+            state.set_global_seed(job.seed);
+        }
+        if job.max_operations.is_some() || job.max_init_operations.is_some() {
+            let defaults = SandboxConfig::default();
+            state.set_sandbox_config(SandboxConfig {
+                max_operations: job.max_operations.unwrap_or(defaults.max_operations),
+                max_init_operations: job
+                    .max_init_operations
+                    .unwrap_or(defaults.max_init_operations),
+                ..defaults
+            });
         }
+
+        // Apply package inputs that live on the state (stem signals, event streams,
+        // available stems, and the script namespace configuration), mirroring the
+        // wasm push layer. Must happen before load_script.
         if let Some(pkg) = package.as_ref() {
-            println!(
-                "  Package: {} signals, {} band keys, {} custom, {} composed, {} stem signals, {}/{}/{} event streams (named/authored/band){}",
-                pkg.named_signals.len(),
-                pkg.band_signals.len(),
-                pkg.custom_signals.len(),
-                pkg.composed_signals.len(),
-                pkg.stem_signals.len(),
-                pkg.event_streams.len(),
-                pkg.authored_event_streams.len(),
-                pkg.band_event_streams.len(),
-                if pkg.musical_time.is_some() { ", musical time" } else { "" },
-            );
+            apply_to_state(pkg, &mut state);
+        }
+        // After the package, which resets authored streams.
+        apply_event_streams(&event_streams);
+        if let Some(midi) = midi.as_ref() {
+            apply_midi(midi, &mut state);
+        }
+        audio.apply(&mut state, package.as_ref());
+
+        // Load script
+        state
+            .try_load_script(&script)
+            .map_err(|e| e.with_phase(RenderPhase::ScriptLoading))?;
+
+        state.set_particle_budget(job.max_particles);
+
+        // Rotation/amplitude signal:
+        // - Legacy path: the single --input signal (unchanged behavior).
+        // - Package path: the package's "amplitude" named signal, if present. In the
+        //   browser, rotation_signal is only set by the legacy push_rotation_data /
+        //   push_data calls (which VisualiserPanel no longer makes); "amplitude"
+        //   arrives as a named signal instead and takes precedence inside
+        //   VisualiserState::update. Passing it here too keeps parity with the
+        //   legacy CLI path while sampling identically to the browser.
+        let rotation_signal: Option<SharedSignal> = match package.as_ref() {
+            Some(pkg) => pkg.rotation_signal(),
+            None => audio.audio.clone(),
+        };
+
+        // The waveform overlay draws the same signal: the closest thing a
+        // package has to the raw track.
+        if job.show_waveform && rotation_signal.is_none() {
+            warnings.push("--show-waveform: no track audio to draw".to_string());
+        }
+        state.set_waveform_audio(rotation_signal.clone());
+        state.set_always_show_waveform(job.show_waveform);
+        state.set_reference_grid_allowed(job.show_grid);
+        state.set_exposure(job.exposure);
+
+        // Resolve relative sprite and mesh paths against the script's directory
+        let script_dir = job
+            .script_path
+            .as_ref()
+            .and_then(|path| path.parent())
+            .map(|dir| dir.to_path_buf());
+        state.sprite_registry.set_search_dir(script_dir.clone());
+        state.asset_registry.set_search_dir(script_dir);
+        // Offline renders wait for assets rather than dropping early frames
+        state.asset_registry.set_blocking_loads(true);
+
+        // Loop mode: start at the loop start and wrap at the loop end. Seeking
+        // (rather than setting the time) gives the first pass the same warm-up
+        // as every later one.
+        if let (Some(start), Some(end)) = (job.loop_start, job.loop_end) {
+            let range = LoopRange::new(start, end, track_duration)
+                .map_err(|e| RenderError::new(RenderPhase::Initialization, e.to_string()))?;
+            state.seek(range.start);
+            state.set_loop(Some(range));
         }
-        println!("  Output: {:?}", job.output_dir);
+
+        // --bpm / --beat-offset / --time-signature replace any package musical time.
+        let tempo_override = duration.and_then(|duration| job.tempo_override(duration));
+
+        Ok(Self {
+            state,
+            package,
+            audio,
+            event_streams,
+            midi,
+            script,
+            duration,
+            tempo_override,
+            rotation_signal,
+        })
     }
 
-    // Per-frame signal inputs: from the package when given, otherwise empty
-    // (the legacy path feeds everything through rotation_signal).
-    let empty_signals: SignalMap = HashMap::new();
-    let empty_band_signals: BandSignalMap = HashMap::new();
-    let empty_custom_signals: SignalMap = HashMap::new();
-    let (named_signals, band_signals, custom_signals, musical_time) = match package.as_ref() {
-        Some(pkg) => (
-            &pkg.named_signals,
-            &pkg.band_signals,
-            &pkg.custom_signals,
-            pkg.musical_time.as_ref(),
-        ),
-        None => (
-            &empty_signals,
-            &empty_band_signals,
-            &empty_custom_signals,
-            None,
-        ),
-    };
+    /// Re-run the audio analysis after `--input` or `--stems` changed on
+    /// disk, reloading the script with the new signals (see [`reanalyse_audio`]).
+    pub fn reanalyse(&mut self, job: &RenderJobSpec) -> Result<()> {
+        self.audio = reanalyse_audio(job, &mut self.state, self.package.as_ref(), &self.script)?;
+        if self.package.is_none() {
+            self.rotation_signal = self.audio.audio.clone();
+            self.state.set_waveform_audio(self.rotation_signal.clone());
+        }
+        Ok(())
+    }
 
-    // Rotation/amplitude signal:
-    // - Legacy path: the single --input signal (unchanged behavior).
-    // - Package path: the package's "amplitude" named signal, if present. In the
-    //   browser, rotation_signal is only set by the legacy push_rotation_data /
-    //   push_data calls (which VisualiserPanel no longer makes); "amplitude"
-    //   arrives as a named signal instead and takes precedence inside
-    //   VisualiserState::update. Passing it here too keeps parity with the
-    //   legacy CLI path while sampling identically to the browser.
-    let rotation_signal: Option<SharedSignal> = match package.as_ref() {
-        Some(pkg) => pkg.rotation_signal(),
-        None => legacy_signal.clone(),
-    };
+    /// Advance the state by `dt` seconds, sampling the inputs at the new time.
+    pub fn update(&mut self, dt: f32) {
+        // Per-frame signal inputs: from the package when given, otherwise the
+        // input audio's loudness and band energies (the legacy path feeds
+        // everything else through rotation_signal).
+        let empty_band_signals: BandSignalMap = HashMap::new();
+        let empty_custom_signals: SignalMap = HashMap::new();
+        let (named_signals, band_signals, custom_signals, musical_time) = match &self.package {
+            Some(pkg) => (
+                &pkg.named_signals,
+                &pkg.band_signals,
+                &pkg.custom_signals,
+                pkg.musical_time.as_ref(),
+            ),
+            None => (
+                &self.audio.signals,
+                self.audio
+                    .bands
+                    .as_ref()
+                    .map(|(_, signals)| signals)
+                    .unwrap_or(&empty_band_signals),
+                &empty_custom_signals,
+                None,
+            ),
+        };
+        let musical_time = self.tempo_override.as_ref().or(musical_time);
 
-    // Render frames
-    for i in 0..total_frames {
-        state.update(
+        self.state.update(
             dt,
-            rotation_signal.as_ref(),
+            self.rotation_signal.as_ref(),
             None,
             named_signals,
             band_signals,
             custom_signals,
             musical_time,
         );
+    }
+}
 
-        // Render to texture
-        renderer.render(&texture_view, &state);
-
-        // Copy texture to buffer
-        let mut encoder = renderer
-            .device()
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-
-        encoder.copy_texture_to_buffer(
-            wgpu::ImageCopyTexture {
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            wgpu::ImageCopyBuffer {
-                buffer: &output_buffer,
-                layout: wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: Some(padded_bytes_per_row),
-                    rows_per_image: Some(job.height),
-                },
-            },
-            texture_desc.size,
-        );
+/// Where [`render_frames`] sends frames besides the job's own outputs.
+#[derive(Default)]
+pub struct FrameSinks<'a> {
+    /// Write to the job's output directory: PNG frames (or the contact
+    /// sheet), FFmpeg video, the manifest and replays. Off when the
+    /// embedding API has no output directory.
+    pub write_files: bool,
+    /// Don't print progress to stdout.
+    pub quiet: bool,
+    /// Called with the pixels of every rendered frame.
+    pub on_frame: Option<&'a mut dyn FnMut(&RenderedFrame)>,
+    /// Called after every frame, including frames a contact sheet skips.
+    pub on_progress: Option<&'a mut dyn FnMut(&RenderProgress)>,
+}
+
+/// Result of [`render_frames`].
+#[derive(Debug, Clone)]
+pub struct RenderOutcome {
+    pub frame_count: usize,
+    pub render_duration_secs: f64,
+    /// The encoded video or animated image, if one was written.
+    pub video_path: Option<PathBuf>,
+}
+
+/// Render every frame of a prepared job with `device` and write the job's
+/// outputs: frames or contact sheet, video or animated image, replays and
+/// the manifest. Warnings about skipped outputs are added to `warnings`.
+pub fn render_frames(
+    job: &RenderJobSpec,
+    prepared: &mut PreparedRender,
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    mut sinks: FrameSinks,
+    warnings: &mut Vec<String>,
+) -> Result<RenderOutcome> {
+    let quiet = sinks.quiet;
+    let render_start = std::time::Instant::now();
+
+    let render_duration = prepared.duration.ok_or_else(|| {
+        RenderError::new(
+            RenderPhase::Initialization,
+            "No duration available from --duration, package, or input signal",
+        )
+    })?;
+    let total_frames = job.frame_count(render_duration);
+    let dt = 1.0 / job.fps;
+
+    // Contact sheets draw only the sampled frames, at thumbnail size
+    let contact_sheet_frames = match job.contact_sheet {
+        Some(grid) => Some(
+            grid.sample_frames(total_frames)
+                .map_err(|e| RenderError::new(RenderPhase::Initialization, e))?,
+        ),
+        None => None,
+    };
+    let (frame_width, frame_height) = match job.contact_sheet {
+        Some(grid) => grid.tile_size(job.width, job.height),
+        None => (job.width, job.height),
+    };
+    let mut contact_sheet = job
+        .contact_sheet
+        .map(|grid| ContactSheet::new(grid, frame_width, frame_height));
 
-        renderer.queue().submit(Some(encoder.finish()));
-
-        // Map buffer and save
-        let buffer_slice = output_buffer.slice(..);
-        let (tx, rx) = std::sync::mpsc::channel();
-        buffer_slice.map_async(wgpu::MapMode::Read, move |v| tx.send(v).unwrap());
-        renderer.device().poll(wgpu::Maintain::Wait);
-        rx.recv().unwrap().map_err(|e| {
-            anyhow::anyhow!(
-                "[{}] Buffer mapping failed: {:?}",
-                RenderPhase::FrameSave,
-                e
+    // Create output directory
+    if sinks.write_files {
+        std::fs::create_dir_all(&job.output_dir).map_err(|e| {
+            RenderError::new(
+                RenderPhase::Initialization,
+                format!(
+                    "Failed to create output directory {:?}: {}",
+                    job.output_dir, e
+                ),
             )
         })?;
+    }
 
-        let data = buffer_slice.get_mapped_range();
+    let texture = headless::create_target_texture(&device, FRAME_FORMAT, frame_width, frame_height);
+    let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let mut renderer = Renderer::new(device, queue, FRAME_FORMAT, frame_width, frame_height);
 
-        // Unpad data
-        let mut unpadded_data = Vec::with_capacity((job.width * job.height * 4) as usize);
-        for row in 0..job.height {
-            let start = (row * padded_bytes_per_row) as usize;
-            let end = start + (job.width * 4) as usize;
-            unpadded_data.extend_from_slice(&data[start..end]);
-        }
+    let mut replay = job
+        .replay_seconds
+        .map(|seconds| FrameRingBuffer::for_duration(seconds, job.fps, job.width, job.height));
+    let mut replay_count = 0;
 
-        // Save frame
-        let frame_path = job.output_dir.join(format!("frame_{:05}.png", i));
-        image::save_buffer(
-            &frame_path,
-            &unpadded_data,
-            job.width,
-            job.height,
-            image::ColorType::Rgba8,
-        )
-        .map_err(|e| {
-            anyhow::anyhow!(
-                "[{}] Failed to save frame {}: {}",
-                RenderPhase::FrameSave,
-                i,
-                e
+    if !quiet {
+        print_render_summary(job, prepared, total_frames, replay.as_ref());
+    }
+
+    // GIF/WebP output is encoded from the frames as they are read back
+    let mut animation = match job.animation_format().filter(|_| job.output_video) {
+        Some(format) => Some(
+            AnimationEncoder::new(
+                format,
+                &job.effective_video_path(),
+                job.width,
+                job.height,
+                job.fps,
+                job.loop_count,
             )
-        })?;
+            .map_err(|e| RenderError::new(RenderPhase::VideoEncode, e))?,
+        ),
+        None => None,
+    };
+
+    // Render frames
+    // --watch-audio: polled once per frame, so a change is picked up once
+    // the files look the same on two frames in a row
+    let mut audio_watch = job.watch_audio.then(|| AudioInputWatch::new(job));
+
+    let first_frame_start = prepared.state.time;
+    for i in 0..total_frames {
+        if audio_watch.as_mut().is_some_and(AudioInputWatch::poll) {
+            prepared.reanalyse(job)?;
+            if !quiet {
+                println!("\r  Audio changed on disk: re-analysed at frame {}", i);
+            }
+        }
+        // Outside loop mode, place each frame at its exact time: summing dt
+        // in f32 drifts audibly out of sync over long renders
+        if job.loop_start.is_none() {
+            prepared
+                .state
+                .set_time(job.frame_time(first_frame_start, i) - dt);
+        }
+        prepared.update(dt);
+        let state = &mut prepared.state;
+
+        let tile_index = contact_sheet_frames
+            .as_ref()
+            .and_then(|frames| frames.binary_search(&i).ok());
+
+        if contact_sheet_frames.is_none() || tile_index.is_some() {
+            // Render to texture and read it back
+            renderer.render(&texture_view, state);
+            let pixels =
+                headless::read_texture_rgba8(renderer.device(), renderer.queue(), &texture);
+
+            if let (Some(sheet), Some(tile_index)) = (contact_sheet.as_mut(), tile_index) {
+                sheet
+                    .add_tile(tile_index, &pixels, state.time)
+                    .map_err(|e| RenderError::new(RenderPhase::FrameSave, e))?;
+            } else if sinks.write_files {
+                // Save frame
+                let frame_path = job.output_dir.join(format!("frame_{:05}.png", i));
+                image::save_buffer(
+                    &frame_path,
+                    &pixels,
+                    frame_width,
+                    frame_height,
+                    image::ColorType::Rgba8,
+                )
+                .map_err(|e| {
+                    RenderError::new(
+                        RenderPhase::FrameSave,
+                        format!("Failed to save frame {}: {}", i, e),
+                    )
+                })?;
+            }
+
+            if let Some(encoder) = animation.as_mut() {
+                encoder.add_frame(&pixels).map_err(|e| {
+                    RenderError::new(
+                        RenderPhase::VideoEncode,
+                        format!("Failed to encode frame {}: {}", i, e),
+                    )
+                })?;
+            }
+
+            // Instant replay: buffer the frame, export when the script asks
+            if let Some(replay) = replay.as_mut() {
+                replay.push(&pixels);
+                if state.take_replay_request() {
+                    let (frames_dir, video_path) = job.replay_paths(replay_count);
+                    replay_count += 1;
+                    match replay.export(&frames_dir, &video_path, job.fps, &job.video_options) {
+                        Ok(frames) => {
+                            if !quiet {
+                                println!(
+                                    "\r  Replay: {} frames ending at frame {} saved to {:?}",
+                                    frames, i, video_path
+                                );
+                            }
+                        }
+                        Err(e) => {
+                            let warning = format!("Replay export at frame {} failed: {}", i, e);
+                            if !quiet {
+                                eprintln!("\r  Warning: {}", warning);
+                            }
+                            warnings.push(warning);
+                        }
+                    }
+                }
+            }
 
-        drop(data);
-        output_buffer.unmap();
+            if let Some(on_frame) = sinks.on_frame.as_mut() {
+                on_frame(&RenderedFrame {
+                    index: i,
+                    time: state.time,
+                    width: frame_width,
+                    height: frame_height,
+                    pixels: &pixels,
+                });
+            }
+        }
 
         // Progress reporting
+        if let Some(on_progress) = sinks.on_progress.as_mut() {
+            on_progress(&RenderProgress {
+                frame: i,
+                total_frames,
+                elapsed_secs: render_start.elapsed().as_secs_f64(),
+            });
+        }
         if !quiet && i % 60 == 0 {
             let elapsed = render_start.elapsed().as_secs_f64();
             let fps_actual = (i + 1) as f64 / elapsed;
@@ -560,6 +1009,16 @@ pub async fn execute_render_job(
         }
     }
 
+    if let Some(sheet) = &contact_sheet {
+        let sheet_path = job.output_dir.join("contact_sheet.png");
+        sheet
+            .save(&sheet_path)
+            .map_err(|e| RenderError::new(RenderPhase::FrameSave, e))?;
+        if !quiet {
+            println!("\r  Contact sheet saved to {:?}", sheet_path);
+        }
+    }
+
     let render_duration_secs = render_start.elapsed().as_secs_f64();
 
     if !quiet {
@@ -571,200 +1030,653 @@ pub async fn execute_render_job(
         );
     }
 
-    // Video encoding
-    let video_path = if job.output_video {
-        let video_out = job.effective_video_path();
+    let video_path = match animation {
+        Some(encoder) => {
+            let video_out = job.effective_video_path();
+            let frame_count = encoder
+                .finish()
+                .map_err(|e| RenderError::new(RenderPhase::VideoEncode, e))?;
+            if !quiet {
+                println!(
+                    "  Animation saved to {:?} ({} frames)",
+                    video_out, frame_count
+                );
+            }
+            Some(video_out)
+        }
+        None if job.output_video => encode_frames(job, quiet, warnings),
+        None => None,
+    };
 
-        match check_ffmpeg() {
-            FfmpegStatus::Available(version) => {
-                if !quiet {
-                    println!(
-                        "Encoding video with {}...",
-                        version.split('\n').next().unwrap_or("FFmpeg")
-                    );
-                }
-                match encode_video_with_ffmpeg(&job.output_dir, &video_out, job.fps) {
-                    Ok(()) => {
-                        if !quiet {
-                            println!("  Video saved to {:?}", video_out);
-                        }
-                        Some(video_out)
-                    }
-                    Err(e) => {
-                        let warning = format!("Video encoding failed: {}", e);
-                        if !quiet {
-                            eprintln!("  Warning: {}", warning);
-                        }
-                        warnings.push(warning);
-                        None
-                    }
-                }
+    if job.manifest {
+        save_manifest(job, prepared, quiet)?;
+    }
+
+    Ok(RenderOutcome {
+        frame_count: total_frames,
+        render_duration_secs,
+        video_path,
+    })
+}
+
+/// Print what a render is about to draw, and from which inputs.
+fn print_render_summary(
+    job: &RenderJobSpec,
+    prepared: &PreparedRender,
+    total_frames: usize,
+    replay: Option<&FrameRingBuffer>,
+) {
+    let audio = &prepared.audio;
+    println!(
+        "Rendering {} frames at {}x{} @ {} fps...",
+        total_frames, job.width, job.height, job.fps
+    );
+    if job.seed != 0 {
+        println!("  Seed: {}", job.seed);
+    }
+    if let Some(pkg) = prepared.package.as_ref() {
+        println!(
+            "  Package: {} signals, {} band keys, {} custom, {} composed, {} stem signals, {}/{}/{} event streams (named/authored/band){}",
+            pkg.named_signals.len(),
+            pkg.band_signals.len(),
+            pkg.custom_signals.len(),
+            pkg.composed_signals.len(),
+            pkg.stem_signals.len(),
+            pkg.event_streams.len(),
+            pkg.authored_event_streams.len(),
+            pkg.band_event_streams.len(),
+            if pkg.musical_time.is_some() { ", musical time" } else { "" },
+        );
+    }
+    if let Some(signal) = audio.audio.as_ref().filter(|_| !audio.signals.is_empty()) {
+        println!(
+            "  Loudness: {:.1} LUFS integrated",
+            integrated_loudness(signal.samples(), signal.sample_rate())
+        );
+    }
+    if let Some((bands, _)) = audio.bands.as_ref() {
+        println!("  Bands: {} custom", bands.len());
+    }
+    if let Some(dir) = job.stems.as_ref() {
+        println!("  Stems: {} from {:?}", audio.stems.len(), dir);
+    }
+    if let Some(path) = job.events.as_ref() {
+        println!(
+            "  Events: {} streams from {:?}",
+            prepared.event_streams.len(),
+            path
+        );
+    }
+    if let (Some(midi), Some(path)) = (prepared.midi.as_ref(), job.midi.as_ref()) {
+        println!(
+            "  MIDI: {} notes ({} distinct) over {:.1}s from {:?}",
+            midi.notes.len(),
+            midi.played_notes().len(),
+            midi.duration,
+            path
+        );
+    }
+    if let Some(replay) = replay {
+        println!(
+            "  Instant replay: last {} frames ({:.0} MB)",
+            replay.capacity(),
+            replay.max_memory_bytes() as f64 / (1024.0 * 1024.0)
+        );
+    }
+    if let Some(bpm) = job.bpm {
+        println!(
+            "  Tempo: {} BPM, first beat {:.3}s, {}",
+            bpm,
+            job.beat_offset.unwrap_or(0.0),
+            job.time_signature.as_deref().unwrap_or("4/4")
+        );
+    }
+    if let Some(range) = prepared.state.loop_range() {
+        println!("  Loop: {:.3}s - {:.3}s", range.start, range.end);
+    }
+    if let Some(budget) = prepared.state.particle_budget() {
+        println!("  Max particles: {}", budget);
+    }
+    println!("  Output: {:?}", job.output_dir);
+}
+
+/// Encode the saved PNG frames with FFmpeg. Returns the video path, or None
+/// (with a warning) when FFmpeg is missing or fails.
+fn encode_frames(job: &RenderJobSpec, quiet: bool, warnings: &mut Vec<String>) -> Option<PathBuf> {
+    let video_out = job.effective_video_path();
+
+    match check_ffmpeg() {
+        FfmpegStatus::Available(version) => {
+            if !quiet {
+                println!(
+                    "Encoding video with {}...",
+                    version.split('\n').next().unwrap_or("FFmpeg")
+                );
             }
-            FfmpegStatus::NotFound => {
-                let warning = "FFmpeg not found. Video encoding skipped. Install FFmpeg to enable video output.".to_string();
-                if !quiet {
-                    eprintln!("  Warning: {}", warning);
-                }
-                warnings.push(warning);
-                None
+        }
+        FfmpegStatus::NotFound => {
+            let warning =
+                "FFmpeg not found. Video encoding skipped. Install FFmpeg to enable video output."
+                    .to_string();
+            if !quiet {
+                eprintln!("  Warning: {}", warning);
             }
-            FfmpegStatus::Unknown => {
-                if !quiet {
-                    println!("Encoding video...");
-                }
-                match encode_video_with_ffmpeg(&job.output_dir, &video_out, job.fps) {
-                    Ok(()) => {
-                        if !quiet {
-                            println!("  Video saved to {:?}", video_out);
-                        }
-                        Some(video_out)
-                    }
-                    Err(e) => {
-                        let warning = format!("Video encoding failed: {}", e);
-                        if !quiet {
-                            eprintln!("  Warning: {}", warning);
-                        }
-                        warnings.push(warning);
-                        None
-                    }
-                }
+            warnings.push(warning);
+            return None;
+        }
+        FfmpegStatus::Unknown => {
+            if !quiet {
+                println!("Encoding video...");
             }
         }
-    } else {
-        None
+    }
+
+    match encode_video_with_options(&job.output_dir, &video_out, job.fps, &job.video_options) {
+        Ok(()) => {
+            if !quiet {
+                println!("  Video saved to {:?}", video_out);
+            }
+            Some(video_out)
+        }
+        Err(e) => {
+            let warning = format!("Video encoding failed: {}", e);
+            if !quiet {
+                eprintln!("  Warning: {}", warning);
+            }
+            warnings.push(warning);
+            None
+        }
+    }
+}
+
+/// Hashes of the script and of the input that fed the render, for the
+/// manifest and metadata.
+fn render_hashes(job: &RenderJobSpec, script: &str) -> (String, String) {
+    // Hash the script file when one was given; otherwise the script came
+    // from the package (or memory), so hash its content directly.
+    let script_hash = match &job.script_path {
+        Some(script_path) => {
+            RenderMetadata::hash_file(script_path).unwrap_or_else(|_| "unknown".to_string())
+        }
+        None => RenderMetadata::hash_bytes(script.as_bytes()),
     };
+    // The "input" is whichever source fed the render: the legacy signal
+    // file or the interpretation package.
+    let input_hash = job
+        .input_path
+        .as_ref()
+        .or(job.package_path.as_ref())
+        .and_then(|path| RenderMetadata::hash_file(path).ok())
+        .unwrap_or_else(|| "unknown".to_string());
+    (script_hash, input_hash)
+}
 
-    // Save metadata
-    if save_metadata {
-        let end_time = Utc::now();
+/// Write the job's manifest: its settings, hashes, effect chain and event streams.
+fn save_manifest(job: &RenderJobSpec, prepared: &PreparedRender, quiet: bool) -> Result<()> {
+    let (script_hash, input_hash) = render_hashes(job, &prepared.script);
+    let mut manifest = RenderManifest::from_job(job, script_hash, input_hash);
+    manifest.effect_chain = prepared
+        .state
+        .post_chain()
+        .enabled_effects()
+        .map(|effect| effect.effect_id.clone())
+        .collect();
+    if let Some(pkg) = prepared.package.as_ref() {
+        let sources = [
+            ("detected", &pkg.event_streams),
+            ("authored", &pkg.authored_event_streams),
+            ("band", &pkg.band_event_streams),
+        ];
+        for (source, streams) in sources {
+            manifest
+                .event_streams
+                .extend(streams.iter().map(|(name, stream)| ManifestEventStream {
+                    name: name.clone(),
+                    source: source.to_string(),
+                    event_count: stream.len(),
+                }));
+        }
+    }
+    manifest
+        .event_streams
+        .extend(
+            prepared
+                .event_streams
+                .iter()
+                .map(|(name, stream)| ManifestEventStream {
+                    name: name.clone(),
+                    source: "authored".to_string(),
+                    event_count: stream.len(),
+                }),
+        );
+    if let Some(midi) = prepared.midi.as_ref() {
+        manifest
+            .event_streams
+            .extend(
+                midi.event_streams()
+                    .iter()
+                    .map(|(name, stream)| ManifestEventStream {
+                        name: name.clone(),
+                        source: "midi".to_string(),
+                        event_count: stream.len(),
+                    }),
+            );
+    }
 
-        // Hash the script file when one was given; otherwise the script came
-        // from the package, so hash its content directly.
-        let script_hash = match &job.script_path {
-            Some(script_path) => {
-                RenderMetadata::hash_file(script_path).unwrap_or_else(|_| "unknown".to_string())
+    let manifest_path = job.manifest_path();
+    manifest
+        .save(&manifest_path)
+        .map_err(|e| RenderError::new(RenderPhase::MetadataSave, e.to_string()))?;
+
+    if !quiet {
+        println!("  Manifest saved to {:?}", manifest_path);
+    }
+    Ok(())
+}
+
+/// Audio analysis behind a render: the `--input` signal and its loudness,
+/// `--bands` energy, and `--stems` envelopes.
+pub struct AudioAnalysis {
+    /// The `--input` signal.
+    pub audio: Option<SharedSignal>,
+    /// Signals computed from `--input` (its loudness), by feature name.
+    pub signals: SignalMap,
+    /// `--bands` configs with their energy signals.
+    pub bands: Option<(Vec<BandConfig>, BandSignalMap)>,
+    /// Analysed `--stems` files.
+    pub stems: Vec<LoadedStem>,
+}
+
+impl AudioAnalysis {
+    /// Load and analyse the job's audio inputs.
+    pub fn load(job: &RenderJobSpec) -> Result<Self, RenderError> {
+        // Load input signal (legacy single-signal path only)
+        let legacy_signal: Option<SharedSignal> = match &job.input_path {
+            Some(input_path) => {
+                let mut file = File::open(input_path).map_err(|e| {
+                    RenderError::new(
+                        RenderPhase::InputLoading,
+                        format!("Failed to open input {:?}: {}", input_path, e),
+                    )
+                })?;
+                let mut contents = String::new();
+                file.read_to_string(&mut contents).map_err(|e| {
+                    RenderError::new(
+                        RenderPhase::InputLoading,
+                        format!("Failed to read input: {}", e),
+                    )
+                })?;
+
+                let samples: Vec<f32> = serde_json::from_str(&contents)
+                    .or_else(|_| {
+                        contents
+                            .split_whitespace()
+                            .map(|s| s.parse::<f32>())
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                    .map_err(|_| {
+                        RenderError::new(RenderPhase::InputLoading, "Failed to parse input file as JSON list of floats or whitespace separated floats")
+                    })?;
+
+                Some(std::rc::Rc::new(InputSignal::new(
+                    samples,
+                    job.input_sample_rate,
+                )))
+            }
+            None => None,
+        };
+        Self::analyse(job, legacy_signal)
+    }
+
+    /// Analyse `audio` (in place of the job's `--input`) along with the
+    /// job's `--bands` and `--stems`.
+    pub fn analyse(
+        job: &RenderJobSpec,
+        legacy_signal: Option<SharedSignal>,
+    ) -> Result<Self, RenderError> {
+        // Custom bands: compute per-band energy signals from the input audio.
+        let custom_bands: Option<(Vec<BandConfig>, BandSignalMap)> = match &job.bands {
+            Some(spec) => {
+                let audio = legacy_signal.as_ref().ok_or_else(|| {
+                    RenderError::new(
+                        RenderPhase::InputLoading,
+                        "--bands requires an --input audio signal",
+                    )
+                })?;
+                let bands = load_band_spec(spec, audio.sample_rate())
+                    .map_err(|e| RenderError::new(RenderPhase::InputLoading, e.to_string()))?;
+                check_band_resolution(&bands, audio.sample_rate())
+                    .map_err(|e| RenderError::new(RenderPhase::InputLoading, e))?;
+                let band_signals = band_energy_signals(audio, &bands);
+                Some((bands, band_signals))
             }
-            None => RenderMetadata::hash_bytes(script_content.as_bytes()),
+            None => None,
         };
-        // The "input" is whichever source fed the render: the legacy signal
-        // file or the interpretation package.
-        let input_hash = job
-            .input_path
+
+        // Loudness: short-term LUFS of the input audio, exposed as
+        // inputs.mix.loudness (legacy path only; a low-rate envelope has none).
+        let legacy_signals: SignalMap = legacy_signal
             .as_ref()
-            .or(job.package_path.as_ref())
-            .and_then(|path| RenderMetadata::hash_file(path).ok())
-            .unwrap_or_else(|| "unknown".to_string());
+            .map(|audio| short_term_loudness(audio.samples(), audio.sample_rate(), LOUDNESS_RATE))
+            .filter(|loudness| !loudness.is_empty())
+            .map(|loudness| {
+                let signal: SharedSignal =
+                    std::rc::Rc::new(InputSignal::new(loudness, LOUDNESS_RATE));
+                HashMap::from([(LOUDNESS_FEATURE.to_string(), signal)])
+            })
+            .unwrap_or_default();
+
+        // Pre-separated stems: analyse each wav into energy/amplitude signals, plus
+        // per-band energy when custom bands are given.
+        let stems: Vec<LoadedStem> = match &job.stems {
+            Some(dir) => {
+                let bands = custom_bands
+                    .as_ref()
+                    .map(|(bands, _)| bands.as_slice())
+                    .unwrap_or(&[]);
+                load_stem_dir(dir, bands)
+                    .map_err(|e| RenderError::new(RenderPhase::InputLoading, e))?
+            }
+            None => Vec::new(),
+        };
 
-        let metadata = RenderMetadata {
-            job: job.clone(),
-            started_at: start_time,
-            completed_at: end_time,
-            render_duration_secs,
-            frame_count: total_frames,
-            average_render_fps: total_frames as f64 / render_duration_secs,
-            script_hash,
-            input_hash,
-            octoseq_version: env!("CARGO_PKG_VERSION").to_string(),
-            gpu_adapter: gpu_adapter_str,
-            video_path,
-            warnings,
+        Ok(Self {
+            audio: legacy_signal,
+            signals: legacy_signals,
+            bands: custom_bands,
+            stems,
+        })
+    }
+
+    /// Publish the analysed signals, bands and stems to the script
+    /// environment (taking effect when the script is next loaded), and the
+    /// `--input` audio to the spectrum analysers.
+    pub fn apply(&self, state: &mut VisualiserState, package: Option<&LoadedPackage>) {
+        state.set_spectrum_audio(self.audio.clone());
+        if package.is_none() && !self.signals.is_empty() {
+            state.set_available_signals(self.signals.keys().cloned().collect());
+        }
+        if let Some((bands, _)) = self.bands.as_ref() {
+            state.set_available_bands(band_config_ids(bands));
+        }
+        if !self.stems.is_empty() {
+            let package_stems = package
+                .map(|pkg| pkg.available_stems.clone())
+                .unwrap_or_default();
+            apply_stems(&self.stems, package_stems, state);
+        }
+    }
+}
+
+/// Re-run the audio analysis for `job` and publish the result to `state`,
+/// e.g. after stems were re-separated on disk.
+///
+/// Stems that are no longer on disk disappear from `inputs.stems`. The
+/// script is reloaded so `inputs` reflects the new signal set, then the
+/// state seeks to its current time to re-warm stateful signals.
+pub fn reanalyse_audio(
+    job: &RenderJobSpec,
+    state: &mut VisualiserState,
+    package: Option<&LoadedPackage>,
+    script: &str,
+) -> Result<AudioAnalysis> {
+    let analysis = AudioAnalysis::load(job)?;
+
+    state.clear_stem_signals();
+    match package {
+        Some(pkg) => apply_stem_signals(pkg, state),
+        None => state.set_available_stems(Vec::new()),
+    }
+    analysis.apply(state, package);
+
+    let time = state.time;
+    state
+        .try_load_script(script)
+        .map_err(|e| e.with_phase(RenderPhase::ScriptLoading))?;
+    state.seek(time);
+    Ok(analysis)
+}
+
+/// Size and modification time of each watched file.
+type AudioFingerprint = Vec<(PathBuf, u64, Option<std::time::SystemTime>)>;
+
+/// Watches a job's audio files (`--input` and the `.wav` files in `--stems`)
+/// so a host can call [`reanalyse_audio`] when they change.
+///
+/// A change is reported only once the files look the same on two polls in a
+/// row, so a file that is still being written isn't analysed half-way.
+pub struct AudioInputWatch {
+    input: Option<PathBuf>,
+    stems_dir: Option<PathBuf>,
+    seen: AudioFingerprint,
+    pending: Option<AudioFingerprint>,
+}
+
+impl AudioInputWatch {
+    /// Start watching `job`'s audio files as they are now.
+    pub fn new(job: &RenderJobSpec) -> Self {
+        let mut watch = Self {
+            input: job.input_path.clone(),
+            stems_dir: job.stems.clone(),
+            seen: Vec::new(),
+            pending: None,
         };
+        watch.seen = watch.fingerprint();
+        watch
+    }
 
-        let metadata_path = job.output_dir.join("metadata.json");
-        metadata
-            .save(&metadata_path)
-            .map_err(|e| anyhow::anyhow!("[{}] {}", RenderPhase::MetadataSave, e))?;
+    /// Check the files; true when a change has settled and is ready to reanalyse.
+    pub fn poll(&mut self) -> bool {
+        let current = self.fingerprint();
+        if current == self.seen {
+            self.pending = None;
+            return false;
+        }
+        if self.pending.as_ref() == Some(&current) {
+            self.seen = current;
+            self.pending = None;
+            return true;
+        }
+        self.pending = Some(current);
+        false
+    }
 
-        if !quiet {
-            println!("  Metadata saved to {:?}", metadata_path);
+    fn fingerprint(&self) -> AudioFingerprint {
+        let mut paths: Vec<PathBuf> = self.input.iter().cloned().collect();
+        if let Some(entries) = self
+            .stems_dir
+            .as_ref()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+        {
+            paths.extend(
+                entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| {
+                        path.extension()
+                            .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+                    }),
+            );
         }
+        paths.sort();
+        paths
+            .into_iter()
+            .map(|path| {
+                let metadata = std::fs::metadata(&path).ok();
+                let len = metadata.as_ref().map_or(0, |m| m.len());
+                let modified = metadata.and_then(|m| m.modified().ok());
+                (path, len, modified)
+            })
+            .collect()
     }
+}
 
-    if !quiet {
-        println!("Done.");
+/// Sample rate (Hz) of computed band energy signals.
+const BAND_ENERGY_RATE: f32 = 100.0;
+
+/// Sample rate (Hz) of the computed loudness signal.
+const LOUDNESS_RATE: f32 = 100.0;
+
+/// Resolve a `--bands` spec for audio at `sample_rate`: a path to a JSON
+/// band config file, `mel:N`, or inline JSON.
+fn load_band_spec(spec: &str, sample_rate: f32) -> Result<Vec<BandConfig>, String> {
+    let path = std::path::Path::new(spec);
+    if path.is_file() {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read band config {:?}: {}", path, e))?;
+        parse_band_configs(&json)
+    } else {
+        parse_band_spec(spec, sample_rate)
     }
+}
 
-    Ok(())
+/// Compute band energy signals from audio, keyed like the package band
+/// signals: by band id and, when different, also by label.
+fn band_energy_signals(audio: &InputSignal, bands: &[BandConfig]) -> BandSignalMap {
+    let energies = compute_band_energies(
+        audio.samples(),
+        audio.sample_rate(),
+        bands,
+        BAND_ENERGY_RATE,
+    );
+    let mut band_signals: BandSignalMap = HashMap::new();
+    for (band, samples) in bands.iter().zip(energies) {
+        let signal: SharedSignal = std::rc::Rc::new(InputSignal::new(samples, BAND_ENERGY_RATE));
+        band_signals
+            .entry(band.id.clone())
+            .or_default()
+            .insert(BAND_ENERGY_FEATURE.to_string(), signal.clone());
+        if band.label() != band.id {
+            band_signals
+                .entry(band.label().to_string())
+                .or_default()
+                .insert(BAND_ENERGY_FEATURE.to_string(), signal);
+        }
+    }
+    band_signals
 }
 
 /// Run a batch of render jobs from a config file.
-async fn run_batch(
+fn run_batch(
     config_path: &PathBuf,
     out_override: Option<PathBuf>,
     continue_on_error: bool,
     quiet: bool,
 ) -> Result<()> {
-    let mut batch = BatchJobSpec::from_file(config_path)
-        .map_err(|e| anyhow::anyhow!("Failed to load batch config: {}", e))?;
+    let mut batch = BatchJobSpec::from_file(config_path).map_err(|e| {
+        VisualiserError::InvalidConfig(format!("Failed to load batch config: {}", e))
+    })?;
 
     // Override output base if specified
     if let Some(out) = out_override {
         batch.output_base = out;
     }
 
-    // Validate batch
+    // Generate output paths, then validate (including output collisions)
+    batch.generate_output_paths();
     batch
         .validate()
-        .map_err(|e| anyhow::anyhow!("Batch validation failed: {}", e))?;
-
-    // Generate output paths
-    batch.generate_output_paths();
+        .map_err(|e| VisualiserError::InvalidConfig(format!("Batch validation failed: {}", e)))?;
 
     if !quiet {
         println!("Batch: {} ({} jobs)", batch.batch_id, batch.jobs.len());
     }
 
-    let mut completed = 0;
-    let mut failed = 0;
+    let report = run_batch_jobs(&batch, continue_on_error, quiet, |job| {
+        pollster::block_on(execute_render_job(job, true, quiet))
+    });
+
+    if !quiet {
+        println!(
+            "\nBatch complete: {} succeeded, {} failed",
+            report.completed,
+            report.failures.len()
+        );
+    }
+    report.into_result()
+}
+
+/// Outcome of a batch run.
+#[derive(Debug, Default)]
+struct BatchReport {
+    completed: usize,
+    /// (job index, job label, error) for each failed job.
+    failures: Vec<(usize, String, String)>,
+    /// Jobs not attempted because the batch stopped at a failure.
+    skipped: usize,
+}
+
+impl BatchReport {
+    /// Ok if every job succeeded, otherwise one error listing each failure.
+    fn into_result(self) -> Result<()> {
+        if self.failures.is_empty() {
+            return Ok(());
+        }
+        let mut message = format!("{} of {} jobs failed", self.failures.len(), self.total());
+        if self.skipped > 0 {
+            message.push_str(&format!(" ({} not run)", self.skipped));
+        }
+        for (i, label, error) in &self.failures {
+            message.push_str(&format!("\n  job {} ({}): {}", i, label, error));
+        }
+        Err(VisualiserError::Batch(message))
+    }
+
+    fn total(&self) -> usize {
+        self.completed + self.failures.len() + self.skipped
+    }
+}
+
+/// Render each job in order with `render`, reporting per-job progress.
+/// Failures are collected rather than propagated; unless
+/// `continue_on_error` is set, the first failure stops the batch. Jobs run
+/// sequentially since scripts share process-wide state (authored event
+/// streams, debug options).
+fn run_batch_jobs(
+    batch: &BatchJobSpec,
+    continue_on_error: bool,
+    quiet: bool,
+    mut render: impl FnMut(&RenderJobSpec) -> Result<()>,
+) -> BatchReport {
+    let mut report = BatchReport::default();
     let total = batch.jobs.len();
 
     for (i, job) in batch.jobs.iter().enumerate() {
-        let preset_name = job.preset_name.as_deref().unwrap_or("default");
+        let label = job.preset_name.as_deref().unwrap_or("default").to_string();
 
         if !quiet {
-            println!("\n[{}/{}] Rendering preset: {}", i + 1, total, preset_name);
+            println!(
+                "\n[{}/{}] Rendering preset: {} -> {:?}",
+                i + 1,
+                total,
+                label,
+                job.output_dir
+            );
         }
 
-        match execute_render_job(job, true, quiet).await {
-            Ok(()) => {
-                completed += 1;
-            }
+        match render(job) {
+            Ok(()) => report.completed += 1,
             Err(e) => {
-                failed += 1;
-                eprintln!("Error rendering {}: {}", preset_name, e);
+                eprintln!("Error rendering {}: {}", label, e);
+                report.failures.push((i, label, e.to_string()));
                 if !continue_on_error {
-                    return Err(anyhow::anyhow!(
-                        "Batch aborted after {} of {} jobs ({} failed)",
-                        i + 1,
-                        total,
-                        failed
-                    ));
+                    report.skipped = total - i - 1;
+                    break;
                 }
             }
         }
     }
 
-    if !quiet {
-        println!(
-            "\nBatch complete: {} succeeded, {} failed",
-            completed, failed
-        );
-    }
-
-    if failed > 0 && !continue_on_error {
-        return Err(anyhow::anyhow!("{} jobs failed", failed));
-    }
-
-    Ok(())
+    report
 }
 
 /// Validate a job or batch config file without rendering.
 fn validate_config(config_path: &PathBuf) -> Result<()> {
     let content = std::fs::read_to_string(config_path)
-        .map_err(|e| anyhow::anyhow!("Failed to read config file: {}", e))?;
+        .map_err(|e| std::io::Error::new(e.kind(), format!("Failed to read config file: {}", e)))?;
 
     // Try parsing as batch first
-    if let Ok(batch) = serde_json::from_str::<BatchJobSpec>(&content) {
+    if let Ok(mut batch) = serde_json::from_str::<BatchJobSpec>(&content) {
+        batch.generate_output_paths();
         println!("Detected: Batch config");
         println!("  Batch ID: {}", batch.batch_id);
         println!("  Jobs: {}", batch.jobs.len());
@@ -772,7 +1684,7 @@ fn validate_config(config_path: &PathBuf) -> Result<()> {
 
         batch
             .validate()
-            .map_err(|e| anyhow::anyhow!("Validation failed: {}", e))?;
+            .map_err(|e| VisualiserError::InvalidConfig(format!("Validation failed: {}", e)))?;
 
         println!("  Status: Valid");
         return Ok(());
@@ -794,13 +1706,500 @@ fn validate_config(config_path: &PathBuf) -> Result<()> {
         println!("  FPS: {}", job.fps);
 
         job.validate()
-            .map_err(|e| anyhow::anyhow!("Validation failed: {}", e))?;
+            .map_err(|e| VisualiserError::InvalidConfig(format!("Validation failed: {}", e)))?;
 
         println!("  Status: Valid");
         return Ok(());
     }
 
-    Err(anyhow::anyhow!(
-        "Config file is neither a valid batch config nor a valid job config"
+    Err(VisualiserError::InvalidConfig(
+        "Config file is neither a valid batch config nor a valid job config".to_string(),
     ))
 }
+
+/// A parameter row shared by the effect and material listings.
+struct ParamRow {
+    name: String,
+    param_type: String,
+    description: String,
+    default: Vec<f32>,
+}
+
+fn format_default(values: &[f32]) -> String {
+    match values {
+        [v] => format!("{}", v),
+        _ => format!(
+            "[{}]",
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Render a registry listing as JSON or as a readable table.
+fn format_listing(entries: Vec<(String, String, String, Vec<ParamRow>)>, json: bool) -> String {
+    if json {
+        let value: Vec<serde_json::Value> = entries
+            .iter()
+            .map(|(id, name, description, params)| {
+                serde_json::json!({
+                    "id": id,
+                    "name": name,
+                    "description": description,
+                    "params": params
+                        .iter()
+                        .map(|p| serde_json::json!({
+                            "name": p.name,
+                            "type": p.param_type,
+                            "description": p.description,
+                            "default": if p.default.len() == 1 {
+                                serde_json::json!(p.default[0])
+                            } else {
+                                serde_json::json!(p.default)
+                            },
+                        }))
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect();
+        let mut out = serde_json::to_string_pretty(&value).unwrap_or_default();
+        out.push('\n');
+        return out;
+    }
+
+    let mut out = String::new();
+    for (id, name, description, params) in &entries {
+        out.push_str(&format!("{} ({}): {}\n", id, name, description));
+        for p in params {
+            out.push_str(&format!(
+                "  {:<20} {:<6} {:<22} {}\n",
+                p.name,
+                p.param_type,
+                format_default(&p.default),
+                p.description
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// List all post-processing effects from the registry.
+fn format_effects(json: bool) -> String {
+    use crate::post_processing::PostEffectRegistry;
+    let registry = PostEffectRegistry::new();
+    let mut ids = registry.list_ids();
+    ids.sort_unstable();
+    let entries = ids
+        .into_iter()
+        .filter_map(|id| registry.get(id))
+        .map(|effect| {
+            let params = effect
+                .params
+                .iter()
+                .map(|p| ParamRow {
+                    name: p.name.clone(),
+                    param_type: format!("{:?}", p.param_type),
+                    description: p.description.clone(),
+                    default: effect_param_components(&p.default_value),
+                })
+                .collect();
+            (
+                effect.id.clone(),
+                effect.name.clone(),
+                effect.description.clone(),
+                params,
+            )
+        })
+        .collect();
+    format_listing(entries, json)
+}
+
+/// List all materials from the registry.
+fn format_materials(json: bool) -> String {
+    use crate::material::MaterialRegistry;
+    let registry = MaterialRegistry::new();
+    let mut ids = registry.list_ids();
+    ids.sort_unstable();
+    let entries = ids
+        .into_iter()
+        .filter_map(|id| registry.get(id))
+        .map(|material| {
+            let params = material
+                .params
+                .iter()
+                .map(|p| {
+                    let default = p
+                        .default_value
+                        .as_static()
+                        .map(|v| material_param_components(&v))
+                        .unwrap_or_default();
+                    ParamRow {
+                        name: p.name.clone(),
+                        param_type: format!("{:?}", p.param_type),
+                        description: p.description.clone(),
+                        default,
+                    }
+                })
+                .collect();
+            (
+                material.id.clone(),
+                material.name.clone(),
+                material.description.clone(),
+                params,
+            )
+        })
+        .collect();
+    format_listing(entries, json)
+}
+
+fn effect_param_components(value: &crate::post_processing::EffectParamValue) -> Vec<f32> {
+    use crate::post_processing::EffectParamValue;
+    match value {
+        EffectParamValue::Float(v) => vec![*v],
+        EffectParamValue::Vec2(v) => v.to_vec(),
+        EffectParamValue::Vec3(v) => v.to_vec(),
+        EffectParamValue::Vec4(v) => v.to_vec(),
+    }
+}
+
+fn material_param_components(value: &crate::material::ParamValue) -> Vec<f32> {
+    use crate::material::ParamValue;
+    match value {
+        ParamValue::Float(v) => vec![*v],
+        ParamValue::Vec2(v) => v.to_vec(),
+        ParamValue::Vec3(v) => v.to_vec(),
+        ParamValue::Vec4(v) => v.to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stem_files::wav_16bit;
+
+    #[test]
+    fn test_list_effects_contains_bloom() {
+        let table = format_effects(false);
+        assert!(table.contains("bloom (Bloom)"));
+        assert!(table.contains("threshold"));
+
+        let json: serde_json::Value = serde_json::from_str(&format_effects(true)).unwrap();
+        let bloom = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|e| e["id"] == "bloom")
+            .expect("bloom listed");
+        assert_eq!(bloom["params"][0]["name"], "threshold");
+        assert_eq!(bloom["params"][0]["default"], serde_json::json!(0.8f32));
+    }
+
+    fn render_video_options(args: &[&str]) -> Result<VideoEncodingOptions, clap::Error> {
+        let base = [
+            "visualiser",
+            "render",
+            "--input",
+            "in.json",
+            "--script",
+            "s.rhai",
+            "--out",
+            "frames",
+        ];
+        let cli = Cli::try_parse_from(base.iter().chain(args))?;
+        match cli.command {
+            Commands::Render { video, .. } => Ok(video.options()),
+            _ => unreachable!("parsed a render command"),
+        }
+    }
+
+    #[test]
+    fn test_video_options_from_cli_args() {
+        assert_eq!(
+            render_video_options(&[]).unwrap(),
+            VideoEncodingOptions::default()
+        );
+
+        let options =
+            render_video_options(&["--codec", "h265", "--bitrate", "8M", "--pix-fmt", "yuv444p"])
+                .unwrap();
+        assert_eq!(options.codec, VideoCodec::H265);
+        assert_eq!(options.bitrate.as_deref(), Some("8M"));
+        assert_eq!(options.crf, None);
+        assert_eq!(options.pixel_format, Some(PixelFormat::Yuv444p));
+        assert!(options.validate().is_ok());
+
+        let options =
+            render_video_options(&["--bitrate", "4M", "--two-pass", "--faststart"]).unwrap();
+        assert!(options.two_pass && options.faststart);
+        assert!(options.validate().is_ok());
+
+        let options = render_video_options(&["--codec", "vp9", "--crf", "30"]).unwrap();
+        assert_eq!(
+            options.ffmpeg_args(),
+            [
+                "-c:v",
+                "libvpx-vp9",
+                "-pix_fmt",
+                "yuv420p",
+                "-crf",
+                "30",
+                "-b:v",
+                "0"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_video_options_reject_invalid_cli_args() {
+        assert!(render_video_options(&["--codec", "mpeg2"]).is_err());
+        assert!(render_video_options(&["--pix-fmt", "rgb24"]).is_err());
+        assert!(render_video_options(&["--crf", "20", "--bitrate", "8M"]).is_err());
+        assert!(render_video_options(&["--two-pass"]).is_err());
+
+        // Parses, but ProRes has no CRF mode
+        let options = render_video_options(&["--codec", "prores", "--crf", "20"]).unwrap();
+        assert!(options.validate().is_err());
+    }
+
+    #[test]
+    fn test_list_materials_json() {
+        let json: serde_json::Value = serde_json::from_str(&format_materials(true)).unwrap();
+        let default = json
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|m| m["id"] == "default")
+            .expect("default material listed");
+        assert_eq!(
+            default["params"][0]["default"],
+            serde_json::json!([1.0, 1.0, 1.0, 1.0])
+        );
+    }
+
+    fn two_job_batch() -> BatchJobSpec {
+        let mut batch: BatchJobSpec = serde_json::from_str(
+            r#"{
+                "batchId": "set",
+                "outputBase": "/renders",
+                "jobs": [
+                    { "packagePath": "tracks/intro.json", "scriptPath": "a.rhai",
+                      "outputDir": "", "presetName": "calm", "seed": 1,
+                      "width": 640, "height": 360 },
+                    { "packagePath": "tracks/drop.json", "scriptPath": "b.rhai",
+                      "outputDir": "", "presetName": "wild", "seed": 2, "fps": 30 }
+                ]
+            }"#,
+        )
+        .unwrap();
+        batch.generate_output_paths();
+        batch
+    }
+
+    #[test]
+    fn test_batch_renders_each_job_with_its_parameters() {
+        let batch = two_job_batch();
+        let mut rendered = Vec::new();
+        let report = run_batch_jobs(&batch, false, true, |job| {
+            rendered.push((
+                job.script_path.clone().unwrap(),
+                job.output_dir.clone(),
+                job.seed,
+                (job.width, job.height),
+                job.fps,
+            ));
+            Ok(())
+        });
+
+        assert_eq!(
+            rendered,
+            vec![
+                (
+                    PathBuf::from("a.rhai"),
+                    PathBuf::from("/renders/intro/calm/frames"),
+                    1,
+                    (640, 360),
+                    60.0
+                ),
+                (
+                    PathBuf::from("b.rhai"),
+                    PathBuf::from("/renders/drop/wild/frames"),
+                    2,
+                    (1920, 1080),
+                    30.0
+                ),
+            ]
+        );
+        assert_eq!(report.completed, 2);
+        assert!(report.into_result().is_ok());
+    }
+
+    #[test]
+    fn test_batch_aggregates_failures() {
+        let batch = two_job_batch();
+        let fail_first = |job: &RenderJobSpec| match job.seed {
+            1 => Err(VisualiserError::ScriptRuntime("GPU lost".to_string())),
+            _ => Ok(()),
+        };
+
+        // With --continue-on-error the second job still runs.
+        let report = run_batch_jobs(&batch, true, true, fail_first);
+        assert_eq!((report.completed, report.failures.len()), (1, 1));
+        let message = report.into_result().unwrap_err().to_string();
+        assert!(message.contains("1 of 2 jobs failed"), "{}", message);
+        assert!(message.contains("job 0 (calm)") && message.contains("GPU lost"));
+
+        // Otherwise the batch stops at the first failure.
+        let report = run_batch_jobs(&batch, false, true, fail_first);
+        assert_eq!((report.completed, report.skipped), (0, 1));
+    }
+
+    /// Script whose cube sits at x = 1 while a "drums" stem exists and at
+    /// y = the "bass" stem's amplitude while that exists.
+    const STEM_PROBE_SCRIPT: &str = r#"
+        let cube;
+        fn init(ctx) {
+            cube = mesh.cube();
+            scene.add(cube);
+        }
+        fn update(dt, frame) {
+            cube.position.x = if inputs.stems.contains("drums") { 1.0 } else { 0.0 };
+            cube.position.y = if inputs.stems.contains("bass") { inputs.stems["bass"].amplitude } else { 0.0 };
+        }
+    "#;
+
+    /// A fresh directory holding a "drums" stem, and a job reading stems from it.
+    fn drums_stem_job(prefix: &str) -> (PathBuf, RenderJobSpec) {
+        let dir = std::env::temp_dir().join(format!(
+            "{}-{}-{}",
+            prefix,
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("drums.wav"), wav_16bit(&[0.5; 8000], 1, 8000)).unwrap();
+
+        let mut job = RenderJobSpec::new(
+            PathBuf::from("unused.json"),
+            PathBuf::from("unused.rhai"),
+            dir.join("frames"),
+        );
+        job.input_path = None;
+        job.stems = Some(dir.clone());
+        (dir, job)
+    }
+
+    /// Replace the drums stem with a bass stem, as an external re-separation would.
+    fn reseparate_stems(dir: &std::path::Path) {
+        std::fs::remove_file(dir.join("drums.wav")).unwrap();
+        std::fs::write(dir.join("bass.wav"), wav_16bit(&[0.25; 8000], 1, 8000)).unwrap();
+    }
+
+    #[test]
+    fn test_reanalyse_audio_updates_available_stems() {
+        let (dir, job) = drums_stem_job("octoseq-reanalyse");
+        let script = STEM_PROBE_SCRIPT;
+        let mut state = VisualiserState::new();
+        AudioAnalysis::load(&job).unwrap().apply(&mut state, None);
+        state.try_load_script(script).unwrap();
+        let signals: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        let position = |state: &mut VisualiserState| {
+            state.update(0.1, None, None, &signals, &bands, &signals, None);
+            let (_, cube) = state.scene_graph().scene_entities().next().unwrap();
+            cube.transform().position
+        };
+        assert_eq!(position(&mut state).x, 1.0);
+
+        // Stems re-separated externally: drums replaced by bass. The watch
+        // waits for the files to settle before reporting the change.
+        let mut watch = AudioInputWatch::new(&job);
+        assert!(!watch.poll());
+        reseparate_stems(&dir);
+        assert!(!watch.poll());
+        assert!(watch.poll());
+        assert!(!watch.poll());
+
+        let analysis = reanalyse_audio(&job, &mut state, None, script).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(analysis.stems.len(), 1);
+        assert_eq!(state.seek_generation(), 1);
+
+        let moved = position(&mut state);
+        assert_eq!(moved.x, 0.0, "stale drums stem still available");
+        assert!((moved.y - 0.25).abs() < 1e-3, "bass amplitude: {}", moved.y);
+    }
+
+    #[test]
+    fn test_watch_audio_reanalyses_during_render() {
+        let (dir, mut job) = drums_stem_job("octoseq-watch-audio");
+        job.watch_audio = true;
+        job.width = 16;
+        job.height = 16;
+        job.fps = 10.0;
+        job.duration = Some(0.5);
+
+        let sources = RenderSources {
+            script: Some(STEM_PROBE_SCRIPT.to_string()),
+            audio: None,
+        };
+        let mut prepared = PreparedRender::load(&job, sources, &mut Vec::new()).unwrap();
+        let (device, queue) = match headless::request_device() {
+            Ok(device) => device,
+            Err(e) => {
+                eprintln!("Skipping watch-audio render test: {}", e);
+                std::fs::remove_dir_all(&dir).ok();
+                return;
+            }
+        };
+
+        // Re-separate after the first frame; the watch reports it once the
+        // files have settled, and later frames see the new stems.
+        let mut rendered = Vec::new();
+        let mut on_frame = |frame: &RenderedFrame| {
+            if frame.index == 0 {
+                reseparate_stems(&dir);
+            }
+            rendered.push(frame.index);
+        };
+        let sinks = FrameSinks {
+            quiet: true,
+            on_frame: Some(&mut on_frame),
+            ..FrameSinks::default()
+        };
+        render_frames(&job, &mut prepared, device, queue, sinks, &mut Vec::new()).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(rendered.len(), 5);
+        assert_eq!(prepared.audio.stems.len(), 1);
+        let (_, cube) = prepared
+            .state
+            .scene_graph()
+            .scene_entities()
+            .next()
+            .unwrap();
+        let position = cube.transform().position;
+        assert_eq!(position.x, 0.0, "stale drums stem still available");
+        assert!(
+            (position.y - 0.25).abs() < 1e-3,
+            "bass amplitude: {}",
+            position.y
+        );
+    }
+
+    #[test]
+    fn test_batch_rejects_colliding_output_dirs() {
+        let mut batch = two_job_batch();
+        batch.jobs[1].preset_name = Some("calm".to_string());
+        batch.jobs[1].package_path = Some(PathBuf::from("other/intro.json"));
+        batch.generate_output_paths();
+        let error = batch.validate().unwrap_err().to_string();
+        assert!(error.contains("both write to"), "{}", error);
+    }
+}
@@ -0,0 +1,225 @@
+//! Named colormaps for data visualisation (spectrograms, heatmaps).
+//!
+//! Each colormap is a table of evenly spaced RGB stops, linearly interpolated.
+//! Scripts reach them through `color.map(name, value)`.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A named colormap mapping 0-1 to RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Colormap {
+    /// Perceptually uniform blue-green-yellow (matplotlib's default).
+    #[default]
+    Viridis,
+    /// Perceptually uniform black-purple-orange-cream.
+    Magma,
+    /// Perceptually uniform black-purple-orange-yellow.
+    Inferno,
+    /// High-contrast rainbow (Google's Turbo).
+    Turbo,
+    /// Black to white.
+    Grayscale,
+}
+
+const VIRIDIS: [[u8; 3]; 9] = [
+    [0x44, 0x01, 0x54],
+    [0x47, 0x2c, 0x7a],
+    [0x3b, 0x52, 0x8b],
+    [0x2c, 0x72, 0x8e],
+    [0x21, 0x91, 0x8c],
+    [0x28, 0xae, 0x80],
+    [0x5e, 0xc9, 0x62],
+    [0xad, 0xdc, 0x30],
+    [0xfd, 0xe7, 0x25],
+];
+
+const MAGMA: [[u8; 3]; 9] = [
+    [0x00, 0x00, 0x04],
+    [0x1c, 0x10, 0x44],
+    [0x4f, 0x12, 0x7b],
+    [0x81, 0x25, 0x81],
+    [0xb5, 0x36, 0x7a],
+    [0xe5, 0x50, 0x64],
+    [0xfb, 0x87, 0x61],
+    [0xfe, 0xc2, 0x87],
+    [0xfc, 0xfd, 0xbf],
+];
+
+const INFERNO: [[u8; 3]; 9] = [
+    [0x00, 0x00, 0x04],
+    [0x1f, 0x0c, 0x48],
+    [0x55, 0x0f, 0x6d],
+    [0x88, 0x22, 0x6a],
+    [0xba, 0x36, 0x55],
+    [0xe3, 0x59, 0x33],
+    [0xf9, 0x8e, 0x09],
+    [0xf9, 0xcb, 0x35],
+    [0xfc, 0xff, 0xa4],
+];
+
+const TURBO: [[u8; 3]; 9] = [
+    [0x23, 0x17, 0x1b],
+    [0x45, 0x69, 0xee],
+    [0x26, 0xbc, 0xe1],
+    [0x3f, 0xf3, 0x93],
+    [0x95, 0xfb, 0x51],
+    [0xec, 0xd1, 0x2e],
+    [0xff, 0x82, 0x1d],
+    [0xcb, 0x2f, 0x0d],
+    [0x90, 0x0c, 0x00],
+];
+
+const GRAYSCALE: [[u8; 3]; 2] = [[0x00, 0x00, 0x00], [0xff, 0xff, 0xff]];
+
+impl Colormap {
+    /// Every colormap, in the order they are listed to scripts.
+    pub const ALL: [Colormap; 5] = [
+        Colormap::Viridis,
+        Colormap::Magma,
+        Colormap::Inferno,
+        Colormap::Turbo,
+        Colormap::Grayscale,
+    ];
+
+    /// Name used in scripts and options.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Colormap::Viridis => "viridis",
+            Colormap::Magma => "magma",
+            Colormap::Inferno => "inferno",
+            Colormap::Turbo => "turbo",
+            Colormap::Grayscale => "grayscale",
+        }
+    }
+
+    fn stops(&self) -> &'static [[u8; 3]] {
+        match self {
+            Colormap::Viridis => &VIRIDIS,
+            Colormap::Magma => &MAGMA,
+            Colormap::Inferno => &INFERNO,
+            Colormap::Turbo => &TURBO,
+            Colormap::Grayscale => &GRAYSCALE,
+        }
+    }
+
+    /// RGB in 0-1 for `value`, clamped to 0-1. NaN maps to the low end.
+    pub fn rgb(&self, value: f32) -> [f32; 3] {
+        let stops = self.stops();
+        let t = if value.is_nan() {
+            0.0
+        } else {
+            value.clamp(0.0, 1.0)
+        };
+        let position = t * (stops.len() - 1) as f32;
+        let index = (position as usize).min(stops.len() - 2);
+        let frac = position - index as f32;
+        let (a, b) = (stops[index], stops[index + 1]);
+        std::array::from_fn(|c| (a[c] as f32 + (b[c] as f32 - a[c] as f32) * frac) / 255.0)
+    }
+
+    /// Opaque RGBA8 for `value`, for CPU-generated textures.
+    pub fn rgba8(&self, value: f32) -> [u8; 4] {
+        let [r, g, b] = self.rgb(value);
+        [
+            (r * 255.0).round() as u8,
+            (g * 255.0).round() as u8,
+            (b * 255.0).round() as u8,
+            255,
+        ]
+    }
+}
+
+impl fmt::Display for Colormap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Colormap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "greyscale" {
+            return Ok(Colormap::Grayscale);
+        }
+        Colormap::ALL
+            .into_iter()
+            .find(|colormap| colormap.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Colormap::ALL.iter().map(Colormap::name).collect();
+                format!(
+                    "Unknown colormap '{}' (expected one of {})",
+                    s,
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoints_and_midpoints() {
+        let cases = [
+            (
+                Colormap::Viridis,
+                [0x44, 0x01, 0x54],
+                [0x21, 0x91, 0x8c],
+                [0xfd, 0xe7, 0x25],
+            ),
+            (
+                Colormap::Magma,
+                [0x00, 0x00, 0x04],
+                [0xb5, 0x36, 0x7a],
+                [0xfc, 0xfd, 0xbf],
+            ),
+            (
+                Colormap::Inferno,
+                [0x00, 0x00, 0x04],
+                [0xba, 0x36, 0x55],
+                [0xfc, 0xff, 0xa4],
+            ),
+            (
+                Colormap::Turbo,
+                [0x23, 0x17, 0x1b],
+                [0x95, 0xfb, 0x51],
+                [0x90, 0x0c, 0x00],
+            ),
+            (
+                Colormap::Grayscale,
+                [0x00, 0x00, 0x00],
+                [0x80, 0x80, 0x80],
+                [0xff, 0xff, 0xff],
+            ),
+        ];
+        for (colormap, low, mid, high) in cases {
+            let rgb = |v: f32| {
+                let [r, g, b, _] = colormap.rgba8(v);
+                [r, g, b]
+            };
+            assert_eq!(rgb(0.0), low, "{} at 0", colormap);
+            assert_eq!(rgb(0.5), mid, "{} at 0.5", colormap);
+            assert_eq!(rgb(1.0), high, "{} at 1", colormap);
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_values_clamp() {
+        let colormap = Colormap::Viridis;
+        assert_eq!(colormap.rgb(-1.0), colormap.rgb(0.0));
+        assert_eq!(colormap.rgb(2.0), colormap.rgb(1.0));
+        assert_eq!(colormap.rgb(f32::NAN), colormap.rgb(0.0));
+    }
+
+    #[test]
+    fn test_parse_names() {
+        for colormap in Colormap::ALL {
+            assert_eq!(colormap.name().parse(), Ok(colormap));
+        }
+        assert_eq!("greyscale".parse(), Ok(Colormap::Grayscale));
+        assert!("rainbow".parse::<Colormap>().is_err());
+    }
+}
@@ -0,0 +1,297 @@
+//! Contact sheets: a grid of thumbnails at evenly spaced times, for reviewing a render at a glance.
+//!
+//! The render loop still steps the script through every frame so the state at
+//! each sample time matches a full render, but only the sampled frames are drawn,
+//! at thumbnail size. Each tile is labelled with its timestamp using a small
+//! built-in bitmap font.
+
+use std::fmt;
+use std::path::Path;
+use std::str::FromStr;
+
+use image::{Rgba, RgbaImage};
+use serde::{Deserialize, Serialize};
+
+use crate::bitmap_font::{glyph, GLYPH_ADVANCE, GLYPH_HEIGHT, GLYPH_WIDTH};
+
+/// Width of each thumbnail in pixels (smaller renders are not upscaled).
+pub const TILE_WIDTH: u32 = 320;
+
+/// Spacing between tiles and around the edge of the sheet.
+pub const TILE_GAP: u32 = 4;
+
+const BACKGROUND: Rgba<u8> = Rgba([24, 24, 24, 255]);
+const LABEL_BACKGROUND: Rgba<u8> = Rgba([0, 0, 0, 255]);
+const LABEL_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+/// How many image pixels each font pixel covers.
+const GLYPH_SCALE: u32 = 2;
+
+/// Grid layout of a contact sheet, written `<cols>x<rows>` (e.g. `4x3`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContactSheetGrid {
+    cols: u32,
+    rows: u32,
+}
+
+/// Most tiles a contact sheet may have.
+pub const MAX_TILES: u32 = 256;
+
+impl ContactSheetGrid {
+    /// A grid of `cols` x `rows` tiles, between 1 and [`MAX_TILES`] in total.
+    pub fn new(cols: u32, rows: u32) -> Result<Self, String> {
+        if cols == 0 || rows == 0 || cols.checked_mul(rows).is_none_or(|n| n > MAX_TILES) {
+            return Err(format!(
+                "Contact sheet grid '{}x{}' must have between 1 and {} tiles",
+                cols, rows, MAX_TILES
+            ));
+        }
+        Ok(Self { cols, rows })
+    }
+
+    /// Number of columns.
+    pub fn cols(&self) -> u32 {
+        self.cols
+    }
+
+    /// Number of rows.
+    pub fn rows(&self) -> u32 {
+        self.rows
+    }
+
+    /// Number of tiles.
+    pub fn tile_count(&self) -> usize {
+        self.cols
+            .checked_mul(self.rows)
+            .expect("contact sheet grids are validated in ContactSheetGrid::new") as usize
+    }
+
+    /// Thumbnail size for a render of `width` x `height`, keeping the aspect ratio.
+    pub fn tile_size(&self, width: u32, height: u32) -> (u32, u32) {
+        let tile_width = width.min(TILE_WIDTH);
+        let tile_height =
+            ((tile_width as f64 * height as f64 / width as f64).round() as u32).max(1);
+        (tile_width, tile_height)
+    }
+
+    /// Frame indices to sample out of `total_frames`: the middle of each of
+    /// `tile_count` equal spans, so the first tile isn't the empty first frame.
+    pub fn sample_frames(&self, total_frames: usize) -> Result<Vec<usize>, String> {
+        let count = self.tile_count();
+        if total_frames < count {
+            return Err(format!(
+                "A {} contact sheet needs at least {} frames, the render has {}",
+                self, count, total_frames
+            ));
+        }
+        Ok((0..count)
+            .map(|k| (2 * k + 1) * total_frames / (2 * count))
+            .collect())
+    }
+}
+
+impl fmt::Display for ContactSheetGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}x{}", self.cols, self.rows)
+    }
+}
+
+impl FromStr for ContactSheetGrid {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid contact sheet grid '{}' (expected e.g. 4x3)", s);
+        let (cols, rows) = s.split_once(['x', 'X']).ok_or_else(invalid)?;
+        let cols: u32 = cols.trim().parse().map_err(|_| invalid())?;
+        let rows: u32 = rows.trim().parse().map_err(|_| invalid())?;
+        Self::new(cols, rows)
+    }
+}
+
+impl TryFrom<String> for ContactSheetGrid {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ContactSheetGrid> for String {
+    fn from(grid: ContactSheetGrid) -> Self {
+        grid.to_string()
+    }
+}
+
+/// A contact sheet being assembled tile by tile.
+pub struct ContactSheet {
+    grid: ContactSheetGrid,
+    tile_width: u32,
+    tile_height: u32,
+    image: RgbaImage,
+}
+
+impl ContactSheet {
+    /// Create an empty sheet for tiles of the given size.
+    pub fn new(grid: ContactSheetGrid, tile_width: u32, tile_height: u32) -> Self {
+        let width = grid.cols * tile_width + (grid.cols + 1) * TILE_GAP;
+        let height = grid.rows * tile_height + (grid.rows + 1) * TILE_GAP;
+        Self {
+            grid,
+            tile_width,
+            tile_height,
+            image: RgbaImage::from_pixel(width, height, BACKGROUND),
+        }
+    }
+
+    /// Place tile `index` (row-major) from tightly packed RGBA8 rows and label it with `time_secs`.
+    pub fn add_tile(&mut self, index: usize, rgba: &[u8], time_secs: f32) -> Result<(), String> {
+        if index >= self.grid.tile_count() {
+            return Err(format!("Tile {} is outside the {} grid", index, self.grid));
+        }
+        let tile = RgbaImage::from_raw(self.tile_width, self.tile_height, rgba.to_vec())
+            .ok_or_else(|| {
+                format!(
+                    "Tile is {} bytes, expected {}x{} RGBA",
+                    rgba.len(),
+                    self.tile_width,
+                    self.tile_height
+                )
+            })?;
+
+        let col = index as u32 % self.grid.cols;
+        let row = index as u32 / self.grid.cols;
+        let x = TILE_GAP + col * (self.tile_width + TILE_GAP);
+        let y = TILE_GAP + row * (self.tile_height + TILE_GAP);
+        for (tx, ty, pixel) in tile.enumerate_pixels() {
+            self.image
+                .put_pixel(x + tx, y + ty, Rgba([pixel[0], pixel[1], pixel[2], 255]));
+        }
+
+        self.draw_label(x, y + self.tile_height, &format_timestamp(time_secs));
+        Ok(())
+    }
+
+    /// The assembled image.
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    /// Write the sheet as a PNG.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        self.image
+            .save(path)
+            .map_err(|e| format!("Failed to save contact sheet {:?}: {}", path, e))
+    }
+
+    /// Draw `text` on a dark box in the bottom-left corner of the tile whose
+    /// bottom-left pixel row starts at (`x`, `bottom`).
+    fn draw_label(&mut self, x: u32, bottom: u32, text: &str) {
+        let padding = GLYPH_SCALE;
+        let advance = GLYPH_ADVANCE * GLYPH_SCALE;
+        let box_width = (text.len() as u32 * advance + padding).min(self.tile_width);
+        let box_height = (GLYPH_HEIGHT * GLYPH_SCALE + 2 * padding).min(self.tile_height);
+        let top = bottom - box_height;
+
+        for py in top..bottom {
+            for px in x..x + box_width {
+                self.image.put_pixel(px, py, LABEL_BACKGROUND);
+            }
+        }
+
+        for (i, c) in text.chars().enumerate() {
+            let Some(rows) = glyph(c) else { continue };
+            let gx = x + padding + i as u32 * advance;
+            for (gy, bits) in rows.iter().enumerate() {
+                for bit in 0..GLYPH_WIDTH {
+                    if bits & (0b100 >> bit) == 0 {
+                        continue;
+                    }
+                    for sy in 0..GLYPH_SCALE {
+                        for sx in 0..GLYPH_SCALE {
+                            let px = gx + bit * GLYPH_SCALE + sx;
+                            let py = top + padding + gy as u32 * GLYPH_SCALE + sy;
+                            if px < x + box_width && py < bottom {
+                                self.image.put_pixel(px, py, LABEL_COLOR);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Format seconds as `m:ss.s`.
+fn format_timestamp(time_secs: f32) -> String {
+    let tenths = (time_secs.max(0.0) * 10.0).round() as u32;
+    format!("{}:{:02}.{}", tenths / 600, (tenths / 10) % 60, tenths % 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid() {
+        let grid: ContactSheetGrid = "4x3".parse().unwrap();
+        assert_eq!(grid, ContactSheetGrid::new(4, 3).unwrap());
+        assert_eq!(grid.to_string(), "4x3");
+        assert!("4".parse::<ContactSheetGrid>().is_err());
+        assert!("0x3".parse::<ContactSheetGrid>().is_err());
+        assert!("axb".parse::<ContactSheetGrid>().is_err());
+        assert!("17x16".parse::<ContactSheetGrid>().is_err());
+        assert!("100000x100000".parse::<ContactSheetGrid>().is_err());
+    }
+
+    #[test]
+    fn test_new_rejects_out_of_range_grids() {
+        assert!(ContactSheetGrid::new(0, 1).is_err());
+        assert!(ContactSheetGrid::new(70000, 70000).is_err());
+        assert!(ContactSheetGrid::new(16, 17).is_err());
+        assert_eq!(ContactSheetGrid::new(16, 16).unwrap().tile_count(), 256);
+    }
+
+    #[test]
+    fn test_sample_frames_are_evenly_spaced() {
+        let grid = ContactSheetGrid::new(2, 2).unwrap();
+        assert_eq!(grid.sample_frames(100).unwrap(), [12, 37, 62, 87]);
+        assert_eq!(grid.sample_frames(4).unwrap(), [0, 1, 2, 3]);
+        assert!(grid.sample_frames(3).is_err());
+    }
+
+    #[test]
+    fn test_two_by_two_sheet_dimensions_and_placement() {
+        let grid = ContactSheetGrid::new(2, 2).unwrap();
+        let (tile_width, tile_height) = grid.tile_size(1920, 1080);
+        assert_eq!((tile_width, tile_height), (320, 180));
+
+        let mut sheet = ContactSheet::new(grid, tile_width, tile_height);
+        for index in 0..4 {
+            let shade = 60 * (index as u8 + 1);
+            let tile: Vec<u8> = (0..tile_width * tile_height)
+                .flat_map(|_| [shade, 0, 0, 255])
+                .collect();
+            sheet.add_tile(index, &tile, index as f32 * 1.5).unwrap();
+        }
+
+        let image = sheet.image();
+        assert_eq!(
+            image.dimensions(),
+            (2 * 320 + 3 * TILE_GAP, 2 * 180 + 3 * TILE_GAP)
+        );
+        // Top-right corner of the last tile, away from its label
+        let x = TILE_GAP * 2 + 320 + 319;
+        let y = TILE_GAP * 2 + 180;
+        assert_eq!(image.get_pixel(x, y).0, [240, 0, 0, 255]);
+        // Gap between tiles
+        assert_eq!(image.get_pixel(TILE_GAP + 320, 10).0, BACKGROUND.0);
+        assert!(sheet.add_tile(4, &[], 0.0).is_err());
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0), "0:00.0");
+        assert_eq!(format_timestamp(65.34), "1:05.3");
+    }
+}
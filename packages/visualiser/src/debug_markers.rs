@@ -3,7 +3,9 @@
 //! This module provides infrastructure for rendering debug markers that help
 //! visualize event timing and distribution in the scene.
 
+use crate::bitmap_font::{text_strokes, GLYPH_HEIGHT};
 use crate::event_stream::EventStream;
+use crate::musical_time::MusicalTimeStructure;
 use std::cell::RefCell;
 use std::sync::Arc;
 
@@ -18,6 +20,94 @@ pub struct DebugMarker {
     pub size: f32,
     /// Time at which the marker should disappear (in beats)
     pub expire_at_beat: f32,
+    /// Geometry drawn for the marker
+    pub shape: MarkerShape,
+    /// Optional text label, drawn to the right of the marker
+    pub label: Option<String>,
+}
+
+/// Number of segments used to draw a dot marker outline.
+const DOT_SEGMENTS: usize = 12;
+
+/// Label font pixel size as a fraction of the marker size.
+const LABEL_PIXEL_SCALE: f32 = 0.4;
+
+impl DebugMarker {
+    /// World-space line segments (`[start xyz, end xyz]`) outlining the marker
+    /// and its label, for drawing with the polyline pipeline.
+    pub fn segments(&self) -> Vec<[f32; 6]> {
+        let [x, y, z] = self.position;
+        let r = self.size;
+        let seg = |ax: f32, ay: f32, bx: f32, by: f32| [x + ax, y + ay, z, x + bx, y + by, z];
+        let mut segments = self.shape_segments(seg, r);
+
+        // Label text to the right, vertically centred on the marker
+        if let Some(label) = &self.label {
+            let pixel = r * LABEL_PIXEL_SCALE;
+            let (left, top) = (r * 1.5, GLYPH_HEIGHT as f32 * 0.5 * pixel);
+            segments.extend(text_strokes(label).into_iter().map(|[x0, y0, x1, y1]| {
+                seg(
+                    left + x0 * pixel,
+                    top - y0 * pixel,
+                    left + x1 * pixel,
+                    top - y1 * pixel,
+                )
+            }));
+        }
+        segments
+    }
+
+    fn shape_segments(
+        &self,
+        seg: impl Fn(f32, f32, f32, f32) -> [f32; 6],
+        r: f32,
+    ) -> Vec<[f32; 6]> {
+        match self.shape {
+            MarkerShape::Dot => (0..DOT_SEGMENTS)
+                .map(|i| {
+                    let a0 = std::f32::consts::TAU * i as f32 / DOT_SEGMENTS as f32;
+                    let a1 = std::f32::consts::TAU * (i + 1) as f32 / DOT_SEGMENTS as f32;
+                    seg(r * a0.cos(), r * a0.sin(), r * a1.cos(), r * a1.sin())
+                })
+                .collect(),
+            MarkerShape::Cross => vec![seg(-r, -r, r, r), seg(-r, r, r, -r)],
+            MarkerShape::Line => vec![seg(0.0, -r, 0.0, r)],
+            MarkerShape::Triangle => {
+                let (half_w, low) = (r * 0.866, -r * 0.5);
+                vec![
+                    seg(0.0, r, half_w, low),
+                    seg(half_w, low, -half_w, low),
+                    seg(-half_w, low, 0.0, r),
+                ]
+            }
+        }
+    }
+}
+
+/// Geometry drawn for each debug marker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MarkerShape {
+    /// Small circle
+    #[default]
+    Dot,
+    /// Diagonal cross
+    Cross,
+    /// Vertical tick
+    Line,
+    /// Upward-pointing triangle
+    Triangle,
+}
+
+impl MarkerShape {
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "dot" | "circle" => MarkerShape::Dot,
+            "cross" | "x" => MarkerShape::Cross,
+            "line" | "tick" => MarkerShape::Line,
+            "triangle" | "tri" => MarkerShape::Triangle,
+            _ => MarkerShape::default(),
+        }
+    }
 }
 
 /// How markers should be spread in space when multiple events occur.
@@ -59,6 +149,13 @@ pub struct ShowEventsOptions {
     pub spread: MarkerSpreadMode,
     /// Spacing between markers when spread
     pub spread_spacing: f32,
+    /// Snap marker display times to the nearest grid subdivision, in beats
+    /// (1.0 = beats, 0.5 = eighths). None disables snapping.
+    pub snap_to_grid: Option<f32>,
+    /// Marker geometry
+    pub shape: MarkerShape,
+    /// Optional text label attached to each marker
+    pub label: Option<String>,
 }
 
 impl Default for ShowEventsOptions {
@@ -69,6 +166,9 @@ impl Default for ShowEventsOptions {
             duration_beats: 0.25,
             spread: MarkerSpreadMode::default(),
             spread_spacing: 0.1,
+            snap_to_grid: None,
+            shape: MarkerShape::default(),
+            label: None,
         }
     }
 }
@@ -114,14 +214,28 @@ impl DebugMarkerLayer {
     }
 
     /// Update markers: remove expired ones and add new ones from requests.
-    pub fn update(&mut self, current_beat: f32, bpm: f32) {
+    ///
+    /// `musical_time`, when available, defines the grid used by `snap_to_grid`;
+    /// otherwise the grid is a constant `bpm` starting at time zero.
+    pub fn update(
+        &mut self,
+        current_beat: f32,
+        bpm: f32,
+        musical_time: Option<&MusicalTimeStructure>,
+    ) {
         // Remove expired markers
         self.markers.retain(|m| m.expire_at_beat > current_beat);
 
         // Process pending requests
         let requests = take_pending_requests();
         for request in requests {
-            Self::generate_markers_for_request(&mut self.markers, &request, current_beat, bpm);
+            Self::generate_markers_for_request(
+                &mut self.markers,
+                &request,
+                current_beat,
+                bpm,
+                musical_time,
+            );
         }
     }
 
@@ -135,6 +249,7 @@ impl DebugMarkerLayer {
         request: &DebugMarkerRequest,
         current_beat: f32,
         bpm: f32,
+        musical_time: Option<&MusicalTimeStructure>,
     ) {
         let events = &request.events.events;
         let options = &request.options;
@@ -144,17 +259,23 @@ impl DebugMarkerLayer {
         let lookback_seconds = options.duration_beats * seconds_per_beat;
         let current_time = current_beat * seconds_per_beat;
 
+        // Display time of each event, snapped to the grid when requested
+        let display_time = |time: f32| match options.snap_to_grid {
+            Some(subdivision) => snap_time_to_grid(time, subdivision, bpm, musical_time),
+            None => time,
+        };
+
         // Find events within the lookback window
         let recent_events: Vec<_> = events
             .iter()
             .enumerate()
-            .filter(|(_, e)| {
-                let event_time = e.time;
-                event_time <= current_time && event_time >= current_time - lookback_seconds
+            .map(|(idx, e)| (idx, display_time(e.time)))
+            .filter(|(_, event_time)| {
+                *event_time <= current_time && *event_time >= current_time - lookback_seconds
             })
             .collect();
 
-        for (idx, event) in recent_events {
+        for (idx, event_time) in recent_events {
             let position = Self::calculate_marker_position(
                 idx,
                 events.len(),
@@ -163,7 +284,7 @@ impl DebugMarkerLayer {
             );
 
             // Calculate remaining duration based on event age
-            let event_age = current_time - event.time;
+            let event_age = current_time - event_time;
             let remaining_beats = options.duration_beats - (event_age / seconds_per_beat);
 
             if remaining_beats > 0.0 {
@@ -177,6 +298,8 @@ impl DebugMarkerLayer {
                     color,
                     size: options.size * (0.5 + 0.5 * alpha_scale),
                     expire_at_beat: current_beat + remaining_beats,
+                    shape: options.shape,
+                    label: options.label.clone(),
                 });
             }
         }
@@ -204,6 +327,30 @@ impl DebugMarkerLayer {
     }
 }
 
+/// Snap a time (seconds) to the nearest multiple of `subdivision` beats.
+///
+/// Uses the musical time segment covering `time` when available, so the grid
+/// follows the segment's tempo and phase; otherwise a constant `bpm` grid
+/// anchored at zero.
+pub fn snap_time_to_grid(
+    time: f32,
+    subdivision: f32,
+    bpm: f32,
+    musical_time: Option<&MusicalTimeStructure>,
+) -> f32 {
+    if subdivision <= 0.0 {
+        return time;
+    }
+    let (phase_offset, period) = match musical_time.and_then(|mt| mt.segment_at(time)) {
+        Some(segment) => (segment.phase_offset, 60.0 / segment.bpm),
+        None if bpm > 0.0 => (0.0, 60.0 / bpm),
+        None => return time,
+    };
+    let beat = (time - phase_offset) / period;
+    let snapped_beat = (beat / subdivision).round() * subdivision;
+    phase_offset + snapped_beat * period
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,6 +379,40 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_marker_shape_from_str() {
+        assert_eq!(MarkerShape::from_str("cross"), MarkerShape::Cross);
+        assert_eq!(MarkerShape::from_str("Line"), MarkerShape::Line);
+        assert_eq!(MarkerShape::from_str("triangle"), MarkerShape::Triangle);
+        assert_eq!(MarkerShape::from_str("unknown"), MarkerShape::Dot);
+    }
+
+    #[test]
+    fn test_marker_segments_per_shape() {
+        let mut marker = DebugMarker {
+            position: [1.0, 0.5, 0.0],
+            color: [1.0; 4],
+            size: 0.1,
+            expire_at_beat: 1.0,
+            shape: MarkerShape::Cross,
+            label: None,
+        };
+        assert_eq!(marker.segments().len(), 2);
+        marker.shape = MarkerShape::Line;
+        assert_eq!(marker.segments(), vec![[1.0, 0.4, 0.0, 1.0, 0.6, 0.0]]);
+        marker.shape = MarkerShape::Triangle;
+        assert_eq!(marker.segments().len(), 3);
+        marker.shape = MarkerShape::Dot;
+        assert_eq!(marker.segments().len(), DOT_SEGMENTS);
+
+        // A label adds its strokes to the right of the marker
+        marker.label = Some("K".to_string());
+        let segments = marker.segments();
+        let label = &segments[DOT_SEGMENTS..];
+        assert_eq!(label.len(), crate::bitmap_font::text_strokes("K").len());
+        assert!(label.iter().all(|s| s[0] > 1.1 && s[3] > 1.1));
+    }
+
     #[test]
     fn test_default_options() {
         let opts = ShowEventsOptions::default();
@@ -245,4 +426,44 @@ mod tests {
         let layer = DebugMarkerLayer::new();
         assert!(layer.markers().is_empty());
     }
+
+    fn single_event_request(time: f32, snap_to_grid: Option<f32>) -> DebugMarkerRequest {
+        use crate::event_stream::{Event, PickEventsOptions};
+        DebugMarkerRequest {
+            events: Arc::new(EventStream::new(
+                vec![Event::new(time, 1.0)],
+                "test".to_string(),
+                PickEventsOptions::default(),
+            )),
+            options: ShowEventsOptions {
+                duration_beats: 1.0,
+                snap_to_grid,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_snap_to_grid_displays_marker_on_beat() {
+        let bpm = 120.0;
+        assert!((snap_time_to_grid(0.48, 1.0, bpm, None) - 0.5).abs() < 1e-6);
+        assert!((snap_time_to_grid(0.62, 0.5, bpm, None) - 0.5).abs() < 1e-6);
+
+        // Unsnapped: the 0.48s event is already visible at 0.49s.
+        let mut layer = DebugMarkerLayer::new();
+        add_marker_request(single_event_request(0.48, None));
+        layer.update(0.49 * bpm / 60.0, bpm, None);
+        assert_eq!(layer.markers().len(), 1);
+
+        // Snapped: it only appears at the 0.5s beat, at full strength.
+        let mut layer = DebugMarkerLayer::new();
+        add_marker_request(single_event_request(0.48, Some(1.0)));
+        layer.update(0.49 * bpm / 60.0, bpm, None);
+        assert!(layer.markers().is_empty());
+
+        add_marker_request(single_event_request(0.48, Some(1.0)));
+        layer.update(0.5 * bpm / 60.0, bpm, None);
+        assert_eq!(layer.markers().len(), 1);
+        assert!((layer.markers()[0].color[3] - 1.0).abs() < 1e-4);
+    }
 }
@@ -295,7 +295,7 @@ fn apply_noise(vertices: &mut [Vertex], scale: f32, amplitude: f32, seed: u32) {
 /// Deterministic 3D noise function using hash-based approach.
 ///
 /// Returns a value in range [-1, 1].
-fn deterministic_noise_3d(x: f32, y: f32, z: f32, seed: u32) -> f32 {
+pub(crate) fn deterministic_noise_3d(x: f32, y: f32, z: f32, seed: u32) -> f32 {
     // Use integer grid points and interpolate
     let ix = x.floor() as i32;
     let iy = y.floor() as i32;
@@ -430,6 +430,39 @@ mod tests {
         assert!((result[1].position[1] - 0.0).abs() < 1e-5);
     }
 
+    #[test]
+    fn test_wave_on_subdivided_plane_is_smoother() {
+        use crate::gpu::mesh::create_plane_geometry;
+
+        // Distinct heights a wave along X gives the plane's vertices
+        fn distinct_heights(subdivisions: u32) -> usize {
+            let (vertices, _) = create_plane_geometry(subdivisions);
+            let deformations = vec![Deformation::Wave {
+                axis: DeformAxis::X,
+                direction: DeformAxis::Y,
+                amplitude: 0.2,
+                frequency: 1.5,
+                phase: 0.3,
+            }];
+            let mut heights: Vec<i32> = apply_deformations(&vertices, &deformations)
+                .iter()
+                .map(|v| (v.position[1] * 1e4).round() as i32)
+                .collect();
+            heights.sort_unstable();
+            heights.dedup();
+            heights.len()
+        }
+
+        let (coarse, _) = create_plane_geometry(1);
+        let (fine, _) = create_plane_geometry(16);
+        assert_eq!(coarse.len(), 4);
+        assert_eq!(fine.len(), 17 * 17);
+
+        // Only the two corner columns move on the coarse plane
+        assert_eq!(distinct_heights(1), 2);
+        assert_eq!(distinct_heights(16), 17);
+    }
+
     #[test]
     fn test_noise_determinism() {
         let vertices = make_test_vertices();
@@ -0,0 +1,53 @@
+//! Error type for the public library API.
+//!
+//! Embedders can match on the failure kind instead of parsing message strings.
+//! Display output is the plain message, matching the strings these APIs
+//! returned before.
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::render_job::{RenderError, RenderPhase};
+
+/// Errors returned by the script engine, render jobs and the CLI.
+#[derive(Debug, thiserror::Error)]
+pub enum VisualiserError {
+    /// The script failed to parse.
+    #[error("{0}")]
+    ScriptCompile(String),
+    /// The script parsed but failed while running.
+    #[error("{0}")]
+    ScriptRuntime(String),
+    /// No Signal variable with this name exists in the script.
+    #[error("Signal '{0}' not found in scope")]
+    SignalNotFound(String),
+    /// A render job or batch config is invalid.
+    #[error("{0}")]
+    InvalidConfig(String),
+    /// One or more jobs in a batch failed.
+    #[error("{0}")]
+    Batch(String),
+    /// Rendering failed in a specific phase.
+    #[cfg(not(target_arch = "wasm32"))]
+    #[error(transparent)]
+    Render(#[from] RenderError),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl VisualiserError {
+    /// Prefix the message with the render phase it occurred in, keeping the variant.
+    /// Render errors already carry their phase and I/O errors are left as is.
+    pub fn with_phase(self, phase: RenderPhase) -> Self {
+        let tag = |message: String| format!("[{}] {}", phase, message);
+        match self {
+            VisualiserError::ScriptCompile(m) => VisualiserError::ScriptCompile(tag(m)),
+            VisualiserError::ScriptRuntime(m) => VisualiserError::ScriptRuntime(tag(m)),
+            VisualiserError::InvalidConfig(m) => VisualiserError::InvalidConfig(tag(m)),
+            VisualiserError::Batch(m) => VisualiserError::Batch(tag(m)),
+            other => other,
+        }
+    }
+}
+
+/// Result alias for the public library API.
+pub type Result<T, E = VisualiserError> = std::result::Result<T, E>;
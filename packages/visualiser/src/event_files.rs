@@ -0,0 +1,174 @@
+//! Authored event streams loaded from a JSON file (`--events <file.json>`).
+//!
+//! The file maps stream names to event lists. Each event is either a bare
+//! time in seconds or an object with a `time` and optional `velocity` (0-1,
+//! the event weight, default 1) and `label`:
+//!
+//! ```json
+//! {
+//!   "kicks": [0.5, 1.0, 1.5],
+//!   "drops": [{ "time": 32.0, "velocity": 1.0, "label": "first drop" }]
+//! }
+//! ```
+//!
+//! Streams are registered as authored event streams, so scripts read them as
+//! `inputs.customEvents["kicks"]`, exactly like streams authored in the web
+//! lab. A label becomes the event's `source`; unlabelled events get
+//! `authored:<name>`.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::event_rhai::store_authored_event_stream;
+use crate::event_stream::{Event, EventStream, PickEventsOptions};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum EventEntry {
+    Time(f32),
+    Full {
+        time: f32,
+        #[serde(default)]
+        velocity: Option<f32>,
+        #[serde(default)]
+        label: Option<String>,
+    },
+}
+
+/// Read and parse an events file. See [`parse_event_streams`].
+pub fn load_events_file(path: &Path) -> Result<Vec<(String, EventStream)>, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read events file {:?}: {}", path, e))?;
+    parse_event_streams(&json).map_err(|e| format!("Invalid events file {:?}: {}", path, e))
+}
+
+/// Parse `{ "<stream name>": [<event>, ...], ... }` into named event
+/// streams, sorted by name.
+///
+/// Stream names must be non-empty and free of quotes and backslashes (they
+/// are embedded in the generated `inputs.customEvents["..."]` code). Times
+/// must be finite and non-negative, velocities within 0-1.
+pub fn parse_event_streams(json: &str) -> Result<Vec<(String, EventStream)>, String> {
+    let streams: BTreeMap<String, Vec<EventEntry>> = serde_json::from_str(json)
+        .map_err(|e| format!("expected an object of stream name -> event list ({})", e))?;
+    if streams.is_empty() {
+        return Err("no event streams defined".to_string());
+    }
+
+    streams
+        .into_iter()
+        .map(|(name, entries)| {
+            if name.trim().is_empty() || name.contains(['"', '\\']) {
+                return Err(format!("invalid stream name {:?}", name));
+            }
+            let source = format!("authored:{}", name);
+            let events = entries
+                .into_iter()
+                .map(|entry| {
+                    let (time, velocity, label) = match entry {
+                        EventEntry::Time(time) => (time, None, None),
+                        EventEntry::Full {
+                            time,
+                            velocity,
+                            label,
+                        } => (time, velocity, label),
+                    };
+                    if !(time.is_finite() && time >= 0.0) {
+                        return Err(format!("stream {:?}: invalid event time {}", name, time));
+                    }
+                    let weight = velocity.unwrap_or(1.0);
+                    if !(0.0..=1.0).contains(&weight) {
+                        return Err(format!(
+                            "stream {:?}: velocity {} at {}s is outside 0-1",
+                            name, weight, time
+                        ));
+                    }
+                    Ok(Event::new(time, weight)
+                        .with_source(label.unwrap_or_else(|| source.clone())))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            let stream = EventStream::new(events, source, PickEventsOptions::default());
+            Ok((name, stream))
+        })
+        .collect()
+}
+
+/// Register streams as authored event streams. Call after any package has
+/// been applied (which resets authored streams) and before `load_script`,
+/// so the `inputs.customEvents` accessors are generated.
+pub fn apply_event_streams(streams: &[(String, EventStream)]) {
+    for (name, stream) in streams {
+        store_authored_event_stream(name.clone(), stream.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_rhai::clear_authored_event_streams;
+    use crate::input::{BandSignalMap, SignalMap};
+    use crate::visualiser::VisualiserState;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_events_file_registers_named_streams() {
+        let streams = parse_event_streams(
+            r#"{
+                "kicks": [1.5, 0.5, 1.0],
+                "drops": [{ "time": 4.0, "velocity": 0.75, "label": "first drop" }]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            streams
+                .iter()
+                .map(|(n, s)| (n.as_str(), s.len()))
+                .collect::<Vec<_>>(),
+            vec![("drops", 1), ("kicks", 3)]
+        );
+
+        clear_authored_event_streams();
+        apply_event_streams(&streams);
+        let mut state = VisualiserState::new();
+        state
+            .try_load_script(
+                r#"
+                let cube;
+                fn init(ctx) {
+                    cube = mesh.cube();
+                    scene.add(cube);
+                }
+                fn update(dt, frame) {
+                    let kicks = inputs.customEvents["kicks"];
+                    let drops = inputs.customEvents["drops"];
+                    cube.position.x = kicks.get(0).time;
+                    cube.position.y = drops.get(0).weight;
+                    cube.position.z = kicks.len() + drops.len();
+                }
+            "#,
+            )
+            .unwrap();
+        clear_authored_event_streams();
+
+        let signals: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        state.update(0.05, None, None, &signals, &bands, &signals, None);
+        let (_, cube) = state.scene_graph().scene_entities().next().unwrap();
+        let position = cube.transform().position;
+        // Events are time-ordered regardless of file order.
+        assert!((position.x - 0.5).abs() < 1e-6);
+        assert!((position.y - 0.75).abs() < 1e-6);
+        assert!((position.z - 4.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_events_file_validation() {
+        assert!(parse_event_streams("[1.0, 2.0]").is_err());
+        assert!(parse_event_streams("{}").is_err());
+        assert!(parse_event_streams(r#"{ "kicks": [-1.0] }"#).is_err());
+        assert!(parse_event_streams(r#"{ "kicks": [{ "time": 1.0, "velocity": 2.0 }] }"#).is_err());
+        assert!(parse_event_streams(r#"{ "bad\"name": [1.0] }"#).is_err());
+    }
+}
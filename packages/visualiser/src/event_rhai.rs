@@ -341,6 +341,11 @@ pub fn register_event_api(engine: &mut Engine) {
         es.beat_phase_between()
     });
 
+    // === Payload Method ===
+    engine.register_fn("held_weight", |es: &mut EventStream| -> Signal {
+        es.held_weight()
+    });
+
     // === EventStream filtering methods ===
     engine.register_fn(
         "filter_time",
@@ -370,6 +375,13 @@ pub fn register_event_api(engine: &mut Engine) {
         es.limit(max_events as usize)
     });
 
+    engine.register_fn("shift", |es: &mut EventStream, seconds: f32| {
+        es.shift(seconds)
+    });
+    engine.register_fn("shift", |es: &mut EventStream, seconds: i64| {
+        es.shift(seconds as f32)
+    });
+
     // === EventStream probe method ===
     // Converts to signal and attaches a debug probe for analysis visualization.
     // Returns a Signal (not EventStream) since the probe wraps a signal.
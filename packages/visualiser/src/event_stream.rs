@@ -415,6 +415,17 @@ impl EventStream {
         crate::signal::Signal::from_events_phase_between(self.events.clone())
     }
 
+    // =========================================================================
+    // Payload Method
+    // =========================================================================
+
+    /// Weight of the most recent event, held until the next one (0 before the
+    /// first). For authored and MIDI streams the weight is the velocity, so
+    /// multiplying by this scales any event-driven effect by the hit strength.
+    pub fn held_weight(&self) -> crate::signal::Signal {
+        crate::signal::Signal::from_events_weight_held(self.events.clone())
+    }
+
     // =========================================================================
     // Filtering Methods
     // =========================================================================
@@ -473,6 +484,33 @@ impl EventStream {
             options: self.options.clone(),
         }
     }
+
+    /// Offset every event by `seconds` (positive = later, negative = earlier,
+    /// for anticipating hits).
+    ///
+    /// Events shifted before 0 are dropped rather than clamped, so a lookahead
+    /// never stacks several events on the first frame. Beat info is cleared,
+    /// since it described the original times.
+    pub fn shift(&self, seconds: f32) -> EventStream {
+        let shifted: Vec<Event> = self
+            .events
+            .iter()
+            .map(|e| Event {
+                time: e.time + seconds,
+                beat_position: None,
+                beat_phase: None,
+                ..e.clone()
+            })
+            .filter(|e| e.time >= 0.0)
+            .collect();
+
+        EventStream {
+            id: EventStreamId::new(),
+            events: Arc::new(shifted),
+            source_description: format!("{} [shift {:+.3}s]", self.source_description, seconds),
+            options: self.options.clone(),
+        }
+    }
 }
 
 /// A single event in an EventStream.
@@ -758,6 +796,28 @@ mod tests {
         assert!((nearest.time - 2.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_shift_moves_events_and_drops_negative_times() {
+        let events = vec![
+            Event::new(0.02, 0.5),
+            Event::new(0.5, 0.8),
+            Event::with_beat_info(1.0, 0.6, 2.0, 0.0),
+        ];
+        let stream = EventStream::new(events, "test".to_string(), PickEventsOptions::default());
+
+        let early = stream.shift(-0.05);
+        assert_eq!(early.len(), 2);
+        let times: Vec<f32> = early.iter().map(|e| e.time).collect();
+        assert!((times[0] - 0.45).abs() < 1e-6);
+        assert!((times[1] - 0.95).abs() < 1e-6);
+        assert!((early.get(0).unwrap().weight - 0.8).abs() < 1e-6);
+        assert!(early.get(1).unwrap().beat_position.is_none());
+
+        let late = stream.shift(0.05);
+        assert_eq!(late.len(), 3);
+        assert!((late.get(0).unwrap().time - 0.07).abs() < 1e-6);
+    }
+
     #[test]
     fn test_time_span() {
         let events = vec![
@@ -141,6 +141,10 @@ pub enum ColorOperator {
     Posterize,
     /// Channel offset: RGB split / chromatic aberration.
     ChannelOffset,
+    /// Invert: 1 - rgb.
+    Invert,
+    /// Hue rotation by an angle in radians (chroma rotation about the grey axis).
+    HueRotate,
 }
 
 impl ColorOperator {
@@ -152,6 +156,8 @@ impl ColorOperator {
             ColorOperator::HsvShift => 2,
             ColorOperator::Posterize => 3,
             ColorOperator::ChannelOffset => 4,
+            ColorOperator::Invert => 5,
+            ColorOperator::HueRotate => 6,
         }
     }
 
@@ -164,6 +170,8 @@ impl ColorOperator {
             "channel_offset" | "channeloffset" | "rgb_split" | "chromatic" => {
                 ColorOperator::ChannelOffset
             }
+            "invert" => ColorOperator::Invert,
+            "hue_rotate" | "huerotate" => ColorOperator::HueRotate,
             _ => ColorOperator::None,
         }
     }
@@ -251,6 +259,55 @@ impl FeedbackBlend {
     }
 }
 
+/// Region of the frame that feedback applies to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FeedbackMaskShape {
+    /// Whole frame (default).
+    #[default]
+    Full,
+    /// Circle around a center; radius is in units of frame height.
+    Circle,
+    /// Axis-aligned rectangle around a center; size is in UV units.
+    Rect,
+}
+
+impl FeedbackMaskShape {
+    /// Convert to GPU-compatible u32.
+    pub fn to_u32(self) -> u32 {
+        match self {
+            FeedbackMaskShape::Full => 0,
+            FeedbackMaskShape::Circle => 1,
+            FeedbackMaskShape::Rect => 2,
+        }
+    }
+}
+
+/// Feedback mask: outside the region the output is the current frame, untouched.
+///
+/// All numeric parameters can be either static f32 values or dynamic Signals.
+#[derive(Clone, Debug, Default)]
+pub struct FeedbackMask {
+    pub shape: FeedbackMaskShape,
+    /// Region center in UV coordinates [x, y].
+    pub center: [SignalOrF32; 2],
+    /// Circle radius (only [0] is used) or rect width/height.
+    pub size: [SignalOrF32; 2],
+    /// Width of the soft edge inside the region boundary (0 = hard edge).
+    pub softness: SignalOrF32,
+}
+
+impl FeedbackMask {
+    fn signals(&self) -> [&SignalOrF32; 5] {
+        [
+            &self.center[0],
+            &self.center[1],
+            &self.size[0],
+            &self.size[1],
+            &self.softness,
+        ]
+    }
+}
+
 /// Warp parameters that can be signal-driven.
 ///
 /// All numeric parameters can be either static f32 values or dynamic Signals.
@@ -266,6 +323,8 @@ pub struct WarpParams {
     pub translate: [SignalOrF32; 2],
     /// Frequency for noise warp.
     pub frequency: SignalOrF32,
+    /// Drift speed of the noise warp field (UV units per second at frequency 1).
+    pub speed: SignalOrF32,
     /// Edge falloff (0 = no falloff, 1 = fade at edges).
     pub falloff: SignalOrF32,
     /// Seed for deterministic noise warp (always static).
@@ -284,6 +343,7 @@ impl WarpParams {
                 self.translate[1].evaluate(ctx),
             ],
             frequency: self.frequency.evaluate(ctx),
+            speed: self.speed.evaluate(ctx),
             falloff: self.falloff.evaluate(ctx),
             seed: self.seed,
         }
@@ -298,6 +358,7 @@ impl Default for WarpParams {
             rotation: SignalOrF32::Scalar(0.0),
             translate: [SignalOrF32::Scalar(0.0), SignalOrF32::Scalar(0.0)],
             frequency: SignalOrF32::Scalar(1.0),
+            speed: SignalOrF32::Scalar(0.0),
             falloff: SignalOrF32::Scalar(0.0),
             seed: 0,
         }
@@ -312,6 +373,7 @@ pub struct EvaluatedWarpParams {
     pub rotation: f32,
     pub translate: [f32; 2],
     pub frequency: f32,
+    pub speed: f32,
     pub falloff: f32,
     pub seed: u32,
 }
@@ -329,6 +391,8 @@ pub struct ColorParams {
     pub posterize_levels: SignalOrF32,
     /// Channel offset amount [x, y] for chromatic aberration.
     pub channel_offset: [SignalOrF32; 2],
+    /// Hue rotation angle in radians.
+    pub hue_rotation: SignalOrF32,
 }
 
 impl ColorParams {
@@ -346,6 +410,7 @@ impl ColorParams {
                 self.channel_offset[0].evaluate(ctx),
                 self.channel_offset[1].evaluate(ctx),
             ],
+            hue_rotation: self.hue_rotation.evaluate(ctx),
         }
     }
 }
@@ -361,6 +426,7 @@ impl Default for ColorParams {
             ],
             posterize_levels: SignalOrF32::Scalar(8.0),
             channel_offset: [SignalOrF32::Scalar(0.0), SignalOrF32::Scalar(0.0)],
+            hue_rotation: SignalOrF32::Scalar(0.0),
         }
     }
 }
@@ -372,6 +438,7 @@ pub struct EvaluatedColorParams {
     pub hsv_shift: [f32; 3],
     pub posterize_levels: f32,
     pub channel_offset: [f32; 2],
+    pub hue_rotation: f32,
 }
 
 /// A single warp operation in a chain.
@@ -417,6 +484,13 @@ pub struct FeedbackConfig {
     /// Blend opacity (0 = no feedback visible, 1 = full feedback).
     pub opacity: SignalOrF32,
 
+    /// Per-frame translation of the previous frame in UV units (x right, y down),
+    /// applied after the warp chain. Nonzero values smear trails in one direction.
+    pub offset: [SignalOrF32; 2],
+
+    /// Region feedback applies to (full frame by default).
+    pub mask: FeedbackMask,
+
     /// Where in the pipeline feedback samples from.
     pub sampling_mode: FeedbackSamplingMode,
 }
@@ -499,6 +573,18 @@ impl FeedbackConfig {
         self
     }
 
+    /// Builder: set the per-frame echo offset (accepts f32 or Signal).
+    pub fn with_offset(mut self, dx: impl Into<SignalOrF32>, dy: impl Into<SignalOrF32>) -> Self {
+        self.offset = [dx.into(), dy.into()];
+        self
+    }
+
+    /// Builder: restrict feedback to a region of the frame.
+    pub fn with_mask(mut self, mask: FeedbackMask) -> Self {
+        self.mask = mask;
+        self
+    }
+
     /// Convert to GPU uniforms by evaluating all signal parameters.
     ///
     /// This method resolves any Signal values to their current f32 values
@@ -511,6 +597,21 @@ impl FeedbackConfig {
         uniforms.color_count = self.color_chain.len().min(MAX_COLOR_CHAIN) as u32;
         uniforms.blend_mode = self.blend.to_u32();
         uniforms.opacity = self.opacity.evaluate(ctx);
+        uniforms.offset_x = self.offset[0].evaluate(ctx);
+        uniforms.offset_y = self.offset[1].evaluate(ctx);
+        uniforms.time = ctx.time;
+
+        // Mask
+        uniforms.mask_shape = self.mask.shape.to_u32();
+        uniforms.mask_softness = self.mask.softness.evaluate(ctx);
+        uniforms.mask_center = [
+            self.mask.center[0].evaluate(ctx),
+            self.mask.center[1].evaluate(ctx),
+        ];
+        uniforms.mask_size = [
+            self.mask.size[0].evaluate(ctx),
+            self.mask.size[1].evaluate(ctx),
+        ];
 
         // Fill warp steps - evaluate each parameter
         for (i, step) in self.warp_chain.iter().take(MAX_WARP_CHAIN).enumerate() {
@@ -524,6 +625,9 @@ impl FeedbackConfig {
                 translate_y: params.translate[1],
                 frequency: params.frequency,
                 falloff: params.falloff,
+                speed: params.speed,
+                seed: params.seed,
+                _padding: [0.0; 2],
             };
         }
 
@@ -539,6 +643,8 @@ impl FeedbackConfig {
                 hsv_v: params.hsv_shift[2],
                 offset_x: params.channel_offset[0],
                 offset_y: params.channel_offset[1],
+                hue_rotation: params.hue_rotation,
+                _padding: [0.0; 3],
             };
         }
 
@@ -547,8 +653,12 @@ impl FeedbackConfig {
 
     /// Check if this config contains any signals that need evaluation.
     pub fn has_signals(&self) -> bool {
-        // Check opacity
-        if !self.opacity.is_scalar() {
+        // Check opacity, offset and mask
+        if !self.opacity.is_scalar()
+            || !self.offset[0].is_scalar()
+            || !self.offset[1].is_scalar()
+            || self.mask.signals().iter().any(|s| !s.is_scalar())
+        {
             return true;
         }
 
@@ -560,6 +670,7 @@ impl FeedbackConfig {
                 || !step.params.translate[0].is_scalar()
                 || !step.params.translate[1].is_scalar()
                 || !step.params.frequency.is_scalar()
+                || !step.params.speed.is_scalar()
                 || !step.params.falloff.is_scalar()
             {
                 return true;
@@ -575,6 +686,7 @@ impl FeedbackConfig {
                 || !step.params.hsv_shift[2].is_scalar()
                 || !step.params.channel_offset[0].is_scalar()
                 || !step.params.channel_offset[1].is_scalar()
+                || !step.params.hue_rotation.is_scalar()
             {
                 return true;
             }
@@ -596,8 +708,15 @@ impl FeedbackConfig {
             }
         }
 
-        // Opacity
+        // Opacity and offset
         extract(&self.opacity, &mut signals);
+        extract(&self.offset[0], &mut signals);
+        extract(&self.offset[1], &mut signals);
+
+        // Mask
+        for s in self.mask.signals() {
+            extract(s, &mut signals);
+        }
 
         // Warp chain
         for step in &self.warp_chain {
@@ -607,6 +726,7 @@ impl FeedbackConfig {
             extract(&step.params.translate[0], &mut signals);
             extract(&step.params.translate[1], &mut signals);
             extract(&step.params.frequency, &mut signals);
+            extract(&step.params.speed, &mut signals);
             extract(&step.params.falloff, &mut signals);
         }
 
@@ -619,6 +739,7 @@ impl FeedbackConfig {
             extract(&step.params.hsv_shift[2], &mut signals);
             extract(&step.params.channel_offset[0], &mut signals);
             extract(&step.params.channel_offset[1], &mut signals);
+            extract(&step.params.hue_rotation, &mut signals);
         }
 
         signals
@@ -639,6 +760,7 @@ impl FeedbackConfig {
 ///     .color.decay(0.95)
 ///     .blend.add()
 ///     .opacity(0.9)
+///     .offset(0.002, 0.0)
 ///     .sample_after_effects()
 ///     .build();
 /// ```
@@ -648,6 +770,8 @@ pub struct FeedbackBuilder {
     color_chain: Vec<ColorStep>,
     blend: FeedbackBlend,
     opacity: SignalOrF32,
+    offset: [SignalOrF32; 2],
+    mask: FeedbackMask,
     sampling_mode: FeedbackSamplingMode,
 }
 
@@ -689,6 +813,16 @@ impl FeedbackBuilder {
         self.opacity = opacity;
     }
 
+    /// Set the per-frame echo offset in UV units.
+    pub fn set_offset(&mut self, dx: SignalOrF32, dy: SignalOrF32) {
+        self.offset = [dx, dy];
+    }
+
+    /// Set the region feedback applies to.
+    pub fn set_mask(&mut self, mask: FeedbackMask) {
+        self.mask = mask;
+    }
+
     /// Set the sampling mode.
     pub fn set_sampling_mode(&mut self, mode: FeedbackSamplingMode) {
         self.sampling_mode = mode;
@@ -718,6 +852,8 @@ impl FeedbackBuilder {
             color_chain: self.color_chain.clone(),
             blend: self.blend,
             opacity: self.opacity.clone(),
+            offset: self.offset.clone(),
+            mask: self.mask.clone(),
             sampling_mode: self.sampling_mode,
         }
     }
@@ -858,6 +994,30 @@ impl WarpBuilder {
         self.0
     }
 
+    /// Add a time-animated noise displacement warp.
+    ///
+    /// `frequency` is the spatial scale of the field and `speed` how fast it
+    /// drifts; `seed` picks a different but reproducible field.
+    pub fn noise_animated(
+        mut self,
+        strength: impl Into<SignalOrF32>,
+        frequency: impl Into<SignalOrF32>,
+        speed: impl Into<SignalOrF32>,
+        seed: u32,
+    ) -> FeedbackBuilder {
+        self.0.push_warp(WarpStep::new(
+            WarpOperator::Noise,
+            WarpParams {
+                strength: strength.into(),
+                frequency: frequency.into(),
+                speed: speed.into(),
+                seed,
+                ..Default::default()
+            },
+        ));
+        self.0
+    }
+
     /// Add a shear warp.
     /// Parameters can be f32 or Signal for audio-reactive effects.
     pub fn shear(mut self, strength: impl Into<SignalOrF32>) -> FeedbackBuilder {
@@ -937,6 +1097,28 @@ impl ColorBuilder {
         ));
         self.0
     }
+
+    /// Add a color inversion effect.
+    pub fn invert(mut self) -> FeedbackBuilder {
+        self.0.push_color(ColorStep::new(
+            ColorOperator::Invert,
+            ColorParams::default(),
+        ));
+        self.0
+    }
+
+    /// Add a hue rotation effect (angle in radians).
+    /// Parameters can be f32 or Signal for audio-reactive effects.
+    pub fn hue_rotate(mut self, angle: impl Into<SignalOrF32>) -> FeedbackBuilder {
+        self.0.push_color(ColorStep::new(
+            ColorOperator::HueRotate,
+            ColorParams {
+                hue_rotation: angle.into(),
+                ..Default::default()
+            },
+        ));
+        self.0
+    }
 }
 
 /// Sub-builder for blend mode selection. Returned by `builder.blend`.
@@ -987,7 +1169,53 @@ impl BlendBuilder {
     }
 }
 
-/// GPU warp step (32 bytes = 2 × 16-byte blocks).
+/// Sub-builder for the feedback region. Returned by `builder.mask`.
+#[derive(Clone, Debug)]
+pub struct MaskBuilder(pub FeedbackBuilder);
+
+impl MaskBuilder {
+    /// Apply feedback only inside a circle (radius in units of frame height).
+    /// Parameters can be f32 or Signal for audio-reactive effects.
+    pub fn circle(
+        mut self,
+        center: [SignalOrF32; 2],
+        radius: impl Into<SignalOrF32>,
+        softness: impl Into<SignalOrF32>,
+    ) -> FeedbackBuilder {
+        self.0.set_mask(FeedbackMask {
+            shape: FeedbackMaskShape::Circle,
+            center,
+            size: [radius.into(), SignalOrF32::Scalar(0.0)],
+            softness: softness.into(),
+        });
+        self.0
+    }
+
+    /// Apply feedback only inside a rectangle (width/height in UV units).
+    /// Parameters can be f32 or Signal for audio-reactive effects.
+    pub fn rect(
+        mut self,
+        center: [SignalOrF32; 2],
+        size: [SignalOrF32; 2],
+        softness: impl Into<SignalOrF32>,
+    ) -> FeedbackBuilder {
+        self.0.set_mask(FeedbackMask {
+            shape: FeedbackMaskShape::Rect,
+            center,
+            size,
+            softness: softness.into(),
+        });
+        self.0
+    }
+
+    /// Apply feedback to the whole frame (default).
+    pub fn full(mut self) -> FeedbackBuilder {
+        self.0.set_mask(FeedbackMask::default());
+        self.0
+    }
+}
+
+/// GPU warp step (48 bytes = 3 × 16-byte blocks).
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Default, Pod, Zeroable)]
 pub struct GpuWarpStep {
@@ -1001,9 +1229,13 @@ pub struct GpuWarpStep {
     pub translate_y: f32,
     pub frequency: f32,
     pub falloff: f32,
+    // Block 3 (16 bytes)
+    pub speed: f32,
+    pub seed: u32,
+    pub _padding: [f32; 2],
 }
 
-/// GPU color step (32 bytes = 2 × 16-byte blocks).
+/// GPU color step (48 bytes = 3 × 16-byte blocks).
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Default, Pod, Zeroable)]
 pub struct GpuColorStep {
@@ -1017,25 +1249,41 @@ pub struct GpuColorStep {
     pub hsv_v: f32,
     pub offset_x: f32,
     pub offset_y: f32,
+    // Block 3 (16 bytes)
+    pub hue_rotation: f32,
+    pub _padding: [f32; 3],
 }
 
 /// GPU-compatible uniform buffer for feedback shader with chained transforms.
 ///
-/// Layout: header (16 bytes) + 4 warp steps (128 bytes) + 4 color steps (128 bytes) = 272 bytes.
+/// Layout: header (32 bytes), mask (32 bytes), 4 warp steps (192 bytes) and
+/// 4 color steps (192 bytes), 448 bytes in total.
 /// All blocks are 16-byte aligned for WGSL compatibility.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Pod, Zeroable)]
 pub struct FeedbackUniforms {
-    // Header (16 bytes)
+    // Header (32 bytes)
     pub warp_count: u32,
     pub color_count: u32,
     pub blend_mode: u32,
     pub opacity: f32,
-
-    // Warp steps array (4 × 32 = 128 bytes)
+    pub offset_x: f32,
+    pub offset_y: f32,
+    /// Seconds since start, for time-animated warps.
+    pub time: f32,
+    pub _padding: f32,
+
+    // Mask (32 bytes)
+    pub mask_shape: u32,
+    pub mask_softness: f32,
+    pub mask_center: [f32; 2],
+    pub mask_size: [f32; 2],
+    pub _mask_padding: [f32; 2],
+
+    // Warp steps array (4 × 48 = 192 bytes)
     pub warp_steps: [GpuWarpStep; MAX_WARP_CHAIN],
 
-    // Color steps array (4 × 32 = 128 bytes)
+    // Color steps array (4 × 48 = 192 bytes)
     pub color_steps: [GpuColorStep; MAX_COLOR_CHAIN],
 }
 
@@ -1046,12 +1294,36 @@ impl Default for FeedbackUniforms {
     }
 }
 
+impl FeedbackUniforms {
+    /// Evaluated value of a named parameter, for `dbg.plotParam("feedback", name)`:
+    /// `opacity`, `offsetX`/`offsetY`, the first warp step's `strength`,
+    /// `scale` or `rotation`, or the first decay step's `decay`.
+    pub fn param(&self, name: &str) -> Option<f32> {
+        let warp = self.warp_steps[..self.warp_count as usize].first();
+        match name {
+            "opacity" => Some(self.opacity),
+            "offsetX" => Some(self.offset_x),
+            "offsetY" => Some(self.offset_y),
+            "strength" => warp.map(|step| step.strength),
+            "scale" => warp.map(|step| step.scale),
+            "rotation" => warp.map(|step| step.rotation),
+            "decay" => self.color_steps[..self.color_count as usize]
+                .iter()
+                .find(|step| step.color_type == ColorOperator::Decay.to_u32())
+                .map(|step| step.decay_rate),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::signal_eval::EvalContext;
     use crate::signal_state::SignalState;
     use crate::signal_stats::StatisticsCache;
+    use crate::test_support::{headless_or_skip, step, HeadlessRenderer, RenderedFrame};
+    use crate::visualiser::VisualiserState;
     use std::collections::HashMap;
 
     /// Create a minimal EvalContext for testing.
@@ -1084,6 +1356,11 @@ mod tests {
             ColorOperator::from_str("channel_offset"),
             ColorOperator::ChannelOffset
         );
+        assert_eq!(ColorOperator::from_str("invert"), ColorOperator::Invert);
+        assert_eq!(
+            ColorOperator::from_str("hue_rotate"),
+            ColorOperator::HueRotate
+        );
     }
 
     #[test]
@@ -1102,10 +1379,10 @@ mod tests {
 
     #[test]
     fn test_uniform_size() {
-        // Header (16) + 4 warp steps (128) + 4 color steps (128) = 272 bytes
-        assert_eq!(std::mem::size_of::<FeedbackUniforms>(), 272);
-        assert_eq!(std::mem::size_of::<GpuWarpStep>(), 32);
-        assert_eq!(std::mem::size_of::<GpuColorStep>(), 32);
+        // Header (32) + mask (32) + 4 warp steps (192) + 4 color steps (192) = 448 bytes
+        assert_eq!(std::mem::size_of::<FeedbackUniforms>(), 448);
+        assert_eq!(std::mem::size_of::<GpuWarpStep>(), 48);
+        assert_eq!(std::mem::size_of::<GpuColorStep>(), 48);
     }
 
     #[test]
@@ -1242,4 +1519,218 @@ mod tests {
         assert_eq!(uniforms.blend_mode, 3); // Screen
         assert_eq!(uniforms.opacity, 0.85);
     }
+
+    #[test]
+    fn test_noise_warp_uniforms_carry_seed_speed_and_time() {
+        let (mut state, stats) = test_eval_ctx();
+        let inputs = HashMap::new();
+        let bands = HashMap::new();
+        let stems = HashMap::new();
+        let custom_signals = HashMap::new();
+        let composed_signals = HashMap::new();
+        let mut ctx = EvalContext::new(
+            2.5,
+            0.016,
+            0,
+            None,
+            &inputs,
+            &bands,
+            &stems,
+            &custom_signals,
+            &composed_signals,
+            &stats,
+            &mut state,
+            None,
+        );
+
+        let config = WarpBuilder(FeedbackBuilder::new())
+            .noise_animated(0.3, 6.0, 0.5, 7)
+            .build();
+
+        let uniforms = config.to_uniforms(&mut ctx);
+        assert_eq!(uniforms.warp_steps[0].warp_type, 4); // Noise
+        assert_eq!(uniforms.warp_steps[0].strength, 0.3);
+        assert_eq!(uniforms.warp_steps[0].frequency, 6.0);
+        assert_eq!(uniforms.warp_steps[0].speed, 0.5);
+        assert_eq!(uniforms.warp_steps[0].seed, 7);
+        assert_eq!(uniforms.time, 2.5);
+    }
+
+    #[test]
+    fn test_config_to_uniforms_offset() {
+        let (mut state, stats) = test_eval_ctx();
+        let inputs = HashMap::new();
+        let bands = HashMap::new();
+        let stems = HashMap::new();
+        let custom_signals = HashMap::new();
+        let composed_signals = HashMap::new();
+        let mut ctx = EvalContext::new(
+            0.0,
+            0.016,
+            0,
+            None,
+            &inputs,
+            &bands,
+            &stems,
+            &custom_signals,
+            &composed_signals,
+            &stats,
+            &mut state,
+            None,
+        );
+
+        let config = FeedbackConfig::new()
+            .with_warp(WarpOperator::Radial)
+            .with_offset(0.25, -0.1);
+
+        let uniforms = config.to_uniforms(&mut ctx);
+        assert_eq!(uniforms.warp_count, 1);
+        assert_eq!(uniforms.offset_x, 0.25);
+        assert_eq!(uniforms.offset_y, -0.1);
+        assert_eq!(FeedbackConfig::new().to_uniforms(&mut ctx).offset_x, 0.0);
+    }
+
+    #[test]
+    fn test_feedback_offset_shifts_previous_frame() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // Horizontal center of the red pixels on the middle row
+        let red_center = |frame: &RenderedFrame| {
+            let xs: Vec<u32> = (0..frame.width)
+                .filter(|&x| {
+                    let [r, g, _, _] = frame.pixel(x, frame.height / 2);
+                    r > g.saturating_add(64)
+                })
+                .collect();
+            assert!(!xs.is_empty(), "no red pixels on the middle row");
+            xs.iter().sum::<u32>() as f32 / xs.len() as f32
+        };
+
+        // One frame of a 0.25 UV offset moves the echo a quarter of the width right
+        let [first, echo] = render_cube_echo(&mut headless, ".offset(0.25, 0.0)");
+        let shift = red_center(&echo) - red_center(&first);
+        assert!((shift - 16.0).abs() <= 1.0, "echo moved {} px", shift);
+    }
+
+    /// Render a red cube for one frame, then only its feedback echo, with
+    /// `chain` added to the feedback builder (e.g. `.color.decay(0.9)`).
+    fn render_cube_echo(headless: &mut HeadlessRenderer, chain: &str) -> [RenderedFrame; 2] {
+        let mut state = VisualiserState::new();
+        let script = format!(
+            r#"
+            let cube = mesh.cube();
+            cube.color.r = 1.0;
+            cube.color.g = 0.0;
+            cube.color.b = 0.0;
+            let frames = 0;
+
+            fn init(ctx) {{
+                scene.add(cube);
+                feedback.enable(feedback.builder(){}.blend.max().opacity(1.0).build());
+            }}
+
+            fn update(dt, frame) {{
+                frames += 1;
+                if frames > 1 {{
+                    cube.position.x = 1000.0;
+                }}
+            }}
+        "#,
+            chain
+        );
+        assert!(state.load_script(&script));
+        headless.renderer.request_feedback_clear();
+
+        let mut render = || {
+            step(&mut state, 1.0 / 60.0);
+            headless.render(&state)
+        };
+        [render(), render()]
+    }
+
+    #[test]
+    fn test_feedback_hue_rotate() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // A full turn leaves the red echo as it is without any colour step
+        let [_, plain] = render_cube_echo(&mut headless, "");
+        let [_, echo] = render_cube_echo(&mut headless, ".color.hueRotate(2.0 * PI())");
+        let [r0, g0, b0, _] = plain.center();
+        let [r, g, b, _] = echo.center();
+        assert!(r0 > g0.saturating_mul(2), "plain echo {:?}", plain.center());
+        assert!(
+            r.abs_diff(r0) <= 2 && g.abs_diff(g0) <= 2 && b.abs_diff(b0) <= 2,
+            "2π rotation changed {:?} to {:?}",
+            plain.center(),
+            echo.center()
+        );
+
+        // Half a turn moves red to the opposite hue, cyan
+        let [_, echo] = render_cube_echo(&mut headless, ".color.hueRotate(PI())");
+        let [r, g, b, _] = echo.center();
+        assert!(g > r && b > r, "π rotation gave {:?}", echo.center());
+        assert!(g.abs_diff(b) <= 2, "π rotation gave {:?}", echo.center());
+    }
+
+    #[test]
+    fn test_feedback_mask_leaves_outside_untouched() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // The echo at the center survives when the mask covers it...
+        let [_, inside] = render_cube_echo(
+            &mut headless,
+            ".mask.circle(#{ x: 0.5, y: 0.5 }, 0.3, 0.05)",
+        );
+        let [r, g, _, _] = inside.center();
+        assert!(
+            r > g.saturating_mul(2),
+            "masked-in echo {:?}",
+            inside.center()
+        );
+
+        // ...and outside the mask the output is just the current scene (background)
+        let [_, outside] =
+            render_cube_echo(&mut headless, ".mask.circle(#{ x: 0.1, y: 0.1 }, 0.1, 0.0)");
+        let background = outside.pixel(32, 60);
+        let [r, g, b, _] = background;
+        assert!(r == g && g == b, "background {:?}", background);
+        assert_eq!(outside.center(), background);
+    }
+
+    #[test]
+    fn test_feedback_noise_warp_with_zero_strength_leaves_uvs_unchanged() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        let [_, plain] = render_cube_echo(&mut headless, "");
+        let [_, still] = render_cube_echo(
+            &mut headless,
+            ".warp.noise(#{ strength: 0.0, scale: 8.0, speed: 2.0, seed: 3 })",
+        );
+        assert!(
+            plain.pixels == still.pixels,
+            "zero-strength noise moved the echo"
+        );
+
+        // A nonzero strength does displace it
+        let [_, warped] = render_cube_echo(
+            &mut headless,
+            ".warp.noise(#{ strength: 1.0, scale: 8.0, speed: 2.0, seed: 3 })",
+        );
+        assert!(plain.pixels != warped.pixels);
+
+        // The field is seeded, so a rerun renders the same frame
+        let [_, rerun] = render_cube_echo(
+            &mut headless,
+            ".warp.noise(#{ strength: 1.0, scale: 8.0, speed: 2.0, seed: 3 })",
+        );
+        assert!(warped.pixels == rerun.pixels);
+    }
 }
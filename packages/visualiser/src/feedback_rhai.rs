@@ -3,8 +3,8 @@
 use rhai::{Dynamic, Engine, EvalAltResult};
 
 use crate::feedback::{
-    BlendBuilder, ColorBuilder, FeedbackBuilder, FeedbackConfig, FeedbackSamplingMode, SignalOrF32,
-    WarpBuilder,
+    BlendBuilder, ColorBuilder, FeedbackBuilder, FeedbackConfig, FeedbackSamplingMode, MaskBuilder,
+    SignalOrF32, WarpBuilder,
 };
 use crate::signal::Signal;
 
@@ -47,6 +47,22 @@ fn to_signal_or_f32(value: Dynamic) -> Result<SignalOrF32, Box<EvalAltResult>> {
     .into())
 }
 
+/// Convert a Rhai `#{ x, y }` map to a pair of SignalOrF32 (missing keys are 0).
+fn to_signal_or_f32_vec2(value: Dynamic) -> Result<[SignalOrF32; 2], Box<EvalAltResult>> {
+    let Some(map) = value.clone().try_cast::<rhai::Map>() else {
+        return Err(format!(
+            "Expected #{{ x, y }} map for feedback parameter, got {}",
+            value.type_name()
+        )
+        .into());
+    };
+    let component = |key: &str| match map.get(key) {
+        Some(v) => to_signal_or_f32(v.clone()),
+        None => Ok(SignalOrF32::Scalar(0.0)),
+    };
+    Ok([component("x")?, component("y")?])
+}
+
 /// Register the feedback builder API with the Rhai engine.
 pub fn register_feedback_builder_api(engine: &mut Engine) {
     // Register builder types
@@ -54,6 +70,7 @@ pub fn register_feedback_builder_api(engine: &mut Engine) {
     engine.register_type_with_name::<WarpBuilder>("WarpBuilder");
     engine.register_type_with_name::<ColorBuilder>("ColorBuilder");
     engine.register_type_with_name::<BlendBuilder>("BlendBuilder");
+    engine.register_type_with_name::<MaskBuilder>("MaskBuilder");
     engine.register_type_with_name::<FeedbackConfig>("FeedbackConfig");
 
     // Factory function for creating a new builder
@@ -69,6 +86,8 @@ pub fn register_feedback_builder_api(engine: &mut Engine) {
 
     engine.register_get("blend", |fb: &mut FeedbackBuilder| BlendBuilder(fb.clone()));
 
+    engine.register_get("mask", |fb: &mut FeedbackBuilder| MaskBuilder(fb.clone()));
+
     // ========================================================================
     // FeedbackBuilder methods
     // ========================================================================
@@ -83,6 +102,19 @@ pub fn register_feedback_builder_api(engine: &mut Engine) {
         },
     );
 
+    // offset(dx, dy) - per-frame echo translation in UV units, accepts f32/i64 or Signal
+    engine.register_fn(
+        "offset",
+        |fb: &mut FeedbackBuilder,
+         dx: Dynamic,
+         dy: Dynamic|
+         -> Result<FeedbackBuilder, Box<EvalAltResult>> {
+            let mut fb = fb.clone();
+            fb.set_offset(to_signal_or_f32(dx)?, to_signal_or_f32(dy)?);
+            Ok(fb)
+        },
+    );
+
     // sample_before_effects() - feedback samples from scene before post-FX (default)
     engine.register_fn("sample_before_effects", |fb: &mut FeedbackBuilder| {
         let mut fb = fb.clone();
@@ -198,6 +230,31 @@ pub fn register_feedback_builder_api(engine: &mut Engine) {
         },
     );
 
+    // noise(#{ strength, scale, speed, seed }) - time-animated noise field
+    engine.register_fn(
+        "noise",
+        |wb: &mut WarpBuilder, options: rhai::Map| -> Result<FeedbackBuilder, Box<EvalAltResult>> {
+            let param = |key: &str, default: f32| match options.get(key) {
+                Some(v) => to_signal_or_f32(v.clone()),
+                None => Ok(SignalOrF32::Scalar(default)),
+            };
+            let seed = match options.get("seed") {
+                Some(v) => v
+                    .as_int()
+                    .ok()
+                    .and_then(|s| u32::try_from(s).ok())
+                    .ok_or("Expected non-negative int for noise seed")?,
+                None => 0,
+            };
+            Ok(wb.clone().noise_animated(
+                param("strength", 0.5)?,
+                param("scale", 4.0)?,
+                param("speed", 0.0)?,
+                seed,
+            ))
+        },
+    );
+
     // shear(strength)
     engine.register_fn(
         "shear",
@@ -256,6 +313,17 @@ pub fn register_feedback_builder_api(engine: &mut Engine) {
         },
     );
 
+    // invert()
+    engine.register_fn("invert", |cb: &mut ColorBuilder| cb.clone().invert());
+
+    // hueRotate(angle)
+    engine.register_fn(
+        "hueRotate",
+        |cb: &mut ColorBuilder, angle: Dynamic| -> Result<FeedbackBuilder, Box<EvalAltResult>> {
+            Ok(cb.clone().hue_rotate(to_signal_or_f32(angle)?))
+        },
+    );
+
     // ========================================================================
     // BlendBuilder methods - each returns FeedbackBuilder for chaining
     // ========================================================================
@@ -269,4 +337,43 @@ pub fn register_feedback_builder_api(engine: &mut Engine) {
         bb.clone().difference()
     });
     engine.register_fn("max", |bb: &mut BlendBuilder| bb.clone().max());
+
+    // ========================================================================
+    // MaskBuilder methods - each returns FeedbackBuilder for chaining
+    // center/size are #{ x, y } maps; all numbers accept f32/i64 or Signal
+    // ========================================================================
+
+    // circle(center, radius, softness)
+    engine.register_fn(
+        "circle",
+        |mb: &mut MaskBuilder,
+         center: Dynamic,
+         radius: Dynamic,
+         softness: Dynamic|
+         -> Result<FeedbackBuilder, Box<EvalAltResult>> {
+            Ok(mb.clone().circle(
+                to_signal_or_f32_vec2(center)?,
+                to_signal_or_f32(radius)?,
+                to_signal_or_f32(softness)?,
+            ))
+        },
+    );
+
+    // rect(center, size, softness)
+    engine.register_fn(
+        "rect",
+        |mb: &mut MaskBuilder,
+         center: Dynamic,
+         size: Dynamic,
+         softness: Dynamic|
+         -> Result<FeedbackBuilder, Box<EvalAltResult>> {
+            Ok(mb.clone().rect(
+                to_signal_or_f32_vec2(center)?,
+                to_signal_or_f32_vec2(size)?,
+                to_signal_or_f32(softness)?,
+            ))
+        },
+    );
+
+    engine.register_fn("full", |mb: &mut MaskBuilder| mb.clone().full());
 }
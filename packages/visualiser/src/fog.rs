@@ -0,0 +1,100 @@
+//! Distance fog (`scene.fog()`).
+//!
+//! Meshes fade towards the fog colour with distance from the camera, starting
+//! at `start` and fully fogged from `end`:
+//!
+//! ```rhai
+//! scene.fog(#{ color: #{ r: 0.1, g: 0.1, b: 0.15 }, start: 5.0, end: 30.0 });
+//! scene.fog(());   // off
+//! ```
+//!
+//! Colour components and distances can be Signals. Fog is applied per
+//! fragment in the material shaders, so primitives and loaded assets fade
+//! alike; matching the fog colour to `scene.background` makes distant meshes
+//! dissolve into it.
+
+use rhai::Dynamic;
+
+/// An evaluated `scene.fog()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneFog {
+    /// Linear RGB fog colour.
+    pub color: [f32; 3],
+    /// Camera distance where fog begins.
+    pub start: f32,
+    /// Camera distance where meshes are entirely fog coloured.
+    pub end: f32,
+}
+
+impl SceneFog {
+    /// Parse a `scene.fog()` options map, evaluating numbers with `eval`.
+    /// Returns None for `()` or a map without a colour.
+    pub fn from_script(
+        value: &Dynamic,
+        eval: &mut impl FnMut(&Dynamic) -> Option<f32>,
+    ) -> Option<Self> {
+        let map = value.read_lock::<rhai::Map>()?;
+        let color = map.get("color")?.read_lock::<rhai::Map>()?;
+        let mut component = |key: &str| color.get(key).and_then(&mut *eval);
+        let color = [component("r")?, component("g")?, component("b")?];
+        let start = map.get("start").and_then(&mut *eval).unwrap_or(0.0);
+        let end = map.get("end").and_then(&mut *eval).unwrap_or(start + 1.0);
+        Some(Self { color, start, end })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::headless_or_skip;
+
+    #[test]
+    fn test_fog_pulls_distant_meshes_towards_fog_color() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // The default camera sits at (4, 2, 4) looking at the origin; the far
+        // cube is three times further along the same line, and three times
+        // bigger so it still fills the centre pixel.
+        let fog = [0.0, 1.0, 0.0];
+        let mut render_cube_at = |k: f32| {
+            let script = format!(
+                r#"
+                let cube = mesh.cube();
+                cube.lit = false;
+                cube.color = #{{ r: 1.0, g: 0.0, b: 0.0, a: 1.0 }};
+                cube.position = #{{ x: {x}, y: {y}, z: {x} }};
+                cube.scale = {k};
+
+                fn init(ctx) {{
+                    scene.add(cube);
+                    scene.fog(#{{ color: #{{ r: 0.0, g: 1.0, b: 0.0 }}, start: 4.0, end: 24.0 }});
+                }}
+
+                fn update(dt, frame) {{}}
+                "#,
+                x = -4.0 * (k - 1.0),
+                y = -2.0 * (k - 1.0),
+                k = k,
+            );
+            headless.render_script(&script);
+            headless.read_scene_hdr()[32 * 64 + 32]
+        };
+
+        let distance_to_fog =
+            |pixel: [f32; 4]| -> f32 { (0..3).map(|c| (pixel[c] - fog[c]).powi(2)).sum() };
+        let near = render_cube_at(1.0);
+        let far = render_cube_at(3.0);
+        assert!(
+            near[0] > near[1],
+            "near cube should stay mostly red, got {:?}",
+            near
+        );
+        assert!(
+            distance_to_fog(far) < distance_to_fog(near),
+            "far {:?} should be closer to the fog colour than near {:?}",
+            far,
+            near
+        );
+    }
+}
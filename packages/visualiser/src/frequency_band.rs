@@ -247,6 +247,321 @@ pub struct FrequencyBoundsAtTime {
     pub enabled: bool,
 }
 
+// ============================================================================
+// Static band configuration (CLI / JSON config)
+// ============================================================================
+
+/// Feature name under which computed band energies are exposed
+/// (`inputs.mix.bands["id"].energy`).
+pub const BAND_ENERGY_FEATURE: &str = "energy";
+
+/// Analysis window size (samples) used for band energy computation.
+const BAND_ENERGY_WINDOW: usize = 2048;
+
+/// A static frequency band with an explicit Hz range.
+///
+/// Unlike [`FrequencyBand`], which mirrors the time-varying bands authored in
+/// the web lab, this is a flat config supplied from the CLI or a JSON file:
+///
+/// ```json
+/// [{ "id": "sub", "lo": 20, "hi": 60 }, { "id": "bass", "label": "Bass", "lo": 60, "hi": 250 }]
+/// ```
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct BandConfig {
+    /// Band id used as the `inputs.mix.bands[...]` key.
+    pub id: String,
+    /// Display label. Defaults to the id.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    /// Lower bound in Hz (inclusive).
+    pub lo: f32,
+    /// Upper bound in Hz (exclusive).
+    pub hi: f32,
+}
+
+impl BandConfig {
+    /// Create a band config with no explicit label.
+    pub fn new(id: impl Into<String>, lo: f32, hi: f32) -> Self {
+        Self {
+            id: id.into(),
+            label: None,
+            lo,
+            hi,
+        }
+    }
+
+    /// The display label, falling back to the id.
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(&self.id)
+    }
+
+    /// Check if a frequency falls within this band.
+    pub fn contains_hz(&self, hz: f32) -> bool {
+        hz >= self.lo && hz < self.hi
+    }
+
+    /// Divide `min_hz..max_hz` into `count` contiguous bands equally spaced on
+    /// the mel scale, so low bands are narrow and high bands are wide.
+    /// Ids are `mel-0`, `mel-1`, ... from low to high.
+    pub fn mel_scale(count: usize, min_hz: f32, max_hz: f32) -> Vec<Self> {
+        let mel_min = hz_to_mel(min_hz);
+        let mel_max = hz_to_mel(max_hz);
+        let step = (mel_max - mel_min) / count.max(1) as f32;
+        (0..count)
+            .map(|i| {
+                let lo = if i == 0 {
+                    min_hz
+                } else {
+                    mel_to_hz(mel_min + step * i as f32)
+                };
+                let hi = if i + 1 == count {
+                    max_hz
+                } else {
+                    mel_to_hz(mel_min + step * (i + 1) as f32)
+                };
+                Self::new(format!("mel-{}", i), lo, hi)
+            })
+            .collect()
+    }
+}
+
+/// Default frequency range for generated (mel) bands. The top is clamped to
+/// Nyquist for audio sampled below 40 kHz (see [`mel_bands_max_hz`]).
+pub const MEL_BANDS_MIN_HZ: f32 = 20.0;
+pub const MEL_BANDS_MAX_HZ: f32 = 20000.0;
+
+/// Top of the generated (mel) band range for audio at `sample_rate`:
+/// [`MEL_BANDS_MAX_HZ`], or Nyquist if that is lower.
+pub fn mel_bands_max_hz(sample_rate: f32) -> f32 {
+    MEL_BANDS_MAX_HZ.min(sample_rate / 2.0)
+}
+
+/// Convert Hz to mels (HTK formula).
+pub fn hz_to_mel(hz: f32) -> f32 {
+    2595.0 * (1.0 + hz / 700.0).log10()
+}
+
+/// Convert mels to Hz (HTK formula).
+pub fn mel_to_hz(mel: f32) -> f32 {
+    700.0 * (10f32.powf(mel / 2595.0) - 1.0)
+}
+
+/// Parse a band spec for audio at `sample_rate`: either `mel:N` (N
+/// mel-spaced bands over [`MEL_BANDS_MIN_HZ`]..[`mel_bands_max_hz`]) or a
+/// JSON array of band configs.
+pub fn parse_band_spec(spec: &str, sample_rate: f32) -> Result<Vec<BandConfig>, String> {
+    let spec = spec.trim();
+    if let Some(count) = spec.strip_prefix("mel:") {
+        let count: usize = count
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid mel band count '{}'", count))?;
+        if count == 0 {
+            return Err("Mel band count must be at least 1".to_string());
+        }
+        let bands = BandConfig::mel_scale(count, MEL_BANDS_MIN_HZ, mel_bands_max_hz(sample_rate));
+        validate_band_configs(&bands)?;
+        return Ok(bands);
+    }
+    parse_band_configs(spec)
+}
+
+/// Parse a JSON array of band configs and validate it.
+pub fn parse_band_configs(json: &str) -> Result<Vec<BandConfig>, String> {
+    let bands: Vec<BandConfig> =
+        serde_json::from_str(json).map_err(|e| format!("Invalid band config: {}", e))?;
+    validate_band_configs(&bands)?;
+    Ok(bands)
+}
+
+/// Validate band configs: ids must be unique and non-empty, ranges must be
+/// positive, and bands must be sorted by frequency without overlapping.
+pub fn validate_band_configs(bands: &[BandConfig]) -> Result<(), String> {
+    if bands.is_empty() {
+        return Err("Band config must define at least one band".to_string());
+    }
+    let mut seen = std::collections::HashSet::new();
+    for band in bands {
+        if band.id.is_empty() {
+            return Err("Band id must not be empty".to_string());
+        }
+        if !seen.insert(band.id.as_str()) {
+            return Err(format!("Duplicate band id '{}'", band.id));
+        }
+        if !(band.lo >= 0.0 && band.hi > band.lo) {
+            return Err(format!(
+                "Band '{}' has an invalid range {}..{} Hz (need 0 <= lo < hi)",
+                band.id, band.lo, band.hi
+            ));
+        }
+    }
+    for pair in bands.windows(2) {
+        if pair[1].lo < pair[0].hi {
+            return Err(format!(
+                "Bands must be sorted and non-overlapping: '{}' ({}..{} Hz) overlaps '{}' ({}..{} Hz)",
+                pair[0].id, pair[0].lo, pair[0].hi, pair[1].id, pair[1].lo, pair[1].hi
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// (id, label) pairs for `VisualiserState::set_available_bands`.
+pub fn band_config_ids(bands: &[BandConfig]) -> Vec<(String, String)> {
+    bands
+        .iter()
+        .map(|b| (b.id.clone(), b.label().to_string()))
+        .collect()
+}
+
+/// FFT bin range `lo..hi` covered by a band, clamped to Nyquist.
+fn band_bin_range(band: &BandConfig, bin_hz: f32) -> (usize, usize) {
+    let lo = (band.lo / bin_hz).ceil() as usize;
+    let hi = ((band.hi / bin_hz).ceil() as usize).min(BAND_ENERGY_WINDOW / 2);
+    (lo.min(hi), hi)
+}
+
+fn covers_bins(band: &BandConfig, bin_hz: f32) -> bool {
+    let (lo, hi) = band_bin_range(band, bin_hz);
+    lo < hi
+}
+
+/// Check that every band covers at least one FFT bin at `sample_rate`.
+///
+/// A band narrower than the analysis resolution (or above Nyquist) gets no
+/// bins and would always read zero energy, so it is rejected up front.
+pub fn check_band_resolution(bands: &[BandConfig], sample_rate: f32) -> Result<(), String> {
+    let bin_hz = sample_rate / BAND_ENERGY_WINDOW as f32;
+    let Some(band) = bands.iter().find(|band| !covers_bins(band, bin_hz)) else {
+        return Ok(());
+    };
+    let mut message = format!(
+        "Band '{}' ({:.1}..{:.1} Hz) covers no FFT bins at {} Hz (bin width {:.1} Hz)",
+        band.id, band.lo, band.hi, sample_rate, bin_hz
+    );
+    if bands.iter().all(|b| b.id.starts_with("mel-")) {
+        message.push_str(&format!("; use at most mel:{}", max_mel_bands(sample_rate)));
+    }
+    Err(message)
+}
+
+/// Largest `mel:N` whose bands all cover at least one FFT bin at
+/// `sample_rate`.
+pub fn max_mel_bands(sample_rate: f32) -> usize {
+    let bin_hz = sample_rate / BAND_ENERGY_WINDOW as f32;
+    (1..)
+        .take_while(|&count| {
+            BandConfig::mel_scale(count, MEL_BANDS_MIN_HZ, mel_bands_max_hz(sample_rate))
+                .iter()
+                .all(|band| covers_bins(band, bin_hz))
+        })
+        .last()
+        .unwrap_or(0)
+}
+
+/// Compute per-band energy envelopes from raw audio samples.
+///
+/// The audio is analysed with a Hann-windowed FFT, one frame every
+/// `1 / frame_rate` seconds. Each band's value is the RMS of the signal
+/// content within its range, so a full-scale sine inside a band reads
+/// roughly 0.707. Returns one envelope per band, in config order, sampled at
+/// `frame_rate`.
+pub fn compute_band_energies(
+    samples: &[f32],
+    sample_rate: f32,
+    bands: &[BandConfig],
+    frame_rate: f32,
+) -> Vec<Vec<f32>> {
+    let mut energies = vec![Vec::new(); bands.len()];
+    if samples.is_empty() || sample_rate <= 0.0 || frame_rate <= 0.0 {
+        return energies;
+    }
+
+    let n = BAND_ENERGY_WINDOW;
+    let hop = (sample_rate / frame_rate).max(1.0);
+    let frame_count = (samples.len() as f32 / hop).ceil() as usize;
+    let bin_hz = sample_rate / n as f32;
+
+    let window: Vec<f32> = (0..n)
+        .map(|i| 0.5 - 0.5 * (std::f32::consts::TAU * i as f32 / n as f32).cos())
+        .collect();
+    // Parseval normalisation so a band's value is the RMS of its content.
+    let norm = 2.0 / (n as f32 * window.iter().map(|w| w * w).sum::<f32>());
+
+    let bin_ranges: Vec<(usize, usize)> = bands
+        .iter()
+        .map(|band| band_bin_range(band, bin_hz))
+        .collect();
+
+    let mut re = vec![0.0f32; n];
+    let mut im = vec![0.0f32; n];
+    for frame in 0..frame_count {
+        // Centre the window on the frame time.
+        let center = (frame as f32 * hop) as isize;
+        let start = center - (n / 2) as isize;
+        for i in 0..n {
+            let idx = start + i as isize;
+            re[i] = if idx >= 0 && (idx as usize) < samples.len() {
+                samples[idx as usize] * window[i]
+            } else {
+                0.0
+            };
+            im[i] = 0.0;
+        }
+        fft_in_place(&mut re, &mut im);
+
+        for (band_idx, &(lo, hi)) in bin_ranges.iter().enumerate() {
+            let power: f32 = (lo..hi).map(|k| re[k] * re[k] + im[k] * im[k]).sum();
+            energies[band_idx].push((power * norm).sqrt());
+        }
+    }
+    energies
+}
+
+/// Iterative radix-2 FFT. `re.len()` must be a power of two.
+pub(crate) fn fft_in_place(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two() && im.len() == n);
+
+    // Bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let mut len = 2;
+    while len <= n {
+        let angle = -std::f32::consts::TAU / len as f32;
+        let (w_im, w_re) = angle.sin_cos();
+        for start in (0..n).step_by(len) {
+            let (mut cur_re, mut cur_im) = (1.0f32, 0.0f32);
+            for k in 0..len / 2 {
+                let a = start + k;
+                let b = a + len / 2;
+                let t_re = re[b] * cur_re - im[b] * cur_im;
+                let t_im = re[b] * cur_im + im[b] * cur_re;
+                re[b] = re[a] - t_re;
+                im[b] = im[a] - t_im;
+                re[a] += t_re;
+                im[a] += t_im;
+                let next_re = cur_re * w_re - cur_im * w_im;
+                cur_im = cur_re * w_im + cur_im * w_re;
+                cur_re = next_re;
+            }
+        }
+        len <<= 1;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -558,4 +873,129 @@ mod tests {
 
         assert_eq!(band.provenance.preset_name, Some("Sweep Band".to_string()));
     }
+
+    #[test]
+    fn test_band_config_validation() {
+        let json = r#"[{"id": "sub", "lo": 20, "hi": 60}, {"id": "bass", "label": "Bass", "lo": 60, "hi": 250}]"#;
+        let bands = parse_band_configs(json).unwrap();
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].label(), "sub");
+        assert_eq!(
+            band_config_ids(&bands),
+            vec![
+                ("sub".to_string(), "sub".to_string()),
+                ("bass".to_string(), "Bass".to_string())
+            ]
+        );
+
+        // Overlapping / unsorted
+        let overlapping = vec![
+            BandConfig::new("a", 20.0, 100.0),
+            BandConfig::new("b", 80.0, 200.0),
+        ];
+        assert!(validate_band_configs(&overlapping).is_err());
+        let unsorted = vec![
+            BandConfig::new("a", 200.0, 400.0),
+            BandConfig::new("b", 20.0, 100.0),
+        ];
+        assert!(validate_band_configs(&unsorted).is_err());
+
+        // Inverted range, duplicate id, empty
+        assert!(validate_band_configs(&[BandConfig::new("a", 100.0, 50.0)]).is_err());
+        let duplicate = vec![
+            BandConfig::new("a", 20.0, 60.0),
+            BandConfig::new("a", 60.0, 100.0),
+        ];
+        assert!(validate_band_configs(&duplicate).is_err());
+        assert!(validate_band_configs(&[]).is_err());
+    }
+
+    #[test]
+    fn test_band_energy_matches_synthetic_tone() {
+        let sample_rate = 44100.0;
+        let tone_hz = 440.0;
+        let samples: Vec<f32> = (0..sample_rate as usize)
+            .map(|i| (std::f32::consts::TAU * tone_hz * i as f32 / sample_rate).sin())
+            .collect();
+
+        let bands = vec![
+            BandConfig::new("low", 20.0, 200.0),
+            BandConfig::new("mid", 200.0, 1000.0),
+            BandConfig::new("high", 1000.0, 8000.0),
+        ];
+        let energies = compute_band_energies(&samples, sample_rate, &bands, 100.0);
+        assert_eq!(energies.len(), 3);
+        assert_eq!(energies[1].len(), 100);
+
+        // Away from the edges the whole tone lands in "mid" (RMS of a unit sine).
+        let mid = energies[1][50];
+        assert!(
+            (mid - std::f32::consts::FRAC_1_SQRT_2).abs() < 0.05,
+            "mid = {mid}"
+        );
+        assert!(energies[0][50] < 0.01 * mid);
+        assert!(energies[2][50] < 0.01 * mid);
+    }
+
+    #[test]
+    fn test_mel_bands_widen_with_frequency() {
+        let bands = parse_band_spec("mel:16", 44100.0).unwrap();
+        assert_eq!(bands.len(), 16);
+        assert_eq!(bands[0].id, "mel-0");
+        assert!((bands[0].lo - MEL_BANDS_MIN_HZ).abs() < 0.01);
+        assert!((bands[15].hi - MEL_BANDS_MAX_HZ).abs() < 0.01);
+
+        // Contiguous, and each band is wider than the one below it.
+        for pair in bands.windows(2) {
+            assert!((pair[0].hi - pair[1].lo).abs() < 0.01);
+            assert!(pair[1].hi - pair[1].lo > pair[0].hi - pair[0].lo);
+        }
+
+        assert!(parse_band_spec("mel:0", 44100.0).is_err());
+        assert!(parse_band_spec("mel:abc", 44100.0).is_err());
+    }
+
+    #[test]
+    fn test_mel_bands_must_cover_an_fft_bin() {
+        let sample_rate = 44100.0;
+        let max = max_mel_bands(sample_rate);
+        assert!(max >= 16, "max = {max}");
+
+        // At the limit every band gets energy from a broadband signal.
+        let bands = parse_band_spec(&format!("mel:{}", max), sample_rate).unwrap();
+        assert!(check_band_resolution(&bands, sample_rate).is_ok());
+        let noise: Vec<f32> = (0..44100u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 16) as f32 / 32768.0 - 1.0)
+            .collect();
+        let energies = compute_band_energies(&noise, sample_rate, &bands, 10.0);
+        for (band, envelope) in bands.iter().zip(&energies) {
+            assert!(envelope[5] > 0.0, "band {} is empty", band.id);
+        }
+
+        // One more band leaves a low band narrower than a bin.
+        let bands = parse_band_spec(&format!("mel:{}", max + 1), sample_rate).unwrap();
+        let err = check_band_resolution(&bands, sample_rate).unwrap_err();
+        assert!(err.contains("covers no FFT bins"), "{err}");
+        assert!(err.contains(&format!("mel:{}", max)), "{err}");
+    }
+
+    #[test]
+    fn test_mel_bands_stop_at_nyquist() {
+        let sample_rate = 22050.0;
+        let max = max_mel_bands(sample_rate);
+        assert!(max >= 16, "max = {max}");
+
+        let bands = parse_band_spec("mel:16", sample_rate).unwrap();
+        assert!((bands[15].hi - sample_rate / 2.0).abs() < 0.01);
+        assert!(check_band_resolution(&bands, sample_rate).is_ok());
+
+        // Every band, including the top one, picks up broadband energy.
+        let noise: Vec<f32> = (0..22050u32)
+            .map(|i| (i.wrapping_mul(2654435761) >> 16) as f32 / 32768.0 - 1.0)
+            .collect();
+        let energies = compute_band_energies(&noise, sample_rate, &bands, 10.0);
+        for (band, envelope) in bands.iter().zip(&energies) {
+            assert!(envelope[5] > 0.0, "band {} is empty", band.id);
+        }
+    }
 }
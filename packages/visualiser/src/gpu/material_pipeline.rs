@@ -7,7 +7,7 @@ use bytemuck::{Pod, Zeroable};
 use std::collections::HashMap;
 
 use crate::gpu::mesh::Vertex;
-use crate::material::{Material, MaterialId, MaterialRegistry, ParamValue};
+use crate::material::{BlendMode, Material, MaterialId, MaterialRegistry, ParamValue};
 
 /// Maximum size for material uniform buffer (in bytes).
 /// Must be large enough for any material's parameters.
@@ -49,8 +49,15 @@ pub struct GlobalUniforms {
 
     // Camera position (for rim lighting and view-dependent effects)
     pub camera_position: [f32; 4], // 16 bytes (xyz, w unused)
+
+    // Distance fog
+    pub fog_color: [f32; 4], // 16 bytes (rgb, a unused)
+    pub fog_start: f32,      // 4 bytes (camera distance where fog begins)
+    pub fog_end: f32,        // 4 bytes (camera distance where fog is total)
+    pub fog_enabled: u32,    // 4 bytes (0 = disabled, 1 = enabled)
+    pub _fog_padding: u32,   // 4 bytes (16-byte alignment)
 }
-// Total: 64 + 64 + 16 + 16 + 16 + 16 + 16 + 16 = 224 bytes
+// Total: 64 + 64 + 16 + 16 + 16 + 16 + 16 + 16 + 16 + 16 = 256 bytes
 
 impl Default for GlobalUniforms {
     fn default() -> Self {
@@ -71,14 +78,26 @@ impl Default for GlobalUniforms {
             entity_emissive: 0.0,
             _light_padding: [0; 2],
             camera_position: [0.0, 0.0, 0.0, 0.0],
+            // Fog defaults (disabled)
+            fog_color: [0.0, 0.0, 0.0, 1.0],
+            fog_start: 0.0,
+            fog_end: 1.0,
+            fog_enabled: 0,
+            _fog_padding: 0,
         }
     }
 }
 
 /// GPU resources for a single material.
 pub struct MaterialGpuResources {
-    /// Render pipeline for this material.
+    /// Render pipeline using the material's own blend mode.
     pub pipeline: wgpu::RenderPipeline,
+    /// Pipelines for other blend modes, created on first use by
+    /// [`MaterialPipelineManager::ensure_blend_variants`].
+    pub blend_variants: HashMap<BlendMode, wgpu::RenderPipeline>,
+    /// Layout and shader the blend variants are built from.
+    pipeline_layout: wgpu::PipelineLayout,
+    shader: wgpu::ShaderModule,
     /// Wireframe pipeline (if applicable).
     pub wireframe_pipeline: Option<wgpu::RenderPipeline>,
     /// Bind group layout for material-specific uniforms.
@@ -89,6 +108,18 @@ pub struct MaterialGpuResources {
     pub bind_group: wgpu::BindGroup,
 }
 
+impl MaterialGpuResources {
+    /// Get the pipeline for a blend mode.
+    ///
+    /// Falls back to `pipeline` (the material's own blend mode) when no
+    /// variant has been created for `blend_mode`.
+    pub fn pipeline_for(&self, blend_mode: BlendMode) -> &wgpu::RenderPipeline {
+        self.blend_variants
+            .get(&blend_mode)
+            .unwrap_or(&self.pipeline)
+    }
+}
+
 /// Manages all material pipelines and shared GPU resources.
 pub struct MaterialPipelineManager {
     /// Per-material GPU resources.
@@ -226,39 +257,14 @@ impl MaterialPipelineManager {
         // Create main pipeline with the material's topology
         let topology = material.topology.to_wgpu();
 
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some(&format!("Material Pipeline: {}", material.id)),
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some(&material.vertex_entry),
-                buffers: &[Vertex::desc()],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some(&material.fragment_entry),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: self.format,
-                    blend: Some(material.blend_mode.to_blend_state()),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: wgpu::PipelineCompilationOptions::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: material.cull_mode,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState::default(),
-            multiview: None,
-            cache: None,
-        });
+        let pipeline = self.create_blend_pipeline(
+            device,
+            material,
+            &pipeline_layout,
+            &shader,
+            topology,
+            material.blend_mode,
+        );
 
         // Create wireframe pipeline if needed (only for triangle-based materials)
         let wireframe_pipeline =
@@ -306,6 +312,9 @@ impl MaterialPipelineManager {
             material.id.clone(),
             MaterialGpuResources {
                 pipeline,
+                blend_variants: HashMap::new(),
+                pipeline_layout,
+                shader,
                 wireframe_pipeline,
                 material_bind_group_layout,
                 uniform_buffer,
@@ -316,6 +325,96 @@ impl MaterialPipelineManager {
         Ok(())
     }
 
+    /// Create `blend_mode` pipelines for materials that don't have one yet.
+    ///
+    /// Called before a frame that overrides material blend modes (e.g. with
+    /// `scene.blendMode = "add"`), so variants are only built once a scene
+    /// asks for them.
+    pub fn ensure_blend_variants(
+        &mut self,
+        device: &wgpu::Device,
+        registry: &MaterialRegistry,
+        blend_mode: BlendMode,
+    ) {
+        let missing: Vec<MaterialId> = self
+            .resources
+            .iter()
+            .filter(|(id, resources)| {
+                !resources.blend_variants.contains_key(&blend_mode)
+                    && registry
+                        .get(id)
+                        .is_some_and(|material| material.blend_mode != blend_mode)
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in missing {
+            let Some(material) = registry.get(&id) else {
+                continue;
+            };
+            let resources = &self.resources[&id];
+            let variant = self.create_blend_pipeline(
+                device,
+                &material,
+                &resources.pipeline_layout,
+                &resources.shader,
+                material.topology.to_wgpu(),
+                blend_mode,
+            );
+            if let Some(resources) = self.resources.get_mut(&id) {
+                resources.blend_variants.insert(blend_mode, variant);
+            }
+        }
+    }
+
+    /// Create the main render pipeline for a material with a given blend mode.
+    fn create_blend_pipeline(
+        &self,
+        device: &wgpu::Device,
+        material: &Material,
+        layout: &wgpu::PipelineLayout,
+        shader: &wgpu::ShaderModule,
+        topology: wgpu::PrimitiveTopology,
+        blend_mode: BlendMode,
+    ) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(&format!(
+                "Material Pipeline: {} ({:?})",
+                material.id, blend_mode
+            )),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: Some(&material.vertex_entry),
+                buffers: &[Vertex::desc()],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: Some(&material.fragment_entry),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: self.format,
+                    blend: Some(blend_mode.to_blend_state()),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: wgpu::PipelineCompilationOptions::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: material.cull_mode,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        })
+    }
+
     /// Load the shader module for a material.
     fn load_material_shader(
         &self,
@@ -444,3 +543,65 @@ impl EvaluatedMaterialParams {
         data
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::background::DEFAULT_CLEAR_COLOR;
+    use crate::test_support::{headless_or_skip, HeadlessRenderer};
+
+    #[test]
+    fn test_additive_material_accumulates_overlaps() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // `count` cubes with `material` stacked at the origin, face-on to the
+        // camera; returns the red channel of the HDR scene at the centre
+        let render_stack =
+            |headless: &mut HeadlessRenderer, count: usize, material: &str, blend_mode: &str| {
+                let cubes: String = (0..count)
+                    .map(|i| format!("let c{i} = mesh.cube(); c{i}.material = \"{material}\";\n"))
+                    .collect();
+                let adds: String = (0..count).map(|i| format!("scene.add(c{i});\n")).collect();
+                let script = format!(
+                    r#"
+                {cubes}
+                fn init(ctx) {{
+                    camera.position = #{{ x: 0.0, y: 0.0, z: 3.0 }};
+                    camera.lookAt(#{{ x: 0.0, y: 0.0, z: 0.0 }});
+                    scene.blendMode = "{blend_mode}";
+                    {adds}
+                }}
+
+                fn update(dt, frame) {{}}
+                "#
+                );
+                headless.render_script(&script);
+                headless.read_scene_hdr()[32 * 64 + 32][0]
+            };
+
+        // Each additive cube adds the same amount on top of the background
+        let background = DEFAULT_CLEAR_COLOR[0];
+        let one = render_stack(&mut headless, 1, "soft_additive", "normal") - background;
+        let two = render_stack(&mut headless, 2, "soft_additive", "normal") - background;
+        assert!(one > 0.1, "one cube added {}", one);
+        assert!((two - 2.0 * one).abs() < 0.01, "{} vs 2 x {}", two, one);
+
+        // Alpha-blended materials don't accumulate
+        let one = render_stack(&mut headless, 1, "emissive", "normal");
+        let two = render_stack(&mut headless, 2, "emissive", "normal");
+        assert!(two <= one + 0.01, "{} vs {}", two, one);
+
+        // Variants for other blend modes are only built once a scene asks for them
+        let manager = headless.renderer.material_pipeline_manager();
+        assert!(manager.get("emissive").unwrap().blend_variants.is_empty());
+        render_stack(&mut headless, 2, "emissive", "add");
+        let manager = headless.renderer.material_pipeline_manager();
+        assert!(manager
+            .get("emissive")
+            .unwrap()
+            .blend_variants
+            .contains_key(&BlendMode::Additive));
+    }
+}
@@ -42,6 +42,94 @@ impl Vertex {
     }
 }
 
+/// A vertex with the barycentric coordinate of its triangle corner, for
+/// wireframe overlays shaded in the fragment shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+pub struct BarycentricVertex {
+    pub vertex: Vertex,
+    pub barycentric: [f32; 3],
+}
+
+impl BarycentricVertex {
+    pub fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<BarycentricVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 0,
+                    format: wgpu::VertexFormat::Float32x3, // position
+                },
+                wgpu::VertexAttribute {
+                    offset: 12,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x3, // normal
+                },
+                wgpu::VertexAttribute {
+                    offset: 24,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x3, // color
+                },
+                wgpu::VertexAttribute {
+                    offset: 36, // after the Vertex fields
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Float32x3, // barycentric
+                },
+            ],
+        }
+    }
+}
+
+/// Joint influences for a skinned vertex, bound as a second vertex buffer
+/// alongside `Vertex`. Vertices with all-zero weights are left unskinned.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Pod, Zeroable)]
+pub struct SkinVertex {
+    pub joints: [u32; 4],
+    pub weights: [f32; 4],
+}
+
+impl SkinVertex {
+    pub fn desc<'a>() -> wgpu::VertexBufferLayout<'a> {
+        wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<SkinVertex>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 3,
+                    format: wgpu::VertexFormat::Uint32x4, // joints
+                },
+                wgpu::VertexAttribute {
+                    offset: 16,
+                    shader_location: 4,
+                    format: wgpu::VertexFormat::Float32x4, // weights
+                },
+            ],
+        }
+    }
+}
+
+/// Expand indexed triangles into unshared vertices, each tagged with its
+/// corner's barycentric coordinate, for a non-indexed draw.
+pub fn expand_barycentric(vertices: &[Vertex], indices: &[u16]) -> Vec<BarycentricVertex> {
+    const CORNERS: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+
+    indices
+        .chunks_exact(3)
+        .flat_map(|tri| {
+            tri.iter()
+                .zip(CORNERS)
+                .map(|(&index, barycentric)| BarycentricVertex {
+                    vertex: vertices[index as usize],
+                    barycentric,
+                })
+        })
+        .collect()
+}
+
 pub fn create_cube_geometry() -> (Vec<Vertex>, Vec<u16>) {
     // Per-face normals for flat shading
     let front: [f32; 3] = [0.0, 0.0, 1.0];
@@ -96,31 +184,67 @@ pub fn create_cube_geometry() -> (Vec<Vertex>, Vec<u16>) {
     (vertices, indices)
 }
 
+/// Default grid cells per side for `mesh.plane()`.
+pub const DEFAULT_PLANE_SUBDIVISIONS: u32 = 1;
+/// Largest plane grid whose `(n + 1)^2` vertices fit in u16 indices.
+pub const MAX_PLANE_SUBDIVISIONS: u32 = 255;
+/// Default longitude segments for `mesh.sphere()`.
+pub const DEFAULT_SPHERE_SEGMENTS: u32 = 32;
+/// Fewest longitude segments that still enclose a volume.
+pub const MIN_SPHERE_SEGMENTS: u32 = 4;
+/// Most longitude segments whose vertices fit in u16 indices.
+pub const MAX_SPHERE_SEGMENTS: u32 = 360;
+
 /// Create a unit plane in the XZ plane (Y up), centered at origin.
-pub fn create_plane_geometry() -> (Vec<Vertex>, Vec<u16>) {
+///
+/// The plane is a grid of `subdivisions` x `subdivisions` quads, so
+/// deformations have interior vertices to displace. Clamped to
+/// `1..=MAX_PLANE_SUBDIVISIONS`.
+pub fn create_plane_geometry(subdivisions: u32) -> (Vec<Vertex>, Vec<u16>) {
+    let cells = subdivisions.clamp(1, MAX_PLANE_SUBDIVISIONS);
+    let row = cells + 1;
+
     // Constant Y-up normal for all vertices
     let up: [f32; 3] = [0.0, 1.0, 0.0];
 
-    let vertices = vec![
-        // Four corners of the plane (white/gray gradient)
-        Vertex::new([-0.5, 0.0, -0.5], up, [0.8, 0.8, 0.8]),
-        Vertex::new([0.5, 0.0, -0.5], up, [0.9, 0.9, 0.9]),
-        Vertex::new([0.5, 0.0, 0.5], up, [1.0, 1.0, 1.0]),
-        Vertex::new([-0.5, 0.0, 0.5], up, [0.9, 0.9, 0.9]),
-    ];
+    let mut vertices = Vec::with_capacity((row * row) as usize);
+    for j in 0..=cells {
+        let v = j as f32 / cells as f32;
+        for i in 0..=cells {
+            let u = i as f32 / cells as f32;
+            // White/gray gradient: 0.8 at the -X-Z corner up to 1.0 at +X+Z
+            let shade = 0.8 + 0.1 * (u + v);
+            vertices.push(Vertex::new(
+                [u - 0.5, 0.0, v - 0.5],
+                up,
+                [shade, shade, shade],
+            ));
+        }
+    }
 
-    let indices = vec![
-        0, 1, 2, 2, 3, 0, // Top face
-    ];
+    let mut indices = Vec::with_capacity((cells * cells * 6) as usize);
+    for j in 0..cells {
+        for i in 0..cells {
+            let a = (j * row + i) as u16;
+            let b = a + 1;
+            let c = b + row as u16;
+            let d = a + row as u16;
+
+            // Two triangles per quad, facing +Y
+            indices.extend_from_slice(&[a, b, c, c, d, a]);
+        }
+    }
 
     (vertices, indices)
 }
 
 /// Create a UV sphere centered at origin with radius 0.5.
-/// Uses 16 latitude rings and 32 longitude segments.
-pub fn create_sphere_geometry() -> (Vec<Vertex>, Vec<u16>) {
-    let lat_segments = 16;
-    let lon_segments = 32;
+///
+/// Uses `segments` longitude segments and half as many latitude rings,
+/// clamped to `MIN_SPHERE_SEGMENTS..=MAX_SPHERE_SEGMENTS`.
+pub fn create_sphere_geometry(segments: u32) -> (Vec<Vertex>, Vec<u16>) {
+    let lon_segments = segments.clamp(MIN_SPHERE_SEGMENTS, MAX_SPHERE_SEGMENTS);
+    let lat_segments = lon_segments / 2;
     let radius = 0.5;
 
     let mut vertices = Vec::new();
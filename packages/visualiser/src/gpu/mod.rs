@@ -1,4 +1,6 @@
 pub mod bloom_processor;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod headless;
 pub mod material_pipeline;
 pub mod mesh;
 pub mod pipeline;
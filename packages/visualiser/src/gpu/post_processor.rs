@@ -2,6 +2,10 @@
 //!
 //! Handles intermediate render targets and effect chain execution.
 //! Also includes frame feedback for temporal visual memory (V7).
+//!
+//! The scene, intermediate and feedback textures are HDR (`HDR_FORMAT`), so
+//! bloom and additive blending keep values above 1.0; a final tonemapping pass
+//! maps the result to the output format.
 
 use bytemuck::{Pod, Zeroable};
 use std::collections::HashMap;
@@ -9,7 +13,11 @@ use wgpu::util::DeviceExt;
 
 use crate::feedback::{FeedbackConfig, FeedbackSamplingMode, FeedbackUniforms};
 use crate::gpu::bloom_processor::{BloomParams, BloomProcessor};
-use crate::post_processing::{EffectParamValue, PostEffectRegistry, PostProcessingChain};
+use crate::post_processing::{EffectId, EffectParamValue, PostEffectRegistry, PostProcessingChain};
+use crate::scene_graph::SceneTonemap;
+
+/// Format of the scene and all intermediate render targets.
+pub const HDR_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba16Float;
 
 /// Maximum size for effect uniform buffer (in bytes).
 const MAX_EFFECT_UNIFORM_SIZE: u64 = 128;
@@ -63,6 +71,16 @@ const QUAD_VERTICES: &[QuadVertex] = &[
     },
 ];
 
+/// Uniforms for the tonemapping pass.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct TonemapUniforms {
+    exposure: f32,
+    /// 1.0 for ACES, 0.0 to clip.
+    aces: f32,
+    _padding: [f32; 2],
+}
+
 /// GPU resources for a single effect.
 struct EffectResources {
     pipeline: wgpu::RenderPipeline,
@@ -88,14 +106,13 @@ pub struct PostProcessor {
     texture_bind_group_layout: wgpu::BindGroupLayout,
     /// Bind group layout for effect uniforms.
     uniform_bind_group_layout: wgpu::BindGroupLayout,
-    /// Blit pipeline (for simple copy).
-    blit_pipeline: wgpu::RenderPipeline,
-    blit_bind_group: wgpu::BindGroup,
+    /// Tonemapping pipeline (HDR input to the output format).
+    tonemap_pipeline: wgpu::RenderPipeline,
+    tonemap_uniform_buffer: wgpu::Buffer,
+    tonemap_uniform_bind_group: wgpu::BindGroup,
     /// Current dimensions.
     width: u32,
     height: u32,
-    /// Texture format.
-    format: wgpu::TextureFormat,
 
     // === Feedback system (V7) ===
     /// Feedback texture (stores previous frame, persists across frames).
@@ -113,6 +130,8 @@ pub struct PostProcessor {
     feedback_applied_this_frame: bool,
     /// Whether the feedback texture needs to be cleared (first use or after resize).
     feedback_needs_clear: bool,
+    /// Only run this effect from the chain (via `dbg.soloEffect()`).
+    solo_effect: Option<EffectId>,
 
     // === Optimized bloom processor ===
     /// Multi-pass bloom processor (separable blur + downsampling)
@@ -120,7 +139,7 @@ pub struct PostProcessor {
 }
 
 impl PostProcessor {
-    /// Create a new post-processor.
+    /// Create a new post-processor writing its final output in `format`.
     pub fn new(
         device: &wgpu::Device,
         format: wgpu::TextureFormat,
@@ -143,7 +162,7 @@ impl PostProcessor {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format,
+                format: HDR_FORMAT,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT
                     | wgpu::TextureUsages::TEXTURE_BINDING
                     | wgpu::TextureUsages::COPY_SRC,
@@ -159,7 +178,7 @@ impl PostProcessor {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format,
+                format: HDR_FORMAT,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT
                     | wgpu::TextureUsages::TEXTURE_BINDING
                     | wgpu::TextureUsages::COPY_DST,
@@ -271,29 +290,30 @@ impl PostProcessor {
                 ],
             });
 
-        // Create blit pipeline
-        let blit_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: Some("Blit Shader"),
-            source: wgpu::ShaderSource::Wgsl(include_str!("shader_post_blit.wgsl").into()),
+        // Create tonemap pipeline
+        let tonemap_shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("Tonemap Shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shader_post_tonemap.wgsl").into()),
         });
 
-        let blit_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("Blit Pipeline Layout"),
-            bind_group_layouts: &[&texture_bind_group_layout],
-            push_constant_ranges: &[],
-        });
+        let tonemap_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Tonemap Pipeline Layout"),
+                bind_group_layouts: &[&texture_bind_group_layout, &uniform_bind_group_layout],
+                push_constant_ranges: &[],
+            });
 
-        let blit_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Blit Pipeline"),
-            layout: Some(&blit_pipeline_layout),
+        let tonemap_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Tonemap Pipeline"),
+            layout: Some(&tonemap_pipeline_layout),
             vertex: wgpu::VertexState {
-                module: &blit_shader,
+                module: &tonemap_shader,
                 entry_point: Some("vs_main"),
                 buffers: &[QuadVertex::desc()],
                 compilation_options: wgpu::PipelineCompilationOptions::default(),
             },
             fragment: Some(wgpu::FragmentState {
-                module: &blit_shader,
+                module: &tonemap_shader,
                 entry_point: Some("fs_main"),
                 targets: &[Some(wgpu::ColorTargetState {
                     format,
@@ -312,20 +332,23 @@ impl PostProcessor {
             cache: None,
         });
 
-        // Create bind group for blit (using scene texture as input)
-        let blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Blit Bind Group"),
-            layout: &texture_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&scene_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&sampler),
-                },
-            ],
+        let tonemap_uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Tonemap Uniform Buffer"),
+            contents: bytemuck::bytes_of(&TonemapUniforms {
+                exposure: 1.0,
+                aces: 0.0,
+                _padding: [0.0; 2],
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let tonemap_uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Tonemap Uniform Bind Group"),
+            layout: &uniform_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: tonemap_uniform_buffer.as_entire_binding(),
+            }],
         });
 
         // === Feedback pipeline ===
@@ -357,7 +380,7 @@ impl PostProcessor {
                 module: &feedback_shader,
                 entry_point: Some("fs_main"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format,
+                    format: HDR_FORMAT,
                     blend: Some(wgpu::BlendState::REPLACE),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -392,7 +415,7 @@ impl PostProcessor {
         });
 
         // Create optimized bloom processor
-        let bloom_processor = BloomProcessor::new(device, format, width, height);
+        let bloom_processor = BloomProcessor::new(device, HDR_FORMAT, width, height);
 
         let mut processor = Self {
             intermediate_textures: [tex_a, tex_b],
@@ -404,11 +427,11 @@ impl PostProcessor {
             sampler,
             texture_bind_group_layout,
             uniform_bind_group_layout,
-            blit_pipeline,
-            blit_bind_group,
+            tonemap_pipeline,
+            tonemap_uniform_buffer,
+            tonemap_uniform_bind_group,
             width,
             height,
-            format,
             // Feedback resources
             feedback_texture: feedback_tex,
             feedback_view,
@@ -418,6 +441,7 @@ impl PostProcessor {
             feedback_uniform_bind_group,
             feedback_applied_this_frame: false,
             feedback_needs_clear: true,
+            solo_effect: None,
             // Bloom processor
             bloom_processor,
         };
@@ -478,7 +502,7 @@ impl PostProcessor {
                 module: &shader,
                 entry_point: Some("fs_main"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format: self.format,
+                    format: HDR_FORMAT,
                     blend: Some(wgpu::BlendState::REPLACE),
                     write_mask: wgpu::ColorWrites::ALL,
                 })],
@@ -528,6 +552,11 @@ impl PostProcessor {
         &self.scene_view
     }
 
+    /// Get the HDR scene texture (e.g. to read back unclipped values).
+    pub fn scene_texture(&self) -> &wgpu::Texture {
+        &self.scene_texture
+    }
+
     /// Resize the post-processor.
     pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) {
         if width == self.width && height == self.height {
@@ -550,7 +579,7 @@ impl PostProcessor {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: self.format,
+                format: HDR_FORMAT,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT
                     | wgpu::TextureUsages::TEXTURE_BINDING
                     | wgpu::TextureUsages::COPY_SRC,
@@ -565,7 +594,7 @@ impl PostProcessor {
                 mip_level_count: 1,
                 sample_count: 1,
                 dimension: wgpu::TextureDimension::D2,
-                format: self.format,
+                format: HDR_FORMAT,
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT
                     | wgpu::TextureUsages::TEXTURE_BINDING
                     | wgpu::TextureUsages::COPY_DST,
@@ -599,26 +628,34 @@ impl PostProcessor {
             self.height,
             self.bloom_processor.current_downsample(),
         );
+    }
 
-        // Recreate blit bind group with new scene view
-        self.blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Blit Bind Group"),
-            layout: &self.texture_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&self.scene_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+    /// Run only this effect from the chain, bypassing the others (None runs
+    /// the whole chain).
+    pub fn set_solo_effect(&mut self, effect_id: Option<EffectId>) {
+        self.solo_effect = effect_id;
+    }
+
+    /// Set the tonemapping curve and the exposure multiplier applied before it.
+    pub fn set_tonemap(&self, queue: &wgpu::Queue, tonemap: SceneTonemap, exposure: f32) {
+        queue.write_buffer(
+            &self.tonemap_uniform_buffer,
+            0,
+            bytemuck::bytes_of(&TonemapUniforms {
+                exposure,
+                aces: if tonemap == SceneTonemap::Aces {
+                    1.0
+                } else {
+                    0.0
                 },
-            ],
-        });
+                _padding: [0.0; 2],
+            }),
+        );
     }
 
     /// Process the post-processing chain.
-    /// Takes the scene render target and applies effects to the final output.
+    /// Takes the scene render target, applies effects in HDR and tonemaps the
+    /// result to the final output.
     ///
     /// If feedback was applied this frame (via `process_feedback`), uses
     /// the feedback output as the input instead of the raw scene.
@@ -631,42 +668,12 @@ impl PostProcessor {
         chain: &PostProcessingChain,
         evaluated_params: &HashMap<String, Vec<EffectParamValue>>,
     ) {
-        let enabled_effects: Vec<_> = chain.enabled_effects().collect();
+        let enabled_effects: Vec<_> = chain.active_effects(self.solo_effect.as_deref()).collect();
 
         // Determine the input: feedback output if feedback was applied, else scene
         let initial_input_view = self.feedback_input_view();
 
-        if enabled_effects.is_empty() {
-            // No effects - blit input directly to output
-            // Need to create a blit bind group for the appropriate input
-            if self.feedback_applied_this_frame {
-                let blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-                    label: Some("Blit Bind Group (Feedback)"),
-                    layout: &self.texture_bind_group_layout,
-                    entries: &[
-                        wgpu::BindGroupEntry {
-                            binding: 0,
-                            resource: wgpu::BindingResource::TextureView(initial_input_view),
-                        },
-                        wgpu::BindGroupEntry {
-                            binding: 1,
-                            resource: wgpu::BindingResource::Sampler(&self.sampler),
-                        },
-                    ],
-                });
-                self.blit(encoder, initial_input_view, output_view, &blit_bind_group);
-            } else {
-                self.blit(
-                    encoder,
-                    initial_input_view,
-                    output_view,
-                    &self.blit_bind_group,
-                );
-            }
-            return;
-        }
-
-        // Process effects in chain order
+        // Process effects in chain order, then tonemap the last result
         let mut current_input_view = initial_input_view;
         // If feedback was applied, intermediate[0] is already used, start ping-pong at 1
         let mut ping = if self.feedback_applied_this_frame {
@@ -675,13 +682,8 @@ impl PostProcessor {
             0
         };
 
-        for (i, effect) in enabled_effects.iter().enumerate() {
-            let is_last = i == enabled_effects.len() - 1;
-            let output = if is_last {
-                output_view
-            } else {
-                &self.intermediate_views[ping]
-            };
+        for effect in &enabled_effects {
+            let output = &self.intermediate_views[ping];
 
             // Check if this is a bloom effect - route through optimized BloomProcessor
             if effect.effect_id == "bloom" {
@@ -738,22 +740,38 @@ impl PostProcessor {
             }
 
             // Update for next pass
-            if !is_last {
-                current_input_view = &self.intermediate_views[ping];
-                ping = 1 - ping;
-            }
+            current_input_view = output;
+            ping = 1 - ping;
         }
+
+        self.tonemap(device, encoder, current_input_view, output_view);
     }
 
-    fn blit(
+    /// Tonemap an HDR texture into the output.
+    fn tonemap(
         &self,
+        device: &wgpu::Device,
         encoder: &mut wgpu::CommandEncoder,
-        _input: &wgpu::TextureView,
+        input: &wgpu::TextureView,
         output: &wgpu::TextureView,
-        bind_group: &wgpu::BindGroup,
     ) {
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Tonemap Texture Bind Group"),
+            layout: &self.texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(input),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+        });
+
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-            label: Some("Blit Pass"),
+            label: Some("Tonemap Pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
                 view: output,
                 resolve_target: None,
@@ -767,8 +785,9 @@ impl PostProcessor {
             occlusion_query_set: None,
         });
 
-        render_pass.set_pipeline(&self.blit_pipeline);
-        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.set_pipeline(&self.tonemap_pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_bind_group(1, &self.tonemap_uniform_bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.quad_vertex_buffer.slice(..));
         render_pass.draw(0..6, 0..1);
     }
@@ -952,6 +971,14 @@ impl PostProcessor {
         self.feedback_applied_this_frame = true;
     }
 
+    /// Clear the feedback buffer before it is next read.
+    ///
+    /// Use this when the encoder isn't at hand (e.g. on seek); the clear runs
+    /// at the start of the next feedback pass.
+    pub fn request_feedback_clear(&mut self) {
+        self.feedback_needs_clear = true;
+    }
+
     /// Clear the feedback buffer to black.
     ///
     /// Call this when seeking to ensure clean feedback state.
@@ -1051,7 +1078,7 @@ impl PostProcessor {
     /// The flow is:
     /// 1. Apply post-FX chain to scene → intermediate
     /// 2. Feedback samples from post-FX result
-    /// 3. Output feedback result to final target
+    /// 3. Tonemap the feedback result to the final target
     fn process_feedback_post_fx(
         &mut self,
         device: &wgpu::Device,
@@ -1064,18 +1091,16 @@ impl PostProcessor {
         evaluated_params: &HashMap<String, Vec<EffectParamValue>>,
     ) {
         self.feedback_applied_this_frame = false;
-        let has_effects = post_chain.has_enabled_effects();
+        let has_effects = post_chain
+            .active_effects(self.solo_effect.as_deref())
+            .next()
+            .is_some();
         let has_feedback = feedback_config.enabled;
 
         // Handle trivial cases
         if !has_effects && !has_feedback {
-            // Nothing to do - just blit scene to output
-            self.blit(
-                encoder,
-                &self.scene_view,
-                output_view,
-                &self.blit_bind_group,
-            );
+            // Nothing to do - just tonemap scene to output
+            self.tonemap(device, encoder, &self.scene_view, output_view);
             return;
         }
 
@@ -1136,36 +1161,6 @@ impl PostProcessor {
             ],
         });
 
-        // Render feedback pass directly to output
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Feedback Pass (PostFx mode)"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: output_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            render_pass.set_pipeline(&self.feedback_pipeline);
-            render_pass.set_bind_group(0, &feedback_texture_bind_group, &[]);
-            render_pass.set_bind_group(1, &self.feedback_uniform_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, self.quad_vertex_buffer.slice(..));
-            render_pass.draw(0..6, 0..1);
-        }
-
-        // Copy output to feedback texture for next frame
-        // We need to copy from output, but we can't read from swapchain.
-        // Instead, we'll render feedback to intermediate[0] AND output simultaneously.
-        // Actually, we need to render to intermediate[0] first, then blit to output.
-        // Let me refactor this...
-
         // Render feedback to intermediate[0]
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -1213,27 +1208,8 @@ impl PostProcessor {
             size,
         );
 
-        // Blit intermediate[0] to output
-        let blit_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("Blit Bind Group (PostFx feedback)"),
-            layout: &self.texture_bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&self.intermediate_views[0]),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&self.sampler),
-                },
-            ],
-        });
-        self.blit(
-            encoder,
-            &self.intermediate_views[0],
-            output_view,
-            &blit_bind_group,
-        );
+        // Tonemap intermediate[0] to output
+        self.tonemap(device, encoder, &self.intermediate_views[0], output_view);
 
         self.feedback_applied_this_frame = true;
     }
@@ -1248,7 +1224,7 @@ impl PostProcessor {
         evaluated_params: &HashMap<String, Vec<EffectParamValue>>,
         target_intermediate: usize,
     ) {
-        let enabled_effects: Vec<_> = chain.enabled_effects().collect();
+        let enabled_effects: Vec<_> = chain.active_effects(self.solo_effect.as_deref()).collect();
         let num_effects = enabled_effects.len();
 
         if num_effects == 0 {
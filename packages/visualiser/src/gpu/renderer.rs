@@ -2,18 +2,28 @@
 //!
 //! Renders meshes and line strips from the script-driven scene graph.
 
+use crate::background::{SceneBackground, DEFAULT_CLEAR_COLOR};
 use crate::camera::CameraUniforms;
 use crate::deformation::apply_deformations;
 use crate::gpu::material_pipeline::{GlobalUniforms, MaterialPipelineManager};
-use crate::gpu::mesh::{self, Vertex};
+use crate::gpu::mesh::{self, BarycentricVertex, Vertex};
 use crate::gpu::pipeline;
-use crate::gpu::post_processor::PostProcessor;
-use crate::material::{MaterialRegistry, ParamValue};
+use crate::gpu::post_processor::{PostProcessor, HDR_FORMAT};
+use crate::material::{BlendMode, MaterialRegistry, ParamValue};
 use crate::mesh_asset::{BoundingBox, MeshAsset, CUBE_BOUNDS, PLANE_BOUNDS, SPHERE_BOUNDS};
+use crate::particle::ParticleBlend;
 use crate::particle_eval::{GpuMeshParticleInstance, GpuParticleInstance};
 use crate::post_processing::PostEffectRegistry;
-use crate::scene_graph::{EntityId, MeshType, RenderMode, Transform};
+use crate::scene_graph::{
+    AnimationPlayback, EntityId, MeshType, RenderMode, SceneBlendMode, Transform,
+};
+use crate::skinning::{self, Skin, MAX_JOINTS};
+use crate::sparkline::{DebugPlot, DEBUG_PLOT_POINTS};
+use crate::spectrogram::{Spectrogram, SPECTROGRAM_COLUMNS, SPECTROGRAM_ROWS};
+use crate::sprite_asset::SpriteAsset;
+use crate::ui::{self, MAX_UI_RECTS, UI_VERTEX_FLOATS};
 use crate::visualiser::VisualiserState;
+use crate::waveform::{playhead_fraction, WaveformEnvelope, MAX_WAVEFORM_COLUMNS, WAVEFORM_REGION};
 use bytemuck::{Pod, Zeroable};
 use std::collections::{HashMap, HashSet};
 use std::iter;
@@ -32,6 +42,12 @@ const MAX_POINTS_PER_CLOUD: usize = 10000;
 /// Maximum connected segments in one generated polyline.
 const MAX_POLYLINE_SEGMENTS: usize = 1024;
 
+/// Maximum number of debug markers drawn per frame.
+const MAX_DEBUG_MARKERS: usize = 256;
+
+/// Maximum number of axis gizmo segments drawn per frame (three per gizmo).
+const MAX_AXIS_SEGMENTS: usize = 3 * 32;
+
 /// Maximum number of meshes that can be rendered per frame.
 /// Each mesh needs its own uniform slot in the dynamic uniform buffer.
 const MAX_MESHES_PER_FRAME: usize = 256;
@@ -39,6 +55,22 @@ const MAX_MESHES_PER_FRAME: usize = 256;
 /// Uniform buffer alignment (WebGPU minUniformBufferOffsetAlignment is typically 256 bytes)
 const UNIFORM_ALIGNMENT: usize = 256;
 
+/// Maximum number of skinned meshes drawn with the GPU skinning pipeline per
+/// frame. Further skinned meshes are skinned on the CPU instead.
+const MAX_SKINNED_MESHES_PER_FRAME: usize = 16;
+
+/// Size of one mesh's bone matrices in the bone uniform buffer.
+const BONE_SLOT_SIZE: usize = MAX_JOINTS * std::mem::size_of::<glam::Mat4>();
+
+/// Bone matrices for a skin's current animation playback (or its rest pose).
+fn playback_bones(skin: &Skin, playback: Option<&AnimationPlayback>) -> Vec<glam::Mat4> {
+    let clip = playback.and_then(|playback| {
+        skin.animation(&playback.name)
+            .map(|clip| (clip, clip.clip_time(playback.time, playback.looping)))
+    });
+    skin.bone_matrices(clip)
+}
+
 fn build_polyline_segments(points: &[crate::scene_graph::Vec3]) -> Vec<[f32; 6]> {
     points
         .windows(2)
@@ -57,8 +89,19 @@ struct Uniforms {
     view_proj: [[f32; 4]; 4],
     model: [[f32; 4]; 4],
     instance_color: [f32; 4],
-    // Padding to reach 256-byte alignment (144 bytes of data + 112 bytes padding)
-    _padding: [f32; 28],
+    camera_position: [f32; 4],
+    // Distance fog (see GlobalUniforms)
+    fog_color: [f32; 4],
+    fog_start: f32,
+    fog_end: f32,
+    fog_enabled: u32,
+    _fog_padding: u32,
+    // Wireframe overlay edges (RenderMode::WireframeOverlay)
+    wireframe_color: [f32; 4],
+    wireframe_width: f32,
+    _wireframe_padding: [f32; 3],
+    // Padding to reach 256-byte alignment (224 bytes of data + 32 bytes padding)
+    _padding: [f32; 8],
 }
 
 /// Uniforms for blob shadow rendering.
@@ -80,7 +123,16 @@ impl Uniforms {
             view_proj: glam::Mat4::IDENTITY.to_cols_array_2d(),
             model: glam::Mat4::IDENTITY.to_cols_array_2d(),
             instance_color: [1.0, 1.0, 1.0, 1.0], // Default: no tint
-            _padding: [0.0; 28],
+            camera_position: [0.0; 4],
+            fog_color: [0.0, 0.0, 0.0, 1.0],
+            fog_start: 0.0,
+            fog_end: 1.0,
+            fog_enabled: 0,
+            _fog_padding: 0,
+            wireframe_color: [1.0, 1.0, 1.0, 1.0],
+            wireframe_width: 1.0,
+            _wireframe_padding: [0.0; 3],
+            _padding: [0.0; 8],
         }
     }
 
@@ -145,53 +197,6 @@ impl Uniforms {
     }
 }
 
-/// Compute the world transform matrix for an entity, walking up the parent chain.
-fn compute_world_matrix(
-    entity_id: crate::scene_graph::EntityId,
-    scene_graph: &crate::scene_graph::SceneGraph,
-) -> glam::Mat4 {
-    let mut chain = Vec::new();
-    let mut visited = std::collections::HashSet::new();
-    let mut current = Some(entity_id);
-
-    while let Some(id) = current {
-        if !visited.insert(id) {
-            log::error!(
-                "Scene graph cycle detected while resolving entity {:?}",
-                entity_id
-            );
-            return glam::Mat4::IDENTITY;
-        }
-        let Some(entity) = scene_graph.get(id) else {
-            return glam::Mat4::IDENTITY;
-        };
-        let transform = entity.transform();
-        let translation = glam::Mat4::from_translation(glam::Vec3::new(
-            transform.position.x,
-            transform.position.y,
-            transform.position.z,
-        ));
-        let rotation = glam::Mat4::from_euler(
-            glam::EulerRot::XYZ,
-            transform.rotation.x,
-            transform.rotation.y,
-            transform.rotation.z,
-        );
-        let scale = glam::Mat4::from_scale(glam::Vec3::new(
-            transform.scale.x,
-            transform.scale.y,
-            transform.scale.z,
-        ));
-        chain.push(translation * rotation * scale);
-        current = scene_graph.get_parent(id);
-    }
-
-    chain
-        .into_iter()
-        .rev()
-        .fold(glam::Mat4::IDENTITY, |world, local| world * local)
-}
-
 /// Compute world-space bounding box vertices from local bounds and world transform.
 /// Returns 8 vertices with the given color.
 fn compute_world_bounds_vertices(
@@ -225,26 +230,91 @@ fn compute_world_bounds_vertices(
     vertices
 }
 
-/// Check if an entity or any of its ancestors is invisible.
-fn is_entity_visible(
-    entity_id: crate::scene_graph::EntityId,
-    scene_graph: &crate::scene_graph::SceneGraph,
-) -> bool {
-    let mut visited = std::collections::HashSet::new();
-    let mut current = Some(entity_id);
-    while let Some(id) = current {
-        if !visited.insert(id) {
-            return false;
-        }
-        let Some(entity) = scene_graph.get(id) else {
-            return false;
-        };
-        if !entity.visible() {
-            return false;
-        }
-        current = scene_graph.get_parent(id);
+/// Upload indexed triangles as unindexed barycentric vertices for the
+/// wireframe overlay. Returns the buffer and its vertex count.
+fn create_barycentric_vertex_buffer(
+    device: &wgpu::Device,
+    label: &str,
+    vertices: &[Vertex],
+    indices: &[u16],
+) -> (wgpu::Buffer, u32) {
+    let expanded = mesh::expand_barycentric(vertices, indices);
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(label),
+        contents: bytemuck::cast_slice(&expanded),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+    (buffer, expanded.len() as u32)
+}
+
+/// Upload triangles with their wireframe edges and overlay vertices.
+fn create_mesh_geometry(
+    device: &wgpu::Device,
+    label: &str,
+    vertices: &[Vertex],
+    indices: &[u16],
+) -> MeshGeometry {
+    let edge_indices = mesh::extract_edges(indices);
+    let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(&format!("{label} Vertex Buffer")),
+        contents: bytemuck::cast_slice(vertices),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+    let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(&format!("{label} Index Buffer")),
+        contents: bytemuck::cast_slice(indices),
+        usage: wgpu::BufferUsages::INDEX,
+    });
+    let wireframe_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some(&format!("{label} Wireframe Index Buffer")),
+        contents: bytemuck::cast_slice(&edge_indices),
+        usage: wgpu::BufferUsages::INDEX,
+    });
+    let (barycentric_vertex_buffer, num_barycentric_vertices) = create_barycentric_vertex_buffer(
+        device,
+        &format!("{label} Barycentric Vertex Buffer"),
+        vertices,
+        indices,
+    );
+
+    MeshGeometry {
+        vertex_buffer,
+        index_buffer,
+        num_indices: indices.len() as u32,
+        num_vertices: vertices.len() as u32,
+        wireframe_index_buffer: Some(wireframe_index_buffer),
+        num_edges: edge_indices.len() as u32,
+        barycentric_vertex_buffer: Some(barycentric_vertex_buffer),
+        num_barycentric_vertices,
     }
-    true
+}
+
+/// Bind the background uniforms with a skybox texture (or the placeholder).
+fn create_background_bind_group(
+    device: &wgpu::Device,
+    layout: &wgpu::BindGroupLayout,
+    uniform_buffer: &wgpu::Buffer,
+    texture_view: &wgpu::TextureView,
+    sampler: &wgpu::Sampler,
+) -> wgpu::BindGroup {
+    device.create_bind_group(&wgpu::BindGroupDescriptor {
+        layout,
+        entries: &[
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::TextureView(texture_view),
+            },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+        label: Some("background_bind_group"),
+    })
 }
 
 #[repr(C)]
@@ -258,6 +328,48 @@ struct LineUniforms {
     _padding: [f32; 2],
 }
 
+/// Uniforms for the spectrogram overlay quad.
+/// Matches shader_spectrogram.wgsl SpectrogramUniforms struct.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct SpectrogramUniforms {
+    offset: [f32; 2],
+    scale: [f32; 2],
+    scroll: f32,
+    _padding: [f32; 3],
+}
+
+/// Uniforms for the reference grid overlay.
+/// Matches shader_reference_grid.wgsl GridUniforms struct.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct GridUniforms {
+    color: [f32; 4],
+    viewport: [f32; 2],
+    spacing: f32,
+    checker: f32,
+}
+
+/// Uniforms for the UI overlay.
+/// Matches shader_ui.wgsl UiUniforms struct.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct UiUniforms {
+    projection: [[f32; 4]; 4],
+}
+
+/// Uniforms for the scene background.
+/// Matches shader_background.wgsl BackgroundUniforms struct.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Pod, Zeroable)]
+struct BackgroundUniforms {
+    inv_view_proj: [[f32; 4]; 4],
+    top: [f32; 4],
+    bottom: [f32; 4],
+    mode: u32,
+    _padding: [u32; 3],
+}
+
 /// Uniforms for point cloud rendering.
 /// Matches shader_point_cloud.wgsl PointCloudUniforms struct.
 #[repr(C)]
@@ -278,6 +390,32 @@ struct PointSpriteResources {
     bind_group: wgpu::BindGroup,
 }
 
+/// Per-plot GPU resources for `dbg.plot()` sparklines.
+struct SparklineResources {
+    vertex_buffer: wgpu::Buffer,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+/// GPU resources for the waveform overlay. The envelope is uploaded once per
+/// (audio generation, column count) and reused every frame.
+struct WaveformResources {
+    envelope: SparklineResources,
+    playhead: SparklineResources,
+    point_count: usize,
+    key: (u64, usize),
+}
+
+/// GPU mirror of the spectrogram ring buffer. Normally one new column is
+/// uploaded per frame; a full upload happens when the texture falls behind.
+struct SpectrogramResources {
+    texture: wgpu::Texture,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+    /// Columns written when the texture was last updated; None until the first upload.
+    uploaded_columns: Option<u64>,
+}
+
 struct PolylineResources {
     segment_buffer: wgpu::Buffer,
     uniform_buffer: wgpu::Buffer,
@@ -293,6 +431,16 @@ struct MeshGeometry {
     /// Edge indices for wireframe rendering.
     wireframe_index_buffer: Option<wgpu::Buffer>,
     num_edges: u32,
+    /// Unindexed triangles with barycentric coordinates for the wireframe overlay.
+    barycentric_vertex_buffer: Option<wgpu::Buffer>,
+    num_barycentric_vertices: u32,
+}
+
+/// Primitive shapes whose geometry depends on a subdivision level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum PrimitiveKind {
+    Plane,
+    Sphere,
 }
 
 /// Buffers for a loaded mesh asset.
@@ -300,9 +448,13 @@ struct LoadedMeshBuffers {
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
     wireframe_index_buffer: wgpu::Buffer,
+    barycentric_vertex_buffer: wgpu::Buffer,
+    // Joint weights for skinned assets (vertex slot 1 of the skinned pipeline)
+    skin_vertex_buffer: Option<wgpu::Buffer>,
     num_indices: u32,
     num_edges: u32,
     num_vertices: u32,
+    num_barycentric_vertices: u32,
 }
 
 pub struct Renderer {
@@ -313,16 +465,27 @@ pub struct Renderer {
     // Mesh rendering
     mesh_pipeline: wgpu::RenderPipeline,
     wireframe_pipeline: wgpu::RenderPipeline,
+    additive_mesh_pipeline: wgpu::RenderPipeline,
+    additive_wireframe_pipeline: wgpu::RenderPipeline,
+    wireframe_overlay_pipeline: wgpu::RenderPipeline,
+    additive_wireframe_overlay_pipeline: wgpu::RenderPipeline,
     #[allow(dead_code)]
     mesh_bind_group_layout: wgpu::BindGroupLayout,
     uniform_buffer: wgpu::Buffer,
     mesh_bind_group: wgpu::BindGroup,
     uniforms: Uniforms,
 
+    // Skinned mesh rendering (one bone matrix slot per skinned mesh)
+    skinned_pipeline: wgpu::RenderPipeline,
+    additive_skinned_pipeline: wgpu::RenderPipeline,
+    bone_buffer: wgpu::Buffer,
+    bone_bind_group: wgpu::BindGroup,
+
     // Shared geometry
     cube_geometry: MeshGeometry,
     plane_geometry: MeshGeometry,
-    sphere_geometry: MeshGeometry,
+    // Planes and spheres by (kind, subdivision level), created on demand
+    primitive_geometry: HashMap<(PrimitiveKind, u32), MeshGeometry>,
 
     // Debug bounding box geometry (8-vertex cube with 12 edges)
     debug_cube_geometry: MeshGeometry,
@@ -337,9 +500,12 @@ pub struct Renderer {
 
     // Staging buffer for deformed vertices (reused each frame)
     deformed_vertex_staging: wgpu::Buffer,
+    // Staging buffer for deformed wireframe overlay vertices (grown on demand)
+    deformed_barycentric_staging: Option<wgpu::Buffer>,
 
     // Line rendering
     line_pipeline: wgpu::RenderPipeline,
+    additive_line_pipeline: wgpu::RenderPipeline,
     #[allow(dead_code)]
     line_bind_group_layout: wgpu::BindGroupLayout,
 
@@ -347,18 +513,53 @@ pub struct Renderer {
     line_vertex_buffer: wgpu::Buffer,
     line_uniform_buffer: wgpu::Buffer,
     line_bind_group: wgpu::BindGroup,
+    // One sparkline slot per dbg.plot(), plus a polyline slot for its label
+    plot_resources: Vec<SparklineResources>,
+    plot_label_resources: Vec<PolylineResources>,
+    // Waveform overlay (dbg.showWaveform()), created on first use
+    waveform_resources: Option<WaveformResources>,
+    // Spectrogram overlay (dbg.showSpectrogram()), created on first use
+    spectrogram_pipeline: wgpu::RenderPipeline,
+    spectrogram_bind_group_layout: wgpu::BindGroupLayout,
+    spectrogram_resources: Option<SpectrogramResources>,
+    // Reference grid overlay (dbg.showGrid())
+    reference_grid_pipeline: wgpu::RenderPipeline,
+    reference_grid_uniform_buffer: wgpu::Buffer,
+    reference_grid_bind_group: wgpu::BindGroup,
+
+    // Pixel-space UI overlay (ui.rect()), drawn after post-processing
+    ui_pipeline: wgpu::RenderPipeline,
+    ui_uniform_buffer: wgpu::Buffer,
+    ui_bind_group: wgpu::BindGroup,
+    ui_vertex_buffer: wgpu::Buffer,
+    // Scene background (scene.background)
+    background_pipeline: wgpu::RenderPipeline,
+    background_bind_group_layout: wgpu::BindGroupLayout,
+    background_uniform_buffer: wgpu::Buffer,
+    background_sampler: wgpu::Sampler,
+    /// Background bind group with a 1x1 placeholder texture, for gradients
+    background_bind_group: wgpu::BindGroup,
+    /// Skybox bind group, rebuilt when the skybox asset changes
+    skybox_bind_group: Option<(Arc<SpriteAsset>, wgpu::BindGroup)>,
 
     // Point cloud rendering
     point_cloud_pipeline: wgpu::RenderPipeline,
+    additive_point_cloud_pipeline: wgpu::RenderPipeline,
     point_cloud_bind_group_layout: wgpu::BindGroupLayout,
     point_sprite_resources: HashMap<EntityId, PointSpriteResources>,
 
     // Connected screen-space polylines (radial waves)
     polyline_pipeline: wgpu::RenderPipeline,
+    additive_polyline_pipeline: wgpu::RenderPipeline,
     polyline_resources: HashMap<EntityId, PolylineResources>,
+    // One polyline slot per debug marker (markers each carry their own color)
+    marker_resources: Vec<PolylineResources>,
+    // One polyline slot per axis gizmo segment (dbg.showAxes)
+    axis_resources: Vec<PolylineResources>,
 
     // Mesh particle rendering
     mesh_particle_pipeline: wgpu::RenderPipeline,
+    additive_mesh_particle_pipeline: wgpu::RenderPipeline,
     mesh_particle_instance_buffer: wgpu::Buffer,
     mesh_particle_view_buffer: wgpu::Buffer,
     /// Bind group for mesh particles (uses view_proj only)
@@ -366,12 +567,22 @@ pub struct Renderer {
 
     // Billboard particle rendering
     billboard_particle_pipeline: wgpu::RenderPipeline,
+    additive_billboard_particle_pipeline: wgpu::RenderPipeline,
     billboard_particle_instance_buffer: wgpu::Buffer,
     billboard_particle_uniform_buffer: wgpu::Buffer,
     billboard_particle_bind_group: wgpu::BindGroup,
+    sprite_particle_pipeline: wgpu::RenderPipeline,
+    additive_sprite_particle_pipeline: wgpu::RenderPipeline,
+    sprite_bind_group_layout: wgpu::BindGroupLayout,
+    sprite_sampler: wgpu::Sampler,
+    /// Uploaded sprite textures, rebuilt when the registered asset changes
+    sprite_bind_groups: HashMap<String, (Arc<SpriteAsset>, wgpu::BindGroup)>,
     billboard_quad_vertex_buffer: wgpu::Buffer,
     billboard_quad_index_buffer: wgpu::Buffer,
 
+    // Last seen VisualiserState::seek_generation (feedback is cleared on change)
+    last_seek_generation: u64,
+
     // Material system
     material_registry: MaterialRegistry,
     material_pipeline_manager: MaterialPipelineManager,
@@ -391,6 +602,8 @@ pub struct Renderer {
 }
 
 impl Renderer {
+    /// Create a renderer whose frames are written in `format`. The scene and
+    /// post-processing run in `HDR_FORMAT` and are tonemapped to `format`.
     pub fn new(
         device: wgpu::Device,
         queue: wgpu::Queue,
@@ -455,10 +668,98 @@ impl Renderer {
             push_constant_ranges: &[],
         });
 
-        let mesh_pipeline =
-            pipeline::create_render_pipeline(&device, &mesh_pipeline_layout, format);
-        let wireframe_pipeline =
-            pipeline::create_wireframe_pipeline(&device, &mesh_pipeline_layout, format);
+        let mesh_pipeline = pipeline::create_render_pipeline(
+            &device,
+            &mesh_pipeline_layout,
+            HDR_FORMAT,
+            wgpu::BlendState::REPLACE,
+        );
+        let wireframe_pipeline = pipeline::create_wireframe_pipeline(
+            &device,
+            &mesh_pipeline_layout,
+            HDR_FORMAT,
+            wgpu::BlendState::ALPHA_BLENDING,
+        );
+
+        // Additive variants used when scene.blendMode = "add"
+        let additive_blend = BlendMode::Additive.to_blend_state();
+        let additive_mesh_pipeline = pipeline::create_render_pipeline(
+            &device,
+            &mesh_pipeline_layout,
+            HDR_FORMAT,
+            additive_blend,
+        );
+        let additive_wireframe_pipeline = pipeline::create_wireframe_pipeline(
+            &device,
+            &mesh_pipeline_layout,
+            HDR_FORMAT,
+            additive_blend,
+        );
+        let wireframe_overlay_pipeline = pipeline::create_wireframe_overlay_pipeline(
+            &device,
+            &mesh_pipeline_layout,
+            HDR_FORMAT,
+            wgpu::BlendState::REPLACE,
+        );
+        let additive_wireframe_overlay_pipeline = pipeline::create_wireframe_overlay_pipeline(
+            &device,
+            &mesh_pipeline_layout,
+            HDR_FORMAT,
+            additive_blend,
+        );
+
+        // === Skinned Mesh Pipeline Setup ===
+
+        let bone_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Bone Uniform Buffer (Dynamic)"),
+            size: (BONE_SLOT_SIZE * MAX_SKINNED_MESHES_PER_FRAME) as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bone_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: true,
+                        min_binding_size: wgpu::BufferSize::new(BONE_SLOT_SIZE as u64),
+                    },
+                    count: None,
+                }],
+                label: Some("bone_bind_group_layout"),
+            });
+        let bone_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bone_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                    buffer: &bone_buffer,
+                    offset: 0,
+                    size: wgpu::BufferSize::new(BONE_SLOT_SIZE as u64),
+                }),
+            }],
+            label: Some("bone_bind_group"),
+        });
+        let skinned_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Skinned Pipeline Layout"),
+                bind_group_layouts: &[&mesh_bind_group_layout, &bone_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        let skinned_pipeline = pipeline::create_skinned_pipeline(
+            &device,
+            &skinned_pipeline_layout,
+            HDR_FORMAT,
+            wgpu::BlendState::REPLACE,
+        );
+        let additive_skinned_pipeline = pipeline::create_skinned_pipeline(
+            &device,
+            &skinned_pipeline_layout,
+            HDR_FORMAT,
+            additive_blend,
+        );
 
         // === Geometry Setup ===
 
@@ -481,6 +782,12 @@ impl Renderer {
                 contents: bytemuck::cast_slice(&cube_edge_indices),
                 usage: wgpu::BufferUsages::INDEX,
             });
+        let (cube_barycentric_buffer, cube_barycentric_vertices) = create_barycentric_vertex_buffer(
+            &device,
+            "Cube Barycentric Vertex Buffer",
+            &cube_vertices,
+            &cube_indices,
+        );
         let cube_geometry = MeshGeometry {
             vertex_buffer: cube_vertex_buffer,
             index_buffer: cube_index_buffer,
@@ -488,63 +795,15 @@ impl Renderer {
             num_vertices: cube_vertices.len() as u32,
             wireframe_index_buffer: Some(cube_wireframe_index_buffer),
             num_edges: cube_edge_indices.len() as u32,
+            barycentric_vertex_buffer: Some(cube_barycentric_buffer),
+            num_barycentric_vertices: cube_barycentric_vertices,
         };
 
-        // Plane geometry
-        let (plane_vertices, plane_indices) = mesh::create_plane_geometry();
-        let plane_edge_indices = mesh::extract_edges(&plane_indices);
-        let plane_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Plane Vertex Buffer"),
-            contents: bytemuck::cast_slice(&plane_vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-        let plane_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Plane Index Buffer"),
-            contents: bytemuck::cast_slice(&plane_indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-        let plane_wireframe_index_buffer =
-            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Plane Wireframe Index Buffer"),
-                contents: bytemuck::cast_slice(&plane_edge_indices),
-                usage: wgpu::BufferUsages::INDEX,
-            });
-        let plane_geometry = MeshGeometry {
-            vertex_buffer: plane_vertex_buffer,
-            index_buffer: plane_index_buffer,
-            num_indices: plane_indices.len() as u32,
-            num_vertices: plane_vertices.len() as u32,
-            wireframe_index_buffer: Some(plane_wireframe_index_buffer),
-            num_edges: plane_edge_indices.len() as u32,
-        };
-
-        // Sphere geometry
-        let (sphere_vertices, sphere_indices) = mesh::create_sphere_geometry();
-        let sphere_edge_indices = mesh::extract_edges(&sphere_indices);
-        let sphere_vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Sphere Vertex Buffer"),
-            contents: bytemuck::cast_slice(&sphere_vertices),
-            usage: wgpu::BufferUsages::VERTEX,
-        });
-        let sphere_index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Sphere Index Buffer"),
-            contents: bytemuck::cast_slice(&sphere_indices),
-            usage: wgpu::BufferUsages::INDEX,
-        });
-        let sphere_wireframe_index_buffer =
-            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some("Sphere Wireframe Index Buffer"),
-                contents: bytemuck::cast_slice(&sphere_edge_indices),
-                usage: wgpu::BufferUsages::INDEX,
-            });
-        let sphere_geometry = MeshGeometry {
-            vertex_buffer: sphere_vertex_buffer,
-            index_buffer: sphere_index_buffer,
-            num_indices: sphere_indices.len() as u32,
-            num_vertices: sphere_vertices.len() as u32,
-            wireframe_index_buffer: Some(sphere_wireframe_index_buffer),
-            num_edges: sphere_edge_indices.len() as u32,
-        };
+        // Default plane, also the quad under blob shadows
+        let (plane_vertices, plane_indices) =
+            mesh::create_plane_geometry(mesh::DEFAULT_PLANE_SUBDIVISIONS);
+        let plane_geometry =
+            create_mesh_geometry(&device, "Plane", &plane_vertices, &plane_indices);
 
         // Debug cube geometry (8 vertices, 12 edges for wireframe bounding box)
         let (debug_cube_vertices, debug_cube_edges) = mesh::create_debug_cube_geometry();
@@ -567,6 +826,8 @@ impl Renderer {
             num_vertices: debug_cube_vertices.len() as u32,
             wireframe_index_buffer: None, // We use index_buffer directly for edges
             num_edges: debug_cube_edges.len() as u32,
+            barycentric_vertex_buffer: None,
+            num_barycentric_vertices: 0,
         };
 
         // Staging buffer for transformed debug bounding box vertices
@@ -611,8 +872,230 @@ impl Renderer {
             push_constant_ranges: &[],
         });
 
-        let line_pipeline =
-            pipeline::create_sparkline_pipeline(&device, &line_pipeline_layout, format);
+        let line_pipeline = pipeline::create_sparkline_pipeline(
+            &device,
+            &line_pipeline_layout,
+            HDR_FORMAT,
+            wgpu::BlendState::ALPHA_BLENDING,
+        );
+        let additive_line_pipeline = pipeline::create_sparkline_pipeline(
+            &device,
+            &line_pipeline_layout,
+            HDR_FORMAT,
+            additive_blend,
+        );
+
+        // Spectrogram overlay: uniforms, ring-buffer texture and sampler
+        let spectrogram_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+                label: Some("spectrogram_bind_group_layout"),
+            });
+        let spectrogram_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Spectrogram Pipeline Layout"),
+                bind_group_layouts: &[&spectrogram_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        let spectrogram_pipeline = pipeline::create_spectrogram_pipeline(
+            &device,
+            &spectrogram_pipeline_layout,
+            HDR_FORMAT,
+        );
+
+        // Reference grid overlay: a single uniform buffer
+        let reference_grid_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+                label: Some("reference_grid_bind_group_layout"),
+            });
+        let reference_grid_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Reference Grid Pipeline Layout"),
+                bind_group_layouts: &[&reference_grid_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        let reference_grid_pipeline = pipeline::create_reference_grid_pipeline(
+            &device,
+            &reference_grid_pipeline_layout,
+            HDR_FORMAT,
+        );
+        let reference_grid_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Reference Grid Uniform Buffer"),
+            size: std::mem::size_of::<GridUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let reference_grid_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &reference_grid_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: reference_grid_uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("reference_grid_bind_group"),
+        });
+
+        // UI overlay: a projection uniform and a vertex buffer rebuilt each
+        // frame. It draws onto the tonemapped output, so it uses `format`.
+        let ui_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[wgpu::BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                }],
+                label: Some("ui_bind_group_layout"),
+            });
+        let ui_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("UI Pipeline Layout"),
+            bind_group_layouts: &[&ui_bind_group_layout],
+            push_constant_ranges: &[],
+        });
+        let ui_pipeline = pipeline::create_ui_pipeline(&device, &ui_pipeline_layout, format);
+        let ui_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("UI Uniform Buffer"),
+            size: std::mem::size_of::<UiUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let ui_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &ui_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: ui_uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("ui_bind_group"),
+        });
+        let ui_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("UI Vertex Buffer"),
+            size: (MAX_UI_RECTS * 6 * UI_VERTEX_FLOATS * std::mem::size_of::<f32>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        // Scene background: uniforms plus a skybox texture (a 1x1 placeholder
+        // for gradients). Equirectangular images wrap horizontally.
+        let background_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 2,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+                label: Some("background_bind_group_layout"),
+            });
+        let background_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Background Pipeline Layout"),
+                bind_group_layouts: &[&background_bind_group_layout],
+                push_constant_ranges: &[],
+            });
+        let background_pipeline =
+            pipeline::create_background_pipeline(&device, &background_pipeline_layout, HDR_FORMAT);
+        let background_uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Background Uniform Buffer"),
+            size: std::mem::size_of::<BackgroundUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let background_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Background Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let placeholder_texture = device.create_texture_with_data(
+            &queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Background Placeholder Texture"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &[0, 0, 0, 255],
+        );
+        let background_bind_group = create_background_bind_group(
+            &device,
+            &background_bind_group_layout,
+            &background_uniform_buffer,
+            &placeholder_texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            &background_sampler,
+        );
 
         // Line vertex buffer (stores x,y pairs as floats)
         let line_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
@@ -671,10 +1154,30 @@ impl Renderer {
                 push_constant_ranges: &[],
             });
 
-        let point_cloud_pipeline =
-            pipeline::create_point_cloud_pipeline(&device, &point_cloud_pipeline_layout, format);
-        let polyline_pipeline =
-            pipeline::create_polyline_pipeline(&device, &point_cloud_pipeline_layout, format);
+        let point_cloud_pipeline = pipeline::create_point_cloud_pipeline(
+            &device,
+            &point_cloud_pipeline_layout,
+            HDR_FORMAT,
+            wgpu::BlendState::ALPHA_BLENDING,
+        );
+        let additive_point_cloud_pipeline = pipeline::create_point_cloud_pipeline(
+            &device,
+            &point_cloud_pipeline_layout,
+            HDR_FORMAT,
+            additive_blend,
+        );
+        let polyline_pipeline = pipeline::create_polyline_pipeline(
+            &device,
+            &point_cloud_pipeline_layout,
+            HDR_FORMAT,
+            wgpu::BlendState::ALPHA_BLENDING,
+        );
+        let additive_polyline_pipeline = pipeline::create_polyline_pipeline(
+            &device,
+            &point_cloud_pipeline_layout,
+            HDR_FORMAT,
+            additive_blend,
+        );
 
         // === Mesh Particle Pipeline Setup ===
 
@@ -721,7 +1224,14 @@ impl Renderer {
         let mesh_particle_pipeline = pipeline::create_mesh_particle_pipeline(
             &device,
             &mesh_particle_pipeline_layout,
-            format,
+            HDR_FORMAT,
+            ParticleBlend::Alpha.blend_mode().to_blend_state(),
+        );
+        let additive_mesh_particle_pipeline = pipeline::create_mesh_particle_pipeline(
+            &device,
+            &mesh_particle_pipeline_layout,
+            HDR_FORMAT,
+            ParticleBlend::Add.blend_mode().to_blend_state(),
         );
 
         // Instance buffer for mesh particles
@@ -735,9 +1245,8 @@ impl Renderer {
 
         // === Billboard Particle Pipeline Setup ===
 
-        // Billboard uniforms: view_proj (mat4) + camera_right (vec4) + camera_up (vec4)
-        // Total: 16 + 4 + 4 = 24 floats = 96 bytes
-        let billboard_uniform_data: [f32; 24] = [0.0; 24];
+        // Billboard uniforms: view_proj (mat4) + camera_right + camera_up + depth_params (vec4s)
+        let billboard_uniform_data = [0.0f32; pipeline::BILLBOARD_PARTICLE_UNIFORM_FLOATS];
         let billboard_particle_uniform_buffer =
             device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                 label: Some("Billboard Particle Uniform Buffer"),
@@ -745,28 +1254,71 @@ impl Renderer {
                 usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
             });
 
-        // Bind group layout for billboard particles (view_proj + camera vectors)
+        // Soft particles sample scene depth. The scene pass has no depth buffer yet, so bind
+        // a 1x1 texture at the far plane (depth 1.0), which leaves every particle unfaded.
+        let soft_particle_depth_texture = device.create_texture_with_data(
+            &queue,
+            &wgpu::TextureDescriptor {
+                label: Some("Soft Particle Depth Placeholder"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R32Float,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            bytemuck::bytes_of(&1.0f32),
+        );
+        let soft_particle_depth_view =
+            soft_particle_depth_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Bind group layout for billboard particles (uniforms + scene depth)
         let billboard_particle_bind_group_layout =
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                entries: &[wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Buffer {
+                            ty: wgpu::BufferBindingType::Uniform,
+                            has_dynamic_offset: false,
+                            min_binding_size: None,
+                        },
+                        count: None,
                     },
-                    count: None,
-                }],
+                    // Depth formats can also be bound as unfilterable float
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                ],
                 label: Some("billboard_particle_bind_group_layout"),
             });
 
         let billboard_particle_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
             layout: &billboard_particle_bind_group_layout,
-            entries: &[wgpu::BindGroupEntry {
-                binding: 0,
-                resource: billboard_particle_uniform_buffer.as_entire_binding(),
-            }],
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: billboard_particle_uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&soft_particle_depth_view),
+                },
+            ],
             label: Some("billboard_particle_bind_group"),
         });
 
@@ -780,9 +1332,77 @@ impl Renderer {
         let billboard_particle_pipeline = pipeline::create_billboard_particle_pipeline(
             &device,
             &billboard_particle_pipeline_layout,
-            format,
+            HDR_FORMAT,
+            ParticleBlend::Alpha.blend_mode().to_blend_state(),
+            "fs_particle",
+        );
+        let additive_billboard_particle_pipeline = pipeline::create_billboard_particle_pipeline(
+            &device,
+            &billboard_particle_pipeline_layout,
+            HDR_FORMAT,
+            ParticleBlend::Add.blend_mode().to_blend_state(),
+            "fs_particle",
+        );
+
+        // Sprite particles add a texture + sampler at group 1
+        let sprite_bind_group_layout =
+            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+                entries: &[
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Texture {
+                            sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                            view_dimension: wgpu::TextureViewDimension::D2,
+                            multisampled: false,
+                        },
+                        count: None,
+                    },
+                    wgpu::BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: wgpu::ShaderStages::FRAGMENT,
+                        ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                        count: None,
+                    },
+                ],
+                label: Some("sprite_particle_bind_group_layout"),
+            });
+
+        let sprite_particle_pipeline_layout =
+            device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                label: Some("Sprite Particle Pipeline Layout"),
+                bind_group_layouts: &[
+                    &billboard_particle_bind_group_layout,
+                    &sprite_bind_group_layout,
+                ],
+                push_constant_ranges: &[],
+            });
+
+        let sprite_particle_pipeline = pipeline::create_billboard_particle_pipeline(
+            &device,
+            &sprite_particle_pipeline_layout,
+            HDR_FORMAT,
+            ParticleBlend::Alpha.blend_mode().to_blend_state(),
+            "fs_particle_sprite",
+        );
+        let additive_sprite_particle_pipeline = pipeline::create_billboard_particle_pipeline(
+            &device,
+            &sprite_particle_pipeline_layout,
+            HDR_FORMAT,
+            ParticleBlend::Add.blend_mode().to_blend_state(),
+            "fs_particle_sprite",
         );
 
+        // Clamp so sheet cells don't bleed into their neighbours at the quad edges
+        let sprite_sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Sprite Particle Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
         // Instance buffer for billboard particles
         let billboard_particle_instance_buffer = device.create_buffer(&wgpu::BufferDescriptor {
             label: Some("Billboard Particle Instance Buffer"),
@@ -818,7 +1438,7 @@ impl Renderer {
         // === Material System Setup ===
         let material_registry = MaterialRegistry::new();
         let material_pipeline_manager =
-            MaterialPipelineManager::new(&device, format, &material_registry);
+            MaterialPipelineManager::new(&device, HDR_FORMAT, &material_registry);
         let material_global_uniforms = GlobalUniforms::default();
 
         // === Blob Shadow Pipeline Setup ===
@@ -882,7 +1502,7 @@ impl Renderer {
                 module: &shadow_shader,
                 entry_point: Some("fs_main"),
                 targets: &[Some(wgpu::ColorTargetState {
-                    format,
+                    format: HDR_FORMAT,
                     // Multiply blend for shadow (darkens the background)
                     blend: Some(wgpu::BlendState {
                         color: wgpu::BlendComponent {
@@ -943,39 +1563,80 @@ impl Renderer {
             size,
             mesh_pipeline,
             wireframe_pipeline,
+            additive_mesh_pipeline,
+            additive_wireframe_pipeline,
+            wireframe_overlay_pipeline,
+            additive_wireframe_overlay_pipeline,
             mesh_bind_group_layout,
             uniform_buffer,
             mesh_bind_group,
             uniforms,
+            skinned_pipeline,
+            additive_skinned_pipeline,
+            bone_buffer,
+            bone_bind_group,
             cube_geometry,
             plane_geometry,
-            sphere_geometry,
+            primitive_geometry: HashMap::new(),
             debug_cube_geometry,
             debug_bounds_vertex_buffer,
             loaded_mesh_buffers: HashMap::new(),
             radial_ring_geometry: None,
             radial_ring_params: None,
             deformed_vertex_staging,
+            deformed_barycentric_staging: None,
             line_pipeline,
+            additive_line_pipeline,
             line_bind_group_layout,
             line_vertex_buffer,
             line_uniform_buffer,
             line_bind_group,
+            plot_resources: Vec::new(),
+            plot_label_resources: Vec::new(),
+            waveform_resources: None,
+            spectrogram_pipeline,
+            spectrogram_bind_group_layout,
+            spectrogram_resources: None,
+            reference_grid_pipeline,
+            reference_grid_uniform_buffer,
+            reference_grid_bind_group,
+            ui_pipeline,
+            ui_uniform_buffer,
+            ui_bind_group,
+            ui_vertex_buffer,
+            background_pipeline,
+            background_bind_group_layout,
+            background_uniform_buffer,
+            background_sampler,
+            background_bind_group,
+            skybox_bind_group: None,
             point_cloud_pipeline,
+            additive_point_cloud_pipeline,
             point_cloud_bind_group_layout,
             point_sprite_resources: HashMap::new(),
             polyline_pipeline,
+            additive_polyline_pipeline,
             polyline_resources: HashMap::new(),
+            marker_resources: Vec::new(),
+            axis_resources: Vec::new(),
             mesh_particle_pipeline,
+            additive_mesh_particle_pipeline,
             mesh_particle_instance_buffer,
             mesh_particle_view_buffer,
             mesh_particle_bind_group,
             billboard_particle_pipeline,
+            additive_billboard_particle_pipeline,
             billboard_particle_instance_buffer,
             billboard_particle_uniform_buffer,
             billboard_particle_bind_group,
+            sprite_particle_pipeline,
+            additive_sprite_particle_pipeline,
+            sprite_bind_group_layout,
+            sprite_sampler,
+            sprite_bind_groups: HashMap::new(),
             billboard_quad_vertex_buffer,
             billboard_quad_index_buffer,
+            last_seek_generation: 0,
             material_registry,
             material_pipeline_manager,
             material_global_uniforms,
@@ -996,6 +1657,261 @@ impl Renderer {
         &self.queue
     }
 
+    /// The HDR texture the scene is drawn into, before post-processing and tonemapping.
+    pub fn scene_texture(&self) -> &wgpu::Texture {
+        self.post_processor.scene_texture()
+    }
+
+    /// Discard accumulated feedback before the next frame is drawn.
+    pub fn request_feedback_clear(&mut self) {
+        self.post_processor.request_feedback_clear();
+    }
+
+    /// Solid mesh pipeline for the scene blend mode.
+    fn mesh_pipeline_for(&self, scene_blend: SceneBlendMode) -> &wgpu::RenderPipeline {
+        match scene_blend {
+            SceneBlendMode::Normal => &self.mesh_pipeline,
+            SceneBlendMode::Add => &self.additive_mesh_pipeline,
+        }
+    }
+
+    /// Skinned mesh pipeline for the scene blend mode.
+    fn skinned_pipeline_for(&self, scene_blend: SceneBlendMode) -> &wgpu::RenderPipeline {
+        match scene_blend {
+            SceneBlendMode::Normal => &self.skinned_pipeline,
+            SceneBlendMode::Add => &self.additive_skinned_pipeline,
+        }
+    }
+
+    /// Whether a mesh's skin can be applied by the skinned pipeline. Other
+    /// render modes, materials, morphs and deformations need skinned vertices
+    /// on the CPU.
+    fn skins_on_gpu(&self, mesh: &crate::scene_graph::MeshInstance, skin: &Skin) -> bool {
+        mesh.render_mode == RenderMode::Solid
+            && mesh.deformations.is_empty()
+            && mesh.morph_weights.is_empty()
+            && skin.joints.len() <= MAX_JOINTS
+            && !mesh
+                .material_id
+                .as_ref()
+                .is_some_and(|id| self.material_registry.exists(id))
+    }
+
+    /// Billboard particle pipeline for a particle blend mode.
+    fn billboard_particle_pipeline_for(&self, blend: ParticleBlend) -> &wgpu::RenderPipeline {
+        match blend {
+            ParticleBlend::Alpha => &self.billboard_particle_pipeline,
+            ParticleBlend::Add => &self.additive_billboard_particle_pipeline,
+        }
+    }
+
+    /// Textured sprite particle pipeline for a particle blend mode.
+    fn sprite_particle_pipeline_for(&self, blend: ParticleBlend) -> &wgpu::RenderPipeline {
+        match blend {
+            ParticleBlend::Alpha => &self.sprite_particle_pipeline,
+            ParticleBlend::Add => &self.additive_sprite_particle_pipeline,
+        }
+    }
+
+    /// Upload a sprite texture unless the cached bind group already holds this asset.
+    fn ensure_sprite_bind_group(&mut self, asset: &Arc<SpriteAsset>) {
+        if let Some((cached, _)) = self.sprite_bind_groups.get(&asset.id) {
+            if Arc::ptr_eq(cached, asset) {
+                return;
+            }
+        }
+
+        let texture = self.device.create_texture_with_data(
+            &self.queue,
+            &wgpu::TextureDescriptor {
+                label: Some(&format!("Sprite Texture: {}", asset.id)),
+                size: wgpu::Extent3d {
+                    width: asset.width,
+                    height: asset.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &asset.rgba,
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.sprite_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sprite_sampler),
+                },
+            ],
+            label: Some("sprite_particle_bind_group"),
+        });
+        self.sprite_bind_groups
+            .insert(asset.id.clone(), (Arc::clone(asset), bind_group));
+    }
+
+    /// Upload a skybox texture unless the cached bind group already holds this asset.
+    fn ensure_skybox_bind_group(&mut self, asset: &Arc<SpriteAsset>) {
+        if let Some((cached, _)) = &self.skybox_bind_group {
+            if Arc::ptr_eq(cached, asset) {
+                return;
+            }
+        }
+
+        let texture = self.device.create_texture_with_data(
+            &self.queue,
+            &wgpu::TextureDescriptor {
+                label: Some(&format!("Skybox Texture: {}", asset.id)),
+                size: wgpu::Extent3d {
+                    width: asset.width,
+                    height: asset.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            wgpu::util::TextureDataOrder::LayerMajor,
+            &asset.rgba,
+        );
+        let bind_group = create_background_bind_group(
+            &self.device,
+            &self.background_bind_group_layout,
+            &self.background_uniform_buffer,
+            &texture.create_view(&wgpu::TextureViewDescriptor::default()),
+            &self.background_sampler,
+        );
+        self.skybox_bind_group = Some((Arc::clone(asset), bind_group));
+    }
+
+    /// Write the background uniforms for this frame. Returns whether to draw
+    /// the skybox, the gradient, or nothing (no background, or a skybox whose
+    /// image isn't loaded): Some(true), Some(false) or None.
+    fn prepare_background(&mut self, state: &VisualiserState) -> Option<bool> {
+        let (top, bottom, skybox) = match state.scene_background()? {
+            SceneBackground::Gradient { top, bottom } => (*top, *bottom, false),
+            SceneBackground::Skybox { asset_id } => {
+                let asset = state.sprite_registry.get(asset_id)?;
+                self.ensure_skybox_bind_group(&asset);
+                ([0.0; 4], [0.0; 4], true)
+            }
+        };
+        let uniforms = BackgroundUniforms {
+            inv_view_proj: glam::Mat4::from_cols_array_2d(&self.uniforms.view_proj)
+                .inverse()
+                .to_cols_array_2d(),
+            top,
+            bottom,
+            mode: skybox as u32,
+            _padding: [0; 3],
+        };
+        self.queue.write_buffer(
+            &self.background_uniform_buffer,
+            0,
+            bytemuck::cast_slice(&[uniforms]),
+        );
+        Some(skybox)
+    }
+
+    /// Mesh particle pipeline for a particle blend mode.
+    fn mesh_particle_pipeline_for(&self, blend: ParticleBlend) -> &wgpu::RenderPipeline {
+        match blend {
+            ParticleBlend::Alpha => &self.mesh_particle_pipeline,
+            ParticleBlend::Add => &self.additive_mesh_particle_pipeline,
+        }
+    }
+
+    /// Wireframe pipeline for the scene blend mode.
+    fn wireframe_pipeline_for(&self, scene_blend: SceneBlendMode) -> &wgpu::RenderPipeline {
+        match scene_blend {
+            SceneBlendMode::Normal => &self.wireframe_pipeline,
+            SceneBlendMode::Add => &self.additive_wireframe_pipeline,
+        }
+    }
+
+    /// Single-pass wireframe overlay pipeline for the scene blend mode.
+    fn wireframe_overlay_pipeline_for(&self, scene_blend: SceneBlendMode) -> &wgpu::RenderPipeline {
+        match scene_blend {
+            SceneBlendMode::Normal => &self.wireframe_overlay_pipeline,
+            SceneBlendMode::Add => &self.additive_wireframe_overlay_pipeline,
+        }
+    }
+
+    /// Draw barycentric vertices with the wireframe overlay pipeline, using
+    /// the mesh's pre-written uniform slot.
+    fn draw_wireframe_overlay(
+        &self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        scene_blend: SceneBlendMode,
+        dynamic_offset: u32,
+        vertex_buffer: &wgpu::Buffer,
+        num_vertices: u32,
+    ) {
+        render_pass.set_pipeline(self.wireframe_overlay_pipeline_for(scene_blend));
+        render_pass.set_bind_group(0, &self.mesh_bind_group, &[dynamic_offset]);
+        render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
+        render_pass.draw(0..num_vertices, 0..1);
+    }
+
+    /// Write deformed wireframe overlay vertices to the staging buffer,
+    /// growing it if needed.
+    fn write_deformed_barycentric(&mut self, vertices: &[BarycentricVertex]) {
+        let size = std::mem::size_of_val(vertices) as u64;
+        if self
+            .deformed_barycentric_staging
+            .as_ref()
+            .is_none_or(|buffer| buffer.size() < size)
+        {
+            self.deformed_barycentric_staging =
+                Some(self.device.create_buffer(&wgpu::BufferDescriptor {
+                    label: Some("Deformed Barycentric Vertex Staging Buffer"),
+                    size,
+                    usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+                    mapped_at_creation: false,
+                }));
+        }
+        if let Some(buffer) = &self.deformed_barycentric_staging {
+            self.queue
+                .write_buffer(buffer, 0, bytemuck::cast_slice(vertices));
+        }
+    }
+
+    /// Line strip pipeline for the scene blend mode.
+    fn line_pipeline_for(&self, scene_blend: SceneBlendMode) -> &wgpu::RenderPipeline {
+        match scene_blend {
+            SceneBlendMode::Normal => &self.line_pipeline,
+            SceneBlendMode::Add => &self.additive_line_pipeline,
+        }
+    }
+
+    /// Polyline pipeline for the scene blend mode.
+    fn polyline_pipeline_for(&self, scene_blend: SceneBlendMode) -> &wgpu::RenderPipeline {
+        match scene_blend {
+            SceneBlendMode::Normal => &self.polyline_pipeline,
+            SceneBlendMode::Add => &self.additive_polyline_pipeline,
+        }
+    }
+
+    /// Point cloud (and ribbon point) pipeline for the scene blend mode.
+    fn point_cloud_pipeline_for(&self, scene_blend: SceneBlendMode) -> &wgpu::RenderPipeline {
+        match scene_blend {
+            SceneBlendMode::Normal => &self.point_cloud_pipeline,
+            SceneBlendMode::Add => &self.additive_point_cloud_pipeline,
+        }
+    }
+
     /// Get the material registry.
     pub fn material_registry(&self) -> &MaterialRegistry {
         &self.material_registry
@@ -1089,9 +2005,143 @@ impl Renderer {
         }
     }
 
-    fn create_polyline_resources(&self, entity_id: EntityId) -> PolylineResources {
+    fn create_sparkline_resources(&self, label: &str, max_points: usize) -> SparklineResources {
+        let vertex_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} Vertex Buffer", label)),
+            size: (max_points * 2 * std::mem::size_of::<f32>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let uniform_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} Uniform Buffer", label)),
+            size: std::mem::size_of::<LineUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.line_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some(&format!("{} Bind Group", label)),
+        });
+
+        SparklineResources {
+            vertex_buffer,
+            uniform_buffer,
+            bind_group,
+        }
+    }
+
+    fn create_spectrogram_resources(&self) -> SpectrogramResources {
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Spectrogram Texture"),
+            size: wgpu::Extent3d {
+                width: SPECTROGRAM_COLUMNS,
+                height: SPECTROGRAM_ROWS,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        // Repeat in u so the scroll offset wraps around the ring buffer
+        let sampler = self.device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Spectrogram Sampler"),
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let uniform_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Spectrogram Uniform Buffer"),
+            size: std::mem::size_of::<SpectrogramUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.spectrogram_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+            label: Some("spectrogram_bind_group"),
+        });
+
+        SpectrogramResources {
+            texture,
+            uniform_buffer,
+            bind_group,
+            uploaded_columns: None,
+        }
+    }
+
+    /// Bring the spectrogram texture up to date: the newest column when one
+    /// frame behind, otherwise the whole ring buffer.
+    fn upload_spectrogram(&mut self, spectrogram: &Spectrogram) {
+        if self.spectrogram_resources.is_none() {
+            self.spectrogram_resources = Some(self.create_spectrogram_resources());
+        }
+        let Some(resources) = self.spectrogram_resources.as_mut() else {
+            return;
+        };
+        let written = spectrogram.columns_written();
+        if resources.uploaded_columns == Some(written) {
+            return;
+        }
+
+        let (origin_x, width, data) = if resources.uploaded_columns.map(|c| c + 1) == Some(written)
+        {
+            let column = (spectrogram.cursor() + SPECTROGRAM_COLUMNS - 1) % SPECTROGRAM_COLUMNS;
+            (column, 1, spectrogram.column_rgba(column))
+        } else {
+            (0, SPECTROGRAM_COLUMNS, spectrogram.pixels().to_vec())
+        };
+        self.queue.write_texture(
+            wgpu::ImageCopyTexture {
+                texture: &resources.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: origin_x,
+                    y: 0,
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            &data,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(SPECTROGRAM_ROWS),
+            },
+            wgpu::Extent3d {
+                width,
+                height: SPECTROGRAM_ROWS,
+                depth_or_array_layers: 1,
+            },
+        );
+        resources.uploaded_columns = Some(written);
+    }
+
+    fn create_polyline_resources(&self, name: &str) -> PolylineResources {
         let segment_buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
-            label: Some(&format!("Polyline Segments {}", entity_id.0)),
+            label: Some(&format!("Polyline Segments {}", name)),
             size: (MAX_POLYLINE_SEGMENTS * 6 * std::mem::size_of::<f32>()) as u64,
             usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
             mapped_at_creation: false,
@@ -1107,7 +2157,7 @@ impl Renderer {
         let uniform_buffer = self
             .device
             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
-                label: Some(&format!("Polyline Uniforms {}", entity_id.0)),
+                label: Some(&format!("Polyline Uniforms {}", name)),
                 contents: bytemuck::cast_slice(&[uniforms]),
                 usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
             });
@@ -1117,7 +2167,7 @@ impl Renderer {
                 binding: 0,
                 resource: uniform_buffer.as_entire_binding(),
             }],
-            label: Some(&format!("Polyline Bind Group {}", entity_id.0)),
+            label: Some(&format!("Polyline Bind Group {}", name)),
         });
 
         PolylineResources {
@@ -1127,6 +2177,47 @@ impl Renderer {
         }
     }
 
+    /// Create geometry for a plane or sphere's subdivision level if it is not
+    /// cached yet.
+    fn ensure_primitive_geometry(&mut self, mesh_type: &MeshType) {
+        let key = match *mesh_type {
+            MeshType::Plane { subdivisions } => (PrimitiveKind::Plane, subdivisions),
+            MeshType::Sphere { segments } => (PrimitiveKind::Sphere, segments),
+            _ => return,
+        };
+        if self.primitive_geometry.contains_key(&key) {
+            return;
+        }
+
+        let (label, (vertices, indices)) = match key {
+            (PrimitiveKind::Plane, level) => (
+                format!("Plane x{level}"),
+                mesh::create_plane_geometry(level),
+            ),
+            (PrimitiveKind::Sphere, level) => (
+                format!("Sphere x{level}"),
+                mesh::create_sphere_geometry(level),
+            ),
+        };
+        let geometry = create_mesh_geometry(&self.device, &label, &vertices, &indices);
+        self.primitive_geometry.insert(key, geometry);
+    }
+
+    /// Shared geometry for a primitive mesh type. Planes and spheres must
+    /// have been created with `ensure_primitive_geometry`.
+    fn primitive_geometry(&self, mesh_type: &MeshType) -> Option<&MeshGeometry> {
+        match *mesh_type {
+            MeshType::Cube => Some(&self.cube_geometry),
+            MeshType::Plane { subdivisions } => self
+                .primitive_geometry
+                .get(&(PrimitiveKind::Plane, subdivisions)),
+            MeshType::Sphere { segments } => self
+                .primitive_geometry
+                .get(&(PrimitiveKind::Sphere, segments)),
+            MeshType::Asset(_) | MeshType::RadialRing { .. } => None,
+        }
+    }
+
     /// Get or create geometry for a radial ring, regenerating if parameters changed.
     fn get_or_create_radial_ring_geometry(
         &mut self,
@@ -1175,6 +2266,14 @@ impl Renderer {
                         usage: wgpu::BufferUsages::INDEX,
                     });
 
+            let (barycentric_vertex_buffer, num_barycentric_vertices) =
+                create_barycentric_vertex_buffer(
+                    &self.device,
+                    "Radial Ring Barycentric Vertex Buffer",
+                    &vertices,
+                    &indices,
+                );
+
             self.radial_ring_geometry = Some(MeshGeometry {
                 vertex_buffer,
                 index_buffer,
@@ -1182,6 +2281,8 @@ impl Renderer {
                 num_indices: indices.len() as u32,
                 num_edges: edge_indices.len() as u32,
                 num_vertices: vertices.len() as u32,
+                barycentric_vertex_buffer: Some(barycentric_vertex_buffer),
+                num_barycentric_vertices,
             });
             self.radial_ring_params = Some(params);
         }
@@ -1213,6 +2314,21 @@ impl Renderer {
                         contents: bytemuck::cast_slice(&asset.edge_indices),
                         usage: wgpu::BufferUsages::INDEX,
                     });
+            let (barycentric_vertex_buffer, num_barycentric_vertices) =
+                create_barycentric_vertex_buffer(
+                    &self.device,
+                    &format!("Loaded Mesh Barycentric Vertex Buffer: {}", asset.id),
+                    &asset.vertices,
+                    &asset.indices,
+                );
+            let skin_vertex_buffer = asset.skin.as_ref().map(|skin| {
+                self.device
+                    .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some(&format!("Loaded Mesh Skin Vertex Buffer: {}", asset.id)),
+                        contents: bytemuck::cast_slice(&skin.vertices),
+                        usage: wgpu::BufferUsages::VERTEX,
+                    })
+            });
 
             self.loaded_mesh_buffers.insert(
                 asset.id.clone(),
@@ -1220,9 +2336,12 @@ impl Renderer {
                     vertex_buffer,
                     index_buffer,
                     wireframe_index_buffer,
+                    barycentric_vertex_buffer,
+                    skin_vertex_buffer,
                     num_indices: asset.indices.len() as u32,
                     num_edges: asset.edge_indices.len() as u32,
                     num_vertices: asset.vertices.len() as u32,
+                    num_barycentric_vertices,
                 },
             );
         }
@@ -1233,6 +2352,22 @@ impl Renderer {
         let scene_graph = state.scene_graph();
         let camera = state.camera_uniforms();
         let lighting = state.lighting_uniforms();
+        let scene_blend = state.scene_blend_mode();
+
+        // A seek (or loop wrap) invalidates accumulated feedback
+        if state.seek_generation() != self.last_seek_generation {
+            self.post_processor.request_feedback_clear();
+            self.last_seek_generation = state.seek_generation();
+        }
+
+        // Build material pipelines for an overriding scene blend mode on first use
+        if let Some(blend_mode) = scene_blend.override_blend() {
+            self.material_pipeline_manager.ensure_blend_variants(
+                &self.device,
+                &self.material_registry,
+                blend_mode,
+            );
+        }
 
         // Update view projection
         self.uniforms.update_view_proj(self.size, camera);
@@ -1247,9 +2382,27 @@ impl Renderer {
         self.material_global_uniforms.lighting_enabled = lighting.enabled;
         self.material_global_uniforms.camera_position = camera.position;
 
+        // Distance fog applies to meshes with and without materials
+        let fog = state.scene_fog();
+        let fog_color = fog.map_or([0.0, 0.0, 0.0, 1.0], |fog| {
+            let [r, g, b] = fog.color;
+            [r, g, b, 1.0]
+        });
+        let (fog_start, fog_end) = fog.map_or((0.0, 1.0), |fog| (fog.start, fog.end));
+        let fog_enabled = fog.is_some() as u32;
+        self.material_global_uniforms.fog_color = fog_color;
+        self.material_global_uniforms.fog_start = fog_start;
+        self.material_global_uniforms.fog_end = fog_end;
+        self.material_global_uniforms.fog_enabled = fog_enabled;
+        self.uniforms.camera_position = camera.position;
+        self.uniforms.fog_color = fog_color;
+        self.uniforms.fog_start = fog_start;
+        self.uniforms.fog_end = fog_end;
+        self.uniforms.fog_enabled = fog_enabled;
+
         // Collect meshes to render (we need to clone data to avoid borrow conflicts)
         // Include entity_id for debug bounds checking
-        let meshes_to_render: Vec<_> = scene_graph
+        let mut meshes_to_render: Vec<_> = scene_graph
             .meshes()
             .filter(|(entity_id, _mesh)| {
                 // Check isolation mode
@@ -1259,16 +2412,29 @@ impl Renderer {
                     }
                 }
                 // Check visibility
-                is_entity_visible(*entity_id, scene_graph)
+                scene_graph.is_effectively_visible(*entity_id)
             })
             .map(|(entity_id, mesh)| {
-                let world_matrix = compute_world_matrix(entity_id, scene_graph);
+                let world_matrix = scene_graph.world_matrix(entity_id);
                 (entity_id, mesh.clone(), world_matrix)
             })
             .collect();
 
+        // Draw order: lower layers first, then back to front within a layer
+        let camera_position = glam::Vec3::from_slice(&camera.position[..3]);
+        let camera_distance = |world_matrix: &glam::Mat4| {
+            world_matrix
+                .transform_point3(glam::Vec3::ZERO)
+                .distance_squared(camera_position)
+        };
+        meshes_to_render.sort_by(|(_, a, a_matrix), (_, b, b_matrix)| {
+            a.layer
+                .cmp(&b.layer)
+                .then(camera_distance(b_matrix).total_cmp(&camera_distance(a_matrix)))
+        });
+
         // Collect lines to render
-        let lines_to_render: Vec<_> = scene_graph
+        let mut lines_to_render: Vec<_> = scene_graph
             .lines()
             .enumerate()
             .filter(|(_, (entity_id, line))| {
@@ -1277,13 +2443,15 @@ impl Renderer {
                         return false;
                     }
                 }
-                is_entity_visible(*entity_id, scene_graph) && line.count > 0
+                scene_graph.is_effectively_visible(*entity_id) && line.count > 0
             })
             .map(|(idx, (_entity_id, line))| (idx, line.clone()))
             .collect();
+        // Lines are screen-space strips, so only their layer orders them
+        lines_to_render.sort_by_key(|(_, line)| line.layer);
 
         // Collect point clouds to render
-        let point_clouds_to_render: Vec<_> = scene_graph
+        let mut point_clouds_to_render: Vec<_> = scene_graph
             .point_clouds()
             .filter(|(entity_id, cloud)| {
                 if let Some(isolated_id) = state.debug_options.isolated_entity {
@@ -1291,13 +2459,19 @@ impl Renderer {
                         return false;
                     }
                 }
-                is_entity_visible(*entity_id, scene_graph) && !cloud.positions.is_empty()
+                scene_graph.is_effectively_visible(*entity_id) && !cloud.positions.is_empty()
             })
             .map(|(entity_id, cloud)| {
-                let world_matrix = compute_world_matrix(entity_id, scene_graph);
+                let world_matrix = scene_graph.world_matrix(entity_id);
                 (entity_id, cloud.clone(), world_matrix)
             })
             .collect();
+        // Same order as meshes: by layer, then back to front
+        point_clouds_to_render.sort_by(|(_, a, a_matrix), (_, b, b_matrix)| {
+            a.layer
+                .cmp(&b.layer)
+                .then(camera_distance(b_matrix).total_cmp(&camera_distance(a_matrix)))
+        });
 
         // Collect radial waves to render
         let radial_waves_to_render: Vec<_> = scene_graph
@@ -1308,10 +2482,10 @@ impl Renderer {
                         return false;
                     }
                 }
-                is_entity_visible(*entity_id, scene_graph)
+                scene_graph.is_effectively_visible(*entity_id)
             })
             .map(|(entity_id, wave)| {
-                let world_matrix = compute_world_matrix(entity_id, scene_graph);
+                let world_matrix = scene_graph.world_matrix(entity_id);
                 (entity_id, wave.clone(), world_matrix)
             })
             .collect();
@@ -1325,10 +2499,10 @@ impl Renderer {
                         return false;
                     }
                 }
-                is_entity_visible(*entity_id, scene_graph) && ribbon.count > 0
+                scene_graph.is_effectively_visible(*entity_id) && ribbon.count > 0
             })
             .map(|(entity_id, ribbon)| {
-                let world_matrix = compute_world_matrix(entity_id, scene_graph);
+                let world_matrix = scene_graph.world_matrix(entity_id);
                 (entity_id, ribbon.clone(), world_matrix)
             })
             .collect();
@@ -1358,11 +2532,85 @@ impl Renderer {
             .retain(|entity_id, _| polyline_ids.contains(entity_id));
         for entity_id in polyline_ids {
             if !self.polyline_resources.contains_key(&entity_id) {
-                let resources = self.create_polyline_resources(entity_id);
+                let resources = self.create_polyline_resources(&entity_id.0.to_string());
                 self.polyline_resources.insert(entity_id, resources);
             }
         }
 
+        let plots_to_render = state.debug_plots();
+        while self.plot_resources.len() < plots_to_render.len() {
+            let resources = self.create_sparkline_resources(
+                &format!("Debug Plot {}", self.plot_resources.len()),
+                DEBUG_PLOT_POINTS,
+            );
+            self.plot_resources.push(resources);
+            let label = self.create_polyline_resources(&format!(
+                "Debug Plot Label {}",
+                self.plot_label_resources.len()
+            ));
+            self.plot_label_resources.push(label);
+        }
+
+        let spectrogram_to_render = state.spectrogram();
+        match spectrogram_to_render {
+            Some(spectrogram) => self.upload_spectrogram(spectrogram),
+            None => self.spectrogram_resources = None,
+        }
+
+        // Rebuild the waveform envelope only when the audio or width changes
+        let waveform_to_render = state.waveform_overlay();
+        if let Some((audio, generation)) = waveform_to_render {
+            let columns = (self.size.width as usize).clamp(1, MAX_WAVEFORM_COLUMNS);
+            let key = (generation, columns);
+            if self.waveform_resources.as_ref().map(|r| r.key) != Some(key) {
+                let resources = match self.waveform_resources.take() {
+                    Some(resources) => resources,
+                    None => WaveformResources {
+                        envelope: self
+                            .create_sparkline_resources("Waveform", MAX_WAVEFORM_COLUMNS * 2),
+                        playhead: self.create_sparkline_resources("Waveform Playhead", 2),
+                        point_count: 0,
+                        key,
+                    },
+                };
+                let points = WaveformEnvelope::from_samples(audio.samples(), columns).to_gpu_data();
+                self.queue.write_buffer(
+                    &resources.envelope.vertex_buffer,
+                    0,
+                    bytemuck::cast_slice(&points),
+                );
+                self.queue.write_buffer(
+                    &resources.playhead.vertex_buffer,
+                    0,
+                    bytemuck::cast_slice(&[[0.0f32, 0.0], [0.0, 1.0]]),
+                );
+                self.waveform_resources = Some(WaveformResources {
+                    point_count: points.len(),
+                    key,
+                    ..resources
+                });
+            }
+        }
+
+        let markers_to_render = &state.debug_marker_layer().markers()[..state
+            .debug_marker_layer()
+            .markers()
+            .len()
+            .min(MAX_DEBUG_MARKERS)];
+        while self.marker_resources.len() < markers_to_render.len() {
+            let resources =
+                self.create_polyline_resources(&format!("marker {}", self.marker_resources.len()));
+            self.marker_resources.push(resources);
+        }
+
+        let mut axis_segments = state.axis_gizmo_segments();
+        axis_segments.truncate(MAX_AXIS_SEGMENTS);
+        while self.axis_resources.len() < axis_segments.len() {
+            let resources =
+                self.create_polyline_resources(&format!("axis {}", self.axis_resources.len()));
+            self.axis_resources.push(resources);
+        }
+
         let mut encoder = self
             .device
             .create_command_encoder(&wgpu::CommandEncoderDescriptor {
@@ -1372,6 +2620,9 @@ impl Renderer {
         // Pre-write all mesh uniform data to the buffer BEFORE the render pass.
         // This is critical: queue.write_buffer() is immediate, not recorded in the command stream.
         // If we write during the render pass, all meshes would use the last mesh's data.
+        // Bone matrices for GPU-skinned meshes get the same treatment.
+        let mut skin_offsets: Vec<Option<u32>> = vec![None; meshes_to_render.len()];
+        let mut skin_slot = 0;
         for (mesh_idx, (_entity_id, mesh, world_matrix)) in meshes_to_render.iter().enumerate() {
             if mesh_idx >= MAX_MESHES_PER_FRAME {
                 log::warn!(
@@ -1384,6 +2635,8 @@ impl Renderer {
 
             self.uniforms.model = world_matrix.to_cols_array_2d();
             self.uniforms.instance_color = mesh.color;
+            self.uniforms.wireframe_color = mesh.wireframe_color;
+            self.uniforms.wireframe_width = mesh.wireframe_width;
             let offset = (mesh_idx * 2 * UNIFORM_ALIGNMENT) as u64;
             self.queue.write_buffer(
                 &self.uniform_buffer,
@@ -1396,6 +2649,28 @@ impl Renderer {
                 offset + UNIFORM_ALIGNMENT as u64,
                 bytemuck::cast_slice(&[self.uniforms]),
             );
+
+            if let MeshType::Asset(asset_id) = &mesh.mesh_type {
+                let asset = state.asset_registry.get(asset_id);
+                let skin = asset.as_ref().and_then(|asset| asset.skin.as_ref());
+                if let Some(skin) = skin {
+                    if skin_slot < MAX_SKINNED_MESHES_PER_FRAME && self.skins_on_gpu(mesh, skin) {
+                        let bones: Vec<[[f32; 4]; 4]> =
+                            playback_bones(skin, mesh.animation.as_ref())
+                                .iter()
+                                .map(|bone| bone.to_cols_array_2d())
+                                .collect();
+                        let bone_offset = skin_slot * BONE_SLOT_SIZE;
+                        self.queue.write_buffer(
+                            &self.bone_buffer,
+                            bone_offset as u64,
+                            bytemuck::cast_slice(&bones),
+                        );
+                        skin_offsets[mesh_idx] = Some(bone_offset as u32);
+                        skin_slot += 1;
+                    }
+                }
+            }
         }
 
         // Pre-write material global uniforms for all meshes BEFORE the render pass.
@@ -1406,7 +2681,7 @@ impl Renderer {
         self.material_global_uniforms.view_proj = self.uniforms.view_proj;
         self.material_global_uniforms.model = glam::Mat4::IDENTITY.to_cols_array_2d();
         self.material_global_uniforms.time = state.time;
-        self.material_global_uniforms.dt = 0.016;
+        self.material_global_uniforms.dt = state.dt;
         self.material_global_uniforms.lighting_enabled = lighting.enabled;
         self.material_global_uniforms.entity_emissive = 0.0;
         self.material_pipeline_manager.update_global_uniforms_at(
@@ -1426,7 +2701,7 @@ impl Renderer {
                 self.material_global_uniforms.view_proj = self.uniforms.view_proj;
                 self.material_global_uniforms.model = world_matrix.to_cols_array_2d();
                 self.material_global_uniforms.time = state.time;
-                self.material_global_uniforms.dt = 0.016; // ~60fps default
+                self.material_global_uniforms.dt = state.dt;
                 self.material_global_uniforms.lighting_enabled =
                     if mesh.lit { lighting.enabled } else { 0 };
                 self.material_global_uniforms.entity_emissive = mesh.emissive;
@@ -1439,6 +2714,8 @@ impl Renderer {
             }
         }
 
+        let background = self.prepare_background(state);
+
         // Render scene to post-processor's scene texture
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -1448,10 +2725,10 @@ impl Renderer {
                     resolve_target: None,
                     ops: wgpu::Operations {
                         load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.1,
-                            b: 0.1,
-                            a: 1.0,
+                            r: DEFAULT_CLEAR_COLOR[0] as f64,
+                            g: DEFAULT_CLEAR_COLOR[1] as f64,
+                            b: DEFAULT_CLEAR_COLOR[2] as f64,
+                            a: DEFAULT_CLEAR_COLOR[3] as f64,
                         }),
                         store: wgpu::StoreOp::Store,
                     },
@@ -1461,6 +2738,18 @@ impl Renderer {
                 occlusion_query_set: None,
             });
 
+            // === Render Background ===
+            // Drawn first so everything else composites over it
+            if let Some(skybox) = background {
+                let bind_group = match &self.skybox_bind_group {
+                    Some((_, bind_group)) if skybox => bind_group,
+                    _ => &self.background_bind_group,
+                };
+                render_pass.set_pipeline(&self.background_pipeline);
+                render_pass.set_bind_group(0, bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+
             // === Render Blob Shadows ===
             // Shadows are rendered before meshes so they appear under objects
             render_pass.set_pipeline(&self.shadow_pipeline);
@@ -1525,16 +2814,43 @@ impl Renderer {
                     MeshType::Asset(asset_id) => {
                         // Look up the loaded mesh asset
                         if let Some(asset) = state.asset_registry.get(asset_id) {
-                            // Apply deformations if any
-                            let deformed_vertices = if mesh.deformations.is_empty() {
+                            // Blend morph targets, skin on the CPU when the skinned
+                            // pipeline can't, then apply deformations, if any
+                            let skin_offset = skin_offsets[mesh_idx];
+                            let cpu_skin = asset.skin.as_ref().filter(|_| skin_offset.is_none());
+                            let deformed_vertices = if mesh.deformations.is_empty()
+                                && mesh.morph_weights.is_empty()
+                                && cpu_skin.is_none()
+                            {
                                 None
                             } else {
-                                Some(apply_deformations(&asset.vertices, &mesh.deformations))
+                                let morphed = (!mesh.morph_weights.is_empty())
+                                    .then(|| asset.morphed_vertices(&mesh.morph_weights));
+                                let vertices = morphed.as_deref().unwrap_or(&asset.vertices);
+                                let skinned = cpu_skin.map(|skin| {
+                                    let bones = playback_bones(skin, mesh.animation.as_ref());
+                                    skinning::skin_vertices(vertices, &skin.vertices, &bones)
+                                });
+                                let vertices = skinned.as_deref().unwrap_or(vertices);
+                                Some(apply_deformations(vertices, &mesh.deformations))
                             };
 
-                            // Ensure buffers exist for this asset
-                            let _buffers = self.get_or_create_loaded_mesh_buffers(&asset);
-                            let buffers = self.loaded_mesh_buffers.get(asset_id).unwrap();
+                            // The wireframe overlay draws deformed triangles unindexed
+                            let deformed_barycentric = match &deformed_vertices {
+                                Some(vertices)
+                                    if mesh.render_mode == RenderMode::WireframeOverlay =>
+                                {
+                                    Some(mesh::expand_barycentric(vertices, &asset.indices))
+                                }
+                                _ => None,
+                            };
+                            if let Some(ref vertices) = deformed_barycentric {
+                                self.write_deformed_barycentric(vertices);
+                            }
+
+                            // Ensure buffers exist for this asset
+                            let _buffers = self.get_or_create_loaded_mesh_buffers(&asset);
+                            let buffers = self.loaded_mesh_buffers.get(asset_id).unwrap();
 
                             // Determine which vertex buffer to use
                             let use_deformed = deformed_vertices.is_some();
@@ -1556,12 +2872,14 @@ impl Renderer {
                                         .filter(|id| self.material_registry.exists(id));
 
                                     if let Some(mat_id) = material_id {
-                                        // Get material topology
-                                        let material_topology = self
+                                        // Get material topology and blend mode
+                                        let (material_topology, blend_mode) = self
                                             .material_registry
                                             .get(mat_id)
-                                            .map(|m| m.topology)
+                                            .map(|m| (m.topology, m.blend_mode))
                                             .unwrap_or_default();
+                                        let blend_mode =
+                                            scene_blend.override_blend().unwrap_or(blend_mode);
 
                                         // Use material pipeline
                                         if let Some(resources) =
@@ -1587,7 +2905,8 @@ impl Renderer {
                                                 MaterialPipelineManager::dynamic_offset_for_slot(
                                                     mesh_idx,
                                                 );
-                                            render_pass.set_pipeline(&resources.pipeline);
+                                            render_pass
+                                                .set_pipeline(resources.pipeline_for(blend_mode));
                                             render_pass.set_bind_group(
                                                 0,
                                                 self.material_pipeline_manager.global_bind_group(),
@@ -1638,9 +2957,34 @@ impl Renderer {
                                                 render_pass.draw(0..buffers.num_vertices, 0..1);
                                             }
                                         }
+                                    } else if let (Some(bone_offset), Some(skin_buffer)) =
+                                        (skin_offset, &buffers.skin_vertex_buffer)
+                                    {
+                                        // Skin on the GPU with the pre-written bone matrices
+                                        render_pass
+                                            .set_pipeline(self.skinned_pipeline_for(scene_blend));
+                                        render_pass.set_bind_group(
+                                            0,
+                                            &self.mesh_bind_group,
+                                            &[dynamic_offset],
+                                        );
+                                        render_pass.set_bind_group(
+                                            1,
+                                            &self.bone_bind_group,
+                                            &[bone_offset],
+                                        );
+                                        render_pass
+                                            .set_vertex_buffer(0, buffers.vertex_buffer.slice(..));
+                                        render_pass.set_vertex_buffer(1, skin_buffer.slice(..));
+                                        render_pass.set_index_buffer(
+                                            buffers.index_buffer.slice(..),
+                                            wgpu::IndexFormat::Uint16,
+                                        );
+                                        render_pass.draw_indexed(0..buffers.num_indices, 0, 0..1);
                                     } else {
                                         // Fallback to legacy pipeline - use pre-written uniforms with dynamic offset
-                                        render_pass.set_pipeline(&self.mesh_pipeline);
+                                        render_pass
+                                            .set_pipeline(self.mesh_pipeline_for(scene_blend));
                                         render_pass.set_bind_group(
                                             0,
                                             &self.mesh_bind_group,
@@ -1665,7 +3009,8 @@ impl Renderer {
                                     }
                                 }
                                 RenderMode::Wireframe => {
-                                    render_pass.set_pipeline(&self.wireframe_pipeline);
+                                    render_pass
+                                        .set_pipeline(self.wireframe_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -1686,9 +3031,22 @@ impl Renderer {
                                     );
                                     render_pass.draw_indexed(0..buffers.num_edges, 0, 0..1);
                                 }
+                                RenderMode::WireframeOverlay => {
+                                    let vertex_buffer = match &self.deformed_barycentric_staging {
+                                        Some(staging) if deformed_barycentric.is_some() => staging,
+                                        _ => &buffers.barycentric_vertex_buffer,
+                                    };
+                                    self.draw_wireframe_overlay(
+                                        &mut render_pass,
+                                        scene_blend,
+                                        dynamic_offset,
+                                        vertex_buffer,
+                                        buffers.num_barycentric_vertices,
+                                    );
+                                }
                                 RenderMode::SolidWithWireframe => {
                                     // First pass: solid - use pre-written uniforms with dynamic offset
-                                    render_pass.set_pipeline(&self.mesh_pipeline);
+                                    render_pass.set_pipeline(self.mesh_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -1710,7 +3068,8 @@ impl Renderer {
                                     render_pass.draw_indexed(0..buffers.num_indices, 0, 0..1);
 
                                     // Second pass: wireframe overlay with its own color slot.
-                                    render_pass.set_pipeline(&self.wireframe_pipeline);
+                                    render_pass
+                                        .set_pipeline(self.wireframe_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -1766,11 +3125,13 @@ impl Renderer {
                                     .filter(|id| self.material_registry.exists(id));
 
                                 if let Some(mat_id) = material_id {
-                                    let material_topology = self
+                                    let (material_topology, blend_mode) = self
                                         .material_registry
                                         .get(mat_id)
-                                        .map(|m| m.topology)
+                                        .map(|m| (m.topology, m.blend_mode))
                                         .unwrap_or_default();
+                                    let blend_mode =
+                                        scene_blend.override_blend().unwrap_or(blend_mode);
 
                                     if let Some(resources) =
                                         self.material_pipeline_manager.get(mat_id)
@@ -1794,7 +3155,8 @@ impl Renderer {
                                             MaterialPipelineManager::dynamic_offset_for_slot(
                                                 mesh_idx,
                                             );
-                                        render_pass.set_pipeline(&resources.pipeline);
+                                        render_pass
+                                            .set_pipeline(resources.pipeline_for(blend_mode));
                                         render_pass.set_bind_group(
                                             0,
                                             self.material_pipeline_manager.global_bind_group(),
@@ -1825,7 +3187,7 @@ impl Renderer {
                                         }
                                     }
                                 } else {
-                                    render_pass.set_pipeline(&self.mesh_pipeline);
+                                    render_pass.set_pipeline(self.mesh_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -1844,7 +3206,8 @@ impl Renderer {
                                 let geometry = self.radial_ring_geometry.as_ref().unwrap();
                                 if let Some(ref wireframe_buffer) = geometry.wireframe_index_buffer
                                 {
-                                    render_pass.set_pipeline(&self.wireframe_pipeline);
+                                    render_pass
+                                        .set_pipeline(self.wireframe_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -1859,9 +3222,23 @@ impl Renderer {
                                     render_pass.draw_indexed(0..num_edges, 0, 0..1);
                                 }
                             }
+                            RenderMode::WireframeOverlay => {
+                                let geometry = self.radial_ring_geometry.as_ref().unwrap();
+                                if let Some(ref barycentric_buffer) =
+                                    geometry.barycentric_vertex_buffer
+                                {
+                                    self.draw_wireframe_overlay(
+                                        &mut render_pass,
+                                        scene_blend,
+                                        dynamic_offset,
+                                        barycentric_buffer,
+                                        geometry.num_barycentric_vertices,
+                                    );
+                                }
+                            }
                             RenderMode::SolidWithWireframe => {
                                 let geometry = self.radial_ring_geometry.as_ref().unwrap();
-                                render_pass.set_pipeline(&self.mesh_pipeline);
+                                render_pass.set_pipeline(self.mesh_pipeline_for(scene_blend));
                                 render_pass.set_bind_group(
                                     0,
                                     &self.mesh_bind_group,
@@ -1876,7 +3253,8 @@ impl Renderer {
 
                                 if let Some(ref wireframe_buffer) = geometry.wireframe_index_buffer
                                 {
-                                    render_pass.set_pipeline(&self.wireframe_pipeline);
+                                    render_pass
+                                        .set_pipeline(self.wireframe_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -1895,12 +3273,10 @@ impl Renderer {
                     }
                     _ => {
                         // Primitive mesh types (Cube, Plane, Sphere)
+                        self.ensure_primitive_geometry(&mesh.mesh_type);
                         // Get geometry reference based on type, avoiding borrow conflicts
-                        let geometry = match &mesh.mesh_type {
-                            MeshType::Cube => &self.cube_geometry,
-                            MeshType::Plane => &self.plane_geometry,
-                            MeshType::Sphere => &self.sphere_geometry,
-                            MeshType::Asset(_) | MeshType::RadialRing { .. } => continue, // Already handled above
+                        let Some(geometry) = self.primitive_geometry(&mesh.mesh_type) else {
+                            continue; // Assets and radial rings are handled above
                         };
 
                         let num_indices = geometry.num_indices;
@@ -1909,11 +3285,9 @@ impl Renderer {
 
                         match mesh.render_mode {
                             RenderMode::Solid => {
-                                let geometry = match &mesh.mesh_type {
-                                    MeshType::Cube => &self.cube_geometry,
-                                    MeshType::Plane => &self.plane_geometry,
-                                    MeshType::Sphere => &self.sphere_geometry,
-                                    _ => continue,
+                                let Some(geometry) = self.primitive_geometry(&mesh.mesh_type)
+                                else {
+                                    continue;
                                 };
 
                                 // Check if mesh has a material
@@ -1923,12 +3297,14 @@ impl Renderer {
                                     .filter(|id| self.material_registry.exists(id));
 
                                 if let Some(mat_id) = material_id {
-                                    // Get material topology
-                                    let material_topology = self
+                                    // Get material topology and blend mode
+                                    let (material_topology, blend_mode) = self
                                         .material_registry
                                         .get(mat_id)
-                                        .map(|m| m.topology)
+                                        .map(|m| (m.topology, m.blend_mode))
                                         .unwrap_or_default();
+                                    let blend_mode =
+                                        scene_blend.override_blend().unwrap_or(blend_mode);
 
                                     // Use material pipeline
                                     if let Some(resources) =
@@ -1953,7 +3329,8 @@ impl Renderer {
                                             MaterialPipelineManager::dynamic_offset_for_slot(
                                                 mesh_idx,
                                             );
-                                        render_pass.set_pipeline(&resources.pipeline);
+                                        render_pass
+                                            .set_pipeline(resources.pipeline_for(blend_mode));
                                         render_pass.set_bind_group(
                                             0,
                                             self.material_pipeline_manager.global_bind_group(),
@@ -1989,7 +3366,7 @@ impl Renderer {
                                     }
                                 } else {
                                     // Fallback to legacy pipeline - use pre-written uniforms with dynamic offset
-                                    render_pass.set_pipeline(&self.mesh_pipeline);
+                                    render_pass.set_pipeline(self.mesh_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -2005,15 +3382,14 @@ impl Renderer {
                                 }
                             }
                             RenderMode::Wireframe => {
-                                let geometry = match &mesh.mesh_type {
-                                    MeshType::Cube => &self.cube_geometry,
-                                    MeshType::Plane => &self.plane_geometry,
-                                    MeshType::Sphere => &self.sphere_geometry,
-                                    _ => continue,
+                                let Some(geometry) = self.primitive_geometry(&mesh.mesh_type)
+                                else {
+                                    continue;
                                 };
                                 if let Some(ref wireframe_buffer) = geometry.wireframe_index_buffer
                                 {
-                                    render_pass.set_pipeline(&self.wireframe_pipeline);
+                                    render_pass
+                                        .set_pipeline(self.wireframe_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -2028,15 +3404,30 @@ impl Renderer {
                                     render_pass.draw_indexed(0..num_edges, 0, 0..1);
                                 }
                             }
+                            RenderMode::WireframeOverlay => {
+                                let Some(geometry) = self.primitive_geometry(&mesh.mesh_type)
+                                else {
+                                    continue;
+                                };
+                                if let Some(ref barycentric_buffer) =
+                                    geometry.barycentric_vertex_buffer
+                                {
+                                    self.draw_wireframe_overlay(
+                                        &mut render_pass,
+                                        scene_blend,
+                                        dynamic_offset,
+                                        barycentric_buffer,
+                                        geometry.num_barycentric_vertices,
+                                    );
+                                }
+                            }
                             RenderMode::SolidWithWireframe => {
                                 // First pass: solid - use pre-written uniforms with dynamic offset
-                                let geometry = match &mesh.mesh_type {
-                                    MeshType::Cube => &self.cube_geometry,
-                                    MeshType::Plane => &self.plane_geometry,
-                                    MeshType::Sphere => &self.sphere_geometry,
-                                    _ => continue,
+                                let Some(geometry) = self.primitive_geometry(&mesh.mesh_type)
+                                else {
+                                    continue;
                                 };
-                                render_pass.set_pipeline(&self.mesh_pipeline);
+                                render_pass.set_pipeline(self.mesh_pipeline_for(scene_blend));
                                 render_pass.set_bind_group(
                                     0,
                                     &self.mesh_bind_group,
@@ -2050,15 +3441,14 @@ impl Renderer {
                                 render_pass.draw_indexed(0..num_indices, 0, 0..1);
 
                                 // Second pass: wireframe overlay with its own color slot.
-                                let geometry = match &mesh.mesh_type {
-                                    MeshType::Cube => &self.cube_geometry,
-                                    MeshType::Plane => &self.plane_geometry,
-                                    MeshType::Sphere => &self.sphere_geometry,
-                                    _ => continue,
+                                let Some(geometry) = self.primitive_geometry(&mesh.mesh_type)
+                                else {
+                                    continue;
                                 };
                                 if let Some(ref wireframe_buffer) = geometry.wireframe_index_buffer
                                 {
-                                    render_pass.set_pipeline(&self.wireframe_pipeline);
+                                    render_pass
+                                        .set_pipeline(self.wireframe_pipeline_for(scene_blend));
                                     render_pass.set_bind_group(
                                         0,
                                         &self.mesh_bind_group,
@@ -2101,8 +3491,8 @@ impl Renderer {
                     // Get local bounds based on mesh type
                     let local_bounds = match &mesh.mesh_type {
                         MeshType::Cube => CUBE_BOUNDS,
-                        MeshType::Plane => PLANE_BOUNDS,
-                        MeshType::Sphere => SPHERE_BOUNDS,
+                        MeshType::Plane { .. } => PLANE_BOUNDS,
+                        MeshType::Sphere { .. } => SPHERE_BOUNDS,
                         MeshType::Asset(asset_id) => state
                             .asset_registry
                             .get(asset_id)
@@ -2131,8 +3521,10 @@ impl Renderer {
                         bytemuck::cast_slice(&vertices),
                     );
 
-                    // Set identity model matrix (vertices are already in world space)
+                    // Set identity model matrix (vertices are already in world space);
+                    // debug overlays aren't fogged
                     self.uniforms.model = glam::Mat4::IDENTITY.to_cols_array_2d();
+                    self.uniforms.fog_enabled = 0;
                     self.uniforms.instance_color =
                         [debug_color[0], debug_color[1], debug_color[2], 1.0];
                     self.queue.write_buffer(
@@ -2152,7 +3544,7 @@ impl Renderer {
             }
 
             // Render line strips
-            render_pass.set_pipeline(&self.line_pipeline);
+            render_pass.set_pipeline(self.line_pipeline_for(scene_blend));
 
             for (idx, line) in &lines_to_render {
                 // Upload line points
@@ -2186,8 +3578,163 @@ impl Renderer {
                 render_pass.draw(0..line.count as u32, 0..1);
             }
 
+            // Render dbg.plot() sparklines, each in its own screen region.
+            // Debug overlays always alpha blend, regardless of scene.blendMode.
+            render_pass.set_pipeline(&self.line_pipeline);
+            for (slot, (plot, resources)) in
+                plots_to_render.iter().zip(&self.plot_resources).enumerate()
+            {
+                let points_data = plot.sparkline.to_gpu_data();
+                if points_data.len() < 2 {
+                    continue;
+                }
+                self.queue.write_buffer(
+                    &resources.vertex_buffer,
+                    0,
+                    bytemuck::cast_slice(&points_data),
+                );
+                let (offset, scale) = DebugPlot::screen_region(slot);
+                let plot_uniforms = LineUniforms {
+                    color: [0.3, 0.9, 1.0, 1.0],
+                    offset,
+                    scale,
+                    count: (points_data.len() - 1) as f32,
+                    max_points: DEBUG_PLOT_POINTS as f32,
+                    _padding: [0.0; 2],
+                };
+                self.queue.write_buffer(
+                    &resources.uniform_buffer,
+                    0,
+                    bytemuck::cast_slice(&[plot_uniforms]),
+                );
+
+                render_pass.set_bind_group(0, &resources.bind_group, &[]);
+                render_pass.set_vertex_buffer(0, resources.vertex_buffer.slice(..));
+                render_pass.draw(0..points_data.len() as u32, 0..1);
+            }
+
+            // Label each plot with its name, just above its region
+            render_pass.set_pipeline(&self.polyline_pipeline);
+            let viewport = [self.size.width as f32, self.size.height as f32];
+            for (slot, (plot, resources)) in plots_to_render
+                .iter()
+                .zip(&self.plot_label_resources)
+                .enumerate()
+            {
+                let mut segments_data = plot.label_segments(slot, viewport);
+                segments_data.truncate(MAX_POLYLINE_SEGMENTS);
+                if segments_data.is_empty() {
+                    continue;
+                }
+                self.queue.write_buffer(
+                    &resources.segment_buffer,
+                    0,
+                    bytemuck::cast_slice(&segments_data),
+                );
+                let label_uniforms = PointCloudUniforms {
+                    view_proj: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    model: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    color: [0.3, 0.9, 1.0, 1.0],
+                    point_size: 1.5,
+                    _padding: 0.0,
+                    viewport_size: viewport,
+                };
+                self.queue.write_buffer(
+                    &resources.uniform_buffer,
+                    0,
+                    bytemuck::cast_slice(&[label_uniforms]),
+                );
+
+                render_pass.set_bind_group(0, &resources.bind_group, &[]);
+                render_pass.set_vertex_buffer(0, resources.segment_buffer.slice(..));
+                render_pass.draw(0..6, 0..segments_data.len() as u32);
+            }
+
+            // Render the waveform overlay along the bottom, with its playhead
+            if let (Some((audio, _)), Some(waveform)) =
+                (waveform_to_render, self.waveform_resources.as_ref())
+            {
+                if waveform.point_count >= 2 {
+                    let (offset, scale) = WAVEFORM_REGION;
+                    let envelope_uniforms = LineUniforms {
+                        color: [0.3, 0.9, 1.0, 0.6],
+                        offset,
+                        scale,
+                        count: (waveform.point_count - 1) as f32,
+                        max_points: (MAX_WAVEFORM_COLUMNS * 2) as f32,
+                        _padding: [0.0; 2],
+                    };
+                    self.queue.write_buffer(
+                        &waveform.envelope.uniform_buffer,
+                        0,
+                        bytemuck::cast_slice(&[envelope_uniforms]),
+                    );
+                    render_pass.set_bind_group(0, &waveform.envelope.bind_group, &[]);
+                    render_pass.set_vertex_buffer(0, waveform.envelope.vertex_buffer.slice(..));
+                    render_pass.draw(0..waveform.point_count as u32, 0..1);
+
+                    // A zero-width strip at the playhead x draws a vertical line
+                    let playhead = playhead_fraction(state.time, audio.get_duration());
+                    let playhead_uniforms = LineUniforms {
+                        color: [1.0, 1.0, 1.0, 1.0],
+                        offset: [offset[0] + playhead * scale[0], offset[1]],
+                        scale: [0.0, scale[1]],
+                        count: 1.0,
+                        max_points: 2.0,
+                        _padding: [0.0; 2],
+                    };
+                    self.queue.write_buffer(
+                        &waveform.playhead.uniform_buffer,
+                        0,
+                        bytemuck::cast_slice(&[playhead_uniforms]),
+                    );
+                    render_pass.set_bind_group(0, &waveform.playhead.bind_group, &[]);
+                    render_pass.set_vertex_buffer(0, waveform.playhead.vertex_buffer.slice(..));
+                    render_pass.draw(0..2, 0..1);
+                }
+            }
+
+            // Render the spectrogram overlay, scrolled so the newest column is on the right
+            if let (Some(spectrogram), Some(resources)) =
+                (spectrogram_to_render, self.spectrogram_resources.as_ref())
+            {
+                let (offset, scale) = spectrogram.options.ndc_region();
+                let spectrogram_uniforms = SpectrogramUniforms {
+                    offset,
+                    scale,
+                    scroll: spectrogram.cursor() as f32 / SPECTROGRAM_COLUMNS as f32,
+                    _padding: [0.0; 3],
+                };
+                self.queue.write_buffer(
+                    &resources.uniform_buffer,
+                    0,
+                    bytemuck::cast_slice(&[spectrogram_uniforms]),
+                );
+                render_pass.set_pipeline(&self.spectrogram_pipeline);
+                render_pass.set_bind_group(0, &resources.bind_group, &[]);
+                render_pass.draw(0..6, 0..1);
+            }
+
+            // Render the reference grid over the whole frame
+            if let Some(grid) = state.reference_grid() {
+                let grid_uniforms = GridUniforms {
+                    color: grid.color,
+                    viewport: [self.size.width as f32, self.size.height as f32],
+                    spacing: grid.spacing,
+                    checker: if grid.checker { 1.0 } else { 0.0 },
+                };
+                self.queue.write_buffer(
+                    &self.reference_grid_uniform_buffer,
+                    0,
+                    bytemuck::cast_slice(&[grid_uniforms]),
+                );
+                render_pass.set_pipeline(&self.reference_grid_pipeline);
+                render_pass.set_bind_group(0, &self.reference_grid_bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+
             // Render point clouds
-            render_pass.set_pipeline(&self.point_cloud_pipeline);
+            render_pass.set_pipeline(self.point_cloud_pipeline_for(scene_blend));
 
             for (entity_id, cloud, world_matrix) in &point_clouds_to_render {
                 // Upload point positions (as flat f32 array: x, y, z, x, y, z, ...)
@@ -2232,7 +3779,7 @@ impl Renderer {
             }
 
             // Render radial waves as connected, screen-space thick polylines.
-            render_pass.set_pipeline(&self.polyline_pipeline);
+            render_pass.set_pipeline(self.polyline_pipeline_for(scene_blend));
             for (entity_id, wave, world_matrix) in &radial_waves_to_render {
                 let points = wave.generate_points();
                 let segments_data = build_polyline_segments(&points);
@@ -2269,9 +3816,65 @@ impl Renderer {
                 render_pass.draw(0..6, 0..segment_count as u32);
             }
 
+            // Render debug markers (dbg.showEvents) as screen-space outlines.
+            render_pass.set_pipeline(&self.polyline_pipeline);
+            for (marker, resources) in markers_to_render.iter().zip(&self.marker_resources) {
+                let mut segments_data = marker.segments();
+                segments_data.truncate(MAX_POLYLINE_SEGMENTS);
+                self.queue.write_buffer(
+                    &resources.segment_buffer,
+                    0,
+                    bytemuck::cast_slice(&segments_data),
+                );
+                let marker_uniforms = PointCloudUniforms {
+                    view_proj: self.uniforms.view_proj,
+                    model: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    color: marker.color,
+                    point_size: 2.5,
+                    _padding: 0.0,
+                    viewport_size: [self.size.width as f32, self.size.height as f32],
+                };
+                self.queue.write_buffer(
+                    &resources.uniform_buffer,
+                    0,
+                    bytemuck::cast_slice(&[marker_uniforms]),
+                );
+
+                render_pass.set_bind_group(0, &resources.bind_group, &[]);
+                render_pass.set_vertex_buffer(0, resources.segment_buffer.slice(..));
+                render_pass.draw(0..6, 0..segments_data.len() as u32);
+            }
+
+            // Render axis gizmos (dbg.showAxes) as world-space segments. The
+            // scene pass has no depth buffer yet, so they draw over the scene.
+            for (segment, resources) in axis_segments.iter().zip(&self.axis_resources) {
+                self.queue.write_buffer(
+                    &resources.segment_buffer,
+                    0,
+                    bytemuck::cast_slice(&[segment.to_gpu_data()]),
+                );
+                let axis_uniforms = PointCloudUniforms {
+                    view_proj: self.uniforms.view_proj,
+                    model: glam::Mat4::IDENTITY.to_cols_array_2d(),
+                    color: segment.color,
+                    point_size: 2.5,
+                    _padding: 0.0,
+                    viewport_size: [self.size.width as f32, self.size.height as f32],
+                };
+                self.queue.write_buffer(
+                    &resources.uniform_buffer,
+                    0,
+                    bytemuck::cast_slice(&[axis_uniforms]),
+                );
+
+                render_pass.set_bind_group(0, &resources.bind_group, &[]);
+                render_pass.set_vertex_buffer(0, resources.segment_buffer.slice(..));
+                render_pass.draw(0..6, 0..1);
+            }
+
             // Render ribbon center points as sized sprites until full ribbon
             // extrusion is handled by its dedicated strip/tube renderer.
-            render_pass.set_pipeline(&self.point_cloud_pipeline);
+            render_pass.set_pipeline(self.point_cloud_pipeline_for(scene_blend));
             for (entity_id, ribbon, world_matrix) in &ribbons_to_render {
                 // Generate center line points for the ribbon
                 let points = ribbon.generate_center_points();
@@ -2328,6 +3931,10 @@ impl Renderer {
         let feedback_uniforms = state.feedback_uniforms();
         let post_chain = state.post_chain();
         let evaluated_params = post_chain.build_params_map(&self.post_effect_registry);
+        self.post_processor
+            .set_tonemap(&self.queue, state.scene_tonemap(), state.exposure());
+        self.post_processor
+            .set_solo_effect(state.debug_options.solo_effect.clone());
         self.post_processor.process_all(
             &self.device,
             &mut encoder,
@@ -2339,6 +3946,50 @@ impl Renderer {
             &evaluated_params,
         );
 
+        // === Render UI ===
+        // Pixel-space UI draws over the tonemapped frame, so effects don't touch it
+        let ui_vertices: Vec<[f32; UI_VERTEX_FLOATS]> = scene_graph
+            .ui_rects()
+            .filter(|(entity_id, _)| scene_graph.is_effectively_visible(*entity_id))
+            .take(MAX_UI_RECTS)
+            .flat_map(|(_, rect)| ui::rect_vertices(rect))
+            .collect();
+        if !ui_vertices.is_empty() {
+            let ui_uniforms = UiUniforms {
+                projection: ui::pixel_projection(self.size.width as f32, self.size.height as f32)
+                    .to_cols_array_2d(),
+            };
+            self.queue.write_buffer(
+                &self.ui_uniform_buffer,
+                0,
+                bytemuck::cast_slice(&[ui_uniforms]),
+            );
+            self.queue.write_buffer(
+                &self.ui_vertex_buffer,
+                0,
+                bytemuck::cast_slice(&ui_vertices),
+            );
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("UI Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+            render_pass.set_pipeline(&self.ui_pipeline);
+            render_pass.set_bind_group(0, &self.ui_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.ui_vertex_buffer.slice(..));
+            render_pass.draw(0..ui_vertices.len() as u32, 0..1);
+        }
+
         self.queue.submit(iter::once(encoder.finish()));
     }
 
@@ -2348,7 +3999,7 @@ impl Renderer {
     /// Each particle is rendered as an instance of the specified mesh asset.
     ///
     /// # Arguments
-    /// * `view` - The texture view to render to
+    /// * `view` - The texture view to render to (in `HDR_FORMAT`)
     /// * `instances` - GPU-ready particle instance data
     /// * `asset` - The mesh asset to instance
     /// * `state` - Visualiser state for view projection
@@ -2440,7 +4091,8 @@ impl Renderer {
     ) {
         use crate::particle::ParticleGeometry;
         use crate::particle_eval::{
-            generate_gpu_instances, generate_mesh_particle_instances, ParticleEvalContext,
+            allocate_particle_budget, generate_gpu_instances, generate_mesh_particle_instances,
+            sort_groups_back_to_front, ParticleEvalContext,
         };
 
         let particle_systems = state.particle_systems();
@@ -2455,17 +4107,36 @@ impl Renderer {
             current_time_secs: state.time,
             current_beat: state.time / secs_per_beat,
             secs_per_beat,
-            dt: 0.016, // Approximate frame time
-            dt_beats: 0.016 / secs_per_beat,
+            dt: state.dt,
+            dt_beats: state.dt / secs_per_beat,
         };
 
-        // Collect all particle instances by type
-        let mut mesh_instances_by_asset: std::collections::HashMap<
-            String,
-            Vec<GpuMeshParticleInstance>,
-        > = std::collections::HashMap::new();
-        let mut billboard_instances: Vec<GpuParticleInstance> = Vec::new();
+        // Generate instances per system so the global budget can be shared across them
+        enum SystemInstances {
+            Billboard(ParticleBlend, Option<String>, Vec<GpuParticleInstance>),
+            Mesh(String, ParticleBlend, Vec<GpuMeshParticleInstance>),
+        }
+        impl SystemInstances {
+            fn len(&self) -> usize {
+                match self {
+                    SystemInstances::Billboard(_, _, instances) => instances.len(),
+                    SystemInstances::Mesh(_, _, instances) => instances.len(),
+                }
+            }
+            /// Keep the newest `n` instances (instances are stored oldest first).
+            fn keep_newest(&mut self, n: usize) {
+                match self {
+                    SystemInstances::Billboard(_, _, instances) => {
+                        instances.drain(..instances.len().saturating_sub(n));
+                    }
+                    SystemInstances::Mesh(_, _, instances) => {
+                        instances.drain(..instances.len().saturating_sub(n));
+                    }
+                }
+            }
+        }
 
+        let mut per_system: Vec<SystemInstances> = Vec::new();
         let mut ordered_systems: Vec<_> = particle_systems.iter().collect();
         ordered_systems.sort_by_key(|(id, _)| **id);
         for (_id, system) in ordered_systems {
@@ -2473,11 +4144,13 @@ impl Renderer {
                 continue;
             }
 
+            let blend = system.config.blend;
             match &system.geometry {
                 ParticleGeometry::Billboard { .. } | ParticleGeometry::Point { .. } => {
                     // Collect billboard/point particle instances
                     let instances = generate_gpu_instances(system, &particle_ctx);
-                    billboard_instances.extend(instances);
+                    let sprite = system.config.sprite.as_ref().map(|s| s.asset_id.clone());
+                    per_system.push(SystemInstances::Billboard(blend, sprite, instances));
                 }
                 ParticleGeometry::Mesh {
                     asset_id,
@@ -2487,24 +4160,100 @@ impl Renderer {
                     let instances =
                         generate_mesh_particle_instances(system, &particle_ctx, *base_scale);
                     if !instances.is_empty() {
-                        mesh_instances_by_asset
-                            .entry(asset_id.clone())
-                            .or_default()
-                            .extend(instances);
+                        per_system.push(SystemInstances::Mesh(asset_id.clone(), blend, instances));
                     }
                 }
             }
         }
 
-        // Render billboard particles
-        if !billboard_instances.is_empty() {
-            let instance_count = billboard_instances.len().min(MAX_MESH_PARTICLE_INSTANCES);
-            let instances = &billboard_instances[..instance_count];
+        // Scale every system down proportionally when the total exceeds the budget
+        if let Some(budget) = state.particle_budget() {
+            let requested: Vec<usize> = per_system.iter().map(SystemInstances::len).collect();
+            let allocation = allocate_particle_budget(&requested, budget);
+            for (instances, allowed) in per_system.iter_mut().zip(allocation) {
+                instances.keep_newest(allowed);
+            }
+        }
 
-            // Update billboard uniforms (view_proj + camera vectors)
-            let camera = state.camera_uniforms();
-            self.uniforms.update_view_proj(self.size, camera);
+        // Collect all particle instances by type and blend mode
+        let mut mesh_instances_by_asset: std::collections::BTreeMap<
+            (String, ParticleBlend),
+            Vec<GpuMeshParticleInstance>,
+        > = std::collections::BTreeMap::new();
+        let mut billboard_instances_by_blend: std::collections::BTreeMap<
+            (ParticleBlend, Option<String>),
+            Vec<GpuParticleInstance>,
+        > = std::collections::BTreeMap::new();
+        for instances in per_system {
+            match instances {
+                SystemInstances::Billboard(blend, sprite, instances) => {
+                    billboard_instances_by_blend
+                        .entry((blend, sprite))
+                        .or_default()
+                        .extend(instances)
+                }
+                SystemInstances::Mesh(asset_id, blend, instances) => mesh_instances_by_asset
+                    .entry((asset_id, blend))
+                    .or_default()
+                    .extend(instances),
+            }
+        }
+
+        let camera = state.camera_uniforms();
+        self.uniforms.update_view_proj(self.size, camera);
 
+        // Render billboard particles: alpha first, then additive on top, each split by
+        // sprite texture. All groups share one instance buffer upload and draw their own
+        // range of it. Sprites that aren't loaded fall back to soft circles.
+        let mut alpha_groups: Vec<(Option<String>, Vec<GpuParticleInstance>)> = Vec::new();
+        let mut additive_groups: Vec<(Option<String>, Vec<GpuParticleInstance>)> = Vec::new();
+        let mut room = MAX_MESH_PARTICLE_INSTANCES;
+        for ((blend, sprite), mut instances) in billboard_instances_by_blend {
+            let sprite = sprite.filter(|id| match state.sprite_registry.get(id) {
+                Some(asset) => {
+                    self.ensure_sprite_bind_group(&asset);
+                    true
+                }
+                None => false,
+            });
+            instances.truncate(room);
+            if instances.is_empty() {
+                continue;
+            }
+            room -= instances.len();
+            if blend.needs_sorting() {
+                alpha_groups.push((sprite, instances));
+            } else {
+                additive_groups.push((sprite, instances));
+            }
+        }
+
+        // Alpha-blended particles must be drawn far-to-near to composite correctly, across
+        // sprite textures too, so they are sorted as one layer and drawn in sprite runs.
+        let (mut billboard_instances, alpha_runs) =
+            sort_groups_back_to_front(alpha_groups, camera.view_matrix());
+        let mut billboard_draws: Vec<(ParticleBlend, Option<String>, std::ops::Range<u32>)> =
+            alpha_runs
+                .into_iter()
+                .map(|(sprite, run)| {
+                    (
+                        ParticleBlend::Alpha,
+                        sprite,
+                        run.start as u32..run.end as u32,
+                    )
+                })
+                .collect();
+        for (sprite, instances) in additive_groups {
+            let first = billboard_instances.len() as u32;
+            billboard_instances.extend(instances);
+            billboard_draws.push((
+                ParticleBlend::Add,
+                sprite,
+                first..billboard_instances.len() as u32,
+            ));
+        }
+
+        if !billboard_instances.is_empty() {
             // Compute camera vectors from dynamic camera uniforms
             let right = camera.right();
             let cam_up = camera.camera_up();
@@ -2512,7 +4261,8 @@ impl Renderer {
             let camera_right = [right.x, right.y, right.z, 0.0];
             let camera_up = [cam_up.x, cam_up.y, cam_up.z, 0.0];
 
-            // Build billboard uniform data: view_proj (16) + camera_right (4) + camera_up (4)
+            // Build billboard uniform data:
+            // view_proj (16) + camera_right (4) + camera_up (4) + depth_params (4)
             // Flatten the 4x4 matrix to [f32; 16]
             let vp = &self.uniforms.view_proj;
             let view_proj_flat: [f32; 16] = [
@@ -2520,10 +4270,12 @@ impl Renderer {
                 vp[2][0], vp[2][1], vp[2][2], vp[2][3], vp[3][0], vp[3][1], vp[3][2], vp[3][3],
             ];
 
-            let mut billboard_uniforms: [f32; 24] = [0.0; 24];
+            let mut billboard_uniforms = [0.0f32; pipeline::BILLBOARD_PARTICLE_UNIFORM_FLOATS];
             billboard_uniforms[0..16].copy_from_slice(&view_proj_flat);
             billboard_uniforms[16..20].copy_from_slice(&camera_right);
             billboard_uniforms[20..24].copy_from_slice(&camera_up);
+            let (near, far) = camera.clip_planes();
+            billboard_uniforms[24..28].copy_from_slice(&[near, far, 0.0, 0.0]);
 
             self.queue.write_buffer(
                 &self.billboard_particle_uniform_buffer,
@@ -2535,7 +4287,7 @@ impl Renderer {
             self.queue.write_buffer(
                 &self.billboard_particle_instance_buffer,
                 0,
-                bytemuck::cast_slice(instances),
+                bytemuck::cast_slice(&billboard_instances),
             );
 
             // Create render pass for billboard particles
@@ -2555,7 +4307,6 @@ impl Renderer {
                     occlusion_query_set: None,
                 });
 
-                render_pass.set_pipeline(&self.billboard_particle_pipeline);
                 render_pass.set_bind_group(0, &self.billboard_particle_bind_group, &[]);
                 render_pass.set_vertex_buffer(0, self.billboard_quad_vertex_buffer.slice(..));
                 render_pass.set_vertex_buffer(1, self.billboard_particle_instance_buffer.slice(..));
@@ -2563,20 +4314,32 @@ impl Renderer {
                     self.billboard_quad_index_buffer.slice(..),
                     wgpu::IndexFormat::Uint16,
                 );
-                render_pass.draw_indexed(0..6, 0, 0..instance_count as u32);
+                for (blend, sprite, range) in billboard_draws {
+                    let sprite_bind_group = sprite
+                        .as_ref()
+                        .and_then(|id| self.sprite_bind_groups.get(id))
+                        .map(|(_, bind_group)| bind_group);
+                    match sprite_bind_group {
+                        Some(bind_group) => {
+                            render_pass.set_pipeline(self.sprite_particle_pipeline_for(blend));
+                            render_pass.set_bind_group(1, bind_group, &[]);
+                        }
+                        None => {
+                            render_pass.set_pipeline(self.billboard_particle_pipeline_for(blend));
+                        }
+                    }
+                    render_pass.draw_indexed(0..6, 0, range);
+                }
             }
         }
 
-        // Render mesh particles
-        let mut ordered_mesh_assets: Vec<_> = mesh_instances_by_asset.iter().collect();
-        ordered_mesh_assets.sort_by(|(left, _), (right, _)| left.cmp(right));
-        for (asset_id, instances) in ordered_mesh_assets {
-            if instances.is_empty() {
-                continue;
-            }
-
+        // Render mesh particles. All groups are uploaded into one instance buffer before
+        // drawing, since queued buffer writes land before any pass in this encoder runs.
+        let mut mesh_instances: Vec<GpuMeshParticleInstance> = Vec::new();
+        let mut mesh_draws: Vec<(String, ParticleBlend, std::ops::Range<u32>)> = Vec::new();
+        for ((asset_id, blend), mut instances) in mesh_instances_by_asset {
             // Look up the mesh asset
-            let asset = match state.asset_registry.get(asset_id) {
+            let asset = match state.asset_registry.get(&asset_id) {
                 Some(a) => a,
                 None => {
                     log::warn!("Particle mesh asset not found: {}", asset_id);
@@ -2585,57 +4348,67 @@ impl Renderer {
             };
 
             // Clamp to max instances
-            let instance_count = instances.len().min(MAX_MESH_PARTICLE_INSTANCES);
-            let instances = &instances[..instance_count];
+            instances.truncate(MAX_MESH_PARTICLE_INSTANCES - mesh_instances.len());
+            if instances.is_empty() {
+                continue;
+            }
 
             // Ensure mesh buffers exist
             let _ = self.get_or_create_loaded_mesh_buffers(&asset);
-            let buffers = match self.loaded_mesh_buffers.get(asset_id) {
-                Some(b) => b,
-                None => continue,
-            };
+            if !self.loaded_mesh_buffers.contains_key(&asset_id) {
+                continue;
+            }
 
-            // Update view projection matrix
-            self.uniforms
-                .update_view_proj(self.size, state.camera_uniforms());
-            self.queue.write_buffer(
-                &self.mesh_particle_view_buffer,
-                0,
-                bytemuck::cast_slice(&self.uniforms.view_proj),
-            );
+            let first = mesh_instances.len() as u32;
+            mesh_instances.extend(instances);
+            mesh_draws.push((asset_id, blend, first..mesh_instances.len() as u32));
+        }
 
-            // Upload instance data
-            self.queue.write_buffer(
-                &self.mesh_particle_instance_buffer,
-                0,
-                bytemuck::cast_slice(instances),
-            );
+        if mesh_draws.is_empty() {
+            return;
+        }
 
-            // Create render pass to scene view
-            {
-                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                    label: Some("Mesh Particle Render Pass"),
-                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: self.post_processor.scene_view(),
-                        resolve_target: None,
-                        ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Load,
-                            store: wgpu::StoreOp::Store,
-                        },
-                    })],
-                    depth_stencil_attachment: None,
-                    timestamp_writes: None,
-                    occlusion_query_set: None,
-                });
+        // Update view projection matrix
+        self.queue.write_buffer(
+            &self.mesh_particle_view_buffer,
+            0,
+            bytemuck::cast_slice(&self.uniforms.view_proj),
+        );
 
-                render_pass.set_pipeline(&self.mesh_particle_pipeline);
-                render_pass.set_bind_group(0, &self.mesh_particle_bind_group, &[]);
-                render_pass.set_vertex_buffer(0, buffers.vertex_buffer.slice(..));
-                render_pass.set_vertex_buffer(1, self.mesh_particle_instance_buffer.slice(..));
-                render_pass
-                    .set_index_buffer(buffers.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
-                render_pass.draw_indexed(0..buffers.num_indices, 0, 0..instance_count as u32);
-            }
+        // Upload instance data
+        self.queue.write_buffer(
+            &self.mesh_particle_instance_buffer,
+            0,
+            bytemuck::cast_slice(&mesh_instances),
+        );
+
+        // Create render pass to scene view
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Mesh Particle Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: self.post_processor.scene_view(),
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_bind_group(0, &self.mesh_particle_bind_group, &[]);
+        render_pass.set_vertex_buffer(1, self.mesh_particle_instance_buffer.slice(..));
+        for (asset_id, blend, range) in mesh_draws {
+            let buffers = match self.loaded_mesh_buffers.get(&asset_id) {
+                Some(b) => b,
+                None => continue,
+            };
+            render_pass.set_pipeline(self.mesh_particle_pipeline_for(blend));
+            render_pass.set_vertex_buffer(0, buffers.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(buffers.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+            render_pass.draw_indexed(0..buffers.num_indices, 0, range);
         }
     }
 }
@@ -2644,6 +4417,7 @@ impl Renderer {
 mod tests {
     use super::*;
     use crate::scene_graph::{RadialWave, Vec3};
+    use crate::test_support::headless_or_skip;
 
     #[test]
     fn polyline_segments_connect_adjacent_points() {
@@ -2678,4 +4452,138 @@ mod tests {
     fn point_uniform_layout_matches_wgsl_alignment() {
         assert_eq!(std::mem::size_of::<PointCloudUniforms>(), 160);
     }
+
+    #[test]
+    fn test_higher_layer_mesh_draws_on_top_regardless_of_depth() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // A bright cube at the origin in front of a dim, bigger cube further
+        // along the default camera's line of sight; both cover the centre.
+        let mut render_with_far_layer = |layer: i32| {
+            let script = format!(
+                r#"
+                let far = mesh.cube();
+                far.lit = false;
+                far.color = #{{ r: 0.2, g: 0.2, b: 0.2, a: 1.0 }};
+                far.position = #{{ x: -4.0, y: -2.0, z: -4.0 }};
+                far.scale = 2.0;
+                far.layer = {layer};
+
+                let near = mesh.cube();
+                near.lit = false;
+
+                fn init(ctx) {{
+                    scene.add(near);
+                    scene.add(far);
+                }}
+
+                fn update(dt, frame) {{}}
+                "#,
+            );
+            headless.render_script(&script);
+            headless.read_scene_hdr()[32 * 64 + 32][0]
+        };
+
+        // Within a layer, the nearer mesh draws last (despite being added first)
+        let same_layer = render_with_far_layer(0);
+        assert!(
+            same_layer > 0.5,
+            "near cube should cover the centre, got {}",
+            same_layer
+        );
+
+        // A higher layer draws after, even though it's further away
+        let far_on_top = render_with_far_layer(1);
+        assert!(
+            far_on_top < 0.5,
+            "far cube should draw over the near one, got {}",
+            far_on_top
+        );
+    }
+
+    #[test]
+    fn test_point_clouds_sort_by_layer_then_depth() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // A red single-point cloud at the origin, added before a green one
+        // further along the camera's line of sight; both cover the centre.
+        let mut render_with_far_layer = |layer: i32| {
+            let script = format!(
+                r#"
+                let near = points.cloud(#{{ count: 1, spread: 0.0, point_size: 16.0 }});
+                near.color = #{{ r: 1.0, g: 0.0, b: 0.0, a: 1.0 }};
+
+                let far = points.cloud(#{{ count: 1, spread: 0.0, point_size: 16.0 }});
+                far.color = #{{ r: 0.0, g: 1.0, b: 0.0, a: 1.0 }};
+                far.position = #{{ x: -4.0, y: -2.0, z: -4.0 }};
+                far.layer = {layer};
+
+                fn init(ctx) {{
+                    scene.add(near);
+                    scene.add(far);
+                }}
+
+                fn update(dt, frame) {{}}
+                "#,
+            );
+            headless.render_script(&script);
+            headless.read_scene_hdr()[32 * 64 + 32]
+        };
+
+        // Within a layer, the nearer cloud draws last (despite being added first)
+        let same_layer = render_with_far_layer(0);
+        assert!(same_layer[0] > same_layer[1], "got {:?}", same_layer);
+
+        // A higher layer draws after, even though it's further away
+        let far_on_top = render_with_far_layer(1);
+        assert!(far_on_top[1] > far_on_top[0], "got {:?}", far_on_top);
+    }
+
+    #[test]
+    fn test_wireframe_overlay_shades_edges_over_fill() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // Face-on to the cube's front face, which is split by a diagonal
+        // through its centre into two triangles.
+        let script = r#"
+            let cube = mesh.cube();
+            cube.renderMode = "wireframeOverlay";
+            cube.wireframeWidth = 2.0;
+
+            fn init(ctx) {
+                camera.position = #{ x: 0.0, y: 0.0, z: 3.0 };
+                camera.lookAt(#{ x: 0.0, y: 0.0, z: 0.0 });
+                scene.add(cube);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        headless.render_script(script);
+        let hdr = headless.read_scene_hdr();
+
+        // The fill is red (the front face's vertex colour), edges white
+        let row: Vec<[f32; 4]> = (0..64).map(|x| hdr[32 * 64 + x]).collect();
+        let is_edge = |p: [f32; 4]| p[0] > 0.5 && p[1] > 0.5 && p[2] > 0.5;
+        let is_fill = |p: [f32; 4]| p[0] > 0.5 && p[1] < 0.3 && p[2] < 0.3;
+        let covered: Vec<usize> = (0..64).filter(|&x| row[x][0] > 0.5).collect();
+        let (left, right) = (covered[0], *covered.last().unwrap());
+        assert!(right - left > 16, "face should span the row: {:?}", covered);
+
+        // Edge pixels at the face's sides and on the diagonal at the centre
+        assert!(is_edge(row[left]), "left side {:?}", row[left]);
+        assert!(is_edge(row[right]), "right side {:?}", row[right]);
+        assert!(is_edge(row[32]), "diagonal {:?}", row[32]);
+
+        // Fill between them
+        let between = (left + 32) / 2;
+        assert!(is_fill(row[between]), "fill {:?}", row[between]);
+        let between = (32 + right) / 2;
+        assert!(is_fill(row[between]), "fill {:?}", row[between]);
+    }
 }
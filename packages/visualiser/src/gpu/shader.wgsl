@@ -2,6 +2,13 @@ struct Uniforms {
     view_proj: mat4x4<f32>,
     model: mat4x4<f32>,
     instance_color: vec4<f32>,
+    camera_position: vec4<f32>,
+    // Distance fog
+    fog_color: vec4<f32>,
+    fog_start: f32,
+    fog_end: f32,
+    fog_enabled: u32,
+    _fog_padding: u32,
 }
 
 struct SparklineUniforms {
@@ -19,26 +26,38 @@ var<uniform> spark_uniforms: SparklineUniforms;
 
 struct VertexInput {
     @location(0) position: vec3<f32>,
-    @location(1) color: vec3<f32>,
+    @location(2) color: vec3<f32>,
 }
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec3<f32>,
+    @location(1) world_pos: vec3<f32>,
 }
 
 @vertex
 fn vs_main(model: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     out.color = model.color;
-    out.clip_position = uniforms.view_proj * uniforms.model * vec4<f32>(model.position, 1.0);
+    let world_pos = uniforms.model * vec4<f32>(model.position, 1.0);
+    out.clip_position = uniforms.view_proj * world_pos;
+    out.world_pos = world_pos.xyz;
     return out;
 }
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Multiply vertex color by instance color (tint)
-    return vec4<f32>(in.color, 1.0) * uniforms.instance_color;
+    let color = vec4<f32>(in.color, 1.0) * uniforms.instance_color;
+
+    // Blend towards the fog colour with distance from the camera
+    if uniforms.fog_enabled == 0u {
+        return color;
+    }
+    let distance = length(in.world_pos - uniforms.camera_position.xyz);
+    let range = max(uniforms.fog_end - uniforms.fog_start, 1e-4);
+    let amount = clamp((distance - uniforms.fog_start) / range, 0.0, 1.0);
+    return vec4<f32>(mix(color.rgb, uniforms.fog_color.rgb, amount), color.a);
 }
 
 // Sparkline Shader
@@ -0,0 +1,52 @@
+struct BackgroundUniforms {
+    inv_view_proj: mat4x4<f32>, // Clip space to world space, for skybox directions
+    top: vec4<f32>,             // Gradient colour at the top edge
+    bottom: vec4<f32>,          // Gradient colour at the bottom edge
+    mode: u32,                  // 0 = gradient, 1 = skybox
+    _padding0: u32,
+    _padding1: u32,
+    _padding2: u32,
+}
+
+@group(0) @binding(0)
+var<uniform> background: BackgroundUniforms;
+@group(0) @binding(1)
+var skybox_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var skybox_sampler: sampler;
+
+const PI: f32 = 3.14159265;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+}
+
+// Full-screen triangle generated from the vertex index
+@vertex
+fn vs_background(@builtin(vertex_index) in_vertex_index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((in_vertex_index << 1u) & 2u), f32(in_vertex_index & 2u));
+    var out: VertexOutput;
+    out.ndc = uv * 2.0 - 1.0;
+    out.position = vec4<f32>(out.ndc, 0.0, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_background(in: VertexOutput) -> @location(0) vec4<f32> {
+    if background.mode == 1u {
+        // View direction through this pixel, from the near to the far plane
+        let near = background.inv_view_proj * vec4<f32>(in.ndc, 0.0, 1.0);
+        let far = background.inv_view_proj * vec4<f32>(in.ndc, 1.0, 1.0);
+        let dir = normalize(far.xyz / far.w - near.xyz / near.w);
+        // Equirectangular lookup; an explicit LOD avoids a seam where u wraps
+        let u = atan2(dir.z, dir.x) / (2.0 * PI) + 0.5;
+        let v = acos(clamp(dir.y, -1.0, 1.0)) / PI;
+        let color = textureSampleLevel(skybox_texture, skybox_sampler, vec2<f32>(u, v), 0.0);
+        return vec4<f32>(color.rgb, 1.0);
+    }
+
+    // ndc.y is 1 at the top edge and -1 at the bottom
+    let t = clamp(in.ndc.y * 0.5 + 0.5, 0.0, 1.0);
+    return mix(background.bottom, background.top, t);
+}
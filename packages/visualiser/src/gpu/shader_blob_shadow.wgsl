@@ -18,6 +18,12 @@ struct GlobalUniforms {
     entity_emissive: f32,
     _light_padding: vec2<u32>,
     camera_position: vec4<f32>,
+    // Distance fog
+    fog_color: vec4<f32>,
+    fog_start: f32,
+    fog_end: f32,
+    fog_enabled: u32,
+    _fog_padding: u32,
 }
 
 struct ShadowUniforms {
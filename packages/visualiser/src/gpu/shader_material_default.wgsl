@@ -18,6 +18,12 @@ struct GlobalUniforms {
     entity_emissive: f32,
     _light_padding: vec2<u32>,
     camera_position: vec4<f32>,
+    // Distance fog
+    fog_color: vec4<f32>,
+    fog_start: f32,
+    fog_end: f32,
+    fog_enabled: u32,
+    _fog_padding: u32,
 }
 
 struct MaterialUniforms {
@@ -43,6 +49,17 @@ struct VertexOutput {
     @location(2) world_normal: vec3<f32>,
 }
 
+// Blend towards the fog colour with distance from the camera
+fn apply_fog(color: vec3<f32>, world_pos: vec3<f32>) -> vec3<f32> {
+    if globals.fog_enabled == 0u {
+        return color;
+    }
+    let distance = length(world_pos - globals.camera_position.xyz);
+    let range = max(globals.fog_end - globals.fog_start, 1e-4);
+    let amount = clamp((distance - globals.fog_start) / range, 0.0, 1.0);
+    return mix(color, globals.fog_color.rgb, amount);
+}
+
 @vertex
 fn vs_main(input: VertexInput) -> VertexOutput {
     var out: VertexOutput;
@@ -98,5 +115,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let emissive = final_color.rgb * globals.entity_emissive;
     final_color = vec4<f32>(final_color.rgb + emissive, final_color.a);
 
-    return final_color;
+    return vec4<f32>(apply_fog(final_color.rgb, in.world_pos), final_color.a);
 }
@@ -18,6 +18,12 @@ struct GlobalUniforms {
     entity_emissive: f32,
     _light_padding: vec2<u32>,
     camera_position: vec4<f32>,
+    // Distance fog
+    fog_color: vec4<f32>,
+    fog_start: f32,
+    fog_end: f32,
+    fog_enabled: u32,
+    _fog_padding: u32,
 }
 
 struct MaterialUniforms {
@@ -41,6 +47,18 @@ struct VertexInput {
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec3<f32>,
+    @location(1) world_pos: vec3<f32>,
+}
+
+// Blend towards the fog colour with distance from the camera
+fn apply_fog(color: vec3<f32>, world_pos: vec3<f32>) -> vec3<f32> {
+    if globals.fog_enabled == 0u {
+        return color;
+    }
+    let distance = length(world_pos - globals.camera_position.xyz);
+    let range = max(globals.fog_end - globals.fog_start, 1e-4);
+    let amount = clamp((distance - globals.fog_start) / range, 0.0, 1.0);
+    return mix(color, globals.fog_color.rgb, amount);
 }
 
 @vertex
@@ -48,6 +66,7 @@ fn vs_main(input: VertexInput) -> VertexOutput {
     var out: VertexOutput;
     let world_pos = globals.model * vec4<f32>(input.position, 1.0);
     out.clip_position = globals.view_proj * world_pos;
+    out.world_pos = world_pos.xyz;
     out.color = input.color;
     return out;
 }
@@ -56,5 +75,6 @@ fn vs_main(input: VertexInput) -> VertexOutput {
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Points are always unlit
     let intensity = clamp(material.point_size, 0.1, 10.0);
-    return material.point_color * vec4<f32>(in.color * intensity, 1.0);
+    let color = material.point_color * vec4<f32>(in.color * intensity, 1.0);
+    return vec4<f32>(apply_fog(color.rgb, in.world_pos), color.a);
 }
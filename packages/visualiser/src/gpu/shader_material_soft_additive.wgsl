@@ -18,6 +18,12 @@ struct GlobalUniforms {
     entity_emissive: f32,
     _light_padding: vec2<u32>,
     camera_position: vec4<f32>,
+    // Distance fog
+    fog_color: vec4<f32>,
+    fog_start: f32,
+    fog_end: f32,
+    fog_enabled: u32,
+    _fog_padding: u32,
 }
 
 struct MaterialUniforms {
@@ -44,6 +50,17 @@ struct VertexOutput {
     @location(1) world_pos: vec3<f32>,
 }
 
+// Blend towards the fog colour with distance from the camera
+fn apply_fog(color: vec3<f32>, world_pos: vec3<f32>) -> vec3<f32> {
+    if globals.fog_enabled == 0u {
+        return color;
+    }
+    let distance = length(world_pos - globals.camera_position.xyz);
+    let range = max(globals.fog_end - globals.fog_start, 1e-4);
+    let amount = clamp((distance - globals.fog_start) / range, 0.0, 1.0);
+    return mix(color, globals.fog_color.rgb, amount);
+}
+
 @vertex
 fn vs_main(input: VertexInput) -> VertexOutput {
     var out: VertexOutput;
@@ -59,5 +76,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Soft additive is always unlit
     let base = vec4<f32>(in.color, 1.0) * material.base_color;
     let soft_alpha = base.a * (1.0 - material.softness * 0.5);
-    return vec4<f32>(base.rgb, soft_alpha);
+    return vec4<f32>(apply_fog(base.rgb, in.world_pos), soft_alpha);
 }
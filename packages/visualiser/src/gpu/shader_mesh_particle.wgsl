@@ -11,7 +11,7 @@ var<uniform> view: ViewUniforms;
 // Vertex attributes from the mesh (per-vertex)
 struct MeshVertex {
     @location(0) position: vec3<f32>,
-    @location(1) color: vec3<f32>,
+    @location(2) color: vec3<f32>,
 }
 
 // Instance attributes (per-instance)
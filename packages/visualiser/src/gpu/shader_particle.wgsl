@@ -1,15 +1,29 @@
 // Particle system shader with billboarding support.
-// Renders camera-facing quads with per-instance position, scale, and color.
+// Renders camera-facing or velocity-stretched quads with per-instance position, scale,
+// color, and spin, either as soft circles or textured with a sprite (sheet).
 
 struct ParticleUniforms {
     view_proj: mat4x4<f32>,
     camera_right: vec4<f32>,
     camera_up: vec4<f32>,
+    // x = near, y = far (zw unused)
+    depth_params: vec4<f32>,
 }
 
 @group(0) @binding(0)
 var<uniform> uniforms: ParticleUniforms;
 
+// Scene depth for soft particles. Until the scene pass writes a depth buffer this is a
+// 1x1 texture cleared to the far plane, so the fade is a no-op.
+@group(0) @binding(1)
+var scene_depth: texture_2d<f32>;
+
+// Sprite texture, only bound for the textured pipeline (fs_particle_sprite)
+@group(1) @binding(0)
+var sprite_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var sprite_sampler: sampler;
+
 struct VertexInput {
     // Per-vertex: quad corner position (-0.5 to 0.5)
     @location(0) local_position: vec2<f32>,
@@ -20,12 +34,46 @@ struct InstanceInput {
     @location(1) world_position: vec3<f32>,
     @location(2) scale: f32,
     @location(3) color: vec4<f32>,
+    @location(4) rotation: f32,
+    @location(5) velocity: vec3<f32>,
+    @location(6) stretch: f32,
+    @location(7) soft_fade: f32,
+    // Sprite sheet lookup: x = frame, y = cols, z = rows
+    @location(8) sprite: vec4<f32>,
 }
 
+// Below this on-screen speed stretched billboards stay camera-facing.
+// Must match MIN_STRETCH_SPEED in particle_eval.rs.
+const MIN_STRETCH_SPEED: f32 = 1e-4;
+
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) uv: vec2<f32>,
+    // View-space distance of the particle, for soft-particle fading
+    @location(2) view_depth: f32,
+    @location(3) soft_fade: f32,
+    @location(4) @interpolate(flat) sprite: vec4<f32>,
+}
+
+// Alpha multiplier that fades particles out as they approach geometry behind them.
+// Must match soft_particle_fade in particle_eval.rs.
+fn soft_particle_fade(depth: f32, particle_depth: f32, soft_fade: f32) -> f32 {
+    if soft_fade <= 0.0 {
+        return 1.0;
+    }
+    let near = uniforms.depth_params.x;
+    let far = uniforms.depth_params.y;
+    let scene_linear = near * far / (far - depth * (far - near));
+    return clamp((scene_linear - particle_depth) / soft_fade, 0.0, 1.0);
+}
+
+// Soft-particle fade for a fragment, sampling the scene depth under it.
+fn scene_depth_fade(in: VertexOutput) -> f32 {
+    let depth_size = vec2<i32>(textureDimensions(scene_depth));
+    let pixel = clamp(vec2<i32>(in.clip_position.xy), vec2<i32>(0), depth_size - 1);
+    let depth = textureLoad(scene_depth, pixel, 0).r;
+    return soft_particle_fade(depth, in.view_depth, in.soft_fade);
 }
 
 @vertex
@@ -33,15 +81,41 @@ fn vs_particle(
     vertex: VertexInput,
     instance: InstanceInput,
 ) -> VertexOutput {
+    let camera_right = uniforms.camera_right.xyz;
+    let camera_up = uniforms.camera_up.xyz;
+
+    // Spin the quad in its own plane by default
+    let c = cos(instance.rotation);
+    let s = sin(instance.rotation);
+    var axis_x = camera_right * c + camera_up * s;
+    var axis_y = camera_right * -s + camera_up * c;
+    var extent_x = 1.0;
+
+    // Stretched billboards align X with the on-screen velocity instead
+    let screen_velocity = vec2<f32>(
+        dot(instance.velocity, camera_right),
+        dot(instance.velocity, camera_up),
+    );
+    let speed = length(screen_velocity);
+    if instance.stretch > 0.0 && speed > MIN_STRETCH_SPEED {
+        let dir = screen_velocity / speed;
+        axis_x = camera_right * dir.x + camera_up * dir.y;
+        axis_y = camera_right * -dir.y + camera_up * dir.x;
+        extent_x = 1.0 + instance.stretch * speed;
+    }
+
     // Billboard: expand quad in camera space
-    let right = uniforms.camera_right.xyz * vertex.local_position.x * instance.scale;
-    let up = uniforms.camera_up.xyz * vertex.local_position.y * instance.scale;
+    let right = axis_x * vertex.local_position.x * instance.scale * extent_x;
+    let up = axis_y * vertex.local_position.y * instance.scale;
     let world_pos = instance.world_position + right + up;
 
     var out: VertexOutput;
     out.clip_position = uniforms.view_proj * vec4<f32>(world_pos, 1.0);
     out.color = instance.color;
     out.uv = vertex.local_position + 0.5; // Convert to 0-1 range
+    out.view_depth = out.clip_position.w;
+    out.soft_fade = instance.soft_fade;
+    out.sprite = instance.sprite;
     return out;
 }
 
@@ -52,7 +126,10 @@ fn fs_particle(in: VertexOutput) -> @location(0) vec4<f32> {
     let dist = distance(in.uv, center) * 2.0; // Distance from center (0 to 1)
 
     // Soft circle falloff
-    let alpha = 1.0 - smoothstep(0.8, 1.0, dist);
+    var alpha = 1.0 - smoothstep(0.8, 1.0, dist);
+
+    // Fade where the particle meets scene geometry
+    alpha *= scene_depth_fade(in);
 
     // Discard fully transparent pixels
     if alpha < 0.01 {
@@ -62,6 +139,24 @@ fn fs_particle(in: VertexOutput) -> @location(0) vec4<f32> {
     return vec4<f32>(in.color.rgb, in.color.a * alpha);
 }
 
+@fragment
+fn fs_particle_sprite(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Pick the sheet cell for this frame (left-to-right, top-to-bottom)
+    let grid = max(in.sprite.yz, vec2<f32>(1.0));
+    let frame = in.sprite.x;
+    let cell = vec2<f32>(frame % grid.x, floor(frame / grid.x));
+    // Quad UVs have +Y up; texture rows run top-down
+    let local_uv = vec2<f32>(in.uv.x, 1.0 - in.uv.y);
+    let texel = textureSample(sprite_texture, sprite_sampler, (cell + local_uv) / grid);
+
+    let alpha = in.color.a * texel.a * scene_depth_fade(in);
+    if alpha < 0.01 {
+        discard;
+    }
+
+    return vec4<f32>(in.color.rgb * texel.rgb, alpha);
+}
+
 // Simple point rendering (no billboarding)
 @vertex
 fn vs_particle_point(
@@ -72,6 +167,9 @@ fn vs_particle_point(
     out.clip_position = uniforms.view_proj * vec4<f32>(instance.world_position, 1.0);
     out.color = instance.color;
     out.uv = vec2<f32>(0.5, 0.5);
+    out.view_depth = out.clip_position.w;
+    out.soft_fade = 0.0;
+    out.sprite = instance.sprite;
     return out;
 }
 
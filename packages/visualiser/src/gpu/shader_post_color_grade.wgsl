@@ -16,12 +16,51 @@ struct ColorGradeUniforms {
     saturation: f32,
     gamma: f32,
     tint: vec4<f32>,
+    exposure: f32,
+    temperature: f32,
+    tint_shift: f32,
 }
 
 @group(0) @binding(0) var input_texture: texture_2d<f32>;
 @group(0) @binding(1) var input_sampler: sampler;
 @group(1) @binding(0) var<uniform> grade: ColorGradeUniforms;
 
+// Linear sRGB <-> LMS (CAT02), rows of the matrices
+const LIN_TO_LMS_0 = vec3<f32>(0.390405, 0.549941, 0.00892632);
+const LIN_TO_LMS_1 = vec3<f32>(0.0708416, 0.963172, 0.00135775);
+const LIN_TO_LMS_2 = vec3<f32>(0.0231082, 0.128021, 0.936245);
+const LMS_TO_LIN_0 = vec3<f32>(2.85847, -1.62879, -0.024891);
+const LMS_TO_LIN_1 = vec3<f32>(-0.210182, 1.1582, 0.000324281);
+const LMS_TO_LIN_2 = vec3<f32>(-0.041812, -0.118169, 1.06867);
+
+// Per-channel LMS gains adapting the white point given by temperature/tint
+// (both -1..1) to D65.
+fn white_balance_gains(temperature: f32, tint: f32) -> vec3<f32> {
+    let t1 = temperature * 10.0 / 6.0;
+    let t2 = tint * 10.0 / 6.0;
+
+    // CIE xy of the source white: move along the daylight locus, then off it for tint
+    let x = 0.31271 - t1 * select(0.1, 0.05, t1 < 0.0);
+    let daylight_y = 2.87 * x - 3.0 * x * x - 0.27509507;
+    let y = daylight_y + t2 * 0.05;
+
+    // xy (Y = 1) -> XYZ -> LMS
+    let X = x / y;
+    let Z = (1.0 - x - y) / y;
+    let source = vec3<f32>(
+        0.7328 * X + 0.4296 - 0.1624 * Z,
+        -0.7036 * X + 1.6975 + 0.0061 * Z,
+        0.0030 * X + 0.0136 + 0.9834 * Z,
+    );
+    let d65 = vec3<f32>(0.949237, 1.03542, 1.08728);
+    return d65 / source;
+}
+
+fn white_balance(rgb: vec3<f32>, gains: vec3<f32>) -> vec3<f32> {
+    let lms = vec3<f32>(dot(LIN_TO_LMS_0, rgb), dot(LIN_TO_LMS_1, rgb), dot(LIN_TO_LMS_2, rgb)) * gains;
+    return vec3<f32>(dot(LMS_TO_LIN_0, lms), dot(LMS_TO_LIN_1, lms), dot(LMS_TO_LIN_2, lms));
+}
+
 @vertex
 fn vs_main(input: VertexInput) -> VertexOutput {
     var out: VertexOutput;
@@ -34,6 +73,15 @@ fn vs_main(input: VertexInput) -> VertexOutput {
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     var color = textureSample(input_texture, input_sampler, in.uv);
 
+    // Exposure (stops), applied in linear light before everything else
+    color = vec4<f32>(color.rgb * exp2(grade.exposure), color.a);
+
+    // White balance
+    if grade.temperature != 0.0 || grade.tint_shift != 0.0 {
+        let gains = white_balance_gains(grade.temperature, grade.tint_shift);
+        color = vec4<f32>(white_balance(color.rgb, gains), color.a);
+    }
+
     // Brightness
     color = vec4<f32>(color.rgb + grade.brightness, color.a);
 
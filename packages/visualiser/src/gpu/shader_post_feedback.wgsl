@@ -2,7 +2,7 @@
 // Provides Milkdrop-style temporal visual memory with spatial warping,
 // colour transforms, and blend modes.
 //
-// Pipeline: previous_frame -> warp₁ -> warp₂ -> ... -> color₁ -> color₂ -> ... -> blend(current) -> output
+// Pipeline: previous_frame -> warp₁ -> warp₂ -> ... -> offset -> color₁ -> color₂ -> ... -> blend(current) -> output
 
 struct VertexInput {
     @location(0) position: vec2<f32>,
@@ -14,7 +14,7 @@ struct VertexOutput {
     @location(0) uv: vec2<f32>,
 }
 
-// GPU warp step (32 bytes = 2 × 16-byte blocks)
+// GPU warp step (48 bytes = 3 × 16-byte blocks)
 struct GpuWarpStep {
     // Block 1
     warp_type: u32,
@@ -26,9 +26,14 @@ struct GpuWarpStep {
     translate_y: f32,
     frequency: f32,
     falloff: f32,
+    // Block 3
+    speed: f32,
+    seed: u32,
+    _pad0: f32,
+    _pad1: f32,
 }
 
-// GPU color step (32 bytes = 2 × 16-byte blocks)
+// GPU color step (48 bytes = 3 × 16-byte blocks)
 struct GpuColorStep {
     // Block 1
     color_type: u32,
@@ -40,21 +45,36 @@ struct GpuColorStep {
     hsv_v: f32,
     offset_x: f32,
     offset_y: f32,
+    // Block 3
+    hue_rotation: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
 }
 
 // Main uniforms structure
-// Header (16 bytes) + 4 warp steps (128 bytes) + 4 color steps (128 bytes) = 272 bytes
+// Header (32 bytes) + mask (32 bytes) + 4 warp steps (192 bytes) + 4 color steps (192 bytes) = 448 bytes
 struct FeedbackUniforms {
-    // Header (16 bytes)
+    // Header (32 bytes)
     warp_count: u32,
     color_count: u32,
     blend_mode: u32,
     opacity: f32,
-
-    // Warp steps array (4 × 32 = 128 bytes)
+    offset: vec2<f32>,   // Per-frame echo translation in UV units
+    time: f32,           // Seconds, for time-animated warps
+    _padding: f32,
+
+    // Mask (32 bytes)
+    mask_shape: u32,     // 0 = full frame, 1 = circle, 2 = rect
+    mask_softness: f32,
+    mask_center: vec2<f32>,
+    mask_size: vec2<f32>, // Circle: (radius, unused); rect: (width, height)
+    _mask_padding: vec2<f32>,
+
+    // Warp steps array (4 × 48 = 192 bytes)
     warp_steps: array<GpuWarpStep, 4>,
 
-    // Color steps array (4 × 32 = 128 bytes)
+    // Color steps array (4 × 48 = 192 bytes)
     color_steps: array<GpuColorStep, 4>,
 }
 
@@ -155,6 +175,16 @@ fn hsv_to_rgb(hsv: vec3<f32>) -> vec3<f32> {
     return vec3<f32>(v, p, q);
 }
 
+// Rotate hue by `angle` radians: Rodrigues rotation about the grey (1,1,1) axis,
+// which keeps luminance-neutral greys fixed and cycles red -> green -> blue
+fn hue_rotate(rgb: vec3<f32>, angle: f32) -> vec3<f32> {
+    let k = vec3<f32>(0.57735027);
+    let c = cos(angle);
+    let s = sin(angle);
+    let rotated = rgb * c + cross(k, rgb) * s + k * dot(k, rgb) * (1.0 - c);
+    return max(rotated, vec3<f32>(0.0));
+}
+
 // ============================================================================
 // Spatial warp operators (single step)
 // ============================================================================
@@ -216,9 +246,10 @@ fn apply_warp_step(uv: vec2<f32>, step: GpuWarpStep) -> vec2<f32> {
 
             return center + vec2<f32>(cos(new_angle), sin(new_angle)) * new_dist;
         }
-        // Noise displacement
+        // Noise displacement: a seeded value-noise field drifting with time
         case 4u: {
-            let n = noise2d(uv * step.frequency, 0u);
+            let drift = vec2<f32>(1.0, 0.7) * params.time * step.speed;
+            let n = noise2d(uv * step.frequency + drift, step.seed);
             return uv + n * effective_strength * 0.1;
         }
         // Shear
@@ -277,6 +308,14 @@ fn apply_color_step(color: vec4<f32>, step: GpuColorStep) -> vec4<f32> {
         case 4u: {
             return color;
         }
+        // Invert
+        case 5u: {
+            return vec4<f32>(max(1.0 - color.rgb, vec3<f32>(0.0)), color.a);
+        }
+        // Hue rotate
+        case 6u: {
+            return vec4<f32>(hue_rotate(color.rgb, step.hue_rotation), color.a);
+        }
         default: {
             return color;
         }
@@ -326,6 +365,36 @@ fn apply_channel_offset(warped_uv: vec2<f32>) -> vec4<f32> {
     return vec4<f32>(r, g, b, a);
 }
 
+// ============================================================================
+// Mask
+// ============================================================================
+
+// How much feedback applies at uv: 1 inside the region, 0 outside,
+// with a soft edge of width mask_softness just inside the boundary
+fn mask_weight(uv: vec2<f32>) -> f32 {
+    let d = uv - params.mask_center;
+    var dist: f32;
+
+    switch params.mask_shape {
+        // Circle (radius in units of frame height, so it stays round)
+        case 1u: {
+            let dims = vec2<f32>(textureDimensions(current_texture));
+            dist = length(vec2<f32>(d.x * dims.x / dims.y, d.y)) - params.mask_size.x;
+        }
+        // Rect
+        case 2u: {
+            let q = abs(d) - params.mask_size * 0.5;
+            dist = max(q.x, q.y);
+        }
+        default: {
+            return 1.0;
+        }
+    }
+
+    let softness = max(params.mask_softness, 0.00001);
+    return 1.0 - smoothstep(-softness, 0.0, dist);
+}
+
 // ============================================================================
 // Blend modes
 // ============================================================================
@@ -394,8 +463,9 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // Sample current frame
     let current = textureSample(current_texture, tex_sampler, in.uv);
 
-    // Apply all spatial warps in sequence to get feedback UV
-    let warped_uv = apply_warp_chain(in.uv);
+    // Apply all spatial warps in sequence, then the echo offset, to get feedback UV.
+    // Sampling at uv - offset moves the previous frame's content by +offset.
+    let warped_uv = apply_warp_chain(in.uv) - params.offset;
 
     // Clamp to valid UV range to prevent edge artifacts
     let clamped_uv = clamp(warped_uv, vec2<f32>(0.001), vec2<f32>(0.999));
@@ -418,6 +488,6 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         feedback = apply_color_chain(feedback);
     }
 
-    // Blend feedback with current frame
-    return apply_blend(current, feedback);
+    // Blend feedback with current frame, leaving it untouched outside the mask
+    return mix(current, apply_blend(current, feedback), mask_weight(in.uv));
 }
@@ -0,0 +1,52 @@
+// Tonemap shader - maps the HDR scene to the display range.
+// Applies exposure, then either the ACES filmic curve (Narkowicz fit) or a plain clip.
+
+struct VertexInput {
+    @location(0) position: vec2<f32>,
+    @location(1) uv: vec2<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+struct TonemapParams {
+    exposure: f32,
+    // 1.0 for ACES, 0.0 to clip
+    aces: f32,
+    _padding1: f32,
+    _padding2: f32,
+}
+
+@group(0) @binding(0) var input_texture: texture_2d<f32>;
+@group(0) @binding(1) var input_sampler: sampler;
+
+@group(1) @binding(0) var<uniform> params: TonemapParams;
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.position = vec4<f32>(input.position, 0.0, 1.0);
+    out.uv = input.uv;
+    return out;
+}
+
+fn aces(x: vec3<f32>) -> vec3<f32> {
+    let a = 2.51;
+    let b = 0.03;
+    let c = 2.43;
+    let d = 0.59;
+    let e = 0.14;
+    return clamp((x * (a * x + b)) / (x * (c * x + d) + e), vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(input_texture, input_sampler, in.uv);
+    let exposed = max(color.rgb, vec3<f32>(0.0)) * params.exposure;
+    if params.aces > 0.5 {
+        return vec4<f32>(aces(exposed), color.a);
+    }
+    return vec4<f32>(min(exposed, vec3<f32>(1.0)), color.a);
+}
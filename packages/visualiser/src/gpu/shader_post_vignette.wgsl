@@ -10,11 +10,15 @@ struct VertexOutput {
     @location(0) uv: vec2<f32>,
 }
 
+// Field order matches the effect's registered params (packed back to back).
 struct VignetteUniforms {
     intensity: f32,
     smoothness: f32,
-    _padding: vec2<f32>,
+    roundness: f32,
+    aspect: f32,
     color: vec4<f32>,
+    inner_radius: f32,
+    outer_radius: f32,
 }
 
 @group(0) @binding(0) var input_texture: texture_2d<f32>;
@@ -33,14 +37,17 @@ fn vs_main(input: VertexInput) -> VertexOutput {
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let color = textureSample(input_texture, input_sampler, in.uv);
 
-    // Calculate distance from center
-    let center = vec2<f32>(0.5, 0.5);
-    let dist = distance(in.uv, center);
+    // Offset from center, squeezed horizontally by the aspect so an aspect
+    // above 1 gives a wider ellipse
+    let offset = abs(in.uv - vec2<f32>(0.5, 0.5)) / vec2<f32>(max(vignette.aspect, 0.01), 1.0);
+
+    // Superellipse distance: roundness 1 is an ellipse, 0 a rounded rectangle
+    let p = mix(8.0, 2.0, clamp(vignette.roundness, 0.0, 1.0));
+    let dist = pow(pow(offset.x, p) + pow(offset.y, p), 1.0 / p);
 
     // Calculate vignette factor
-    let outer_radius = 0.7;
-    let inner_radius = outer_radius - vignette.smoothness * 0.5;
-    let vignette_factor = 1.0 - smoothstep(inner_radius, outer_radius, dist) * vignette.intensity;
+    let vignette_factor = 1.0
+        - smoothstep(vignette.inner_radius, vignette.outer_radius, dist) * vignette.intensity;
 
     // Mix original color with vignette color
     let final_color = mix(vignette.color.rgb, color.rgb, vignette_factor);
@@ -0,0 +1,41 @@
+struct GridUniforms {
+    color: vec4<f32>,
+    viewport: vec2<f32>, // Target size in pixels
+    spacing: f32,        // Cell size as a fraction of the viewport height
+    checker: f32,        // 1.0 fills alternate cells instead of drawing lines
+}
+
+@group(0) @binding(0)
+var<uniform> grid_uniforms: GridUniforms;
+
+// Full-screen triangle generated from the vertex index
+@vertex
+fn vs_grid(@builtin(vertex_index) in_vertex_index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((in_vertex_index << 1u) & 2u), f32(in_vertex_index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_grid(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    // Cells are measured from the screen centre, so the middle lines mark it
+    let p = frag_coord.xy - grid_uniforms.viewport * 0.5;
+    let cell = max(grid_uniforms.spacing * grid_uniforms.viewport.y, 2.0);
+
+    if grid_uniforms.checker > 0.5 {
+        let index = floor(p / cell);
+        let odd = abs(index.x + index.y) % 2.0;
+        return vec4<f32>(grid_uniforms.color.rgb, grid_uniforms.color.a * odd);
+    }
+
+    // One-pixel lines at cell boundaries; the centre axes at full alpha
+    let to_line = abs(p - round(p / cell) * cell);
+    let on_line = min(to_line.x, to_line.y) < 0.5;
+    let on_axis = min(abs(p.x), abs(p.y)) < 1.0;
+    var alpha = 0.0;
+    if on_axis {
+        alpha = min(grid_uniforms.color.a * 2.0, 1.0);
+    } else if on_line {
+        alpha = grid_uniforms.color.a;
+    }
+    return vec4<f32>(grid_uniforms.color.rgb, alpha);
+}
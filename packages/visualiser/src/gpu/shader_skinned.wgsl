@@ -0,0 +1,80 @@
+// Skinned mesh shader: blends up to four bone matrices per vertex before
+// the usual model/view/projection transform. Shading matches shader.wgsl.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    model: mat4x4<f32>,
+    instance_color: vec4<f32>,
+    camera_position: vec4<f32>,
+    // Distance fog
+    fog_color: vec4<f32>,
+    fog_start: f32,
+    fog_end: f32,
+    fog_enabled: u32,
+    _fog_padding: u32,
+}
+
+// Must match MAX_JOINTS in skinning.rs
+struct Bones {
+    matrices: array<mat4x4<f32>, 128>,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@group(1) @binding(0)
+var<uniform> bones: Bones;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(2) color: vec3<f32>,
+    @location(3) joints: vec4<u32>,
+    @location(4) weights: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+    @location(1) world_pos: vec3<f32>,
+}
+
+@vertex
+fn vs_main(model: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.color = model.color;
+
+    // Vertices without joint weights are not attached to the skeleton
+    var skin = mat4x4<f32>(
+        vec4<f32>(1.0, 0.0, 0.0, 0.0),
+        vec4<f32>(0.0, 1.0, 0.0, 0.0),
+        vec4<f32>(0.0, 0.0, 1.0, 0.0),
+        vec4<f32>(0.0, 0.0, 0.0, 1.0),
+    );
+    let total = model.weights.x + model.weights.y + model.weights.z + model.weights.w;
+    if total > 0.0 {
+        skin = bones.matrices[model.joints.x] * model.weights.x
+            + bones.matrices[model.joints.y] * model.weights.y
+            + bones.matrices[model.joints.z] * model.weights.z
+            + bones.matrices[model.joints.w] * model.weights.w;
+    }
+
+    let world_pos = uniforms.model * skin * vec4<f32>(model.position, 1.0);
+    out.clip_position = uniforms.view_proj * world_pos;
+    out.world_pos = world_pos.xyz;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Multiply vertex color by instance color (tint)
+    let color = vec4<f32>(in.color, 1.0) * uniforms.instance_color;
+
+    // Blend towards the fog colour with distance from the camera
+    if uniforms.fog_enabled == 0u {
+        return color;
+    }
+    let distance = length(in.world_pos - uniforms.camera_position.xyz);
+    let range = max(uniforms.fog_end - uniforms.fog_start, 1e-4);
+    let amount = clamp((distance - uniforms.fog_start) / range, 0.0, 1.0);
+    return vec4<f32>(mix(color.rgb, uniforms.fog_color.rgb, amount), color.a);
+}
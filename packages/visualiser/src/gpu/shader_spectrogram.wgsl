@@ -0,0 +1,47 @@
+struct SpectrogramUniforms {
+    offset: vec2<f32>, // NDC of the bottom-left corner
+    scale: vec2<f32>,  // NDC size
+    scroll: f32,       // Texture u of the oldest column
+    _padding0: f32,
+    _padding1: f32,
+    _padding2: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> spectrogram_uniforms: SpectrogramUniforms;
+@group(0) @binding(1)
+var spectrogram_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var spectrogram_sampler: sampler;
+
+struct SpectrogramOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+// Two triangles covering the region, generated from the vertex index
+@vertex
+fn vs_spectrogram(@builtin(vertex_index) in_vertex_index: u32) -> SpectrogramOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    let corner = corners[in_vertex_index];
+
+    var out: SpectrogramOutput;
+    let pos = spectrogram_uniforms.offset + corner * spectrogram_uniforms.scale;
+    out.clip_position = vec4<f32>(pos, 0.0, 1.0);
+    out.uv = vec2<f32>(corner.x, 1.0 - corner.y);
+    return out;
+}
+
+@fragment
+fn fs_spectrogram(in: SpectrogramOutput) -> @location(0) vec4<f32> {
+    // Oldest column on the left, newest on the right
+    let u = fract(in.uv.x + spectrogram_uniforms.scroll);
+    return textureSample(spectrogram_texture, spectrogram_sampler, vec2<f32>(u, in.uv.y));
+}
@@ -0,0 +1,29 @@
+struct UiUniforms {
+    projection: mat4x4<f32>, // Pixels (origin top-left, y down) to NDC
+}
+
+@group(0) @binding(0)
+var<uniform> ui_uniforms: UiUniforms;
+
+struct VertexInput {
+    @location(0) position: vec2<f32>, // Pixels
+    @location(1) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+@vertex
+fn vs_ui(input: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.clip_position = ui_uniforms.projection * vec4<f32>(input.position, 0.0, 1.0);
+    out.color = input.color;
+    return out;
+}
+
+@fragment
+fn fs_ui(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
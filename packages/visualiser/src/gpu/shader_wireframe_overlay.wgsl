@@ -0,0 +1,69 @@
+// Solid fill with anti-aliased wireframe edges in a single pass. Each vertex
+// carries its triangle corner's barycentric coordinate; a fragment is on an
+// edge where one coordinate is near zero, measured in pixels with fwidth.
+
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    model: mat4x4<f32>,
+    instance_color: vec4<f32>,
+    camera_position: vec4<f32>,
+    // Distance fog
+    fog_color: vec4<f32>,
+    fog_start: f32,
+    fog_end: f32,
+    fog_enabled: u32,
+    _fog_padding: u32,
+    // Wireframe overlay
+    wireframe_color: vec4<f32>,
+    wireframe_width: f32, // Edge width in pixels
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(2) color: vec3<f32>,
+    @location(3) barycentric: vec3<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+    @location(1) world_pos: vec3<f32>,
+    @location(2) barycentric: vec3<f32>,
+}
+
+@vertex
+fn vs_main(model: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    out.color = model.color;
+    let world_pos = uniforms.model * vec4<f32>(model.position, 1.0);
+    out.clip_position = uniforms.view_proj * world_pos;
+    out.world_pos = world_pos.xyz;
+    out.barycentric = model.barycentric;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Fill matches the solid mesh shader
+    let fill = vec4<f32>(in.color, 1.0) * uniforms.instance_color;
+
+    // Distance to the nearest edge in pixels, smoothed over one pixel
+    let pixels = in.barycentric / max(fwidth(in.barycentric), vec3<f32>(1e-6));
+    let nearest = min(min(pixels.x, pixels.y), pixels.z);
+    let half_width = uniforms.wireframe_width * 0.5;
+    let edge = (1.0 - smoothstep(half_width - 0.5, half_width + 0.5, nearest))
+        * uniforms.wireframe_color.a;
+    let color = vec4<f32>(mix(fill.rgb, uniforms.wireframe_color.rgb, edge), fill.a);
+
+    // Blend towards the fog colour with distance from the camera
+    if uniforms.fog_enabled == 0u {
+        return color;
+    }
+    let distance = length(in.world_pos - uniforms.camera_position.xyz);
+    let range = max(uniforms.fog_end - uniforms.fog_start, 1e-4);
+    let amount = clamp((distance - uniforms.fog_start) / range, 0.0, 1.0);
+    return vec4<f32>(mix(color.rgb, uniforms.fog_color.rgb, amount), color.a);
+}
@@ -34,6 +34,15 @@ impl InputSignal {
         self.duration
     }
 
+    /// Raw samples backing this signal.
+    pub fn samples(&self) -> &[f32] {
+        &self.samples
+    }
+
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+
     pub fn sample(&self, time: f32) -> f32 {
         if time < 0.0 || time > self.duration {
             return 0.0;
@@ -60,8 +69,12 @@ impl InputSignal {
     /// Samples the max absolute value within the window [time - window, time]
     /// This is useful for capturing transients when downsampling high-frequency signals (like audio)
     /// to low-frequency frames (60fps).
+    ///
+    /// A window shorter than one sample (render fps above the analysis rate)
+    /// has no transients to preserve, so it interpolates instead of holding
+    /// the nearest sample.
     pub fn sample_window(&self, time: f32, window: f32) -> f32 {
-        if window <= 0.0 {
+        if window * self.sample_rate < 1.0 {
             return self.sample(time);
         }
 
@@ -109,3 +122,29 @@ impl InputSignal {
         1.0 / den
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_interpolates_between_analysis_frames() {
+        // 100 Hz analysis frames: 0.2 at 10ms, 0.6 at 20ms.
+        let signal = InputSignal::new(vec![0.0, 0.2, 0.6, 0.6], 100.0);
+        assert!((signal.sample(0.015) - 0.4).abs() < 1e-5);
+
+        // A 240 fps frame window straddling the 20ms frame interpolates too.
+        let window = 1.0 / 240.0;
+        assert!((signal.sample_window(0.015, window) - 0.4).abs() < 1e-5);
+        assert!((signal.sample_window(0.021, window) - 0.6).abs() < 1e-5);
+        let ramp: Vec<f32> = (0..10)
+            .map(|i| signal.sample_window(0.01 + i as f32 * window / 2.0, window))
+            .collect();
+        assert!(ramp.windows(2).all(|w| w[1] >= w[0]));
+        assert!(ramp[1] > ramp[0] && ramp[1] < 0.6);
+
+        // Windows spanning several frames still keep the peak.
+        let spiky = InputSignal::new(vec![0.0, 0.0, 1.0, 0.0, 0.0], 100.0);
+        assert_eq!(spiky.sample_window(0.035, 0.03), 1.0);
+    }
+}
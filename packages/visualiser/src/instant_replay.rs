@@ -0,0 +1,158 @@
+//! Instant replay: the last few seconds of rendered frames, kept in memory so
+//! a moment can be exported after it happened.
+//!
+//! With `render --replay-seconds N`, every frame read back from the GPU is
+//! also copied into a [`FrameRingBuffer`]. When the script calls
+//! `dbg.captureReplay()`, the buffered frames (ending with the current one)
+//! are written out and encoded to `replay_NN.<ext>` with the render's video
+//! options. This captures moments that won't come out the same twice, such
+//! as unseeded randomness or live input.
+//!
+//! Frames are stored as raw RGBA8, `width * height * 4` bytes each: 5 seconds
+//! of 1080p at 60 fps is about 2.5 GB. The buffer never grows past
+//! [`MAX_REPLAY_BYTES`]; longer requests keep fewer frames.
+
+#![cfg(not(target_arch = "wasm32"))]
+
+use std::collections::VecDeque;
+use std::path::Path;
+
+use crate::video_encode::{encode_video_with_options, VideoEncodingOptions};
+
+/// Upper bound on buffered frame memory (4 GiB).
+pub const MAX_REPLAY_BYTES: usize = 4 << 30;
+
+/// Bounded buffer of the most recent RGBA8 frames. Pushing onto a full
+/// buffer drops the oldest frame.
+pub struct FrameRingBuffer {
+    frames: VecDeque<Vec<u8>>,
+    capacity: usize,
+    width: u32,
+    height: u32,
+}
+
+impl FrameRingBuffer {
+    /// A buffer holding at most `capacity` frames of `width` x `height`.
+    pub fn new(capacity: usize, width: u32, height: u32) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+            width,
+            height,
+        }
+    }
+
+    /// A buffer for the last `seconds` at `fps`, capped at
+    /// [`MAX_REPLAY_BYTES`].
+    pub fn for_duration(seconds: f32, fps: f32, width: u32, height: u32) -> Self {
+        let wanted = (seconds * fps).ceil().max(1.0) as usize;
+        let frame_bytes = (width as usize * height as usize * 4).max(1);
+        Self::new(wanted.min(MAX_REPLAY_BYTES / frame_bytes), width, height)
+    }
+
+    /// Append a frame (tightly packed RGBA8). Once full, the oldest frame's
+    /// allocation is reused, so steady-state pushes don't allocate.
+    pub fn push(&mut self, rgba: &[u8]) {
+        debug_assert_eq!(rgba.len(), self.frame_bytes());
+        let mut frame = if self.frames.len() == self.capacity {
+            self.frames.pop_front().unwrap_or_default()
+        } else {
+            Vec::with_capacity(rgba.len())
+        };
+        frame.clear();
+        frame.extend_from_slice(rgba);
+        self.frames.push_back(frame);
+    }
+
+    /// Number of buffered frames.
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Whether no frames have been buffered yet.
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Maximum number of frames kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Bytes per frame.
+    pub fn frame_bytes(&self) -> usize {
+        self.width as usize * self.height as usize * 4
+    }
+
+    /// Memory used by a full buffer, in bytes.
+    pub fn max_memory_bytes(&self) -> usize {
+        self.capacity * self.frame_bytes()
+    }
+
+    /// Buffered frames, oldest first.
+    pub fn frames(&self) -> impl Iterator<Item = &[u8]> {
+        self.frames.iter().map(Vec::as_slice)
+    }
+
+    /// Write the buffered frames to `frames_dir` as `frame_XXXXX.png`, then
+    /// encode them to `video_path`. Returns the number of frames written.
+    /// The PNGs are kept if encoding fails.
+    pub fn export(
+        &self,
+        frames_dir: &Path,
+        video_path: &Path,
+        fps: f32,
+        options: &VideoEncodingOptions,
+    ) -> Result<usize, String> {
+        self.write_frames(frames_dir)?;
+        encode_video_with_options(frames_dir, video_path, fps, options)?;
+        Ok(self.len())
+    }
+
+    /// Write the buffered frames to `dir` as `frame_XXXXX.png`, oldest first.
+    pub fn write_frames(&self, dir: &Path) -> Result<(), String> {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create replay directory {:?}: {}", dir, e))?;
+        for (i, frame) in self.frames().enumerate() {
+            let path = dir.join(format!("frame_{:05}.png", i));
+            image::save_buffer(
+                &path,
+                frame,
+                self.width,
+                self.height,
+                image::ColorType::Rgba8,
+            )
+            .map_err(|e| format!("Failed to save replay frame {:?}: {}", path, e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_keeps_last_n_frames() {
+        let mut buffer = FrameRingBuffer::new(3, 1, 1);
+        for i in 0..5u8 {
+            buffer.push(&[i, 0, 0, 255]);
+        }
+        assert_eq!(buffer.len(), 3);
+        let firsts: Vec<u8> = buffer.frames().map(|f| f[0]).collect();
+        assert_eq!(firsts, [2, 3, 4]);
+    }
+
+    #[test]
+    fn test_capacity_from_duration_is_capped_by_memory() {
+        assert_eq!(
+            FrameRingBuffer::for_duration(2.0, 30.0, 4, 4).capacity(),
+            60
+        );
+        // 8K frames are ~132 MB each: 10 minutes would be far over the cap.
+        let huge = FrameRingBuffer::for_duration(600.0, 60.0, 7680, 4320);
+        assert!(huge.max_memory_bytes() <= MAX_REPLAY_BYTES);
+        assert!(huge.capacity() >= 1);
+    }
+}
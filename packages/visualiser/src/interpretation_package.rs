@@ -451,6 +451,19 @@ pub fn load_package(json: &str) -> Result<LoadedPackage> {
     })
 }
 
+/// Push the package's stem signals and publish its available stems.
+pub fn apply_stem_signals(pkg: &LoadedPackage, state: &mut VisualiserState) {
+    for stem in &pkg.stem_signals {
+        state.push_stem_signal(
+            &stem.stem_id,
+            &stem.label,
+            &stem.feature,
+            Rc::clone(&stem.signal),
+        );
+    }
+    state.set_available_stems(pkg.available_stems.clone());
+}
+
 /// Apply the state-resident parts of a loaded package to a [`VisualiserState`],
 /// mirroring the wasm push layer:
 ///
@@ -477,15 +490,7 @@ pub fn apply_to_state(pkg: &LoadedPackage, state: &mut VisualiserState) {
     clear_authored_event_streams();
     clear_band_event_streams();
 
-    for stem in &pkg.stem_signals {
-        state.push_stem_signal(
-            &stem.stem_id,
-            &stem.label,
-            &stem.feature,
-            Rc::clone(&stem.signal),
-        );
-    }
-    state.set_available_stems(pkg.available_stems.clone());
+    apply_stem_signals(pkg, state);
 
     for (name, signal) in &pkg.composed_signals {
         state.push_composed_signal(name, Rc::clone(signal));
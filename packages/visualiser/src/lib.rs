@@ -3,9 +3,16 @@
 #![allow(clippy::ptr_arg)]
 
 pub mod analysis_runner;
+pub mod axis_gizmo;
+pub mod background;
+pub mod bitmap_font;
+pub mod colormap;
 pub mod debug_collector;
+pub mod error;
+pub mod fog;
 pub mod gpu;
 pub mod input;
+pub mod reference_grid;
 pub mod scene_graph;
 pub mod script_api;
 pub mod script_diagnostics;
@@ -13,10 +20,15 @@ pub mod script_introspection;
 pub mod script_log;
 pub mod scripting;
 pub mod sparkline;
+pub mod spectrogram;
+pub mod ui;
 pub mod visualiser;
+pub mod waveform;
 
 // Signal API modules
+pub mod beat_tracker;
 pub mod frequency_band;
+pub mod loudness;
 pub mod musical_time;
 pub mod signal;
 pub mod signal_eval;
@@ -36,6 +48,8 @@ pub mod interpretation_package;
 // Mesh asset modules
 pub mod deformation;
 pub mod mesh_asset;
+pub mod skinning;
+pub mod sprite_asset;
 
 // Material system
 pub mod material;
@@ -55,24 +69,54 @@ pub mod camera_rhai;
 pub mod lighting;
 pub mod lighting_rhai;
 
+// Transform math
+pub mod math_rhai;
+
+// Parameter presets
+pub mod preset_rhai;
+
+// Path following
+pub mod path;
+
 // Debug visualization
 pub mod debug_markers;
 
 // Performance profiling
 pub mod perf_profiling;
 
+// Deterministic replay verification
+pub mod replay;
+
 // Particle system modules
 pub mod particle;
 pub mod particle_eval;
 pub mod particle_rhai;
 
-// Native-only modules (CLI, rendering, video encoding)
+// Native-only modules (embedding API, CLI, rendering, video encoding)
+#[cfg(not(target_arch = "wasm32"))]
+pub mod animated_image;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod builder;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod cli;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod contact_sheet;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod event_files;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod instant_replay;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod midi_files;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod render_job;
 #[cfg(not(target_arch = "wasm32"))]
+pub mod stem_files;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod video_encode;
 
+// Headless GPU harness for rendering tests (never shipped in release builds)
+#[cfg(all(not(target_arch = "wasm32"), test))]
+pub mod test_support;
+
 #[cfg(target_arch = "wasm32")]
 pub mod wasm;
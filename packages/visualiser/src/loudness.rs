@@ -0,0 +1,241 @@
+//! LUFS-style loudness analysis (ITU-R BS.1770 / EBU R128).
+//!
+//! Audio is K-weighted (a high-shelf "head" filter followed by the RLB
+//! high-pass), then measured as mean-square power over a sliding window and
+//! converted to LUFS. Unlike raw amplitude, the result tracks perceived
+//! loudness, so a script driven by `inputs.mix.loudness` behaves consistently
+//! across tracks mastered at different levels.
+//!
+//! - [`short_term_loudness`]: a 3 s sliding-window envelope (EBU R128
+//!   short-term loudness), floored at the absolute gate.
+//! - [`integrated_loudness`]: a single gated value for a whole signal
+//!   (400 ms blocks, absolute and relative gating).
+
+/// Feature / signal name under which loudness is exposed (`inputs.mix.loudness`).
+pub const LOUDNESS_FEATURE: &str = "loudness";
+
+/// Short-term loudness window in seconds (EBU R128).
+pub const SHORT_TERM_WINDOW: f32 = 3.0;
+
+/// Absolute gate in LUFS. Quieter blocks are ignored by integrated loudness,
+/// and short-term loudness never reads below it (silence reads -70).
+pub const ABSOLUTE_GATE: f32 = -70.0;
+
+/// Relative gate in LU below the absolute-gated loudness.
+const RELATIVE_GATE: f32 = -10.0;
+
+/// Gating block length (s) and step (s) for integrated loudness: 400 ms
+/// blocks with 75% overlap.
+const GATING_BLOCK: f32 = 0.4;
+const GATING_STEP: f32 = 0.1;
+
+/// Lowest sample rate at which K-weighting is meaningful. The head filter's
+/// shelf sits around 1.7 kHz, so it needs audio-rate input, not an envelope.
+pub const MIN_LOUDNESS_SAMPLE_RATE: f32 = 8000.0;
+
+/// Apply the BS.1770 K-weighting filter to `samples`.
+///
+/// Coefficients are derived for `sample_rate` from the analogue prototypes,
+/// so any audio rate works (at 48 kHz they match the published table).
+pub fn k_weight(samples: &[f32], sample_rate: f32) -> Vec<f32> {
+    let fs = sample_rate as f64;
+
+    // Stage 1: high shelf (+4 dB above ~1.7 kHz) modelling the head.
+    let k = (std::f64::consts::PI * 1681.974450955533 / fs).tan();
+    let q = 0.7071752369554196;
+    let vh = 10f64.powf(3.999843853973347 / 20.0);
+    let vb = vh.powf(0.4996667741545416);
+    let a0 = 1.0 + k / q + k * k;
+    let shelf = Biquad {
+        b: [
+            (vh + vb * k / q + k * k) / a0,
+            2.0 * (k * k - vh) / a0,
+            (vh - vb * k / q + k * k) / a0,
+        ],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    };
+
+    // Stage 2: RLB high-pass (~38 Hz).
+    let k = (std::f64::consts::PI * 38.13547087602444 / fs).tan();
+    let q = 0.5003270373238773;
+    let a0 = 1.0 + k / q + k * k;
+    let high_pass = Biquad {
+        b: [1.0, -2.0, 1.0],
+        a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+    };
+
+    high_pass.apply(&shelf.apply(samples))
+}
+
+/// Short-term loudness envelope in LUFS, one value every `1 / frame_rate`
+/// seconds.
+///
+/// Each value is the K-weighted loudness of the [`SHORT_TERM_WINDOW`] ending
+/// at the end of its frame (shorter at the start of the signal). Values are
+/// floored at [`ABSOLUTE_GATE`]. There are `ceil(len / hop)` frames, like the
+/// stem envelopes. Returns an empty envelope for input below
+/// [`MIN_LOUDNESS_SAMPLE_RATE`].
+pub fn short_term_loudness(samples: &[f32], sample_rate: f32, frame_rate: f32) -> Vec<f32> {
+    if samples.is_empty() || sample_rate < MIN_LOUDNESS_SAMPLE_RATE || frame_rate <= 0.0 {
+        return Vec::new();
+    }
+
+    let prefix = power_prefix_sums(&k_weight(samples, sample_rate));
+    let hop = ((sample_rate / frame_rate).round() as usize).max(1);
+    let window = ((SHORT_TERM_WINDOW * sample_rate).round() as usize).max(1);
+    let frame_count = samples.len().div_ceil(hop);
+
+    (0..frame_count)
+        .map(|frame| {
+            let end = ((frame + 1) * hop).min(samples.len());
+            let start = end.saturating_sub(window);
+            let mean_square = (prefix[end] - prefix[start]) / (end - start) as f64;
+            lufs(mean_square).max(ABSOLUTE_GATE)
+        })
+        .collect()
+}
+
+/// Integrated (gated) loudness of a whole signal in LUFS.
+///
+/// The K-weighted signal is split into 400 ms blocks with 75% overlap.
+/// Blocks below [`ABSOLUTE_GATE`] are discarded, then blocks more than
+/// 10 LU below the loudness of the remainder. Returns [`ABSOLUTE_GATE`] for
+/// silence or input below [`MIN_LOUDNESS_SAMPLE_RATE`].
+pub fn integrated_loudness(samples: &[f32], sample_rate: f32) -> f32 {
+    if samples.is_empty() || sample_rate < MIN_LOUDNESS_SAMPLE_RATE {
+        return ABSOLUTE_GATE;
+    }
+
+    let prefix = power_prefix_sums(&k_weight(samples, sample_rate));
+    let block = ((GATING_BLOCK * sample_rate).round() as usize).clamp(1, samples.len());
+    let step = ((GATING_STEP * sample_rate).round() as usize).max(1);
+
+    let blocks: Vec<f64> = (0..=(samples.len() - block) / step)
+        .map(|i| {
+            let start = i * step;
+            (prefix[start + block] - prefix[start]) / block as f64
+        })
+        .filter(|&mean_square| lufs(mean_square) > ABSOLUTE_GATE)
+        .collect();
+    if blocks.is_empty() {
+        return ABSOLUTE_GATE;
+    }
+
+    let relative_gate = lufs(mean(&blocks)) + RELATIVE_GATE;
+    let gated: Vec<f64> = blocks
+        .into_iter()
+        .filter(|&mean_square| lufs(mean_square) > relative_gate)
+        .collect();
+    if gated.is_empty() {
+        return ABSOLUTE_GATE;
+    }
+    lufs(mean(&gated)).max(ABSOLUTE_GATE)
+}
+
+/// Convert a K-weighted mean-square power to LUFS.
+fn lufs(mean_square: f64) -> f32 {
+    if mean_square <= 0.0 {
+        return f32::NEG_INFINITY;
+    }
+    (-0.691 + 10.0 * mean_square.log10()) as f32
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+/// Prefix sums of squared samples, so any window's power is one subtraction.
+fn power_prefix_sums(samples: &[f32]) -> Vec<f64> {
+    let mut prefix = Vec::with_capacity(samples.len() + 1);
+    let mut sum = 0.0f64;
+    prefix.push(sum);
+    for &s in samples {
+        sum += s as f64 * s as f64;
+        prefix.push(sum);
+    }
+    prefix
+}
+
+/// Direct-form I biquad with normalised coefficients (`a0 == 1`).
+struct Biquad {
+    b: [f64; 3],
+    /// `a1`, `a2`.
+    a: [f64; 2],
+}
+
+impl Biquad {
+    fn apply(&self, input: &[f32]) -> Vec<f32> {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0f64, 0.0f64, 0.0f64, 0.0f64);
+        input
+            .iter()
+            .map(|&x| {
+                let x = x as f64;
+                let y = self.b[0] * x + self.b[1] * x1 + self.b[2] * x2
+                    - self.a[0] * y1
+                    - self.a[1] * y2;
+                x2 = x1;
+                x1 = x;
+                y2 = y1;
+                y1 = y;
+                y as f32
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(hz: f32, amplitude: f32, seconds: f32, sample_rate: f32) -> Vec<f32> {
+        (0..(seconds * sample_rate) as usize)
+            .map(|i| amplitude * (std::f32::consts::TAU * hz * i as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_full_scale_1khz_sine_reads_minus_3_lufs() {
+        // BS.1770 reference: a 0 dBFS 1 kHz sine in one channel reads -3.01.
+        let tone = sine(1000.0, 1.0, 4.0, 48000.0);
+        assert!((integrated_loudness(&tone, 48000.0) + 3.01).abs() < 0.1);
+
+        let envelope = short_term_loudness(&tone, 48000.0, 10.0);
+        assert_eq!(envelope.len(), 40);
+        assert!((envelope[35] + 3.01).abs() < 0.1, "{}", envelope[35]);
+    }
+
+    #[test]
+    fn test_louder_signal_reads_higher_loudness() {
+        // Same spectrum (a 440 Hz + 3 kHz mix), 12 dB apart in level.
+        let mix = |gain: f32| -> Vec<f32> {
+            sine(440.0, 0.4 * gain, 4.0, 44100.0)
+                .iter()
+                .zip(sine(3000.0, 0.2 * gain, 4.0, 44100.0))
+                .map(|(a, b)| a + b)
+                .collect()
+        };
+        let loud = mix(1.0);
+        let quiet = mix(0.25);
+
+        let loud_lufs = integrated_loudness(&loud, 44100.0);
+        let quiet_lufs = integrated_loudness(&quiet, 44100.0);
+        assert!(loud_lufs > quiet_lufs);
+        assert!((loud_lufs - quiet_lufs - 12.04).abs() < 0.1);
+
+        let loud_env = short_term_loudness(&loud, 44100.0, 100.0);
+        let quiet_env = short_term_loudness(&quiet, 44100.0, 100.0);
+        assert!(loud_env.iter().zip(&quiet_env).skip(1).all(|(l, q)| l > q));
+    }
+
+    #[test]
+    fn test_silence_and_envelope_rates_floor_at_absolute_gate() {
+        let silence = vec![0.0; 48000];
+        assert_eq!(integrated_loudness(&silence, 48000.0), ABSOLUTE_GATE);
+        assert!(short_term_loudness(&silence, 48000.0, 100.0)
+            .iter()
+            .all(|&v| v == ABSOLUTE_GATE));
+
+        // A 100 Hz envelope is not audio: no loudness is computed.
+        assert!(short_term_loudness(&[0.5; 100], 100.0, 100.0).is_empty());
+    }
+}
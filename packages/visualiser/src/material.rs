@@ -366,7 +366,7 @@ impl MaterialTopology {
 }
 
 /// Blend modes for materials.
-#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
 pub enum BlendMode {
     /// No blending, fully opaque.
     #[default]
@@ -763,6 +763,29 @@ mod tests {
         assert_eq!(registry.len(), 7);
     }
 
+    #[test]
+    fn test_blend_states_match_blend_modes() {
+        assert_eq!(
+            BlendMode::Opaque.to_blend_state(),
+            wgpu::BlendState::REPLACE
+        );
+        assert_eq!(
+            BlendMode::AlphaBlend.to_blend_state(),
+            wgpu::BlendState::ALPHA_BLENDING
+        );
+
+        // Additive adds the alpha-weighted source onto whatever is there
+        let additive = BlendMode::Additive.to_blend_state();
+        assert_eq!(additive.color.src_factor, wgpu::BlendFactor::SrcAlpha);
+        assert_eq!(additive.color.dst_factor, wgpu::BlendFactor::One);
+        assert_eq!(additive.color.operation, wgpu::BlendOperation::Add);
+
+        // Multiply scales the destination by the source
+        let multiply = BlendMode::Multiply.to_blend_state();
+        assert_eq!(multiply.color.src_factor, wgpu::BlendFactor::Dst);
+        assert_eq!(multiply.color.dst_factor, wgpu::BlendFactor::Zero);
+    }
+
     #[test]
     fn test_material_params() {
         let registry = MaterialRegistry::new();
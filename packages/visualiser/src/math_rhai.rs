@@ -0,0 +1,363 @@
+//! Rhai registration and namespace generation for transform math.
+//!
+//! Scripts build 4x4 transforms with `math.translate()`, `math.rotateY()` etc.
+//! and compose them with `*`, so positions can be computed procedurally
+//! without hand-written trigonometry:
+//!
+//! ```rhai
+//! // Translate first, then rotate about Y (applied right to left).
+//! let m = math.rotateY(PI() / 2.0) * math.translate(1.0, 0.0, 0.0);
+//! cube.position = m.transformPoint(#{ x: 0.0, y: 0.0, z: 0.0 });
+//! ```
+//!
+//! The `vec` namespace has the matching vector helpers (`vec.dot()`,
+//! `vec.cross()`, `vec.normalize()`, ...) on `#{ x, y, z }` maps, so vectors
+//! stay the same maps entity positions use.
+//!
+//! Matrices and vectors are plain numbers (not Signals); evaluate them in
+//! `update()` for per-frame motion.
+//!
+//! The `ease` namespace exposes the Penner easings (`ease.outCubic(t)`, ...).
+//! Unlike the above they also accept a Signal, returning `signal.ease(name)`.
+
+use rhai::{Dynamic, Engine, EvalAltResult};
+
+use crate::signal::{EasingFunction, Signal};
+use crate::signal_rhai::easing_by_name;
+
+/// A 4x4 transform matrix exposed to scripts as `Mat4`.
+///
+/// Column-vector convention (like glam and the renderer): `a * b` applies `b`
+/// first, then `a`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4(pub glam::Mat4);
+
+/// Convert a script number (float or int) to f32.
+fn number(value: &Dynamic, name: &str) -> Result<f32, Box<EvalAltResult>> {
+    if let Ok(f) = value.as_float() {
+        return Ok(f);
+    }
+    if let Ok(i) = value.as_int() {
+        return Ok(i as f32);
+    }
+    Err(format!(
+        "math: {} must be a number, got {} (Signals are not supported here)",
+        name,
+        value.type_name()
+    )
+    .into())
+}
+
+/// Convert a `#{ x, y, z }` map to a vector (missing components are 0).
+fn to_vec3(value: &Dynamic) -> Result<glam::Vec3, Box<EvalAltResult>> {
+    let Some(map) = value.read_lock::<rhai::Map>() else {
+        return Err(format!(
+            "math: expected #{{ x, y, z }} map, got {}",
+            value.type_name()
+        )
+        .into());
+    };
+    let component = |key: &str| match map.get(key) {
+        Some(v) => number(v, key),
+        None => Ok(0.0),
+    };
+    Ok(glam::Vec3::new(
+        component("x")?,
+        component("y")?,
+        component("z")?,
+    ))
+}
+
+fn from_vec3(v: glam::Vec3) -> rhai::Map {
+    let mut map = rhai::Map::new();
+    map.insert("x".into(), Dynamic::from(v.x));
+    map.insert("y".into(), Dynamic::from(v.y));
+    map.insert("z".into(), Dynamic::from(v.z));
+    map
+}
+
+/// Register the `Mat4` type and the `__math_*` constructors with the engine.
+pub fn register_math_api(engine: &mut Engine) {
+    engine.register_type_with_name::<Mat4>("Mat4");
+
+    // Constructors (called from the generated `math` namespace)
+    engine.register_fn("__math_identity", || Mat4(glam::Mat4::IDENTITY));
+    engine.register_fn(
+        "__math_translate",
+        |x: Dynamic, y: Dynamic, z: Dynamic| -> Result<Mat4, Box<EvalAltResult>> {
+            Ok(Mat4(glam::Mat4::from_translation(glam::Vec3::new(
+                number(&x, "x")?,
+                number(&y, "y")?,
+                number(&z, "z")?,
+            ))))
+        },
+    );
+    engine.register_fn(
+        "__math_scale",
+        |x: Dynamic, y: Dynamic, z: Dynamic| -> Result<Mat4, Box<EvalAltResult>> {
+            Ok(Mat4(glam::Mat4::from_scale(glam::Vec3::new(
+                number(&x, "x")?,
+                number(&y, "y")?,
+                number(&z, "z")?,
+            ))))
+        },
+    );
+    engine.register_fn(
+        "__math_rotate_x",
+        |radians: Dynamic| -> Result<Mat4, Box<EvalAltResult>> {
+            Ok(Mat4(glam::Mat4::from_rotation_x(number(
+                &radians, "radians",
+            )?)))
+        },
+    );
+    engine.register_fn(
+        "__math_rotate_y",
+        |radians: Dynamic| -> Result<Mat4, Box<EvalAltResult>> {
+            Ok(Mat4(glam::Mat4::from_rotation_y(number(
+                &radians, "radians",
+            )?)))
+        },
+    );
+    engine.register_fn(
+        "__math_rotate_z",
+        |radians: Dynamic| -> Result<Mat4, Box<EvalAltResult>> {
+            Ok(Mat4(glam::Mat4::from_rotation_z(number(
+                &radians, "radians",
+            )?)))
+        },
+    );
+
+    // Composition
+    engine.register_fn("*", |a: Mat4, b: Mat4| Mat4(a.0 * b.0));
+    engine.register_fn("mul", |a: &mut Mat4, b: Mat4| Mat4(a.0 * b.0));
+    engine.register_fn("inverse", |m: &mut Mat4| Mat4(m.0.inverse()));
+
+    // Applying to points (with translation) and directions (without)
+    engine.register_fn(
+        "transformPoint",
+        |m: &mut Mat4, point: Dynamic| -> Result<rhai::Map, Box<EvalAltResult>> {
+            Ok(from_vec3(m.0.transform_point3(to_vec3(&point)?)))
+        },
+    );
+    engine.register_fn(
+        "transformVector",
+        |m: &mut Mat4, vector: Dynamic| -> Result<rhai::Map, Box<EvalAltResult>> {
+            Ok(from_vec3(m.0.transform_vector3(to_vec3(&vector)?)))
+        },
+    );
+
+    engine.register_fn("to_string", |m: &mut Mat4| format!("{:?}", m.0));
+    engine.register_fn("to_debug", |m: &mut Mat4| format!("{:?}", m.0));
+
+    // Vector helpers on #{ x, y, z } maps (called from the generated `vec` namespace)
+    engine.register_fn(
+        "__vec_dot",
+        |a: Dynamic, b: Dynamic| -> Result<f32, Box<EvalAltResult>> {
+            Ok(to_vec3(&a)?.dot(to_vec3(&b)?))
+        },
+    );
+    engine.register_fn(
+        "__vec_cross",
+        |a: Dynamic, b: Dynamic| -> Result<rhai::Map, Box<EvalAltResult>> {
+            Ok(from_vec3(to_vec3(&a)?.cross(to_vec3(&b)?)))
+        },
+    );
+    engine.register_fn(
+        "__vec_length",
+        |a: Dynamic| -> Result<f32, Box<EvalAltResult>> { Ok(to_vec3(&a)?.length()) },
+    );
+    // A zero vector has no direction: normalizing it returns zero.
+    engine.register_fn(
+        "__vec_normalize",
+        |a: Dynamic| -> Result<rhai::Map, Box<EvalAltResult>> {
+            Ok(from_vec3(to_vec3(&a)?.normalize_or_zero()))
+        },
+    );
+    engine.register_fn(
+        "__vec_add",
+        |a: Dynamic, b: Dynamic| -> Result<rhai::Map, Box<EvalAltResult>> {
+            Ok(from_vec3(to_vec3(&a)? + to_vec3(&b)?))
+        },
+    );
+    engine.register_fn(
+        "__vec_sub",
+        |a: Dynamic, b: Dynamic| -> Result<rhai::Map, Box<EvalAltResult>> {
+            Ok(from_vec3(to_vec3(&a)? - to_vec3(&b)?))
+        },
+    );
+    engine.register_fn(
+        "__vec_scale",
+        |a: Dynamic, factor: Dynamic| -> Result<rhai::Map, Box<EvalAltResult>> {
+            Ok(from_vec3(to_vec3(&a)? * number(&factor, "factor")?))
+        },
+    );
+    engine.register_fn(
+        "__vec_lerp",
+        |a: Dynamic, b: Dynamic, t: Dynamic| -> Result<rhai::Map, Box<EvalAltResult>> {
+            Ok(from_vec3(to_vec3(&a)?.lerp(to_vec3(&b)?, number(&t, "t")?)))
+        },
+    );
+
+    // Easing (called from the generated `ease` namespace): numbers are eased
+    // directly, Signals get an ease node.
+    engine.register_fn(
+        "__ease",
+        |name: &str, value: Dynamic| -> Result<Dynamic, Box<EvalAltResult>> {
+            let easing = easing_by_name(name)?;
+            if let Some(signal) = value.clone().try_cast::<Signal>() {
+                return Ok(Dynamic::from(signal.ease(easing)));
+            }
+            Ok(Dynamic::from(easing.apply(number(&value, "t")?)))
+        },
+    );
+}
+
+/// Generate the `math`, `vec` and `ease` namespace Rhai code.
+pub fn generate_math_namespace() -> String {
+    let mut code = String::from(
+        r#"
+// === Math Namespace ===
+let math = #{};
+math.__type = "math_namespace";
+math.identity = || __math_identity();
+math.translate = |x, y, z| __math_translate(x, y, z);
+math.scale = |x, y, z| __math_scale(x, y, z);
+math.rotateX = |radians| __math_rotate_x(radians);
+math.rotateY = |radians| __math_rotate_y(radians);
+math.rotateZ = |radians| __math_rotate_z(radians);
+
+// === Vec Namespace ===
+let vec = #{};
+vec.__type = "vec_namespace";
+vec.dot = |a, b| __vec_dot(a, b);
+vec.cross = |a, b| __vec_cross(a, b);
+vec.length = |a| __vec_length(a);
+vec.normalize = |a| __vec_normalize(a);
+vec.add = |a, b| __vec_add(a, b);
+vec.sub = |a, b| __vec_sub(a, b);
+vec.scale = |a, factor| __vec_scale(a, factor);
+vec.lerp = |a, b, t| __vec_lerp(a, b, t);
+
+// === Ease Namespace ===
+let ease = #{};
+ease.__type = "ease_namespace";
+"#,
+    );
+    for (name, _) in EasingFunction::NAMED {
+        code.push_str(&format!("ease.{name} = |t| __ease(\"{name}\", t);\n"));
+    }
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{BandSignalMap, SignalMap};
+    use crate::visualiser::VisualiserState;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_translate_then_rotate_point() {
+        let mut state = VisualiserState::new();
+        state
+            .try_load_script(
+                r#"
+                let cube;
+                fn init(ctx) {
+                    cube = mesh.cube();
+                    // Move to x = 1, then rotate a quarter turn about Y.
+                    let m = math.rotateY(PI() / 2.0) * math.translate(1, 0, 0);
+                    cube.position = m.transformPoint(#{ x: 0.0, y: 2.0, z: 0.0 });
+                    // Directions ignore translation.
+                    let dir = m.transformVector(#{ x: 0.0, y: 0.0, z: 1.0 });
+                    cube.rotation = dir;
+                    scene.add(cube);
+                }
+                fn update(dt, frame) {}
+            "#,
+            )
+            .unwrap();
+
+        let signals: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        state.update(0.05, None, None, &signals, &bands, &signals, None);
+        let (_, cube) = state.scene_graph().scene_entities().next().unwrap();
+        let position = cube.transform().position;
+        assert!(position.x.abs() < 1e-5);
+        assert!((position.y - 2.0).abs() < 1e-5);
+        assert!((position.z + 1.0).abs() < 1e-5);
+        let direction = cube.transform().rotation;
+        assert!((direction.x - 1.0).abs() < 1e-5);
+        assert!(direction.z.abs() < 1e-5);
+    }
+
+    /// Evaluate `expr` with the math API and namespaces available.
+    fn eval<T: Clone + 'static>(expr: &str) -> T {
+        let mut engine = Engine::new();
+        register_math_api(&mut engine);
+        engine
+            .eval::<T>(&format!("{}\n{}", generate_math_namespace(), expr))
+            .unwrap()
+    }
+
+    fn eval_vec(expr: &str) -> (f32, f32, f32) {
+        let v = eval::<rhai::Map>(expr);
+        let c = |key: &str| v[key].as_float().unwrap();
+        (c("x"), c("y"), c("z"))
+    }
+
+    #[test]
+    fn test_vec_helpers() {
+        let x = "#{ x: 1.0, y: 0.0, z: 0.0 }";
+        let y = "#{ x: 0.0, y: 1.0, z: 0.0 }";
+        let v = "#{ x: 3, y: 4, z: 0 }";
+
+        assert_eq!(eval::<f32>(&format!("vec.dot({v}, {v})")), 25.0);
+        assert_eq!(eval::<f32>(&format!("vec.dot({x}, {y})")), 0.0);
+        assert_eq!(eval_vec(&format!("vec.cross({x}, {y})")), (0.0, 0.0, 1.0));
+        assert_eq!(eval::<f32>(&format!("vec.length({v})")), 5.0);
+        assert_eq!(eval_vec(&format!("vec.normalize({v})")), (0.6, 0.8, 0.0));
+        assert_eq!(eval_vec(&format!("vec.add({x}, {y})")), (1.0, 1.0, 0.0));
+        assert_eq!(eval_vec(&format!("vec.sub({v}, {x})")), (2.0, 4.0, 0.0));
+        assert_eq!(eval_vec(&format!("vec.scale({v}, 2)")), (6.0, 8.0, 0.0));
+        assert_eq!(
+            eval_vec(&format!("vec.lerp({x}, {y}, 0.25)")),
+            (0.75, 0.25, 0.0)
+        );
+        // Missing components read as 0.
+        assert_eq!(eval::<f32>("vec.length(#{ x: 3.0, y: 4.0 })"), 5.0);
+    }
+
+    #[test]
+    fn test_normalize_zero_vector_is_zero() {
+        assert_eq!(
+            eval_vec("vec.normalize(#{ x: 0.0, y: 0.0, z: 0.0 })"),
+            (0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_ease_namespace_and_signal_ease() {
+        assert_eq!(eval::<f32>("ease.outCubic(0.5)"), 0.875);
+        assert_eq!(eval::<f32>("ease.inQuad(2.0)"), 1.0);
+
+        let mut engine = Engine::new();
+        register_math_api(&mut engine);
+        crate::signal_rhai::register_signal_api(&mut engine);
+        let mut scope = rhai::Scope::new();
+        scope.push("s", Signal::constant(0.5));
+        let signal = engine
+            .eval_with_scope::<Signal>(
+                &mut scope,
+                &format!("{}\nease.outCubic(s)", generate_math_namespace()),
+            )
+            .unwrap();
+        assert!(matches!(
+            &*signal.node,
+            crate::signal::SignalNode::Ease {
+                easing: EasingFunction::CubicOut,
+                ..
+            }
+        ));
+    }
+}
@@ -1,17 +1,57 @@
 //! Mesh asset loading and management.
 //!
-//! This module provides support for loading external 3D meshes from OBJ format
-//! and preparing them for rendering, including wireframe edge extraction.
+//! This module provides support for loading external 3D meshes from OBJ and
+//! glTF/GLB formats and preparing them for rendering, including wireframe edge
+//! extraction. Hosts register assets by id; on native builds an unregistered id
+//! is treated as a file path and loaded from disk on first use.
 //!
 //! ## Normal Handling
 //!
-//! OBJ meshes use provided normals when available. Normals are generated only
-//! when missing, using area-weighted averaging of adjacent face normals.
+//! OBJ and glTF meshes use provided normals when available. Normals are
+//! generated only when missing, using area-weighted averaging of adjacent face
+//! normals.
+//!
+//! ## Vertex Colors
+//!
+//! OBJ `v x y z r g b` colors and glTF `COLOR_0` attributes become vertex
+//! colors, which the mesh shaders multiply by the entity's color. OBJ models
+//! without colors are shaded by position instead.
+//!
+//! ## Morph Targets
+//!
+//! glTF morph targets are kept as named position (and normal) offsets, named
+//! by the mesh's `extras.targetNames` or else by index. Targets with the same
+//! name in different primitives are merged. Entities blend them additively
+//! each frame on the CPU, before any deformations.
+//!
+//! ## Skinning
+//!
+//! The first skin in a glTF scene is imported with its joint hierarchy,
+//! `JOINTS_0`/`WEIGHTS_0` vertex weights, and the animations that move its
+//! joints (see `crate::skinning`). Vertices of meshes using that skin stay in
+//! bind space; other meshes are baked with their node transforms and left
+//! unweighted, so they don't move.
+//!
+//! ## glTF Limitations
+//!
+//! A glTF file becomes a single mesh asset: every triangle primitive in the
+//! default scene is merged, with node transforms baked in. Each primitive is
+//! colored by its material's base color factor times its vertex colors. Textures,
+//! extra skins, non-joint node animations, and point/line primitives are ignored, buffers must be embedded (GLB binary
+//! chunk or base64 data URI), and the merged mesh must fit in 16-bit indices.
 
 use std::collections::{HashMap, HashSet};
-use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
-use crate::gpu::mesh::Vertex;
+use base64::Engine as _;
+use glam::{Mat3, Mat4, Quat, Vec3};
+
+use crate::gpu::mesh::{SkinVertex, Vertex};
+use crate::skinning::{
+    AnimationChannel, AnimationClip, ChannelValues, Interpolation, Joint, Skin, MAX_JOINTS,
+};
 
 /// Axis-aligned bounding box for a mesh.
 #[derive(Debug, Clone, Copy, Default)]
@@ -90,6 +130,37 @@ pub struct MeshAsset {
     pub edge_indices: Vec<u16>,
     /// Axis-aligned bounding box.
     pub bounds: BoundingBox,
+    /// Named morph targets, blended by entity morph weights.
+    pub morph_targets: Vec<MorphTarget>,
+    /// Skeleton and animations, for skinned meshes.
+    pub skin: Option<Skin>,
+}
+
+/// Alternate vertex positions stored as per-vertex offsets from the base mesh.
+#[derive(Debug, Clone)]
+pub struct MorphTarget {
+    /// Name scripts refer to the target by.
+    pub name: String,
+    /// Position offset for each vertex.
+    pub position_offsets: Vec<[f32; 3]>,
+    /// Normal offset for each vertex (zero when the target has no normals).
+    pub normal_offsets: Vec<[f32; 3]>,
+}
+
+impl MorphTarget {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            position_offsets: Vec::new(),
+            normal_offsets: Vec::new(),
+        }
+    }
+
+    /// Pad the offsets with zeros up to `len` vertices.
+    fn pad(&mut self, len: usize) {
+        self.position_offsets.resize(len, [0.0; 3]);
+        self.normal_offsets.resize(len, [0.0; 3]);
+    }
 }
 
 impl MeshAsset {
@@ -104,19 +175,61 @@ impl MeshAsset {
             indices,
             edge_indices,
             bounds,
+            morph_targets: Vec::new(),
+            skin: None,
         }
     }
 
+    /// Find a morph target by name.
+    pub fn morph_target(&self, name: &str) -> Option<&MorphTarget> {
+        self.morph_targets.iter().find(|target| target.name == name)
+    }
+
+    /// Blend morph targets into the vertices, adding each named target's
+    /// offsets scaled by its weight. Unknown names are ignored.
+    pub fn morphed_vertices(&self, weights: &[(String, f32)]) -> Vec<Vertex> {
+        let mut vertices = self.vertices.clone();
+        let mut blended_normals = false;
+        for (name, weight) in weights {
+            let Some(target) = self.morph_target(name) else {
+                continue;
+            };
+            if *weight == 0.0 {
+                continue;
+            }
+            for ((vertex, position), normal) in vertices
+                .iter_mut()
+                .zip(&target.position_offsets)
+                .zip(&target.normal_offsets)
+            {
+                for axis in 0..3 {
+                    vertex.position[axis] += position[axis] * weight;
+                    vertex.normal[axis] += normal[axis] * weight;
+                }
+            }
+            blended_normals = true;
+        }
+        if blended_normals {
+            for vertex in &mut vertices {
+                let normal = Vec3::from(vertex.normal).normalize_or(Vec3::Y);
+                vertex.normal = normal.into();
+            }
+        }
+        vertices
+    }
+
     /// Parse a mesh asset from OBJ format content.
     ///
     /// The OBJ content should be a valid Wavefront OBJ string.
-    /// Vertex positions and faces are required. Normals are used when present;
-    /// otherwise they are computed using area-weighted averaging.
+    /// Vertex positions and faces are required. Polygons are fan-triangulated for
+    /// rendering while the wireframe keeps their outlines. Normals are used when
+    /// present; otherwise they are computed using area-weighted averaging.
     pub fn from_obj(id: String, obj_content: &str) -> Result<Self, String> {
         let mut cursor = std::io::Cursor::new(obj_content.as_bytes());
 
         let load_options = tobj::LoadOptions {
-            triangulate: true,
+            // Triangulated below, so wireframes can follow the polygon outlines
+            triangulate: false,
             single_index: true,
             ..Default::default()
         };
@@ -132,8 +245,11 @@ impl MeshAsset {
         // Combine all models into a single mesh
         let mut all_positions: Vec<[f32; 3]> = Vec::new();
         let mut all_normals: Vec<[f32; 3]> = Vec::new();
+        let mut all_colors: Vec<[f32; 3]> = Vec::new();
         let mut all_indices = Vec::new();
-        let mut vertex_offset = 0u16;
+        let mut face_indices = Vec::new();
+        let mut face_arities = Vec::new();
+        let mut vertex_offset = 0usize;
         let mut has_normals = true;
 
         for model in &models {
@@ -146,13 +262,22 @@ impl MeshAsset {
 
             let vertex_count = mesh.positions.len() / 3;
 
+            if vertex_offset + vertex_count > u16::MAX as usize + 1 {
+                return Err(format!(
+                    "OBJ file has more than {} vertices",
+                    u16::MAX as usize + 1
+                ));
+            }
+
             // Check if this model has normals
             let model_has_normals = mesh.normals.len() == mesh.positions.len();
             if !model_has_normals {
                 has_normals = false;
             }
 
-            // Extract positions and normals
+            let model_has_colors = mesh.vertex_color.len() == mesh.positions.len();
+
+            // Extract positions, normals and colors
             for i in 0..vertex_count {
                 let position = [
                     mesh.positions[i * 3],
@@ -169,15 +294,51 @@ impl MeshAsset {
                     ];
                     all_normals.push(normal);
                 }
+
+                let color = if model_has_colors {
+                    [
+                        mesh.vertex_color[i * 3],
+                        mesh.vertex_color[i * 3 + 1],
+                        mesh.vertex_color[i * 3 + 2],
+                    ]
+                } else {
+                    // Use vertex position-based coloring for visual interest
+                    [
+                        (position[0].abs() * 0.5 + 0.5).clamp(0.3, 1.0),
+                        (position[1].abs() * 0.5 + 0.5).clamp(0.3, 1.0),
+                        (position[2].abs() * 0.5 + 0.5).clamp(0.3, 1.0),
+                    ]
+                };
+                all_colors.push(color);
             }
 
-            // Extract indices with offset
-            for idx in &mesh.indices {
-                let adjusted_idx = vertex_offset + (*idx as u16);
-                all_indices.push(adjusted_idx);
+            // Extract polygons with offset (no arities means all triangles)
+            let arities: Vec<u32> = if mesh.face_arities.is_empty() {
+                vec![3; mesh.indices.len() / 3]
+            } else {
+                mesh.face_arities.clone()
+            };
+            let mut start = 0;
+            for arity in arities {
+                let end = start + arity as usize;
+                let Some(face) = mesh.indices.get(start..end) else {
+                    return Err("OBJ face refers to missing indices".to_string());
+                };
+                let face: Vec<u16> = face
+                    .iter()
+                    .map(|&idx| (vertex_offset + idx as usize) as u16)
+                    .collect();
+
+                // Fan-triangulate around the first vertex
+                for i in 1..face.len().saturating_sub(1) {
+                    all_indices.extend_from_slice(&[face[0], face[i], face[i + 1]]);
+                }
+                face_indices.extend_from_slice(&face);
+                face_arities.push(arity);
+                start = end;
             }
 
-            vertex_offset += vertex_count as u16;
+            vertex_offset += vertex_count;
         }
 
         if all_positions.is_empty() {
@@ -195,18 +356,219 @@ impl MeshAsset {
         let vertices: Vec<Vertex> = all_positions
             .iter()
             .zip(normals.iter())
-            .map(|(position, normal)| {
-                // Use vertex position-based coloring for visual interest
-                let color = [
-                    (position[0].abs() * 0.5 + 0.5).clamp(0.3, 1.0),
-                    (position[1].abs() * 0.5 + 0.5).clamp(0.3, 1.0),
-                    (position[2].abs() * 0.5 + 0.5).clamp(0.3, 1.0),
-                ];
-                Vertex::new(*position, *normal, color)
-            })
+            .zip(all_colors.iter())
+            .map(|((position, normal), color)| Vertex::new(*position, *normal, *color))
             .collect();
 
-        Ok(Self::new(id, vertices, all_indices))
+        let mut asset = Self::new(id, vertices, all_indices);
+        asset.edge_indices = extract_face_edges(&face_indices, &face_arities);
+        Ok(asset)
+    }
+
+    /// Parse a mesh asset from glTF content (binary GLB or embedded JSON glTF).
+    ///
+    /// See the module docs for what is imported. Normals are used when present;
+    /// otherwise they are computed per primitive.
+    pub fn from_gltf(id: String, gltf_content: &[u8]) -> Result<Self, String> {
+        let gltf = gltf::Gltf::from_slice(gltf_content)
+            .map_err(|e| format!("Failed to parse glTF: {}", e))?;
+        let buffers = load_gltf_buffers(&gltf)?;
+
+        // Walk the default scene (or the first one) so node transforms apply
+        let mut meshes = Vec::new();
+        match gltf.default_scene().or_else(|| gltf.scenes().next()) {
+            Some(scene) => {
+                for node in scene.nodes() {
+                    collect_gltf_meshes(&node, Mat4::IDENTITY, &mut meshes);
+                }
+            }
+            None => meshes.extend(gltf.meshes().map(|mesh| (mesh, Mat4::IDENTITY, None))),
+        }
+        let skin = meshes.iter().find_map(|(_, _, skin)| skin.clone());
+        let joint_count = skin.as_ref().map_or(0, |skin| skin.joints().len());
+
+        let mut vertices: Vec<Vertex> = Vec::new();
+        let mut indices: Vec<u16> = Vec::new();
+        let mut morph_targets: Vec<MorphTarget> = Vec::new();
+        let mut skin_vertices: Vec<SkinVertex> = Vec::new();
+
+        for (mesh, node_transform, mesh_skin) in &meshes {
+            let skinned = skin.is_some()
+                && mesh_skin.as_ref().map(gltf::Skin::index)
+                    == skin.as_ref().map(gltf::Skin::index);
+            // Skinned meshes are placed by their joints rather than their node
+            let transform = if skinned {
+                Mat4::IDENTITY
+            } else {
+                *node_transform
+            };
+            let normal_matrix = Mat3::from_mat4(transform).inverse().transpose();
+            let target_names = gltf_target_names(mesh);
+
+            for primitive in mesh.primitives() {
+                if primitive.mode() != gltf::mesh::Mode::Triangles {
+                    continue;
+                }
+
+                let reader =
+                    primitive.reader(|buffer| buffers.get(buffer.index()).map(Vec::as_slice));
+                let Some(positions) = reader.read_positions() else {
+                    continue;
+                };
+                let positions: Vec<[f32; 3]> = positions.collect();
+                let primitive_indices: Vec<u32> = match reader.read_indices() {
+                    Some(read) => read.into_u32().collect(),
+                    None => (0..positions.len() as u32).collect(),
+                };
+
+                let vertex_offset = vertices.len();
+                if vertex_offset + positions.len() > u16::MAX as usize + 1 {
+                    return Err(format!(
+                        "glTF mesh has more than {} vertices",
+                        u16::MAX as usize + 1
+                    ));
+                }
+                if let Some(&bad) = primitive_indices
+                    .iter()
+                    .find(|&&index| index as usize >= positions.len())
+                {
+                    return Err(format!("glTF primitive index {} is out of range", bad));
+                }
+
+                let local_indices: Vec<u16> = primitive_indices.iter().map(|&i| i as u16).collect();
+                let normals: Vec<[f32; 3]> = match reader.read_normals() {
+                    Some(normals) => normals.collect(),
+                    None => compute_vertex_normals(&positions, &local_indices),
+                };
+                if normals.len() != positions.len() {
+                    return Err("glTF primitive has mismatched normal count".to_string());
+                }
+
+                let base_color = primitive
+                    .material()
+                    .pbr_metallic_roughness()
+                    .base_color_factor();
+                let base_color = Vec3::new(base_color[0], base_color[1], base_color[2]);
+                let colors: Vec<[f32; 3]> = match reader.read_colors(0) {
+                    Some(colors) => colors.into_rgb_f32().collect(),
+                    None => vec![[1.0; 3]; positions.len()],
+                };
+                if colors.len() != positions.len() {
+                    return Err("glTF primitive has mismatched color count".to_string());
+                }
+
+                for ((position, normal), color) in positions.iter().zip(&normals).zip(&colors) {
+                    let position = transform.transform_point3(Vec3::from(*position));
+                    let normal = (normal_matrix * Vec3::from(*normal)).normalize_or(Vec3::Y);
+                    let color = base_color * Vec3::from(*color);
+                    vertices.push(Vertex::new(position.into(), normal.into(), color.into()));
+                }
+
+                if skin.is_some() {
+                    let (joints, weights) = if skinned {
+                        (
+                            reader
+                                .read_joints(0)
+                                .map(|joints| joints.into_u16().collect())
+                                .unwrap_or_default(),
+                            reader
+                                .read_weights(0)
+                                .map(|weights| weights.into_f32().collect())
+                                .unwrap_or_default(),
+                        )
+                    } else {
+                        (Vec::new(), Vec::new())
+                    };
+                    for i in 0..positions.len() {
+                        skin_vertices.push(match (joints.get(i), weights.get(i)) {
+                            (Some(&joints), Some(&weights)) => {
+                                gltf_skin_vertex(joints, weights, joint_count)?
+                            }
+                            _ => SkinVertex::default(),
+                        });
+                    }
+                }
+
+                for (index, (positions_delta, normals_delta, _)) in
+                    reader.read_morph_targets().enumerate()
+                {
+                    let name = target_names
+                        .get(index)
+                        .cloned()
+                        .unwrap_or_else(|| index.to_string());
+                    let target = match morph_targets.iter().position(|t| t.name == name) {
+                        Some(existing) => &mut morph_targets[existing],
+                        None => {
+                            morph_targets.push(MorphTarget::new(name));
+                            morph_targets.last_mut().unwrap()
+                        }
+                    };
+                    target.pad(vertex_offset);
+
+                    let positions_delta: Vec<[f32; 3]> =
+                        positions_delta.map(Iterator::collect).unwrap_or_default();
+                    let normals_delta: Vec<[f32; 3]> =
+                        normals_delta.map(Iterator::collect).unwrap_or_default();
+                    for i in 0..positions.len() {
+                        let position = positions_delta.get(i).copied().unwrap_or([0.0; 3]);
+                        let normal = normals_delta.get(i).copied().unwrap_or([0.0; 3]);
+                        target
+                            .position_offsets
+                            .push(transform.transform_vector3(Vec3::from(position)).into());
+                        target
+                            .normal_offsets
+                            .push((normal_matrix * Vec3::from(normal)).into());
+                    }
+                }
+                indices.extend(local_indices.iter().map(|&i| i + vertex_offset as u16));
+            }
+        }
+
+        if vertices.is_empty() {
+            return Err("glTF file contains no triangle meshes".to_string());
+        }
+
+        for target in &mut morph_targets {
+            target.pad(vertices.len());
+        }
+        let skin = match skin {
+            Some(skin) => Some(read_gltf_skin(&gltf, &skin, &buffers, skin_vertices)?),
+            None => None,
+        };
+        let mut asset = Self::new(id, vertices, indices);
+        asset.morph_targets = morph_targets;
+        asset.skin = skin;
+        Ok(asset)
+    }
+
+    /// Parse a mesh asset from OBJ, GLB, or glTF bytes. The format comes from
+    /// `name`'s extension (a file name or URL), or the GLB magic number.
+    pub fn from_bytes(id: String, name: &str, bytes: &[u8]) -> Result<Self, String> {
+        let path = name.split(['?', '#']).next().unwrap_or(name);
+        let extension = path
+            .rsplit_once('.')
+            .map(|(_, ext)| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            _ if bytes.starts_with(b"glTF") => Self::from_gltf(id, bytes),
+            Some("gltf" | "glb") => Self::from_gltf(id, bytes),
+            Some("obj") => {
+                let content = std::str::from_utf8(bytes)
+                    .map_err(|e| format!("OBJ file is not valid UTF-8: {}", e))?;
+                Self::from_obj(id, content)
+            }
+            _ => Err(format!(
+                "Unsupported mesh file '{}' (expected .obj, .glb or .gltf)",
+                name
+            )),
+        }
+    }
+
+    /// Load a mesh asset from an OBJ, GLB, or glTF file, by extension.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file(id: String, path: &Path) -> Result<Self, String> {
+        let bytes =
+            std::fs::read(path).map_err(|e| format!("Failed to read mesh {:?}: {}", path, e))?;
+        Self::from_bytes(id, &path.to_string_lossy(), &bytes)
     }
 
     /// Get the number of triangles in the mesh.
@@ -224,16 +586,30 @@ impl MeshAsset {
 ///
 /// Returns a flat array of vertex index pairs: [a0, b0, a1, b1, ...]
 fn extract_edges(indices: &[u16]) -> Vec<u16> {
+    extract_face_edges(indices, &vec![3; indices.len() / 3])
+}
+
+/// Extract unique edges from polygon outlines, where `face_arities` gives each
+/// face's vertex count.
+///
+/// Returns a flat array of vertex index pairs: [a0, b0, a1, b1, ...]
+fn extract_face_edges(indices: &[u16], face_arities: &[u32]) -> Vec<u16> {
     let mut edges: HashSet<(u16, u16)> = HashSet::new();
 
-    // Process each triangle
-    for tri in indices.chunks(3) {
-        if tri.len() != 3 {
+    // Process each face
+    let mut start = 0;
+    for &arity in face_arities {
+        let Some(face) = indices.get(start..start + arity as usize) else {
+            break;
+        };
+        start += arity as usize;
+        if face.len() < 3 {
             continue;
         }
 
-        // Add three edges per triangle, normalized to ensure uniqueness
-        for (a, b) in [(tri[0], tri[1]), (tri[1], tri[2]), (tri[2], tri[0])] {
+        // Add one edge per side, normalized to ensure uniqueness
+        for (i, &a) in face.iter().enumerate() {
+            let b = face[(i + 1) % face.len()];
             let edge = if a < b { (a, b) } else { (b, a) };
             edges.insert(edge);
         }
@@ -305,12 +681,285 @@ fn compute_vertex_normals(positions: &[[f32; 3]], indices: &[u16]) -> Vec<[f32;
     normals
 }
 
+/// Resolve every glTF buffer to bytes: the GLB binary chunk or a base64 data URI.
+fn load_gltf_buffers(gltf: &gltf::Gltf) -> Result<Vec<Vec<u8>>, String> {
+    gltf.buffers()
+        .map(|buffer| {
+            let data = match buffer.source() {
+                gltf::buffer::Source::Bin => gltf.blob.clone().ok_or_else(|| {
+                    "glTF buffer refers to a missing GLB binary chunk".to_string()
+                })?,
+                gltf::buffer::Source::Uri(uri) => {
+                    let encoded = uri
+                        .strip_prefix("data:")
+                        .and_then(|rest| rest.split_once(";base64,"))
+                        .map(|(_, encoded)| encoded)
+                        .ok_or_else(|| {
+                            format!("glTF external buffer '{}' is not supported; use GLB", uri)
+                        })?;
+                    base64::engine::general_purpose::STANDARD
+                        .decode(encoded)
+                        .map_err(|e| format!("Failed to decode glTF buffer: {}", e))?
+                }
+            };
+            if data.len() < buffer.length() {
+                return Err("glTF buffer is shorter than declared".to_string());
+            }
+            Ok(data)
+        })
+        .collect()
+}
+
+/// Check a vertex's joint indices and normalize its weights.
+fn gltf_skin_vertex(
+    joints: [u16; 4],
+    weights: [f32; 4],
+    joint_count: usize,
+) -> Result<SkinVertex, String> {
+    if let Some(&bad) = joints.iter().find(|&&joint| joint as usize >= joint_count) {
+        return Err(format!("glTF vertex joint {} is out of range", bad));
+    }
+    let total: f32 = weights.iter().sum();
+    if total <= 0.0 {
+        return Ok(SkinVertex::default());
+    }
+    Ok(SkinVertex {
+        joints: joints.map(u32::from),
+        weights: weights.map(|weight| weight / total),
+    })
+}
+
+/// Read a skin's joint hierarchy and the animations that move its joints.
+fn read_gltf_skin(
+    gltf: &gltf::Gltf,
+    skin: &gltf::Skin,
+    buffers: &[Vec<u8>],
+    vertices: Vec<SkinVertex>,
+) -> Result<Skin, String> {
+    let joint_nodes: Vec<gltf::Node> = skin.joints().collect();
+    if joint_nodes.is_empty() {
+        return Err("glTF skin has no joints".to_string());
+    }
+    if joint_nodes.len() > MAX_JOINTS {
+        return Err(format!("glTF skin has more than {} joints", MAX_JOINTS));
+    }
+
+    let node_count = gltf.nodes().len();
+    let parents: HashMap<usize, gltf::Node> = gltf
+        .nodes()
+        .flat_map(|node| {
+            node.children()
+                .map(move |child| (child.index(), node.clone()))
+        })
+        .collect();
+    let joint_index: HashMap<usize, usize> = joint_nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.index(), index))
+        .collect();
+
+    // World transform of a non-joint node, from the chain of its ancestors
+    let node_global = |node: &gltf::Node| -> Result<Mat4, String> {
+        let mut global = Mat4::from_cols_array_2d(&node.transform().matrix());
+        let mut current = parents.get(&node.index());
+        for _ in 0..node_count {
+            let Some(parent) = current else {
+                return Ok(global);
+            };
+            global = Mat4::from_cols_array_2d(&parent.transform().matrix()) * global;
+            current = parents.get(&parent.index());
+        }
+        Err("glTF node hierarchy has a cycle".to_string())
+    };
+
+    let inverse_binds: Vec<Mat4> = skin
+        .reader(|buffer| buffers.get(buffer.index()).map(Vec::as_slice))
+        .read_inverse_bind_matrices()
+        .map(|matrices| matrices.map(|m| Mat4::from_cols_array_2d(&m)).collect())
+        .unwrap_or_default();
+
+    let mut joints = Vec::with_capacity(joint_nodes.len());
+    for (index, node) in joint_nodes.iter().enumerate() {
+        let (translation, rotation, scale) = node.transform().decomposed();
+        let parent_node = parents.get(&node.index());
+        let parent = parent_node.and_then(|parent| joint_index.get(&parent.index()).copied());
+        let root_transform = match (parent, parent_node) {
+            (None, Some(parent_node)) => node_global(parent_node)?,
+            _ => Mat4::IDENTITY,
+        };
+        joints.push(Joint {
+            name: node.name().unwrap_or_default().to_string(),
+            parent,
+            root_transform,
+            translation: Vec3::from(translation),
+            rotation: Quat::from_array(rotation),
+            scale: Vec3::from(scale),
+            inverse_bind: inverse_binds.get(index).copied().unwrap_or(Mat4::IDENTITY),
+        });
+    }
+    // Joint parents are node parents, so a tree unless the file has a cycle
+    for index in 0..joints.len() {
+        let mut current = joints[index].parent;
+        for _ in 0..=joints.len() {
+            current = match current {
+                Some(parent) => joints[parent].parent,
+                None => break,
+            };
+        }
+        if current.is_some() {
+            return Err("glTF joint hierarchy has a cycle".to_string());
+        }
+    }
+
+    let mut animations = Vec::new();
+    for (index, animation) in gltf.animations().enumerate() {
+        let mut channels = Vec::new();
+        for channel in animation.channels() {
+            let Some(&joint) = joint_index.get(&channel.target().node().index()) else {
+                continue;
+            };
+            let reader = channel.reader(|buffer| buffers.get(buffer.index()).map(Vec::as_slice));
+            let (Some(inputs), Some(outputs)) = (reader.read_inputs(), reader.read_outputs())
+            else {
+                continue;
+            };
+            let times: Vec<f32> = inputs.collect();
+            let (interpolation, cubic) = match channel.sampler().interpolation() {
+                gltf::animation::Interpolation::Step => (Interpolation::Step, false),
+                gltf::animation::Interpolation::Linear => (Interpolation::Linear, false),
+                // Keyframe values only; the tangents are dropped
+                gltf::animation::Interpolation::CubicSpline => (Interpolation::Linear, true),
+            };
+            let values = match outputs {
+                gltf::animation::util::ReadOutputs::Translations(values) => {
+                    ChannelValues::Translation(keyframe_values(values.map(Vec3::from), cubic))
+                }
+                gltf::animation::util::ReadOutputs::Rotations(values) => ChannelValues::Rotation(
+                    keyframe_values(values.into_f32().map(Quat::from_array), cubic),
+                ),
+                gltf::animation::util::ReadOutputs::Scales(values) => {
+                    ChannelValues::Scale(keyframe_values(values.map(Vec3::from), cubic))
+                }
+                gltf::animation::util::ReadOutputs::MorphTargetWeights(_) => continue,
+            };
+            channels.push(AnimationChannel {
+                joint,
+                interpolation,
+                times,
+                values,
+            });
+        }
+        let duration = channels
+            .iter()
+            .filter_map(|channel| channel.times.last().copied())
+            .fold(0.0, f32::max);
+        animations.push(AnimationClip {
+            name: animation
+                .name()
+                .map(str::to_string)
+                .unwrap_or_else(|| index.to_string()),
+            duration,
+            channels,
+        });
+    }
+
+    Ok(Skin {
+        joints,
+        vertices,
+        animations,
+    })
+}
+
+/// Keyframe values from a sampler's output, skipping cubic spline tangents
+/// (stored as in-tangent, value, out-tangent triples).
+fn keyframe_values<T: Copy>(values: impl Iterator<Item = T>, cubic: bool) -> Vec<T> {
+    let values: Vec<T> = values.collect();
+    if cubic {
+        values.chunks_exact(3).map(|triple| triple[1]).collect()
+    } else {
+        values
+    }
+}
+
+/// Morph target names from a glTF mesh's `extras.targetNames`, the
+/// convention used by Blender and three.js exporters.
+fn gltf_target_names(mesh: &gltf::Mesh) -> Vec<String> {
+    let Some(extras) = mesh.extras() else {
+        return Vec::new();
+    };
+    let Ok(extras) = serde_json::from_str::<serde_json::Value>(extras.get()) else {
+        return Vec::new();
+    };
+    extras
+        .get("targetNames")
+        .and_then(|names| names.as_array())
+        .map(|names| {
+            names
+                .iter()
+                .filter_map(|name| name.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A glTF mesh with its node's world transform and skin.
+type GltfMeshNode<'a> = (gltf::Mesh<'a>, Mat4, Option<gltf::Skin<'a>>);
+
+/// Collect a node's meshes and its descendants' with their world transforms.
+fn collect_gltf_meshes<'a>(
+    node: &gltf::Node<'a>,
+    parent: Mat4,
+    meshes: &mut Vec<GltfMeshNode<'a>>,
+) {
+    let transform = parent * Mat4::from_cols_array_2d(&node.transform().matrix());
+    if let Some(mesh) = node.mesh() {
+        meshes.push((mesh, transform, node.skin()));
+    }
+    for child in node.children() {
+        collect_gltf_meshes(&child, transform, meshes);
+    }
+}
+
+/// Load state of a mesh asset id.
+#[derive(Debug, Clone)]
+pub enum MeshAssetStatus {
+    /// Not registered or requested.
+    Missing,
+    /// Being loaded in the background; not drawn yet.
+    Loading,
+    /// Ready to render.
+    Loaded(Arc<MeshAsset>),
+    /// Loading failed; not retried until re-registered.
+    Failed,
+}
+
+/// Background loads that have finished but not been collected by `poll_loads`,
+/// tagged with the registry generation they were started in.
+type FinishedLoads = Arc<Mutex<Vec<(u64, String, Result<MeshAsset, String>)>>>;
+
 /// Registry for loaded mesh assets.
 ///
 /// Caches assets to avoid redundant loading and allows sharing across instances.
+/// Missing assets are loaded in the background (a thread on native, `fetch` in
+/// the browser); meshes using them are skipped until they are ready.
 #[derive(Debug, Default)]
 pub struct MeshAssetRegistry {
     assets: HashMap<String, Arc<MeshAsset>>,
+    /// Ids that failed to load, so they are not retried every frame.
+    failed: HashSet<String>,
+    /// Ids being loaded in the background.
+    pending: HashSet<String>,
+    /// Results from background loads, collected by `poll_loads`.
+    finished: FinishedLoads,
+    /// Bumped by `clear`, so loads started before it are ignored when they finish.
+    generation: u64,
+    /// Directory relative mesh paths are resolved against (native only).
+    #[cfg(not(target_arch = "wasm32"))]
+    search_dir: Option<PathBuf>,
+    /// Load on the calling thread instead (native only), so offline renders
+    /// see assets from the first frame.
+    #[cfg(not(target_arch = "wasm32"))]
+    blocking_loads: bool,
 }
 
 impl MeshAssetRegistry {
@@ -324,12 +973,29 @@ impl MeshAssetRegistry {
     /// Returns true if the asset was registered successfully.
     pub fn register_from_obj(&mut self, asset_id: &str, obj_content: &str) -> Result<(), String> {
         let asset = MeshAsset::from_obj(asset_id.to_string(), obj_content)?;
+        self.failed.remove(asset_id);
+        self.pending.remove(asset_id);
+        self.assets.insert(asset_id.to_string(), Arc::new(asset));
+        Ok(())
+    }
+
+    /// Register a mesh asset from glTF (GLB or embedded JSON) content.
+    pub fn register_from_gltf(
+        &mut self,
+        asset_id: &str,
+        gltf_content: &[u8],
+    ) -> Result<(), String> {
+        let asset = MeshAsset::from_gltf(asset_id.to_string(), gltf_content)?;
+        self.failed.remove(asset_id);
+        self.pending.remove(asset_id);
         self.assets.insert(asset_id.to_string(), Arc::new(asset));
         Ok(())
     }
 
     /// Register a pre-built mesh asset.
     pub fn register(&mut self, asset: MeshAsset) {
+        self.failed.remove(&asset.id);
+        self.pending.remove(&asset.id);
         self.assets.insert(asset.id.clone(), Arc::new(asset));
     }
 
@@ -338,6 +1004,19 @@ impl MeshAssetRegistry {
         self.assets.get(asset_id).cloned()
     }
 
+    /// Get an asset's load state.
+    pub fn status(&self, asset_id: &str) -> MeshAssetStatus {
+        if let Some(asset) = self.assets.get(asset_id) {
+            MeshAssetStatus::Loaded(Arc::clone(asset))
+        } else if self.pending.contains(asset_id) {
+            MeshAssetStatus::Loading
+        } else if self.failed.contains(asset_id) {
+            MeshAssetStatus::Failed
+        } else {
+            MeshAssetStatus::Missing
+        }
+    }
+
     /// Check if an asset is registered.
     pub fn contains(&self, asset_id: &str) -> bool {
         self.assets.contains_key(asset_id)
@@ -356,12 +1035,144 @@ impl MeshAssetRegistry {
     /// Clear all registered assets.
     pub fn clear(&mut self) {
         self.assets.clear();
+        self.failed.clear();
+        // Loads still in flight are ignored when they finish, even if the same
+        // id is requested again before then
+        self.pending.clear();
+        self.generation += 1;
     }
+
+    /// Set the directory relative mesh paths are resolved against.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_search_dir(&mut self, dir: Option<PathBuf>) {
+        self.search_dir = dir;
+    }
+
+    /// Load on the calling thread instead of in the background.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_blocking_loads(&mut self, blocking: bool) {
+        self.blocking_loads = blocking;
+    }
+
+    /// Start loading any of `ids` that aren't registered yet, treating each id
+    /// as a file path, so `mesh.load("model.obj")` works without a host
+    /// registering it. Failures are logged once and not retried.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_missing<'a>(&mut self, ids: impl IntoIterator<Item = &'a str>) {
+        self.poll_loads();
+        for id in ids {
+            if !self.should_load(id) {
+                continue;
+            }
+            let path = match &self.search_dir {
+                Some(dir) => dir.join(id),
+                None => PathBuf::from(id),
+            };
+            self.pending.insert(id.to_string());
+
+            let id = id.to_string();
+            let generation = self.generation;
+            if self.blocking_loads {
+                let result = MeshAsset::from_file(id.clone(), &path);
+                self.finish_load(generation, id, result);
+                continue;
+            }
+            let finished = Arc::clone(&self.finished);
+            std::thread::spawn(move || {
+                let result = MeshAsset::from_file(id.clone(), &path);
+                if let Ok(mut finished) = finished.lock() {
+                    finished.push((generation, id, result));
+                }
+            });
+        }
+    }
+
+    /// Start fetching any of `ids` that aren't registered yet, treating each id
+    /// as a URL. Failures are logged once and not retried.
+    #[cfg(target_arch = "wasm32")]
+    pub fn load_missing<'a>(&mut self, ids: impl IntoIterator<Item = &'a str>) {
+        self.poll_loads();
+        for id in ids {
+            if !self.should_load(id) {
+                continue;
+            }
+            self.pending.insert(id.to_string());
+
+            let id = id.to_string();
+            let generation = self.generation;
+            let finished = Arc::clone(&self.finished);
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = match fetch_bytes(&id).await {
+                    Ok(bytes) => MeshAsset::from_bytes(id.clone(), &id, &bytes),
+                    Err(e) => Err(format!("Failed to fetch mesh '{}': {}", id, e)),
+                };
+                if let Ok(mut finished) = finished.lock() {
+                    finished.push((generation, id, result));
+                }
+            });
+        }
+    }
+
+    /// Move finished background loads into the registry.
+    pub fn poll_loads(&mut self) {
+        let finished = match self.finished.lock() {
+            Ok(mut finished) => std::mem::take(&mut *finished),
+            Err(_) => return,
+        };
+        for (generation, id, result) in finished {
+            self.finish_load(generation, id, result);
+        }
+    }
+
+    /// Whether `id` is neither available, in flight, nor known to fail.
+    fn should_load(&self, id: &str) -> bool {
+        !self.assets.contains_key(id) && !self.pending.contains(id) && !self.failed.contains(id)
+    }
+
+    /// Record a load result, unless the id was registered or cleared meanwhile.
+    fn finish_load(&mut self, generation: u64, id: String, result: Result<MeshAsset, String>) {
+        if generation != self.generation || !self.pending.remove(&id) {
+            return;
+        }
+        match result {
+            Ok(asset) => {
+                self.assets.insert(id, Arc::new(asset));
+            }
+            Err(e) => {
+                log::warn!("{}", e);
+                self.failed.insert(id);
+            }
+        }
+    }
+}
+
+/// Fetch a URL's body with the browser's `fetch`.
+#[cfg(target_arch = "wasm32")]
+async fn fetch_bytes(url: &str) -> Result<Vec<u8>, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("no window")?;
+    let response: web_sys::Response = JsFuture::from(window.fetch_with_str(url))
+        .await
+        .map_err(|e| format!("{:?}", e))?
+        .dyn_into()
+        .map_err(|_| "fetch did not return a Response".to_string())?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let buffer = response.array_buffer().map_err(|e| format!("{:?}", e))?;
+    let buffer = JsFuture::from(buffer)
+        .await
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(js_sys::Uint8Array::new(&buffer).to_vec())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{headless_or_skip, step};
+    use crate::visualiser::VisualiserState;
 
     #[test]
     fn test_bounding_box_from_vertices() {
@@ -409,6 +1220,453 @@ mod tests {
         assert_eq!(asset.edge_count(), 3);
     }
 
+    const OBJ_CUBE: &str = "
+        v -1 -1 -1
+        v 1 -1 -1
+        v 1 1 -1
+        v -1 1 -1
+        v -1 -1 1
+        v 1 -1 1
+        v 1 1 1
+        v -1 1 1
+        f 1 4 3 2
+        f 5 6 7 8
+        f 1 2 6 5
+        f 3 4 8 7
+        f 2 3 7 6
+        f 1 5 8 4
+    ";
+
+    #[test]
+    fn test_obj_cube_keeps_quad_edges() {
+        let asset = MeshAsset::from_obj("cube.obj".to_string(), OBJ_CUBE).unwrap();
+        assert_eq!(asset.vertices.len(), 8);
+        assert_eq!(asset.triangle_count(), 12);
+        // Quad outlines only, without the triangulation diagonals
+        assert_eq!(asset.edge_count(), 12);
+        assert_eq!(asset.bounds.min, [-1.0, -1.0, -1.0]);
+        assert_eq!(asset.bounds.max, [1.0, 1.0, 1.0]);
+
+        // Missing normals are computed, pointing away from the centre
+        for vertex in &asset.vertices {
+            let dot: f32 = (0..3).map(|i| vertex.normal[i] * vertex.position[i]).sum();
+            assert!(dot > 0.0, "{:?}", vertex);
+        }
+    }
+
+    #[test]
+    fn test_load_missing_by_file_name() {
+        let dir = std::env::temp_dir().join(format!("octoseq-mesh-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("model.obj"), OBJ_CUBE).unwrap();
+
+        let mut registry = MeshAssetRegistry::new();
+        registry.set_search_dir(Some(dir.clone()));
+        registry.set_blocking_loads(true);
+        registry.load_missing(["model.obj", "missing.obj"]);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(registry.get("model.obj").unwrap().vertices.len(), 8);
+        assert!(!registry.contains("missing.obj"));
+        assert!(matches!(
+            registry.status("missing.obj"),
+            MeshAssetStatus::Failed
+        ));
+    }
+
+    #[test]
+    fn test_background_load_is_pending_then_loaded() {
+        let dir = std::env::temp_dir().join(format!("octoseq-mesh-bg-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("model.obj"), OBJ_CUBE).unwrap();
+
+        let mut registry = MeshAssetRegistry::new();
+        registry.set_search_dir(Some(dir.clone()));
+        registry.load_missing(["model.obj"]);
+        assert!(matches!(
+            registry.status("model.obj"),
+            MeshAssetStatus::Loading
+        ));
+        assert!(registry.get("model.obj").is_none());
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while registry.get("model.obj").is_none() && std::time::Instant::now() < deadline {
+            std::thread::sleep(std::time::Duration::from_millis(1));
+            registry.poll_loads();
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        match registry.status("model.obj") {
+            MeshAssetStatus::Loaded(asset) => assert_eq!(asset.vertices.len(), 8),
+            status => panic!("expected loaded asset, got {:?}", status),
+        }
+    }
+
+    #[test]
+    fn test_loads_started_before_clear_are_ignored() {
+        let cube = || MeshAsset::from_obj("model.obj".to_string(), OBJ_CUBE);
+        let mut registry = MeshAssetRegistry::new();
+        let stale = registry.generation;
+        registry.pending.insert("model.obj".to_string());
+        registry.clear();
+
+        // Requested again after the clear, but the earlier load finishes first
+        registry.pending.insert("model.obj".to_string());
+        registry
+            .finished
+            .lock()
+            .unwrap()
+            .push((stale, "model.obj".to_string(), cube()));
+        registry.poll_loads();
+        assert!(matches!(
+            registry.status("model.obj"),
+            MeshAssetStatus::Loading
+        ));
+
+        let current = registry.generation;
+        registry
+            .finished
+            .lock()
+            .unwrap()
+            .push((current, "model.obj".to_string(), cube()));
+        registry.poll_loads();
+        assert!(registry.contains("model.obj"));
+    }
+
+    /// Pack glTF JSON and a binary buffer into a GLB container.
+    fn build_glb(json: &str, bin: &[u8]) -> Vec<u8> {
+        let mut json = json.as_bytes().to_vec();
+        json.resize(json.len().next_multiple_of(4), b' ');
+        let mut bin = bin.to_vec();
+        bin.resize(bin.len().next_multiple_of(4), 0);
+
+        let total = 12 + 8 + json.len() + 8 + bin.len();
+        let mut glb = Vec::with_capacity(total);
+        glb.extend_from_slice(b"glTF");
+        glb.extend_from_slice(&2u32.to_le_bytes());
+        glb.extend_from_slice(&(total as u32).to_le_bytes());
+        glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"JSON");
+        glb.extend_from_slice(&json);
+        glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(b"BIN\0");
+        glb.extend_from_slice(&bin);
+        glb
+    }
+
+    #[test]
+    fn test_gltf_parsing() {
+        // A unit quad in XY, translated up by 1 and colored by its material
+        let positions: [[f32; 3]; 4] = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ];
+        let indices: [u16; 6] = [0, 1, 2, 2, 3, 0];
+        let mut bin: Vec<u8> = bytemuck::cast_slice(&positions).to_vec();
+        bin.extend_from_slice(bytemuck::cast_slice(&indices));
+
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": [{ "mesh": 0, "translation": [0.0, 1.0, 0.0] }],
+            "meshes": [{
+                "primitives": [{ "attributes": { "POSITION": 0 }, "indices": 1, "material": 0 }]
+            }],
+            "materials": [{ "pbrMetallicRoughness": { "baseColorFactor": [1.0, 0.5, 0.25, 1.0] } }],
+            "buffers": [{ "byteLength": 60 }],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": 48 },
+                { "buffer": 0, "byteOffset": 48, "byteLength": 12 }
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0]
+                },
+                { "bufferView": 1, "componentType": 5123, "count": 6, "type": "SCALAR" }
+            ]
+        }"#;
+
+        let asset = MeshAsset::from_gltf("quad".to_string(), &build_glb(json, &bin)).unwrap();
+        assert_eq!(asset.vertices.len(), 4);
+        assert_eq!(asset.indices, vec![0, 1, 2, 2, 3, 0]);
+        assert_eq!(asset.triangle_count(), 2);
+        assert_eq!(asset.edge_count(), 5);
+        assert_eq!(asset.bounds.min, [0.0, 1.0, 0.0]);
+        assert_eq!(asset.bounds.max, [1.0, 2.0, 0.0]);
+        assert_eq!(asset.vertices[0].color, [1.0, 0.5, 0.25]);
+
+        // Normals are computed facing +Z
+        for vertex in &asset.vertices {
+            assert!((vertex.normal[2] - 1.0).abs() < 1e-5, "{:?}", vertex.normal);
+        }
+    }
+
+    #[test]
+    fn test_gltf_vertex_colors_multiply_base_color() {
+        let positions: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let colors: [[f32; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+        let mut bin: Vec<u8> = bytemuck::cast_slice(&positions).to_vec();
+        bin.extend_from_slice(bytemuck::cast_slice(&colors));
+
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "meshes": [{
+                "primitives": [{ "attributes": { "POSITION": 0, "COLOR_0": 1 }, "material": 0 }]
+            }],
+            "materials": [{ "pbrMetallicRoughness": { "baseColorFactor": [0.5, 1.0, 1.0, 1.0] } }],
+            "buffers": [{ "byteLength": 72 }],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+                { "buffer": 0, "byteOffset": 36, "byteLength": 36 }
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0]
+                },
+                { "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3" }
+            ]
+        }"#;
+
+        let asset = MeshAsset::from_gltf("tri".to_string(), &build_glb(json, &bin)).unwrap();
+        assert_eq!(asset.vertices[0].color, [0.5, 0.0, 0.0]);
+        assert_eq!(asset.vertices[1].color, [0.0, 1.0, 0.0]);
+        assert_eq!(asset.vertices[2].color, [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_gltf_morph_target_blending() {
+        // A triangle with an "open" target lifting the top vertex and an unnamed
+        // one pushing every vertex along +Z
+        let positions: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let open: [[f32; 3]; 3] = [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+        let push: [[f32; 3]; 3] = [[0.0, 0.0, 1.0]; 3];
+        let mut bin: Vec<u8> = bytemuck::cast_slice(&positions).to_vec();
+        bin.extend_from_slice(bytemuck::cast_slice(&open));
+        bin.extend_from_slice(bytemuck::cast_slice(&push));
+
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0] }],
+            "nodes": [{ "mesh": 0, "scale": [2.0, 2.0, 2.0] }],
+            "meshes": [{
+                "primitives": [{
+                    "attributes": { "POSITION": 0 },
+                    "targets": [{ "POSITION": 1 }, { "POSITION": 2 }]
+                }],
+                "extras": { "targetNames": ["open"] }
+            }],
+            "buffers": [{ "byteLength": 108 }],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": 36 },
+                { "buffer": 0, "byteOffset": 36, "byteLength": 36 },
+                { "buffer": 0, "byteOffset": 72, "byteLength": 36 }
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0, "componentType": 5126, "count": 3, "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0]
+                },
+                {
+                    "bufferView": 1, "componentType": 5126, "count": 3, "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0], "max": [0.0, 1.0, 0.0]
+                },
+                {
+                    "bufferView": 2, "componentType": 5126, "count": 3, "type": "VEC3",
+                    "min": [0.0, 0.0, 1.0], "max": [0.0, 0.0, 1.0]
+                }
+            ]
+        }"#;
+
+        let asset = MeshAsset::from_gltf("tri".to_string(), &build_glb(json, &bin)).unwrap();
+        let names: Vec<&str> = asset
+            .morph_targets
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["open", "1"]);
+
+        let positions = |weights: &[(&str, f32)]| -> Vec<[f32; 3]> {
+            let weights: Vec<(String, f32)> = weights
+                .iter()
+                .map(|(name, weight)| (name.to_string(), *weight))
+                .collect();
+            asset
+                .morphed_vertices(&weights)
+                .iter()
+                .map(|v| v.position)
+                .collect()
+        };
+
+        // Amount 1 reaches the target shape (with the node's scale applied)
+        assert_eq!(
+            positions(&[("open", 1.0)]),
+            vec![[0.0, 0.0, 0.0], [2.0, 0.0, 0.0], [0.0, 4.0, 0.0]]
+        );
+        assert_eq!(positions(&[("open", 0.5)])[2], [0.0, 3.0, 0.0]);
+
+        // Targets add up; unknown names are ignored
+        assert_eq!(
+            positions(&[("open", 1.0), ("1", 0.5), ("missing", 1.0)]),
+            vec![[0.0, 0.0, 1.0], [2.0, 0.0, 1.0], [0.0, 4.0, 1.0]]
+        );
+        assert_eq!(positions(&[])[2], [0.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_gltf_skin_and_animation() {
+        // A quad whose top edge follows a "tip" joint one unit above "root",
+        // under an armature node; the "lift" clip raises the tip by one unit
+        let positions: [[f32; 3]; 4] = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+        ];
+        let indices: [u16; 6] = [0, 1, 2, 2, 3, 0];
+        let joints: [[u16; 4]; 4] = [[0, 0, 0, 0], [0, 0, 0, 0], [1, 0, 0, 0], [1, 0, 0, 0]];
+        let weights: [[f32; 4]; 4] = [
+            [1.0, 0.0, 0.0, 0.0],
+            [2.0, 0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0, 0.0],
+        ];
+        let inverse_binds: [Mat4; 2] = [
+            Mat4::from_translation(Vec3::new(-1.0, 0.0, 0.0)),
+            Mat4::from_translation(Vec3::new(-1.0, -1.0, 0.0)),
+        ];
+        let times: [f32; 2] = [0.0, 1.0];
+        let translations: [[f32; 3]; 2] = [[0.0, 1.0, 0.0], [0.0, 2.0, 0.0]];
+        let mut bin: Vec<u8> = bytemuck::cast_slice(&positions).to_vec();
+        bin.extend_from_slice(bytemuck::cast_slice(&indices));
+        bin.extend_from_slice(bytemuck::cast_slice(&joints));
+        bin.extend_from_slice(bytemuck::cast_slice(&weights));
+        for matrix in &inverse_binds {
+            bin.extend_from_slice(bytemuck::cast_slice(&matrix.to_cols_array()));
+        }
+        bin.extend_from_slice(bytemuck::cast_slice(&times));
+        bin.extend_from_slice(bytemuck::cast_slice(&translations));
+
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [{ "nodes": [0, 3] }],
+            "nodes": [
+                { "name": "Armature", "translation": [1.0, 0.0, 0.0], "children": [1] },
+                { "name": "root", "children": [2] },
+                { "name": "tip", "translation": [0.0, 1.0, 0.0] },
+                { "mesh": 0, "skin": 0, "translation": [9.0, 9.0, 9.0] }
+            ],
+            "skins": [{ "joints": [1, 2], "inverseBindMatrices": 4 }],
+            "meshes": [{
+                "primitives": [{
+                    "attributes": { "POSITION": 0, "JOINTS_0": 2, "WEIGHTS_0": 3 },
+                    "indices": 1
+                }]
+            }],
+            "animations": [{
+                "name": "lift",
+                "channels": [{ "sampler": 0, "target": { "node": 2, "path": "translation" } }],
+                "samplers": [{ "input": 5, "output": 6 }]
+            }],
+            "buffers": [{ "byteLength": 316 }],
+            "bufferViews": [
+                { "buffer": 0, "byteOffset": 0, "byteLength": 48 },
+                { "buffer": 0, "byteOffset": 48, "byteLength": 12 },
+                { "buffer": 0, "byteOffset": 60, "byteLength": 32 },
+                { "buffer": 0, "byteOffset": 92, "byteLength": 64 },
+                { "buffer": 0, "byteOffset": 156, "byteLength": 128 },
+                { "buffer": 0, "byteOffset": 284, "byteLength": 8 },
+                { "buffer": 0, "byteOffset": 292, "byteLength": 24 }
+            ],
+            "accessors": [
+                {
+                    "bufferView": 0, "componentType": 5126, "count": 4, "type": "VEC3",
+                    "min": [0.0, 0.0, 0.0], "max": [1.0, 1.0, 0.0]
+                },
+                { "bufferView": 1, "componentType": 5123, "count": 6, "type": "SCALAR" },
+                { "bufferView": 2, "componentType": 5123, "count": 4, "type": "VEC4" },
+                { "bufferView": 3, "componentType": 5126, "count": 4, "type": "VEC4" },
+                { "bufferView": 4, "componentType": 5126, "count": 2, "type": "MAT4" },
+                {
+                    "bufferView": 5, "componentType": 5126, "count": 2, "type": "SCALAR",
+                    "min": [0.0], "max": [1.0]
+                },
+                { "bufferView": 6, "componentType": 5126, "count": 2, "type": "VEC3" }
+            ]
+        }"#;
+
+        let asset = MeshAsset::from_gltf("arm".to_string(), &build_glb(json, &bin)).unwrap();
+        // The skinned mesh's own node transform is ignored
+        assert_eq!(asset.bounds.max, [1.0, 1.0, 0.0]);
+
+        let skin = asset.skin.as_ref().unwrap();
+        let names: Vec<&str> = skin.joints.iter().map(|j| j.name.as_str()).collect();
+        assert_eq!(names, vec!["root", "tip"]);
+        assert_eq!(skin.joints[0].parent, None);
+        assert_eq!(skin.joints[1].parent, Some(0));
+        assert_eq!(
+            skin.joints[0].root_transform,
+            Mat4::from_translation(Vec3::X)
+        );
+        assert_eq!(
+            skin.vertices[1],
+            SkinVertex {
+                joints: [0; 4],
+                weights: [1.0, 0.0, 0.0, 0.0],
+            }
+        );
+
+        // The rest pose matches the bind pose
+        for bone in skin.bone_matrices(None) {
+            assert!(bone.abs_diff_eq(Mat4::IDENTITY, 1e-5), "{bone:?}");
+        }
+
+        let clip = skin.animation("lift").unwrap();
+        assert_eq!(clip.duration, 1.0);
+        let bones = skin.bone_matrices(Some((clip, 1.0)));
+        let posed = crate::skinning::skin_vertices(&asset.vertices, &skin.vertices, &bones);
+        let posed: Vec<[f32; 3]> = posed.iter().map(|v| v.position).collect();
+        assert_eq!(
+            posed,
+            vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 2.0, 0.0],
+                [0.0, 2.0, 0.0]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gltf_external_buffer_rejected() {
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "buffers": [{ "byteLength": 60, "uri": "quad.bin" }]
+        }"#;
+
+        let err = MeshAsset::from_gltf("quad".to_string(), json.as_bytes()).unwrap_err();
+        assert!(err.contains("quad.bin"), "{}", err);
+    }
+
+    #[test]
+    fn test_obj_vertex_colors() {
+        let obj = "v 0 0 0 1 0 0\nv 1 0 0 0 0 1\nv 0 1 0 1 0 0\nf 1 2 3";
+        let asset = MeshAsset::from_obj("tri".to_string(), obj).unwrap();
+        assert_eq!(asset.vertices[0].color, [1.0, 0.0, 0.0]);
+        assert_eq!(asset.vertices[1].color, [0.0, 0.0, 1.0]);
+
+        // Without colors, vertices are shaded by position
+        let obj = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3";
+        let asset = MeshAsset::from_obj("tri".to_string(), obj).unwrap();
+        assert_eq!(asset.vertices[1].color, [1.0, 0.5, 0.5]);
+    }
+
     #[test]
     fn test_registry() {
         let mut registry = MeshAssetRegistry::new();
@@ -425,4 +1683,55 @@ mod tests {
         registry.unregister("test");
         assert!(!registry.contains("test"));
     }
+
+    #[test]
+    fn test_vertex_colors_are_tinted_by_instance_color() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // A quad facing the camera, red on the left and blue on the right
+        let mut state = VisualiserState::new();
+        let obj = "v -1 -1 0 1 0 0\nv 1 -1 0 0 0 1\nv 1 1 0 0 0 1\nv -1 1 0 1 0 0\n\
+                   vn 0 0 1\nf 1//1 2//1 3//1 4//1";
+        state.register_mesh_asset("gradient", obj).unwrap();
+        let script = r#"
+            let quad = mesh.load("gradient");
+            quad.color = #{ r: 1.0, g: 1.0, b: 0.5, a: 1.0 };
+
+            fn init(ctx) {
+                camera.position = #{ x: 0.0, y: 0.0, z: 3.0 };
+                camera.lookAt(#{ x: 0.0, y: 0.0, z: 0.0 });
+                scene.add(quad);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        assert!(state.load_script(script));
+        step(&mut state, 1.0 / 60.0);
+        headless.render(&state);
+        let hdr = headless.read_scene_hdr();
+
+        let row: Vec<[f32; 4]> = (0..64).map(|x| hdr[32 * 64 + x]).collect();
+        // The quad has no green, unlike the grey background
+        let covered: Vec<usize> = (0..64).filter(|&x| row[x][1] < 1e-3).collect();
+        let (left, right) = (covered[0], *covered.last().unwrap());
+        assert!(right - left > 16, "quad should span the row: {:?}", covered);
+
+        // Red fades into blue across the face
+        let reds: Vec<f32> = row[left..=right].iter().map(|p| p[0]).collect();
+        let blues: Vec<f32> = row[left..=right].iter().map(|p| p[2]).collect();
+        assert!(reds.windows(2).all(|w| w[1] <= w[0] + 1e-4), "{:?}", reds);
+        assert!(blues.windows(2).all(|w| w[1] + 1e-4 >= w[0]), "{:?}", blues);
+        assert!(row[left][0] > row[left][2] * 4.0, "left {:?}", row[left]);
+        assert!(
+            row[right][2] > row[right][0] * 4.0,
+            "right {:?}",
+            row[right]
+        );
+
+        // The instance color halves blue relative to red
+        let ratio = row[right][2] / row[left][0];
+        assert!((ratio - 0.5).abs() < 0.1, "blue/red ratio {}", ratio);
+    }
 }
@@ -0,0 +1,436 @@
+//! MIDI file loading for native renders (`--midi <file.mid>`).
+//!
+//! Note-on events become authored event streams and held notes become
+//! velocity signals, so visuals can follow a track's MIDI exactly:
+//!
+//! - `inputs.customEvents["midi:ch1"]`: every note-on on channel 1 (the
+//!   note number is the event's cluster id), and
+//!   `inputs.customEvents["midi:ch1:36"]`: note 36 on channel 1 only. Event
+//!   weight is the note-on velocity (0-1).
+//! - `inputs.midi.ch1.note36`: note 36's velocity (0-1) while held, 0
+//!   otherwise. `inputs.midi.ch1.events` and `inputs.midi.ch1.note36Events`
+//!   are shortcuts for the event streams above.
+//!
+//! Channels are numbered 1-16, as in DAWs. Tick times are converted to
+//! seconds through the file's tempo map (tempo changes from every track), or
+//! directly for SMPTE-timed files. Velocity signals are stored as composed
+//! signals under the per-note stream name, without being listed in
+//! `inputs.composedSignals`.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+use std::rc::Rc;
+
+use midly::{MetaMessage, MidiMessage, Smf, Timing, TrackEventKind};
+
+use crate::event_rhai::store_authored_event_stream;
+use crate::event_stream::{Event, EventStream, PickEventsOptions};
+use crate::input::InputSignal;
+use crate::visualiser::VisualiserState;
+
+/// Sample rate (Hz) of the note velocity signals.
+pub const MIDI_SIGNAL_RATE: f32 = 100.0;
+
+/// Tempo assumed until the first tempo event (120 BPM), per the SMF spec.
+const DEFAULT_MICROS_PER_BEAT: f64 = 500_000.0;
+
+/// Event source recorded on MIDI note events.
+const MIDI_EVENT_SOURCE: &str = "midi";
+
+/// One note, from note-on to note-off, in seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MidiNote {
+    /// Channel, 1-16.
+    pub channel: u8,
+    /// Note number, 0-127 (60 = middle C).
+    pub key: u8,
+    /// Note-on velocity, 1-127.
+    pub velocity: u8,
+    pub start: f32,
+    pub end: f32,
+}
+
+/// A parsed MIDI file: its notes sorted by start time.
+pub struct LoadedMidi {
+    pub notes: Vec<MidiNote>,
+    /// Time of the last event in seconds.
+    pub duration: f32,
+}
+
+/// Read and parse a standard MIDI file. See [`parse_midi`].
+pub fn load_midi_file(path: &Path) -> Result<LoadedMidi, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Failed to read MIDI file {:?}: {}", path, e))?;
+    parse_midi(&bytes).map_err(|e| format!("Invalid MIDI file {:?}: {}", path, e))
+}
+
+/// Parse a standard MIDI file into timed notes.
+///
+/// A note-on with velocity 0 counts as a note-off. Overlapping notes on the
+/// same key close first-in first-out; notes still held at the end of the
+/// file end with it.
+pub fn parse_midi(bytes: &[u8]) -> Result<LoadedMidi, String> {
+    let smf = Smf::parse(bytes).map_err(|e| e.to_string())?;
+
+    // (tick, track, channel, key, velocity); velocity 0 is a note-off.
+    let mut note_events: Vec<(u64, usize, u8, u8, u8)> = Vec::new();
+    let mut tempo_changes: Vec<(u64, f64)> = Vec::new();
+    let mut last_tick = 0u64;
+    for (track_index, track) in smf.tracks.iter().enumerate() {
+        let mut tick = 0u64;
+        for event in track {
+            tick += event.delta.as_int() as u64;
+            match event.kind {
+                TrackEventKind::Midi { channel, message } => {
+                    let (key, velocity) = match message {
+                        MidiMessage::NoteOn { key, vel } => (key.as_int(), vel.as_int()),
+                        MidiMessage::NoteOff { key, .. } => (key.as_int(), 0),
+                        _ => continue,
+                    };
+                    note_events.push((tick, track_index, channel.as_int() + 1, key, velocity));
+                }
+                TrackEventKind::Meta(MetaMessage::Tempo(micros)) => {
+                    tempo_changes.push((tick, micros.as_int() as f64));
+                }
+                _ => {}
+            }
+        }
+        last_tick = last_tick.max(tick);
+    }
+
+    let tempo_map = TempoMap::new(smf.header.timing, tempo_changes);
+    if !tempo_map.is_valid() {
+        return Err("invalid timing division".to_string());
+    }
+
+    // Merge tracks by tick, keeping each track's own event order.
+    note_events.sort_by_key(|&(tick, track, ..)| (tick, track));
+
+    let mut open: BTreeMap<(u8, u8), VecDeque<(f32, u8)>> = BTreeMap::new();
+    let mut notes = Vec::new();
+    for (tick, _, channel, key, velocity) in note_events {
+        let time = tempo_map.seconds(tick);
+        if velocity > 0 {
+            open.entry((channel, key))
+                .or_default()
+                .push_back((time, velocity));
+        } else if let Some((start, velocity)) = open
+            .get_mut(&(channel, key))
+            .and_then(|held| held.pop_front())
+        {
+            notes.push(MidiNote {
+                channel,
+                key,
+                velocity,
+                start,
+                end: time,
+            });
+        }
+    }
+
+    let duration = tempo_map.seconds(last_tick);
+    for ((channel, key), held) in open {
+        notes.extend(held.into_iter().map(|(start, velocity)| MidiNote {
+            channel,
+            key,
+            velocity,
+            start,
+            end: duration,
+        }));
+    }
+    notes.sort_by(|a, b| {
+        a.start
+            .total_cmp(&b.start)
+            .then((a.channel, a.key).cmp(&(b.channel, b.key)))
+    });
+
+    Ok(LoadedMidi { notes, duration })
+}
+
+/// Event stream name for a channel's note-ons, or one note's when `key` is
+/// given: `midi:ch1`, `midi:ch1:36`. Also the composed signal name of the
+/// note's velocity signal.
+pub fn midi_stream_name(channel: u8, key: Option<u8>) -> String {
+    match key {
+        Some(key) => format!("midi:ch{}:{}", channel, key),
+        None => format!("midi:ch{}", channel),
+    }
+}
+
+impl LoadedMidi {
+    /// Distinct (channel, note) pairs that are played, sorted.
+    pub fn played_notes(&self) -> Vec<(u8, u8)> {
+        let mut played: Vec<(u8, u8)> = self.notes.iter().map(|n| (n.channel, n.key)).collect();
+        played.sort_unstable();
+        played.dedup();
+        played
+    }
+
+    /// Note-on event streams per channel, each followed by its notes' streams.
+    pub fn event_streams(&self) -> Vec<(String, EventStream)> {
+        let mut streams: BTreeMap<(u8, Option<u8>), Vec<Event>> = BTreeMap::new();
+        for note in &self.notes {
+            let event = Event::new(note.start, note.velocity as f32 / 127.0)
+                .with_cluster(note.key as u32)
+                .with_source(MIDI_EVENT_SOURCE);
+            streams
+                .entry((note.channel, None))
+                .or_default()
+                .push(event.clone());
+            streams
+                .entry((note.channel, Some(note.key)))
+                .or_default()
+                .push(event);
+        }
+        streams
+            .into_iter()
+            .map(|((channel, key), events)| {
+                let name = midi_stream_name(channel, key);
+                let stream = EventStream::new(events, name.clone(), PickEventsOptions::default());
+                (name, stream)
+            })
+            .collect()
+    }
+
+    /// Velocity (0-1) while held, per played note, at [`MIDI_SIGNAL_RATE`].
+    /// Every note covers at least one sample, so short hits still register.
+    pub fn velocity_signals(&self) -> Vec<((u8, u8), Rc<InputSignal>)> {
+        let len = (self.duration * MIDI_SIGNAL_RATE).ceil() as usize + 1;
+        let mut envelopes: BTreeMap<(u8, u8), Vec<f32>> = BTreeMap::new();
+        for note in &self.notes {
+            let envelope = envelopes
+                .entry((note.channel, note.key))
+                .or_insert_with(|| vec![0.0; len]);
+            let start = ((note.start * MIDI_SIGNAL_RATE).round() as usize).min(len - 1);
+            let end = ((note.end * MIDI_SIGNAL_RATE).round() as usize).clamp(start + 1, len);
+            let velocity = note.velocity as f32 / 127.0;
+            for value in &mut envelope[start..end] {
+                *value = value.max(velocity);
+            }
+        }
+        envelopes
+            .into_iter()
+            .map(|(note, values)| (note, Rc::new(InputSignal::new(values, MIDI_SIGNAL_RATE))))
+            .collect()
+    }
+}
+
+/// Register the file's event streams as authored streams and its velocity
+/// signals as composed signals, and make `inputs.midi` available. Call after
+/// any package has been applied (which resets both) and before `load_script`.
+pub fn apply_midi(midi: &LoadedMidi, state: &mut VisualiserState) {
+    for (name, stream) in midi.event_streams() {
+        store_authored_event_stream(name, stream);
+    }
+    for ((channel, key), signal) in midi.velocity_signals() {
+        state.push_composed_signal(&midi_stream_name(channel, Some(key)), signal);
+    }
+    state.set_available_midi_notes(midi.played_notes());
+}
+
+/// Tick to seconds conversion.
+enum TempoMap {
+    /// Ticks per beat, and tempo segments as (start tick, start seconds,
+    /// microseconds per beat).
+    Metrical {
+        ticks_per_beat: f64,
+        segments: Vec<(u64, f64, f64)>,
+    },
+    /// Ticks per second.
+    Timecode(f64),
+}
+
+impl TempoMap {
+    fn new(timing: Timing, mut tempo_changes: Vec<(u64, f64)>) -> Self {
+        match timing {
+            Timing::Metrical(ticks_per_beat) => {
+                let ticks_per_beat = ticks_per_beat.as_int() as f64;
+                tempo_changes.sort_by_key(|&(tick, _)| tick);
+                let mut segments = vec![(0u64, 0.0f64, DEFAULT_MICROS_PER_BEAT)];
+                for (tick, micros) in tempo_changes {
+                    let (start_tick, start_seconds, current) = *segments.last().unwrap();
+                    let seconds =
+                        start_seconds + (tick - start_tick) as f64 * current / 1e6 / ticks_per_beat;
+                    if tick == start_tick {
+                        segments.pop();
+                    }
+                    segments.push((tick, seconds, micros));
+                }
+                TempoMap::Metrical {
+                    ticks_per_beat,
+                    segments,
+                }
+            }
+            Timing::Timecode(fps, subframes) => {
+                TempoMap::Timecode(fps.as_f32() as f64 * subframes as f64)
+            }
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            TempoMap::Metrical { ticks_per_beat, .. } => *ticks_per_beat > 0.0,
+            TempoMap::Timecode(ticks_per_second) => *ticks_per_second > 0.0,
+        }
+    }
+
+    fn seconds(&self, tick: u64) -> f32 {
+        match self {
+            TempoMap::Metrical {
+                ticks_per_beat,
+                segments,
+            } => {
+                let index = segments.partition_point(|&(start, ..)| start <= tick) - 1;
+                let (start_tick, start_seconds, micros) = segments[index];
+                (start_seconds + (tick - start_tick) as f64 * micros / 1e6 / ticks_per_beat) as f32
+            }
+            TempoMap::Timecode(ticks_per_second) => (tick as f64 / ticks_per_second) as f32,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_rhai::clear_authored_event_streams;
+    use crate::input::{BandSignalMap, SignalMap};
+    use midly::num::{u15, u24, u28, u4, u7};
+    use midly::{Format, Header, TrackEvent};
+    use std::collections::HashMap;
+
+    fn note(delta: u32, channel: u8, key: u8, vel: u8) -> TrackEvent<'static> {
+        let (key, vel) = (u7::new(key), u7::new(vel));
+        TrackEvent {
+            delta: u28::new(delta),
+            kind: TrackEventKind::Midi {
+                channel: u4::new(channel),
+                message: if vel.as_int() > 0 {
+                    MidiMessage::NoteOn { key, vel }
+                } else {
+                    MidiMessage::NoteOff { key, vel }
+                },
+            },
+        }
+    }
+
+    fn meta(delta: u32, message: MetaMessage<'static>) -> TrackEvent<'static> {
+        TrackEvent {
+            delta: u28::new(delta),
+            kind: TrackEventKind::Meta(message),
+        }
+    }
+
+    /// Format 1 file at 480 ticks per beat: a tempo track at 120 BPM that
+    /// drops to 60 BPM at beat 2, and a kick (channel 1, note 36) on beats
+    /// 1-3 plus a snare (channel 10, note 38) on beat 2.
+    fn tiny_midi() -> Vec<u8> {
+        let tempo_track = vec![
+            meta(0, MetaMessage::Tempo(u24::new(500_000))),
+            meta(480, MetaMessage::Tempo(u24::new(1_000_000))),
+            meta(0, MetaMessage::EndOfTrack),
+        ];
+        let note_track = vec![
+            note(0, 0, 36, 127),
+            note(240, 0, 36, 0),
+            note(240, 0, 36, 64),
+            note(0, 9, 38, 100),
+            // A note-on with velocity 0 ends the snare.
+            note(120, 9, 38, 0),
+            note(120, 0, 36, 0),
+            note(240, 0, 36, 127),
+            note(240, 0, 36, 0),
+            meta(0, MetaMessage::EndOfTrack),
+        ];
+        let mut smf = Smf::new(Header::new(
+            Format::Parallel,
+            Timing::Metrical(u15::new(480)),
+        ));
+        smf.tracks = vec![tempo_track, note_track];
+        let mut bytes = Vec::new();
+        smf.write_std(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_midi_file_note_times_follow_tempo_map() {
+        let path = std::env::temp_dir().join(format!("octoseq-midi-{}.mid", std::process::id()));
+        std::fs::write(&path, tiny_midi()).unwrap();
+        let midi = load_midi_file(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        // Beat 1 at 120 BPM (0.5s), then beats at 60 BPM (1s each).
+        let times: Vec<(u8, u8, f32, f32)> = midi
+            .notes
+            .iter()
+            .map(|n| (n.channel, n.key, n.start, n.end))
+            .collect();
+        let expected = [
+            (1, 36, 0.0, 0.25),
+            (1, 36, 0.5, 1.0),
+            (10, 38, 0.5, 0.75),
+            (1, 36, 1.5, 2.0),
+        ];
+        assert_eq!(times.len(), expected.len());
+        for (actual, expected) in times.iter().zip(expected) {
+            assert_eq!((actual.0, actual.1), (expected.0, expected.1));
+            assert!((actual.2 - expected.2).abs() < 1e-6, "{:?}", actual);
+            assert!((actual.3 - expected.3).abs() < 1e-6, "{:?}", actual);
+        }
+        assert!((midi.duration - 2.0).abs() < 1e-6);
+
+        let streams = midi.event_streams();
+        let names: Vec<&str> = streams.iter().map(|(n, _)| n.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["midi:ch1", "midi:ch1:36", "midi:ch10", "midi:ch10:38"]
+        );
+        let kicks = &streams[1].1;
+        assert_eq!(kicks.len(), 3);
+        assert!((kicks.get(1).unwrap().weight - 64.0 / 127.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_midi_namespace_reads_held_note_velocity() {
+        let midi = parse_midi(&tiny_midi()).unwrap();
+
+        clear_authored_event_streams();
+        let mut state = VisualiserState::new();
+        apply_midi(&midi, &mut state);
+        state
+            .try_load_script(
+                r#"
+                let cube;
+                fn init(ctx) {
+                    cube = mesh.cube();
+                    scene.add(cube);
+                }
+                fn update(dt, frame) {
+                    cube.position.x = inputs.midi.ch1.note36;
+                    cube.position.y = inputs.midi.ch10.note38;
+                    cube.position.z = inputs.midi.ch1.events.len()
+                        + inputs.customEvents["midi:ch10:38"].len();
+                }
+            "#,
+            )
+            .unwrap();
+        clear_authored_event_streams();
+
+        let signals: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        let mut position_at = |time: f32| {
+            state.set_time(time - 0.05);
+            state.update(0.05, None, None, &signals, &bands, &signals, None);
+            let (_, cube) = state.scene_graph().scene_entities().next().unwrap();
+            cube.transform().position
+        };
+
+        let held = position_at(0.7);
+        assert!((held.x - 64.0 / 127.0).abs() < 1e-3, "{}", held.x);
+        assert!((held.y - 100.0 / 127.0).abs() < 1e-3, "{}", held.y);
+        assert!((held.z - 4.0).abs() < 1e-6);
+
+        let released = position_at(1.2);
+        assert!(released.x.abs() < 1e-6, "{}", released.x);
+        assert!(released.y.abs() < 1e-6, "{}", released.y);
+    }
+}
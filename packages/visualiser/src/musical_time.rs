@@ -50,6 +50,37 @@ impl MusicalTimeStructure {
     pub fn is_empty(&self) -> bool {
         self.segments.is_empty()
     }
+
+    /// Build a constant-tempo structure: one segment covering `[0, duration)`
+    /// with its first beat at `phase_offset` seconds.
+    ///
+    /// `source` is recorded as the segment provenance (e.g. "cli", "auto").
+    pub fn constant(
+        bpm: f32,
+        phase_offset: f32,
+        beats_per_bar: Option<f32>,
+        duration: f32,
+        source: &str,
+    ) -> Self {
+        Self {
+            segments: vec![MusicalTimeSegment {
+                id: format!("{}-tempo", source),
+                bpm,
+                phase_offset,
+                start_time: 0.0,
+                end_time: duration.max(0.0),
+                confidence: None,
+                beats_per_bar,
+                provenance: MusicalTimeProvenance {
+                    source: source.to_string(),
+                    source_hypothesis_id: None,
+                    promoted_at: String::new(),
+                    user_nudge: None,
+                },
+            }],
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for MusicalTimeStructure {
@@ -85,6 +116,11 @@ pub struct MusicalTimeSegment {
     /// Confidence score frozen at lock time (optional, for display).
     pub confidence: Option<f32>,
 
+    /// Beats per bar (time signature numerator). Authored segments carry
+    /// none, in which case [`DEFAULT_BEATS_PER_BAR`] (4/4) is assumed.
+    #[serde(default)]
+    pub beats_per_bar: Option<f32>,
+
     /// Provenance metadata.
     pub provenance: MusicalTimeProvenance,
 }
@@ -129,6 +165,71 @@ impl MusicalTimeSegment {
         let period = 60.0 / self.bpm;
         self.phase_offset + beat_index as f32 * period
     }
+
+    /// Beats per bar, defaulting to 4/4.
+    pub fn beats_per_bar(&self) -> f32 {
+        self.beats_per_bar.unwrap_or(DEFAULT_BEATS_PER_BAR)
+    }
+
+    /// Continuous bar position at a given time.
+    ///
+    /// Bars are counted from the phase offset, which is taken as a downbeat:
+    /// bar 0 starts at `phase_offset` and each bar spans `beats_per_bar`
+    /// beats. Negative before the first downbeat.
+    pub fn bar_position_at(&self, time: f32) -> f32 {
+        self.seconds_to_beats(time - self.phase_offset) / self.beats_per_bar()
+    }
+
+    /// Get the time of a specific bar's downbeat.
+    pub fn bar_time(&self, bar_index: i32) -> f32 {
+        self.phase_offset + self.beats_to_seconds(bar_index as f32 * self.beats_per_bar())
+    }
+}
+
+/// A time signature such as `3/4` or `6/8`.
+///
+/// Only the numerator affects timing (beats per bar); BPM always counts
+/// beats, whatever the note value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeSignature {
+    /// Beats per bar (numerator).
+    pub beats_per_bar: u32,
+    /// Note value of one beat (denominator): 1, 2, 4, 8, 16 or 32.
+    pub beat_unit: u32,
+}
+
+impl TimeSignature {
+    /// Parse `"<beats>/<unit>"`, e.g. `"3/4"`.
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let (beats, unit) = s
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| format!("Invalid time signature '{}': expected e.g. 3/4", s))?;
+        let beats_per_bar: u32 = beats
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid time signature '{}': bad beat count", s))?;
+        let beat_unit: u32 = unit
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid time signature '{}': bad beat unit", s))?;
+        if !(1..=32).contains(&beats_per_bar) {
+            return Err(format!(
+                "Invalid time signature '{}': beats per bar must be 1-32",
+                s
+            ));
+        }
+        if !matches!(beat_unit, 1 | 2 | 4 | 8 | 16 | 32) {
+            return Err(format!(
+                "Invalid time signature '{}': beat unit must be 1, 2, 4, 8, 16 or 32",
+                s
+            ));
+        }
+        Ok(Self {
+            beats_per_bar,
+            beat_unit,
+        })
+    }
 }
 
 /// Provenance metadata for a musical time segment.
@@ -170,6 +271,9 @@ pub struct BeatPosition {
 /// Default BPM used when no musical time is available.
 pub const DEFAULT_BPM: f32 = 120.0;
 
+/// Beats per bar. Segments carry no time signature, so 4/4 is assumed.
+pub const DEFAULT_BEATS_PER_BAR: f32 = 4.0;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +286,7 @@ mod tests {
             start_time: 0.0,
             end_time: 10.0,
             confidence: Some(0.9),
+            beats_per_bar: None,
             provenance: MusicalTimeProvenance {
                 source: "test".to_string(),
                 source_hypothesis_id: None,
@@ -257,6 +362,23 @@ mod tests {
         assert!((segment.beat_time(-1) - 0.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_bar_position_follows_downbeat_and_meter() {
+        // 3/4 at 120 BPM with the first downbeat at 0.5s: bars every 1.5s.
+        let mut segment = make_test_segment();
+        segment.beats_per_bar = Some(3.0);
+
+        assert!((segment.bar_time(0) - 0.5).abs() < 0.001);
+        assert!((segment.bar_time(1) - 2.0).abs() < 0.001);
+        assert!((segment.bar_time(2) - 3.5).abs() < 0.001);
+
+        assert!((segment.bar_position_at(0.5) - 0.0).abs() < 0.001);
+        assert!((segment.bar_position_at(1.25) - 0.5).abs() < 0.001);
+        assert!((segment.bar_position_at(2.0) - 1.0).abs() < 0.001);
+        // Before the first downbeat the position is negative (bar -1).
+        assert!((segment.bar_position_at(0.0) + 1.0 / 3.0).abs() < 0.001);
+    }
+
     #[test]
     fn test_musical_time_structure() {
         let structure = MusicalTimeStructure {
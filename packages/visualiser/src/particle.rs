@@ -6,6 +6,7 @@
 use std::sync::Arc;
 
 use crate::event_stream::Event;
+use crate::material::BlendMode;
 use crate::scene_graph::{Transform, Vec3};
 use crate::signal::{EasingFunction, EnvelopeShape, Signal};
 
@@ -90,6 +91,8 @@ pub enum StreamMode {
 pub struct ParticleConfig {
     /// Maximum number of live instances.
     pub max_instances: usize,
+    /// What to do when spawning would exceed `max_instances`.
+    pub overflow: OverflowPolicy,
     /// Particle lifespan in beats.
     pub lifetime_beats: f32,
     /// Base color [r, g, b, a].
@@ -104,12 +107,31 @@ pub struct ParticleConfig {
     pub variation: VariationConfig,
     /// Material ID override. If None, inherits from parent mesh.
     pub material_id: Option<String>,
+    /// Per-particle spin configuration.
+    pub rotation: RotationConfig,
+    /// Scale multiplier keyframes over normalized age, sorted by `t`.
+    /// Empty means a constant multiplier of 1.0.
+    pub size_over_life: Vec<SizeStop>,
+    /// Particle velocity in units per second. Only orients stretched billboards;
+    /// it does not move particles.
+    pub velocity: Vec3,
+    /// Billboard stretch factor along velocity. 0.0 keeps billboards camera-facing.
+    pub stretch: f32,
+    /// Soft-particle fade distance in world units. Billboards fade out as they approach
+    /// scene geometry behind them. 0.0 disables the fade. Needs a scene depth buffer,
+    /// which the renderer does not write yet, so it has no visible effect for now.
+    pub soft_fade: f32,
+    /// How particles composite onto the scene.
+    pub blend: ParticleBlend,
+    /// Sprite texture for billboards. None draws soft circles.
+    pub sprite: Option<SpriteSheet>,
 }
 
 impl Default for ParticleConfig {
     fn default() -> Self {
         Self {
             max_instances: 1000,
+            overflow: OverflowPolicy::default(),
             lifetime_beats: 1.0,
             base_color: [1.0, 1.0, 1.0, 1.0],
             base_scale: 0.1,
@@ -117,10 +139,125 @@ impl Default for ParticleConfig {
             envelope: ParticleEnvelope::default(),
             variation: VariationConfig::default(),
             material_id: None,
+            rotation: RotationConfig::default(),
+            size_over_life: Vec::new(),
+            velocity: Vec3::new(0.0, 0.0, 0.0),
+            stretch: 0.0,
+            soft_fade: 0.0,
+            blend: ParticleBlend::default(),
+            sprite: None,
         }
     }
 }
 
+/// A size-over-lifetime keyframe.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SizeStop {
+    /// Normalized age (0.0 = spawn, 1.0 = end of lifetime).
+    pub t: f32,
+    /// Scale multiplier at this age.
+    pub size: f32,
+}
+
+/// Sprite texture (optionally an animated sheet) mapped onto billboard particles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpriteSheet {
+    /// Sprite asset id (a file path on native builds).
+    pub asset_id: String,
+    /// Columns in the sheet grid.
+    pub cols: u32,
+    /// Rows in the sheet grid.
+    pub rows: u32,
+    /// Number of animation frames, read left-to-right, top-to-bottom.
+    pub frames: u32,
+    /// Playback rate in frames per second. None plays the sheet once over the
+    /// particle's lifetime.
+    pub fps: Option<f32>,
+}
+
+impl SpriteSheet {
+    /// A single, non-animated sprite.
+    pub fn single(asset_id: String) -> Self {
+        Self {
+            asset_id,
+            cols: 1,
+            rows: 1,
+            frames: 1,
+            fps: None,
+        }
+    }
+}
+
+/// Blend mode for a particle system.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ParticleBlend {
+    /// Standard alpha blending (smoke, dust). Drawn back-to-front.
+    #[default]
+    Alpha,
+    /// Additive blending (sparks, fire). Order-independent, so never sorted.
+    Add,
+}
+
+impl ParticleBlend {
+    /// Parse from a script string ("alpha" or "add").
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "alpha" | "normal" => Some(ParticleBlend::Alpha),
+            "add" | "additive" => Some(ParticleBlend::Add),
+            _ => None,
+        }
+    }
+
+    /// The material blend mode whose blend state the particle pipeline uses.
+    pub fn blend_mode(&self) -> BlendMode {
+        match self {
+            ParticleBlend::Alpha => BlendMode::AlphaBlend,
+            ParticleBlend::Add => BlendMode::Additive,
+        }
+    }
+
+    /// Whether instances must be depth-sorted to composite correctly.
+    pub fn needs_sorting(&self) -> bool {
+        matches!(self, ParticleBlend::Alpha)
+    }
+}
+
+/// Policy applied when a system is at its live-instance cap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OverflowPolicy {
+    /// Evict the oldest live instances to make room.
+    #[default]
+    DropOldest,
+    /// Discard new spawns until instances expire.
+    DropNew,
+}
+
+impl OverflowPolicy {
+    /// Parse from a script string ("oldest" or "new").
+    pub fn from_str(s: &str) -> Option<Self> {
+        match s {
+            "oldest" | "drop_oldest" | "dropOldest" => Some(OverflowPolicy::DropOldest),
+            "new" | "drop_new" | "dropNew" => Some(OverflowPolicy::DropNew),
+            _ => None,
+        }
+    }
+}
+
+/// Per-particle spin configuration.
+///
+/// Billboards spin in screen space; mesh particles spin about their local Z axis
+/// on top of any `rotation_variation` orientation.
+#[derive(Clone, Debug, Default)]
+pub struct RotationConfig {
+    /// Initial rotation angle in radians.
+    pub initial: f32,
+    /// Angular velocity in radians per second.
+    pub velocity: f32,
+    /// Randomization amount (0.0-1.0). Offsets the initial angle by up to ±PI
+    /// and scales the velocity by a factor in [1 - randomize, 1 + randomize].
+    pub randomize: f32,
+}
+
 /// Envelope configuration for particle lifetime.
 #[derive(Clone, Debug)]
 pub struct ParticleEnvelope {
@@ -189,6 +326,10 @@ pub struct ParticleInstance {
     pub event_weight: f32,
     /// Local rotation as quaternion [x, y, z, w] for mesh particles.
     pub local_rotation: [f32; 4],
+    /// Current spin angle in radians.
+    pub rotation: f32,
+    /// Spin rate in radians per second.
+    pub angular_velocity: f32,
 }
 
 impl ParticleSystem {
@@ -279,6 +420,8 @@ impl ParticleSystem {
             color_shift: variation.color_shift,
             event_weight,
             local_rotation: variation.rotation,
+            rotation: variation.spin_angle,
+            angular_velocity: variation.spin_velocity,
         });
     }
 
@@ -334,11 +477,26 @@ impl ParticleSystem {
             [0.0, 0.0, 0.0, 1.0]
         };
 
+        // Spin is only randomized when requested so existing seeds keep their sequence
+        let spin = &self.config.rotation;
+        let (spin_angle, spin_velocity) = if spin.randomize > 0.0 {
+            let angle_jitter = (next_f32() - 0.5) * 2.0 * std::f32::consts::PI;
+            let velocity_jitter = (next_f32() - 0.5) * 2.0;
+            (
+                spin.initial + angle_jitter * spin.randomize,
+                spin.velocity * (1.0 + velocity_jitter * spin.randomize),
+            )
+        } else {
+            (spin.initial, spin.velocity)
+        };
+
         VariationResult {
             position_offset,
             scale_mult,
             color_shift,
             rotation,
+            spin_angle,
+            spin_velocity,
         }
     }
 }
@@ -349,11 +507,14 @@ struct VariationResult {
     scale_mult: f32,
     color_shift: [f32; 3],
     rotation: [f32; 4],
+    spin_angle: f32,
+    spin_velocity: f32,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::headless_or_skip;
 
     #[test]
     fn test_particle_system_from_events() {
@@ -430,6 +591,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_additive_particles_brighten_overlapping_sprites() {
+        let config = ParticleConfig {
+            blend: ParticleBlend::from_str("add").unwrap(),
+            ..Default::default()
+        };
+        assert_eq!(config.blend.blend_mode(), BlendMode::Additive);
+        assert!(!config.blend.needs_sorting());
+
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // A burst of half-transparent white sprites stacked at the origin;
+        // returns the red channel of the HDR scene at the centre
+        let mut render_burst = |blend: &str| {
+            let script = format!(
+                r#"
+                let sys = particles.stream(gen.constant(1.0), #{{
+                    mode: "threshold",
+                    instances_per_burst: 8,
+                    envelope: "step",
+                    lifetime_beats: 100.0,
+                    spread: #{{ x: 0.0, y: 0.0, z: 0.0 }},
+                    scale: 0.5,
+                    color: #{{ r: 1.0, g: 1.0, b: 1.0, a: 0.5 }},
+                }});
+                sys.blend("{blend}");
+
+                fn init(ctx) {{
+                    camera.position = #{{ x: 0.0, y: 0.0, z: 3.0 }};
+                    camera.lookAt(#{{ x: 0.0, y: 0.0, z: 0.0 }});
+                    scene.add(sys);
+                }}
+
+                fn update(dt, frame) {{}}
+                "#
+            );
+            headless.render_script(&script);
+            headless.read_scene_hdr()[32 * 64 + 32][0]
+        };
+
+        // Alpha compositing approaches white; additive sums past it
+        let alpha = render_burst("alpha");
+        let added = render_burst("add");
+        assert!(alpha <= 1.0 + 1e-3, "alpha blend gave {alpha}");
+        assert!(added > 2.0, "additive blend gave {added}");
+    }
+
     #[test]
     fn test_seed_zero_produces_valid_variation() {
         // Seed 0 was previously degenerate (xorshift produces all zeros)
@@ -14,6 +14,16 @@ pub struct GpuParticleInstance {
     pub position: [f32; 3],
     pub scale: f32,
     pub color: [f32; 4],
+    /// Screen-space spin angle in radians.
+    pub rotation: f32,
+    /// World-space velocity in units per second.
+    pub velocity: [f32; 3],
+    /// Stretch factor along velocity (0.0 = camera-facing).
+    pub stretch: f32,
+    /// Soft-particle fade distance in world units (0.0 = hard edges).
+    pub soft_fade: f32,
+    /// Sprite sheet lookup: (frame, cols, rows, unused).
+    pub sprite: [f32; 4],
 }
 
 impl GpuParticleInstance {
@@ -42,6 +52,36 @@ impl GpuParticleInstance {
                     shader_location: 3,
                     format: wgpu::VertexFormat::Float32x4,
                 },
+                // rotation: f32
+                wgpu::VertexAttribute {
+                    offset: 32,
+                    shader_location: 4,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                // velocity: vec3<f32>
+                wgpu::VertexAttribute {
+                    offset: 36,
+                    shader_location: 5,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
+                // stretch: f32
+                wgpu::VertexAttribute {
+                    offset: 48,
+                    shader_location: 6,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                // soft_fade: f32
+                wgpu::VertexAttribute {
+                    offset: 52,
+                    shader_location: 7,
+                    format: wgpu::VertexFormat::Float32,
+                },
+                // sprite: vec4<f32> (frame, cols, rows, unused)
+                wgpu::VertexAttribute {
+                    offset: 56,
+                    shader_location: 8,
+                    format: wgpu::VertexFormat::Float32x4,
+                },
             ],
         }
     }
@@ -98,7 +138,10 @@ impl GpuMeshParticleInstance {
         }
     }
 }
-use crate::particle::{EmissionSource, ParticleEnvelope, ParticleSystem, StreamMode};
+use crate::particle::{
+    EmissionSource, OverflowPolicy, ParticleEnvelope, ParticleSystem, SizeStop, SpriteSheet,
+    StreamMode,
+};
 use crate::signal::{EasingFunction, EnvelopeShape};
 use crate::signal_eval::EvalContext;
 
@@ -128,8 +171,29 @@ pub fn update_particle_system(
         .instances
         .retain(|instance| ctx.current_time_secs - instance.spawn_time_secs < lifetime_secs);
 
+    // Integrate spin for surviving instances
+    for instance in &mut system.instances {
+        instance.rotation += instance.angular_velocity * ctx.dt;
+    }
+
     // Collect spawn requests to avoid borrowing issues
-    let spawn_requests = collect_spawn_requests(system, ctx, eval_ctx);
+    let mut spawn_requests = collect_spawn_requests(system, ctx, eval_ctx);
+
+    // Enforce the live-instance cap (it may also have been lowered since the last frame)
+    let capacity = system.config.max_instances;
+    let over_cap = system.instances.len().saturating_sub(capacity);
+    system.instances.drain(..over_cap);
+    match system.config.overflow {
+        OverflowPolicy::DropNew => {
+            spawn_requests.truncate(capacity - system.instances.len());
+        }
+        OverflowPolicy::DropOldest => {
+            // Evict in one batch rather than per spawn
+            let needed = (system.instances.len() + spawn_requests.len()).saturating_sub(capacity);
+            let evict = needed.min(system.instances.len());
+            system.instances.drain(..evict);
+        }
+    }
 
     // Spawn new instances
     for request in spawn_requests {
@@ -242,8 +306,11 @@ pub fn generate_gpu_instances(
             let age_beats = age_secs / ctx.secs_per_beat;
             let envelope_value = evaluate_particle_envelope(age_beats, &system.config.envelope);
 
+            let size =
+                sample_size_over_life(&system.config.size_over_life, age_secs / lifetime_secs);
+
             // Apply envelope to scale and opacity
-            let scale = system.config.base_scale * instance.local_scale * envelope_value;
+            let scale = system.config.base_scale * instance.local_scale * envelope_value * size;
             let opacity = envelope_value * instance.event_weight;
 
             // Calculate world position
@@ -265,6 +332,23 @@ pub fn generate_gpu_instances(
                 position,
                 scale,
                 color,
+                rotation: instance.rotation,
+                velocity: [
+                    system.config.velocity.x,
+                    system.config.velocity.y,
+                    system.config.velocity.z,
+                ],
+                stretch: system.config.stretch,
+                soft_fade: system.config.soft_fade,
+                sprite: match &system.config.sprite {
+                    Some(sheet) => [
+                        sprite_frame(sheet, age_secs, lifetime_secs) as f32,
+                        sheet.cols as f32,
+                        sheet.rows as f32,
+                        0.0,
+                    ],
+                    None => [0.0, 1.0, 1.0, 0.0],
+                },
             })
         })
         .collect()
@@ -291,9 +375,15 @@ pub fn generate_mesh_particle_instances(
             let age_beats = age_secs / ctx.secs_per_beat;
             let envelope_value = evaluate_particle_envelope(age_beats, &system.config.envelope);
 
+            let size =
+                sample_size_over_life(&system.config.size_over_life, age_secs / lifetime_secs);
+
             // Apply envelope to scale and opacity
-            let scale =
-                base_mesh_scale * system.config.base_scale * instance.local_scale * envelope_value;
+            let scale = base_mesh_scale
+                * system.config.base_scale
+                * instance.local_scale
+                * envelope_value
+                * size;
             let opacity = envelope_value * instance.event_weight;
 
             // Calculate world position
@@ -311,8 +401,11 @@ pub fn generate_mesh_particle_instances(
                 system.config.base_color[3] * opacity,
             ];
 
-            // Use local rotation from variation (quaternion xyzw)
-            let rotation = instance.local_rotation;
+            // Spin about the local Z axis on top of the variation rotation (quaternion xyzw)
+            let rotation = quat_mul(
+                instance.local_rotation,
+                quat_from_z_angle(instance.rotation),
+            );
 
             Some(GpuMeshParticleInstance {
                 position,
@@ -324,6 +417,212 @@ pub fn generate_mesh_particle_instances(
         .collect()
 }
 
+/// Speed (in screen-plane units per second) below which stretched billboards
+/// fall back to camera-facing quads. Must match `MIN_STRETCH_SPEED` in shader_particle.wgsl.
+pub const MIN_STRETCH_SPEED: f32 = 1e-4;
+
+/// Compute the world-space corners of a billboard quad.
+///
+/// CPU mirror of `vs_particle` in shader_particle.wgsl, so the stretch math can be
+/// checked without a GPU. Keep the two in sync.
+/// Corners are in quad order (-x-y, +x-y, +x+y, -x+y).
+pub fn billboard_corners(
+    instance: &GpuParticleInstance,
+    camera_right: glam::Vec3,
+    camera_up: glam::Vec3,
+) -> [glam::Vec3; 4] {
+    let (axis_x, axis_y, extent_x) = billboard_axes(instance, camera_right, camera_up);
+    let center = glam::Vec3::from(instance.position);
+    [(-0.5, -0.5), (0.5, -0.5), (0.5, 0.5), (-0.5, 0.5)].map(|(x, y)| {
+        center + axis_x * (x * instance.scale * extent_x) + axis_y * (y * instance.scale)
+    })
+}
+
+/// Quad axes and the along-axis length multiplier for a billboard instance.
+fn billboard_axes(
+    instance: &GpuParticleInstance,
+    camera_right: glam::Vec3,
+    camera_up: glam::Vec3,
+) -> (glam::Vec3, glam::Vec3, f32) {
+    let velocity = glam::Vec3::from(instance.velocity);
+    let screen_velocity = glam::Vec2::new(velocity.dot(camera_right), velocity.dot(camera_up));
+    let speed = screen_velocity.length();
+
+    if instance.stretch > 0.0 && speed > MIN_STRETCH_SPEED {
+        // Velocity-aligned: X runs along the on-screen motion, Y across it
+        let dir = screen_velocity / speed;
+        let along = camera_right * dir.x + camera_up * dir.y;
+        let across = camera_right * -dir.y + camera_up * dir.x;
+        (along, across, 1.0 + instance.stretch * speed)
+    } else {
+        let (s, c) = instance.rotation.sin_cos();
+        (
+            camera_right * c + camera_up * s,
+            camera_right * -s + camera_up * c,
+            1.0,
+        )
+    }
+}
+
+/// Sprite-sheet frame for a particle of the given age.
+///
+/// With `fps` set the sheet loops at that rate; otherwise it plays once across the
+/// particle's lifetime, holding the last frame at the end.
+pub fn sprite_frame(sheet: &SpriteSheet, age_secs: f32, lifetime_secs: f32) -> u32 {
+    let frames = sheet.frames.max(1);
+    match sheet.fps {
+        Some(fps) => (age_secs.max(0.0) * fps) as u32 % frames,
+        None => {
+            let t = if lifetime_secs > 0.0 {
+                (age_secs / lifetime_secs).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            ((t * frames as f32) as u32).min(frames - 1)
+        }
+    }
+}
+
+/// Soft-particle alpha multiplier.
+///
+/// CPU mirror of `soft_particle_fade` in shader_particle.wgsl. `scene_depth` is the
+/// non-linear [0, 1] depth-buffer value behind the fragment and `particle_depth` the
+/// particle's view-space distance. Returns 1.0 when `soft_fade` is disabled.
+pub fn soft_particle_fade(
+    scene_depth: f32,
+    particle_depth: f32,
+    near: f32,
+    far: f32,
+    soft_fade: f32,
+) -> f32 {
+    if soft_fade <= 0.0 {
+        return 1.0;
+    }
+    let scene_linear = near * far / (far - scene_depth * (far - near));
+    ((scene_linear - particle_depth) / soft_fade).clamp(0.0, 1.0)
+}
+
+/// Sample a size-over-lifetime curve at normalized age `t`.
+///
+/// Stops must be sorted by `t`. Ages outside the first/last stop hold the end values,
+/// and an empty curve yields 1.0.
+pub fn sample_size_over_life(stops: &[SizeStop], t: f32) -> f32 {
+    let (first, last) = match (stops.first(), stops.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return 1.0,
+    };
+    if t <= first.t {
+        return first.size;
+    }
+    if t >= last.t {
+        return last.size;
+    }
+
+    for pair in stops.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if t <= b.t {
+            let span = b.t - a.t;
+            if span <= f32::EPSILON {
+                return b.size;
+            }
+            return a.size + (b.size - a.size) * (t - a.t) / span;
+        }
+    }
+
+    last.size
+}
+
+/// Sort billboard instances back-to-front for alpha blending.
+///
+/// Orders by camera-space depth from `view` (right-handed, so farther instances have
+/// more negative z). The sort is stable, so equal depths keep their emission order.
+pub fn sort_back_to_front(instances: &mut [GpuParticleInstance], view: glam::Mat4) {
+    let depth = |instance: &GpuParticleInstance| {
+        view.transform_point3(glam::Vec3::from(instance.position)).z
+    };
+    instances.sort_by(|a, b| depth(a).total_cmp(&depth(b)));
+}
+
+/// Merge alpha-blended instance groups and sort them back-to-front as one layer.
+///
+/// Each group is keyed by what it is drawn with (e.g. its sprite texture). Returns the
+/// sorted instances and the draw runs over them: maximal ranges of consecutive
+/// instances with the same key. Particles from different groups therefore still
+/// composite in depth order, at the cost of one draw per run.
+pub fn sort_groups_back_to_front<K: Clone + PartialEq>(
+    groups: Vec<(K, Vec<GpuParticleInstance>)>,
+    view: glam::Mat4,
+) -> (Vec<GpuParticleInstance>, Vec<(K, std::ops::Range<usize>)>) {
+    let mut keys: Vec<K> = Vec::with_capacity(groups.len());
+    let mut tagged: Vec<(usize, GpuParticleInstance)> = Vec::new();
+    for (group, (key, instances)) in groups.into_iter().enumerate() {
+        keys.push(key);
+        tagged.extend(instances.into_iter().map(|instance| (group, instance)));
+    }
+    let depth = |instance: &GpuParticleInstance| {
+        view.transform_point3(glam::Vec3::from(instance.position)).z
+    };
+    tagged.sort_by(|a, b| depth(&a.1).total_cmp(&depth(&b.1)));
+
+    let mut runs: Vec<(K, std::ops::Range<usize>)> = Vec::new();
+    for (index, (group, _)) in tagged.iter().enumerate() {
+        match runs.last_mut() {
+            Some((key, run)) if *key == keys[*group] => run.end = index + 1,
+            _ => runs.push((keys[*group].clone(), index..index + 1)),
+        }
+    }
+    (
+        tagged.into_iter().map(|(_, instance)| instance).collect(),
+        runs,
+    )
+}
+
+/// Split a global instance budget across systems in proportion to what each wants.
+///
+/// Returns the per-system allowance, in the same order as `requested`. When the total
+/// fits within `budget` every system gets its full request; otherwise shares are scaled
+/// down proportionally, with leftover instances going to the largest remainders.
+pub fn allocate_particle_budget(requested: &[usize], budget: usize) -> Vec<usize> {
+    let total: usize = requested.iter().sum();
+    if total <= budget {
+        return requested.to_vec();
+    }
+
+    let mut allocation: Vec<usize> = requested.iter().map(|&n| n * budget / total).collect();
+    let mut remaining = budget - allocation.iter().sum::<usize>();
+
+    // Hand out the rounding leftovers by largest remainder (ties go to the earlier system)
+    let mut by_remainder: Vec<usize> = (0..requested.len()).collect();
+    by_remainder.sort_by_key(|&i| std::cmp::Reverse(requested[i] * budget % total));
+    for i in by_remainder {
+        if remaining == 0 {
+            break;
+        }
+        if allocation[i] < requested[i] {
+            allocation[i] += 1;
+            remaining -= 1;
+        }
+    }
+
+    allocation
+}
+
+/// Quaternion for a rotation of `angle` radians about the Z axis.
+fn quat_from_z_angle(angle: f32) -> [f32; 4] {
+    let half = angle * 0.5;
+    [0.0, 0.0, half.sin(), half.cos()]
+}
+
+/// Hamilton product `a * b` of two xyzw quaternions.
+fn quat_mul(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [
+        a[3] * b[0] + a[0] * b[3] + a[1] * b[2] - a[2] * b[1],
+        a[3] * b[1] - a[0] * b[2] + a[1] * b[3] + a[2] * b[0],
+        a[3] * b[2] + a[0] * b[1] - a[1] * b[0] + a[2] * b[3],
+        a[3] * b[3] - a[0] * b[0] - a[1] * b[1] - a[2] * b[2],
+    ]
+}
+
 /// Evaluate the particle envelope at a given age.
 pub fn evaluate_particle_envelope(age_beats: f32, envelope: &ParticleEnvelope) -> f32 {
     let raw_value = match envelope.shape {
@@ -373,44 +672,7 @@ pub fn evaluate_particle_envelope(age_beats: f32, envelope: &ParticleEnvelope) -
 /// Apply an easing function to a value.
 fn apply_easing(t: f32, easing: EasingFunction) -> f32 {
     match easing {
-        EasingFunction::Linear => t,
-        EasingFunction::QuadraticIn => t * t,
-        EasingFunction::QuadraticOut => t * (2.0 - t),
-        EasingFunction::QuadraticInOut => {
-            if t < 0.5 {
-                2.0 * t * t
-            } else {
-                -1.0 + (4.0 - 2.0 * t) * t
-            }
-        }
-        EasingFunction::CubicIn => t * t * t,
-        EasingFunction::CubicOut => {
-            let t1 = t - 1.0;
-            t1 * t1 * t1 + 1.0
-        }
-        EasingFunction::CubicInOut => {
-            if t < 0.5 {
-                4.0 * t * t * t
-            } else {
-                let t1 = 2.0 * t - 2.0;
-                0.5 * t1 * t1 * t1 + 1.0
-            }
-        }
-        EasingFunction::ExponentialIn => {
-            if t == 0.0 {
-                0.0
-            } else {
-                (2.0_f32).powf(10.0 * (t - 1.0))
-            }
-        }
-        EasingFunction::ExponentialOut => {
-            if t == 1.0 {
-                1.0
-            } else {
-                1.0 - (2.0_f32).powf(-10.0 * t)
-            }
-        }
-        EasingFunction::SmoothStep => t * t * (3.0 - 2.0 * t),
+        // Particle envelopes keep their original elastic curve (period 0.3).
         EasingFunction::Elastic => {
             if t == 0.0 || t == 1.0 {
                 t
@@ -420,6 +682,7 @@ fn apply_easing(t: f32, easing: EasingFunction) -> f32 {
                 (2.0_f32).powf(-10.0 * t) * ((t - s) * std::f32::consts::TAU / p).sin() + 1.0
             }
         }
+        _ => easing.apply(t),
     }
 }
 
@@ -427,10 +690,11 @@ fn apply_easing(t: f32, easing: EasingFunction) -> f32 {
 mod tests {
     use super::*;
     use crate::input::{BandSignalMap, SignalMap};
-    use crate::particle::{ParticleConfig, ParticleEnvelope};
+    use crate::particle::{ParticleConfig, ParticleEnvelope, RotationConfig};
     use crate::signal::Signal;
     use crate::signal_state::SignalState;
     use crate::signal_stats::StatisticsCache;
+    use std::sync::Arc;
 
     fn update_constant_stream(value: f32) -> usize {
         let signal_map = SignalMap::new();
@@ -508,6 +772,319 @@ mod tests {
         assert!((v - 0.5).abs() < 0.01); // Smoothstep(0.5) = 0.5
     }
 
+    #[test]
+    fn test_rotation_advances_by_velocity_times_dt() {
+        let signal_map = SignalMap::new();
+        let band_map = BandSignalMap::new();
+        let stats = StatisticsCache::new();
+        let mut state = SignalState::new();
+        let mut eval_ctx = EvalContext::new(
+            0.0,
+            0.1,
+            0,
+            None,
+            &signal_map,
+            &band_map,
+            &band_map,
+            &signal_map,
+            &signal_map,
+            &stats,
+            &mut state,
+            None,
+        );
+        let mut config = ParticleConfig {
+            seed: 7,
+            lifetime_beats: 100.0,
+            ..Default::default()
+        };
+        config.rotation = RotationConfig {
+            initial: 0.25,
+            velocity: 2.0,
+            randomize: 0.5,
+        };
+        let mut system = ParticleSystem::from_events(Arc::new(Vec::new()), 1, config);
+        system.spawn_instance(0.0, 0.0, 1.0);
+        let initial = system.instances[0].rotation;
+        let velocity = system.instances[0].angular_velocity;
+        assert!(velocity > 0.0);
+
+        let dt = 0.1;
+        for frame in 1..=5 {
+            let ctx = ParticleEvalContext {
+                current_time_secs: frame as f32 * dt,
+                current_beat: frame as f32 * dt * 2.0,
+                secs_per_beat: 0.5,
+                dt,
+                dt_beats: dt * 2.0,
+            };
+            update_particle_system(&mut system, &ctx, &mut eval_ctx);
+        }
+
+        let expected = initial + velocity * dt * 5.0;
+        assert!((system.instances[0].rotation - expected).abs() < 1e-5);
+
+        let ctx = ParticleEvalContext {
+            current_time_secs: 0.5,
+            current_beat: 1.0,
+            secs_per_beat: 0.5,
+            dt,
+            dt_beats: dt * 2.0,
+        };
+        let gpu = generate_gpu_instances(&system, &ctx);
+        assert!((gpu[0].rotation - expected).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_size_over_life_interpolates_between_stops() {
+        let stops = [
+            SizeStop { t: 0.0, size: 0.0 },
+            SizeStop { t: 0.4, size: 1.0 },
+            SizeStop { t: 0.8, size: 0.5 },
+        ];
+        // Halfway between the 0.4 and 0.8 stops
+        assert!((sample_size_over_life(&stops, 0.6) - 0.75).abs() < 1e-6);
+        assert_eq!(sample_size_over_life(&stops, 1.0), 0.5);
+        assert_eq!(sample_size_over_life(&[], 0.5), 1.0);
+        assert_eq!(
+            sample_size_over_life(&[SizeStop { t: 0.3, size: 2.0 }], 0.9),
+            2.0
+        );
+
+        let config = ParticleConfig {
+            lifetime_beats: 2.0,
+            base_scale: 1.0,
+            envelope: ParticleEnvelope {
+                shape: EnvelopeShape::Step,
+                ..Default::default()
+            },
+            size_over_life: vec![
+                SizeStop { t: 0.0, size: 1.0 },
+                SizeStop { t: 1.0, size: 3.0 },
+            ],
+            ..Default::default()
+        };
+        let mut system = ParticleSystem::from_events(Arc::new(Vec::new()), 1, config);
+        system.spawn_instance(0.0, 0.0, 1.0);
+
+        // Lifetime is 1s at 0.5 s/beat, so t = 0.5s is age 0.5
+        let ctx = ParticleEvalContext {
+            current_time_secs: 0.5,
+            current_beat: 1.0,
+            secs_per_beat: 0.5,
+            dt: 0.5,
+            dt_beats: 1.0,
+        };
+        let gpu = generate_gpu_instances(&system, &ctx);
+        assert!((gpu[0].scale - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_stretched_billboard_elongates_along_velocity() {
+        let aabb_extent = |instance: &GpuParticleInstance| {
+            let corners = billboard_corners(instance, glam::Vec3::X, glam::Vec3::Y);
+            let min = corners.iter().fold(glam::Vec3::MAX, |a, c| a.min(*c));
+            let max = corners.iter().fold(glam::Vec3::MIN, |a, c| a.max(*c));
+            max - min
+        };
+        let mut instance = GpuParticleInstance {
+            position: [0.0, 0.0, 0.0],
+            scale: 0.1,
+            color: [1.0; 4],
+            rotation: 0.0,
+            velocity: [0.0, 4.0, 0.0],
+            stretch: 0.5,
+            soft_fade: 0.0,
+            sprite: [0.0, 1.0, 1.0, 0.0],
+        };
+
+        let moving = aabb_extent(&instance);
+        assert!((moving.y - 0.1 * 3.0).abs() < 1e-5);
+        assert!((moving.x - 0.1).abs() < 1e-5);
+
+        // Near-zero velocity falls back to a camera-facing square
+        instance.velocity = [0.0, 0.0, 0.0];
+        let still = aabb_extent(&instance);
+        assert!((still.x - still.y).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_soft_particle_fade_tracks_depth_gap() {
+        let (near, far) = (0.1, 100.0);
+        // Depth-buffer value for geometry 5 units away
+        let depth_at = |z: f32| (far * (z - near)) / (z * (far - near));
+        let geometry = depth_at(5.0);
+
+        assert!((soft_particle_fade(geometry, 4.5, near, far, 1.0) - 0.5).abs() < 1e-3);
+        assert_eq!(soft_particle_fade(geometry, 6.0, near, far, 1.0), 0.0);
+        // The cleared depth buffer (far plane) leaves particles fully opaque
+        assert_eq!(soft_particle_fade(1.0, 4.5, near, far, 1.0), 1.0);
+        assert_eq!(soft_particle_fade(geometry, 4.9, near, far, 0.0), 1.0);
+    }
+
+    fn run_capped_stream(overflow: OverflowPolicy) -> ParticleSystem {
+        let signal_map = SignalMap::new();
+        let band_map = BandSignalMap::new();
+        let stats = StatisticsCache::new();
+        let mut state = SignalState::new();
+        let mut eval_ctx = EvalContext::new(
+            0.0,
+            0.5,
+            0,
+            None,
+            &signal_map,
+            &band_map,
+            &band_map,
+            &signal_map,
+            &signal_map,
+            &stats,
+            &mut state,
+            None,
+        );
+        let config = ParticleConfig {
+            max_instances: 10,
+            overflow,
+            lifetime_beats: 100.0,
+            ..Default::default()
+        };
+        let mut system = ParticleSystem::from_stream(
+            Signal::constant(1.0),
+            StreamMode::Proportional { rate_per_beat: 6.0 },
+            config,
+        );
+
+        // 6 spawns per frame for 5 frames: 30 requested against a cap of 10
+        for frame in 1..=5 {
+            let ctx = ParticleEvalContext {
+                current_time_secs: frame as f32 * 0.5,
+                current_beat: frame as f32,
+                secs_per_beat: 0.5,
+                dt: 0.5,
+                dt_beats: 1.0,
+            };
+            update_particle_system(&mut system, &ctx, &mut eval_ctx);
+            assert!(system.instances.len() <= 10);
+        }
+        system
+    }
+
+    #[test]
+    fn test_particle_cap_evicts_by_overflow_policy() {
+        let oldest = run_capped_stream(OverflowPolicy::DropOldest);
+        assert_eq!(oldest.instances.len(), 10);
+        // Newest spawns survive: 4 from frame 4 and 6 from frame 5
+        assert!(oldest.instances.iter().all(|i| i.spawn_time_secs >= 2.0));
+        assert_eq!(
+            oldest
+                .instances
+                .iter()
+                .filter(|i| i.spawn_time_secs == 2.5)
+                .count(),
+            6
+        );
+
+        let new = run_capped_stream(OverflowPolicy::DropNew);
+        assert_eq!(new.instances.len(), 10);
+        // First spawns are kept: 6 from frame 1 and 4 from frame 2
+        assert!(new.instances.iter().all(|i| i.spawn_time_secs <= 1.0));
+    }
+
+    #[test]
+    fn test_sprite_frame_advances_with_age() {
+        let mut sheet = SpriteSheet {
+            asset_id: "spark.png".to_string(),
+            cols: 4,
+            rows: 2,
+            frames: 8,
+            fps: None,
+        };
+        // Plays once over a 2 second lifetime
+        assert_eq!(sprite_frame(&sheet, 0.0, 2.0), 0);
+        assert_eq!(sprite_frame(&sheet, 0.6, 2.0), 2);
+        assert_eq!(sprite_frame(&sheet, 1.99, 2.0), 7);
+        assert_eq!(sprite_frame(&sheet, 2.0, 2.0), 7);
+
+        // Loops at a fixed rate
+        sheet.fps = Some(10.0);
+        assert_eq!(sprite_frame(&sheet, 0.35, 2.0), 3);
+        assert_eq!(sprite_frame(&sheet, 0.95, 2.0), 1);
+    }
+
+    #[test]
+    fn test_sort_back_to_front_orders_far_to_near() {
+        let at = |z: f32| GpuParticleInstance {
+            position: [0.0, 0.0, z],
+            scale: 0.1,
+            color: [1.0; 4],
+            rotation: 0.0,
+            velocity: [0.0; 3],
+            stretch: 0.0,
+            soft_fade: 0.0,
+            sprite: [0.0, 1.0, 1.0, 0.0],
+        };
+        let mut instances = vec![at(0.0), at(2.0), at(-3.0), at(1.0)];
+
+        // Camera on +Z looking at the origin
+        let view = glam::Mat4::look_at_rh(
+            glam::Vec3::new(0.0, 0.0, 5.0),
+            glam::Vec3::ZERO,
+            glam::Vec3::Y,
+        );
+        sort_back_to_front(&mut instances, view);
+
+        let order: Vec<f32> = instances.iter().map(|i| i.position[2]).collect();
+        assert_eq!(order, vec![-3.0, 0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_sort_groups_back_to_front_interleaves_sprites() {
+        let at = |z: f32| GpuParticleInstance {
+            position: [0.0, 0.0, z],
+            scale: 0.1,
+            color: [1.0; 4],
+            rotation: 0.0,
+            velocity: [0.0; 3],
+            stretch: 0.0,
+            soft_fade: 0.0,
+            sprite: [0.0, 1.0, 1.0, 0.0],
+        };
+        let view = glam::Mat4::look_at_rh(
+            glam::Vec3::new(0.0, 0.0, 5.0),
+            glam::Vec3::ZERO,
+            glam::Vec3::Y,
+        );
+
+        let (instances, runs) = sort_groups_back_to_front(
+            vec![
+                ("smoke", vec![at(0.0), at(-3.0), at(-2.0)]),
+                ("dust", vec![at(2.0), at(-1.0)]),
+            ],
+            view,
+        );
+
+        let order: Vec<f32> = instances.iter().map(|i| i.position[2]).collect();
+        assert_eq!(order, vec![-3.0, -2.0, -1.0, 0.0, 2.0]);
+        assert_eq!(
+            runs,
+            vec![
+                ("smoke", 0..2),
+                ("dust", 2..3),
+                ("smoke", 3..4),
+                ("dust", 4..5)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_particle_budget_is_shared_proportionally() {
+        assert_eq!(allocate_particle_budget(&[80, 80], 100), vec![50, 50]);
+        assert_eq!(allocate_particle_budget(&[30, 40], 100), vec![30, 40]);
+
+        let uneven = allocate_particle_budget(&[90, 30, 1], 50);
+        assert_eq!(uneven.iter().sum::<usize>(), 50);
+        assert_eq!(uneven[0], 37);
+        assert_eq!(uneven[1], 12);
+    }
+
     #[test]
     fn stream_emission_uses_the_supplied_signal() {
         assert_eq!(update_constant_stream(0.0), 0);
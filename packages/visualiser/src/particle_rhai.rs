@@ -26,8 +26,9 @@ use std::sync::Arc;
 
 use crate::event_stream::EventStream;
 use crate::particle::{
-    ParticleConfig, ParticleEnvelope, ParticleGeometry, ParticleSystem, StreamMode,
-    VariationConfig, MAX_PARTICLE_INSTANCES,
+    OverflowPolicy, ParticleBlend, ParticleConfig, ParticleEnvelope, ParticleGeometry,
+    ParticleSystem, RotationConfig, SizeStop, SpriteSheet, StreamMode, VariationConfig,
+    MAX_PARTICLE_INSTANCES,
 };
 use crate::scene_graph::Vec3;
 use crate::signal::{EasingFunction, EnvelopeShape, Signal};
@@ -168,6 +169,67 @@ pub fn register_particle_api(engine: &mut Engine) {
         h.system.reset();
     });
 
+    // sys.rotation(#{ initial, velocity, randomize })
+    engine.register_fn("rotation", |h: &mut ParticleSystemHandle, options: Map| {
+        h.system.config.rotation = parse_rotation(&options);
+    });
+
+    // sys.blend("alpha" | "add")
+    engine.register_fn("blend", |h: &mut ParticleSystemHandle, mode: &str| {
+        match ParticleBlend::from_str(mode) {
+            Some(blend) => h.system.config.blend = blend,
+            None => log::warn!("Unknown particle blend mode: {}", mode),
+        }
+    });
+
+    // sys.maxParticles(n)
+    engine.register_fn("maxParticles", |h: &mut ParticleSystemHandle, n: i64| {
+        h.system.config.max_instances = usize::try_from(n)
+            .unwrap_or(1)
+            .clamp(1, MAX_PARTICLE_INSTANCES);
+    });
+
+    // sys.overflow("oldest" | "new")
+    engine.register_fn("overflow", |h: &mut ParticleSystemHandle, policy: &str| {
+        match OverflowPolicy::from_str(policy) {
+            Some(policy) => h.system.config.overflow = policy,
+            None => log::warn!("Unknown particle overflow policy: {}", policy),
+        }
+    });
+
+    // sys.stretchBillboards(factor)
+    engine.register_fn(
+        "stretchBillboards",
+        |h: &mut ParticleSystemHandle, factor: f32| {
+            h.system.config.stretch = factor.max(0.0);
+        },
+    );
+    engine.register_fn(
+        "stretchBillboards",
+        |h: &mut ParticleSystemHandle, factor: i64| {
+            h.system.config.stretch = (factor as f32).max(0.0);
+        },
+    );
+
+    // sys.sizeOverLife([#{ t, size }, ...])
+    engine.register_fn(
+        "sizeOverLife",
+        |h: &mut ParticleSystemHandle, stops: rhai::Array| {
+            h.system.config.size_over_life = parse_size_stops(&stops);
+        },
+    );
+
+    // sys.sprite("path.png") / sys.sprite("sheet.png", #{ frames, rows, cols, fps })
+    engine.register_fn("sprite", |h: &mut ParticleSystemHandle, path: &str| {
+        h.system.config.sprite = Some(SpriteSheet::single(path.to_string()));
+    });
+    engine.register_fn(
+        "sprite",
+        |h: &mut ParticleSystemHandle, path: &str, options: Map| {
+            h.system.config.sprite = Some(parse_sprite_sheet(path, &options));
+        },
+    );
+
     // === Register ParticlesBuilder ===
     engine.register_type_with_name::<ParticlesBuilder>("ParticlesBuilder");
 
@@ -249,6 +311,23 @@ fn parse_particle_config(options: &Map) -> ParticleConfig {
         }
     }
 
+    // Soft-particle fade distance
+    if let Some(soft_fade) = get_float(options, "soft_fade") {
+        config.soft_fade = soft_fade.max(0.0);
+    }
+
+    // Velocity that stretched billboards align with
+    if let Some(velocity_map) = options
+        .get("velocity")
+        .and_then(|v| v.clone().try_cast::<Map>())
+    {
+        config.velocity = Vec3::new(
+            get_float(&velocity_map, "x").unwrap_or(0.0),
+            get_float(&velocity_map, "y").unwrap_or(0.0),
+            get_float(&velocity_map, "z").unwrap_or(0.0),
+        );
+    }
+
     // Envelope configuration
     config.envelope = parse_envelope(options);
 
@@ -346,6 +425,51 @@ fn parse_variation(options: &Map) -> VariationConfig {
     variation
 }
 
+/// Parse per-particle spin configuration from options.
+fn parse_rotation(options: &Map) -> RotationConfig {
+    RotationConfig {
+        initial: get_float(options, "initial").unwrap_or(0.0),
+        velocity: get_float(options, "velocity").unwrap_or(0.0),
+        randomize: get_float(options, "randomize")
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0),
+    }
+}
+
+/// Parse size-over-lifetime stops, dropping malformed entries and sorting by age.
+fn parse_size_stops(stops: &rhai::Array) -> Vec<SizeStop> {
+    let mut parsed: Vec<SizeStop> = stops
+        .iter()
+        .filter_map(|stop| stop.clone().try_cast::<Map>())
+        .filter_map(|stop| {
+            Some(SizeStop {
+                t: get_float(&stop, "t")?.clamp(0.0, 1.0),
+                size: get_float(&stop, "size")?,
+            })
+        })
+        .collect();
+    parsed.sort_by(|a, b| a.t.total_cmp(&b.t));
+    parsed
+}
+
+/// Parse a sprite sheet layout. Missing grid dimensions are inferred from `frames`
+/// (a sheet with only `frames` is read as a single row).
+fn parse_sprite_sheet(path: &str, options: &Map) -> SpriteSheet {
+    let grid_dim = |key: &str| get_int(options, key).map(|n| n.clamp(1, 1024) as u32);
+    let frames = grid_dim("frames");
+    let rows = grid_dim("rows").unwrap_or(1);
+    let cols = grid_dim("cols")
+        .or_else(|| frames.map(|f| f.div_ceil(rows)))
+        .unwrap_or(1);
+    SpriteSheet {
+        asset_id: path.to_string(),
+        cols,
+        rows,
+        frames: frames.unwrap_or(cols * rows).min(cols * rows),
+        fps: get_float(options, "fps").filter(|fps| *fps > 0.0),
+    }
+}
+
 /// Parse stream mode from options.
 fn parse_stream_mode(options: &Map) -> StreamMode {
     let mode_str = get_string(options, "mode").unwrap_or_else(|| "proportional".to_string());
@@ -518,6 +642,53 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_parse_rotation_clamps_randomize() {
+        let mut options = Map::new();
+        options.insert("initial".into(), Dynamic::from(0.5f32));
+        options.insert("velocity".into(), Dynamic::from(2_i64));
+        options.insert("randomize".into(), Dynamic::from(3.0f32));
+
+        let rotation = parse_rotation(&options);
+        assert!((rotation.initial - 0.5).abs() < 1e-6);
+        assert!((rotation.velocity - 2.0).abs() < 1e-6);
+        assert_eq!(rotation.randomize, 1.0);
+    }
+
+    #[test]
+    fn test_parse_size_stops_sorts_by_age() {
+        let stop = |t: f32, size: f32| {
+            let mut map = Map::new();
+            map.insert("t".into(), Dynamic::from(t));
+            map.insert("size".into(), Dynamic::from(size));
+            Dynamic::from(map)
+        };
+        let stops = vec![stop(1.0, 0.0), stop(0.0, 0.2), stop(0.5, 1.0)];
+
+        let parsed = parse_size_stops(&stops);
+        let ages: Vec<f32> = parsed.iter().map(|s| s.t).collect();
+        assert_eq!(ages, vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn test_parse_sprite_sheet_infers_grid() {
+        let mut options = Map::new();
+        options.insert("frames".into(), Dynamic::from(6_i64));
+        let strip = parse_sprite_sheet("smoke.png", &options);
+        assert_eq!((strip.cols, strip.rows, strip.frames), (6, 1, 6));
+
+        options.insert("rows".into(), Dynamic::from(2_i64));
+        let grid = parse_sprite_sheet("smoke.png", &options);
+        assert_eq!((grid.cols, grid.rows, grid.frames), (3, 2, 6));
+
+        let mut options = Map::new();
+        options.insert("cols".into(), Dynamic::from(4_i64));
+        options.insert("rows".into(), Dynamic::from(4_i64));
+        let full = parse_sprite_sheet("fire.png", &options);
+        assert_eq!(full.frames, 16);
+        assert_eq!(full.fps, None);
+    }
+
     #[test]
     fn invalid_particle_counts_are_bounded() {
         let mut options = Map::new();
@@ -0,0 +1,140 @@
+//! Waypoint paths for choreographed motion (`entity.followPath()`).
+//!
+//! A [`Path`] is a polyline through a list of waypoints, optionally smoothed
+//! into a Catmull-Rom spline that passes through every waypoint. Sampling is
+//! arc-length parameterized: progress 0.5 is halfway along the path's length,
+//! not halfway through its waypoints, so a linear progress Signal moves the
+//! entity at an even speed however the waypoints are spaced.
+
+/// Line segments per waypoint span when smoothing into a spline.
+pub const SPLINE_SUBDIVISIONS: usize = 16;
+
+/// A sampled path with cumulative arc lengths.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    points: Vec<glam::Vec3>,
+    /// Distance along the path to each point; starts at 0.
+    lengths: Vec<f32>,
+}
+
+impl Path {
+    /// Straight segments between the waypoints. `None` without waypoints.
+    pub fn linear(waypoints: &[glam::Vec3]) -> Option<Self> {
+        if waypoints.is_empty() {
+            return None;
+        }
+        let mut lengths = Vec::with_capacity(waypoints.len());
+        let mut total = 0.0;
+        for (i, point) in waypoints.iter().enumerate() {
+            if i > 0 {
+                total += point.distance(waypoints[i - 1]);
+            }
+            lengths.push(total);
+        }
+        Some(Self {
+            points: waypoints.to_vec(),
+            lengths,
+        })
+    }
+
+    /// A uniform Catmull-Rom spline through the waypoints (the end waypoints
+    /// are repeated as their own neighbours). `None` without waypoints.
+    pub fn catmull_rom(waypoints: &[glam::Vec3]) -> Option<Self> {
+        if waypoints.len() < 3 {
+            return Self::linear(waypoints);
+        }
+        let last = waypoints.len() - 1;
+        let mut points = Vec::with_capacity(last * SPLINE_SUBDIVISIONS + 1);
+        for i in 0..last {
+            let p0 = waypoints[i.saturating_sub(1)];
+            let p1 = waypoints[i];
+            let p2 = waypoints[i + 1];
+            let p3 = waypoints[(i + 2).min(last)];
+            for step in 0..SPLINE_SUBDIVISIONS {
+                let t = step as f32 / SPLINE_SUBDIVISIONS as f32;
+                points.push(catmull_rom_point(p0, p1, p2, p3, t));
+            }
+        }
+        points.push(waypoints[last]);
+        Self::linear(&points)
+    }
+
+    /// Total arc length.
+    pub fn length(&self) -> f32 {
+        self.lengths.last().copied().unwrap_or(0.0)
+    }
+
+    /// The point `progress` (clamped to 0..1) of the way along the path.
+    pub fn sample(&self, progress: f32) -> glam::Vec3 {
+        if self.points.len() < 2 {
+            return self.points[0];
+        }
+        let distance = progress.clamp(0.0, 1.0) * self.length();
+        // First point at or past the distance; the segment ends there.
+        let end = self
+            .lengths
+            .partition_point(|&length| length < distance)
+            .clamp(1, self.points.len() - 1);
+        let start = end - 1;
+        let span = self.lengths[end] - self.lengths[start];
+        if span <= f32::EPSILON {
+            return self.points[end];
+        }
+        let t = (distance - self.lengths[start]) / span;
+        self.points[start].lerp(self.points[end], t)
+    }
+}
+
+/// Uniform Catmull-Rom interpolation between `p1` (t = 0) and `p2` (t = 1).
+fn catmull_rom_point(
+    p0: glam::Vec3,
+    p1: glam::Vec3,
+    p2: glam::Vec3,
+    p3: glam::Vec3,
+    t: f32,
+) -> glam::Vec3 {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    0.5 * (2.0 * p1
+        + (p2 - p0) * t
+        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
+        + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t3)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    #[test]
+    fn test_sampling_is_even_along_uneven_waypoints() {
+        // The first span is 1 long, the second 3: halfway is inside the second.
+        let path = Path::linear(&[Vec3::ZERO, Vec3::X, Vec3::new(4.0, 0.0, 0.0)]).unwrap();
+        assert_eq!(path.length(), 4.0);
+        assert_eq!(path.sample(0.5), Vec3::new(2.0, 0.0, 0.0));
+        assert_eq!(path.sample(-1.0), Vec3::ZERO);
+        assert_eq!(path.sample(2.0), Vec3::new(4.0, 0.0, 0.0));
+
+        let single = Path::linear(&[Vec3::ONE]).unwrap();
+        assert_eq!(single.sample(0.5), Vec3::ONE);
+        assert!(Path::linear(&[]).is_none());
+    }
+
+    #[test]
+    fn test_spline_passes_through_waypoints() {
+        let waypoints = [
+            Vec3::ZERO,
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(2.0, 0.0, 0.0),
+            Vec3::new(3.0, 1.0, 0.0),
+        ];
+        let path = Path::catmull_rom(&waypoints).unwrap();
+        for waypoint in waypoints {
+            assert!(path.points.contains(&waypoint), "{:?}", waypoint);
+        }
+        assert_eq!(path.sample(0.0), waypoints[0]);
+        assert_eq!(path.sample(1.0), waypoints[3]);
+        // Curved, so longer than the straight-line path.
+        assert!(path.length() > Path::linear(&waypoints).unwrap().length());
+    }
+}
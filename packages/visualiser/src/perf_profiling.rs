@@ -84,6 +84,14 @@ mod wasm_timing {
             f()
         }
     }
+
+    /// Monotonic wall-clock time in milliseconds (performance.now()).
+    pub fn now_ms() -> f64 {
+        web_sys::window()
+            .and_then(|w| w.performance())
+            .map(|p| p.now())
+            .unwrap_or(0.0)
+    }
 }
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -117,9 +125,17 @@ mod wasm_timing {
             f()
         }
     }
+
+    /// Monotonic wall-clock time in milliseconds (since first use).
+    pub fn now_ms() -> f64 {
+        thread_local! {
+            static EPOCH: Instant = Instant::now();
+        }
+        EPOCH.with(|epoch| epoch.elapsed().as_secs_f64() * 1000.0)
+    }
 }
 
-pub use wasm_timing::{time_end, time_start, timed};
+pub use wasm_timing::{now_ms, time_end, time_start, timed};
 
 // ============================================================================
 // Collection Size Tracking
@@ -8,6 +8,9 @@ use std::collections::HashMap;
 /// Unique identifier for a post-processing effect.
 pub type EffectId = String;
 
+/// Upper bound on radial blur samples; each sample is a texture fetch per pixel.
+pub const RADIAL_BLUR_MAX_SAMPLES: f32 = 32.0;
+
 /// Types of parameters for post-processing effects.
 #[derive(Clone, Debug, PartialEq)]
 pub enum EffectParamType {
@@ -258,6 +261,16 @@ impl PostProcessingChain {
         self.effects.push(effect);
     }
 
+    /// Evaluated float parameter of the first effect with this ID, for
+    /// `dbg.plotParam()`. None if the effect isn't in the chain or the
+    /// parameter isn't a float.
+    pub fn float_param(&self, effect_id: &str, param: &str) -> Option<f32> {
+        match self.get(effect_id)?.get_param(param)? {
+            EffectParamValue::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
     /// Remove an effect by ID.
     pub fn remove(&mut self, effect_id: &str) {
         self.effects.retain(|e| e.effect_id != effect_id);
@@ -283,6 +296,17 @@ impl PostProcessingChain {
         self.effects.iter().filter(|e| e.enabled)
     }
 
+    /// Effects to run this frame: the enabled effects in order or, with an
+    /// effect soloed via `dbg.soloEffect()`, only that effect (none if it
+    /// isn't enabled in the chain).
+    pub fn active_effects<'a>(
+        &'a self,
+        solo: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a PostEffectInstance> {
+        self.enabled_effects()
+            .filter(move |e| solo.is_none_or(|id| e.effect_id == id))
+    }
+
     /// Enable or disable an effect by ID.
     pub fn set_enabled(&mut self, effect_id: &str, enabled: bool) {
         for effect in &mut self.effects {
@@ -417,6 +441,23 @@ impl PostEffectRegistry {
                     EffectParamDef::color("tint", [1.0, 1.0, 1.0, 1.0])
                         .with_description("Color tint"),
                 )
+                .param(
+                    EffectParamDef::float("exposure", 0.0)
+                        .with_range(-5.0, 5.0)
+                        .with_description("Exposure in stops, applied first (+1 doubles)"),
+                )
+                .param(
+                    EffectParamDef::float("temperature", 0.0)
+                        .with_range(-1.0, 1.0)
+                        .with_description("White balance: negative cools, positive warms"),
+                )
+                .param(
+                    EffectParamDef::float("tint_shift", 0.0)
+                        .with_range(-1.0, 1.0)
+                        .with_description(
+                            "White balance: negative towards green, positive towards magenta",
+                        ),
+                )
                 .build(),
         );
 
@@ -433,12 +474,34 @@ impl PostEffectRegistry {
                 .param(
                     EffectParamDef::float("smoothness", 0.5)
                         .with_range(0.0, 1.0)
-                        .with_description("Edge smoothness"),
+                        .with_description(
+                            "Edge smoothness; sets inner_radius to outer_radius - smoothness / 2 when inner_radius isn't given",
+                        ),
+                )
+                .param(
+                    EffectParamDef::float("roundness", 1.0)
+                        .with_range(0.0, 1.0)
+                        .with_description("Shape (1 = ellipse, 0 = rounded rectangle)"),
+                )
+                .param(
+                    EffectParamDef::float("aspect", 1.0)
+                        .with_range(0.25, 4.0)
+                        .with_description("Width/height of the shape (above 1 = wider)"),
                 )
                 .param(
                     EffectParamDef::color("color", [0.0, 0.0, 0.0, 1.0])
                         .with_description("Vignette color"),
                 )
+                .param(
+                    EffectParamDef::float("inner_radius", 0.45)
+                        .with_range(0.0, 1.5)
+                        .with_description("Distance from center where darkening starts"),
+                )
+                .param(
+                    EffectParamDef::float("outer_radius", 0.7)
+                        .with_range(0.0, 1.5)
+                        .with_description("Distance from center where darkening is full"),
+                )
                 .build(),
         );
 
@@ -497,7 +560,7 @@ impl PostEffectRegistry {
                 )
                 .param(
                     EffectParamDef::float("samples", 8.0)
-                        .with_range(2.0, 32.0)
+                        .with_range(2.0, RADIAL_BLUR_MAX_SAMPLES)
                         .with_description("Number of blur samples (higher = smoother)"),
                 )
                 .param(
@@ -602,6 +665,10 @@ impl Default for PostEffectRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::{
+        headless_or_skip, script_state, step, HeadlessRenderer, RenderedFrame,
+    };
+    use crate::visualiser::VisualiserState;
 
     #[test]
     fn test_registry_has_builtin_effects() {
@@ -632,4 +699,173 @@ mod tests {
         chain.remove("bloom");
         assert_eq!(chain.effects.len(), 1);
     }
+
+    #[test]
+    fn test_solo_runs_only_the_soloed_effect() {
+        let mut chain = PostProcessingChain::new();
+        chain.add(PostEffectInstance::new("bloom"));
+        chain.add(PostEffectInstance::new("vignette"));
+        chain.add(PostEffectInstance::new("grain"));
+        assert_eq!(chain.active_effects(None).count(), 3);
+
+        let soloed: Vec<_> = chain
+            .active_effects(Some("vignette"))
+            .map(|e| e.effect_id.as_str())
+            .collect();
+        assert_eq!(soloed, ["vignette"]);
+
+        // Soloing a disabled or missing effect bypasses the whole chain.
+        chain.set_enabled("vignette", false);
+        assert_eq!(chain.active_effects(Some("vignette")).count(), 0);
+        assert_eq!(chain.active_effects(Some("zoom_wrap")).count(), 0);
+    }
+
+    #[test]
+    fn test_bright_scene_keeps_values_above_one_until_tonemapping() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        let script = |tonemap: &str| {
+            format!(
+                r#"
+            let cube = mesh.cube();
+            cube.lit = false;
+            cube.color.r = 2.0;
+            cube.color.g = 0.0;
+            cube.color.b = 0.0;
+
+            fn init(ctx) {{
+                scene.add(cube);
+                scene.tonemap = "{}";
+            }}
+
+            fn update(dt, frame) {{}}
+        "#,
+                tonemap
+            )
+        };
+
+        // Without a tonemap the output clips, as it did before HDR rendering
+        let clipped = headless.render(&script_state(&script("none")));
+        assert_eq!(clipped.center()[0], 255);
+
+        let mut state = script_state(&script("aces"));
+        let frame = headless.render(&state);
+        let scene = headless.read_scene_hdr();
+        assert_eq!(scene.len(), 64 * 64);
+
+        // The HDR scene holds the unclipped colour...
+        let r = scene[(32 * 64 + 32) as usize][0];
+        assert!(
+            (r - 2.0).abs() < 0.01,
+            "scene should keep HDR values, got {}",
+            r
+        );
+
+        // ...which ACES compresses into range without clipping it
+        let [r, _, _, _] = frame.center();
+        assert!(r > 200 && r < 255, "tonemapped {:?}", frame.center());
+
+        // Lower exposure darkens the output
+        state.set_exposure(0.1);
+        let darker = headless.render(&state);
+        assert!(
+            darker.center()[0] < r,
+            "exposure 0.1 gave {:?}",
+            darker.center()
+        );
+    }
+
+    /// Render the empty scene through the post effect created by `effect`
+    /// (e.g. `fx.vignette(#{})`).
+    fn render_background_with_effect(
+        headless: &mut HeadlessRenderer,
+        effect: &str,
+        exposure: f32,
+    ) -> RenderedFrame {
+        let mut state = VisualiserState::new();
+        let script = format!(
+            r#"
+            fn init(ctx) {{
+                post.add({});
+            }}
+
+            fn update(dt, frame) {{}}
+        "#,
+            effect
+        );
+        assert!(state.load_script(&script));
+        state.set_exposure(exposure);
+        step(&mut state, 1.0 / 60.0);
+        headless.render(&state)
+    }
+
+    #[test]
+    fn test_color_grade_exposure_stop_doubles_linear_values() {
+        let Some(mut headless) = headless_or_skip(16, 16) else {
+            return;
+        };
+
+        // +1 stop in the grade matches doubling the linear (HDR) background
+        // before tonemapping
+        let graded =
+            render_background_with_effect(&mut headless, "fx.colorGrade(#{ exposure: 1.0 })", 1.0)
+                .center();
+        let doubled =
+            render_background_with_effect(&mut headless, "fx.colorGrade(#{})", 2.0).center();
+        let original =
+            render_background_with_effect(&mut headless, "fx.colorGrade(#{})", 1.0).center();
+        for c in 0..3 {
+            assert!(
+                graded[c].abs_diff(doubled[c]) <= 1,
+                "graded {:?} vs doubled {:?}",
+                graded,
+                doubled
+            );
+        }
+        assert!(graded[0] > original[0]);
+
+        // Warming the white balance pushes the grey background towards red
+        let [r, _, b, _] = render_background_with_effect(
+            &mut headless,
+            "fx.colorGrade(#{ temperature: 0.5 })",
+            1.0,
+        )
+        .center();
+        assert!(r > b, "warm background should be reddish, got {:?}", [r, b]);
+    }
+
+    #[test]
+    fn test_vignette_aspect_darkens_edges_unevenly() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        let edges = |frame: &RenderedFrame| (frame.pixel(0, 32)[0], frame.pixel(32, 0)[0]);
+
+        // Round: left and top edge midpoints are equally dark
+        let round =
+            render_background_with_effect(&mut headless, "fx.vignette(#{ intensity: 1.0 })", 1.0);
+        let (left, top) = edges(&round);
+        assert!(
+            left.abs_diff(top) <= 1,
+            "round vignette: left {} top {}",
+            left,
+            top
+        );
+        assert!(left < round.center()[0]);
+        // The default black vignette color stays neutral
+        let [r, g, b, _] = round.pixel(0, 0);
+        assert!(r == g && g == b, "corner {:?}", round.pixel(0, 0));
+
+        // Wide: the horizontal extent grows, so the left edge darkens less than the top
+        let wide = render_background_with_effect(
+            &mut headless,
+            "fx.vignette(#{ intensity: 1.0, aspect: 2.0 })",
+            1.0,
+        );
+        let (left, top) = edges(&wide);
+        assert!(left > top, "wide vignette: left {} top {}", left, top);
+    }
 }
@@ -0,0 +1,248 @@
+//! Rhai registration and namespace generation for parameter presets.
+//!
+//! Scripts define named sets of reactive parameters and crossfade between
+//! them, e.g. to move from an "ambient" to an "energetic" look at a drop:
+//!
+//! ```rhai
+//! preset.define("ambient", #{ bloom: 0.2, speed: 0.5, tint: #{ r: 0.2, g: 0.4, b: 1.0 } });
+//! preset.define("energetic", #{ bloom: 1.5, speed: 2.0, tint: #{ r: 1.0, g: 0.3, b: 0.1 } });
+//! let p = preset.blend("ambient", "energetic", inputs.energy.smooth.exponential(0.5, 0.5));
+//! ```
+//!
+//! `preset.blend(a, b, amount)` returns a map with every key of either
+//! preset:
+//!
+//! - numbers are interpolated (`amount` 0 is all `a`, 1 is all `b`);
+//! - if either value or `amount` is a Signal, the result is a Signal built
+//!   with `lerp`, so the crossfade is evaluated every frame;
+//! - nested maps (colours, vectors) are blended key by key;
+//! - anything else switches from `a` to `b` halfway (and stays `a` for a
+//!   Signal `amount`, which can't be decided at blend time);
+//! - keys in only one preset keep that preset's value.
+//!
+//! Presets are stored in the `preset` namespace map, so they belong to the
+//! script and are reset when it reloads.
+
+use rhai::{Dynamic, Engine, EvalAltResult};
+
+use crate::signal::Signal;
+
+/// A preset value as a Signal, if it's numeric or already a Signal.
+fn to_signal(value: &Dynamic) -> Option<Signal> {
+    if let Some(signal) = value.clone().try_cast::<Signal>() {
+        return Some(signal);
+    }
+    number(value).map(Signal::constant)
+}
+
+/// A plain script number (float or int).
+fn number(value: &Dynamic) -> Option<f32> {
+    value
+        .as_float()
+        .ok()
+        .or_else(|| value.as_int().ok().map(|i| i as f32))
+}
+
+/// Check that a preset only holds numbers, Signals, nested maps and other
+/// plain values (strings, bools). Functions can't be blended.
+fn validate(params: &rhai::Map, path: &str) -> Result<(), Box<EvalAltResult>> {
+    for (key, value) in params {
+        let key_path = if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        };
+        if value.is_fnptr() {
+            return Err(format!("preset: {} is a function and can't be blended", key_path).into());
+        }
+        if let Some(map) = value.read_lock::<rhai::Map>() {
+            validate(&map, &key_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Blend two values; see the module docs.
+fn blend_value(a: &Dynamic, b: &Dynamic, amount: &Dynamic) -> Dynamic {
+    if let (Some(a_map), Some(b_map)) = (
+        a.clone().try_cast::<rhai::Map>(),
+        b.clone().try_cast::<rhai::Map>(),
+    ) {
+        return Dynamic::from(blend_maps(&a_map, &b_map, amount));
+    }
+
+    if let (Some(a), Some(b), Some(t)) = (number(a), number(b), number(amount)) {
+        return Dynamic::from(a + (b - a) * t.clamp(0.0, 1.0));
+    }
+
+    if let (Some(a), Some(b)) = (to_signal(a), to_signal(b)) {
+        let blended = match amount.clone().try_cast::<Signal>() {
+            Some(t) => a.lerp(b, t),
+            None => a.lerp(b, number(amount).unwrap_or(0.0).clamp(0.0, 1.0)),
+        };
+        return Dynamic::from(blended);
+    }
+
+    match number(amount) {
+        Some(t) if t >= 0.5 => b.clone(),
+        _ => a.clone(),
+    }
+}
+
+/// Blend every key of `a` and `b`.
+pub fn blend_maps(a: &rhai::Map, b: &rhai::Map, amount: &Dynamic) -> rhai::Map {
+    let mut result = a.clone();
+    for (key, b_value) in b {
+        let value = match a.get(key) {
+            Some(a_value) => blend_value(a_value, b_value, amount),
+            None => b_value.clone(),
+        };
+        result.insert(key.clone(), value);
+    }
+    result
+}
+
+/// Look up a defined preset by name.
+fn lookup(sets: &rhai::Map, name: &str) -> Result<rhai::Map, Box<EvalAltResult>> {
+    if let Some(params) = sets
+        .get(name)
+        .and_then(|value| value.clone().try_cast::<rhai::Map>())
+    {
+        return Ok(params);
+    }
+    let mut names: Vec<&str> = sets.keys().map(|k| k.as_str()).collect();
+    names.sort_unstable();
+    let defined = if names.is_empty() {
+        "none".to_string()
+    } else {
+        names.join(", ")
+    };
+    Err(format!("preset: unknown preset '{}' (defined: {})", name, defined).into())
+}
+
+/// Register the `__preset_*` helpers with the engine.
+pub fn register_preset_api(engine: &mut Engine) {
+    // Validate a parameter map before it is stored.
+    engine.register_fn(
+        "__preset_check",
+        |params: rhai::Map| -> Result<rhai::Map, Box<EvalAltResult>> {
+            validate(&params, "")?;
+            Ok(params)
+        },
+    );
+
+    engine.register_fn(
+        "__preset_blend",
+        |sets: rhai::Map,
+         a: &str,
+         b: &str,
+         amount: Dynamic|
+         -> Result<rhai::Map, Box<EvalAltResult>> {
+            if number(&amount).is_none() && !amount.is::<Signal>() {
+                return Err(format!(
+                    "preset: blend amount must be a number or Signal, got {}",
+                    amount.type_name()
+                )
+                .into());
+            }
+            Ok(blend_maps(&lookup(&sets, a)?, &lookup(&sets, b)?, &amount))
+        },
+    );
+
+    engine.register_fn(
+        "__preset_get",
+        |sets: rhai::Map, name: &str| -> Result<rhai::Map, Box<EvalAltResult>> {
+            lookup(&sets, name)
+        },
+    );
+}
+
+/// Generate the `preset` namespace Rhai code.
+pub fn generate_preset_namespace() -> String {
+    r#"
+// === Preset Namespace ===
+let preset = #{};
+preset.__type = "preset_namespace";
+preset.__sets = #{};
+preset.define = |name, params| { this.__sets[name] = __preset_check(params); };
+preset.get = |name| __preset_get(this.__sets, name);
+preset.blend = |a, b, amount| __preset_blend(this.__sets, a, b, amount);
+"#
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{BandSignalMap, SignalMap};
+    use crate::visualiser::VisualiserState;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_blend_at_half_yields_midpoint_params() {
+        let mut state = VisualiserState::new();
+        state
+            .try_load_script(
+                r#"
+                let cube;
+                fn init(ctx) {
+                    preset.define("ambient", #{ speed: 1.0, size: 2, tint: #{ r: 0.0, g: 1.0 }, mode: "soft" });
+                    preset.define("energetic", #{ speed: 3.0, size: 4, tint: #{ r: 1.0, g: 0.0 }, mode: "hard" });
+                    cube = mesh.cube();
+                    scene.add(cube);
+                }
+                fn update(dt, frame) {
+                    let p = preset.blend("ambient", "energetic", 0.5);
+                    cube.position.x = p.speed;
+                    cube.position.y = p.size;
+                    cube.position.z = p.tint.r + p.tint.g;
+                    cube.visible = p.mode == "hard";
+                }
+            "#,
+            )
+            .unwrap();
+
+        let signals: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        state.update(0.05, None, None, &signals, &bands, &signals, None);
+        let (_, cube) = state.scene_graph().scene_entities().next().unwrap();
+        let position = cube.transform().position;
+        assert!((position.x - 2.0).abs() < 1e-6);
+        assert!((position.y - 3.0).abs() < 1e-6);
+        assert!((position.z - 1.0).abs() < 1e-6);
+        // Non-numeric params switch to the second preset from halfway.
+        assert!(cube.visible());
+    }
+
+    #[test]
+    fn test_blend_params() {
+        let map = |pairs: &[(&str, Dynamic)]| -> rhai::Map {
+            pairs
+                .iter()
+                .map(|(k, v)| ((*k).into(), v.clone()))
+                .collect()
+        };
+        let a = map(&[
+            ("x", Dynamic::from(0.0f32)),
+            ("only_a", Dynamic::from(7_i64)),
+        ]);
+        let b = map(&[
+            ("x", Dynamic::from(Signal::constant(10.0))),
+            ("only_b", Dynamic::from("b")),
+        ]);
+
+        // Unshared keys are kept; a Signal on either side blends to a Signal.
+        let blended = blend_maps(&a, &b, &Dynamic::from(0.25f32));
+        assert_eq!(blended["only_a"].as_int().unwrap(), 7);
+        assert_eq!(blended["only_b"].clone().into_string().unwrap(), "b");
+        assert!(blended["x"].is::<Signal>());
+
+        // A Signal amount makes numeric params Signals too.
+        let a = map(&[("x", Dynamic::from(0.0f32))]);
+        let b = map(&[("x", Dynamic::from(10.0f32))]);
+        let blended = blend_maps(&a, &b, &Dynamic::from(Signal::constant(0.5)));
+        assert!(blended["x"].is::<Signal>());
+        let blended = blend_maps(&a, &b, &Dynamic::from(1.5f32));
+        assert_eq!(blended["x"].as_float().unwrap(), 10.0);
+    }
+}
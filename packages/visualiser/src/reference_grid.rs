@@ -0,0 +1,27 @@
+//! Reference grid overlay for composing scenes (`dbg.showGrid()`).
+//!
+//! Lines or a checkerboard in screen space, measured from the screen centre
+//! so the middle lines mark it. The grid is an authoring aid: the live
+//! preview draws it whenever the script asks, offline renders only with
+//! `render --show-grid`.
+
+/// Options from `dbg.showGrid(#{ spacing, color, checker })`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceGridOptions {
+    /// Cell size as a fraction of the screen height.
+    pub spacing: f32,
+    /// Line (or filled cell) colour, RGBA.
+    pub color: [f32; 4],
+    /// Fill alternate cells instead of drawing lines.
+    pub checker: bool,
+}
+
+impl Default for ReferenceGridOptions {
+    fn default() -> Self {
+        Self {
+            spacing: 0.1,
+            color: [1.0, 1.0, 1.0, 0.35],
+            checker: false,
+        }
+    }
+}
@@ -3,6 +3,11 @@
 //! This module defines the formal structures for offline rendering jobs,
 //! including single job specs, batch job specs, and render metadata.
 
+use crate::animated_image::AnimationFormat;
+use crate::contact_sheet::ContactSheetGrid;
+use crate::error::VisualiserError;
+use crate::musical_time::{MusicalTimeStructure, TimeSignature};
+use crate::video_encode::VideoEncodingOptions;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -32,6 +37,19 @@ fn default_sample_rate() -> f32 {
     100.0
 }
 
+/// Default exposure (no change before tonemapping).
+fn default_exposure() -> f32 {
+    1.0
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 /// Specification for a single render job.
 /// Contains all information needed to deterministically render a sequence of frames.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -88,9 +106,116 @@ pub struct RenderJobSpec {
     #[serde(default)]
     pub output_video: bool,
 
-    /// Video output path. If None and output_video is true, defaults to {output_dir}/render.mp4.
+    /// Video output path. If None and output_video is true, defaults to
+    /// {output_dir}/render.{ext}, with the extension chosen by the codec.
     #[serde(default)]
     pub video_path: Option<PathBuf>,
+
+    /// Codec, quality and pixel format for video output.
+    #[serde(default)]
+    pub video_options: VideoEncodingOptions,
+
+    /// How many times GIF/WebP output plays. 0 loops forever.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub loop_count: u32,
+
+    /// Render a contact sheet (`contact_sheet.png`) with this grid of evenly
+    /// spaced thumbnails instead of individual frames.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact_sheet: Option<ContactSheetGrid>,
+
+    /// Write a reproducibility manifest next to the output (see `manifest_path`).
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub manifest: bool,
+
+    /// Custom frequency bands: a path to a JSON band config file, an inline
+    /// JSON array (`[{"id": "sub", "lo": 20, "hi": 60}, ...]`), or `mel:N` for
+    /// N mel-spaced bands. When set, the
+    /// input signal is treated as audio and per-band energies are computed
+    /// from it. Only valid with `input_path`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bands: Option<String>,
+
+    /// Directory of pre-separated stem `.wav` files. Each file becomes a stem
+    /// (`inputs.stems["<name>"]`) with `energy` and `amplitude` signals,
+    /// added on top of any stems from the package.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stems: Option<PathBuf>,
+
+    /// JSON file of authored event streams (`{"name": [times...]}`), exposed
+    /// as `inputs.customEvents["name"]` alongside any package streams.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<PathBuf>,
+
+    /// Standard MIDI file. Note-ons become event streams
+    /// (`inputs.customEvents["midi:ch1"]`, `["midi:ch1:36"]`) and held notes
+    /// velocity signals (`inputs.midi.ch1.note36`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub midi: Option<PathBuf>,
+
+    /// Fixed tempo in BPM. Builds a constant musical time structure that
+    /// replaces the package's (or the 120 BPM default).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bpm: Option<f32>,
+
+    /// Time of the first downbeat in seconds, used with `bpm`. Default 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub beat_offset: Option<f32>,
+
+    /// Time signature used with `bpm`, e.g. `"3/4"`. Sets beats per bar
+    /// (bar timing); default 4/4.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time_signature: Option<String>,
+
+    /// Loop start in seconds. With `loop_end`, playback wraps back here each
+    /// time it reaches `loop_end`, for the whole render duration.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_start: Option<f32>,
+
+    /// Loop end in seconds. Clamped to the track length if it runs past it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_end: Option<f32>,
+
+    /// Global cap on rendered particle instances per frame, shared
+    /// proportionally across particle systems.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_particles: Option<usize>,
+
+    /// Script operation limit per update() call. Raise it for heavy
+    /// per-frame loops; defaults to the sandbox default (100k).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_operations: Option<u64>,
+
+    /// Script operation limit for loading and init(); defaults to the
+    /// sandbox default (1M).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_init_operations: Option<u64>,
+
+    /// Draw the full-track waveform overlay with a playhead, as if the script
+    /// called `dbg.showWaveform()`.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub show_waveform: bool,
+
+    /// Draw the `dbg.showGrid()` reference grid. Without this, offline
+    /// renders leave the grid out.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub show_grid: bool,
+
+    /// Exposure multiplier applied before tonemapping, on top of the
+    /// script's `scene.exposure`.
+    #[serde(default = "default_exposure")]
+    pub exposure: f32,
+
+    /// Keep the last N seconds of frames in memory and export them as
+    /// `replay_NN` videos whenever the script calls `dbg.captureReplay()`.
+    /// Costs `width * height * 4` bytes per buffered frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replay_seconds: Option<f32>,
+
+    /// Re-analyse `input_path` and the `stems` directory when they change on
+    /// disk during the render, reloading the script with the new signals.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub watch_audio: bool,
 }
 
 impl RenderJobSpec {
@@ -110,67 +235,280 @@ impl RenderJobSpec {
             preset_name: None,
             output_video: false,
             video_path: None,
+            video_options: VideoEncodingOptions::default(),
+            loop_count: 0,
+            contact_sheet: None,
+            manifest: false,
+            bands: None,
+            stems: None,
+            events: None,
+            midi: None,
+            bpm: None,
+            beat_offset: None,
+            time_signature: None,
+            loop_start: None,
+            loop_end: None,
+            max_particles: None,
+            max_operations: None,
+            max_init_operations: None,
+            show_waveform: false,
+            show_grid: false,
+            exposure: default_exposure(),
+            replay_seconds: None,
+            watch_audio: false,
         }
     }
 
     /// Validate the job specification.
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> Result<(), VisualiserError> {
         match (&self.input_path, &self.package_path) {
             (Some(_), Some(_)) => {
-                return Err(
+                return Err(VisualiserError::InvalidConfig(
                     "Provide either an input signal (--input) or an interpretation package \
                      (--package), not both"
                         .to_string(),
-                );
+                ));
             }
             (None, None) => {
-                return Err(
+                return Err(VisualiserError::InvalidConfig(
                     "Either an input signal (--input) or an interpretation package (--package) \
                      is required"
                         .to_string(),
-                );
+                ));
             }
             _ => {}
         }
         if let Some(input_path) = &self.input_path {
             if !input_path.exists() {
-                return Err(format!("Input file not found: {:?}", input_path));
+                return Err(VisualiserError::InvalidConfig(format!(
+                    "Input file not found: {:?}",
+                    input_path
+                )));
             }
             // The legacy input path has no other script source.
             if self.script_path.is_none() {
-                return Err(
+                return Err(VisualiserError::InvalidConfig(
                     "A script (--script) is required when rendering from a raw input signal"
                         .to_string(),
-                );
+                ));
+            }
+        }
+        if self.bands.is_some() && self.input_path.is_none() {
+            return Err(VisualiserError::InvalidConfig(
+                "Custom bands (--bands) require an audio input signal (--input)".to_string(),
+            ));
+        }
+        self.validate_settings()
+    }
+
+    /// Validate everything but the input and script sources, for callers
+    /// that supply those in memory (see `VisualiserBuilder`).
+    pub fn validate_settings(&self) -> Result<(), VisualiserError> {
+        if let Some(stems_dir) = &self.stems {
+            if !stems_dir.is_dir() {
+                return Err(VisualiserError::InvalidConfig(format!(
+                    "Stems directory not found: {:?}",
+                    stems_dir
+                )));
             }
         }
+        if let Some(events_path) = &self.events {
+            if !events_path.is_file() {
+                return Err(VisualiserError::InvalidConfig(format!(
+                    "Events file not found: {:?}",
+                    events_path
+                )));
+            }
+        }
+        if let Some(midi_path) = &self.midi {
+            if !midi_path.is_file() {
+                return Err(VisualiserError::InvalidConfig(format!(
+                    "MIDI file not found: {:?}",
+                    midi_path
+                )));
+            }
+        }
+        if let Some(bpm) = self.bpm {
+            if !(bpm > 0.0 && bpm.is_finite()) {
+                return Err(VisualiserError::InvalidConfig(
+                    "--bpm must be positive".to_string(),
+                ));
+            }
+        } else if self.beat_offset.is_some() || self.time_signature.is_some() {
+            return Err(VisualiserError::InvalidConfig(
+                "--beat-offset and --time-signature require --bpm".to_string(),
+            ));
+        }
+        if self.beat_offset.is_some_and(|offset| !offset.is_finite()) {
+            return Err(VisualiserError::InvalidConfig(
+                "--beat-offset must be a finite number of seconds".to_string(),
+            ));
+        }
+        if let Some(signature) = &self.time_signature {
+            TimeSignature::parse(signature).map_err(VisualiserError::InvalidConfig)?;
+        }
+        match (self.loop_start, self.loop_end) {
+            (Some(start), Some(end)) if start < 0.0 || start >= end => {
+                return Err(VisualiserError::InvalidConfig(
+                    "Loop start must be non-negative and before loop end".to_string(),
+                ));
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                return Err(VisualiserError::InvalidConfig(
+                    "Loop mode needs both --loop-start and --loop-end".to_string(),
+                ));
+            }
+            _ => {}
+        }
+        if self.max_particles == Some(0) {
+            return Err(VisualiserError::InvalidConfig(
+                "--max-particles must be at least 1".to_string(),
+            ));
+        }
+        if self.max_operations == Some(0) {
+            return Err(VisualiserError::InvalidConfig(
+                "--max-operations must be at least 1".to_string(),
+            ));
+        }
+        if self.max_init_operations == Some(0) {
+            return Err(VisualiserError::InvalidConfig(
+                "--max-init-operations must be at least 1".to_string(),
+            ));
+        }
         if let Some(package_path) = &self.package_path {
             if !package_path.exists() {
-                return Err(format!("Package file not found: {:?}", package_path));
+                return Err(VisualiserError::InvalidConfig(format!(
+                    "Package file not found: {:?}",
+                    package_path
+                )));
             }
         }
         if let Some(script_path) = &self.script_path {
             if !script_path.exists() {
-                return Err(format!("Script file not found: {:?}", script_path));
+                return Err(VisualiserError::InvalidConfig(format!(
+                    "Script file not found: {:?}",
+                    script_path
+                )));
             }
         }
         if self.fps <= 0.0 {
-            return Err("FPS must be positive".to_string());
+            return Err(VisualiserError::InvalidConfig(
+                "FPS must be positive".to_string(),
+            ));
         }
         if self.width == 0 || self.height == 0 {
-            return Err("Width and height must be positive".to_string());
+            return Err(VisualiserError::InvalidConfig(
+                "Width and height must be positive".to_string(),
+            ));
+        }
+        if !(self.exposure > 0.0 && self.exposure.is_finite()) {
+            return Err(VisualiserError::InvalidConfig(
+                "Exposure must be positive".to_string(),
+            ));
         }
         if self.input_sample_rate <= 0.0 {
-            return Err("Sample rate must be positive".to_string());
+            return Err(VisualiserError::InvalidConfig(
+                "Sample rate must be positive".to_string(),
+            ));
+        }
+        if self
+            .replay_seconds
+            .is_some_and(|seconds| !(seconds > 0.0 && seconds.is_finite()))
+        {
+            return Err(VisualiserError::InvalidConfig(
+                "--replay-seconds must be positive".to_string(),
+            ));
+        }
+        if self.watch_audio && self.input_path.is_none() && self.stems.is_none() {
+            return Err(VisualiserError::InvalidConfig(
+                "--watch-audio needs an --input signal or a --stems directory to watch".to_string(),
+            ));
+        }
+        if self.replay_seconds.is_some() && self.contact_sheet.is_some() {
+            return Err(VisualiserError::InvalidConfig(
+                "Instant replay (--replay-seconds) can't be combined with a contact sheet"
+                    .to_string(),
+            ));
+        }
+        if self.contact_sheet.is_some() && self.output_video {
+            return Err(VisualiserError::InvalidConfig(
+                "A contact sheet (--contact-sheet) can't be combined with video output".to_string(),
+            ));
+        }
+        if self.output_video {
+            match self.animation_format() {
+                Some(format) => format.validate(self.width, self.height, self.fps),
+                None => self.video_options.validate(),
+            }
+            .map_err(VisualiserError::InvalidConfig)?;
         }
         Ok(())
     }
 
+    /// Constant musical time from `bpm` / `beat_offset` / `time_signature`,
+    /// covering `[0, duration)`. None without `bpm`. Call after `validate`.
+    pub fn tempo_override(&self, duration: f32) -> Option<MusicalTimeStructure> {
+        let bpm = self.bpm?;
+        let beats_per_bar = self
+            .time_signature
+            .as_deref()
+            .and_then(|s| TimeSignature::parse(s).ok())
+            .map(|sig| sig.beats_per_bar as f32);
+        Some(MusicalTimeStructure::constant(
+            bpm,
+            self.beat_offset.unwrap_or(0.0),
+            beats_per_bar,
+            duration,
+            "cli",
+        ))
+    }
+
+    /// Number of frames rendered for `duration` seconds at `fps`.
+    pub fn frame_count(&self, duration: f32) -> usize {
+        (duration * self.fps).ceil() as usize
+    }
+
+    /// Time at which frame `index` samples its signals when rendering from
+    /// `start`. Each frame advances by 1/fps before updating, so frame 0 is
+    /// at `start + 1/fps`. Signals are sampled at these times regardless of
+    /// the analysis hop, so `fps` only sets the output frame rate.
+    pub fn frame_time(&self, start: f32, index: usize) -> f32 {
+        start + (index + 1) as f32 / self.fps
+    }
+
+    /// Animated image format when the video path ends in `.gif` or `.webp`.
+    /// Such output is encoded directly instead of through FFmpeg.
+    pub fn animation_format(&self) -> Option<AnimationFormat> {
+        AnimationFormat::from_path(&self.effective_video_path())
+    }
+
+    /// Where the manifest is written: beside the video
+    /// (`render.mp4` -> `render.manifest.json`) or in the frame directory.
+    pub fn manifest_path(&self) -> PathBuf {
+        if self.output_video {
+            self.effective_video_path().with_extension("manifest.json")
+        } else {
+            self.output_dir.join("manifest.json")
+        }
+    }
+
     /// Get the effective video output path.
+    /// Frames directory and video path for the `index`th instant replay:
+    /// `{output_dir}/replay_NN/` and `{output_dir}/replay_NN.{ext}`.
+    pub fn replay_paths(&self, index: usize) -> (PathBuf, PathBuf) {
+        let name = format!("replay_{:02}", index);
+        (
+            self.output_dir.join(&name),
+            self.output_dir
+                .join(format!("{}.{}", name, self.video_options.codec.extension())),
+        )
+    }
+
     pub fn effective_video_path(&self) -> PathBuf {
-        self.video_path
-            .clone()
-            .unwrap_or_else(|| self.output_dir.join("render.mp4"))
+        self.video_path.clone().unwrap_or_else(|| {
+            self.output_dir
+                .join(format!("render.{}", self.video_options.codec.extension()))
+        })
     }
 }
 
@@ -188,7 +526,8 @@ pub struct BatchJobSpec {
     /// List of render jobs in the batch.
     pub jobs: Vec<RenderJobSpec>,
 
-    /// Whether to run jobs in parallel.
+    /// Whether to run jobs in parallel. The CLI currently renders batch jobs
+    /// sequentially regardless, since scripts share process-wide state.
     #[serde(default = "default_parallel")]
     pub parallel: bool,
 
@@ -204,23 +543,47 @@ fn default_parallel() -> bool {
 impl BatchJobSpec {
     /// Load a batch spec from a JSON file.
     #[cfg(not(target_arch = "wasm32"))]
-    pub fn from_file(path: &std::path::Path) -> Result<Self, String> {
-        let content = std::fs::read_to_string(path)
-            .map_err(|e| format!("Failed to read batch file {:?}: {}", path, e))?;
-        serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse batch file {:?}: {}", path, e))
+    pub fn from_file(path: &std::path::Path) -> Result<Self, VisualiserError> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            std::io::Error::new(
+                e.kind(),
+                format!("Failed to read batch file {:?}: {}", path, e),
+            )
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            VisualiserError::InvalidConfig(format!("Failed to parse batch file {:?}: {}", path, e))
+        })
     }
 
     /// Validate all jobs in the batch.
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn validate(&self) -> Result<(), VisualiserError> {
         if self.batch_id.is_empty() {
-            return Err("Batch ID cannot be empty".to_string());
+            return Err(VisualiserError::InvalidConfig(
+                "Batch ID cannot be empty".to_string(),
+            ));
         }
         if self.jobs.is_empty() {
-            return Err("Batch must contain at least one job".to_string());
+            return Err(VisualiserError::InvalidConfig(
+                "Batch must contain at least one job".to_string(),
+            ));
         }
+        if self.max_parallel_jobs == Some(0) {
+            return Err(VisualiserError::InvalidConfig(
+                "maxParallelJobs must be at least 1".to_string(),
+            ));
+        }
+        let mut output_dirs = std::collections::HashMap::new();
         for (i, job) in self.jobs.iter().enumerate() {
-            job.validate().map_err(|e| format!("Job {}: {}", i, e))?;
+            if let Some(first) = output_dirs.insert(&job.output_dir, i) {
+                return Err(VisualiserError::InvalidConfig(format!(
+                    "Jobs {} and {} both write to {:?}; give them distinct presetName values",
+                    first, i, job.output_dir
+                )));
+            }
+        }
+        for (i, job) in self.jobs.iter().enumerate() {
+            job.validate()
+                .map_err(|e| VisualiserError::InvalidConfig(format!("Job {}: {}", i, e)))?;
         }
         Ok(())
     }
@@ -325,10 +688,107 @@ impl RenderMetadata {
     }
 
     /// Save metadata to a JSON file.
-    pub fn save(&self, path: &std::path::Path) -> Result<(), String> {
-        let json = serde_json::to_string_pretty(self)
-            .map_err(|e| format!("Failed to serialize metadata: {}", e))?;
-        std::fs::write(path, json).map_err(|e| format!("Failed to write metadata: {}", e))
+    pub fn save(&self, path: &std::path::Path) -> Result<(), VisualiserError> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to serialize metadata: {}", e),
+            )
+        })?;
+        std::fs::write(path, json).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Failed to write metadata: {}", e)).into()
+        })
+    }
+}
+
+/// Reproducibility manifest for a render.
+///
+/// Unlike `RenderMetadata` this carries only what determines the output, so two
+/// renders with equal manifests should produce identical frames.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_arch = "wasm32"))]
+pub struct RenderManifest {
+    /// Script file, or None when the script came from the package.
+    pub script_path: Option<PathBuf>,
+
+    /// SHA-256 hash of the script content.
+    pub script_hash: String,
+
+    /// Audio source: the input signal file or the interpretation package.
+    pub audio_path: Option<PathBuf>,
+
+    /// SHA-256 hash of the audio source.
+    pub audio_hash: String,
+
+    /// Random seed.
+    pub seed: u64,
+
+    /// Frames per second.
+    pub fps: f32,
+
+    /// Output width in pixels.
+    pub width: u32,
+
+    /// Output height in pixels.
+    pub height: u32,
+
+    /// Post-processing effects enabled at the end of the render, in order.
+    pub effect_chain: Vec<String>,
+
+    /// Event streams available to the script.
+    pub event_streams: Vec<ManifestEventStream>,
+
+    /// Octoseq version.
+    pub octoseq_version: String,
+}
+
+/// An event stream listed in a `RenderManifest`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg(not(target_arch = "wasm32"))]
+pub struct ManifestEventStream {
+    /// Name the script sees the stream under.
+    pub name: String,
+
+    /// Where the stream came from: `detected`, `authored`, `band` or `midi`.
+    pub source: String,
+
+    /// Number of events.
+    pub event_count: usize,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl RenderManifest {
+    /// Manifest for `job`, with the settings taken from the job and the hashes
+    /// computed by the caller. Effect chain and event streams start empty.
+    pub fn from_job(job: &RenderJobSpec, script_hash: String, audio_hash: String) -> Self {
+        Self {
+            script_path: job.script_path.clone(),
+            script_hash,
+            audio_path: job.input_path.clone().or_else(|| job.package_path.clone()),
+            audio_hash,
+            seed: job.seed,
+            fps: job.fps,
+            width: job.width,
+            height: job.height,
+            effect_chain: Vec::new(),
+            event_streams: Vec::new(),
+            octoseq_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// Save the manifest to a JSON file.
+    pub fn save(&self, path: &std::path::Path) -> Result<(), VisualiserError> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Failed to serialize manifest: {}", e),
+            )
+        })?;
+        std::fs::write(path, json).map_err(|e| {
+            std::io::Error::new(e.kind(), format!("Failed to write manifest: {}", e)).into()
+        })
     }
 }
 
@@ -454,6 +914,27 @@ mod tests {
             preset_name: Some("test".to_string()),
             output_video: false,
             video_path: None,
+            video_options: VideoEncodingOptions::default(),
+            loop_count: 0,
+            contact_sheet: None,
+            manifest: false,
+            bands: None,
+            stems: None,
+            events: None,
+            midi: None,
+            bpm: None,
+            beat_offset: None,
+            time_signature: None,
+            loop_start: None,
+            loop_end: None,
+            max_particles: None,
+            max_operations: None,
+            max_init_operations: None,
+            show_waveform: false,
+            show_grid: false,
+            exposure: 1.0,
+            replay_seconds: None,
+            watch_audio: false,
         };
 
         // Should fail because files don't exist
@@ -471,7 +952,10 @@ mod tests {
         // Both input and package is an error, regardless of file existence.
         spec.package_path = Some(PathBuf::from("/nonexistent/package.json"));
         let err = spec.validate().expect_err("both sources must be rejected");
-        assert!(err.contains("not both"), "unexpected error: {err}");
+        assert!(
+            err.to_string().contains("not both"),
+            "unexpected error: {err}"
+        );
 
         // Neither input nor package is an error.
         spec.input_path = None;
@@ -484,6 +968,45 @@ mod tests {
         assert!(spec.validate().is_err());
     }
 
+    #[test]
+    fn test_tempo_override_flags() {
+        let dir = std::env::temp_dir().join(format!("octoseq-tempo-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("input.json"), "[0.5]").unwrap();
+        std::fs::write(dir.join("script.rhai"), "").unwrap();
+        let mut spec = RenderJobSpec::new(
+            dir.join("input.json"),
+            dir.join("script.rhai"),
+            dir.join("out"),
+        );
+        assert!(spec.validate().is_ok());
+        assert!(spec.tempo_override(10.0).is_none());
+
+        // 90 BPM, first downbeat at 0.5s, in 3/4.
+        spec.bpm = Some(90.0);
+        spec.beat_offset = Some(0.5);
+        spec.time_signature = Some("3/4".to_string());
+        assert!(spec.validate().is_ok());
+        let structure = spec.tempo_override(10.0).unwrap();
+        let segment = structure.segment_at(5.0).unwrap();
+        assert!((segment.beat_time(4) - (0.5 + 4.0 * 60.0 / 90.0)).abs() < 1e-5);
+        assert_eq!(segment.beats_per_bar(), 3.0);
+
+        // Invalid values are rejected.
+        spec.time_signature = Some("3-4".to_string());
+        assert!(spec.validate().is_err());
+        spec.time_signature = Some("5/3".to_string());
+        assert!(spec.validate().is_err());
+        spec.time_signature = None;
+        spec.bpm = Some(0.0);
+        assert!(spec.validate().is_err());
+        spec.bpm = None;
+        let err = spec.validate().expect_err("offset without bpm");
+        assert!(err.to_string().contains("require --bpm"), "{err}");
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_render_job_spec_defaults() {
         let spec = RenderJobSpec::new(
@@ -499,6 +1022,84 @@ mod tests {
         assert_eq!(spec.input_sample_rate, 100.0);
     }
 
+    #[test]
+    fn test_manifest_records_seed_and_resolution() {
+        let mut spec = RenderJobSpec::new(
+            PathBuf::from("input.json"),
+            PathBuf::from("script.rhai"),
+            PathBuf::from("output"),
+        );
+        spec.seed = 1234;
+        spec.width = 640;
+        spec.height = 360;
+
+        let manifest = RenderManifest::from_job(&spec, "abc".into(), "def".into());
+        let json: serde_json::Value =
+            serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        assert_eq!(json["seed"], 1234);
+        assert_eq!(json["width"], 640);
+        assert_eq!(json["height"], 360);
+        assert_eq!(json["scriptHash"], "abc");
+        assert_eq!(json["audioPath"], "input.json");
+
+        assert_eq!(spec.manifest_path(), PathBuf::from("output/manifest.json"));
+        spec.output_video = true;
+        assert_eq!(
+            spec.manifest_path(),
+            PathBuf::from("output/render.manifest.json")
+        );
+    }
+
+    #[test]
+    fn test_output_fps_is_independent_of_analysis_rate() {
+        // A 100 Hz analysis signal whose value is its own time.
+        let ramp =
+            crate::input::InputSignal::new((0..=200).map(|i| i as f32 / 100.0).collect(), 100.0);
+
+        for (fps, expected_frames) in [(24.0, 48), (60.0, 120)] {
+            let spec = RenderJobSpec {
+                fps,
+                ..RenderJobSpec::new(
+                    PathBuf::from("in.json"),
+                    PathBuf::from("s.rhai"),
+                    PathBuf::from("/out"),
+                )
+            };
+            let frames = spec.frame_count(2.0);
+            assert_eq!(frames, expected_frames);
+            assert!((spec.frame_time(0.0, frames - 1) - 2.0).abs() < 1e-5);
+
+            // Each frame samples the signal at its own time, not at the
+            // nearest analysis frame boundary.
+            let mut previous = 0.0;
+            for i in 0..frames {
+                let time = spec.frame_time(0.0, i);
+                let value = ramp.sample_window(time, 1.0 / fps);
+                assert!(
+                    (value - time).abs() <= 0.011,
+                    "{} fps frame {}: {} at {}s",
+                    fps,
+                    i,
+                    value,
+                    time
+                );
+                assert!(value > previous);
+                previous = value;
+            }
+        }
+
+        // Frame times don't drift over long renders.
+        let spec = RenderJobSpec {
+            fps: 24.0,
+            ..RenderJobSpec::new(
+                PathBuf::from("in.json"),
+                PathBuf::from("s.rhai"),
+                PathBuf::from("/out"),
+            )
+        };
+        assert!((spec.frame_time(0.0, spec.frame_count(600.0) - 1) - 600.0).abs() < 1e-4);
+    }
+
     #[test]
     fn test_render_progress_percentage() {
         let progress = RenderProgress {
@@ -0,0 +1,202 @@
+//! Deterministic replay verification.
+//!
+//! Offline renders must be reproducible: the same script, seed and inputs have to
+//! produce the same frames. These helpers run a script for N frames, hash the
+//! resulting state after every frame (scene transforms, particle instances and
+//! post-processing parameters) and compare two independent runs. Any
+//! nondeterminism, such as HashMap iteration order leaking into state or an RNG
+//! that isn't seeded, shows up as a hash mismatch at the first frame it affects.
+
+use crate::input::{BandSignalMap, SignalMap};
+use crate::post_processing::EffectParamValue;
+use crate::scene_graph::{Transform, Vec3};
+use crate::visualiser::VisualiserState;
+
+/// Settings for a replay run.
+#[derive(Debug, Clone)]
+pub struct ReplayConfig {
+    /// Number of frames to simulate.
+    pub frames: usize,
+    /// Frame delta time in seconds.
+    pub dt: f32,
+    /// Global seed passed to the script engine.
+    pub seed: u64,
+    /// Tempo used for beat-based timing.
+    pub bpm: f32,
+}
+
+impl Default for ReplayConfig {
+    fn default() -> Self {
+        Self {
+            frames: 120,
+            dt: 1.0 / 60.0,
+            seed: 0,
+            bpm: 120.0,
+        }
+    }
+}
+
+/// FNV-1a over an explicit byte serialization.
+///
+/// `std::hash::DefaultHasher` makes no stability guarantees across releases, so
+/// replay hashes use a fixed algorithm. Floats are hashed by bit pattern.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    fn write_f32(&mut self, value: f32) {
+        self.write_bytes(&value.to_bits().to_le_bytes());
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_bytes(&[value as u8]);
+    }
+
+    fn write_str(&mut self, value: &str) {
+        self.write_u64(value.len() as u64);
+        self.write_bytes(value.as_bytes());
+    }
+
+    fn write_vec3(&mut self, value: &Vec3) {
+        self.write_f32(value.x);
+        self.write_f32(value.y);
+        self.write_f32(value.z);
+    }
+
+    fn write_transform(&mut self, transform: &Transform) {
+        self.write_vec3(&transform.position);
+        self.write_vec3(&transform.rotation);
+        self.write_vec3(&transform.scale);
+    }
+}
+
+/// Hash the render-relevant state of a visualiser after a frame.
+///
+/// Covers scene entities in render order (id, visibility, transform), particle
+/// systems by id (transform and every live instance) and the post-processing
+/// chain in order (effect id, enabled flag, parameters sorted by name).
+pub fn scene_state_hash(state: &VisualiserState) -> u64 {
+    let mut hasher = StableHasher::new();
+
+    for (id, entity) in state.scene_graph().scene_entities() {
+        hasher.write_u64(id.0);
+        hasher.write_bool(entity.visible());
+        hasher.write_transform(entity.transform());
+    }
+
+    let mut systems: Vec<_> = state.particle_systems().iter().collect();
+    systems.sort_by_key(|(id, _)| **id);
+    for (id, system) in systems {
+        hasher.write_u64(*id);
+        hasher.write_bool(system.visible);
+        hasher.write_transform(&system.transform);
+        hasher.write_u64(system.instances.len() as u64);
+        for instance in &system.instances {
+            hasher.write_f32(instance.spawn_time_secs);
+            hasher.write_f32(instance.spawn_beat);
+            hasher.write_vec3(&instance.local_offset);
+            hasher.write_f32(instance.local_scale);
+            for channel in instance.color_shift {
+                hasher.write_f32(channel);
+            }
+            hasher.write_f32(instance.event_weight);
+            for component in instance.local_rotation {
+                hasher.write_f32(component);
+            }
+            hasher.write_f32(instance.rotation);
+            hasher.write_f32(instance.angular_velocity);
+        }
+    }
+
+    for effect in &state.post_chain().effects {
+        hasher.write_str(&effect.effect_id);
+        hasher.write_bool(effect.enabled);
+        let mut params: Vec<_> = effect.params.iter().collect();
+        params.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in params {
+            hasher.write_str(name);
+            let components: &[f32] = match value {
+                EffectParamValue::Float(v) => std::slice::from_ref(v),
+                EffectParamValue::Vec2(v) => v,
+                EffectParamValue::Vec3(v) => v,
+                EffectParamValue::Vec4(v) => v,
+            };
+            for component in components {
+                hasher.write_f32(*component);
+            }
+        }
+    }
+
+    hasher.0
+}
+
+/// Run `script` for `config.frames` frames and return the state hash after each frame.
+pub fn replay_frame_hashes(script: &str, config: &ReplayConfig) -> Result<Vec<u64>, String> {
+    let mut state = VisualiserState::new();
+    state.set_global_seed(config.seed);
+    state.set_bpm(config.bpm);
+
+    if !state.load_script(script) {
+        return Err(state
+            .get_script_error()
+            .unwrap_or("Unknown script error")
+            .to_string());
+    }
+
+    let named_signals = SignalMap::new();
+    let band_signals = BandSignalMap::new();
+    let custom_signals = SignalMap::new();
+
+    let mut hashes = Vec::with_capacity(config.frames);
+    for _ in 0..config.frames {
+        state.update(
+            config.dt,
+            None,
+            None,
+            &named_signals,
+            &band_signals,
+            &custom_signals,
+            None,
+        );
+        if let Some(error) = state.get_script_error() {
+            return Err(error.to_string());
+        }
+        hashes.push(scene_state_hash(&state));
+    }
+
+    Ok(hashes)
+}
+
+/// Run `script` twice with the same settings and check every frame hashes identically.
+///
+/// Returns the final frame's hash, or an error naming the first frame that diverged.
+pub fn verify_deterministic_replay(script: &str, config: &ReplayConfig) -> Result<u64, String> {
+    let first = replay_frame_hashes(script, config)?;
+    let second = replay_frame_hashes(script, config)?;
+
+    if let Some(frame) = first.iter().zip(&second).position(|(a, b)| a != b) {
+        return Err(format!(
+            "Replay diverged at frame {}: {:016x} != {:016x}",
+            frame, first[frame], second[frame]
+        ));
+    }
+
+    Ok(first
+        .last()
+        .copied()
+        .unwrap_or_else(|| StableHasher::new().0))
+}
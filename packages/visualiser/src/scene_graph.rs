@@ -8,7 +8,7 @@ use std::collections::{HashMap, HashSet};
 use glam::Vec2;
 
 use crate::deformation::Deformation;
-use crate::material::ParamValue;
+use crate::material::{BlendMode, ParamValue};
 
 pub const MAX_LINE_POINTS: usize = 65_536;
 pub const MAX_POINT_CLOUD_POINTS: usize = 262_144;
@@ -22,8 +22,14 @@ pub struct EntityId(pub u64);
 #[derive(Debug, Clone, PartialEq)]
 pub enum MeshType {
     Cube,
-    Plane,
-    Sphere,
+    /// A unit plane split into `subdivisions` x `subdivisions` quads.
+    Plane {
+        subdivisions: u32,
+    },
+    /// A UV sphere with `segments` longitude segments.
+    Sphere {
+        segments: u32,
+    },
     /// Reference to a loaded mesh asset by ID.
     Asset(String),
     /// A radial ring/arc, optionally extruded in Z for 3D depth.
@@ -47,6 +53,56 @@ pub enum RenderMode {
     Wireframe,
     /// Render both solid and wireframe overlaid.
     SolidWithWireframe,
+    /// Render solid with anti-aliased wireframe edges, shaded in a single
+    /// pass from per-triangle barycentric coordinates.
+    WireframeOverlay,
+}
+
+/// Scene-wide blending applied to meshes, lines and point clouds
+/// (`scene.blendMode`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SceneBlendMode {
+    /// Each mesh uses its own blending (materials keep their blend mode).
+    #[default]
+    Normal,
+    /// Everything blends additively onto the background.
+    Add,
+}
+
+impl SceneBlendMode {
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "add" | "additive" => SceneBlendMode::Add,
+            _ => SceneBlendMode::Normal,
+        }
+    }
+
+    /// Blend mode that overrides per-material blending, if any.
+    pub fn override_blend(&self) -> Option<BlendMode> {
+        match self {
+            SceneBlendMode::Normal => None,
+            SceneBlendMode::Add => Some(BlendMode::Additive),
+        }
+    }
+}
+
+/// Curve mapping the HDR scene to the output (`scene.tonemap`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SceneTonemap {
+    /// Clip at 1.0, as frames looked before the HDR pipeline.
+    #[default]
+    None,
+    /// ACES filmic curve: highlights roll off instead of clipping.
+    Aces,
+}
+
+impl SceneTonemap {
+    pub fn from_str(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "aces" | "filmic" => SceneTonemap::Aces,
+            _ => SceneTonemap::None,
+        }
+    }
 }
 
 /// 3D position/vector.
@@ -154,6 +210,17 @@ impl MaterialParams {
     }
 }
 
+/// The skeletal animation clip a mesh is playing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationPlayback {
+    /// Clip name in the mesh asset.
+    pub name: String,
+    /// Playback time in seconds, before wrapping or clamping to the clip.
+    pub time: f32,
+    /// Wrap around at the end of the clip instead of holding the last frame.
+    pub looping: bool,
+}
+
 /// A mesh instance - references shared geometry with its own transform.
 #[derive(Debug, Clone)]
 pub struct MeshInstance {
@@ -166,8 +233,14 @@ pub struct MeshInstance {
     pub render_mode: RenderMode,
     /// Wireframe color (used when render_mode includes wireframe).
     pub wireframe_color: [f32; 4],
+    /// Edge width in pixels for `RenderMode::WireframeOverlay`. Default: 1.0.
+    pub wireframe_width: f32,
     /// Deformations to apply to this mesh instance.
     pub deformations: Vec<Deformation>,
+    /// Morph target weights by name, blended additively (asset meshes only).
+    pub morph_weights: Vec<(String, f32)>,
+    /// Skeletal animation being played (skinned asset meshes only).
+    pub animation: Option<AnimationPlayback>,
     /// Material ID (None = use default material).
     pub material_id: Option<String>,
     /// Material parameter bindings (evaluated each frame).
@@ -178,6 +251,8 @@ pub struct MeshInstance {
     pub emissive: f32,
     /// Blob shadow configuration for this mesh.
     pub shadow: BlobShadowConfig,
+    /// Draw order layer; lower layers draw first. Default: 0.
+    pub layer: i32,
 }
 
 impl MeshInstance {
@@ -189,12 +264,16 @@ impl MeshInstance {
             color: [1.0, 1.0, 1.0, 1.0], // Default: no tint (white)
             render_mode: RenderMode::default(),
             wireframe_color: [1.0, 1.0, 1.0, 1.0], // Default: white wireframe
+            wireframe_width: 1.0,
             deformations: Vec::new(),
+            morph_weights: Vec::new(),
+            animation: None,
             material_id: None, // Use default material
             material_params: MaterialParams::new(),
             lit: true,     // Default: affected by lighting
             emissive: 0.0, // Default: no emission
             shadow: BlobShadowConfig::default(),
+            layer: 0,
         }
     }
 }
@@ -232,6 +311,7 @@ pub struct LineStrip {
     pub transform: Transform,
     pub visible: bool,
     pub color: [f32; 4], // RGBA color
+    pub layer: i32,      // Draw order layer; lower layers draw first
 }
 
 impl LineStrip {
@@ -246,6 +326,7 @@ impl LineStrip {
             transform: Transform::default(),
             visible: true,
             color: [0.0, 1.0, 0.0, 1.0], // Default green
+            layer: 0,
         }
     }
 
@@ -354,6 +435,8 @@ pub struct PointCloud {
     pub visible: bool,
     /// RGBA color.
     pub color: [f32; 4],
+    /// Draw order layer; lower layers draw first. Default: 0.
+    pub layer: i32,
 }
 
 impl PointCloud {
@@ -377,6 +460,7 @@ impl PointCloud {
             transform: Transform::default(),
             visible: true,
             color: [1.0, 1.0, 1.0, 1.0],
+            layer: 0,
         }
     }
 
@@ -592,7 +676,36 @@ impl Ribbon {
     }
 }
 
-/// A scene entity - mesh, line, group, point cloud, radial wave, or ribbon.
+/// A pixel-space UI rectangle, drawn after post-processing (see [`crate::ui`]).
+#[derive(Debug, Clone)]
+pub struct UiRect {
+    /// Transform. `position.x`/`position.y` is the top-left corner in pixels
+    /// and `scale.x`/`scale.y` multiply the size; parents are ignored.
+    pub transform: Transform,
+    /// Width in pixels.
+    pub width: f32,
+    /// Height in pixels.
+    pub height: f32,
+    /// Visibility.
+    pub visible: bool,
+    /// RGBA color.
+    pub color: [f32; 4],
+}
+
+impl UiRect {
+    /// Create a white rectangle at the top-left corner.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            transform: Transform::default(),
+            width,
+            height,
+            visible: true,
+            color: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+}
+
+/// A scene entity - mesh, line, group, point cloud, radial wave, ribbon, or UI rectangle.
 #[derive(Debug, Clone)]
 pub enum SceneEntity {
     Mesh(MeshInstance),
@@ -601,6 +714,7 @@ pub enum SceneEntity {
     PointCloud(PointCloud),
     RadialWave(RadialWave),
     Ribbon(Ribbon),
+    UiRect(UiRect),
 }
 
 impl SceneEntity {
@@ -613,6 +727,7 @@ impl SceneEntity {
             SceneEntity::PointCloud(p) => &p.transform,
             SceneEntity::RadialWave(w) => &w.transform,
             SceneEntity::Ribbon(r) => &r.transform,
+            SceneEntity::UiRect(u) => &u.transform,
         }
     }
 
@@ -625,6 +740,7 @@ impl SceneEntity {
             SceneEntity::PointCloud(p) => &mut p.transform,
             SceneEntity::RadialWave(w) => &mut w.transform,
             SceneEntity::Ribbon(r) => &mut r.transform,
+            SceneEntity::UiRect(u) => &mut u.transform,
         }
     }
 
@@ -637,6 +753,7 @@ impl SceneEntity {
             SceneEntity::PointCloud(p) => p.visible,
             SceneEntity::RadialWave(w) => w.visible,
             SceneEntity::Ribbon(r) => r.visible,
+            SceneEntity::UiRect(u) => u.visible,
         }
     }
 
@@ -649,12 +766,13 @@ impl SceneEntity {
             SceneEntity::PointCloud(p) => p.visible = visible,
             SceneEntity::RadialWave(w) => w.visible = visible,
             SceneEntity::Ribbon(r) => r.visible = visible,
+            SceneEntity::UiRect(u) => u.visible = visible,
         }
     }
 }
 
 /// The scene graph - manages all entities created by scripts.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct SceneGraph {
     /// All entities indexed by their ID.
     /// Public for direct access from scripting module when syncing entities.
@@ -863,6 +981,17 @@ impl SceneGraph {
         })
     }
 
+    /// Get all UI rectangles in the scene.
+    pub fn ui_rects(&self) -> impl Iterator<Item = (EntityId, &UiRect)> {
+        self.scene_entities().filter_map(|(id, entity)| {
+            if let SceneEntity::UiRect(rect) = entity {
+                Some((id, rect))
+            } else {
+                None
+            }
+        })
+    }
+
     /// Toggle debug bounding box visualization for an entity.
     /// Returns the new state (true = showing, false = hidden).
     pub fn toggle_debug_bounds(&mut self, id: EntityId) -> bool {
@@ -902,6 +1031,108 @@ impl SceneGraph {
     pub fn is_in_scene(&self, id: EntityId) -> bool {
         self.scene_entities.contains(&id)
     }
+
+    /// Compute the world transform matrix for an entity, walking up the parent chain.
+    pub fn world_matrix(&self, entity_id: EntityId) -> glam::Mat4 {
+        let mut chain = Vec::new();
+        let mut visited = HashSet::new();
+        let mut current = Some(entity_id);
+
+        while let Some(id) = current {
+            if !visited.insert(id) {
+                log::error!(
+                    "Scene graph cycle detected while resolving entity {:?}",
+                    entity_id
+                );
+                return glam::Mat4::IDENTITY;
+            }
+            let Some(entity) = self.get(id) else {
+                return glam::Mat4::IDENTITY;
+            };
+            let transform = entity.transform();
+            let translation = glam::Mat4::from_translation(glam::Vec3::new(
+                transform.position.x,
+                transform.position.y,
+                transform.position.z,
+            ));
+            let rotation = glam::Mat4::from_euler(
+                glam::EulerRot::XYZ,
+                transform.rotation.x,
+                transform.rotation.y,
+                transform.rotation.z,
+            );
+            let scale = glam::Mat4::from_scale(glam::Vec3::new(
+                transform.scale.x,
+                transform.scale.y,
+                transform.scale.z,
+            ));
+            chain.push(translation * rotation * scale);
+            current = self.get_parent(id);
+        }
+
+        chain
+            .into_iter()
+            .rev()
+            .fold(glam::Mat4::IDENTITY, |world, local| world * local)
+    }
+
+    /// Rotate an entity so its local +Z axis points at a world-space
+    /// position, keeping its +Y axis as close to world up as possible. The
+    /// parent's rotation is compensated, so this works inside groups.
+    ///
+    /// Returns false, leaving the rotation unchanged, when the entity is
+    /// missing or sits on the target (no direction to face).
+    pub fn look_at(&mut self, entity_id: EntityId, target: glam::Vec3) -> bool {
+        if !self.exists(entity_id) {
+            return false;
+        }
+        let origin = self
+            .world_matrix(entity_id)
+            .transform_point3(glam::Vec3::ZERO);
+        let Some(forward) = (target - origin).try_normalize() else {
+            return false;
+        };
+        // Looking straight up or down: any horizontal axis will do as "up"
+        let up = if forward.y.abs() > 0.999 {
+            glam::Vec3::Z
+        } else {
+            glam::Vec3::Y
+        };
+        let right = up.cross(forward).normalize();
+        let world_rotation =
+            glam::Quat::from_mat3(&glam::Mat3::from_cols(right, forward.cross(right), forward));
+
+        let parent_rotation = self
+            .get_parent(entity_id)
+            .map(|parent| self.world_matrix(parent).to_scale_rotation_translation().1)
+            .unwrap_or(glam::Quat::IDENTITY);
+        let (x, y, z) = (parent_rotation.inverse() * world_rotation).to_euler(glam::EulerRot::XYZ);
+
+        let Some(entity) = self.get_mut(entity_id) else {
+            return false;
+        };
+        entity.transform_mut().rotation = Vec3::new(x, y, z);
+        true
+    }
+
+    /// Check that an entity and all of its ancestors are visible.
+    pub fn is_effectively_visible(&self, entity_id: EntityId) -> bool {
+        let mut visited = HashSet::new();
+        let mut current = Some(entity_id);
+        while let Some(id) = current {
+            if !visited.insert(id) {
+                return false;
+            }
+            let Some(entity) = self.get(id) else {
+                return false;
+            };
+            if !entity.visible() {
+                return false;
+            }
+            current = self.get_parent(id);
+        }
+        true
+    }
 }
 
 impl Default for SceneGraph {
@@ -913,6 +1144,7 @@ impl Default for SceneGraph {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::headless_or_skip;
 
     #[test]
     fn test_create_mesh() {
@@ -942,6 +1174,61 @@ mod tests {
         assert!(!scene.add_to_scene(id));
     }
 
+    #[test]
+    fn test_scene_blend_mode_from_str() {
+        assert_eq!(SceneBlendMode::from_str("add"), SceneBlendMode::Add);
+        assert_eq!(SceneBlendMode::from_str("normal"), SceneBlendMode::Normal);
+        assert_eq!(SceneBlendMode::Normal.override_blend(), None);
+        assert_eq!(
+            SceneBlendMode::Add.override_blend(),
+            Some(BlendMode::Additive)
+        );
+    }
+
+    #[test]
+    fn test_scene_add_blend_accumulates_past_white() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // Two identical entities at the origin, face-on to the camera; returns
+        // the brightest channel of the HDR scene at the centre
+        let mut render_pair = |blend_mode: &str, create: &str, setup: &str| {
+            let script = format!(
+                r#"
+                let a = {create};
+                let b = {create};
+                {setup}
+
+                fn init(ctx) {{
+                    camera.position = #{{ x: 0.0, y: 0.0, z: 3.0 }};
+                    camera.lookAt(#{{ x: 0.0, y: 0.0, z: 0.0 }});
+                    scene.blendMode = "{blend_mode}";
+                    scene.add(a);
+                    scene.add(b);
+                }}
+
+                fn update(dt, frame) {{}}
+                "#
+            );
+            headless.render_script(&script);
+            let centre = headless.read_scene_hdr()[32 * 64 + 32];
+            centre[..3].iter().copied().fold(0.0, f32::max)
+        };
+
+        let cubes = ("mesh.cube()", "a.lit = false; b.lit = false;");
+        let clouds = (
+            "points.cloud(#{ count: 1, spread: 0.0, point_size: 16.0 })",
+            "",
+        );
+        for (create, setup) in [cubes, clouds] {
+            let normal = render_pair("normal", create, setup);
+            let added = render_pair("add", create, setup);
+            assert!(normal <= 1.0 + 1e-3, "{create}: normal blend gave {normal}");
+            assert!(added > 1.5, "{create}: additive blend gave {added}");
+        }
+    }
+
     #[test]
     fn test_line_strip_ring_buffer() {
         let mut line = LineStrip::new(3, LineMode::Line);
@@ -10,6 +10,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::signal::EasingFunction;
+
 pub const SCRIPT_API_SCHEMA_VERSION: u32 = 1;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -121,6 +123,12 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                 type_name: "Log".to_string(),
                 description: "Logging namespace. Use for non-fatal debugging output.".to_string(),
             },
+            ApiGlobal {
+                name: "color".to_string(),
+                kind: ApiGlobalKind::Object,
+                type_name: "Color".to_string(),
+                description: "Colormap namespace. Map 0-1 values to colors for data visualization.".to_string(),
+            },
             ApiGlobal {
                 name: "dbg".to_string(),
                 kind: ApiGlobalKind::Object,
@@ -149,7 +157,7 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                 name: "help".to_string(),
                 kind: ApiGlobalKind::Function,
                 type_name: "function".to_string(),
-                description: "Human-readable help. `help(x)` returns a short formatted summary.".to_string(),
+                description: "Human-readable help. `help(x)` returns a short formatted summary; `help(\"signals\")` (or `describe(\"signals\")`) lists every Signal operator.".to_string(),
             },
             ApiGlobal {
                 name: "doc".to_string(),
@@ -181,6 +189,36 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                 type_name: "Camera".to_string(),
                 description: "Scene camera controls. Position, rotation, projection with signal support for audio-reactive camera motion.".to_string(),
             },
+            ApiGlobal {
+                name: "math".to_string(),
+                kind: ApiGlobalKind::Object,
+                type_name: "Math".to_string(),
+                description: "Transform math namespace. Build and compose 4x4 transforms to compute positions procedurally.".to_string(),
+            },
+            ApiGlobal {
+                name: "vec".to_string(),
+                kind: ApiGlobalKind::Object,
+                type_name: "VecMath".to_string(),
+                description: "Vector math namespace. Dot/cross products, length and interpolation on #{ x, y, z } maps.".to_string(),
+            },
+            ApiGlobal {
+                name: "ease".to_string(),
+                kind: ApiGlobalKind::Object,
+                type_name: "Ease".to_string(),
+                description: "Easing curves (Penner set). Map 0-1 progress to eased 0-1 values, for numbers or Signals.".to_string(),
+            },
+            ApiGlobal {
+                name: "preset".to_string(),
+                kind: ApiGlobalKind::Object,
+                type_name: "Preset".to_string(),
+                description: "Named parameter presets. Define sets of values and crossfade between them, by a number or a Signal.".to_string(),
+            },
+            ApiGlobal {
+                name: "ui".to_string(),
+                kind: ApiGlobalKind::Object,
+                type_name: "Ui".to_string(),
+                description: "Pixel-space UI overlay, drawn after post-processing.".to_string(),
+            },
         ],
         types: vec![
             // Core value shapes
@@ -283,6 +321,13 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         readonly: false,
                         optional: false,
                     },
+                    ApiProperty {
+                        name: "layer".to_string(),
+                        type_name: "int".to_string(),
+                        description: "Draw order layer (default 0). Lower layers draw first; within a layer, further meshes draw before nearer ones.".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
                     ApiProperty {
                         name: "material".to_string(),
                         type_name: "string".to_string(),
@@ -298,7 +343,111 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         optional: true,
                     },
                 ],
-                methods: vec![],
+                methods: vec![
+                    ApiMethod {
+                        name: "lookAt".to_string(),
+                        description: "Keep the entity's forward (+Z) axis pointed at a target, overriding its rotation each frame.".to_string(),
+                        params: vec![ApiParam {
+                            name: "target".to_string(),
+                            type_name: "Entity | Camera | Vec3 | ()".to_string(),
+                            description: "Entity, `camera`, or world-space point to face; `()` removes the constraint.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "void".to_string(),
+                        overload_id: None,
+                        example: Some("cube.lookAt(camera);".to_string()),
+                        notes: Some("Evaluated after positions are resolved. When the entity and target coincide, the rotation is left unchanged.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "followPath".to_string(),
+                        description: "Move the entity along a waypoint path, overriding its position each frame.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "points".to_string(),
+                                type_name: "Array<Vec3>".to_string(),
+                                description: "Waypoints, in the same space as `position`. Components may be Signals.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "progress".to_string(),
+                                type_name: "Signal | float".to_string(),
+                                description: "Distance along the path, 0 (start) to 1 (end).".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "options".to_string(),
+                                type_name: "Map".to_string(),
+                                description: "`spline: true` smooths the path into a Catmull-Rom spline through the waypoints.".to_string(),
+                                optional: true,
+                                default: None,
+                            },
+                        ],
+                        returns: "void".to_string(),
+                        overload_id: None,
+                        example: Some("cube.followPath([#{ x: 0.0, y: 0.0, z: 0.0 }, #{ x: 2.0, y: 1.0, z: 0.0 }], timing.time.scale(0.1));".to_string()),
+                        notes: Some("Progress is arc-length based, so the entity moves at an even speed however the waypoints are spaced. `followPath(())` stops following.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "morph".to_string(),
+                        description: "Blend one of a loaded mesh's morph targets (shape keys) by name.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "name".to_string(),
+                                type_name: "string".to_string(),
+                                description: "Morph target name, from the glTF `targetNames`, or its index as a string.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "amount".to_string(),
+                                type_name: "Signal | float".to_string(),
+                                description: "Blend weight: 0 is the base shape, 1 the full target.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "void".to_string(),
+                        overload_id: None,
+                        example: Some("face.morph(\"mouthOpen\", inputs.mix.energy.normalise.robust());".to_string()),
+                        notes: Some("Only for `mesh.load()` entities. Several targets blend additively, before deformations. Unknown names are ignored.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "playAnimation".to_string(),
+                        description: "Play one of a loaded mesh's skeletal animation clips by name.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "name".to_string(),
+                                type_name: "string".to_string(),
+                                description: "Animation clip name from the glTF file.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "options".to_string(),
+                                type_name: "Map".to_string(),
+                                description: "`speed` (Signal | float, default 1.0), `looping` (bool, default true), and `time` (Signal, seconds) to scrub the clip instead of advancing it.".to_string(),
+                                optional: true,
+                                default: None,
+                            },
+                        ],
+                        returns: "void".to_string(),
+                        overload_id: None,
+                        example: Some("dancer.playAnimation(\"dance\", #{ time: timing.beatPhase });".to_string()),
+                        notes: Some("Only for `mesh.load()` entities with a skin. One animation plays at a time; playing another clip restarts from 0. `loop` is a Rhai keyword, so write `looping` (or a quoted `\"loop\"` key).".to_string()),
+                    },
+                    ApiMethod {
+                        name: "stopAnimation".to_string(),
+                        description: "Stop skeletal animation playback, returning the mesh to its rest pose.".to_string(),
+                        params: vec![],
+                        returns: "void".to_string(),
+                        overload_id: None,
+                        example: Some("dancer.stopAnimation();".to_string()),
+                        notes: None,
+                    },
+                ],
             },
             ApiType {
                 name: "LineStripEntity".to_string(),
@@ -333,6 +482,13 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         readonly: false,
                         optional: false,
                     },
+                    ApiProperty {
+                        name: "layer".to_string(),
+                        type_name: "int".to_string(),
+                        description: "Draw order layer (default 0). Lower layers draw first; within a layer, lines keep their creation order.".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
                     ApiProperty {
                         name: "color".to_string(),
                         type_name: "Color".to_string(),
@@ -417,11 +573,35 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                     ApiMethod {
                         name: "plane".to_string(),
                         description: "Create a plane mesh entity.".to_string(),
-                        params: vec![],
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "PlaneOptions".to_string(),
+                            description: "Options map (optional keys: subdivisions).".to_string(),
+                            optional: true,
+                            default: None,
+                        }],
                         returns: "MeshEntity".to_string(),
                         overload_id: None,
-                        example: Some("let ground = mesh.plane();".to_string()),
-                        notes: None,
+                        example: Some("let ground = mesh.plane(#{ subdivisions: 32 });".to_string()),
+                        notes: Some("Defaults: subdivisions=1 (grid cells per side, max 255).".to_string()),
+                    },
+                    ApiMethod {
+                        name: "sphere".to_string(),
+                        description: "Create a UV sphere mesh entity.".to_string(),
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "SphereOptions".to_string(),
+                            description: "Options map (optional keys: segments).".to_string(),
+                            optional: true,
+                            default: None,
+                        }],
+                        returns: "MeshEntity".to_string(),
+                        overload_id: None,
+                        example: Some("let ball = mesh.sphere(#{ segments: 12 });".to_string()),
+                        notes: Some(
+                            "Defaults: segments=32 (longitude segments, 4-360; latitude rings are half as many)."
+                                .to_string(),
+                        ),
                     },
                 ],
             },
@@ -572,7 +752,36 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                 name: "Scene".to_string(),
                 kind: ApiTypeKind::Namespace,
                 description: "Scene management namespace.".to_string(),
-                properties: vec![],
+                properties: vec![
+                    ApiProperty {
+                        name: "blendMode".to_string(),
+                        type_name: "string".to_string(),
+                        description: "Scene-wide blending: \"normal\" (default) or \"add\" to blend all meshes, lines and point clouds additively.".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "exposure".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Exposure multiplier applied before tonemapping (default 1.0). Raise to brighten, lower to recover highlights.".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "tonemap".to_string(),
+                        type_name: "string".to_string(),
+                        description: "Curve mapping the HDR scene to the output: \"none\" (default) clips at 1.0, \"aces\" rolls highlights off with a filmic curve.".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "background".to_string(),
+                        type_name: "Color | Map | ()".to_string(),
+                        description: "Scene background: a colour, #{ top, bottom } colours for a vertical gradient, or #{ skybox: \"image\" } for an equirectangular image that turns with the camera. () (default) clears to dark grey.".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
+                ],
                 methods: vec![
                     ApiMethod {
                         name: "add".to_string(),
@@ -604,6 +813,60 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("scene.remove(cube);".to_string()),
                         notes: None,
                     },
+                    ApiMethod {
+                        name: "grid".to_string(),
+                        description: "Create a cols x rows grid of entities from a factory, laid out on the XZ plane and added to the scene.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "factory".to_string(),
+                                type_name: "Fn() -> Entity".to_string(),
+                                description: "Called once per cell to create its entity.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "options".to_string(),
+                                type_name: "Map".to_string(),
+                                description: "`cols` (default 1), `rows` (default 1) and `spacing` between cell centres (default 1.0).".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Array<Entity>".to_string(),
+                        overload_id: None,
+                        example: Some("let cells = scene.grid(|| mesh.cube(), #{ cols: 4, rows: 4, spacing: 1.5 });".to_string()),
+                        notes: Some("The grid is centred on the origin. Cells are returned row by row: cell (col, row) is `cells[row * cols + col]`.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "spectrumBars".to_string(),
+                        description: "Create a group of bars whose heights track the track audio's spectrum, updated automatically each frame.".to_string(),
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "Map".to_string(),
+                            description: "`count` (default 32), `spacing` between bars (default 0.25), full `height` (default 3.0) and optional bar `color`.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Entity".to_string(),
+                        overload_id: None,
+                        example: Some("let bars = scene.spectrumBars(#{ count: 48, height: 4.0 });".to_string()),
+                        notes: Some("Bars run along X, lowest frequency on the left, centred on the group's origin and growing upwards. The group and bars are added to the scene; move, rotate or scale the returned group to place them. Needs raw track audio (the CLI's `--input`); interpretation packages only carry analysis envelopes, so without it the spectrum stays flat and a warning is logged.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "fog".to_string(),
+                        description: "Fade meshes towards a fog colour with distance from the camera. Pass () to turn fog off.".to_string(),
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "Map | ()".to_string(),
+                            description: "Fog `color`, the camera distance where fog begins (`start`, default 0) and where meshes are fully fogged (`end`, default start + 1). All may be Signals.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("scene.fog(#{ color: #{ r: 0.1, g: 0.1, b: 0.15 }, start: 5.0, end: 30.0 });".to_string()),
+                        notes: Some("Applies to primitives and loaded models alike. Match the fog colour to scene.background so distant meshes dissolve into it.".to_string()),
+                    },
                 ],
             },
             ApiType {
@@ -659,6 +922,47 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                     },
                 ],
             },
+            ApiType {
+                name: "Color".to_string(),
+                kind: ApiTypeKind::Namespace,
+                description: "Named colormaps for data visualization.".to_string(),
+                properties: vec![],
+                methods: vec![
+                    ApiMethod {
+                        name: "map".to_string(),
+                        description: "Look up the color for a value in a named colormap.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "name".to_string(),
+                                type_name: "string".to_string(),
+                                description: "Colormap name: \"viridis\", \"magma\", \"inferno\", \"turbo\" or \"grayscale\".".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "value".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Position in the colormap, clamped to 0-1.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Map".to_string(),
+                        overload_id: None,
+                        example: Some("let c = color.map(\"viridis\", 0.25);".to_string()),
+                        notes: Some("Returns #{r, g, b, a} with components in 0-1 and a = 1.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "maps".to_string(),
+                        description: "List the available colormap names.".to_string(),
+                        params: vec![],
+                        returns: "Array".to_string(),
+                        overload_id: None,
+                        example: Some("for name in color.maps() { log.info(name); }".to_string()),
+                        notes: None,
+                    },
+                ],
+            },
             ApiType {
                 name: "Dbg".to_string(),
                 kind: ApiTypeKind::Namespace,
@@ -735,72 +1039,231 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         returns: "Map".to_string(),
                         overload_id: None,
                         example: Some("log.info(dbg.describeEffect(\"bloom\"));".to_string()),
-                        notes: Some("Returns {name, description, params: [{name, type, description}]}.".to_string()),
-                    },
-                ],
-            },
-            // Signal API
-            ApiType {
-                name: "Signal".to_string(),
-                kind: ApiTypeKind::Opaque,
-                description: "Declarative signal computation graph (host-evaluated).".to_string(),
-                properties: vec![
-                    ApiProperty {
-                        name: "smooth".to_string(),
-                        type_name: "SmoothBuilder".to_string(),
-                        description: "Smoothing operations namespace.".to_string(),
-                        readonly: true,
-                        optional: false,
-                    },
-                    ApiProperty {
-                        name: "normalise".to_string(),
-                        type_name: "NormaliseBuilder".to_string(),
-                        description: "Normalisation operations namespace.".to_string(),
-                        readonly: true,
-                        optional: false,
-                    },
-                    ApiProperty {
-                        name: "gate".to_string(),
-                        type_name: "GateBuilder".to_string(),
-                        description: "Gating operations namespace.".to_string(),
-                        readonly: true,
-                        optional: false,
-                    },
-                    ApiProperty {
-                        name: "pick".to_string(),
-                        type_name: "PickBuilder".to_string(),
-                        description: "Event extraction namespace.".to_string(),
-                        readonly: true,
-                        optional: false,
+                        notes: Some("Returns {name, description, params: [{name, type, description, default, min?, max?}]}.".to_string()),
                     },
-                ],
-                methods: vec![
                     ApiMethod {
-                        name: "add".to_string(),
-                        description: "Add another signal.".to_string(),
+                        name: "describeEntity".to_string(),
+                        description: "Describe an entity's rendered scene-graph state, with Signal properties evaluated.".to_string(),
                         params: vec![ApiParam {
-                            name: "other".to_string(),
-                            type_name: "Signal".to_string(),
-                            description: "Signal to add.".to_string(),
+                            name: "entity".to_string(),
+                            type_name: "Entity".to_string(),
+                            description: "Entity to describe.".to_string(),
                             optional: false,
                             default: None,
                         }],
-                        returns: "Signal".to_string(),
-                        overload_id: Some("signal".to_string()),
-                        example: Some("inputs.spectralFlux.add(inputs.onsetEnvelope)".to_string()),
-                        notes: None,
+                        returns: "Map".to_string(),
+                        overload_id: None,
+                        example: Some("log.info(dbg.describeEntity(cube));".to_string()),
+                        notes: Some("Returns {id, type, world_position: {x, y, z}, visible, in_scene, parent, mesh_type?, render_mode?} as of the last synced frame (unit before the first frame). visible includes ancestors.".to_string()),
                     },
                     ApiMethod {
-                        name: "add".to_string(),
-                        description: "Add a constant value.".to_string(),
-                        params: vec![ApiParam {
-                            name: "value".to_string(),
-                            type_name: "float".to_string(),
-                            description: "Constant to add.".to_string(),
-                            optional: false,
-                            default: None,
-                        }],
-                        returns: "Signal".to_string(),
+                        name: "plot".to_string(),
+                        description: "Plot a value as a small sparkline in the top-right corner, sampled every frame.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "name".to_string(),
+                                type_name: "string".to_string(),
+                                description: "Plot name. Calling again with the same name replaces the plotted value but keeps its history.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "signal".to_string(),
+                                type_name: "Signal | float".to_string(),
+                                description: "Value to plot.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.plot(\"energy\", inputs.mix.energy.smooth.exponential(0.2, 0.2));".to_string()),
+                        notes: Some("Shows roughly the last 3 seconds, auto-ranged, labelled with its name. Up to 6 plots are shown; further names are ignored with a warning. Plots persist until the script is reloaded.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "plotParam".to_string(),
+                        description: "Plot an evaluated post-effect or feedback parameter as a sparkline named \"effect.param\", sampled every frame after Signals are resolved.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "effect".to_string(),
+                                type_name: "string".to_string(),
+                                description: "Effect ID in the post chain (e.g. \"bloom\"), or \"feedback\".".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "param".to_string(),
+                                type_name: "string".to_string(),
+                                description: "Float parameter name. For feedback: opacity, offsetX, offsetY, strength, scale, rotation (first warp step) or decay.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.plotParam(\"bloom\", \"intensity\");".to_string()),
+                        notes: Some("Uses the first effect with that ID. Nothing is plotted while the effect is missing or feedback is disabled.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "showWaveform".to_string(),
+                        description: "Draw the full-track waveform across the bottom of the screen with a playhead at the current time.".to_string(),
+                        params: vec![],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.showWaveform();".to_string()),
+                        notes: Some("Stays on until the script is reloaded. Draws nothing when no track audio is available.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "showSpectrogram".to_string(),
+                        description: "Draw a scrolling spectrogram of the track audio, one column per frame, newest on the right.".to_string(),
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "Map".to_string(),
+                            description: "rect: #{x, y, w, h} as fractions of the screen from the top-left (default #{x: 0.02, y: 0.02, w: 0.4, h: 0.2}); colormap: a name from color.maps() (default \"magma\").".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.showSpectrogram(#{ rect: #{ x: 0.6, y: 0.05, w: 0.35, h: 0.2 }, colormap: \"viridis\" });".to_string()),
+                        notes: Some("Frequencies are log-spaced, lowest at the bottom. Calling again replaces the options. Stays on until the script is reloaded. Needs raw track audio (the CLI's `--input`); interpretation packages only carry analysis envelopes, so without it the spectrum stays flat and a warning is logged.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "showGrid".to_string(),
+                        description: "Overlay a screen-space reference grid (or checkerboard) to help line up and frame the scene.".to_string(),
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "Map".to_string(),
+                            description: "spacing: cell size as a fraction of the screen height (default 0.1); color: #{r, g, b, a} (default translucent white); checker: fill alternate cells instead of drawing lines (default false).".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.showGrid(#{ spacing: 0.125, color: #{ r: 0.0, g: 1.0, b: 1.0, a: 0.5 } });".to_string()),
+                        notes: Some("Cells are measured from the screen centre; the centre lines are drawn brighter. Offline renders leave the grid out unless run with `render --show-grid`.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "hideGrid".to_string(),
+                        description: "Remove the reference grid overlay.".to_string(),
+                        params: vec![],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.hideGrid();".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "showAxes".to_string(),
+                        description: "Draw an RGB axis gizmo (X red, Y green, Z blue) at the world origin or at an entity, to check camera and rotation.".to_string(),
+                        params: vec![ApiParam {
+                            name: "target".to_string(),
+                            type_name: "bool | Entity".to_string(),
+                            description: "true/false toggles the gizmo at the world origin; an entity adds a gizmo that follows its world transform.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.showAxes(cube);".to_string()),
+                        notes: Some("Axes are one unit long in the target's space. dbg.showAxes(false) removes all gizmos. Stays on until the script is reloaded.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "captureReplay".to_string(),
+                        description: "Export the instant-replay buffer (the last few seconds of frames, ending with this one) as a video.".to_string(),
+                        params: vec![],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("if frame == 600 { dbg.captureReplay(); }".to_string()),
+                        notes: Some("Only has an effect when rendering with `render --replay-seconds N`; each call writes the next replay_NN video. Does nothing elsewhere.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "soloEffect".to_string(),
+                        description: "Render only this post effect, bypassing the rest of the chain, to tune it in isolation.".to_string(),
+                        params: vec![ApiParam {
+                            name: "effect".to_string(),
+                            type_name: "PostEffect | string".to_string(),
+                            description: "An effect from fx.*, or an effect ID such as \"bloom\".".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.soloEffect(bloom);".to_string()),
+                        notes: Some("Nothing is applied if the effect isn't enabled in the chain. Feedback still runs.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "clearSolo".to_string(),
+                        description: "Stop soloing a post effect and render the whole chain again.".to_string(),
+                        params: vec![],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("dbg.clearSolo();".to_string()),
+                        notes: None,
+                    },
+                ],
+            },
+            // Signal API
+            ApiType {
+                name: "Signal".to_string(),
+                kind: ApiTypeKind::Opaque,
+                description: "Declarative signal computation graph (host-evaluated).".to_string(),
+                properties: vec![
+                    ApiProperty {
+                        name: "smooth".to_string(),
+                        type_name: "SmoothBuilder".to_string(),
+                        description: "Smoothing operations namespace.".to_string(),
+                        readonly: true,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "normalise".to_string(),
+                        type_name: "NormaliseBuilder".to_string(),
+                        description: "Normalisation operations namespace.".to_string(),
+                        readonly: true,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "gate".to_string(),
+                        type_name: "GateBuilder".to_string(),
+                        description: "Gating operations namespace.".to_string(),
+                        readonly: true,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "pick".to_string(),
+                        type_name: "PickBuilder".to_string(),
+                        description: "Event extraction namespace.".to_string(),
+                        readonly: true,
+                        optional: false,
+                    },
+                ],
+                methods: vec![
+                    ApiMethod {
+                        name: "add".to_string(),
+                        description: "Add another signal.".to_string(),
+                        params: vec![ApiParam {
+                            name: "other".to_string(),
+                            type_name: "Signal".to_string(),
+                            description: "Signal to add.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Signal".to_string(),
+                        overload_id: Some("signal".to_string()),
+                        example: Some("inputs.spectralFlux.add(inputs.onsetEnvelope)".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "add".to_string(),
+                        description: "Add a constant value.".to_string(),
+                        params: vec![ApiParam {
+                            name: "value".to_string(),
+                            type_name: "float".to_string(),
+                            description: "Constant to add.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Signal".to_string(),
                         overload_id: Some("scalar".to_string()),
                         example: Some("inputs.amplitude.add(0.5)".to_string()),
                         notes: None,
@@ -874,6 +1337,15 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("inputs.onsetEnvelope.probe(\"onset\")".to_string()),
                         notes: Some("Use with analysis mode / host evaluation; this does not print.".to_string()),
                     },
+                    ApiMethod {
+                        name: "value".to_string(),
+                        description: "Evaluate the signal at the current frame and return the number.".to_string(),
+                        params: vec![],
+                        returns: "float".to_string(),
+                        overload_id: None,
+                        example: Some("if inputs.mix.energy.value() > 0.8 { cube.visible = true; }".to_string()),
+                        notes: Some("Only available inside update(); repeated calls within a frame return the cached value.".to_string()),
+                    },
                     ApiMethod {
                         name: "clamp".to_string(),
                         description: "Clamp signal to [min, max].".to_string(),
@@ -940,6 +1412,84 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("inputs.amplitude.normalise.robust().sigmoid(10.0)".to_string()),
                         notes: Some("Normalize or clamp to 0-1 first for predictable results.".to_string()),
                     },
+                    ApiMethod {
+                        name: "step".to_string(),
+                        description: "Hard threshold: 0 below the edge, 1 at or above it.".to_string(),
+                        params: vec![ApiParam {
+                            name: "edge".to_string(),
+                            type_name: "float | Signal".to_string(),
+                            description: "Threshold.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("inputs.mix.energy.normalise.robust().step(0.6)".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "select".to_string(),
+                        description: "If-then-else: returns a when this > 0.5, else b.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Signal".to_string(),
+                                description: "Value when the condition is true.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "b".to_string(),
+                                type_name: "Signal".to_string(),
+                                description: "Value when the condition is false.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("energy.gt(0.8).select(gen.sin(2.0, 0.0), gen.constant(0.0))".to_string()),
+                        notes: Some("Only the chosen branch is evaluated, so stateful nodes in the inactive branch pause.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "smoothstep".to_string(),
+                        description: "Hermite ramp from 0 at edge0 to 1 at edge1.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "edge0".to_string(),
+                                type_name: "float | Signal".to_string(),
+                                description: "Value where the output starts rising.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "edge1".to_string(),
+                                type_name: "float | Signal".to_string(),
+                                description: "Value where the output reaches 1.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("inputs.mix.energy.normalise.robust().smoothstep(0.4, 0.7)".to_string()),
+                        notes: Some("Equal edges behave like step(edge0).".to_string()),
+                    },
+                    ApiMethod {
+                        name: "ease".to_string(),
+                        description: "Reshape a 0-1 signal with a named easing curve.".to_string(),
+                        params: vec![ApiParam {
+                            name: "name".to_string(),
+                            type_name: "string".to_string(),
+                            description: "Easing name, e.g. \"outCubic\", \"inOutSine\", \"outBounce\".".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("inputs.mix.energy.normalise.robust().ease(\"outCubic\")".to_string()),
+                        notes: Some("Input is clamped to 0-1. Same curves as the `ease` namespace.".to_string()),
+                    },
                     ApiMethod {
                         name: "diff".to_string(),
                         description: "Approximate derivative (rate of change).".to_string(),
@@ -964,6 +1514,66 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("inputs.onsetEnvelope.integrate(0.5)".to_string()),
                         notes: None,
                     },
+                    ApiMethod {
+                        name: "windowMax".to_string(),
+                        description: "Largest value over the last N seconds.".to_string(),
+                        params: vec![ApiParam {
+                            name: "seconds".to_string(),
+                            type_name: "float".to_string(),
+                            description: "Window length in seconds.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("inputs.mix.energy.windowMax(4.0)".to_string()),
+                        notes: Some("Live and local: only frames evaluated so far count (unlike normalise statistics).".to_string()),
+                    },
+                    ApiMethod {
+                        name: "windowMin".to_string(),
+                        description: "Smallest value over the last N seconds.".to_string(),
+                        params: vec![ApiParam {
+                            name: "seconds".to_string(),
+                            type_name: "float".to_string(),
+                            description: "Window length in seconds.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("inputs.mix.energy.windowMin(4.0)".to_string()),
+                        notes: Some("Live and local: only frames evaluated so far count (unlike normalise statistics).".to_string()),
+                    },
+                    ApiMethod {
+                        name: "windowAvg".to_string(),
+                        description: "Average value over the last N seconds.".to_string(),
+                        params: vec![ApiParam {
+                            name: "seconds".to_string(),
+                            type_name: "float".to_string(),
+                            description: "Window length in seconds.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("inputs.mix.energy.windowAvg(2.0)".to_string()),
+                        notes: Some("Live and local: only frames evaluated so far count (unlike normalise statistics).".to_string()),
+                    },
+                    ApiMethod {
+                        name: "normalizeWindow".to_string(),
+                        description: "Normalise to 0-1 against the min/max of the last N seconds (automatic gain control).".to_string(),
+                        params: vec![ApiParam {
+                            name: "seconds".to_string(),
+                            type_name: "float".to_string(),
+                            description: "Window length in seconds.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("inputs.mix.energy.normalizeWindow(8.0)".to_string()),
+                        notes: Some("Computed live, so unlike normalise.global()/robust() it needs no precomputed statistics.".to_string()),
+                    },
                     ApiMethod {
                         name: "delay".to_string(),
                         description: "Delay the signal by N beats.".to_string(),
@@ -1494,6 +2104,15 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("let w = events.min_weight();".to_string()),
                         notes: None,
                     },
+                    ApiMethod {
+                        name: "held_weight".to_string(),
+                        description: "Weight (velocity) of the most recent event, held until the next one. 0 before the first event.".to_string(),
+                        params: vec![],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("let burst = strobe * kicks.held_weight();".to_string()),
+                        notes: Some("For authored and MIDI event streams the weight is the hit velocity.".to_string()),
+                    },
                     ApiMethod {
                         name: "to_signal".to_string(),
                         description: "Convert events to an impulse Signal.".to_string(),
@@ -1575,6 +2194,21 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("let first = events.limit(10);".to_string()),
                         notes: None,
                     },
+                    ApiMethod {
+                        name: "shift".to_string(),
+                        description: "Offset all event times by a number of seconds. Negative values anticipate events (lookahead).".to_string(),
+                        params: vec![ApiParam {
+                            name: "seconds".to_string(),
+                            type_name: "float".to_string(),
+                            description: "Offset in seconds (positive = later).".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "EventStream".to_string(),
+                        overload_id: None,
+                        example: Some("let early = kicks.shift(-0.05);".to_string()),
+                        notes: Some("Events shifted before 0 are dropped, not clamped.".to_string()),
+                    },
                     ApiMethod {
                         name: "probe".to_string(),
                         description: "Convert to signal and attach a debug probe for analysis visualization.".to_string(),
@@ -1749,6 +2383,54 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("let n = gen.perlin(1.0, 42);".to_string()),
                         notes: None,
                     },
+                    ApiMethod {
+                        name: "sequence".to_string(),
+                        description: "Step sequencer that outputs one array value per step, advancing every step_beats beats.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "values".to_string(),
+                                type_name: "array<float>".to_string(),
+                                description: "Step values, played in order.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "options".to_string(),
+                                type_name: "Map".to_string(),
+                                description: "step_beats: beats per step (default 1.0).".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("let pattern = gen.sequence([1.0, 0.0, 0.5, 0.0], #{ step_beats: 0.5 });".to_string()),
+                        notes: Some("Wraps back to the first value after the last step. An empty array outputs 0.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "adsr".to_string(),
+                        description: "ADSR envelope retriggered by each event: attack to the event weight, decay to the sustain level, then release.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "events".to_string(),
+                                type_name: "EventStream".to_string(),
+                                description: "Trigger events.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "options".to_string(),
+                                type_name: "Map".to_string(),
+                                description: "attack, decay, release: beats (defaults 0.1, 0.5, 0.3); sustain: level 0-1 (default 0.7).".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Signal".to_string(),
+                        overload_id: None,
+                        example: Some("let env = gen.adsr(inputs.mix.onset.pick.events(#{}), #{ attack: 0.05, decay: 0.25, sustain: 0.4, release: 1.0 });".to_string()),
+                        notes: Some("Events have no duration, so each trigger is also a release. A trigger during an active envelope restarts the attack from the current level.".to_string()),
+                    },
                     ApiMethod {
                         name: "constant".to_string(),
                         description: "Constant-valued signal.".to_string(),
@@ -1907,78 +2589,645 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         optional: false,
                     },
                     ApiProperty {
-                        name: "flux".to_string(),
-                        type_name: "Signal".to_string(),
-                        description: "Band spectral flux (0–1).".to_string(),
-                        readonly: true,
+                        name: "flux".to_string(),
+                        type_name: "Signal".to_string(),
+                        description: "Band spectral flux (0–1).".to_string(),
+                        readonly: true,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "amplitude".to_string(),
+                        type_name: "Signal".to_string(),
+                        description: "Alias for energy.".to_string(),
+                        readonly: true,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "events".to_string(),
+                        type_name: "EventStream".to_string(),
+                        description: "Band-scoped events (may be empty).".to_string(),
+                        readonly: true,
+                        optional: false,
+                    },
+                ],
+                methods: vec![],
+            },
+            // ================================================================
+            // Feedback Builder Types
+            // ================================================================
+            ApiType {
+                name: "Feedback".to_string(),
+                kind: ApiTypeKind::Namespace,
+                description: "Frame feedback namespace for Milkdrop-style temporal visual effects.".to_string(),
+                properties: vec![],
+                methods: vec![
+                    ApiMethod {
+                        name: "builder".to_string(),
+                        description: "Create a new feedback configuration builder.".to_string(),
+                        params: vec![],
+                        returns: "FeedbackBuilder".to_string(),
+                        overload_id: None,
+                        example: Some("let fb = feedback.builder().warp.spiral(0.5, 0.02).build();".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "enable".to_string(),
+                        description: "Enable feedback with a configuration.".to_string(),
+                        params: vec![ApiParam {
+                            name: "config".to_string(),
+                            type_name: "FeedbackConfig".to_string(),
+                            description: "Feedback configuration from builder.build().".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "void".to_string(),
+                        overload_id: None,
+                        example: Some("feedback.enable(fb);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "disable".to_string(),
+                        description: "Disable feedback effects.".to_string(),
+                        params: vec![],
+                        returns: "void".to_string(),
+                        overload_id: None,
+                        example: Some("feedback.disable();".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "is_enabled".to_string(),
+                        description: "Check if feedback is currently enabled.".to_string(),
+                        params: vec![],
+                        returns: "bool".to_string(),
+                        overload_id: None,
+                        example: Some("if feedback.is_enabled() { ... }".to_string()),
+                        notes: None,
+                    },
+                ],
+            },
+            // ================================================================
+            // Transform Math
+            // ================================================================
+            ApiType {
+                name: "Math".to_string(),
+                kind: ApiTypeKind::Namespace,
+                description: "Transform math namespace. Arguments are plain numbers (not Signals).".to_string(),
+                properties: vec![],
+                methods: vec![
+                    ApiMethod {
+                        name: "identity".to_string(),
+                        description: "Identity transform.".to_string(),
+                        params: vec![],
+                        returns: "Mat4".to_string(),
+                        overload_id: None,
+                        example: Some("let m = math.identity();".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "translate".to_string(),
+                        description: "Translation transform.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "x".to_string(),
+                                type_name: "float".to_string(),
+                                description: "X component.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "y".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Y component.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "z".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Z component.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Mat4".to_string(),
+                        overload_id: None,
+                        example: Some("let m = math.translate(1.0, 0.0, 0.0);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "scale".to_string(),
+                        description: "Per-axis scale transform.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "x".to_string(),
+                                type_name: "float".to_string(),
+                                description: "X component.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "y".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Y component.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "z".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Z component.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Mat4".to_string(),
+                        overload_id: None,
+                        example: Some("let m = math.scale(2.0, 1.0, 1.0);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "rotateX".to_string(),
+                        description: "Rotation about the X axis.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "radians".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Angle in radians.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Mat4".to_string(),
+                        overload_id: None,
+                        example: Some("let m = math.rotateX(PI() / 4.0);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "rotateY".to_string(),
+                        description: "Rotation about the Y axis.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "radians".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Angle in radians.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Mat4".to_string(),
+                        overload_id: None,
+                        example: Some("let m = math.rotateY(PI() / 4.0);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "rotateZ".to_string(),
+                        description: "Rotation about the Z axis.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "radians".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Angle in radians.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Mat4".to_string(),
+                        overload_id: None,
+                        example: Some("let m = math.rotateZ(PI() / 4.0);".to_string()),
+                        notes: None,
+                    },
+                ],
+            },
+            ApiType {
+                name: "VecMath".to_string(),
+                kind: ApiTypeKind::Namespace,
+                description: "Vector math on #{ x, y, z } maps (missing components read as 0). Arguments are plain numbers (not Signals).".to_string(),
+                properties: vec![],
+                methods: vec![
+                    ApiMethod {
+                        name: "dot".to_string(),
+                        description: "Dot product.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "First vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "b".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Second vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "float".to_string(),
+                        overload_id: None,
+                        example: Some("let facing = vec.dot(normal, dir);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "cross".to_string(),
+                        description: "Cross product (perpendicular to both).".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "First vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "b".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Second vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Vec3".to_string(),
+                        overload_id: None,
+                        example: Some("let up = vec.cross(forward, right);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "length".to_string(),
+                        description: "Vector length.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "float".to_string(),
+                        overload_id: None,
+                        example: Some("let d = vec.length(vec.sub(target, cube.position));".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "normalize".to_string(),
+                        description: "Unit vector in the same direction.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Vec3".to_string(),
+                        overload_id: None,
+                        example: Some("let dir = vec.normalize(velocity);".to_string()),
+                        notes: Some("A zero vector normalizes to zero.".to_string()),
+                    },
+                    ApiMethod {
+                        name: "add".to_string(),
+                        description: "Component-wise sum.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "First vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "b".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Second vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Vec3".to_string(),
+                        overload_id: None,
+                        example: Some("let p = vec.add(center, offset);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "sub".to_string(),
+                        description: "Component-wise difference (a - b).".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "First vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "b".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Second vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Vec3".to_string(),
+                        overload_id: None,
+                        example: Some("let offset = vec.sub(target, cube.position);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "scale".to_string(),
+                        description: "Multiply every component by a factor.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "factor".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Scale factor.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Vec3".to_string(),
+                        overload_id: None,
+                        example: Some("let half = vec.scale(offset, 0.5);".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "lerp".to_string(),
+                        description: "Linear interpolation from a (t = 0) to b (t = 1).".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "First vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "b".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Second vector.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "t".to_string(),
+                                type_name: "float".to_string(),
+                                description: "Interpolation amount.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Vec3".to_string(),
+                        overload_id: None,
+                        example: Some("cube.position = vec.lerp(start, end, 0.25);".to_string()),
+                        notes: None,
+                    },
+                ],
+            },
+            ApiType {
+                name: "Ease".to_string(),
+                kind: ApiTypeKind::Namespace,
+                description: "Easing curves: quad, cubic, quart, quint, sine, expo, circ, back, elastic and bounce, each as in/out/inOut. Every curve maps 0 to 0 and 1 to 1; back and elastic overshoot in between.".to_string(),
+                properties: vec![],
+                methods: EasingFunction::NAMED
+                    .iter()
+                    .map(|(name, _)| ApiMethod {
+                        name: name.to_string(),
+                        description: format!("`{}` easing curve.", name),
+                        params: vec![ApiParam {
+                            name: "t".to_string(),
+                            type_name: "float | Signal".to_string(),
+                            description: "Progress, clamped to 0-1.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "float | Signal".to_string(),
+                        overload_id: None,
+                        example: Some(format!("cube.scale = 1.0 + ease.{}(progress);", name)),
+                        notes: None,
+                    })
+                    .collect(),
+            },
+            ApiType {
+                name: "Preset".to_string(),
+                kind: ApiTypeKind::Namespace,
+                description: "Named parameter sets (numbers, Signals, nested maps such as colours) that can be crossfaded, e.g. from an ambient to an energetic look.".to_string(),
+                properties: vec![],
+                methods: vec![
+                    ApiMethod {
+                        name: "define".to_string(),
+                        description: "Store a named parameter set. Redefining a name replaces it.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "name".to_string(),
+                                type_name: "string".to_string(),
+                                description: "Preset name.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "params".to_string(),
+                                type_name: "Map".to_string(),
+                                description: "Parameter values: numbers, Signals, nested maps, strings or bools.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "()".to_string(),
+                        overload_id: None,
+                        example: Some("preset.define(\"ambient\", #{ bloom: 0.2, speed: 0.5, tint: #{ r: 0.2, g: 0.4, b: 1.0 } });".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "get".to_string(),
+                        description: "Get a copy of a defined preset.".to_string(),
+                        params: vec![ApiParam {
+                            name: "name".to_string(),
+                            type_name: "string".to_string(),
+                            description: "Preset name.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "Map".to_string(),
+                        overload_id: None,
+                        example: Some("let p = preset.get(\"ambient\");".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "blend".to_string(),
+                        description: "Crossfade two presets: 0 is all `a`, 1 is all `b`. Returns a map with the keys of both.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "a".to_string(),
+                                type_name: "string".to_string(),
+                                description: "First preset name.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "b".to_string(),
+                                type_name: "string".to_string(),
+                                description: "Second preset name.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "amount".to_string(),
+                                type_name: "float | Signal".to_string(),
+                                description: "Blend amount, 0-1.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Map".to_string(),
+                        overload_id: None,
+                        example: Some("let p = preset.blend(\"ambient\", \"energetic\", inputs.mix.energy.normalise.robust());".to_string()),
+                        notes: Some("Numbers are interpolated; with a Signal value or amount the result is a Signal. Nested maps blend key by key. Other values switch from `a` to `b` at 0.5 (a Signal amount keeps `a`). Keys in one preset keep its value.".to_string()),
+                    },
+                ],
+            },
+            ApiType {
+                name: "Ui".to_string(),
+                kind: ApiTypeKind::Namespace,
+                description: "Pixel-space UI overlay. Positions are in pixels from (0, 0) at the top-left of the frame; UI entities are drawn after post-processing, over everything else.".to_string(),
+                properties: vec![],
+                methods: vec![ApiMethod {
+                    name: "rect".to_string(),
+                    description: "Create a filled rectangle. Add it to the scene with `scene.add()`.".to_string(),
+                    params: vec![ApiParam {
+                        name: "options".to_string(),
+                        type_name: "Map".to_string(),
+                        description: "x, y (top-left corner in pixels), width, height (pixels, default 100), color (default white).".to_string(),
+                        optional: false,
+                        default: None,
+                    }],
+                    returns: "UiRectEntity".to_string(),
+                    overload_id: None,
+                    example: Some("let panel = ui.rect(#{ x: 20.0, y: 20.0, width: 200.0, height: 40.0 });".to_string()),
+                    notes: Some("UI entities aren't affected by bloom, feedback or other effects. They draw in the order they were added.".to_string()),
+                }],
+            },
+            ApiType {
+                name: "UiRectEntity".to_string(),
+                kind: ApiTypeKind::Opaque,
+                description: "A pixel-space rectangle created by `ui.rect(options)`.".to_string(),
+                properties: vec![
+                    ApiProperty {
+                        name: "position".to_string(),
+                        type_name: "Vec3".to_string(),
+                        description: "Top-left corner in pixels (z is ignored).".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "scale".to_string(),
+                        type_name: "float".to_string(),
+                        description: "Size multiplier, growing from the top-left corner.".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "visible".to_string(),
+                        type_name: "bool".to_string(),
+                        description: "Visibility flag.".to_string(),
+                        readonly: false,
+                        optional: false,
+                    },
+                    ApiProperty {
+                        name: "width".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Width in pixels.".to_string(),
+                        readonly: false,
                         optional: false,
                     },
                     ApiProperty {
-                        name: "amplitude".to_string(),
-                        type_name: "Signal".to_string(),
-                        description: "Alias for energy.".to_string(),
-                        readonly: true,
+                        name: "height".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Height in pixels.".to_string(),
+                        readonly: false,
                         optional: false,
                     },
                     ApiProperty {
-                        name: "events".to_string(),
-                        type_name: "EventStream".to_string(),
-                        description: "Band-scoped events (may be empty).".to_string(),
-                        readonly: true,
+                        name: "color".to_string(),
+                        type_name: "Color".to_string(),
+                        description: "Fill color; alpha blends over the frame.".to_string(),
+                        readonly: false,
                         optional: false,
                     },
                 ],
                 methods: vec![],
             },
-            // ================================================================
-            // Feedback Builder Types
-            // ================================================================
             ApiType {
-                name: "Feedback".to_string(),
-                kind: ApiTypeKind::Namespace,
-                description: "Frame feedback namespace for Milkdrop-style temporal visual effects.".to_string(),
+                name: "Mat4".to_string(),
+                kind: ApiTypeKind::Struct,
+                description: "4x4 transform matrix. Compose with `*`: `a * b` applies `b` first, then `a`.".to_string(),
                 properties: vec![],
                 methods: vec![
                     ApiMethod {
-                        name: "builder".to_string(),
-                        description: "Create a new feedback configuration builder.".to_string(),
-                        params: vec![],
-                        returns: "FeedbackBuilder".to_string(),
+                        name: "mul".to_string(),
+                        description: "Compose transforms: the result applies `other` first, then this one. Same as `this * other`.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "other".to_string(),
+                                type_name: "Mat4".to_string(),
+                                description: "Transform applied first.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Mat4".to_string(),
                         overload_id: None,
-                        example: Some("let fb = feedback.builder().warp.spiral(0.5, 0.02).build();".to_string()),
+                        example: Some("let m = math.rotateY(1.0).mul(math.translate(1.0, 0.0, 0.0));".to_string()),
                         notes: None,
                     },
                     ApiMethod {
-                        name: "enable".to_string(),
-                        description: "Enable feedback with a configuration.".to_string(),
-                        params: vec![ApiParam {
-                            name: "config".to_string(),
-                            type_name: "FeedbackConfig".to_string(),
-                            description: "Feedback configuration from builder.build().".to_string(),
-                            optional: false,
-                            default: None,
-                        }],
-                        returns: "void".to_string(),
+                        name: "inverse".to_string(),
+                        description: "Inverse transform.".to_string(),
+                        params: vec![],
+                        returns: "Mat4".to_string(),
                         overload_id: None,
-                        example: Some("feedback.enable(fb);".to_string()),
+                        example: Some("let back = m.inverse();".to_string()),
                         notes: None,
                     },
                     ApiMethod {
-                        name: "disable".to_string(),
-                        description: "Disable feedback effects.".to_string(),
-                        params: vec![],
-                        returns: "void".to_string(),
+                        name: "transformPoint".to_string(),
+                        description: "Transform a position (translation applies).".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "point".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Position to transform.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Vec3".to_string(),
                         overload_id: None,
-                        example: Some("feedback.disable();".to_string()),
+                        example: Some("cube.position = m.transformPoint(#{ x: 0.0, y: 0.0, z: 0.0 });".to_string()),
                         notes: None,
                     },
                     ApiMethod {
-                        name: "is_enabled".to_string(),
-                        description: "Check if feedback is currently enabled.".to_string(),
-                        params: vec![],
-                        returns: "bool".to_string(),
+                        name: "transformVector".to_string(),
+                        description: "Transform a direction (translation ignored).".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "vector".to_string(),
+                                type_name: "Vec3".to_string(),
+                                description: "Direction to transform.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "Vec3".to_string(),
                         overload_id: None,
-                        example: Some("if feedback.is_enabled() { ... }".to_string()),
+                        example: Some("let dir = m.transformVector(#{ x: 0.0, y: 0.0, z: 1.0 });".to_string()),
                         notes: None,
                     },
                 ],
@@ -2127,6 +3376,21 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("camera.pan(0.5, 0.0);".to_string()),
                         notes: None,
                     },
+                    ApiMethod {
+                        name: "shake".to_string(),
+                        description: "Add noise-driven shake on top of the camera position and orientation.".to_string(),
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "Map".to_string(),
+                            description: "`amount` (Signal | float, world units, default 0.1), `frequency` (Hz, default 12), `rotation` (radians per unit of amount, default 0.1), `trigger` (EventStream), `decay` (seconds, default 0.25), `seed` (int, default 0). Pass `()` to stop shaking.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "void".to_string(),
+                        overload_id: None,
+                        example: Some("camera.shake(#{ amount: 0.3, trigger: kicks });".to_string()),
+                        notes: Some("Noise depends only on time and seed, so offline renders reproduce it. With a `trigger`, each event kicks the shake to `amount` times the event weight, decaying exponentially with time constant `decay`.".to_string()),
+                    },
                 ],
             },
             ApiType {
@@ -2155,6 +3419,13 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         readonly: true,
                         optional: false,
                     },
+                    ApiProperty {
+                        name: "mask".to_string(),
+                        type_name: "MaskBuilder".to_string(),
+                        description: "Feedback region namespace.".to_string(),
+                        readonly: true,
+                        optional: false,
+                    },
                 ],
                 methods: vec![
                     ApiMethod {
@@ -2172,6 +3443,30 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some(".opacity(0.9)".to_string()),
                         notes: Some("Default is 0.8.".to_string()),
                     },
+                    ApiMethod {
+                        name: "offset".to_string(),
+                        description: "Translate the previous frame by a fixed UV offset each frame (echo trails).".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "dx".to_string(),
+                                type_name: "float | Signal".to_string(),
+                                description: "Horizontal offset per frame in UV units (positive = right).".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "dy".to_string(),
+                                type_name: "float | Signal".to_string(),
+                                description: "Vertical offset per frame in UV units (positive = down).".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "FeedbackBuilder".to_string(),
+                        overload_id: None,
+                        example: Some(".offset(0.002, 0.0)".to_string()),
+                        notes: Some("Applied after the warp chain. Default is (0, 0).".to_string()),
+                    },
                     ApiMethod {
                         name: "build".to_string(),
                         description: "Build the final feedback configuration.".to_string(),
@@ -2365,10 +3660,25 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                             },
                         ],
                         returns: "FeedbackBuilder".to_string(),
-                        overload_id: None,
+                        overload_id: Some("2-arg".to_string()),
                         example: Some(".warp.noise(0.5, 2.0)".to_string()),
                         notes: None,
                     },
+                    ApiMethod {
+                        name: "noise".to_string(),
+                        description: "Add a time-animated noise displacement warp.".to_string(),
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "Map".to_string(),
+                            description: "#{ strength (float | Signal, default 0.5), scale (float | Signal, default 4.0), speed (float | Signal, default 0.0), seed (int, default 0) }.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "FeedbackBuilder".to_string(),
+                        overload_id: Some("options".to_string()),
+                        example: Some(".warp.noise(#{ strength: 0.4, scale: 6.0, speed: 0.3 })".to_string()),
+                        notes: Some("The field is seeded and driven by frame time, so renders are reproducible.".to_string()),
+                    },
                     ApiMethod {
                         name: "shear".to_string(),
                         description: "Add a shear warp.".to_string(),
@@ -2477,6 +3787,30 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some(".color.channel_offset(0.5, 0.0)".to_string()),
                         notes: None,
                     },
+                    ApiMethod {
+                        name: "invert".to_string(),
+                        description: "Add a color inversion effect (1 - rgb each frame).".to_string(),
+                        params: vec![],
+                        returns: "FeedbackBuilder".to_string(),
+                        overload_id: None,
+                        example: Some(".color.invert()".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "hueRotate".to_string(),
+                        description: "Add a hue rotation effect.".to_string(),
+                        params: vec![ApiParam {
+                            name: "angle".to_string(),
+                            type_name: "float | Signal".to_string(),
+                            description: "Rotation per frame in radians (red -> green -> blue).".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "FeedbackBuilder".to_string(),
+                        overload_id: None,
+                        example: Some(".color.hueRotate(0.05)".to_string()),
+                        notes: Some("2π is a full turn (no change); π gives the complementary hue.".to_string()),
+                    },
                 ],
             },
             ApiType {
@@ -2550,6 +3884,85 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                     },
                 ],
             },
+            ApiType {
+                name: "MaskBuilder".to_string(),
+                kind: ApiTypeKind::Opaque,
+                description: "Feedback region (returned by `builder.mask`). Outside the region the current frame shows without trails.".to_string(),
+                properties: vec![],
+                methods: vec![
+                    ApiMethod {
+                        name: "circle".to_string(),
+                        description: "Apply feedback only inside a circle.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "center".to_string(),
+                                type_name: "Vec2".to_string(),
+                                description: "Circle center in UV coordinates; x and y may be Signals.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "radius".to_string(),
+                                type_name: "float | Signal".to_string(),
+                                description: "Radius in units of frame height.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "softness".to_string(),
+                                type_name: "float | Signal".to_string(),
+                                description: "Width of the soft edge inside the boundary (0 = hard).".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "FeedbackBuilder".to_string(),
+                        overload_id: None,
+                        example: Some(".mask.circle(#{ x: 0.5, y: 0.5 }, 0.3, 0.05)".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "rect".to_string(),
+                        description: "Apply feedback only inside a rectangle.".to_string(),
+                        params: vec![
+                            ApiParam {
+                                name: "center".to_string(),
+                                type_name: "Vec2".to_string(),
+                                description: "Rectangle center in UV coordinates; x and y may be Signals.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "size".to_string(),
+                                type_name: "Vec2".to_string(),
+                                description: "Width and height in UV units; x and y may be Signals.".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                            ApiParam {
+                                name: "softness".to_string(),
+                                type_name: "float | Signal".to_string(),
+                                description: "Width of the soft edge inside the boundary (0 = hard).".to_string(),
+                                optional: false,
+                                default: None,
+                            },
+                        ],
+                        returns: "FeedbackBuilder".to_string(),
+                        overload_id: None,
+                        example: Some(".mask.rect(#{ x: 0.5, y: 0.5 }, #{ x: 0.5, y: 1.0 }, 0.02)".to_string()),
+                        notes: None,
+                    },
+                    ApiMethod {
+                        name: "full".to_string(),
+                        description: "Apply feedback to the whole frame (default).".to_string(),
+                        params: vec![],
+                        returns: "FeedbackBuilder".to_string(),
+                        overload_id: None,
+                        example: Some(".mask.full()".to_string()),
+                        notes: None,
+                    },
+                ],
+            },
             ApiType {
                 name: "FeedbackConfig".to_string(),
                 kind: ApiTypeKind::Opaque,
@@ -2604,7 +4017,7 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         returns: "PostEffect".to_string(),
                         overload_id: None,
                         example: Some("let grade = fx.colorGrade(#{ contrast: 1.1, saturation: 1.2 });".to_string()),
-                        notes: Some("Parameters: brightness, contrast, saturation, gamma, tint.".to_string()),
+                        notes: Some("Parameters: exposure, temperature, tint_shift, brightness, contrast, saturation, gamma, tint.".to_string()),
                     },
                     ApiMethod {
                         name: "vignette".to_string(),
@@ -2636,6 +4049,21 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         example: Some("let dist = fx.distortion(#{ amount: 0.1 });".to_string()),
                         notes: Some("Parameters: amount (-1 to 1), center {x, y}.".to_string()),
                     },
+                    ApiMethod {
+                        name: "radialBlur".to_string(),
+                        description: "Create a radial (zoom) blur effect.".to_string(),
+                        params: vec![ApiParam {
+                            name: "options".to_string(),
+                            type_name: "RadialBlurOptions".to_string(),
+                            description: "Radial blur parameters.".to_string(),
+                            optional: false,
+                            default: None,
+                        }],
+                        returns: "PostEffect".to_string(),
+                        overload_id: None,
+                        example: Some("let blur = fx.radialBlur(#{ strength: 0.3, center: #{ x: 0.5, y: 0.4 } });".to_string()),
+                        notes: Some("Parameters: strength (0-1), center {x, y}, samples (2-32).".to_string()),
+                    },
                 ],
             },
             ApiType {
@@ -2791,6 +4219,27 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         readonly: false,
                         optional: true,
                     },
+                    ApiProperty {
+                        name: "exposure".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Exposure in stops, applied before the other adjustments (+1 doubles). Default: 0.0.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
+                    ApiProperty {
+                        name: "temperature".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "White balance temperature (-1 cool to 1 warm). Default: 0.0.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
+                    ApiProperty {
+                        name: "tint_shift".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "White balance tint (-1 green to 1 magenta). Default: 0.0.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
                 ],
                 methods: vec![],
             },
@@ -2809,7 +4258,7 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                     ApiProperty {
                         name: "smoothness".to_string(),
                         type_name: "float | Signal".to_string(),
-                        description: "Edge smoothness (0-1). Default: 0.5.".to_string(),
+                        description: "Edge smoothness (0-1); without inner_radius the soft edge starts smoothness / 2 inside outer_radius. Default: 0.5.".to_string(),
                         readonly: false,
                         optional: true,
                     },
@@ -2820,6 +4269,34 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                         readonly: false,
                         optional: true,
                     },
+                    ApiProperty {
+                        name: "roundness".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Shape (1 = ellipse, 0 = rounded rectangle). Default: 1.0.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
+                    ApiProperty {
+                        name: "aspect".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Width/height of the shape; above 1 is wider. Default: 1.0.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
+                    ApiProperty {
+                        name: "inner_radius".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Distance from center where darkening starts. Default: outer_radius - smoothness / 2.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
+                    ApiProperty {
+                        name: "outer_radius".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Distance from center where darkening is full. Default: 0.7.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
                 ],
                 methods: vec![],
             },
@@ -2845,6 +4322,35 @@ pub fn script_api_metadata() -> ScriptApiMetadata {
                 ],
                 methods: vec![],
             },
+            ApiType {
+                name: "RadialBlurOptions".to_string(),
+                kind: ApiTypeKind::Struct,
+                description: "Options for fx.radialBlur().".to_string(),
+                properties: vec![
+                    ApiProperty {
+                        name: "strength".to_string(),
+                        type_name: "float | Signal".to_string(),
+                        description: "Blur strength (0-1). Default: 0.0.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
+                    ApiProperty {
+                        name: "center".to_string(),
+                        type_name: "Vec2".to_string(),
+                        description: "Blur center (0-1); x and y may be Signals. Default: {x: 0.5, y: 0.5}.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
+                    ApiProperty {
+                        name: "samples".to_string(),
+                        type_name: "int | Signal".to_string(),
+                        description: "Number of blur samples, clamped to 2-32. Default: 8.".to_string(),
+                        readonly: false,
+                        optional: true,
+                    },
+                ],
+                methods: vec![],
+            },
             ApiType {
                 name: "Vec2".to_string(),
                 kind: ApiTypeKind::Struct,
@@ -17,10 +17,72 @@ pub enum ScriptDiagnosticKind {
     HostApiMisuse,
     /// Internal/host error (e.g. the injected prelude failed).
     HostError,
+    /// Script exceeded a sandbox limit (operations, call depth, data size).
+    ResourceLimit,
     /// Lint warning (not an error, but potentially problematic).
     Warning,
 }
 
+impl ScriptDiagnosticKind {
+    /// Default severity for diagnostics of this kind.
+    pub fn severity(&self) -> ScriptSeverity {
+        match self {
+            ScriptDiagnosticKind::Warning => ScriptSeverity::Warning,
+            _ => ScriptSeverity::Error,
+        }
+    }
+}
+
+/// Stable machine-readable diagnostic code, for editors that handle specific
+/// diagnostics distinctly. Serialized as e.g. `"E_PARSE"`; the message text
+/// may change between versions, codes do not.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+pub enum ScriptDiagnosticCode {
+    #[serde(rename = "E_PARSE")]
+    Parse,
+    #[serde(rename = "E_RUNTIME")]
+    Runtime,
+    #[serde(rename = "E_HOST_API")]
+    HostApiMisuse,
+    #[serde(rename = "E_UNKNOWN_VARIABLE")]
+    UnknownVariable,
+    #[serde(rename = "E_UNKNOWN_PROPERTY")]
+    UnknownProperty,
+    #[serde(rename = "E_UNKNOWN_FUNCTION")]
+    UnknownFunction,
+    #[serde(rename = "E_HOST")]
+    HostError,
+    #[serde(rename = "E_RESOURCE_LIMIT")]
+    ResourceLimit,
+    #[serde(rename = "E_TIMEOUT")]
+    Timeout,
+    #[serde(rename = "W_SCENE_ADD_OUTSIDE_INIT")]
+    SceneAddOutsideInit,
+    #[serde(rename = "W_UNKNOWN_BAND")]
+    UnknownBand,
+}
+
+/// How serious a diagnostic is. Ordered, so `>=` filters by minimum severity.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptSeverity {
+    Info,
+    Warning,
+    Error,
+}
+
+impl ScriptSeverity {
+    /// Parse the serialized name ("info", "warning", "error").
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "info" => Some(ScriptSeverity::Info),
+            "warning" => Some(ScriptSeverity::Warning),
+            "error" => Some(ScriptSeverity::Error),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ScriptPhase {
@@ -40,6 +102,8 @@ pub struct ScriptLocation {
 #[derive(Debug, Clone, Serialize)]
 pub struct ScriptDiagnostic {
     pub kind: ScriptDiagnosticKind,
+    pub code: ScriptDiagnosticCode,
+    pub severity: ScriptSeverity,
     pub phase: ScriptPhase,
     pub message: String,
     pub location: Option<ScriptLocation>,
@@ -94,6 +158,8 @@ pub fn from_parse_error(err: &rhai::ParseError, user_line_offset: usize) -> Scri
 
     ScriptDiagnostic {
         kind: ScriptDiagnosticKind::ParseError,
+        code: ScriptDiagnosticCode::Parse,
+        severity: ScriptSeverity::Error,
         phase: ScriptPhase::Compile,
         message: raw.clone(),
         location,
@@ -107,7 +173,42 @@ pub fn from_eval_error(
     user_line_offset: usize,
 ) -> ScriptDiagnostic {
     let raw = err.to_string();
-    let kind = classify_message(&raw);
+    let (call, flag) = match phase {
+        ScriptPhase::Update => ("update()", "--max-operations"),
+        ScriptPhase::Compile | ScriptPhase::Init => {
+            ("Script loading or init()", "--max-init-operations")
+        }
+    };
+    let (kind, code, message) = if let Some(timeout_ms) = timeout_ms(err) {
+        (
+            ScriptDiagnosticKind::ResourceLimit,
+            ScriptDiagnosticCode::Timeout,
+            format!(
+                "{} timed out after {} ms and was aborted. Do less work per call \
+                 or raise the timeout (SandboxConfig).",
+                call, timeout_ms
+            ),
+        )
+    } else if exceeds_sandbox_limit(err) {
+        let raise = if exceeds_map_size(err) {
+            "SandboxConfig::max_map_size, or set_max_map_size() in the browser".to_string()
+        } else {
+            format!("SandboxConfig, CLI {}", flag)
+        };
+        (
+            ScriptDiagnosticKind::ResourceLimit,
+            ScriptDiagnosticCode::ResourceLimit,
+            format!(
+                "{} - {} exceeded a sandbox limit. Do less work per call \
+                 (smaller loops, fewer entities) or raise the limit ({}).",
+                raw, call, raise
+            ),
+        )
+    } else {
+        let kind = classify_message(&raw);
+        let code = classify_code(err, &kind);
+        (kind, code, raw.clone())
+    };
 
     let pos = err.position();
     let line = pos.line().unwrap_or(0) as u32;
@@ -115,14 +216,69 @@ pub fn from_eval_error(
     let location = map_position_to_user(line, column, user_line_offset);
 
     ScriptDiagnostic {
+        severity: kind.severity(),
         kind,
+        code,
         phase,
-        message: raw.clone(),
+        message,
         location,
         raw: Some(raw),
     }
 }
 
+/// Code for a runtime error: the specific lookup failure if there is one,
+/// otherwise the code of its kind.
+fn classify_code(err: &rhai::EvalAltResult, kind: &ScriptDiagnosticKind) -> ScriptDiagnosticCode {
+    match err {
+        rhai::EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => classify_code(inner, kind),
+        rhai::EvalAltResult::ErrorVariableNotFound(..) => ScriptDiagnosticCode::UnknownVariable,
+        rhai::EvalAltResult::ErrorPropertyNotFound(..) => ScriptDiagnosticCode::UnknownProperty,
+        rhai::EvalAltResult::ErrorFunctionNotFound(..) => ScriptDiagnosticCode::UnknownFunction,
+        _ => match kind {
+            ScriptDiagnosticKind::HostApiMisuse => ScriptDiagnosticCode::HostApiMisuse,
+            ScriptDiagnosticKind::HostError => ScriptDiagnosticCode::HostError,
+            _ => ScriptDiagnosticCode::Runtime,
+        },
+    }
+}
+
+/// Whether the error is a script call being aborted by its wall-clock timeout.
+pub fn is_timeout(err: &rhai::EvalAltResult) -> bool {
+    timeout_ms(err).is_some()
+}
+
+/// The timeout (ms) carried by a timeout termination, looking through
+/// failed function calls.
+fn timeout_ms(err: &rhai::EvalAltResult) -> Option<i64> {
+    match err {
+        rhai::EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => timeout_ms(inner),
+        rhai::EvalAltResult::ErrorTerminated(token, _) => token.as_int().ok(),
+        _ => None,
+    }
+}
+
+/// Whether the error (or the error a failed function call wraps) is a
+/// sandbox limit being hit.
+fn exceeds_sandbox_limit(err: &rhai::EvalAltResult) -> bool {
+    match err {
+        rhai::EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => exceeds_sandbox_limit(inner),
+        rhai::EvalAltResult::ErrorTooManyOperations(_)
+        | rhai::EvalAltResult::ErrorStackOverflow(_)
+        | rhai::EvalAltResult::ErrorDataTooLarge(..) => true,
+        _ => false,
+    }
+}
+
+/// Whether the limit hit was the object map size (e.g. a large array of
+/// entities).
+fn exceeds_map_size(err: &rhai::EvalAltResult) -> bool {
+    match err {
+        rhai::EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => exceeds_map_size(inner),
+        rhai::EvalAltResult::ErrorDataTooLarge(what, _) => what.contains("object map"),
+        _ => false,
+    }
+}
+
 /// Lint the script source for common issues and return warnings.
 ///
 /// Currently checks for:
@@ -162,6 +318,8 @@ pub fn lint_script(source: &str) -> Vec<ScriptDiagnostic> {
 
             warnings.push(ScriptDiagnostic {
                 kind: ScriptDiagnosticKind::Warning,
+                code: ScriptDiagnosticCode::SceneAddOutsideInit,
+                severity: ScriptSeverity::Warning,
                 phase: ScriptPhase::Compile,
                 message: "scene.add() called outside of init() - entities may accumulate on script re-evaluation. Consider moving to init() or calling scene.clear() first.".to_string(),
                 location: Some(ScriptLocation { line: line_num, column }),
@@ -172,3 +330,45 @@ pub fn lint_script(source: &str) -> Vec<ScriptDiagnostic> {
 
     warnings
 }
+
+/// Warn about `bands["<name>"]` lookups (mix or stem bands) whose name is
+/// neither the id nor the label of an available band. Such lookups read 0.
+///
+/// `bands` are the (id, label) pairs of the available frequency bands.
+pub fn lint_band_references(source: &str, bands: &[(String, String)]) -> Vec<ScriptDiagnostic> {
+    const PATTERN: &str = "bands[\"";
+    let mut warnings = Vec::new();
+
+    for (line_idx, line) in source.lines().enumerate() {
+        let mut search_from = 0;
+        while let Some(found) = line[search_from..].find(PATTERN) {
+            let name_start = search_from + found + PATTERN.len();
+            let Some(name_len) = line[name_start..].find('"') else {
+                break;
+            };
+            let name = &line[name_start..name_start + name_len];
+            search_from = name_start + name_len;
+
+            if bands.iter().any(|(id, label)| id == name || label == name) {
+                continue;
+            }
+            warnings.push(ScriptDiagnostic {
+                kind: ScriptDiagnosticKind::Warning,
+                code: ScriptDiagnosticCode::UnknownBand,
+                severity: ScriptSeverity::Warning,
+                phase: ScriptPhase::Compile,
+                message: format!(
+                    "Unknown frequency band \"{}\" - its signals will read 0. Check the band's id or label.",
+                    name
+                ),
+                location: Some(ScriptLocation {
+                    line: (line_idx + 1) as u32,
+                    column: name_start as u32,
+                }),
+                raw: None,
+            });
+        }
+    }
+
+    warnings
+}
@@ -6,6 +6,7 @@
 //! - `describe(x)` -> Map (JSON-like)
 //! - `help(x)` -> String
 //! - `doc("Type.member")` -> Map (JSON-like)
+//! - `help("signals")` / `describe("signals")` -> every Signal operator
 
 use std::collections::HashMap;
 
@@ -129,6 +130,7 @@ fn infer_host_type_name(value: &Dynamic) -> Option<&'static str> {
                         "line_namespace" => "Line",
                         "scene_namespace" => "Scene",
                         "log_namespace" => "Log",
+                        "color_namespace" => "Color",
                         "dbg_namespace" => "Dbg",
                         "gen_namespace" => "Gen",
                         "inputs_signals" => "InputsSignals",
@@ -178,6 +180,81 @@ fn help_for_type(t: &ApiType) -> String {
     out
 }
 
+/// One Signal operator in the cheat sheet.
+pub(crate) struct SignalOperation {
+    /// Call path relative to a signal, e.g. `smooth.exponential`.
+    pub name: String,
+    /// `name(param: type, ...) -> returns`.
+    pub signature: String,
+    pub description: String,
+}
+
+/// Every operator callable on a Signal: its own methods, then the methods of
+/// each builder it exposes (`smooth`, `normalise`, ...), prefixed with the
+/// builder property. Sourced from the Script API metadata, so it stays in
+/// step with editor docs.
+pub(crate) fn signal_operations(types: &HashMap<&str, &ApiType>) -> Vec<SignalOperation> {
+    let Some(signal) = types.get("Signal") else {
+        return Vec::new();
+    };
+    let own = signal.methods.iter().map(|m| ("", m));
+    let builders = signal.properties.iter().flat_map(|p| {
+        types
+            .get(p.type_name.as_str())
+            .map(|t| t.methods.iter().map(move |m| (p.name.as_str(), m)))
+            .into_iter()
+            .flatten()
+    });
+
+    own.chain(builders)
+        .map(|(prefix, m)| {
+            let name = if prefix.is_empty() {
+                m.name.clone()
+            } else {
+                format!("{}.{}", prefix, m.name)
+            };
+            let params = m
+                .params
+                .iter()
+                .map(|p| format!("{}: {}", p.name, p.type_name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            SignalOperation {
+                signature: format!("{}({}) -> {}", name, params, m.returns),
+                name,
+                description: m.description.clone(),
+            }
+        })
+        .collect()
+}
+
+fn describe_signal_operations(types: &HashMap<&str, &ApiType>) -> Map {
+    let mut operations = Array::new();
+    for op in signal_operations(types) {
+        let mut m = Map::new();
+        m.insert("name".into(), Dynamic::from(op.name));
+        m.insert("signature".into(), Dynamic::from(op.signature));
+        m.insert("description".into(), Dynamic::from(op.description));
+        operations.push(Dynamic::from(m));
+    }
+    let mut m = Map::new();
+    m.insert("kind".into(), Dynamic::from("signal_operations"));
+    m.insert("operations".into(), Dynamic::from(operations));
+    m
+}
+
+fn is_signal_operations_query(query: &str) -> bool {
+    query.eq_ignore_ascii_case("signal") || query.eq_ignore_ascii_case("signals")
+}
+
+fn help_for_signal_operations(types: &HashMap<&str, &ApiType>) -> String {
+    let mut out = String::from("Signal operators\n\n");
+    for op in signal_operations(types) {
+        out.push_str(&format!("- {}\n    {}\n", op.signature, op.description));
+    }
+    out
+}
+
 fn doc_lookup(
     path: &str,
     types: &HashMap<&str, &ApiType>,
@@ -258,6 +335,9 @@ pub fn register_introspection_api(engine: &mut Engine) {
 
         // Convenience: describe("Type.method") routes to doc lookup.
         if let Ok(s) = value.clone().into_immutable_string() {
+            if is_signal_operations_query(&s) {
+                return describe_signal_operations(&type_index);
+            }
             return doc_lookup(s.as_str(), &type_index, &global_index);
         }
 
@@ -297,6 +377,10 @@ pub fn register_introspection_api(engine: &mut Engine) {
         let type_index = build_type_index(&api.types);
 
         if let Ok(s) = value.clone().into_immutable_string() {
+            // help("signals") lists every Signal operator, builders included.
+            if is_signal_operations_query(&s) {
+                return ImmutableString::from(help_for_signal_operations(&type_index));
+            }
             // help("Type") behaves like doc("Type") but formatted.
             if let Some(t) = type_index.get(s.as_str()) {
                 return ImmutableString::from(help_for_type(t));
@@ -313,3 +397,42 @@ pub fn register_introspection_api(engine: &mut Engine) {
         ImmutableString::from(format!("{} (no host docs)", value.type_name()))
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_operations_list_methods_and_builders() {
+        let api = script_api_metadata();
+        let ops = signal_operations(&build_type_index(&api.types));
+        let names: Vec<&str> = ops.iter().map(|op| op.name.as_str()).collect();
+
+        for expected in [
+            "add",
+            "smoothstep",
+            "normalizeWindow",
+            "smooth.exponential",
+            "normalise.robust",
+            "gate.threshold",
+        ] {
+            assert!(names.contains(&expected), "missing {}", expected);
+        }
+        let robust = ops.iter().find(|op| op.name == "normalise.robust").unwrap();
+        assert_eq!(robust.signature, "normalise.robust() -> Signal");
+    }
+
+    #[test]
+    fn test_help_signals_is_case_insensitive() {
+        let mut engine = Engine::new();
+        register_introspection_api(&mut engine);
+        let help = engine.eval::<String>(r#"help("signals")"#).unwrap();
+        assert!(help.contains("smooth.exponential("));
+        assert_eq!(help, engine.eval::<String>(r#"help("Signals")"#).unwrap());
+
+        let first = engine
+            .eval::<String>(r#"describe("signals").operations[0].signature"#)
+            .unwrap();
+        assert!(first.starts_with("add("));
+    }
+}
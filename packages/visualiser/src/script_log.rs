@@ -5,7 +5,7 @@
 //! - WASM: browser console (console.log/warn/error)
 //! - Native: stdout/stderr with level prefixes
 
-use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
 
 /// Maximum number of log messages allowed per frame to prevent spam.
 const MAX_LOGS_PER_FRAME: u32 = 100;
@@ -16,6 +16,9 @@ static LOG_COUNT: AtomicU32 = AtomicU32::new(0);
 /// Whether we've already warned about exceeding the log limit this frame.
 static WARNED_LIMIT: AtomicU32 = AtomicU32::new(0);
 
+/// Whether script logs are currently dropped (while replaying frames after a seek).
+static MUTED: AtomicBool = AtomicBool::new(false);
+
 /// Log level for script messages.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
@@ -100,9 +103,14 @@ fn emit_log_native(level: LogLevel, message: &str) {
     }
 }
 
+/// Drop script log messages until called again with `false`.
+pub fn set_muted(muted: bool) {
+    MUTED.store(muted, Ordering::Relaxed);
+}
+
 /// Log a message from a script, respecting the per-frame limit.
 pub fn script_log(level: LogLevel, message: &str) {
-    if can_log() {
+    if !MUTED.load(Ordering::Relaxed) && can_log() {
         emit_log(level, message);
     }
 }
@@ -52,57 +52,85 @@ thread_local! {
 // Atomic counter for effect IDs
 static EFFECT_ID_COUNTER: AtomicI64 = AtomicI64::new(0);
 
+use crate::background::SceneBackground;
 use crate::camera::{CameraConfig, CameraUniforms};
 use crate::camera_rhai::{generate_camera_namespace, sync_camera_from_scope};
+use crate::colormap::Colormap;
 use crate::debug_collector::debug_emit;
 use crate::debug_markers::{
-    add_marker_request, DebugMarkerRequest, MarkerSpreadMode, ShowEventsOptions,
+    add_marker_request, DebugMarkerRequest, MarkerShape, MarkerSpreadMode, ShowEventsOptions,
 };
 use crate::deformation::{DeformAxis, Deformation};
+use crate::error::VisualiserError;
 use crate::event_rhai::{get_authored_event_stream_names, get_named_event_stream_names};
 use crate::event_stream::EventStream;
+use crate::fog::SceneFog;
+use crate::gpu::mesh::{
+    DEFAULT_PLANE_SUBDIVISIONS, DEFAULT_SPHERE_SEGMENTS, MAX_PLANE_SUBDIVISIONS,
+    MAX_SPHERE_SEGMENTS, MIN_SPHERE_SEGMENTS,
+};
 use crate::input::{BandSignalMap, SignalMap};
 use crate::lighting::{LightingConfig, LightingUniforms};
 use crate::lighting_rhai::{generate_lighting_namespace, sync_lighting_from_scope};
+use crate::math_rhai::{generate_math_namespace, register_math_api};
 use crate::musical_time::MusicalTimeStructure;
 use crate::particle_rhai::{
     generate_particles_namespace, register_particle_api, set_global_particle_seed,
 };
-use crate::perf_profiling::{should_log_collections, time_end, time_start};
-use crate::post_processing::{EffectParamValue, PostEffectInstance, PostProcessingChain};
+use crate::perf_profiling::{now_ms, should_log_collections, time_end, time_start};
+use crate::post_processing::{
+    EffectParamValue, PostEffectInstance, PostProcessingChain, RADIAL_BLUR_MAX_SAMPLES,
+};
+use crate::preset_rhai::{generate_preset_namespace, register_preset_api};
+use crate::reference_grid::ReferenceGridOptions;
 use crate::scene_graph::{
-    EntityId, LineMode, LineStrip as SceneLineStrip, MeshType, PointCloudMode, RadialWave,
-    RenderMode, Ribbon, RibbonMode, SceneEntity, SceneGraph, MAX_LINE_POINTS,
-    MAX_POINT_CLOUD_POINTS, MAX_RADIAL_WAVE_RESOLUTION,
+    AnimationPlayback, EntityId, LineMode, LineStrip as SceneLineStrip, MeshType, PointCloudMode,
+    RadialWave, RenderMode, Ribbon, RibbonMode, SceneBlendMode, SceneEntity, SceneGraph,
+    SceneTonemap, MAX_LINE_POINTS, MAX_POINT_CLOUD_POINTS, MAX_RADIAL_WAVE_RESOLUTION,
 };
 use crate::script_diagnostics::{
-    from_eval_error, from_parse_error, lint_script, ScriptDiagnostic, ScriptPhase,
+    from_eval_error, from_parse_error, is_timeout, lint_band_references, lint_script,
+    ScriptDiagnostic, ScriptPhase, ScriptSeverity,
 };
 use crate::script_introspection::register_introspection_api;
 use crate::script_log::{reset_frame_log_count, ScriptLogger};
 use crate::signal::Signal;
 use crate::signal_eval::EvalContext;
-use crate::signal_explorer::{sample_signal_chain, ScriptSignalInfo, SignalChainAnalysis};
+use crate::signal_explorer::{
+    sample_signal_chain, sample_signal_range, ScriptSignalInfo, SignalChainAnalysis,
+};
 use crate::signal_rhai::{
     clear_current_input_signals, generate_bands_namespace, generate_composed_signals_namespace,
     generate_custom_events_namespace, generate_custom_signals_namespace,
-    generate_event_streams_namespace, generate_inputs_namespace, generate_stems_namespace,
-    register_signal_api, set_current_composed_signals, set_current_custom_signals,
-    set_current_input_signals, SIGNAL_API_RHAI,
+    generate_event_streams_namespace, generate_inputs_namespace, generate_midi_namespace,
+    generate_stems_namespace, register_signal_api, set_current_composed_signals,
+    set_current_custom_signals, set_current_input_signals, set_signal_probe, take_signal_probe,
+    SignalProbe, SIGNAL_API_RHAI,
 };
 use crate::signal_state::SignalState;
 use crate::signal_stats::StatisticsCache;
+use crate::sparkline::{DebugPlot, MAX_DEBUG_PLOTS};
+use crate::spectrogram::{spectrum_level, SpectrogramOptions};
 use std::sync::Arc;
 
 /// Global debug options set by scripts.
 /// These are read by the visualiser after each update.
 static DEBUG_WIREFRAME: AtomicBool = AtomicBool::new(false);
 static DEBUG_BOUNDING_BOXES: AtomicBool = AtomicBool::new(false);
+static DEBUG_SHOW_WAVEFORM: AtomicBool = AtomicBool::new(false);
+static DEBUG_SHOW_AXES: AtomicBool = AtomicBool::new(false);
+/// Set by dbg.captureReplay(); consumed by the host via take_replay_request().
+static DEBUG_REPLAY_REQUESTED: AtomicBool = AtomicBool::new(false);
 /// 0 means no isolation, any other value is the entity ID to isolate.
 static DEBUG_ISOLATED_ENTITY: AtomicU64 = AtomicU64::new(0);
+/// Post effect ID soloed via dbg.soloEffect(), if any.
+static DEBUG_SOLO_EFFECT: Mutex<Option<String>> = Mutex::new(None);
 /// Per-entity debug bounding box toggles.
 static DEBUG_BOUNDS_ENTITIES: LazyLock<Mutex<StdHashSet<u64>>> =
     LazyLock::new(|| Mutex::new(StdHashSet::new()));
+/// Entities with an axis gizmo (via dbg.showAxes(entity)).
+static DEBUG_AXES_ENTITIES: LazyLock<Mutex<StdHashSet<u64>>> =
+    LazyLock::new(|| Mutex::new(StdHashSet::new()));
 
 fn bounded_script_usize(value: Option<i64>, default: usize, min: usize, max: usize) -> usize {
     value
@@ -126,6 +154,41 @@ thread_local! {
     static PENDING_FEEDBACK_CONFIG: std::cell::RefCell<Option<crate::feedback::FeedbackConfig>> = const { std::cell::RefCell::new(None) };
 }
 
+/// What a debug plot samples each frame.
+#[derive(Clone)]
+enum DebugPlotSource {
+    /// `dbg.plot(name, value)`: a Signal or number.
+    Value(Dynamic),
+    /// `dbg.plotParam(effect, param)`: an evaluated post-effect or feedback
+    /// parameter, read after the post chain and feedback are synced.
+    Param { effect: String, param: String },
+}
+
+// Pending dbg.plot()/dbg.plotParam() registrations: (plot name, source).
+thread_local! {
+    static PENDING_DEBUG_PLOTS: std::cell::RefCell<Vec<(String, DebugPlotSource)>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+// Entity descriptions for dbg.describeEntity(), keyed by entity id. Refreshed
+// after each scene-graph sync, so they reflect evaluated Signals.
+thread_local! {
+    static ENTITY_DESCRIPTIONS: std::cell::RefCell<std::collections::HashMap<u64, rhai::Map>> =
+        std::cell::RefCell::new(std::collections::HashMap::new());
+}
+
+// Pending dbg.showSpectrogram() options (last call wins).
+thread_local! {
+    static PENDING_SPECTROGRAM: std::cell::RefCell<Option<SpectrogramOptions>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+// Pending dbg.showGrid()/dbg.hideGrid() call (last call wins): Some(None) hides.
+thread_local! {
+    static PENDING_REFERENCE_GRID: std::cell::RefCell<Option<Option<ReferenceGridOptions>>> =
+        const { std::cell::RefCell::new(None) };
+}
+
 /// Debug options requested by the script.
 #[derive(Debug, Clone, Default)]
 pub struct ScriptDebugOptions {
@@ -135,6 +198,14 @@ pub struct ScriptDebugOptions {
     pub isolated_entity: Option<u64>,
     /// Per-entity debug bounding box toggles (via dbg.showBounds()).
     pub debug_bounds_entities: StdHashSet<u64>,
+    /// Full-track waveform overlay (via dbg.showWaveform()).
+    pub show_waveform: bool,
+    /// Only run this post effect (via dbg.soloEffect()).
+    pub solo_effect: Option<String>,
+    /// Axis gizmo at the world origin (via dbg.showAxes(true)).
+    pub show_axes: bool,
+    /// Entities with an axis gizmo (via dbg.showAxes(entity)).
+    pub axes_entities: StdHashSet<u64>,
 }
 
 /// Get the current debug options set by scripts.
@@ -149,19 +220,130 @@ pub fn get_script_debug_options() -> ScriptDebugOptions {
         bounding_boxes: DEBUG_BOUNDING_BOXES.load(Ordering::Relaxed),
         isolated_entity: if isolated == 0 { None } else { Some(isolated) },
         debug_bounds_entities: bounds_entities,
+        show_waveform: DEBUG_SHOW_WAVEFORM.load(Ordering::Relaxed),
+        solo_effect: DEBUG_SOLO_EFFECT
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default(),
+        show_axes: DEBUG_SHOW_AXES.load(Ordering::Relaxed),
+        axes_entities: DEBUG_AXES_ENTITIES
+            .lock()
+            .map(|guard| guard.clone())
+            .unwrap_or_default(),
     }
 }
 
+/// Take a pending `dbg.captureReplay()` request, clearing it.
+pub fn take_replay_request() -> bool {
+    DEBUG_REPLAY_REQUESTED.swap(false, Ordering::Relaxed)
+}
+
 /// Reset script debug options to defaults.
 pub fn reset_script_debug_options() {
     DEBUG_WIREFRAME.store(false, Ordering::Relaxed);
     DEBUG_BOUNDING_BOXES.store(false, Ordering::Relaxed);
+    DEBUG_SHOW_WAVEFORM.store(false, Ordering::Relaxed);
+    DEBUG_SHOW_AXES.store(false, Ordering::Relaxed);
+    DEBUG_REPLAY_REQUESTED.store(false, Ordering::Relaxed);
     DEBUG_ISOLATED_ENTITY.store(0, Ordering::Relaxed);
+    if let Ok(mut guard) = DEBUG_SOLO_EFFECT.lock() {
+        *guard = None;
+    }
     if let Ok(mut guard) = DEBUG_BOUNDS_ENTITIES.lock() {
         guard.clear();
     }
+    if let Ok(mut guard) = DEBUG_AXES_ENTITIES.lock() {
+        guard.clear();
+    }
+}
+
+/// Rhai sandbox limits. Exceeding one aborts the current script call with a
+/// `resource_limit` diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxConfig {
+    /// Operations per update() call. Guards against runaway per-frame
+    /// loops; heavy per-frame work may need more.
+    pub max_operations: u64,
+    /// Operations for loading the script (top-level code) and for init(),
+    /// each. Larger than the per-frame budget so setup can build geometry.
+    pub max_init_operations: u64,
+    /// Nested function call depth.
+    pub max_call_levels: usize,
+    /// Expression nesting depth, globally and inside functions.
+    pub max_expr_depth: usize,
+    pub max_function_expr_depth: usize,
+    /// Maximum string length in characters.
+    pub max_string_size: usize,
+    /// Maximum array length (large for mesh assets).
+    pub max_array_size: usize,
+    /// Maximum number of map entries, counting nested maps: an array of
+    /// entities counts every entity's properties (about 45 per mesh), so
+    /// this is sized for scene.grid()s of around a thousand cells.
+    pub max_map_size: usize,
+    /// Wall-clock limit for one update() call, in milliseconds. Catches
+    /// scripts that stall the render thread without tripping the operation
+    /// limit (e.g. slow host calls). None disables it.
+    pub update_timeout_ms: Option<u64>,
+    /// Wall-clock limit for loading the script and for init(), each.
+    pub init_timeout_ms: Option<u64>,
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            max_operations: 100_000,
+            max_init_operations: 1_000_000,
+            max_call_levels: 64,
+            max_expr_depth: 64,
+            max_function_expr_depth: 64,
+            max_string_size: 10_000,
+            max_array_size: 100_000,
+            max_map_size: 50_000,
+            update_timeout_ms: Some(1_000),
+            init_timeout_ms: Some(10_000),
+        }
+    }
+}
+
+impl SandboxConfig {
+    /// Apply the limits, with the operation budget for `phase` (Rhai's
+    /// operation limit is engine-wide, so it is switched around each call).
+    fn apply(&self, engine: &mut Engine, phase: ScriptPhase) {
+        engine.set_max_expr_depths(self.max_expr_depth, self.max_function_expr_depth);
+        engine.set_max_call_levels(self.max_call_levels);
+        engine.set_max_operations(self.operation_budget(phase));
+        engine.set_max_string_size(self.max_string_size);
+        engine.set_max_array_size(self.max_array_size);
+        engine.set_max_map_size(self.max_map_size);
+    }
+
+    fn operation_budget(&self, phase: ScriptPhase) -> u64 {
+        match phase {
+            ScriptPhase::Update => self.max_operations,
+            ScriptPhase::Compile | ScriptPhase::Init => self.max_init_operations,
+        }
+    }
+
+    fn timeout_ms(&self, phase: ScriptPhase) -> Option<u64> {
+        match phase {
+            ScriptPhase::Update => self.update_timeout_ms,
+            ScriptPhase::Compile | ScriptPhase::Init => self.init_timeout_ms,
+        }
+    }
 }
 
+/// The script timeout is checked every this many operations (reading the
+/// clock on every operation would dominate tight loops).
+const TIMEOUT_CHECK_INTERVAL: u64 = 1024;
+
+/// Smallest spectrum bar height, as a fraction of its full height: a
+/// zero-height bar would have a degenerate transform.
+const MIN_SPECTRUM_BAR_LEVEL: f32 = 0.01;
+
+/// Deadline of the current script call: (deadline in [`now_ms`] time,
+/// timeout in ms). Shared with the engine's progress callback.
+type ScriptDeadline = std::rc::Rc<std::cell::Cell<Option<(f64, u64)>>>;
+
 /// Scripting engine that manages Rhai VM lifecycle and scene graph.
 pub struct ScriptEngine {
     engine: Engine,
@@ -189,15 +371,23 @@ pub struct ScriptEngine {
     available_custom_signals: Vec<(String, String)>,
     /// Available composed signals: (id, label) pairs
     available_composed_signals: Vec<(String, String)>,
+    /// Available MIDI notes: (channel, note) pairs
+    available_midi_notes: Vec<(u8, u8)>,
     /// Runtime state for stateful Signal operations (smooth, gates, delay, etc.)
     signal_state: SignalState,
     /// Precomputed signal statistics for normalization (optional/empty until populated).
     signal_statistics: StatisticsCache,
+    /// Values read via signal.value() this frame, reused when syncing.
+    probed_values: HashMap<crate::signal::SignalId, f32>,
     /// Post-processing effect chain
     pub post_chain: PostProcessingChain,
     /// Global seed for deterministic particle systems.
     /// Used as base seed when particle configs don't specify their own.
     global_seed: u64,
+    /// Sandbox limits; the operation budget is switched per phase.
+    sandbox: SandboxConfig,
+    /// Wall-clock deadline of the current script call, if any.
+    deadline: ScriptDeadline,
     /// Frame counter for time.frames signal.
     frame_count: u64,
     /// Frame feedback configuration (V7)
@@ -208,6 +398,16 @@ pub struct ScriptEngine {
     pub camera_config: CameraConfig,
     /// Evaluated camera uniforms (signals resolved to f32 values for renderer).
     pub camera_uniforms: CameraUniforms,
+    /// Scene-wide blend mode set via `scene.blendMode`.
+    pub scene_blend_mode: SceneBlendMode,
+    /// Exposure applied before tonemapping, set via `scene.exposure`.
+    pub scene_exposure: f32,
+    /// Tonemapping curve set via `scene.tonemap`.
+    pub scene_tonemap: SceneTonemap,
+    /// Background drawn before the scene, set via `scene.background`.
+    pub scene_background: Option<SceneBackground>,
+    /// Distance fog, set via `scene.fog()`.
+    pub scene_fog: Option<SceneFog>,
     /// Lighting configuration with signal support.
     pub lighting_config: LightingConfig,
     /// Evaluated lighting uniforms (signals resolved to f32 values for renderer).
@@ -223,6 +423,47 @@ pub struct ScriptEngine {
     /// Signals that have been evaluated during init().
     /// Populated by re-evaluating parsed_signal_decls expressions.
     evaluated_signals: HashMap<String, Signal>,
+    /// Debug plots registered via dbg.plot(), in registration order.
+    pub debug_plots: Vec<DebugPlot>,
+    /// Plotted value per debug plot name (Signal or number).
+    debug_plot_sources: HashMap<String, DebugPlotSource>,
+    /// Whether a dbg.plot() past MAX_DEBUG_PLOTS has been reported.
+    warned_debug_plot_cap: bool,
+    /// Set while frames are replayed after a seek: dbg.plot() values and
+    /// script logs from those frames are dropped.
+    warming_up: bool,
+    /// Spectrogram overlay requested via dbg.showSpectrogram().
+    pub spectrogram_options: Option<SpectrogramOptions>,
+    /// Reference grid overlay requested via dbg.showGrid().
+    pub reference_grid: Option<ReferenceGridOptions>,
+    /// Spectrum levels (0-1, lowest frequency first) driving
+    /// `scene.spectrumBars()`; set by the host before each update.
+    pub spectrum: Vec<f32>,
+    /// Most bars in any spectrum bar group at the last sync, so the host
+    /// only analyses audio (at that resolution) when bars exist.
+    pub spectrum_bins: usize,
+}
+
+/// A frozen copy of the synced render state, for A/B comparisons while
+/// editing a script. Restoring it puts the scene back exactly as captured;
+/// script variables are not part of the snapshot, so the next `update()`
+/// syncs the script's own state over it again.
+#[derive(Clone, Debug)]
+pub struct SceneSnapshot {
+    pub scene_graph: SceneGraph,
+    pub post_chain: PostProcessingChain,
+    pub feedback_config: crate::feedback::FeedbackConfig,
+    pub feedback_uniforms: crate::feedback::FeedbackUniforms,
+    pub camera_config: CameraConfig,
+    pub camera_uniforms: CameraUniforms,
+    pub scene_blend_mode: SceneBlendMode,
+    pub scene_exposure: f32,
+    pub scene_tonemap: SceneTonemap,
+    pub scene_background: Option<SceneBackground>,
+    pub scene_fog: Option<SceneFog>,
+    pub lighting_config: LightingConfig,
+    pub lighting_uniforms: LightingUniforms,
+    pub particle_systems: HashMap<u64, crate::particle::ParticleSystem>,
 }
 
 impl ScriptEngine {
@@ -231,12 +472,19 @@ impl ScriptEngine {
         let mut engine = Engine::new();
 
         // Sandbox settings
-        engine.set_max_expr_depths(64, 64);
-        engine.set_max_call_levels(64);
-        engine.set_max_operations(100_000); // Prevent infinite loops
-        engine.set_max_string_size(10_000);
-        engine.set_max_array_size(100_000); // Allow larger arrays for mesh assets
-        engine.set_max_map_size(500);
+        SandboxConfig::default().apply(&mut engine, ScriptPhase::Init);
+
+        // Wall-clock timeout: abort the call once its deadline has passed.
+        // The termination token carries the timeout for the diagnostic.
+        let deadline: ScriptDeadline = Default::default();
+        let progress_deadline = deadline.clone();
+        engine.on_progress(move |operations| {
+            if operations % TIMEOUT_CHECK_INTERVAL != 0 {
+                return None;
+            }
+            let (deadline, timeout_ms) = progress_deadline.get()?;
+            (now_ms() > deadline).then(|| Dynamic::from(timeout_ms as i64))
+        });
 
         // Register standalone logging functions (these can be called from anywhere)
         engine
@@ -278,6 +526,44 @@ impl ScriptEngine {
             DEBUG_BOUNDING_BOXES.store(enabled, Ordering::Relaxed);
         });
 
+        engine.register_fn("__debug_show_waveform", || {
+            DEBUG_SHOW_WAVEFORM.store(true, Ordering::Relaxed);
+        });
+
+        engine.register_fn("__debug_capture_replay", || {
+            DEBUG_REPLAY_REQUESTED.store(true, Ordering::Relaxed);
+        });
+
+        engine.register_fn("__debug_show_spectrogram", |options: rhai::Map| {
+            let options = parse_spectrogram_options(&options);
+            PENDING_SPECTROGRAM.with(|pending| *pending.borrow_mut() = Some(options));
+        });
+
+        engine.register_fn("__debug_show_grid", |options: rhai::Map| {
+            let options = parse_reference_grid_options(&options);
+            PENDING_REFERENCE_GRID.with(|pending| *pending.borrow_mut() = Some(Some(options)));
+        });
+
+        engine.register_fn("__debug_hide_grid", || {
+            PENDING_REFERENCE_GRID.with(|pending| *pending.borrow_mut() = Some(None));
+        });
+
+        // Axis gizmo at the origin; hiding it also removes per-entity gizmos
+        engine.register_fn("__debug_show_axes", |enabled: bool| {
+            DEBUG_SHOW_AXES.store(enabled, Ordering::Relaxed);
+            if !enabled {
+                if let Ok(mut guard) = DEBUG_AXES_ENTITIES.lock() {
+                    guard.clear();
+                }
+            }
+        });
+
+        engine.register_fn("__debug_show_axes_entity", |entity_id: i64| {
+            if let Ok(mut guard) = DEBUG_AXES_ENTITIES.lock() {
+                guard.insert(entity_id as u64);
+            }
+        });
+
         engine.register_fn("__debug_isolate", |entity_id: i64| {
             DEBUG_ISOLATED_ENTITY.store(entity_id as u64, Ordering::Relaxed);
         });
@@ -286,6 +572,41 @@ impl ScriptEngine {
             DEBUG_ISOLATED_ENTITY.store(0, Ordering::Relaxed);
         });
 
+        // Solo a post effect by its effect ID (e.g. "bloom"), or clear with ()
+        engine.register_fn("__debug_solo_effect", |effect_id: Dynamic| {
+            if let Ok(mut guard) = DEBUG_SOLO_EFFECT.lock() {
+                *guard = effect_id.into_string().ok();
+            }
+        });
+
+        // Register a debug plot (Signal or number), sampled every frame by the engine
+        engine.register_fn(
+            "__debug_plot",
+            |name: rhai::ImmutableString, value: Dynamic| {
+                PENDING_DEBUG_PLOTS.with(|plots| {
+                    plots
+                        .borrow_mut()
+                        .push((name.to_string(), DebugPlotSource::Value(value)));
+                });
+            },
+        );
+
+        // Register a debug plot of an evaluated effect parameter, named "effect.param"
+        engine.register_fn(
+            "__debug_plot_param",
+            |effect: rhai::ImmutableString, param: rhai::ImmutableString| {
+                PENDING_DEBUG_PLOTS.with(|plots| {
+                    plots.borrow_mut().push((
+                        format!("{}.{}", effect, param),
+                        DebugPlotSource::Param {
+                            effect: effect.to_string(),
+                            param: param.to_string(),
+                        },
+                    ));
+                });
+            },
+        );
+
         // Toggle per-entity debug bounding box visualization
         engine.register_fn("__debug_toggle_bounds", |entity_id: i64| -> bool {
             let id = entity_id as u64;
@@ -367,6 +688,18 @@ impl ScriptEngine {
             },
         );
 
+        // Describe an entity's synced scene-graph state (unit if unknown)
+        engine.register_fn("__debug_describe_entity", |entity_id: i64| -> Dynamic {
+            ENTITY_DESCRIPTIONS.with(|descriptions| {
+                descriptions
+                    .borrow()
+                    .get(&(entity_id as u64))
+                    .cloned()
+                    .map(Dynamic::from)
+                    .unwrap_or(Dynamic::UNIT)
+            })
+        });
+
         engine.register_fn("__debug_list_effects", || -> rhai::Array {
             use crate::post_processing::PostEffectRegistry;
             let registry = PostEffectRegistry::new();
@@ -403,6 +736,16 @@ impl ScriptEngine {
                             );
                             param_info
                                 .insert("description".into(), Dynamic::from(p.description.clone()));
+                            param_info.insert(
+                                "default".into(),
+                                effect_param_value_to_dynamic(&p.default_value),
+                            );
+                            if let Some(min) = p.min {
+                                param_info.insert("min".into(), Dynamic::from(min));
+                            }
+                            if let Some(max) = p.max {
+                                param_info.insert("max".into(), Dynamic::from(max));
+                            }
                             Dynamic::from(param_info)
                         })
                         .collect();
@@ -413,6 +756,31 @@ impl ScriptEngine {
             },
         );
 
+        // Colormap lookups: color.map(name, value) -> #{ r, g, b, a }
+        fn color_map(
+            name: rhai::ImmutableString,
+            value: f32,
+        ) -> Result<rhai::Map, Box<EvalAltResult>> {
+            let colormap: Colormap = name.parse()?;
+            let [r, g, b] = colormap.rgb(value);
+            let mut color = rhai::Map::new();
+            color.insert("r".into(), Dynamic::from(r));
+            color.insert("g".into(), Dynamic::from(g));
+            color.insert("b".into(), Dynamic::from(b));
+            color.insert("a".into(), Dynamic::from(1.0f32));
+            Ok(color)
+        }
+        engine.register_fn("__color_map", color_map);
+        engine.register_fn("__color_map", |name: rhai::ImmutableString, value: i64| {
+            color_map(name, value as f32)
+        });
+        engine.register_fn("__color_maps", || -> rhai::Array {
+            Colormap::ALL
+                .iter()
+                .map(|colormap| Dynamic::from(colormap.name().to_string()))
+                .collect()
+        });
+
         // Register effect creation functions (fully native to avoid Rhai closure issues)
         engine.register_fn("__fx_create_bloom", |options: rhai::Map| -> rhai::Map {
             let id = EFFECT_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
@@ -504,6 +872,15 @@ impl ScriptEngine {
                     "tint".into(),
                     options.get("tint").cloned().unwrap_or(default_tint),
                 );
+                for key in ["exposure", "temperature", "tint_shift"] {
+                    effect.insert(
+                        key.into(),
+                        options
+                            .get(key)
+                            .cloned()
+                            .unwrap_or_else(|| Dynamic::from(0.0_f64)),
+                    );
+                }
                 PENDING_POST_EFFECTS.with(|cell| {
                     cell.borrow_mut().insert(id, effect.clone());
                 });
@@ -532,6 +909,23 @@ impl ScriptEngine {
                     .cloned()
                     .unwrap_or_else(|| Dynamic::from(0.5_f64)),
             );
+            for (key, default) in [
+                ("roundness", 1.0_f64),
+                ("aspect", 1.0),
+                ("outer_radius", 0.7),
+            ] {
+                effect.insert(
+                    key.into(),
+                    options
+                        .get(key)
+                        .cloned()
+                        .unwrap_or_else(|| Dynamic::from(default)),
+                );
+            }
+            // Left unset, the inner radius follows smoothness (see sync_post_effects_from_scope)
+            if let Some(inner_radius) = options.get("inner_radius") {
+                effect.insert("inner_radius".into(), inner_radius.clone());
+            }
             let default_color = {
                 let mut c = rhai::Map::new();
                 c.insert("r".into(), Dynamic::from(0.0_f64));
@@ -828,6 +1222,123 @@ impl ScriptEngine {
         // Register host-assisted introspection helpers (describe/help/doc)
         register_introspection_api(&mut engine);
 
+        // Register transform math (Mat4)
+        register_math_api(&mut engine);
+
+        // Register parameter presets
+        register_preset_api(&mut engine);
+
+        // entity.lookAt(target): stored on the entity, applied as a rotation
+        // constraint each sync. Targets: an entity, the camera, a #{ x, y, z }
+        // point, or () to remove the constraint.
+        engine.register_fn(
+            "lookAt",
+            |entity: &mut rhai::Map, target: Dynamic| -> Result<(), Box<EvalAltResult>> {
+                if !entity.contains_key("__id") {
+                    return Err("lookAt() must be called on an entity".into());
+                }
+                let constraint = if target.is_unit() {
+                    None
+                } else if let Some(map) = target.read_lock::<rhai::Map>() {
+                    if let Some(id) = map.get("__id").and_then(|d| d.as_int().ok()) {
+                        Some(Dynamic::from(id))
+                    } else if map.get("__type").and_then(|d| d.clone().into_string().ok())
+                        == Some("camera".to_string())
+                    {
+                        Some(Dynamic::from("camera"))
+                    } else if ["x", "y", "z"].iter().all(|key| map.contains_key(*key)) {
+                        Some(Dynamic::from(map.clone()))
+                    } else {
+                        return Err(
+                            "lookAt() target must be an entity, camera, or #{ x, y, z } point"
+                                .into(),
+                        );
+                    }
+                } else {
+                    return Err(format!(
+                        "lookAt() target must be an entity, camera, or #{{ x, y, z }} point, got {}",
+                        target.type_name()
+                    )
+                    .into());
+                };
+                match constraint {
+                    Some(constraint) => entity.insert("__look_at".into(), constraint),
+                    None => entity.remove("__look_at"),
+                };
+                Ok(())
+            },
+        );
+
+        // entity.followPath(points, progress[, #{ spline: true }]): stored on
+        // the entity and sampled into its position each sync.
+        // entity.followPath(()) stops following.
+        fn follow_path(
+            entity: &mut rhai::Map,
+            points: rhai::Array,
+            progress: Dynamic,
+            spline: bool,
+        ) -> Result<(), Box<EvalAltResult>> {
+            if !entity.contains_key("__id") {
+                return Err("followPath() must be called on an entity".into());
+            }
+            if points.is_empty() {
+                return Err("followPath() needs at least one point".into());
+            }
+            let is_number = |d: &Dynamic| d.is_float() || d.is_int() || d.is::<Signal>();
+            for (i, point) in points.iter().enumerate() {
+                let valid = point.read_lock::<rhai::Map>().is_some_and(|map| {
+                    ["x", "y", "z"]
+                        .iter()
+                        .all(|key| map.get(*key).is_some_and(is_number))
+                });
+                if !valid {
+                    return Err(format!(
+                        "followPath() point {} must be a #{{ x, y, z }} map of numbers or Signals",
+                        i
+                    )
+                    .into());
+                }
+            }
+            if !is_number(&progress) {
+                return Err(format!(
+                    "followPath() progress must be a number or Signal, got {}",
+                    progress.type_name()
+                )
+                .into());
+            }
+            let mut path = rhai::Map::new();
+            path.insert("points".into(), Dynamic::from(points));
+            path.insert("progress".into(), progress);
+            path.insert("spline".into(), Dynamic::from(spline));
+            entity.insert("__follow_path".into(), Dynamic::from(path));
+            Ok(())
+        }
+        engine.register_fn(
+            "followPath",
+            |entity: &mut rhai::Map, points: rhai::Array, progress: Dynamic| {
+                follow_path(entity, points, progress, false)
+            },
+        );
+        engine.register_fn(
+            "followPath",
+            |entity: &mut rhai::Map,
+             points: rhai::Array,
+             progress: Dynamic,
+             options: rhai::Map|
+             -> Result<(), Box<EvalAltResult>> {
+                let spline = match options.get("spline") {
+                    Some(value) => value
+                        .as_bool()
+                        .map_err(|_| "followPath() option spline must be a bool")?,
+                    None => false,
+                };
+                follow_path(entity, points, progress, spline)
+            },
+        );
+        engine.register_fn("followPath", |entity: &mut rhai::Map, _stop: ()| {
+            entity.remove("__follow_path");
+        });
+
         Self {
             engine,
             ast: None,
@@ -843,13 +1354,22 @@ impl ScriptEngine {
             available_stems: Vec::new(),
             available_custom_signals: Vec::new(),
             available_composed_signals: Vec::new(),
+            available_midi_notes: Vec::new(),
             signal_state: SignalState::new(),
             signal_statistics: StatisticsCache::new(),
+            probed_values: HashMap::new(),
             post_chain: PostProcessingChain::new(),
             global_seed: 0,
+            sandbox: SandboxConfig::default(),
+            deadline,
             frame_count: 0,
             feedback_config: crate::feedback::FeedbackConfig::default(),
             feedback_uniforms: crate::feedback::FeedbackUniforms::default(),
+            scene_blend_mode: SceneBlendMode::default(),
+            scene_exposure: 1.0,
+            scene_tonemap: SceneTonemap::default(),
+            scene_background: None,
+            scene_fog: None,
             camera_config: CameraConfig::default(),
             lighting_config: LightingConfig::default(),
             lighting_uniforms: LightingUniforms::default(),
@@ -858,9 +1378,41 @@ impl ScriptEngine {
             script_source: String::new(),
             parsed_signal_decls: HashMap::new(),
             evaluated_signals: HashMap::new(),
+            debug_plots: Vec::new(),
+            debug_plot_sources: HashMap::new(),
+            warned_debug_plot_cap: false,
+            warming_up: false,
+            spectrogram_options: None,
+            reference_grid: None,
+            spectrum: Vec::new(),
+            spectrum_bins: 0,
         }
     }
 
+    /// The current sandbox limits.
+    pub fn sandbox_config(&self) -> SandboxConfig {
+        self.sandbox
+    }
+
+    /// Replace the sandbox limits. Takes effect from the next script call;
+    /// call before load_script so init() runs under the new limits too.
+    pub fn set_sandbox_config(&mut self, config: SandboxConfig) {
+        config.apply(&mut self.engine, ScriptPhase::Init);
+        self.sandbox = config;
+    }
+
+    /// Prepare for a script call in `phase`: switch the engine-wide operation
+    /// limit to the phase's budget and start its timeout.
+    fn begin_call(&mut self, phase: ScriptPhase) {
+        self.engine
+            .set_max_operations(self.sandbox.operation_budget(phase.clone()));
+        self.deadline.set(
+            self.sandbox
+                .timeout_ms(phase)
+                .map(|timeout_ms| (now_ms() + timeout_ms as f64, timeout_ms)),
+        );
+    }
+
     /// Set the global seed for deterministic particle systems.
     /// This seed is used as a base when particle configs don't specify their own seed.
     pub fn set_global_seed(&mut self, seed: u64) {
@@ -924,6 +1476,14 @@ impl ScriptEngine {
         self.available_composed_signals = signals;
     }
 
+    /// Set the available MIDI notes for the inputs.midi namespace.
+    /// Call this before load_script to make note signals available.
+    ///
+    /// Each note is represented as a tuple of (channel 1-16, note number).
+    pub fn set_available_midi_notes(&mut self, notes: Vec<(u8, u8)>) {
+        self.available_midi_notes = notes;
+    }
+
     /// Initialize scope with API modules and empty entity tracking.
     fn init_scope(&mut self) {
         self.scope = Scope::new();
@@ -952,9 +1512,25 @@ impl ScriptEngine {
         self.scope.push("__next_id", 1_i64);
     }
 
+    /// Reset stateful signals and particles after a discontinuous time jump.
+    pub fn reset_for_seek(&mut self) {
+        self.signal_state.clear();
+        for system in self.particle_systems.values_mut() {
+            system.reset();
+        }
+    }
+
     /// Load and compile a script.
     /// Returns true if successful, false if there was a compilation error.
     pub fn load_script(&mut self, script: &str) -> bool {
+        self.try_load_script(script).is_ok()
+    }
+
+    /// Load and compile a script, reporting why it failed.
+    ///
+    /// Parse errors are `ScriptCompile`; errors while running the script body are
+    /// `ScriptRuntime`. The message is also stored in `last_error`.
+    pub fn try_load_script(&mut self, script: &str) -> Result<(), VisualiserError> {
         // Reset state
         self.ast = None;
         self.init_scope();
@@ -969,6 +1545,16 @@ impl ScriptEngine {
         self.script_source = script.to_string();
         self.parsed_signal_decls.clear();
         self.evaluated_signals.clear();
+        self.debug_plots.clear();
+        self.debug_plot_sources.clear();
+        self.warned_debug_plot_cap = false;
+        self.spectrogram_options = None;
+        PENDING_SPECTROGRAM.with(|pending| pending.borrow_mut().take());
+        self.reference_grid = None;
+        PENDING_REFERENCE_GRID.with(|pending| pending.borrow_mut().take());
+        self.spectrum_bins = 0;
+        PENDING_DEBUG_PLOTS.with(|plots| plots.borrow_mut().clear());
+        ENTITY_DESCRIPTIONS.with(|descriptions| descriptions.borrow_mut().clear());
 
         // Reset feedback config
         PENDING_FEEDBACK_CONFIG.with(|cell| {
@@ -987,7 +1573,8 @@ impl ScriptEngine {
         let bands_namespace = generate_bands_namespace(&self.available_bands);
 
         // Generate stems namespace based on available stems
-        let stems_namespace = generate_stems_namespace(&self.available_stems);
+        let stems_namespace =
+            generate_stems_namespace(&self.available_stems, &self.available_bands);
 
         // Generate event streams namespace based on available named event streams
         let event_stream_names = get_named_event_stream_names();
@@ -1009,6 +1596,9 @@ impl ScriptEngine {
         let composed_signals_namespace =
             generate_composed_signals_namespace(&self.available_composed_signals);
 
+        // Generate MIDI namespace
+        let midi_namespace = generate_midi_namespace(&self.available_midi_notes);
+
         // Generate particles namespace
         let particles_namespace = generate_particles_namespace();
 
@@ -1018,6 +1608,12 @@ impl ScriptEngine {
         // Generate lighting namespace
         let lighting_namespace = generate_lighting_namespace();
 
+        // Generate math namespace
+        let math_namespace = generate_math_namespace();
+
+        // Generate preset namespace
+        let preset_namespace = generate_preset_namespace();
+
         // Wrap user script with API definitions.
         // Note: Rhai Maps require string keys, so we convert IDs to strings using `"" + id`.
         //
@@ -1042,9 +1638,11 @@ mesh.cube = || {{
     entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.scale = 1.0;
     entity.visible = true;
+    entity.layer = 0;
     entity.color = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
     entity.renderMode = "solid";
     entity.wireframeColor = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
+    entity.wireframeWidth = 1.0;
     entity.deformations = [];
     entity.material = ();
     entity.materialParams = #{{}};
@@ -1065,9 +1663,11 @@ mesh.cube = || {{
         clone.rotation = #{{ x: this.rotation.x, y: this.rotation.y, z: this.rotation.z }};
         clone.scale = this.scale;
         clone.visible = this.visible;
+        clone.layer = this.layer;
         clone.color = #{{ r: this.color.r, g: this.color.g, b: this.color.b, a: this.color.a }};
         clone.renderMode = this.renderMode;
         clone.wireframeColor = #{{ r: this.wireframeColor.r, g: this.wireframeColor.g, b: this.wireframeColor.b, a: this.wireframeColor.a }};
+        clone.wireframeWidth = this.wireframeWidth;
         clone.deformations = [];
         clone.material = this.material;
         clone.materialParams = #{{}};
@@ -1084,21 +1684,30 @@ mesh.cube = || {{
     entity
 }};
 
-mesh.plane = || {{
+// plane() and plane(options) share one closure; the named overloads let
+// scripts leave out the options map.
+fn __mesh_plane() {{ this.__plane(()) }}
+fn __mesh_plane(options) {{ this.__plane(options) }}
+mesh.plane = Fn("__mesh_plane");
+mesh.__plane = |options| {{
+    let opts = if type_of(options) == "map" {{ options }} else {{ #{{}} }};
     let id = __next_id;
     __next_id += 1;
 
     let entity = #{{}};
     entity.__id = id;
     entity.__type = "mesh_plane";
+    entity.__subdivisions = if opts.contains("subdivisions") {{ opts.subdivisions }} else {{ 1 }}; // grid cells per side
 
     entity.position = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.scale = 1.0;
     entity.visible = true;
+    entity.layer = 0;
     entity.color = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
     entity.renderMode = "solid";
     entity.wireframeColor = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
+    entity.wireframeWidth = 1.0;
     entity.deformations = [];
     entity.material = ();
     entity.materialParams = #{{}};
@@ -1114,14 +1723,17 @@ mesh.plane = || {{
         let clone = #{{}};
         clone.__id = id;
         clone.__type = this.__type;
+        clone.__subdivisions = this.__subdivisions;
 
         clone.position = #{{ x: this.position.x, y: this.position.y, z: this.position.z }};
         clone.rotation = #{{ x: this.rotation.x, y: this.rotation.y, z: this.rotation.z }};
         clone.scale = this.scale;
         clone.visible = this.visible;
+        clone.layer = this.layer;
         clone.color = #{{ r: this.color.r, g: this.color.g, b: this.color.b, a: this.color.a }};
         clone.renderMode = this.renderMode;
         clone.wireframeColor = #{{ r: this.wireframeColor.r, g: this.wireframeColor.g, b: this.wireframeColor.b, a: this.wireframeColor.a }};
+        clone.wireframeWidth = this.wireframeWidth;
         clone.deformations = [];
         clone.material = this.material;
         clone.materialParams = #{{}};
@@ -1138,21 +1750,29 @@ mesh.plane = || {{
     entity
 }};
 
-mesh.sphere = || {{
+// Optional options map, as for mesh.plane.
+fn __mesh_sphere() {{ this.__sphere(()) }}
+fn __mesh_sphere(options) {{ this.__sphere(options) }}
+mesh.sphere = Fn("__mesh_sphere");
+mesh.__sphere = |options| {{
+    let opts = if type_of(options) == "map" {{ options }} else {{ #{{}} }};
     let id = __next_id;
     __next_id += 1;
 
     let entity = #{{}};
     entity.__id = id;
     entity.__type = "mesh_sphere";
+    entity.__segments = if opts.contains("segments") {{ opts.segments }} else {{ 32 }}; // longitude segments
 
     entity.position = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.scale = 1.0;
     entity.visible = true;
+    entity.layer = 0;
     entity.color = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
     entity.renderMode = "solid";
     entity.wireframeColor = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
+    entity.wireframeWidth = 1.0;
     entity.deformations = [];
     entity.material = ();
     entity.materialParams = #{{}};
@@ -1168,14 +1788,17 @@ mesh.sphere = || {{
         let clone = #{{}};
         clone.__id = id;
         clone.__type = this.__type;
+        clone.__segments = this.__segments;
 
         clone.position = #{{ x: this.position.x, y: this.position.y, z: this.position.z }};
         clone.rotation = #{{ x: this.rotation.x, y: this.rotation.y, z: this.rotation.z }};
         clone.scale = this.scale;
         clone.visible = this.visible;
+        clone.layer = this.layer;
         clone.color = #{{ r: this.color.r, g: this.color.g, b: this.color.b, a: this.color.a }};
         clone.renderMode = this.renderMode;
         clone.wireframeColor = #{{ r: this.wireframeColor.r, g: this.wireframeColor.g, b: this.wireframeColor.b, a: this.wireframeColor.a }};
+        clone.wireframeWidth = this.wireframeWidth;
         clone.deformations = [];
         clone.material = this.material;
         clone.materialParams = #{{}};
@@ -1192,6 +1815,10 @@ mesh.sphere = || {{
     entity
 }};
 
+// playAnimation(name) and playAnimation(name, options) share one closure
+fn __mesh_play_animation(name) {{ this.__playAnimation(name, ()) }}
+fn __mesh_play_animation(name, options) {{ this.__playAnimation(name, options) }}
+
 mesh.load = |asset_id| {{
     let id = __next_id;
     __next_id += 1;
@@ -1205,16 +1832,36 @@ mesh.load = |asset_id| {{
     entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.scale = 1.0;
     entity.visible = true;
+    entity.layer = 0;
     entity.color = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
     entity.renderMode = "solid";
     entity.wireframeColor = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
+    entity.wireframeWidth = 1.0;
     entity.deformations = [];
+    entity.morphs = #{{}};
+    entity.animation = ();
     entity.material = ();
     entity.materialParams = #{{}};
     entity.lit = true;
     entity.emissive = 0.0;
     entity.shadow = #{{ enabled: false, plane_y: 0.0, opacity: 0.5, radius: 1.0, radius_x: 1.0, radius_z: 1.0, softness: 0.3, offset_x: 0.0, offset_z: 0.0, color: #{{ r: 0.0, g: 0.0, b: 0.0 }} }};
 
+    // Morph target blending: weights (Signal | f32) by target name, additive
+    entity.morph = |name, amount| {{ this.morphs[name] = amount; }};
+
+    // Skeletal animation: one clip at a time, options #{{ speed, loop, time }}
+    entity.playAnimation = Fn("__mesh_play_animation");
+    entity.__playAnimation = |name, options| {{
+        let opts = if type_of(options) == "map" {{ options }} else {{ #{{}} }};
+        let animation = #{{ name: name }};
+        animation.speed = if opts.contains("speed") {{ opts.speed }} else {{ 1.0 }};
+        // `loop` is a Rhai keyword, so it only works as a quoted key
+        animation.looping = if opts.contains("looping") {{ opts.looping }} else if opts.contains("loop") {{ opts["loop"] }} else {{ true }};
+        if opts.contains("time") {{ animation.time = opts.time; }}
+        this.animation = animation;
+    }};
+    entity.stopAnimation = || {{ this.animation = (); }};
+
     // Instance method - creates a new entity sharing geometry with copied properties
     entity.instance = || {{
         let id = __next_id;
@@ -1229,15 +1876,23 @@ mesh.load = |asset_id| {{
         clone.rotation = #{{ x: this.rotation.x, y: this.rotation.y, z: this.rotation.z }};
         clone.scale = this.scale;
         clone.visible = this.visible;
+        clone.layer = this.layer;
         clone.color = #{{ r: this.color.r, g: this.color.g, b: this.color.b, a: this.color.a }};
         clone.renderMode = this.renderMode;
         clone.wireframeColor = #{{ r: this.wireframeColor.r, g: this.wireframeColor.g, b: this.wireframeColor.b, a: this.wireframeColor.a }};
+        clone.wireframeWidth = this.wireframeWidth;
         clone.deformations = [];
+        clone.morphs = #{{}};
+        clone.animation = ();
         clone.material = this.material;
         clone.materialParams = #{{}};
         clone.lit = this.lit;
         clone.emissive = this.emissive;
         clone.shadow = this.shadow;
+        clone.morph = this.morph;
+        clone.playAnimation = this.playAnimation;
+        clone.__playAnimation = this.__playAnimation;
+        clone.stopAnimation = this.stopAnimation;
         clone.instance = this.instance;
 
         __entities["" + id] = clone;
@@ -1277,9 +1932,11 @@ radial.ring = |options| {{
     entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.scale = 1.0;
     entity.visible = true;
+    entity.layer = 0;
     entity.color = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
     entity.renderMode = "solid";
     entity.wireframeColor = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
+    entity.wireframeWidth = 1.0;
     entity.deformations = [];
     entity.material = ();
     entity.materialParams = #{{}};
@@ -1319,9 +1976,11 @@ radial.ring = |options| {{
         clone.rotation = #{{ x: this.rotation.x, y: this.rotation.y, z: this.rotation.z }};
         clone.scale = this.scale;
         clone.visible = this.visible;
+        clone.layer = this.layer;
         clone.color = #{{ r: this.color.r, g: this.color.g, b: this.color.b, a: this.color.a }};
         clone.renderMode = this.renderMode;
         clone.wireframeColor = #{{ r: this.wireframeColor.r, g: this.wireframeColor.g, b: this.wireframeColor.b, a: this.wireframeColor.a }};
+        clone.wireframeWidth = this.wireframeWidth;
         clone.deformations = [];
         clone.material = this.material;
         clone.materialParams = #{{}};
@@ -1401,12 +2060,40 @@ points.cloud = |options| {{
     entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.scale = 1.0;
     entity.visible = true;
+    entity.layer = 0;
     entity.color = #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }};
 
     __entities["" + id] = entity;
     entity
 }};
 
+// UI module - pixel-space overlay drawn after post-processing
+let ui = #{{}};
+ui.__type = "ui_namespace";
+
+ui.rect = |options| {{
+    let id = __next_id;
+    __next_id += 1;
+
+    let entity = #{{}};
+    entity.__id = id;
+    entity.__type = "ui_rect";
+
+    // Top-left corner in pixels, from the top-left of the frame
+    let x = if options.contains("x") {{ options.x }} else {{ 0.0 }};
+    let y = if options.contains("y") {{ options.y }} else {{ 0.0 }};
+    entity.position = #{{ x: x, y: y, z: 0.0 }};
+    entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
+    entity.scale = 1.0;
+    entity.visible = true;
+    entity.width = if options.contains("width") {{ options.width }} else {{ 100.0 }};
+    entity.height = if options.contains("height") {{ options.height }} else {{ 100.0 }};
+    entity.color = if options.contains("color") {{ options.color }} else {{ #{{ r: 1.0, g: 1.0, b: 1.0, a: 1.0 }} }};
+
+    __entities["" + id] = entity;
+    entity
+}};
+
 // Deformation module - creates deformation descriptors for mesh.deformations
 let deform = #{{}};
 deform.__type = "deform_namespace";
@@ -1466,6 +2153,7 @@ line.strip = |options| {{
     entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.scale = 1.0;
     entity.visible = true;
+    entity.layer = 0;
     entity.color = #{{ r: 0.0, g: 1.0, b: 0.0, a: 1.0 }};
 
     // Line-specific methods stored on entity
@@ -1515,6 +2203,7 @@ line.trace = |signal, options| {{
     entity.rotation = #{{ x: 0.0, y: 0.0, z: 0.0 }};
     entity.scale = 1.0;
     entity.visible = true;
+    entity.layer = 0;
     entity.color = #{{ r: 0.0, g: 1.0, b: 0.0, a: 1.0 }};
 
     entity.clear = || {{
@@ -1561,11 +2250,22 @@ line.ribbon = |signal, options| {{
 // Scene module
 let scene = #{{}};
 scene.__type = "scene_namespace";
+scene.blendMode = "normal";
+scene.tonemap = "none";
+scene.exposure = 1.0;
+scene.background = ();
+scene.__fog = ();
+scene.fog = |options| {{ this.__fog = options; }};
 scene.add = |entity| {{
     let id = entity.__id;
     if !__scene_ids.contains(id) {{
         __scene_ids.push(id);
     }}
+    // Particle handles are copied by value; keep the registry in step with configuration
+    // applied after creation (e.g. sys.rotation(...)).
+    if type_of(entity) == "ParticleSystem" {{
+        __particle_systems["" + id] = entity;
+    }}
 }};
 
 scene.remove = |entity| {{
@@ -1625,6 +2325,55 @@ scene.group = || {{
     entity
 }};
 
+// grid() creates cols * rows entities with factory(), lays them out on the XZ
+// plane centred on the origin and adds them to the scene. Cells are returned
+// row by row, so cell (col, row) is at index row * cols + col.
+scene.grid = |factory, options| {{
+    let opts = if type_of(options) == "map" {{ options }} else {{ #{{}} }};
+    let cols = if opts.contains("cols") {{ opts.cols }} else {{ 1 }};
+    let rows = if opts.contains("rows") {{ opts.rows }} else {{ 1 }};
+    let spacing = if opts.contains("spacing") {{ opts.spacing }} else {{ 1.0 }};
+
+    let cells = [];
+    for row in 0..rows {{
+        for col in 0..cols {{
+            let cell = factory.call();
+            cell.position.x = (col - (cols - 1) / 2.0) * spacing;
+            cell.position.z = (row - (rows - 1) / 2.0) * spacing;
+            this.add(cell);
+            cells.push(cell);
+        }}
+    }}
+    cells
+}};
+
+// spectrumBars(options) creates a group of count bars along X, centred on the
+// origin and added to the scene. Each frame the engine sets every bar's
+// height from its spectrum bin (lowest frequency on the left).
+scene.spectrumBars = |options| {{
+    let opts = if type_of(options) == "map" {{ options }} else {{ #{{}} }};
+    let count = if opts.contains("count") {{ opts.count }} else {{ 32 }};
+    let spacing = if opts.contains("spacing") {{ opts.spacing }} else {{ 0.25 }};
+    let height = if opts.contains("height") {{ opts.height }} else {{ 3.0 }};
+
+    let group = this.group();
+    for i in 0..count {{
+        let bar = mesh.cube();
+        bar.scale = spacing * 0.8;
+        bar.position.x = (i - (count - 1) / 2.0) * spacing;
+        if opts.contains("color") {{
+            bar.color = opts.color;
+        }}
+        bar.__spectrum_bar = i;
+        bar.__spectrum_bars = count;
+        bar.__spectrum_height = height;
+        group.add(bar);
+        this.add(bar);
+    }}
+    this.add(group);
+    group
+}};
+
 // Log module - wraps native logging functions
 let log = #{{}};
 log.__type = "log_namespace";
@@ -1632,6 +2381,12 @@ log.info = |msg| {{ __log_info(msg); }};
 log.warn = |msg| {{ __log_warn(msg); }};
 log.error = |msg| {{ __log_error(msg); }};
 
+// Color module - colormap lookups for data visualization
+let color = #{{}};
+color.__type = "color_namespace";
+color.map = |name, value| {{ __color_map(name, value) }};
+color.maps = || {{ __color_maps() }};
+
 // Debug module - for emitting debug signals and controlling debug visualization
 // Signals are collected during analysis mode for visualization
 // In playback mode, emit is a no-op
@@ -1641,14 +2396,29 @@ dbg.emit = |name, value| {{ __debug_emit(name, value); }};
 dbg.wireframe = |enabled| {{ __debug_wireframe(enabled); }};
 dbg.boundingBoxes = |enabled| {{ __debug_bounding_boxes(enabled); }};
 dbg.showBounds = |entity| {{ __debug_toggle_bounds(entity.__id) }};
+dbg.showAxes = |target| {{
+    if type_of(target) == "map" {{ __debug_show_axes_entity(target.__id); }} else {{ __debug_show_axes(target); }}
+}};
 dbg.isolate = |entity| {{ __debug_isolate(entity.__id); }};
 dbg.clearIsolation = || {{ __debug_clear_isolation(); }};
+dbg.soloEffect = |effect| {{
+    __debug_solo_effect(if type_of(effect) == "map" {{ effect.__effect_id }} else {{ effect }});
+}};
+dbg.clearSolo = || {{ __debug_solo_effect(()); }};
 dbg.showEvents = |events| {{ __debug_show_events(events); }};
 dbg.showEventsOpts = |events, options| {{ __debug_show_events_opts(events, options); }};
+dbg.plot = |name, signal| {{ __debug_plot(name, signal); }};
+dbg.plotParam = |effect, param| {{ __debug_plot_param(effect, param); }};
+dbg.showWaveform = || {{ __debug_show_waveform(); }};
+dbg.showSpectrogram = |options| {{ __debug_show_spectrogram(options); }};
+dbg.showGrid = |options| {{ __debug_show_grid(options); }};
+dbg.hideGrid = || {{ __debug_hide_grid(); }};
+dbg.captureReplay = || {{ __debug_capture_replay(); }};
 dbg.listMaterials = || {{ __debug_list_materials() }};
 dbg.describeMaterial = |id| {{ __debug_describe_material(id) }};
 dbg.listEffects = || {{ __debug_list_effects() }};
 dbg.describeEffect = |id| {{ __debug_describe_effect(id) }};
+dbg.describeEntity = |entity| {{ __debug_describe_entity(entity.__id) }};
 
 // Post-processing effect factory (fx namespace)
 let fx = #{{}};
@@ -1798,6 +2568,9 @@ feedback.is_enabled = || {{
 // === Composed Signals Namespace (User-authored named 1D curves) ===
 {composed_signals_namespace}
 
+// === MIDI Namespace (Notes from a MIDI file) ===
+{midi_namespace}
+
 // === Particles Namespace ===
 {particles_namespace}
 
@@ -1807,6 +2580,10 @@ feedback.is_enabled = || {{
 // === Lighting Namespace ===
 {lighting_namespace}
 
+{math_namespace}
+
+{preset_namespace}
+
 // === User Script ===
 "#
         );
@@ -1818,28 +2595,32 @@ feedback.is_enabled = || {{
         match self.engine.compile(&full_script) {
             Ok(ast) => {
                 // Run lint checks on the user script (before prelude)
-                let lint_warnings = lint_script(script);
+                let mut lint_warnings = lint_script(script);
+                lint_warnings.extend(lint_band_references(script, &self.available_bands));
                 for warning in lint_warnings {
                     self.push_diagnostic(warning);
                 }
 
                 // Run the script once to initialize global state and API
+                self.begin_call(ScriptPhase::Init);
                 if let Err(e) = self.engine.run_ast_with_scope(&mut self.scope, &ast) {
                     let diag = from_eval_error(ScriptPhase::Init, &e, self.user_line_offset);
+                    let message = diag.message.clone();
                     self.push_diagnostic(diag);
-                    return false;
+                    return Err(VisualiserError::ScriptRuntime(message));
                 }
                 self.ast = Some(ast);
 
                 // Parse signal declarations from the script source
                 self.parse_signal_declarations();
 
-                true
+                Ok(())
             }
             Err(e) => {
                 let diag = from_parse_error(&e, self.user_line_offset);
+                let message = diag.message.clone();
                 self.push_diagnostic(diag);
-                false
+                Err(VisualiserError::ScriptCompile(message))
             }
         }
     }
@@ -1887,6 +2668,7 @@ feedback.is_enabled = || {{
         ctx.insert("__type".into(), Dynamic::from("init_ctx"));
 
         // Call init if it exists
+        self.begin_call(ScriptPhase::Init);
         let result: Result<(), Box<EvalAltResult>> =
             self.engine
                 .call_fn(&mut self.scope, &ast, "init", (Dynamic::from(ctx),));
@@ -1973,7 +2755,20 @@ feedback.is_enabled = || {{
             .unwrap_or(0);
         let next_id_before = self.scope.get_value::<i64>("__next_id").unwrap_or(-1);
 
+        // Lend the signal state to signal.value() for the duration of update()
+        set_signal_probe(SignalProbe {
+            time,
+            dt,
+            frame_count: self.frame_count,
+            musical_time: musical_time.cloned(),
+            stem_signals: stem_signals.clone(),
+            state: std::mem::take(&mut self.signal_state),
+            statistics: std::mem::take(&mut self.signal_statistics),
+            cache: HashMap::new(),
+        });
+
         time_start("rhai_update");
+        self.begin_call(ScriptPhase::Update);
         let result: Result<(), Box<EvalAltResult>> = self.engine.call_fn(
             &mut self.scope,
             &ast,
@@ -1982,6 +2777,12 @@ feedback.is_enabled = || {{
         );
         time_end("rhai_update");
 
+        if let Some(probe) = take_signal_probe() {
+            self.signal_state = probe.state;
+            self.signal_statistics = probe.statistics;
+            self.probed_values = probe.cache;
+        }
+
         // Log if Rhai update added entities
         if should_log_collections() {
             let entities_after_update = self
@@ -2008,6 +2809,12 @@ feedback.is_enabled = || {{
                 let diag = from_eval_error(ScriptPhase::Update, &e, self.user_line_offset);
                 self.push_diagnostic(diag);
             }
+            // A timed-out update left the scope half-updated: keep showing
+            // the last good frame instead of syncing it.
+            if is_timeout(&e) {
+                clear_current_input_signals();
+                return;
+            }
         }
 
         // Sync entities from scope to scene graph, evaluating any Signal properties at render time.
@@ -2022,6 +2829,10 @@ feedback.is_enabled = || {{
             composed_signals,
             musical_time,
         );
+        self.probed_values.clear();
+        if self.script_source.contains("describeEntity") {
+            self.publish_entity_descriptions();
+        }
         time_end("sync_entities");
 
         // Log collection sizes periodically for performance profiling
@@ -2033,6 +2844,132 @@ feedback.is_enabled = || {{
         clear_current_input_signals();
     }
 
+    /// Mark the following updates as replayed warm-up frames (see
+    /// `VisualiserState::seek`): their dbg.plot() values and script logs are dropped.
+    pub fn set_warming_up(&mut self, warming_up: bool) {
+        self.warming_up = warming_up;
+        crate::script_log::set_muted(warming_up);
+    }
+
+    /// Deep-copy the current synced render state (scene graph, post chain,
+    /// feedback, camera, lighting and particle systems).
+    pub fn capture_snapshot(&self) -> SceneSnapshot {
+        SceneSnapshot {
+            scene_graph: self.scene_graph.clone(),
+            post_chain: self.post_chain.clone(),
+            feedback_config: self.feedback_config.clone(),
+            feedback_uniforms: self.feedback_uniforms,
+            camera_config: self.camera_config.clone(),
+            camera_uniforms: self.camera_uniforms,
+            scene_blend_mode: self.scene_blend_mode,
+            scene_exposure: self.scene_exposure,
+            scene_tonemap: self.scene_tonemap,
+            scene_background: self.scene_background.clone(),
+            scene_fog: self.scene_fog,
+            lighting_config: self.lighting_config.clone(),
+            lighting_uniforms: self.lighting_uniforms,
+            particle_systems: self.particle_systems.clone(),
+        }
+    }
+
+    /// Replace the synced render state with a snapshot. Rendering without
+    /// calling `update()` afterwards shows the snapshot as captured.
+    pub fn restore_snapshot(&mut self, snapshot: &SceneSnapshot) {
+        let snapshot = snapshot.clone();
+        self.scene_graph = snapshot.scene_graph;
+        self.post_chain = snapshot.post_chain;
+        self.feedback_config = snapshot.feedback_config;
+        self.feedback_uniforms = snapshot.feedback_uniforms;
+        self.camera_config = snapshot.camera_config;
+        self.camera_uniforms = snapshot.camera_uniforms;
+        self.scene_blend_mode = snapshot.scene_blend_mode;
+        self.scene_exposure = snapshot.scene_exposure;
+        self.scene_tonemap = snapshot.scene_tonemap;
+        self.scene_background = snapshot.scene_background;
+        self.scene_fog = snapshot.scene_fog;
+        self.lighting_config = snapshot.lighting_config;
+        self.lighting_uniforms = snapshot.lighting_uniforms;
+        self.particle_systems = snapshot.particle_systems;
+    }
+
+    /// Describe an entity's synced scene-graph state: world position,
+    /// effective visibility (including ancestors), parent id, and for meshes
+    /// the mesh type and render mode. None if the entity does not exist.
+    pub fn describe_entity(&self, id: u64) -> Option<rhai::Map> {
+        let entity_id = EntityId(id);
+        let entity = self.scene_graph.get(entity_id)?;
+        let mut result = rhai::Map::new();
+
+        let kind = match entity {
+            SceneEntity::Mesh(_) => "mesh",
+            SceneEntity::Line(_) => "line",
+            SceneEntity::Group(_) => "group",
+            SceneEntity::PointCloud(_) => "point_cloud",
+            SceneEntity::RadialWave(_) => "radial_wave",
+            SceneEntity::Ribbon(_) => "ribbon",
+            SceneEntity::UiRect(_) => "ui_rect",
+        };
+        result.insert("id".into(), Dynamic::from(id as i64));
+        result.insert("type".into(), Dynamic::from(kind.to_string()));
+
+        let world = self
+            .scene_graph
+            .world_matrix(entity_id)
+            .transform_point3(glam::Vec3::ZERO);
+        let mut world_position = rhai::Map::new();
+        world_position.insert("x".into(), Dynamic::from(world.x));
+        world_position.insert("y".into(), Dynamic::from(world.y));
+        world_position.insert("z".into(), Dynamic::from(world.z));
+        result.insert("world_position".into(), Dynamic::from(world_position));
+
+        result.insert(
+            "visible".into(),
+            Dynamic::from(self.scene_graph.is_effectively_visible(entity_id)),
+        );
+        result.insert(
+            "in_scene".into(),
+            Dynamic::from(self.scene_graph.is_in_scene(entity_id)),
+        );
+        result.insert(
+            "parent".into(),
+            self.scene_graph
+                .get_parent(entity_id)
+                .map(|parent| Dynamic::from(parent.0 as i64))
+                .unwrap_or(Dynamic::UNIT),
+        );
+
+        if let SceneEntity::Mesh(mesh) = entity {
+            let mesh_type = match &mesh.mesh_type {
+                MeshType::Cube => "cube".to_string(),
+                MeshType::Plane { .. } => "plane".to_string(),
+                MeshType::Sphere { .. } => "sphere".to_string(),
+                MeshType::Asset(asset_id) => format!("asset:{}", asset_id),
+                MeshType::RadialRing { .. } => "radial_ring".to_string(),
+            };
+            let render_mode = match mesh.render_mode {
+                RenderMode::Solid => "solid",
+                RenderMode::Wireframe => "wireframe",
+                RenderMode::SolidWithWireframe => "solidWithWireframe",
+                RenderMode::WireframeOverlay => "wireframeOverlay",
+            };
+            result.insert("mesh_type".into(), Dynamic::from(mesh_type));
+            result.insert("render_mode".into(), Dynamic::from(render_mode.to_string()));
+        }
+
+        Some(result)
+    }
+
+    /// Refresh the descriptions read by dbg.describeEntity().
+    fn publish_entity_descriptions(&self) {
+        let descriptions = self
+            .scene_graph
+            .entities
+            .keys()
+            .filter_map(|id| Some((id.0, self.describe_entity(id.0)?)))
+            .collect();
+        ENTITY_DESCRIPTIONS.with(|cell| *cell.borrow_mut() = descriptions);
+    }
+
     /// Log collection sizes for performance profiling.
     fn log_collection_sizes(&self) {
         let frame = self.frame_count;
@@ -2108,7 +3045,9 @@ feedback.is_enabled = || {{
             &mut signal_state,
             None, // track_duration - TODO: pass actual track duration when available
         );
-        let mut frame_cache: HashMap<crate::signal::SignalId, f32> = HashMap::new();
+        // Start from the values update() already read via signal.value()
+        let mut frame_cache: HashMap<crate::signal::SignalId, f32> =
+            std::mem::take(&mut self.probed_values);
 
         fn eval_f32_opt(
             value: &Dynamic,
@@ -2140,6 +3079,8 @@ feedback.is_enabled = || {{
         let entities_before = entities.len();
         let mut scope_referenced_ids: std::collections::HashSet<i64> =
             std::collections::HashSet::new();
+        let mut scope_entity_maps: Vec<rhai::Map> = Vec::new();
+        let mut array_entity_maps: Vec<rhai::Map> = Vec::new();
 
         for (name, _is_const, value) in self.scope.iter() {
             // Skip internal variables
@@ -2152,38 +3093,52 @@ feedback.is_enabled = || {{
                 continue;
             }
 
-            // Check if this is an entity Map
-            if let Some(mut scope_entity_map) = value.clone().try_cast::<rhai::Map>() {
-                if let Some(id_dyn) = scope_entity_map.get("__id") {
-                    if let Ok(id) = id_dyn.as_int() {
-                        let key = format!("{}", id);
-                        scope_referenced_ids.insert(id);
+            // Check if this is an entity Map, or an array of them (e.g. from scene.grid())
+            if let Some(map) = value.clone().try_cast::<rhai::Map>() {
+                scope_entity_maps.push(map);
+            } else if let Some(array) = value.read_lock::<rhai::Array>() {
+                array_entity_maps.extend(
+                    array
+                        .iter()
+                        .filter_map(|element| element.clone().try_cast::<rhai::Map>()),
+                );
+            }
+        }
 
-                        // Preserve internal fields from the existing entry (like __parent_id)
-                        // that are managed by group methods rather than user code
-                        if let Some(existing_dyn) = entities.get(key.as_str()) {
-                            if let Some(existing_map) = existing_dyn.clone().try_cast::<rhai::Map>()
-                            {
-                                // Preserve __parent_id if the scope variable doesn't have it
-                                if !scope_entity_map.contains_key("__parent_id") {
-                                    if let Some(parent_id) = existing_map.get("__parent_id") {
-                                        scope_entity_map
-                                            .insert("__parent_id".into(), parent_id.clone());
-                                    }
+        // Entities held directly by a variable win over copies inside arrays
+        let direct_count = scope_entity_maps.len();
+        scope_entity_maps.extend(array_entity_maps);
+        for (index, mut scope_entity_map) in scope_entity_maps.into_iter().enumerate() {
+            if let Some(id_dyn) = scope_entity_map.get("__id") {
+                if let Ok(id) = id_dyn.as_int() {
+                    if index >= direct_count && scope_referenced_ids.contains(&id) {
+                        continue;
+                    }
+                    let key = format!("{}", id);
+                    scope_referenced_ids.insert(id);
+
+                    // Preserve internal fields from the existing entry (like __parent_id)
+                    // that are managed by group methods rather than user code
+                    if let Some(existing_dyn) = entities.get(key.as_str()) {
+                        if let Some(existing_map) = existing_dyn.clone().try_cast::<rhai::Map>() {
+                            // Preserve __parent_id if the scope variable doesn't have it
+                            if !scope_entity_map.contains_key("__parent_id") {
+                                if let Some(parent_id) = existing_map.get("__parent_id") {
+                                    scope_entity_map
+                                        .insert("__parent_id".into(), parent_id.clone());
                                 }
-                                // Preserve __children if the scope variable doesn't have it
-                                if !scope_entity_map.contains_key("__children") {
-                                    if let Some(children) = existing_map.get("__children") {
-                                        scope_entity_map
-                                            .insert("__children".into(), children.clone());
-                                    }
+                            }
+                            // Preserve __children if the scope variable doesn't have it
+                            if !scope_entity_map.contains_key("__children") {
+                                if let Some(children) = existing_map.get("__children") {
+                                    scope_entity_map.insert("__children".into(), children.clone());
                                 }
                             }
                         }
-
-                        // Update the central __entities map with this merged copy
-                        entities.insert(key.into(), Dynamic::from(scope_entity_map));
                     }
+
+                    // Update the central __entities map with this merged copy
+                    entities.insert(key.into(), Dynamic::from(scope_entity_map));
                 }
             }
         }
@@ -2273,20 +3228,23 @@ feedback.is_enabled = || {{
         let mut valid_entity_ids: std::collections::HashSet<EntityId> =
             std::collections::HashSet::new();
 
-        // Sync each entity
+        // Sync each entity in id order. `__entities` is keyed by the id's string form, so its
+        // own order is lexicographic ("10" before "2"); sorting numerically keeps scene order
+        // (and with it mesh uniform slots) and stateful signal evaluation stable.
         time_start("sync_entity_props");
         let _signal_eval_count = 0usize;
-        for (_key, value) in entities.iter() {
-            let entity_map = match value.clone().try_cast::<rhai::Map>() {
-                Some(m) => m,
-                None => continue,
-            };
-
-            let id = match entity_map.get("__id").and_then(|d| d.as_int().ok()) {
-                Some(id) => id as u64,
-                None => continue,
-            };
-
+        let mut ordered_entities: Vec<(u64, rhai::Map)> = entities
+            .values()
+            .filter_map(|value| {
+                let entity_map = value.clone().try_cast::<rhai::Map>()?;
+                let id = entity_map.get("__id").and_then(|d| d.as_int().ok())?;
+                Some((id as u64, entity_map))
+            })
+            .collect();
+        ordered_entities.sort_by_key(|(id, _)| *id);
+        let mut look_at_constraints: Vec<(EntityId, Dynamic)> = Vec::new();
+        let mut spectrum_bins = 0;
+        for (id, entity_map) in ordered_entities {
             let entity_type = match entity_map
                 .get("__type")
                 .and_then(|d| d.clone().into_string().ok())
@@ -2306,10 +3264,24 @@ feedback.is_enabled = || {{
                         self.create_entity_with_id(entity_id, MeshType::Cube);
                     }
                     "mesh_plane" => {
-                        self.create_entity_with_id(entity_id, MeshType::Plane);
-                    }
+                        let subdivisions = bounded_script_u32(
+                            entity_map
+                                .get("__subdivisions")
+                                .and_then(|d| d.as_int().ok()),
+                            DEFAULT_PLANE_SUBDIVISIONS,
+                            1,
+                            MAX_PLANE_SUBDIVISIONS,
+                        );
+                        self.create_entity_with_id(entity_id, MeshType::Plane { subdivisions });
+                    }
                     "mesh_sphere" => {
-                        self.create_entity_with_id(entity_id, MeshType::Sphere);
+                        let segments = bounded_script_u32(
+                            entity_map.get("__segments").and_then(|d| d.as_int().ok()),
+                            DEFAULT_SPHERE_SEGMENTS,
+                            MIN_SPHERE_SEGMENTS,
+                            MAX_SPHERE_SEGMENTS,
+                        );
+                        self.create_entity_with_id(entity_id, MeshType::Sphere { segments });
                     }
                     "mesh_asset" => {
                         let asset_id = entity_map
@@ -2414,6 +3386,9 @@ feedback.is_enabled = || {{
                             entity_id, count, spread, mode, seed, point_size,
                         );
                     }
+                    "ui_rect" => {
+                        self.create_ui_rect_with_id(entity_id);
+                    }
                     "radial_wave" => {
                         let base_radius = entity_map
                             .get("__base_radius")
@@ -2492,6 +3467,43 @@ feedback.is_enabled = || {{
                         .unwrap_or(0.0);
                 }
 
+                // entity.followPath() overrides position with the path sample
+                if let Some(follow) = entity_map
+                    .get("__follow_path")
+                    .and_then(|d| d.clone().try_cast::<rhai::Map>())
+                {
+                    let mut eval = |d: &Dynamic| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache);
+                    let waypoints: Vec<glam::Vec3> = follow
+                        .get("points")
+                        .and_then(|d| d.clone().try_cast::<rhai::Array>())
+                        .unwrap_or_default()
+                        .iter()
+                        .filter_map(|point| point.clone().try_cast::<rhai::Map>())
+                        .map(|point| {
+                            let mut component =
+                                |key: &str| point.get(key).and_then(&mut eval).unwrap_or(0.0);
+                            glam::Vec3::new(component("x"), component("y"), component("z"))
+                        })
+                        .collect();
+                    let spline = follow
+                        .get("spline")
+                        .and_then(|d| d.as_bool().ok())
+                        .unwrap_or(false);
+                    let path = if spline {
+                        crate::path::Path::catmull_rom(&waypoints)
+                    } else {
+                        crate::path::Path::linear(&waypoints)
+                    };
+                    if let Some(path) = path {
+                        let progress = follow.get("progress").and_then(&mut eval).unwrap_or(0.0);
+                        let point = path.sample(progress);
+                        let transform = entity.transform_mut();
+                        transform.position.x = point.x;
+                        transform.position.y = point.y;
+                        transform.position.z = point.z;
+                    }
+                }
+
                 // Rotation
                 if let Some(rot) = entity_map
                     .get("rotation")
@@ -2523,12 +3535,35 @@ feedback.is_enabled = || {{
                     transform.scale.z = scale;
                 }
 
+                // scene.spectrumBars(): height tracks the bar's spectrum bin,
+                // growing up from the group's origin
+                if let Some(bar) = entity_map
+                    .get("__spectrum_bar")
+                    .and_then(|d| d.as_int().ok())
+                {
+                    let bars = entity_map
+                        .get("__spectrum_bars")
+                        .and_then(|d| d.as_int().ok())
+                        .unwrap_or(1)
+                        .max(1) as usize;
+                    let height = entity_map
+                        .get("__spectrum_height")
+                        .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                        .unwrap_or(1.0);
+                    spectrum_bins = spectrum_bins.max(bars);
+                    let level = spectrum_level(&self.spectrum, bar.max(0) as usize, bars)
+                        .max(MIN_SPECTRUM_BAR_LEVEL);
+                    let transform = entity.transform_mut();
+                    transform.scale.y = height * level;
+                    transform.position.y = height * level * 0.5;
+                }
+
                 // Visible
                 if let Some(visible) = entity_map.get("visible").and_then(|d| d.as_bool().ok()) {
                     entity.set_visible(visible);
                 }
 
-                // Mesh-specific: sync color, renderMode, wireframeColor, and deformations
+                // Mesh-specific: sync color, renderMode, wireframeColor/Width, and deformations
                 if let SceneEntity::Mesh(mesh) = entity {
                     if let Some(color) = entity_map
                         .get("color")
@@ -2560,6 +3595,7 @@ feedback.is_enabled = || {{
                         mesh.render_mode = match mode_str.as_str() {
                             "wireframe" => RenderMode::Wireframe,
                             "solidWithWireframe" => RenderMode::SolidWithWireframe,
+                            "wireframeOverlay" => RenderMode::WireframeOverlay,
                             _ => RenderMode::Solid,
                         };
                     }
@@ -2587,6 +3623,13 @@ feedback.is_enabled = || {{
                             .unwrap_or(1.0);
                     }
 
+                    if let Some(width) = entity_map
+                        .get("wireframeWidth")
+                        .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                    {
+                        mesh.wireframe_width = width.max(0.0);
+                    }
+
                     // Sync deformations (with Signal support for numeric params)
                     if let Some(deforms) = entity_map
                         .get("deformations")
@@ -2604,6 +3647,58 @@ feedback.is_enabled = || {{
                         }
                     }
 
+                    // Sync morph weights (with Signal support)
+                    if let Some(morphs) = entity_map
+                        .get("morphs")
+                        .and_then(|d| d.clone().try_cast::<rhai::Map>())
+                    {
+                        mesh.morph_weights = morphs
+                            .iter()
+                            .filter_map(|(name, amount)| {
+                                eval_f32_opt(amount, &mut eval_ctx, &mut frame_cache)
+                                    .map(|amount| (name.to_string(), amount))
+                            })
+                            .collect();
+                    }
+
+                    // Sync skeletal animation: a `time` Signal scrubs the clip,
+                    // otherwise playback advances by dt * speed from the last frame
+                    mesh.animation = entity_map
+                        .get("animation")
+                        .and_then(|d| d.clone().try_cast::<rhai::Map>())
+                        .and_then(|animation| {
+                            let name = animation.get("name")?.clone().into_string().ok()?;
+                            let looping = animation
+                                .get("looping")
+                                .and_then(|d| d.as_bool().ok())
+                                .unwrap_or(true);
+                            let time = match animation
+                                .get("time")
+                                .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                            {
+                                Some(time) => time,
+                                None => {
+                                    let speed = animation
+                                        .get("speed")
+                                        .and_then(|d| {
+                                            eval_f32_opt(d, &mut eval_ctx, &mut frame_cache)
+                                        })
+                                        .unwrap_or(1.0);
+                                    match &mesh.animation {
+                                        Some(playing) if playing.name == name => {
+                                            playing.time + dt * speed
+                                        }
+                                        _ => 0.0,
+                                    }
+                                }
+                            };
+                            Some(AnimationPlayback {
+                                name,
+                                time,
+                                looping,
+                            })
+                        });
+
                     // Sync material ID
                     if let Some(material_str) = entity_map
                         .get("material")
@@ -2633,6 +3728,9 @@ feedback.is_enabled = || {{
                     if let Some(lit) = entity_map.get("lit").and_then(|d| d.as_bool().ok()) {
                         mesh.lit = lit;
                     }
+                    if let Some(layer) = entity_map.get("layer").and_then(|d| d.as_int().ok()) {
+                        mesh.layer = layer as i32;
+                    }
                     if let Some(emissive) = entity_map
                         .get("emissive")
                         .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
@@ -2790,6 +3888,11 @@ feedback.is_enabled = || {{
                             .unwrap_or(1.0);
                     }
 
+                    // Sync draw order layer
+                    if let Some(layer) = entity_map.get("layer").and_then(|d| d.as_int().ok()) {
+                        line.layer = layer as i32;
+                    }
+
                     // Check if this is a line_trace (Signal-driven) or line_strip (manual push)
                     if entity_type == "line_trace" {
                         // line.trace - evaluate signal and push a point each frame
@@ -2886,6 +3989,46 @@ feedback.is_enabled = || {{
                     {
                         cloud.point_size = point_size;
                     }
+                    if let Some(layer) = entity_map.get("layer").and_then(|d| d.as_int().ok()) {
+                        cloud.layer = layer as i32;
+                    }
+                }
+
+                // UiRect-specific: sync size and color
+                if let SceneEntity::UiRect(rect) = entity {
+                    if let Some(width) = entity_map
+                        .get("width")
+                        .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                    {
+                        rect.width = width.max(0.0);
+                    }
+                    if let Some(height) = entity_map
+                        .get("height")
+                        .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                    {
+                        rect.height = height.max(0.0);
+                    }
+                    if let Some(color) = entity_map
+                        .get("color")
+                        .and_then(|d| d.clone().try_cast::<rhai::Map>())
+                    {
+                        rect.color[0] = color
+                            .get("r")
+                            .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                            .unwrap_or(1.0);
+                        rect.color[1] = color
+                            .get("g")
+                            .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                            .unwrap_or(1.0);
+                        rect.color[2] = color
+                            .get("b")
+                            .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                            .unwrap_or(1.0);
+                        rect.color[3] = color
+                            .get("a")
+                            .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                            .unwrap_or(1.0);
+                    }
                 }
 
                 // RadialWave-specific: sync color, parameters, and signal value
@@ -3022,6 +4165,11 @@ feedback.is_enabled = || {{
                 self.scene_graph.clear_parent(entity_id);
             }
 
+            // Look-at constraints are applied once every entity is positioned
+            if let Some(target) = entity_map.get("__look_at") {
+                look_at_constraints.push((entity_id, target.clone()));
+            }
+
             // Add/remove from scene based on scene_ids
             if scene_id_set.contains(&(id as i64)) {
                 self.scene_graph.add_to_scene(entity_id);
@@ -3061,6 +4209,55 @@ feedback.is_enabled = || {{
         self.sync_feedback_from_scope(&mut eval_ctx, &mut frame_cache);
         time_end("sync_feedback");
 
+        // Sample dbg.plot() signals and dbg.plotParam() parameters into their sparklines
+        for (name, source) in
+            PENDING_DEBUG_PLOTS.with(|plots| std::mem::take(&mut *plots.borrow_mut()))
+        {
+            if !self.debug_plot_sources.contains_key(&name) {
+                if self.debug_plots.len() >= MAX_DEBUG_PLOTS {
+                    if !self.warned_debug_plot_cap {
+                        log::warn!(
+                            "dbg.plot: at most {} plots are shown; ignoring '{}' and any further plots",
+                            MAX_DEBUG_PLOTS,
+                            name
+                        );
+                        self.warned_debug_plot_cap = true;
+                    }
+                    continue;
+                }
+                self.debug_plots.push(DebugPlot::new(name.clone()));
+            }
+            self.debug_plot_sources.insert(name, source);
+        }
+        // Frames replayed after a seek only rebuild state, so they add no plot points
+        if !self.warming_up {
+            for plot in &mut self.debug_plots {
+                let value = match self.debug_plot_sources.get(&plot.name) {
+                    Some(DebugPlotSource::Value(source)) => {
+                        eval_f32_opt(source, &mut eval_ctx, &mut frame_cache)
+                    }
+                    Some(DebugPlotSource::Param { effect, param }) if effect == "feedback" => self
+                        .feedback_config
+                        .enabled
+                        .then(|| self.feedback_uniforms.param(param))
+                        .flatten(),
+                    Some(DebugPlotSource::Param { effect, param }) => {
+                        self.post_chain.float_param(effect, param)
+                    }
+                    None => None,
+                };
+                if let Some(value) = value {
+                    plot.sparkline.push(value);
+                }
+            }
+        }
+        if let Some(options) = PENDING_SPECTROGRAM.with(|pending| pending.borrow_mut().take()) {
+            self.spectrogram_options = Some(options);
+        }
+        if let Some(grid) = PENDING_REFERENCE_GRID.with(|pending| pending.borrow_mut().take()) {
+            self.reference_grid = grid;
+        }
+
         // Sync camera configuration from scope
         time_start("sync_camera");
         let (camera_config, camera_uniforms) = sync_camera_from_scope(&self.scope, &mut eval_ctx);
@@ -3068,6 +4265,88 @@ feedback.is_enabled = || {{
         self.camera_uniforms = camera_uniforms;
         time_end("sync_camera");
 
+        self.spectrum_bins = spectrum_bins;
+
+        // Apply entity.lookAt() constraints, overriding the synced rotation.
+        // Entities sitting on their target keep their own rotation.
+        for (entity_id, target) in look_at_constraints {
+            let target_position = if let Ok(id) = target.as_int() {
+                let target_id = EntityId(id as u64);
+                if !self.scene_graph.exists(target_id) {
+                    continue;
+                }
+                self.scene_graph
+                    .world_matrix(target_id)
+                    .transform_point3(glam::Vec3::ZERO)
+            } else if let Some(point) = target.clone().try_cast::<rhai::Map>() {
+                let mut component = |key: &str| {
+                    point
+                        .get(key)
+                        .and_then(|d| eval_f32_opt(d, &mut eval_ctx, &mut frame_cache))
+                        .unwrap_or(0.0)
+                };
+                glam::Vec3::new(component("x"), component("y"), component("z"))
+            } else {
+                self.camera_uniforms.position_vec3()
+            };
+            self.scene_graph.look_at(entity_id, target_position);
+        }
+
+        // Sync scene-wide blend mode from scope
+        self.scene_blend_mode = self
+            .scope
+            .get_value::<rhai::Map>("scene")
+            .and_then(|scene| scene.get("blendMode").cloned())
+            .and_then(|mode| mode.into_string().ok())
+            .map(|mode| SceneBlendMode::from_str(&mode))
+            .unwrap_or_default();
+
+        // Sync tonemapping curve from scope
+        self.scene_tonemap = self
+            .scope
+            .get_value::<rhai::Map>("scene")
+            .and_then(|scene| scene.get("tonemap").cloned())
+            .and_then(|mode| mode.into_string().ok())
+            .map(|mode| SceneTonemap::from_str(&mode))
+            .unwrap_or_default();
+
+        // Sync exposure (a number or Signal) from scope
+        let exposure = self
+            .scope
+            .get_value::<rhai::Map>("scene")
+            .and_then(|scene| scene.get("exposure").cloned());
+        self.scene_exposure = match exposure {
+            Some(value) if value.is::<Signal>() => value.cast::<Signal>().evaluate(&mut eval_ctx),
+            Some(value) => value
+                .as_float()
+                .or_else(|_| value.as_int().map(|i| i as f32))
+                .unwrap_or(1.0),
+            None => 1.0,
+        }
+        .max(0.0);
+
+        // Sync background (colours may be Signals) from scope
+        self.scene_background = self
+            .scope
+            .get_value::<rhai::Map>("scene")
+            .and_then(|scene| scene.get("background").cloned())
+            .and_then(|background| {
+                SceneBackground::from_script(&background, &mut |d| {
+                    eval_f32_opt(d, &mut eval_ctx, &mut frame_cache)
+                })
+            });
+
+        // Sync fog (colour and distances may be Signals) from scope
+        self.scene_fog = self
+            .scope
+            .get_value::<rhai::Map>("scene")
+            .and_then(|scene| scene.get("__fog").cloned())
+            .and_then(|fog| {
+                SceneFog::from_script(&fog, &mut |d| {
+                    eval_f32_opt(d, &mut eval_ctx, &mut frame_cache)
+                })
+            });
+
         // Sync lighting configuration from scope
         time_start("sync_lighting");
         let (lighting_config, lighting_uniforms) =
@@ -3159,7 +4438,8 @@ feedback.is_enabled = || {{
         // Update scope with merged effects
         self.scope.set_value("__post_effects", post_effects.clone());
 
-        // Clear existing chain and rebuild
+        // Clear existing chain and rebuild it in `post.add()` order (the effect
+        // registry map is only used for lookup, never iterated)
         self.post_chain.clear();
 
         for id_dyn in chain.iter() {
@@ -3256,6 +4536,14 @@ feedback.is_enabled = || {{
                         let a = Self::eval_color_channel(tint.get("a"), 1.0, eval_ctx, frame_cache);
                         instance.set_param("tint", EffectParamValue::Vec4([r, g, b, a]));
                     }
+                    for key in ["exposure", "temperature", "tint_shift"] {
+                        if let Some(v) = effect_map
+                            .get(key)
+                            .and_then(|d| Self::eval_effect_param(d, eval_ctx, frame_cache))
+                        {
+                            instance.set_param(key, v);
+                        }
+                    }
                 }
                 "vignette" => {
                     if let Some(v) = effect_map
@@ -3270,6 +4558,23 @@ feedback.is_enabled = || {{
                     {
                         instance.set_param("smoothness", v);
                     }
+                    for key in ["roundness", "aspect", "outer_radius", "inner_radius"] {
+                        if let Some(v) = effect_map
+                            .get(key)
+                            .and_then(|d| Self::eval_effect_param(d, eval_ctx, frame_cache))
+                        {
+                            instance.set_param(key, v);
+                        }
+                    }
+                    // Legacy model: the soft edge spans smoothness / 2 inside the outer radius
+                    if !effect_map.contains_key("inner_radius") {
+                        let param = |name: &str, default: f32| {
+                            instance.params.get(name).map_or(default, |v| v.as_float())
+                        };
+                        let inner_radius =
+                            param("outer_radius", 0.7) - param("smoothness", 0.5) * 0.5;
+                        instance.set_param("inner_radius", EffectParamValue::Float(inner_radius));
+                    }
                     if let Some(color) = effect_map
                         .get("color")
                         .and_then(|d| d.clone().try_cast::<rhai::Map>())
@@ -3348,7 +4653,8 @@ feedback.is_enabled = || {{
                         .get("samples")
                         .and_then(|d| Self::eval_effect_param(d, eval_ctx, frame_cache))
                     {
-                        instance.set_param("samples", v);
+                        let samples = v.as_float().round().clamp(2.0, RADIAL_BLUR_MAX_SAMPLES);
+                        instance.set_param("samples", EffectParamValue::Float(samples));
                     }
                     if let Some(center) = effect_map
                         .get("center")
@@ -3555,6 +4861,15 @@ feedback.is_enabled = || {{
                 system.visible = handle.system.visible;
                 system.config.base_color = handle.system.config.base_color;
                 system.config.base_scale = handle.system.config.base_scale;
+                system.config.rotation = handle.system.config.rotation.clone();
+                system.config.size_over_life = handle.system.config.size_over_life.clone();
+                system.config.velocity = handle.system.config.velocity;
+                system.config.stretch = handle.system.config.stretch;
+                system.config.soft_fade = handle.system.config.soft_fade;
+                system.config.max_instances = handle.system.config.max_instances;
+                system.config.overflow = handle.system.config.overflow;
+                system.config.blend = handle.system.config.blend;
+                system.config.sprite = handle.system.config.sprite.clone();
             } else {
                 // Insert new system
                 self.particle_systems
@@ -3627,6 +4942,15 @@ feedback.is_enabled = || {{
             .insert(id, SceneEntity::PointCloud(cloud));
     }
 
+    /// Create a UI rectangle with a specific ID (for syncing from script).
+    fn create_ui_rect_with_id(&mut self, id: EntityId) {
+        use crate::scene_graph::{SceneEntity, UiRect};
+
+        self.scene_graph
+            .entities
+            .insert(id, SceneEntity::UiRect(UiRect::new(0.0, 0.0)));
+    }
+
     fn create_radial_wave_with_id(
         &mut self,
         id: EntityId,
@@ -3669,6 +4993,17 @@ feedback.is_enabled = || {{
         std::mem::take(&mut self.diagnostics)
     }
 
+    /// Drain all pending diagnostics, returning only those at or above
+    /// `min_severity` (the rest are discarded).
+    pub fn take_diagnostics_at_least(
+        &mut self,
+        min_severity: ScriptSeverity,
+    ) -> Vec<ScriptDiagnostic> {
+        let mut diagnostics = self.take_diagnostics();
+        diagnostics.retain(|d| d.severity >= min_severity);
+        diagnostics
+    }
+
     /// Collect all signals that require statistics for normalization.
     ///
     /// This traverses the entity properties and feedback config to find Signal
@@ -3841,6 +5176,7 @@ feedback.is_enabled = || {{
                 || name == "scene"
                 || name == "deform"
                 || name == "log"
+                || name == "color"
                 || name == "dbg"
                 || name == "gen"
                 || name == "time"
@@ -3849,6 +5185,10 @@ feedback.is_enabled = || {{
                 || name == "post"
                 || name == "feedback"
                 || name == "particles"
+                || name == "math"
+                || name == "vec"
+                || name == "ease"
+                || name == "preset"
             {
                 continue;
             }
@@ -3891,16 +5231,16 @@ feedback.is_enabled = || {{
 
     /// Get a Signal variable by name.
     /// Checks scope first, then evaluated cache, then evaluates parsed expression.
-    /// Returns None if the variable doesn't exist or isn't a Signal.
-    pub fn get_signal(&mut self, name: &str) -> Option<Signal> {
+    /// Returns `SignalNotFound` if the variable doesn't exist or isn't a Signal.
+    pub fn get_signal(&mut self, name: &str) -> Result<Signal, VisualiserError> {
         // First check scope
         if let Some(signal) = self.scope.get_value::<Signal>(name) {
-            return Some(signal);
+            return Ok(signal);
         }
 
         // Check evaluated cache
         if let Some(signal) = self.evaluated_signals.get(name) {
-            return Some(signal.clone());
+            return Ok(signal.clone());
         }
 
         // Try to evaluate from parsed declaration
@@ -3908,24 +5248,26 @@ feedback.is_enabled = || {{
             if let Some(signal) = self.evaluate_signal_expression(&expr) {
                 self.evaluated_signals
                     .insert(name.to_string(), signal.clone());
-                return Some(signal);
+                return Ok(signal);
             }
         }
 
-        None
+        Err(VisualiserError::SignalNotFound(name.to_string()))
     }
 
     /// Evaluate a signal expression string and return the Signal.
     fn evaluate_signal_expression(&mut self, expr: &str) -> Option<Signal> {
-        let ast = self.ast.as_ref()?;
+        let ast = self.ast.clone()?;
 
         // Create a mini-script that returns the expression
         let eval_script = format!("{{ {} }}", expr);
 
         match self.engine.compile(&eval_script) {
             Ok(eval_ast) => {
-                // Merge with main AST to access the same scope/definitions
-                let merged = ast.clone().merge(&eval_ast);
+                // Merge with main AST to access the same scope/definitions.
+                // This re-runs the top-level code, so it gets the init budget.
+                let merged = ast.merge(&eval_ast);
+                self.begin_call(ScriptPhase::Init);
                 match self
                     .engine
                     .eval_ast_with_scope::<Dynamic>(&mut self.scope, &merged)
@@ -3968,7 +5310,7 @@ feedback.is_enabled = || {{
     /// - `band_signals`: Band-scoped signal buffers
     /// - `musical_time`: Musical time structure for beat conversion
     ///
-    /// Returns a SignalChainAnalysis with steps and samples, or an error string.
+    /// Returns a SignalChainAnalysis with steps and samples, or `SignalNotFound`.
     pub fn analyze_signal_chain(
         &mut self,
         signal_name: &str,
@@ -3979,10 +5321,8 @@ feedback.is_enabled = || {{
         band_signals: &BandSignalMap,
         stem_signals: &BandSignalMap,
         musical_time: Option<&MusicalTimeStructure>,
-    ) -> Result<SignalChainAnalysis, String> {
-        let signal = self
-            .get_signal(signal_name)
-            .ok_or_else(|| format!("Signal '{}' not found in scope", signal_name))?;
+    ) -> Result<SignalChainAnalysis, VisualiserError> {
+        let signal = self.get_signal(signal_name)?;
 
         let analysis = sample_signal_chain(
             &signal,
@@ -3999,6 +5339,36 @@ feedback.is_enabled = || {{
 
         Ok(analysis)
     }
+
+    /// Sample a named signal over `[start, end]` every `step` seconds.
+    ///
+    /// Evaluates with fresh state (see `sample_signal_range`), so the script's own
+    /// smoothing state is left untouched. Returns `SignalNotFound` for unknown names.
+    pub fn sample_signal_range(
+        &mut self,
+        signal_name: &str,
+        start: f32,
+        end: f32,
+        step: f32,
+        input_signals: &SignalMap,
+        band_signals: &BandSignalMap,
+        stem_signals: &BandSignalMap,
+        musical_time: Option<&MusicalTimeStructure>,
+    ) -> Result<Vec<f32>, VisualiserError> {
+        let signal = self.get_signal(signal_name)?;
+
+        Ok(sample_signal_range(
+            &signal,
+            start,
+            end,
+            step,
+            input_signals,
+            band_signals,
+            stem_signals,
+            &self.signal_statistics,
+            musical_time,
+        ))
+    }
 }
 
 impl Default for ScriptEngine {
@@ -4241,7 +5611,75 @@ fn eval_or_signal_f32(
     None
 }
 
+/// Convert an effect parameter value into the map shape scripts pass to `fx.*`.
+fn effect_param_value_to_dynamic(value: &EffectParamValue) -> Dynamic {
+    let components: &[(&str, f32)] = match value {
+        EffectParamValue::Float(v) => return Dynamic::from(*v),
+        EffectParamValue::Vec2([x, y]) => &[("x", *x), ("y", *y)],
+        EffectParamValue::Vec3([x, y, z]) => &[("x", *x), ("y", *y), ("z", *z)],
+        EffectParamValue::Vec4([r, g, b, a]) => &[("r", *r), ("g", *g), ("b", *b), ("a", *a)],
+    };
+    let mut map = rhai::Map::new();
+    for (name, v) in components {
+        map.insert((*name).into(), Dynamic::from(*v));
+    }
+    Dynamic::from(map)
+}
+
 /// Parse ShowEventsOptions from a Rhai Map.
+/// Parse `dbg.showSpectrogram()` options. Missing or invalid values keep their defaults.
+fn parse_spectrogram_options(options: &rhai::Map) -> SpectrogramOptions {
+    let mut opts = SpectrogramOptions::default();
+
+    // rect: #{ x, y, w, h } as fractions of the screen from the top-left
+    if let Some(rect) = options
+        .get("rect")
+        .and_then(|d| d.clone().try_cast::<rhai::Map>())
+    {
+        for (i, key) in ["x", "y", "w", "h"].iter().enumerate() {
+            if let Some(value) = rect.get(*key).and_then(|d| d.as_float().ok()) {
+                opts.rect[i] = value.clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    if let Some(colormap) = options
+        .get("colormap")
+        .and_then(|d| d.clone().into_string().ok())
+        .and_then(|name| name.parse::<Colormap>().ok())
+    {
+        opts.colormap = colormap;
+    }
+
+    opts
+}
+
+fn parse_reference_grid_options(options: &rhai::Map) -> ReferenceGridOptions {
+    let mut opts = ReferenceGridOptions::default();
+
+    if let Some(spacing) = options.get("spacing").and_then(|d| d.as_float().ok()) {
+        opts.spacing = spacing.clamp(0.01, 1.0);
+    }
+
+    // color: #{ r, g, b, a }, each 0-1; missing channels keep the default
+    if let Some(color) = options
+        .get("color")
+        .and_then(|d| d.clone().try_cast::<rhai::Map>())
+    {
+        for (i, key) in ["r", "g", "b", "a"].iter().enumerate() {
+            if let Some(value) = color.get(*key).and_then(|d| d.as_float().ok()) {
+                opts.color[i] = value.clamp(0.0, 1.0);
+            }
+        }
+    }
+
+    if let Some(checker) = options.get("checker").and_then(|d| d.as_bool().ok()) {
+        opts.checker = checker;
+    }
+
+    opts
+}
+
 fn parse_show_events_options(options: &rhai::Map) -> ShowEventsOptions {
     let mut opts = ShowEventsOptions::default();
 
@@ -4295,12 +5733,43 @@ fn parse_show_events_options(options: &rhai::Map) -> ShowEventsOptions {
         }
     }
 
+    // Parse shape
+    if let Some(shape_dyn) = options.get("shape") {
+        if let Ok(shape_str) = shape_dyn.clone().into_string() {
+            opts.shape = MarkerShape::from_str(&shape_str);
+        }
+    }
+
+    // Parse label
+    if let Some(label_dyn) = options.get("label") {
+        if let Ok(label) = label_dyn.clone().into_string() {
+            opts.label = Some(label);
+        }
+    }
+
+    // Parse snapToGrid: `true` snaps to beats, a number to that many beats
+    if let Some(snap_dyn) = options
+        .get("snapToGrid")
+        .or_else(|| options.get("snap_to_grid"))
+    {
+        if let Ok(enabled) = snap_dyn.as_bool() {
+            opts.snap_to_grid = enabled.then_some(1.0);
+        } else if let Some(subdivision) = snap_dyn
+            .as_float()
+            .ok()
+            .or_else(|| snap_dyn.as_int().ok().map(|i| i as f32))
+        {
+            opts.snap_to_grid = (subdivision > 0.0).then_some(subdivision);
+        }
+    }
+
     opts
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::script_diagnostics::{ScriptDiagnosticCode, ScriptDiagnosticKind};
 
     fn make_signals(time: f32, dt: f32, amplitude: f32, flux: f32) -> HashMap<String, f32> {
         let mut signals = HashMap::new();
@@ -4362,6 +5831,27 @@ mod tests {
         assert!((entity.transform().rotation.y - 1.5).abs() < 0.01);
     }
 
+    #[test]
+    fn test_get_signal_missing_name_is_signal_not_found() {
+        let mut engine = ScriptEngine::new();
+        assert!(engine.load_script("let wobble = gen.sin(1.0, 0.0);"));
+
+        assert!(engine.get_signal("wobble").is_ok());
+        let err = engine.get_signal("missing").unwrap_err();
+        assert!(
+            matches!(&err, VisualiserError::SignalNotFound(name) if name == "missing"),
+            "unexpected error: {err:?}"
+        );
+        assert_eq!(err.to_string(), "Signal 'missing' not found in scope");
+    }
+
+    #[test]
+    fn test_try_load_script_reports_compile_errors() {
+        let mut engine = ScriptEngine::new();
+        let err = engine.try_load_script("fn init(ctx) {").unwrap_err();
+        assert!(matches!(err, VisualiserError::ScriptCompile(_)));
+    }
+
     #[test]
     fn test_composed_signal_input_end_to_end() {
         let mut engine = ScriptEngine::new();
@@ -4908,61 +6398,423 @@ mod tests {
     }
 
     #[test]
-    fn test_sphere_creation() {
+    fn test_sandbox_max_operations_is_configurable() {
+        let script = r#"
+            let total = 0;
+            fn update(dt, frame) {
+                let sum = 0;
+                for i in 0..200000 {
+                    sum += i;
+                }
+                total = sum;
+            }
+        "#;
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+
+        // The default limit aborts the loop with a dedicated diagnostic.
         let mut engine = ScriptEngine::new();
+        assert!(engine.load_script(script));
+        run_update(&mut engine, &signals);
+        let diags = engine.take_diagnostics();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, ScriptDiagnosticKind::ResourceLimit);
+        assert_eq!(diags[0].phase, ScriptPhase::Update);
+        assert!(diags[0].message.contains("max-operations"));
 
-        let script = r#"
-            let sphere;
+        // Raising it lets the same loop finish.
+        let mut engine = ScriptEngine::new();
+        engine.set_sandbox_config(SandboxConfig {
+            max_operations: 10_000_000,
+            ..SandboxConfig::default()
+        });
+        assert!(engine.load_script(script));
+        run_update(&mut engine, &signals);
+        assert!(engine.take_diagnostics().is_empty());
+        let total = engine.scope.get_value::<i64>("total").unwrap();
+        assert_eq!(total, 199_999 * 200_000 / 2);
+    }
 
+    #[test]
+    fn test_update_budget_is_separate_from_init_budget() {
+        // init() does ~400k operations of setup, within the init budget; the
+        // same loop in update() exceeds the per-frame budget.
+        let script = r#"
+            let total = 0;
+            fn heavy() {
+                let sum = 0;
+                for i in 0..200000 {
+                    sum += i;
+                }
+                sum
+            }
             fn init(ctx) {
-                sphere = mesh.sphere();
-                sphere.position = #{ x: 1.0, y: 2.0, z: 3.0 };
-                scene.add(sphere);
+                total = heavy();
             }
-
             fn update(dt, frame) {
-                sphere.scale = 2.0;
+                total = heavy();
             }
         "#;
-
+        let mut engine = ScriptEngine::new();
         assert!(engine.load_script(script));
+        engine.call_init();
+        assert!(engine.take_diagnostics().is_empty());
+        assert_eq!(
+            engine.scope.get_value::<i64>("total").unwrap(),
+            199_999 * 200_000 / 2
+        );
 
         let signals = make_signals(0.0, 0.016, 0.0, 0.0);
         run_update(&mut engine, &signals);
+        let diags = engine.take_diagnostics();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, ScriptDiagnosticKind::ResourceLimit);
+        assert_eq!(diags[0].phase, ScriptPhase::Update);
+        assert!(diags[0].message.contains("update()"));
+    }
 
-        // Check sphere was created
-        assert_eq!(engine.scene_graph.meshes().count(), 1);
-
-        let (_, entity) = engine.scene_graph.scene_entities().next().unwrap();
+    #[test]
+    fn test_diagnostic_severity_filtering() {
+        // A lint warning at load time, then a runtime error in update().
+        let script = r#"
+            let cube = mesh.cube();
+            scene.add(cube);
+            fn update(dt, frame) {
+                let x = 1 / 0;
+            }
+        "#;
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
 
-        // Check position
-        assert!((entity.transform().position.x - 1.0).abs() < 0.01);
-        assert!((entity.transform().position.y - 2.0).abs() < 0.01);
-        assert!((entity.transform().position.z - 3.0).abs() < 0.01);
+        let mut engine = ScriptEngine::new();
+        assert!(engine.load_script(script));
+        run_update(&mut engine, &signals);
+        let diags = engine.take_diagnostics();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].kind, ScriptDiagnosticKind::Warning);
+        assert_eq!(diags[0].severity, ScriptSeverity::Warning);
+        assert_eq!(diags[1].phase, ScriptPhase::Update);
+        assert_eq!(diags[1].severity, ScriptSeverity::Error);
 
-        // Check scale
-        assert!((entity.transform().scale.x - 2.0).abs() < 0.01);
+        // Filtering by minimum severity drops the warning.
+        let mut engine = ScriptEngine::new();
+        assert!(engine.load_script(script));
+        run_update(&mut engine, &signals);
+        let errors = engine.take_diagnostics_at_least(ScriptSeverity::Error);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].severity, ScriptSeverity::Error);
+        assert!(engine.take_diagnostics().is_empty());
     }
 
     #[test]
-    fn test_mesh_color() {
-        use crate::scene_graph::SceneEntity;
+    fn test_diagnostic_codes_distinguish_parse_error_and_unknown_band() {
+        let mut engine = ScriptEngine::new();
+        assert!(!engine.load_script("fn update(dt, frame) { let x = ; }"));
+        let diags = engine.take_diagnostics();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, ScriptDiagnosticCode::Parse);
 
         let mut engine = ScriptEngine::new();
+        engine.set_available_bands(vec![("band-1".to_string(), "Bass".to_string())]);
+        assert!(engine.load_script(
+            r#"
+            fn update(dt, frame) {
+                let kick = inputs.mix.bands["Kick"];
+                let bass = inputs.mix.bands["Bass"];
+            }
+        "#
+        ));
+        let diags = engine.take_diagnostics();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].code, ScriptDiagnosticCode::UnknownBand);
+        assert_eq!(diags[0].severity, ScriptSeverity::Warning);
+        assert_eq!(diags[0].location.as_ref().unwrap().line, 3);
+        assert!(diags[0].message.contains("\"Kick\""));
+
+        // Codes serialize as stable strings for the wasm JSON path.
+        let json = serde_json::to_string(&diags[0]).unwrap();
+        assert!(json.contains(r#""code":"W_UNKNOWN_BAND""#));
+    }
 
+    #[test]
+    fn test_update_timeout_aborts_and_keeps_last_frame() {
+        // The second update() never returns; the operation limit is raised
+        // out of reach so only the wall-clock timeout can stop it.
         let script = r#"
             let cube;
-
+            let calls = 0;
             fn init(ctx) {
                 cube = mesh.cube();
-                cube.color = #{ r: 1.0, g: 0.5, b: 0.25, a: 0.8 };
                 scene.add(cube);
             }
-
             fn update(dt, frame) {
+                cube.position.x += 1.0;
+                calls += 1;
+                if calls > 1 {
+                    loop {}
+                }
             }
         "#;
-
+        let mut engine = ScriptEngine::new();
+        engine.set_sandbox_config(SandboxConfig {
+            max_operations: u64::MAX,
+            update_timeout_ms: Some(50),
+            ..SandboxConfig::default()
+        });
+        assert!(engine.load_script(script));
+
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+        assert!(engine.take_diagnostics().is_empty());
+        run_update(&mut engine, &signals);
+
+        let diags = engine.take_diagnostics();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, ScriptDiagnosticKind::ResourceLimit);
+        assert_eq!(diags[0].phase, ScriptPhase::Update);
+        assert!(diags[0].message.contains("timed out after 50 ms"));
+
+        // The aborted frame is not synced to the scene graph.
+        let (_, cube) = engine.scene_graph.meshes().next().unwrap();
+        assert!((cube.transform.position.x - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_mesh_subdivision_options() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            fn init(ctx) {
+                scene.add(mesh.sphere());
+                scene.add(mesh.sphere(#{ segments: 8 }));
+                scene.add(mesh.plane(#{ subdivisions: 10 }).instance());
+                scene.add(mesh.plane(#{ subdivisions: 100000 }));
+            }
+
+            fn update(dt, frame) {}
+        "#;
+
+        assert!(engine.load_script(script));
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        let mut mesh_types: Vec<MeshType> = engine
+            .scene_graph
+            .meshes()
+            .map(|(_, mesh)| mesh.mesh_type.clone())
+            .collect();
+        mesh_types.sort_by_key(|mesh_type| format!("{mesh_type:?}"));
+        assert_eq!(
+            mesh_types,
+            vec![
+                MeshType::Plane { subdivisions: 10 },
+                MeshType::Plane {
+                    subdivisions: MAX_PLANE_SUBDIVISIONS
+                },
+                MeshType::Sphere {
+                    segments: DEFAULT_SPHERE_SEGMENTS
+                },
+                MeshType::Sphere { segments: 8 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mesh_morph_weights_sync() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let face = mesh.load("face.glb");
+            face.morph("open", gen.constant(0.75));
+            face.morph("smile", 0.25);
+            let copy = face.instance();
+
+            fn init(ctx) {
+                scene.add(face);
+                scene.add(copy);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+
+        assert!(engine.load_script(script));
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        let mut weights: Vec<Vec<(String, f32)>> = engine
+            .scene_graph
+            .meshes()
+            .map(|(_, mesh)| mesh.morph_weights.clone())
+            .collect();
+        weights.sort_by_key(|weights| weights.len());
+        assert_eq!(
+            weights,
+            vec![
+                vec![],
+                vec![("open".to_string(), 0.75), ("smile".to_string(), 0.25)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mesh_animation_playback_sync() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let walker = mesh.load("walker.glb");
+            let dancer = mesh.load("dancer.glb");
+            let idle = mesh.load("idle.glb");
+            walker.playAnimation("walk", #{ speed: 2.0 });
+            dancer.playAnimation("dance", #{ looping: false, time: gen.constant(0.5) });
+            idle.playAnimation("idle");
+            idle.stopAnimation();
+
+            fn init(ctx) {
+                scene.add(walker);
+                scene.add(dancer);
+                scene.add(idle);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+
+        assert!(engine.load_script(script));
+        let signals = make_signals(0.0, 0.25, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+        run_update(&mut engine, &signals);
+
+        let animation = |asset: &str| {
+            engine
+                .scene_graph
+                .meshes()
+                .find(|(_, mesh)| mesh.mesh_type == MeshType::Asset(asset.to_string()))
+                .and_then(|(_, mesh)| mesh.animation.clone())
+        };
+        // Playback starts at 0 and advances by dt * speed
+        assert_eq!(
+            animation("walker.glb"),
+            Some(AnimationPlayback {
+                name: "walk".to_string(),
+                time: 0.5,
+                looping: true,
+            })
+        );
+        // A time Signal scrubs instead
+        assert_eq!(
+            animation("dancer.glb"),
+            Some(AnimationPlayback {
+                name: "dance".to_string(),
+                time: 0.5,
+                looping: false,
+            })
+        );
+        assert_eq!(animation("idle.glb"), None);
+    }
+
+    #[test]
+    fn test_camera_shake_sync() {
+        let camera_position = |shake: &str| {
+            let mut engine = ScriptEngine::new();
+            let script = format!(
+                r#"
+                camera.position = #{{ x: 0.0, y: 0.0, z: 5.0 }};
+                camera.lookAt(#{{ x: 0.0, y: 0.0, z: 0.0 }});
+                {shake}
+
+                fn update(dt, frame) {{}}
+            "#
+            );
+            assert!(engine.load_script(&script));
+            run_update(&mut engine, &make_signals(0.5, 0.016, 0.0, 0.0));
+            engine.camera_uniforms.position_vec3()
+        };
+
+        let still = glam::Vec3::new(0.0, 0.0, 5.0);
+        assert_eq!(camera_position(""), still);
+        let shaken = camera_position("camera.shake(#{ amount: gen.constant(0.5), seed: 3 });");
+        assert!(shaken.distance(still) > 0.0);
+        assert!(shaken.distance(still) <= 0.5 * 3f32.sqrt());
+        assert_eq!(
+            camera_position("camera.shake(#{ amount: 0.5 }); camera.shake(());"),
+            still
+        );
+    }
+
+    #[test]
+    fn test_sample_at_dt_reads_the_frame_clock() {
+        let mut engine = ScriptEngine::new();
+        let script = r#"
+            let cube = mesh.cube();
+
+            fn init(ctx) {
+                scene.add(cube);
+            }
+
+            fn update(dt, frame) {
+                cube.position.x = timing.dt.sample_at(0.0);
+            }
+        "#;
+        assert!(engine.load_script(script));
+        run_update(&mut engine, &make_signals(0.0, 1.0 / 30.0, 0.0, 0.0));
+
+        let (_, cube) = engine.scene_graph.meshes().next().unwrap();
+        assert_eq!(cube.transform.position.x, 1.0 / 30.0);
+    }
+
+    #[test]
+    fn test_sphere_creation() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let sphere;
+
+            fn init(ctx) {
+                sphere = mesh.sphere();
+                sphere.position = #{ x: 1.0, y: 2.0, z: 3.0 };
+                scene.add(sphere);
+            }
+
+            fn update(dt, frame) {
+                sphere.scale = 2.0;
+            }
+        "#;
+
+        assert!(engine.load_script(script));
+
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        // Check sphere was created
+        assert_eq!(engine.scene_graph.meshes().count(), 1);
+
+        let (_, entity) = engine.scene_graph.scene_entities().next().unwrap();
+
+        // Check position
+        assert!((entity.transform().position.x - 1.0).abs() < 0.01);
+        assert!((entity.transform().position.y - 2.0).abs() < 0.01);
+        assert!((entity.transform().position.z - 3.0).abs() < 0.01);
+
+        // Check scale
+        assert!((entity.transform().scale.x - 2.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_mesh_color() {
+        use crate::scene_graph::SceneEntity;
+
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let cube;
+
+            fn init(ctx) {
+                cube = mesh.cube();
+                cube.color = #{ r: 1.0, g: 0.5, b: 0.25, a: 0.8 };
+                scene.add(cube);
+            }
+
+            fn update(dt, frame) {
+            }
+        "#;
+
         assert!(engine.load_script(script));
 
         let signals = make_signals(0.0, 0.016, 0.0, 0.0);
@@ -5041,6 +6893,261 @@ mod tests {
         assert!((group_entity.transform.position.x - 5.0).abs() < 0.01);
     }
 
+    #[test]
+    fn test_look_at_faces_target() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let pointer;
+            let target;
+            let stuck;
+            let watcher;
+
+            fn init(ctx) {
+                target = mesh.sphere();
+                target.position = #{ x: 3.0, y: 0.0, z: 0.0 };
+
+                // Facing +X from the origin needs a quarter turn about Y.
+                pointer = mesh.cube();
+                pointer.lookAt(target);
+
+                // On top of its target: keeps the rotation it was given.
+                stuck = mesh.cube();
+                stuck.position = #{ x: 3.0, y: 0.0, z: 0.0 };
+                stuck.rotation = #{ x: 0.0, y: 0.0, z: 0.25 };
+                stuck.lookAt(target);
+
+                watcher = mesh.cube();
+                watcher.lookAt(camera);
+                camera.position = #{ x: 0.0, y: 5.0, z: 0.0 };
+
+                scene.add(target);
+                scene.add(pointer);
+                scene.add(stuck);
+                scene.add(watcher);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+
+        assert!(engine.load_script(script));
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        let mut ids: Vec<EntityId> = engine.scene_graph.entities.keys().copied().collect();
+        ids.sort_unstable_by_key(|id| id.0);
+        let (pointer, stuck, watcher) = (ids[1], ids[2], ids[3]);
+        let rotation = |id: EntityId| engine.scene_graph.entities[&id].transform().rotation;
+
+        // A quarter turn sits at the XYZ euler pole, where asin loses precision.
+        let yaw = rotation(pointer);
+        assert!(
+            (yaw.y - std::f32::consts::FRAC_PI_2).abs() < 1e-3,
+            "{:?}",
+            yaw
+        );
+        assert!(yaw.x.abs() < 1e-4 && yaw.z.abs() < 1e-4, "{:?}", yaw);
+
+        assert_eq!(rotation(stuck).z, 0.25);
+
+        // The camera is straight above: local +Z now points up.
+        let forward = engine
+            .scene_graph
+            .world_matrix(watcher)
+            .transform_vector3(glam::Vec3::Z);
+        assert!((forward - glam::Vec3::Y).length() < 1e-4, "{:?}", forward);
+    }
+
+    #[test]
+    fn test_follow_path_places_entity_by_progress() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let start;
+            let middle;
+            let end;
+            let stopped;
+
+            fn init(ctx) {
+                let path = [
+                    #{ x: 0.0, y: 0.0, z: 0.0 },
+                    #{ x: 2.0, y: 0.0, z: 0.0 },
+                    #{ x: 2.0, y: 2.0, z: 0.0 },
+                ];
+                start = mesh.cube();
+                start.followPath(path, 0.0);
+                middle = mesh.cube();
+                middle.followPath(path, 0.5);
+                end = mesh.cube();
+                end.followPath(path, 1.0);
+
+                // Stopped: back to its own position
+                stopped = mesh.cube();
+                stopped.position.z = 5.0;
+                stopped.followPath(path, 1.0, #{ spline: true });
+                stopped.followPath(());
+
+                scene.add(start);
+                scene.add(middle);
+                scene.add(end);
+                scene.add(stopped);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+
+        assert!(engine.load_script(script));
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        let mut ids: Vec<EntityId> = engine.scene_graph.entities.keys().copied().collect();
+        ids.sort_unstable_by_key(|id| id.0);
+        let positions: Vec<(f32, f32, f32)> = ids
+            .iter()
+            .map(|id| {
+                let p = engine.scene_graph.entities[id].transform().position;
+                (p.x, p.y, p.z)
+            })
+            .collect();
+        assert_eq!(
+            positions,
+            [
+                (0.0, 0.0, 0.0),
+                (2.0, 0.0, 0.0),
+                (2.0, 2.0, 0.0),
+                (0.0, 0.0, 5.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_large_grid_fits_the_default_map_limit() {
+        let script = r#"
+            let cells = [];
+            fn init(ctx) {
+                cells = scene.grid(|| mesh.cube(), #{ cols: 16, rows: 16 });
+            }
+            fn update(dt, frame) {
+                cells[255].position.y = 1.0;
+            }
+        "#;
+
+        let mut engine = ScriptEngine::new();
+        assert!(engine.load_script(script));
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+        assert!(engine.take_diagnostics().is_empty());
+        assert_eq!(engine.scene_graph.scene_entities().count(), 256);
+
+        // A lowered limit still catches it, and says how to raise it.
+        let mut engine = ScriptEngine::new();
+        engine.set_sandbox_config(SandboxConfig {
+            max_map_size: 500,
+            ..SandboxConfig::default()
+        });
+        assert!(engine.load_script(script));
+        engine.call_init();
+        let diags = engine.take_diagnostics();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, ScriptDiagnosticKind::ResourceLimit);
+        assert!(
+            diags[0].message.contains("max_map_size"),
+            "{}",
+            diags[0].message
+        );
+    }
+
+    #[test]
+    fn test_scene_grid_lays_out_cells() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let cells = [];
+
+            fn init(ctx) {
+                cells = scene.grid(|| mesh.cube(), #{ cols: 3, rows: 3, spacing: 2.0 });
+            }
+
+            fn update(dt, frame) {
+                // Per-cell animation through the returned array
+                cells[8].position.y = 1.0;
+            }
+        "#;
+
+        assert!(engine.load_script(script));
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        let mut positions: Vec<(u64, (f32, f32, f32))> = engine
+            .scene_graph
+            .scene_entities()
+            .map(|(id, entity)| {
+                let p = entity.transform().position;
+                (id.0, (p.x, p.y, p.z))
+            })
+            .collect();
+        positions.sort_by_key(|(id, _)| *id);
+        let positions: Vec<(f32, f32, f32)> = positions.into_iter().map(|(_, p)| p).collect();
+        assert_eq!(
+            positions,
+            [
+                (-2.0, 0.0, -2.0),
+                (0.0, 0.0, -2.0),
+                (2.0, 0.0, -2.0),
+                (-2.0, 0.0, 0.0),
+                (0.0, 0.0, 0.0),
+                (2.0, 0.0, 0.0),
+                (-2.0, 0.0, 2.0),
+                (0.0, 0.0, 2.0),
+                (2.0, 1.0, 2.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_spectrum_bars_track_bins() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            fn init(ctx) {
+                scene.spectrumBars(#{ count: 4, spacing: 0.5, height: 2.0 });
+            }
+
+            fn update(dt, frame) {}
+        "#;
+
+        assert!(engine.load_script(script));
+        engine.spectrum = vec![0.25, 0.5, 0.75, 1.0];
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+        assert_eq!(engine.spectrum_bins, 4);
+
+        let mut bars: Vec<(f32, f32, f32)> = engine
+            .scene_graph
+            .entities
+            .keys()
+            .filter(|id| engine.scene_graph.get_parent(**id).is_some())
+            .map(|id| {
+                let transform = engine.scene_graph.entities[id].transform();
+                (
+                    transform.position.x,
+                    transform.scale.y,
+                    transform.position.y,
+                )
+            })
+            .collect();
+        bars.sort_by(|a, b| a.0.total_cmp(&b.0));
+        assert_eq!(
+            bars,
+            [
+                (-0.75, 0.5, 0.25),
+                (-0.25, 1.0, 0.5),
+                (0.25, 1.5, 0.75),
+                (0.75, 2.0, 1.0),
+            ]
+        );
+    }
+
     #[test]
     fn test_debug_modes() {
         // Reset debug options before test
@@ -5058,12 +7165,14 @@ mod tests {
                 dbg.wireframe(true);
                 dbg.boundingBoxes(true);
                 dbg.isolate(cube);
+                dbg.soloEffect(fx.vignette(#{}));
             }
 
             fn update(dt, frame) {
                 if frame.time > 0.5 {
                     dbg.clearIsolation();
                     dbg.wireframe(false);
+                    dbg.clearSolo();
                 }
             }
         "#;
@@ -5084,6 +7193,7 @@ mod tests {
             debug_opts.isolated_entity.is_some(),
             "isolation should be set"
         );
+        assert_eq!(debug_opts.solo_effect.as_deref(), Some("vignette"));
 
         // Second update after threshold - isolation and wireframe should be cleared
         let signals = make_signals(0.6, 0.016, 0.0, 0.0);
@@ -5099,8 +7209,448 @@ mod tests {
             debug_opts.isolated_entity.is_none(),
             "isolation should be cleared"
         );
+        assert!(debug_opts.solo_effect.is_none(), "solo should be cleared");
 
         // Reset for other tests
         reset_script_debug_options();
     }
+
+    #[test]
+    fn test_show_events_options_shape_and_label() {
+        let mut options = rhai::Map::new();
+        options.insert("shape".into(), "triangle".into());
+        options.insert("label".into(), "kick".into());
+
+        let opts = parse_show_events_options(&options);
+        assert_eq!(opts.shape, MarkerShape::Triangle);
+        assert_eq!(opts.label.as_deref(), Some("kick"));
+
+        let defaults = parse_show_events_options(&rhai::Map::new());
+        assert_eq!(defaults.shape, MarkerShape::Dot);
+        assert!(defaults.label.is_none());
+    }
+
+    #[test]
+    fn test_debug_plot_accumulates_points() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            fn init(ctx) {
+                dbg.plot("time", gen.sin(1.0, 0.0));
+            }
+
+            fn update(dt, frame) {
+                dbg.plot("constant", 0.5);
+            }
+        "#;
+        assert!(engine.load_script(script));
+
+        for frame in 0..5 {
+            let signals = make_signals(frame as f32 * 0.1, 0.1, 0.0, 0.0);
+            run_update(&mut engine, &signals);
+        }
+
+        assert_eq!(engine.debug_plots.len(), 2);
+        assert_eq!(engine.debug_plots[0].name, "time");
+        assert_eq!(engine.debug_plots[0].sparkline.count, 5);
+        // Re-registering each frame keeps a single plot and its history.
+        assert_eq!(engine.debug_plots[1].name, "constant");
+        assert_eq!(engine.debug_plots[1].sparkline.count, 5);
+        assert_eq!(engine.debug_plots[1].sparkline.data[4], 0.5);
+    }
+
+    #[test]
+    fn test_debug_plots_are_capped_and_labelled() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            fn update(dt, frame) {
+                for i in 0..8 {
+                    dbg.plot(`p${i}`, i);
+                }
+            }
+        "#;
+        assert!(engine.load_script(script));
+        run_update(&mut engine, &make_signals(0.0, 0.1, 0.0, 0.0));
+
+        // Plots past the cap are dropped rather than stacked off-screen
+        assert_eq!(engine.debug_plots.len(), MAX_DEBUG_PLOTS);
+        assert_eq!(engine.debug_plots[5].name, "p5");
+        assert!(engine.warned_debug_plot_cap);
+
+        // The label sits just above the plot's region, starting at its left edge
+        let (offset, scale) = DebugPlot::screen_region(0);
+        let label = engine.debug_plots[0].label_segments(0, [800.0, 600.0]);
+        assert!(!label.is_empty());
+        for segment in &label {
+            assert!(segment[0] >= offset[0] && segment[3] >= offset[0]);
+            assert!(segment[1] > offset[1] + scale[1] && segment[4] > offset[1] + scale[1]);
+        }
+    }
+
+    #[test]
+    fn test_debug_plot_param_matches_evaluated_values() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let glow;
+
+            fn init(ctx) {
+                glow = fx.bloom(#{ threshold: 0.5, intensity: gen.sin(1.0, 0.0).scale(0.5).add(1.0) });
+                post.add(glow);
+                feedback.enable(feedback.builder().color.decay(0.9).opacity(0.75).build());
+                dbg.plotParam("bloom", "intensity");
+                dbg.plotParam("feedback", "opacity");
+                dbg.plotParam("vignette", "strength");
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        assert!(engine.load_script(script));
+
+        let mut evaluated = Vec::new();
+        for frame in 0..4 {
+            let signals = make_signals(frame as f32 * 0.1, 0.1, 0.0, 0.0);
+            run_update(&mut engine, &signals);
+            evaluated.push(engine.post_chain.float_param("bloom", "intensity").unwrap());
+        }
+
+        // Effects that aren't in the chain get a plot but no points.
+        let names: Vec<&str> = engine.debug_plots.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(
+            names,
+            ["bloom.intensity", "feedback.opacity", "vignette.strength"]
+        );
+        let intensity = &engine.debug_plots[0].sparkline;
+        assert_eq!(&intensity.data[..intensity.count], evaluated.as_slice());
+        assert!(evaluated.windows(2).any(|w| w[0] != w[1]));
+        let opacity = &engine.debug_plots[1].sparkline;
+        assert_eq!(&opacity.data[..opacity.count], [0.75; 4]);
+        assert_eq!(engine.debug_plots[2].sparkline.count, 0);
+    }
+
+    #[test]
+    fn test_show_spectrogram_parses_options() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            fn init(ctx) {
+                dbg.showSpectrogram(#{
+                    rect: #{ x: 0.5, y: 0.1, w: 0.25 },
+                    colormap: "grayscale"
+                });
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        assert!(engine.load_script(script));
+        let signals = make_signals(0.0, 0.1, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        let options = engine.spectrogram_options.expect("spectrogram enabled");
+        assert_eq!(options.rect, [0.5, 0.1, 0.25, 0.2]);
+        assert_eq!(options.colormap, Colormap::Grayscale);
+    }
+
+    #[test]
+    fn test_color_map_returns_rgba() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let low = color.map("viridis", 0.0);
+            let high = color.map("grayscale", 1);
+            let maps = color.maps();
+
+            fn init(ctx) {}
+            fn update(dt, frame) {}
+        "#;
+        assert!(engine.load_script(script));
+
+        let low: rhai::Map = engine.scope.get_value("low").unwrap();
+        let expected = Colormap::Viridis.rgb(0.0);
+        assert_eq!(low["r"].as_float().unwrap(), expected[0]);
+        assert_eq!(low["g"].as_float().unwrap(), expected[1]);
+        assert_eq!(low["b"].as_float().unwrap(), expected[2]);
+        assert_eq!(low["a"].as_float().unwrap(), 1.0);
+        let high: rhai::Map = engine.scope.get_value("high").unwrap();
+        assert_eq!(high["r"].as_float().unwrap(), 1.0);
+        let maps: rhai::Array = engine.scope.get_value("maps").unwrap();
+        assert_eq!(maps.len(), Colormap::ALL.len());
+
+        assert!(!engine.load_script(r#"let c = color.map("rainbow", 0.5);"#));
+    }
+
+    #[test]
+    fn test_scene_blend_mode_syncs_from_script() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            fn init(ctx) {}
+
+            fn update(dt, frame) {
+                scene.blendMode = "add";
+            }
+        "#;
+        assert!(engine.load_script(script));
+        assert_eq!(engine.scene_blend_mode, SceneBlendMode::Normal);
+
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+        assert_eq!(engine.scene_blend_mode, SceneBlendMode::Add);
+    }
+
+    #[test]
+    fn test_scene_tonemap_syncs_from_script() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            fn init(ctx) {}
+
+            fn update(dt, frame) {
+                scene.tonemap = "aces";
+            }
+        "#;
+        assert!(engine.load_script(script));
+        assert_eq!(engine.scene_tonemap, SceneTonemap::None);
+
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+        assert_eq!(engine.scene_tonemap, SceneTonemap::Aces);
+    }
+
+    #[test]
+    fn test_scene_exposure_syncs_from_script() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            fn init(ctx) {}
+
+            fn update(dt, frame) {
+                scene.exposure = 2;
+            }
+        "#;
+        assert!(engine.load_script(script));
+        assert_eq!(engine.scene_exposure, 1.0);
+
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+        assert_eq!(engine.scene_exposure, 2.0);
+    }
+
+    #[test]
+    fn test_describe_entity_reports_evaluated_world_state() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let group;
+            let cube;
+            let info = ();
+
+            fn init(ctx) {
+                group = scene.group();
+                group.position = #{ x: 1.0, y: 0.0, z: 0.0 };
+                cube = mesh.cube();
+                cube.position.x = gen.constant(2.0);
+                cube.renderMode = "wireframe";
+                group.add(cube);
+                scene.add(group);
+            }
+
+            fn update(dt, frame) {
+                info = dbg.describeEntity(cube);
+            }
+        "#;
+        assert!(engine.load_script(script));
+
+        // The first update runs before any sync: nothing to describe yet.
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+        assert!(engine.scope.get_value::<Dynamic>("info").unwrap().is_unit());
+
+        run_update(&mut engine, &signals);
+        let info = engine.scope.get_value::<rhai::Map>("info").unwrap();
+        let world = info["world_position"].clone_cast::<rhai::Map>();
+        assert!((world["x"].as_float().unwrap() - 3.0).abs() < 1e-6);
+        assert!(info["visible"].as_bool().unwrap());
+        assert!(!info["in_scene"].as_bool().unwrap());
+        assert_eq!(
+            info["parent"].as_int().unwrap(),
+            engine.scope.get_value::<rhai::Map>("group").unwrap()["__id"]
+                .as_int()
+                .unwrap()
+        );
+        assert_eq!(info["mesh_type"].clone().into_string().unwrap(), "cube");
+        assert_eq!(
+            info["render_mode"].clone().into_string().unwrap(),
+            "wireframe"
+        );
+    }
+
+    #[test]
+    fn test_radial_blur_params_sync_and_match_describe_effect() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let info = dbg.describeEffect("radial_blur");
+
+            fn init(ctx) {
+                let blur = fx.radialBlur(#{
+                    strength: gen.constant(0.4),
+                    samples: 100,
+                    center: #{ x: 0.25, y: gen.constant(0.75) }
+                });
+                post.add(blur);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        assert!(engine.load_script(script));
+
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        let blur = &engine.post_chain.effects[0];
+        assert_eq!(blur.effect_id, "radial_blur");
+        assert_eq!(
+            blur.get_param("center"),
+            Some(&EffectParamValue::Vec2([0.25, 0.75]))
+        );
+        assert_eq!(
+            blur.get_param("strength"),
+            Some(&EffectParamValue::Float(0.4))
+        );
+        assert_eq!(
+            blur.get_param("samples"),
+            Some(&EffectParamValue::Float(RADIAL_BLUR_MAX_SAMPLES))
+        );
+
+        // Every synced param is described, and the described defaults are script-shaped
+        let info: rhai::Map = engine.scope.get_value("info").unwrap();
+        let params: rhai::Array = info["params"].clone().into_array().unwrap();
+        let mut described = Vec::new();
+        for param in params {
+            let param: rhai::Map = param.cast();
+            let name = param["name"].clone().into_string().unwrap();
+            assert!(blur.get_param(&name).is_some(), "{name} was not synced");
+            if name == "center" {
+                let center: rhai::Map = param["default"].clone().cast();
+                assert_eq!(center["x"].as_float().unwrap(), 0.5);
+                assert_eq!(center["y"].as_float().unwrap(), 0.5);
+            }
+            if name == "samples" {
+                assert_eq!(param["max"].as_float().unwrap(), RADIAL_BLUR_MAX_SAMPLES);
+            }
+            described.push(name);
+        }
+        assert_eq!(blur.params.len(), described.len());
+    }
+
+    #[test]
+    fn test_signal_value_reads_current_frame_inside_update() {
+        let mut engine = ScriptEngine::new();
+        engine.set_available_signals(vec!["amplitude".to_string()]);
+
+        let script = r#"
+            let loud = false;
+            let level = -1.0;
+
+            fn init(ctx) {}
+
+            fn update(dt, frame) {
+                level = inputs.mix.amplitude.value();
+                loud = inputs.mix.amplitude.value() > 0.8;
+            }
+        "#;
+        assert!(engine.load_script(script));
+
+        let amplitude = |value: f32| {
+            let mut signals: SignalMap = HashMap::new();
+            signals.insert(
+                "amplitude".to_string(),
+                std::rc::Rc::new(crate::input::InputSignal::new(vec![value; 100], 100.0)),
+            );
+            signals
+        };
+        let update = |engine: &mut ScriptEngine, inputs: &SignalMap| {
+            engine.update(
+                0.5,
+                0.016,
+                &make_signals(0.5, 0.016, 0.0, 0.0),
+                inputs,
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                None,
+            );
+        };
+
+        update(&mut engine, &amplitude(0.9));
+        assert!(engine.diagnostics.is_empty(), "{:?}", engine.diagnostics);
+        assert_eq!(engine.scope.get_value::<f32>("level"), Some(0.9));
+        assert_eq!(engine.scope.get_value::<bool>("loud"), Some(true));
+
+        update(&mut engine, &amplitude(0.25));
+        assert_eq!(engine.scope.get_value::<f32>("level"), Some(0.25));
+        assert_eq!(engine.scope.get_value::<bool>("loud"), Some(false));
+
+        // Outside update() there is no frame to evaluate against
+        assert!(!engine.load_script("let v = inputs.amplitude.value();"));
+    }
+
+    #[test]
+    fn test_mesh_render_order_is_stable_across_updates() {
+        let mut engine = ScriptEngine::new();
+
+        // More than nine meshes so string-keyed ids ("10" < "2") would misorder them
+        let script = r#"
+            fn init(ctx) {
+                for i in 0..12 {
+                    let cube = mesh.cube();
+                    cube.position.x = i * 1.0;
+                    scene.add(cube);
+                }
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        assert!(engine.load_script(script));
+
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        let mut orders = Vec::new();
+        for _ in 0..3 {
+            run_update(&mut engine, &signals);
+            let order: Vec<u64> = engine.scene_graph.meshes().map(|(id, _)| id.0).collect();
+            orders.push(order);
+        }
+
+        assert_eq!(orders[0].len(), 12);
+        assert!(orders[0].windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(orders.iter().all(|order| *order == orders[0]));
+    }
+
+    #[test]
+    fn test_particle_rotation_applied_before_scene_add_is_synced() {
+        let mut engine = ScriptEngine::new();
+
+        let script = r#"
+            let sys = particles.stream(gen.constant(1.0), #{ rate_per_beat: 1.0 });
+            sys.rotation(#{ initial: 0.5, velocity: 3.0 });
+            scene.add(sys);
+
+            fn update(dt, frame) {}
+        "#;
+        assert!(engine.load_script(script));
+
+        let signals = make_signals(0.0, 0.016, 0.0, 0.0);
+        run_update(&mut engine, &signals);
+
+        let system = engine
+            .particle_systems
+            .values()
+            .next()
+            .expect("particle system should be synced");
+        assert!((system.config.rotation.initial - 0.5).abs() < 1e-6);
+        assert!((system.config.rotation.velocity - 3.0).abs() < 1e-6);
+    }
 }
@@ -42,6 +42,12 @@ impl Default for SignalId {
     }
 }
 
+/// Feature key under which a stem-scoped band signal is stored in the stem's
+/// signal map, e.g. `band:Bass:energy`.
+pub fn stem_band_feature(band_key: &str, feature: &str) -> String {
+    format!("band:{}:{}", band_key, feature)
+}
+
 /// A Signal represents a lazy, time-indexed value that can be transformed.
 ///
 /// Signals are immutable - all transformation methods return new Signals.
@@ -119,6 +125,13 @@ impl Signal {
         })
     }
 
+    /// Create a stem-scoped band input signal (a band's feature analysed on
+    /// one stem only). Resolves through the stem's signals under the key
+    /// returned by [`stem_band_feature`].
+    pub fn stem_band_input(stem_id: impl Into<String>, band_key: &str, feature: &str) -> Self {
+        Self::stem_input(stem_id, stem_band_feature(band_key, feature))
+    }
+
     /// Create a stem-scoped input signal with custom sampling configuration.
     pub fn stem_input_with_sampling(
         stem_id: impl Into<String>,
@@ -478,6 +491,15 @@ impl Signal {
         })
     }
 
+    /// Apply an easing curve. The value is clamped to 0-1 first, so
+    /// normalise or map the signal into that range.
+    pub fn ease(&self, easing: EasingFunction) -> Signal {
+        Signal::new(SignalNode::Ease {
+            source: self.clone(),
+            easing,
+        })
+    }
+
     // === Mapping / Shaping ===
 
     /// Map this signal from one range to another.
@@ -498,8 +520,18 @@ impl Signal {
         })
     }
 
+    /// Hard threshold: 0 below `edge`, 1 at or above it.
+    /// The edge can be a constant or signal.
+    pub fn step(&self, edge: impl Into<SignalParam>) -> Signal {
+        Signal::new(SignalNode::Step {
+            source: self.clone(),
+            edge: edge.into(),
+        })
+    }
+
     /// Apply smoothstep interpolation.
     /// Returns 0 when x <= edge0, 1 when x >= edge1, smooth interpolation between.
+    /// Equal edges behave like [`Signal::step`].
     /// Edges can be constants or signals.
     pub fn smoothstep(
         &self,
@@ -546,6 +578,33 @@ impl Signal {
         })
     }
 
+    // === Windowed Statistics ===
+
+    /// Largest value over the last `seconds` of evaluation.
+    /// Unlike normalisation statistics this is live: it only sees frames
+    /// that have actually been evaluated.
+    pub fn window_max(&self, seconds: impl Into<SignalParam>) -> Signal {
+        self.window(seconds, WindowAggregate::Max)
+    }
+
+    /// Smallest value over the last `seconds` of evaluation.
+    pub fn window_min(&self, seconds: impl Into<SignalParam>) -> Signal {
+        self.window(seconds, WindowAggregate::Min)
+    }
+
+    /// Mean of the values evaluated over the last `seconds`.
+    pub fn window_avg(&self, seconds: impl Into<SignalParam>) -> Signal {
+        self.window(seconds, WindowAggregate::Avg)
+    }
+
+    fn window(&self, seconds: impl Into<SignalParam>, aggregate: WindowAggregate) -> Signal {
+        Signal::new(SignalNode::Window {
+            source: self.clone(),
+            seconds: seconds.into(),
+            aggregate,
+        })
+    }
+
     // === Time Shifting ===
 
     /// Delay the signal by N beats (look back in time).
@@ -630,6 +689,17 @@ impl Signal {
         })
     }
 
+    /// If-then-else: returns `a` when self > 0.5, else `b`.
+    ///
+    /// Only the chosen branch is evaluated each frame, so stateful nodes
+    /// (smoothing, integrate, diff) in the other branch do not advance
+    /// while it is inactive.
+    pub fn select(&self, a: Signal, b: Signal) -> Signal {
+        SelectBuilder::new()
+            .when(self.gt(Signal::constant(0.5)), a)
+            .otherwise(b)
+    }
+
     // === Utility ===
 
     // === Sampling Configuration ===
@@ -717,8 +787,11 @@ impl Signal {
     fn collect_normalise_sources(&self, sources: &mut Vec<Signal>) {
         match &*self.node {
             SignalNode::Normalise { source, params } => {
-                // For Range normalization, we don't need statistics
-                if !matches!(params, NormaliseParams::Range { .. }) {
+                // Range and Window normalization don't need statistics
+                if !matches!(
+                    params,
+                    NormaliseParams::Range { .. } | NormaliseParams::Window { .. }
+                ) {
                     sources.push(source.clone());
                 }
                 source.collect_normalise_sources(sources);
@@ -785,9 +858,12 @@ impl Signal {
             | SignalNode::Wrap { source, .. }
             | SignalNode::Fract { source }
             | SignalNode::Map { source, .. }
+            | SignalNode::Step { source, .. }
             | SignalNode::Smoothstep { source, .. }
+            | SignalNode::Ease { source, .. }
             | SignalNode::Diff { source }
             | SignalNode::Integrate { source, .. }
+            | SignalNode::Window { source, .. }
             | SignalNode::Delay { source, .. }
             | SignalNode::Anticipate { source, .. }
             | SignalNode::Not { source } => {
@@ -811,11 +887,13 @@ impl Signal {
             | SignalNode::Generator(_)
             | SignalNode::EventStreamSource { .. }
             | SignalNode::EventStreamEnvelope { .. }
+            | SignalNode::Adsr { .. }
             | SignalNode::EventDistanceFromPrev { .. }
             | SignalNode::EventDistanceToNext { .. }
             | SignalNode::EventCountInWindow { .. }
             | SignalNode::EventDensityInWindow { .. }
-            | SignalNode::EventPhaseBetween { .. } => {}
+            | SignalNode::EventPhaseBetween { .. }
+            | SignalNode::EventWeightHeld { .. } => {}
         }
     }
 
@@ -840,6 +918,23 @@ impl Signal {
         Signal::new(SignalNode::EventStreamEnvelope { events, options })
     }
 
+    /// Create an ADSR envelope that retriggers on each event (see [`SignalNode::Adsr`]).
+    pub fn adsr_from_events(
+        events: Arc<Vec<Event>>,
+        attack_beats: f32,
+        decay_beats: f32,
+        sustain_level: f32,
+        release_beats: f32,
+    ) -> Signal {
+        Signal::new(SignalNode::Adsr {
+            events,
+            attack_beats: attack_beats.max(0.0),
+            decay_beats: decay_beats.max(0.0),
+            sustain_level: sustain_level.clamp(0.0, 1.0),
+            release_beats: release_beats.max(0.0),
+        })
+    }
+
     /// Create a signal representing distance from previous event.
     /// Returns 0 at event time, grows linearly until next event.
     pub fn from_events_distance_from_prev(events: Arc<Vec<Event>>, unit: TimeUnit) -> Signal {
@@ -888,6 +983,12 @@ impl Signal {
         Signal::new(SignalNode::EventPhaseBetween { events })
     }
 
+    /// Create a signal holding the weight of the most recent event.
+    /// Returns 0 before the first event.
+    pub fn from_events_weight_held(events: Arc<Vec<Event>>) -> Signal {
+        Signal::new(SignalNode::EventWeightHeld { events })
+    }
+
     /// Returns a human-readable description of the signal's computation graph.
     ///
     /// Useful for debugging to understand how a signal is constructed.
@@ -1022,6 +1123,13 @@ impl Signal {
                 )
             }
             SignalNode::Fract { source } => format!("{}.Fract()", self.describe_node(&source.node)),
+            SignalNode::Ease { source, easing } => {
+                format!(
+                    "{}.Ease({})",
+                    self.describe_node(&source.node),
+                    easing.name()
+                )
+            }
             SignalNode::Wrap { source, min, max } => {
                 format!(
                     "{}.Wrap({}, {})",
@@ -1046,6 +1154,13 @@ impl Signal {
                     self.describe_param(out_max)
                 )
             }
+            SignalNode::Step { source, edge } => {
+                format!(
+                    "{}.Step({})",
+                    self.describe_node(&source.node),
+                    self.describe_param(edge)
+                )
+            }
             SignalNode::Smoothstep {
                 source,
                 edge0,
@@ -1112,6 +1227,13 @@ impl Signal {
                 NormaliseParams::Robust => {
                     format!("{}.Normalise.Robust()", self.describe_node(&source.node))
                 }
+                NormaliseParams::Window { seconds } => {
+                    format!(
+                        "{}.Normalise.Window({}s)",
+                        self.describe_node(&source.node),
+                        seconds
+                    )
+                }
                 NormaliseParams::Range { min, max } => {
                     format!(
                         "{}.Normalise.ToRange({}, {})",
@@ -1152,6 +1274,18 @@ impl Signal {
                     self.describe_param(decay_beats)
                 )
             }
+            SignalNode::Window {
+                source,
+                seconds,
+                aggregate,
+            } => {
+                format!(
+                    "{}.Window{:?}({}s)",
+                    self.describe_node(&source.node),
+                    aggregate,
+                    self.describe_param(seconds)
+                )
+            }
             SignalNode::Delay { source, beats } => {
                 format!(
                     "{}.Delay({})",
@@ -1192,6 +1326,9 @@ impl Signal {
                 GeneratorNode::Perlin { scale_beats, seed } => {
                     format!("gen.perlin({}, {})", scale_beats, seed)
                 }
+                GeneratorNode::Sequence { values, step_beats } => {
+                    format!("gen.sequence({:?}, {})", values, step_beats)
+                }
             },
             SignalNode::EventStreamSource { events } => {
                 format!("Events(count={})", events.len())
@@ -1199,6 +1336,22 @@ impl Signal {
             SignalNode::EventStreamEnvelope { events, .. } => {
                 format!("EventsEnvelope(count={})", events.len())
             }
+            SignalNode::Adsr {
+                events,
+                attack_beats,
+                decay_beats,
+                sustain_level,
+                release_beats,
+            } => {
+                format!(
+                    "Adsr(count={}, a={}, d={}, s={}, r={})",
+                    events.len(),
+                    attack_beats,
+                    decay_beats,
+                    sustain_level,
+                    release_beats
+                )
+            }
             SignalNode::EventDistanceFromPrev { events, unit } => {
                 format!("EventDistanceFromPrev(count={}, {:?})", events.len(), unit)
             }
@@ -1234,6 +1387,9 @@ impl Signal {
             SignalNode::EventPhaseBetween { events } => {
                 format!("EventPhaseBetween(count={})", events.len())
             }
+            SignalNode::EventWeightHeld { events } => {
+                format!("EventWeightHeld(count={})", events.len())
+            }
             // Comparison operations
             SignalNode::Lt(a, b) => {
                 format!(
@@ -1485,6 +1641,18 @@ pub enum SignalNode {
         options: ToSignalOptions,
     },
 
+    /// ADSR envelope triggered by events in the current frame window.
+    /// Events are instantaneous, so each trigger is also a release: attack to the
+    /// event weight, decay to `sustain_level * weight`, then release to 0.
+    /// A trigger during an active envelope restarts the attack from the current level.
+    Adsr {
+        events: Arc<Vec<Event>>,
+        attack_beats: f32,
+        decay_beats: f32,
+        sustain_level: f32,
+        release_beats: f32,
+    },
+
     /// Distance from current time to previous event.
     /// Returns 0 at event time, grows linearly until next event.
     /// Before first event: returns distance to first event.
@@ -1524,6 +1692,10 @@ pub enum SignalNode {
     /// Before first event: returns 0. After last event: returns 1.
     EventPhaseBetween { events: Arc<Vec<Event>> },
 
+    /// Weight (velocity) of the most recent event at or before the current
+    /// time, held until the next event. Before first event: returns 0.
+    EventWeightHeld { events: Arc<Vec<Event>> },
+
     // === Math Primitives ===
     /// Sigmoid curve centered at 0.5.
     Sigmoid { source: Signal, k: SignalParam },
@@ -1614,6 +1786,8 @@ pub enum SignalNode {
         out_min: SignalParam,
         out_max: SignalParam,
     },
+    /// Hard threshold: 0 below the edge, 1 at or above it.
+    Step { source: Signal, edge: SignalParam },
     /// Smoothstep interpolation between edges.
     Smoothstep {
         source: Signal,
@@ -1626,6 +1800,11 @@ pub enum SignalNode {
         b: Signal,
         t: SignalParam,
     },
+    /// Easing curve applied to a 0-1 value (input clamped).
+    Ease {
+        source: Signal,
+        easing: EasingFunction,
+    },
 
     // === Rate and Accumulation ===
     /// Rate of change (derivative approximation).
@@ -1636,6 +1815,15 @@ pub enum SignalNode {
         decay_beats: SignalParam,
     },
 
+    // === Windowed Statistics ===
+    /// Min/max/average over a sliding window of recent samples (seconds can
+    /// be constant or signal).
+    Window {
+        source: Signal,
+        seconds: SignalParam,
+        aggregate: WindowAggregate,
+    },
+
     // === Time Shifting ===
     /// Delay by N beats (look back in time). Beats can be constant or signal.
     Delay { source: Signal, beats: SignalParam },
@@ -1707,6 +1895,18 @@ pub enum GeneratorNode {
     /// - `scale_beats`: Scale factor in beats.
     /// - `seed`: Random seed for deterministic output.
     Perlin { scale_beats: f32, seed: u64 },
+
+    /// Step sequencer: outputs `values[step]`, advancing one step every
+    /// `step_beats` beats and wrapping around the end of the array.
+    Sequence { values: Vec<f32>, step_beats: f32 },
+}
+
+/// Aggregate computed by a sliding-window node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowAggregate {
+    Min,
+    Max,
+    Avg,
 }
 
 /// Type of noise for the noise generator.
@@ -1768,9 +1968,10 @@ pub enum EnvelopeShape {
     ExponentialDecay,
 }
 
-/// Easing function for envelope transitions.
+/// Easing function for envelope transitions and `Signal.ease()`.
 ///
-/// Controls the shape of attack/decay/release curves.
+/// Controls the shape of attack/decay/release curves. Covers the standard
+/// Penner easings; see [`EasingFunction::apply`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum EasingFunction {
     /// Linear interpolation.
@@ -1794,8 +1995,303 @@ pub enum EasingFunction {
     ExponentialOut,
     /// Smooth step (Hermite interpolation).
     SmoothStep,
-    /// Elastic/bouncy effect (overshoots target).
+    /// Elastic/bouncy effect (overshoots target). Penner's elastic ease-out.
     Elastic,
+    /// Exponential ease-in-out.
+    ExponentialInOut,
+    /// Quartic ease-in.
+    QuarticIn,
+    /// Quartic ease-out.
+    QuarticOut,
+    /// Quartic ease-in-out.
+    QuarticInOut,
+    /// Quintic ease-in.
+    QuinticIn,
+    /// Quintic ease-out.
+    QuinticOut,
+    /// Quintic ease-in-out.
+    QuinticInOut,
+    /// Sinusoidal ease-in.
+    SineIn,
+    /// Sinusoidal ease-out.
+    SineOut,
+    /// Sinusoidal ease-in-out.
+    SineInOut,
+    /// Circular ease-in.
+    CircularIn,
+    /// Circular ease-out.
+    CircularOut,
+    /// Circular ease-in-out.
+    CircularInOut,
+    /// Back ease-in (pulls back below 0 before starting).
+    BackIn,
+    /// Back ease-out (overshoots 1 before settling).
+    BackOut,
+    /// Back ease-in-out.
+    BackInOut,
+    /// Elastic ease-in.
+    ElasticIn,
+    /// Elastic ease-in-out.
+    ElasticInOut,
+    /// Bounce ease-in.
+    BounceIn,
+    /// Bounce ease-out (bounces to rest at 1).
+    BounceOut,
+    /// Bounce ease-in-out.
+    BounceInOut,
+}
+
+impl EasingFunction {
+    /// Script names (`ease.outCubic(t)`, `signal.ease("outCubic")`), in
+    /// documentation order.
+    pub const NAMED: &'static [(&'static str, EasingFunction)] = &[
+        ("linear", EasingFunction::Linear),
+        ("smoothstep", EasingFunction::SmoothStep),
+        ("inQuad", EasingFunction::QuadraticIn),
+        ("outQuad", EasingFunction::QuadraticOut),
+        ("inOutQuad", EasingFunction::QuadraticInOut),
+        ("inCubic", EasingFunction::CubicIn),
+        ("outCubic", EasingFunction::CubicOut),
+        ("inOutCubic", EasingFunction::CubicInOut),
+        ("inQuart", EasingFunction::QuarticIn),
+        ("outQuart", EasingFunction::QuarticOut),
+        ("inOutQuart", EasingFunction::QuarticInOut),
+        ("inQuint", EasingFunction::QuinticIn),
+        ("outQuint", EasingFunction::QuinticOut),
+        ("inOutQuint", EasingFunction::QuinticInOut),
+        ("inSine", EasingFunction::SineIn),
+        ("outSine", EasingFunction::SineOut),
+        ("inOutSine", EasingFunction::SineInOut),
+        ("inExpo", EasingFunction::ExponentialIn),
+        ("outExpo", EasingFunction::ExponentialOut),
+        ("inOutExpo", EasingFunction::ExponentialInOut),
+        ("inCirc", EasingFunction::CircularIn),
+        ("outCirc", EasingFunction::CircularOut),
+        ("inOutCirc", EasingFunction::CircularInOut),
+        ("inBack", EasingFunction::BackIn),
+        ("outBack", EasingFunction::BackOut),
+        ("inOutBack", EasingFunction::BackInOut),
+        ("inElastic", EasingFunction::ElasticIn),
+        ("outElastic", EasingFunction::Elastic),
+        ("inOutElastic", EasingFunction::ElasticInOut),
+        ("inBounce", EasingFunction::BounceIn),
+        ("outBounce", EasingFunction::BounceOut),
+        ("inOutBounce", EasingFunction::BounceInOut),
+    ];
+
+    /// Look up an easing by script name. An `ease` prefix is accepted, so
+    /// `"easeOutBack"` and `"outBack"` are the same.
+    pub fn from_name(name: &str) -> Option<EasingFunction> {
+        let name = match name.strip_prefix("ease") {
+            Some(rest) if !rest.is_empty() => rest,
+            _ => name,
+        };
+        Self::NAMED
+            .iter()
+            .find_map(|(candidate, easing)| candidate.eq_ignore_ascii_case(name).then_some(*easing))
+    }
+
+    /// Script name of this easing.
+    pub fn name(self) -> &'static str {
+        Self::NAMED
+            .iter()
+            .find(|(_, easing)| *easing == self)
+            .map(|(name, _)| *name)
+            .unwrap_or("linear")
+    }
+
+    /// Apply the easing to a normalized value. The input is clamped to 0-1;
+    /// every easing maps 0 to 0 and 1 to 1 (back and elastic easings
+    /// overshoot in between).
+    pub fn apply(self, t: f32) -> f32 {
+        use std::f32::consts::PI;
+
+        // Penner's constants for back and elastic easings.
+        const BACK: f32 = 1.70158;
+        const BACK_IN_OUT: f32 = BACK * 1.525;
+        const ELASTIC: f32 = 2.0 * PI / 3.0;
+        const ELASTIC_IN_OUT: f32 = 2.0 * PI / 4.5;
+
+        let t = t.clamp(0.0, 1.0);
+
+        match self {
+            EasingFunction::Linear => t,
+
+            EasingFunction::QuadraticIn => t * t,
+
+            EasingFunction::QuadraticOut => t * (2.0 - t),
+
+            EasingFunction::QuadraticInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    -1.0 + (4.0 - 2.0 * t) * t
+                }
+            }
+
+            EasingFunction::CubicIn => t * t * t,
+
+            EasingFunction::CubicOut => {
+                let t1 = t - 1.0;
+                t1 * t1 * t1 + 1.0
+            }
+
+            EasingFunction::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    let t1 = 2.0 * t - 2.0;
+                    0.5 * t1 * t1 * t1 + 1.0
+                }
+            }
+
+            EasingFunction::QuarticIn => t.powi(4),
+
+            EasingFunction::QuarticOut => 1.0 - (1.0 - t).powi(4),
+
+            EasingFunction::QuarticInOut => {
+                if t < 0.5 {
+                    8.0 * t.powi(4)
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(4) / 2.0
+                }
+            }
+
+            EasingFunction::QuinticIn => t.powi(5),
+
+            EasingFunction::QuinticOut => 1.0 - (1.0 - t).powi(5),
+
+            EasingFunction::QuinticInOut => {
+                if t < 0.5 {
+                    16.0 * t.powi(5)
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(5) / 2.0
+                }
+            }
+
+            EasingFunction::SineIn => 1.0 - (t * PI / 2.0).cos(),
+
+            EasingFunction::SineOut => (t * PI / 2.0).sin(),
+
+            EasingFunction::SineInOut => -((PI * t).cos() - 1.0) / 2.0,
+
+            EasingFunction::ExponentialIn => {
+                if t == 0.0 {
+                    0.0
+                } else {
+                    2.0_f32.powf(10.0 * (t - 1.0))
+                }
+            }
+
+            EasingFunction::ExponentialOut => {
+                if t == 1.0 {
+                    1.0
+                } else {
+                    1.0 - 2.0_f32.powf(-10.0 * t)
+                }
+            }
+
+            EasingFunction::ExponentialInOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else if t < 0.5 {
+                    2.0_f32.powf(20.0 * t - 10.0) / 2.0
+                } else {
+                    (2.0 - 2.0_f32.powf(-20.0 * t + 10.0)) / 2.0
+                }
+            }
+
+            EasingFunction::CircularIn => 1.0 - (1.0 - t * t).sqrt(),
+
+            EasingFunction::CircularOut => (1.0 - (t - 1.0).powi(2)).sqrt(),
+
+            EasingFunction::CircularInOut => {
+                if t < 0.5 {
+                    (1.0 - (1.0 - (2.0 * t).powi(2)).sqrt()) / 2.0
+                } else {
+                    ((1.0 - (-2.0 * t + 2.0).powi(2)).sqrt() + 1.0) / 2.0
+                }
+            }
+
+            EasingFunction::BackIn => (BACK + 1.0) * t * t * t - BACK * t * t,
+
+            EasingFunction::BackOut => {
+                let t1 = t - 1.0;
+                1.0 + (BACK + 1.0) * t1 * t1 * t1 + BACK * t1 * t1
+            }
+
+            EasingFunction::BackInOut => {
+                if t < 0.5 {
+                    (2.0 * t).powi(2) * ((BACK_IN_OUT + 1.0) * 2.0 * t - BACK_IN_OUT) / 2.0
+                } else {
+                    ((2.0 * t - 2.0).powi(2)
+                        * ((BACK_IN_OUT + 1.0) * (t * 2.0 - 2.0) + BACK_IN_OUT)
+                        + 2.0)
+                        / 2.0
+                }
+            }
+
+            EasingFunction::SmoothStep => t * t * (3.0 - 2.0 * t),
+
+            EasingFunction::ElasticIn => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    -(2.0_f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * ELASTIC).sin()
+                }
+            }
+
+            EasingFunction::Elastic => {
+                if t == 0.0 {
+                    0.0
+                } else if t == 1.0 {
+                    1.0
+                } else {
+                    2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * ELASTIC).sin() + 1.0
+                }
+            }
+
+            EasingFunction::ElasticInOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else if t < 0.5 {
+                    -(2.0_f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin())
+                        / 2.0
+                } else {
+                    2.0_f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * ELASTIC_IN_OUT).sin()
+                        / 2.0
+                        + 1.0
+                }
+            }
+
+            EasingFunction::BounceIn => 1.0 - EasingFunction::BounceOut.apply(1.0 - t),
+
+            EasingFunction::BounceOut => {
+                const N1: f32 = 7.5625;
+                const D1: f32 = 2.75;
+                if t < 1.0 / D1 {
+                    N1 * t * t
+                } else if t < 2.0 / D1 {
+                    let t1 = t - 1.5 / D1;
+                    N1 * t1 * t1 + 0.75
+                } else if t < 2.5 / D1 {
+                    let t1 = t - 2.25 / D1;
+                    N1 * t1 * t1 + 0.9375
+                } else {
+                    let t1 = t - 2.625 / D1;
+                    N1 * t1 * t1 + 0.984375
+                }
+            }
+
+            EasingFunction::BounceInOut => {
+                if t < 0.5 {
+                    (1.0 - EasingFunction::BounceOut.apply(1.0 - 2.0 * t)) / 2.0
+                } else {
+                    (1.0 + EasingFunction::BounceOut.apply(2.0 * t - 1.0)) / 2.0
+                }
+            }
+        }
+    }
 }
 
 /// How to combine overlapping envelopes.
@@ -1898,6 +2394,11 @@ pub enum NormaliseParams {
 
     /// Direct range mapping (doesn't require statistics).
     Range { min: f32, max: f32 },
+
+    /// Min-max normalization over the last `seconds` of evaluated frames.
+    /// Computed live (an automatic gain control), so it needs no
+    /// precomputed statistics and works on streaming input.
+    Window { seconds: f32 },
 }
 
 /// Parameters for gating operations.
@@ -2021,6 +2522,11 @@ impl NormaliseBuilder {
     pub fn to_range(self, min: f32, max: f32) -> Signal {
         self.source.normalise(NormaliseParams::Range { min, max })
     }
+
+    /// Apply live min-max normalization over the last `seconds`.
+    pub fn window(self, seconds: f32) -> Signal {
+        self.source.normalise(NormaliseParams::Window { seconds })
+    }
 }
 
 /// Builder for gating operations, returned by `signal.gate`.
@@ -2245,4 +2751,37 @@ mod tests {
             matches!(&*selected.node, SignalNode::Select { cases, default: _ } if cases.len() == 2)
         );
     }
+
+    #[test]
+    fn test_easings_hit_endpoints_and_standard_ones_are_monotonic() {
+        for (name, easing) in EasingFunction::NAMED {
+            assert!(easing.apply(0.0).abs() < 1e-5, "{} at 0", name);
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-5, "{} at 1", name);
+
+            // Back and elastic overshoot, bounce rebounds by design.
+            if name.contains("Back") || name.contains("Elastic") || name.contains("Bounce") {
+                continue;
+            }
+            let samples: Vec<f32> = (0..=100).map(|i| easing.apply(i as f32 / 100.0)).collect();
+            assert!(
+                samples.windows(2).all(|w| w[1] >= w[0] - 1e-6),
+                "{} is not monotonic",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_easing_from_name() {
+        assert_eq!(
+            EasingFunction::from_name("outCubic"),
+            Some(EasingFunction::CubicOut)
+        );
+        assert_eq!(
+            EasingFunction::from_name("easeOutBack"),
+            Some(EasingFunction::BackOut)
+        );
+        assert_eq!(EasingFunction::from_name("wobble"), None);
+        assert_eq!(EasingFunction::CubicOut.name(), "outCubic");
+    }
 }
@@ -9,20 +9,26 @@
 //! - Runtime state (for smoothing, gates)
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 
 use crate::debug_collector::debug_emit;
 use crate::input::{BandSignalMap, InputSignal, SignalMap};
-use crate::musical_time::{MusicalTimeSegment, MusicalTimeStructure, DEFAULT_BPM};
+use crate::musical_time::{
+    MusicalTimeSegment, MusicalTimeStructure, DEFAULT_BEATS_PER_BAR, DEFAULT_BPM,
+};
 use crate::signal::SignalId;
 use crate::signal::{
-    EasingFunction, EnvelopeShape, GateParams, GeneratorNode, NoiseType, NormaliseParams,
-    OverlapMode, SamplingConfig, SamplingStrategy, SamplingWindow, Signal, SignalNode,
-    SmoothParams, TimeUnit, ToSignalOptions, WindowDirection,
+    EnvelopeShape, GateParams, GeneratorNode, NoiseType, NormaliseParams, OverlapMode,
+    SamplingConfig, SamplingStrategy, SamplingWindow, Signal, SignalNode, SmoothParams, TimeUnit,
+    ToSignalOptions, WindowAggregate, WindowDirection,
 };
-use crate::signal_state::SignalState;
+use crate::signal_state::{AdsrState, SignalState};
 use crate::signal_stats::StatisticsCache;
 
+/// Upper bound on samples kept by a sliding-window node (matches the
+/// moving-average and delay buffer caps).
+const MAX_WINDOW_SAMPLES: usize = 10000;
+
 /// Evaluation context for Signal evaluation.
 ///
 /// This struct carries all the state needed to evaluate a Signal at a given time.
@@ -151,6 +157,19 @@ impl<'a> EvalContext<'a> {
         }
     }
 
+    /// Get bar position at current time (continuous).
+    ///
+    /// Bars start on the segment's downbeat (its phase offset) and span its
+    /// beats per bar (4/4 unless a time signature is set).
+    pub fn bar_position(&self) -> f32 {
+        if let Some(segment) = self.current_segment() {
+            segment.bar_position_at(self.time)
+        } else {
+            // Default: 4/4 at 120 BPM starting at time 0
+            self.beat_position() / DEFAULT_BEATS_PER_BAR
+        }
+    }
+
     /// Get track duration, with fallback to a large value if not available.
     pub fn get_track_duration(&self) -> f32 {
         self.track_duration.unwrap_or(f32::MAX)
@@ -208,6 +227,9 @@ impl Signal {
                     "time.beats" => ctx.beat_position(),
                     "time.beatIndex" => ctx.beat_position().floor(),
                     "time.phase" => ctx.beat_position().fract(),
+                    "time.bars" => ctx.bar_position(),
+                    "time.barIndex" => ctx.bar_position().floor(),
+                    "time.barPhase" => ctx.bar_position().rem_euclid(1.0),
                     "time.bpm" => ctx.current_bpm(),
                     _ => ctx
                         .input_signals
@@ -317,6 +339,21 @@ impl Signal {
                 self.evaluate_event_stream_envelope(events, options, ctx)
             }
 
+            SignalNode::Adsr {
+                events,
+                attack_beats,
+                decay_beats,
+                sustain_level,
+                release_beats,
+            } => self.evaluate_adsr(
+                events,
+                *attack_beats,
+                *decay_beats,
+                *sustain_level,
+                *release_beats,
+                ctx,
+            ),
+
             SignalNode::EventDistanceFromPrev { events, unit } => {
                 self.evaluate_event_distance_from_prev(events, *unit, ctx)
             }
@@ -349,6 +386,10 @@ impl Signal {
                 self.evaluate_event_phase_between(events, ctx)
             }
 
+            SignalNode::EventWeightHeld { events } => Self::find_prev_event_index(events, ctx.time)
+                .map(|i| events[i].weight)
+                .unwrap_or(0.0),
+
             // === Math Primitives ===
             SignalNode::Sigmoid { source, k } => {
                 let x = source.evaluate(ctx);
@@ -495,6 +536,7 @@ impl Signal {
             }
 
             SignalNode::Fract { source } => source.evaluate(ctx).fract(),
+            SignalNode::Ease { source, easing } => easing.apply(source.evaluate(ctx)),
 
             // === Mapping / Shaping ===
             SignalNode::Map {
@@ -518,6 +560,14 @@ impl Signal {
                 }
             }
 
+            SignalNode::Step { source, edge } => {
+                if source.evaluate(ctx) < edge.evaluate(ctx) {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+
             SignalNode::Smoothstep {
                 source,
                 edge0,
@@ -557,6 +607,16 @@ impl Signal {
                 self.evaluate_integrate(source, decay, ctx)
             }
 
+            // === Windowed Statistics ===
+            SignalNode::Window {
+                source,
+                seconds,
+                aggregate,
+            } => {
+                let secs = seconds.evaluate(ctx);
+                self.evaluate_window(source, secs, *aggregate, ctx)
+            }
+
             // === Time Shifting ===
             SignalNode::Delay { source, beats } => {
                 let b = beats.evaluate(ctx);
@@ -789,6 +849,14 @@ impl Signal {
                 let t = beat_pos / scale_beats;
                 self.perlin_1d(t, *seed)
             }
+
+            GeneratorNode::Sequence { values, step_beats } => {
+                if values.is_empty() {
+                    return 0.0;
+                }
+                let step = (beat_pos / step_beats).floor() as i64;
+                values[step.rem_euclid(values.len() as i64) as usize]
+            }
         }
     }
 
@@ -972,6 +1040,22 @@ impl Signal {
                 }
             }
 
+            NormaliseParams::Window { seconds } => {
+                // Live min-max over recent frames, doesn't need statistics
+                let samples = self.record_window_sample(raw, *seconds, ctx);
+                let (min, max) = samples
+                    .iter()
+                    .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), (_, v)| {
+                        (lo.min(*v), hi.max(*v))
+                    });
+                let range = max - min;
+                if range > 0.0 {
+                    (raw - min) / range
+                } else {
+                    0.5
+                }
+            }
+
             NormaliseParams::Range { min, max } => {
                 // Direct range mapping, doesn't need statistics
                 let range = max - min;
@@ -1051,6 +1135,73 @@ impl Signal {
         result
     }
 
+    /// Evaluate an event-triggered ADSR envelope.
+    ///
+    /// Events falling in this frame's window `(time - dt, time]` (re)trigger the
+    /// envelope; a retrigger ramps the attack up from the level at that moment.
+    fn evaluate_adsr(
+        &self,
+        events: &[crate::event_stream::Event],
+        attack_beats: f32,
+        decay_beats: f32,
+        sustain_level: f32,
+        release_beats: f32,
+        ctx: &mut EvalContext,
+    ) -> f32 {
+        let attack = ctx.beats_to_seconds(attack_beats);
+        let decay = ctx.beats_to_seconds(decay_beats);
+        let release = ctx.beats_to_seconds(release_beats);
+
+        let level_at = |state: &AdsrState, time: f32| -> f32 {
+            let t = time - state.trigger_time;
+            let sustain = state.peak * sustain_level;
+            if t < 0.0 {
+                0.0
+            } else if t < attack {
+                state.start_level + (state.peak - state.start_level) * (t / attack)
+            } else if t < attack + decay {
+                state.peak - (state.peak - sustain) * ((t - attack) / decay)
+            } else if t < attack + decay + release {
+                sustain * (1.0 - (t - attack - decay) / release)
+            } else {
+                0.0
+            }
+        };
+
+        // Seeking back before the current trigger invalidates it.
+        let previous = ctx
+            .state
+            .adsr_state
+            .get(&self.id)
+            .copied()
+            .filter(|state| state.trigger_time <= ctx.time);
+
+        let window_start = ctx.time - ctx.dt;
+        let trigger = events
+            .iter()
+            .rfind(|event| event.time > window_start && event.time <= ctx.time);
+
+        let current = match trigger {
+            Some(event) => Some(AdsrState {
+                trigger_time: event.time,
+                peak: event.weight,
+                start_level: previous.map_or(0.0, |state| level_at(&state, event.time)),
+            }),
+            None => previous,
+        };
+
+        match current {
+            Some(state) => {
+                ctx.state.adsr_state.insert(self.id, state);
+                level_at(&state, ctx.time)
+            }
+            None => {
+                ctx.state.adsr_state.remove(&self.id);
+                0.0
+            }
+        }
+    }
+
     /// Evaluate a single event's envelope contribution at the current time.
     fn evaluate_single_envelope(
         &self,
@@ -1102,7 +1253,7 @@ impl Signal {
                     } else {
                         1.0
                     };
-                    weight * apply_easing(t, options.easing)
+                    weight * options.easing.apply(t)
                 } else {
                     // Decay phase
                     let decay_dt = dt - attack_sec;
@@ -1110,7 +1261,7 @@ impl Signal {
                         0.0
                     } else {
                         let t = decay_dt / decay_sec;
-                        weight * (1.0 - apply_easing(t, options.easing))
+                        weight * (1.0 - options.easing.apply(t))
                     }
                 }
             }
@@ -1125,7 +1276,7 @@ impl Signal {
                     } else {
                         1.0
                     };
-                    weight * apply_easing(t, options.easing)
+                    weight * options.easing.apply(t)
                 } else if dt < attack_sec + decay_sec {
                     // Decay: 1 → sustain_level
                     let decay_dt = dt - attack_sec;
@@ -1135,7 +1286,7 @@ impl Signal {
                         1.0
                     };
                     let decay_amount = 1.0 - options.sustain_level;
-                    weight * (1.0 - decay_amount * apply_easing(t, options.easing))
+                    weight * (1.0 - decay_amount * options.easing.apply(t))
                 } else if dt < attack_sec + decay_sec + sustain_sec {
                     // Sustain: hold at sustain_level
                     weight * options.sustain_level
@@ -1146,7 +1297,7 @@ impl Signal {
                         0.0
                     } else {
                         let t = release_dt / release_sec;
-                        weight * options.sustain_level * (1.0 - apply_easing(t, options.easing))
+                        weight * options.sustain_level * (1.0 - options.easing.apply(t))
                     }
                 }
             }
@@ -1433,6 +1584,53 @@ impl Signal {
         new_accumulated
     }
 
+    /// Evaluate a sliding-window aggregate over the last `seconds`.
+    fn evaluate_window(
+        &self,
+        source: &Signal,
+        seconds: f32,
+        aggregate: WindowAggregate,
+        ctx: &mut EvalContext,
+    ) -> f32 {
+        let current = source.evaluate(ctx);
+        let samples = self.record_window_sample(current, seconds, ctx);
+        let values = samples.iter().map(|(_, v)| *v);
+        match aggregate {
+            WindowAggregate::Min => values.fold(f32::INFINITY, f32::min),
+            WindowAggregate::Max => values.fold(f32::NEG_INFINITY, f32::max),
+            WindowAggregate::Avg => values.sum::<f32>() / samples.len() as f32,
+        }
+    }
+
+    /// Append this frame's value to the node's sample window and return the
+    /// samples from the last `seconds`.
+    ///
+    /// Samples older than the window are evicted each frame; the current
+    /// sample is always included. Seeking backwards restarts the window.
+    fn record_window_sample<'c>(
+        &self,
+        current: f32,
+        seconds: f32,
+        ctx: &'c mut EvalContext,
+    ) -> &'c VecDeque<(f32, f32)> {
+        let time = ctx.time;
+        let samples = ctx.state.get_window_samples(self.id);
+
+        if samples.back().is_some_and(|(t, _)| *t > time) {
+            samples.clear();
+        }
+        samples.push_back((time, current));
+        let cutoff = time - seconds.max(0.0);
+        while samples.front().is_some_and(|(t, _)| *t < cutoff) {
+            samples.pop_front();
+        }
+        // Bound memory for very long windows at high frame rates.
+        while samples.len() > MAX_WINDOW_SAMPLES {
+            samples.pop_front();
+        }
+        samples
+    }
+
     // =========================================================================
     // Time Shifting Operations
     // =========================================================================
@@ -1483,76 +1681,6 @@ impl Signal {
     }
 }
 
-// =============================================================================
-// Easing Functions
-// =============================================================================
-
-/// Apply an easing function to a normalized value (0-1).
-fn apply_easing(t: f32, easing: EasingFunction) -> f32 {
-    let t = t.clamp(0.0, 1.0);
-
-    match easing {
-        EasingFunction::Linear => t,
-
-        EasingFunction::QuadraticIn => t * t,
-
-        EasingFunction::QuadraticOut => t * (2.0 - t),
-
-        EasingFunction::QuadraticInOut => {
-            if t < 0.5 {
-                2.0 * t * t
-            } else {
-                -1.0 + (4.0 - 2.0 * t) * t
-            }
-        }
-
-        EasingFunction::CubicIn => t * t * t,
-
-        EasingFunction::CubicOut => {
-            let t1 = t - 1.0;
-            t1 * t1 * t1 + 1.0
-        }
-
-        EasingFunction::CubicInOut => {
-            if t < 0.5 {
-                4.0 * t * t * t
-            } else {
-                let t1 = 2.0 * t - 2.0;
-                0.5 * t1 * t1 * t1 + 1.0
-            }
-        }
-
-        EasingFunction::ExponentialIn => {
-            if t == 0.0 {
-                0.0
-            } else {
-                2.0_f32.powf(10.0 * (t - 1.0))
-            }
-        }
-
-        EasingFunction::ExponentialOut => {
-            if t == 1.0 {
-                1.0
-            } else {
-                1.0 - 2.0_f32.powf(-10.0 * t)
-            }
-        }
-
-        EasingFunction::SmoothStep => t * t * (3.0 - 2.0 * t),
-
-        EasingFunction::Elastic => {
-            if t == 0.0 {
-                0.0
-            } else if t == 1.0 {
-                1.0
-            } else {
-                let c4 = (2.0 * std::f32::consts::PI) / 3.0;
-                2.0_f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * c4).sin() + 1.0
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1644,6 +1772,57 @@ mod tests {
         assert!((unknown.evaluate(&mut ctx) - 0.0).abs() < 0.001);
     }
 
+    #[test]
+    fn test_evaluate_stem_band_input() {
+        use crate::signal::stem_band_feature;
+
+        // Drums "Hi" band: quiet for the first half second, loud after.
+        // Bass "Hi" band: constant and low.
+        let drums_hi: Vec<f32> = (0..100).map(|i| if i < 50 { 0.1 } else { 0.9 }).collect();
+        let mut stem_signals: BandSignalMap = HashMap::new();
+        stem_signals.entry("drums".to_string()).or_default().insert(
+            stem_band_feature("Hi", "energy"),
+            std::rc::Rc::new(InputSignal::new(drums_hi, 100.0)),
+        );
+        stem_signals.entry("bass".to_string()).or_default().insert(
+            stem_band_feature("Hi", "energy"),
+            std::rc::Rc::new(InputSignal::new(vec![0.05; 100], 100.0)),
+        );
+
+        let inputs = HashMap::new();
+        let band_signals = HashMap::new();
+        let custom_signals = HashMap::new();
+        let composed_signals = HashMap::new();
+        let stats = StatisticsCache::new();
+        let eval_at = |signal: &Signal, time: f32| {
+            let mut state = SignalState::new();
+            let mut ctx = make_test_context(
+                time,
+                0.016,
+                &inputs,
+                &band_signals,
+                &stem_signals,
+                &custom_signals,
+                &composed_signals,
+                &stats,
+                &mut state,
+            );
+            signal.evaluate(&mut ctx)
+        };
+
+        let drums_hi = Signal::stem_band_input("drums", "Hi", "energy");
+        assert!((eval_at(&drums_hi, 0.25) - 0.1).abs() < 0.001);
+        assert!((eval_at(&drums_hi, 0.75) - 0.9).abs() < 0.001);
+
+        // Scoped to its stem: the bass stem's band reads its own data.
+        let bass_hi = Signal::stem_band_input("bass", "Hi", "energy");
+        assert!((eval_at(&bass_hi, 0.75) - 0.05).abs() < 0.001);
+
+        // Missing band on a known stem returns 0
+        let missing = Signal::stem_band_input("drums", "Lo", "energy");
+        assert!(eval_at(&missing, 0.75).abs() < 0.001);
+    }
+
     #[test]
     fn test_evaluate_band_input() {
         let inputs = HashMap::new();
@@ -1838,6 +2017,238 @@ mod tests {
         assert!((pass_through.evaluate(&mut ctx) - 0.2).abs() < 0.001);
     }
 
+    /// Evaluate `signal` once per frame at 64 fps for `frames` frames, returning each value.
+    fn run_frames(signal: &Signal, frames: usize) -> Vec<f32> {
+        let inputs = HashMap::new();
+        let band_signals = HashMap::new();
+        let stem_signals = HashMap::new();
+        let custom_signals = HashMap::new();
+        let composed_signals = HashMap::new();
+        let stats = StatisticsCache::new();
+        let mut state = SignalState::new();
+        let dt = 1.0 / 64.0;
+
+        (0..frames)
+            .map(|frame| {
+                let mut ctx = make_test_context(
+                    frame as f32 * dt,
+                    dt,
+                    &inputs,
+                    &band_signals,
+                    &stem_signals,
+                    &custom_signals,
+                    &composed_signals,
+                    &stats,
+                    &mut state,
+                );
+                signal.evaluate(&mut ctx)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_evaluate_adsr_single_trigger_shape() {
+        use crate::event_stream::Event;
+
+        // Default 120 BPM: attack and decay of 0.5 beats are 16 frames each,
+        // a 1 beat release is 32 frames. The trigger lands on frame 16.
+        let adsr = Signal::adsr_from_events(
+            std::sync::Arc::new(vec![Event::new(0.25, 1.0)]),
+            0.5,
+            0.5,
+            0.5,
+            1.0,
+        );
+        let values = run_frames(&adsr, 100);
+
+        for (frame, expected) in [
+            (8, 0.0),   // before the trigger
+            (16, 0.0),  // attack starts
+            (24, 0.5),  // halfway up
+            (32, 1.0),  // peak
+            (40, 0.75), // halfway down to sustain
+            (48, 0.5),  // sustain level, release starts
+            (64, 0.25), // halfway released
+            (80, 0.0),  // done
+            (99, 0.0),
+        ] {
+            assert!(
+                (values[frame] - expected).abs() < 1e-4,
+                "frame {frame}: {} != {expected}",
+                values[frame]
+            );
+        }
+    }
+
+    #[test]
+    fn test_evaluate_adsr_retrigger_continues_from_current_level() {
+        use crate::event_stream::Event;
+
+        // Second trigger arrives at the peak of the first (frame 32).
+        let adsr = Signal::adsr_from_events(
+            std::sync::Arc::new(vec![Event::new(0.25, 1.0), Event::new(0.5, 1.0)]),
+            0.5,
+            0.5,
+            0.5,
+            1.0,
+        );
+        let values = run_frames(&adsr, 80);
+
+        // The attack restarts from 1.0 instead of dropping to 0, then decays again.
+        assert!((values[32] - 1.0).abs() < 1e-4);
+        assert!((values[40] - 1.0).abs() < 1e-4);
+        assert!((values[56] - 0.75).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_evaluate_event_weight_held_scales_strobe() {
+        use crate::event_stream::{Event, EventStream};
+
+        // A hard hit at 0.25s and a soft one at 0.75s (frames 16 and 48).
+        let events = EventStream::new(
+            vec![Event::new(0.25, 1.0), Event::new(0.75, 0.4)],
+            "hits".to_string(),
+            Default::default(),
+        );
+        let strobe = Signal::generator(GeneratorNode::Square {
+            freq_beats: 4.0,
+            phase: 0.0,
+            duty: 0.5,
+        })
+        .mul(events.held_weight());
+        let values = run_frames(&strobe, 80);
+        let peak = |frames: std::ops::Range<usize>| {
+            values[frames].iter().fold(f32::MIN, |acc, v| acc.max(*v))
+        };
+
+        assert_eq!(peak(0..16), 0.0);
+        assert!((peak(16..48) - 1.0).abs() < 1e-6);
+        assert!((peak(48..80) - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_evaluate_step_and_smoothstep() {
+        let eval = |signal: Signal| run_frames(&signal, 1)[0];
+        let x = |v: f32| Signal::constant(v);
+
+        assert_eq!(eval(x(0.2).step(0.5)), 0.0);
+        assert_eq!(eval(x(0.5).step(0.5)), 1.0);
+        assert_eq!(eval(x(0.8).step(0.5)), 1.0);
+
+        assert_eq!(eval(x(0.1).smoothstep(0.25, 0.75)), 0.0);
+        assert!((eval(x(0.5).smoothstep(0.25, 0.75)) - 0.5).abs() < 1e-6);
+        // t = 0.25: 3t^2 - 2t^3
+        assert!((eval(x(0.375).smoothstep(0.25, 0.75)) - 0.15625).abs() < 1e-6);
+        assert_eq!(eval(x(0.9).smoothstep(0.25, 0.75)), 1.0);
+
+        // Equal edges fall back to step.
+        assert_eq!(eval(x(0.4).smoothstep(0.5, 0.5)), 0.0);
+        assert_eq!(eval(x(0.5).smoothstep(0.5, 0.5)), 1.0);
+        assert_eq!(eval(x(0.6).smoothstep(0.5, 0.5)), 1.0);
+    }
+
+    #[test]
+    fn test_evaluate_window_stats_follow_a_step() {
+        // 0 for the first second (64 frames at 120 BPM), then 1.
+        let step = Signal::generator(GeneratorNode::Sequence {
+            values: vec![0.0, 1.0],
+            step_beats: 2.0,
+        });
+        let avg = run_frames(&step.window_avg(0.5), 128);
+        let max = run_frames(&step.window_max(0.5), 128);
+        let min = run_frames(&step.window_min(0.5), 128);
+
+        assert_eq!(avg[63], 0.0);
+        assert!(avg[80] > 0.0 && avg[80] < 1.0);
+        // Once the window has passed the step, the average is the new level.
+        assert!(avg[100..].iter().all(|v| (v - 1.0).abs() < 1e-6));
+
+        assert_eq!(max[64], 1.0);
+        assert_eq!(min[80], 0.0);
+        assert_eq!(min[100], 1.0);
+    }
+
+    #[test]
+    fn test_evaluate_normalise_window_tracks_ramping_amplitude() {
+        // A 2 Hz sine whose amplitude grows from 0.1 to 2.1 over 8 seconds.
+        let amplitude = Signal::generator(GeneratorNode::Saw {
+            freq_beats: 1.0 / 16.0,
+            phase: 0.0,
+        })
+        .add_scalar(1.1);
+        let wave = Signal::generator(GeneratorNode::Sin {
+            freq_beats: 1.0,
+            phase: 0.0,
+        })
+        .mul(amplitude);
+        let values = run_frames(
+            &wave.normalise(NormaliseParams::Window { seconds: 1.0 }),
+            512,
+        );
+
+        // After one window of warm-up the output stays in 0-1 and still
+        // spans the range each second, despite the 20x level change.
+        for second in values[64..].chunks(64) {
+            assert!(second.iter().all(|v| (0.0..=1.0).contains(v)));
+            let lo = second.iter().fold(f32::INFINITY, |a, v| a.min(*v));
+            let hi = second.iter().fold(f32::NEG_INFINITY, |a, v| a.max(*v));
+            assert!(lo < 0.1 && hi > 0.9, "range {}..{}", lo, hi);
+        }
+    }
+
+    #[test]
+    fn test_evaluate_ease_in_quad_squares_a_ramp() {
+        // One saw cycle every 2s at the default 120 BPM: -1..1 over 128 frames.
+        let saw = Signal::generator(GeneratorNode::Saw {
+            freq_beats: 0.25,
+            phase: 0.0,
+        });
+        let ramp = saw.add_scalar(1.0).scale(0.5);
+        let in_quad = crate::signal_rhai::easing_by_name("inQuad").unwrap();
+
+        let linear = run_frames(&ramp, 128);
+        let eased = run_frames(&ramp.ease(in_quad), 128);
+        for (x, y) in linear.iter().zip(&eased) {
+            assert!((y - x * x).abs() < 1e-5, "ease({}) = {}", x, y);
+        }
+
+        // Input below 0 is clamped before easing.
+        let raw = run_frames(&saw.ease(in_quad), 64);
+        assert!(raw.iter().all(|v| *v == 0.0));
+    }
+
+    #[test]
+    fn test_evaluate_generator_sequence_advances_each_beat() {
+        let inputs = HashMap::new();
+        let band_signals = HashMap::new();
+        let stem_signals = HashMap::new();
+        let custom_signals = HashMap::new();
+        let composed_signals = HashMap::new();
+        let stats = StatisticsCache::new();
+        let mut state = SignalState::new();
+
+        let sequence = Signal::generator(GeneratorNode::Sequence {
+            values: vec![1.0, 2.0, 3.0],
+            step_beats: 1.0,
+        });
+
+        // Default 120 BPM: one beat every 0.5 seconds, wrapping after three steps.
+        for (time, expected) in [(0.1, 1.0), (0.6, 2.0), (1.1, 3.0), (1.6, 1.0), (2.1, 2.0)] {
+            let mut ctx = make_test_context(
+                time,
+                0.016,
+                &inputs,
+                &band_signals,
+                &stem_signals,
+                &custom_signals,
+                &composed_signals,
+                &stats,
+                &mut state,
+            );
+            assert_eq!(sequence.evaluate(&mut ctx), expected, "at t={time}");
+        }
+    }
+
     #[test]
     fn test_evaluate_generator_sin() {
         let inputs = HashMap::new();
@@ -2128,4 +2539,45 @@ mod tests {
 
         assert!((selected.evaluate(&mut ctx) - 99.0).abs() < 0.001);
     }
+
+    #[test]
+    fn test_evaluate_select_if_then_else_follows_condition() {
+        // Condition is low for the first half second, high for the second.
+        let mut gate = vec![0.0; 50];
+        gate.extend(vec![1.0; 50]);
+        let mut inputs = HashMap::new();
+        inputs.insert(
+            "gate".to_string(),
+            std::rc::Rc::new(InputSignal::new(gate, 100.0)),
+        );
+
+        let band_signals = HashMap::new();
+        let stem_signals = HashMap::new();
+        let custom_signals = HashMap::new();
+        let composed_signals = HashMap::new();
+        let stats = StatisticsCache::new();
+        let mut state = SignalState::new();
+
+        let selected = Signal::input("gate")
+            .interpolate()
+            .select(Signal::constant(10.0), Signal::constant(20.0));
+
+        for (time, expected) in [(0.2, 20.0), (0.7, 10.0), (0.3, 20.0)] {
+            let mut ctx = make_test_context(
+                time,
+                0.016,
+                &inputs,
+                &band_signals,
+                &stem_signals,
+                &custom_signals,
+                &composed_signals,
+                &stats,
+                &mut state,
+            );
+            assert!(
+                (selected.evaluate(&mut ctx) - expected).abs() < 0.001,
+                "at t={time}"
+            );
+        }
+    }
 }
@@ -34,11 +34,11 @@ pub enum TransformType {
     ExpLog,
     /// Modular/Periodic: Mod, Rem, Wrap, Fract
     Modular,
-    /// Mapping/Shaping: Map, Smoothstep, Lerp
+    /// Mapping/Shaping: Map, Step, Smoothstep, Lerp, Ease
     Mapping,
     /// Time shifting: Delay, Anticipate
     TimeShift,
-    /// Rate/Accumulation: Diff, Integrate
+    /// Rate/Accumulation: Diff, Integrate, Window
     RateChange,
     /// Debug probe
     Debug,
@@ -150,11 +150,13 @@ pub fn node_transform_type(node: &SignalNode) -> TransformType {
         | SignalNode::Generator(_)
         | SignalNode::EventStreamSource { .. }
         | SignalNode::EventStreamEnvelope { .. }
+        | SignalNode::Adsr { .. }
         | SignalNode::EventDistanceFromPrev { .. }
         | SignalNode::EventDistanceToNext { .. }
         | SignalNode::EventCountInWindow { .. }
         | SignalNode::EventDensityInWindow { .. }
-        | SignalNode::EventPhaseBetween { .. } => TransformType::Source,
+        | SignalNode::EventPhaseBetween { .. }
+        | SignalNode::EventWeightHeld { .. } => TransformType::Source,
 
         // Transformations
         SignalNode::Smooth { .. } => TransformType::Smooth,
@@ -203,15 +205,19 @@ pub fn node_transform_type(node: &SignalNode) -> TransformType {
         | SignalNode::Fract { .. } => TransformType::Modular,
 
         // Mapping/Shaping
-        SignalNode::Map { .. } | SignalNode::Smoothstep { .. } | SignalNode::Lerp { .. } => {
-            TransformType::Mapping
-        }
+        SignalNode::Map { .. }
+        | SignalNode::Step { .. }
+        | SignalNode::Smoothstep { .. }
+        | SignalNode::Lerp { .. }
+        | SignalNode::Ease { .. } => TransformType::Mapping,
 
         // Time shifting
         SignalNode::Delay { .. } | SignalNode::Anticipate { .. } => TransformType::TimeShift,
 
         // Rate/Accumulation
-        SignalNode::Diff { .. } | SignalNode::Integrate { .. } => TransformType::RateChange,
+        SignalNode::Diff { .. } | SignalNode::Integrate { .. } | SignalNode::Window { .. } => {
+            TransformType::RateChange
+        }
 
         // Debug
         SignalNode::Debug { .. } => TransformType::Debug,
@@ -257,11 +263,13 @@ fn get_primary_source(node: &SignalNode) -> Option<&Signal> {
         | SignalNode::Generator(_)
         | SignalNode::EventStreamSource { .. }
         | SignalNode::EventStreamEnvelope { .. }
+        | SignalNode::Adsr { .. }
         | SignalNode::EventDistanceFromPrev { .. }
         | SignalNode::EventDistanceToNext { .. }
         | SignalNode::EventCountInWindow { .. }
         | SignalNode::EventDensityInWindow { .. }
-        | SignalNode::EventPhaseBetween { .. } => None,
+        | SignalNode::EventPhaseBetween { .. }
+        | SignalNode::EventWeightHeld { .. } => None,
 
         // Unary transforms - single source
         SignalNode::Smooth { source, .. }
@@ -294,9 +302,12 @@ fn get_primary_source(node: &SignalNode) -> Option<&Signal> {
         | SignalNode::Wrap { source, .. }
         | SignalNode::Fract { source }
         | SignalNode::Map { source, .. }
+        | SignalNode::Step { source, .. }
         | SignalNode::Smoothstep { source, .. }
+        | SignalNode::Ease { source, .. }
         | SignalNode::Diff { source }
         | SignalNode::Integrate { source, .. }
+        | SignalNode::Window { source, .. }
         | SignalNode::Delay { source, .. }
         | SignalNode::Anticipate { source, .. }
         | SignalNode::Not { source } => Some(source),
@@ -530,6 +541,53 @@ pub fn sample_signal_chain(
     }
 }
 
+/// Most values `sample_signal_range` returns in one call.
+pub const MAX_RANGE_SAMPLES: usize = 10_000;
+
+/// Densely sample a signal over `[start, end]` every `step` seconds.
+///
+/// Unlike `sample_signal_chain` this returns only the values of the final signal and
+/// evaluates with a fresh `SignalState`, so stateful nodes (smoothing, integration,
+/// envelopes) behave as if playback started at `start`. `end` is included when it
+/// lands on a step. Returns an empty buffer for a non-finite argument, a non-positive
+/// step or an empty range, and at most `MAX_RANGE_SAMPLES` values from `start` on.
+pub fn sample_signal_range(
+    signal: &Signal,
+    start: f32,
+    end: f32,
+    step: f32,
+    input_signals: &SignalMap,
+    band_signals: &BandSignalMap,
+    stem_signals: &BandSignalMap,
+    statistics: &StatisticsCache,
+    musical_time: Option<&MusicalTimeStructure>,
+) -> Vec<f32> {
+    if !(start.is_finite() && end.is_finite() && step.is_finite()) || step <= 0.0 || end < start {
+        return Vec::new();
+    }
+
+    // Tolerate float error so e.g. [0, 1] at 0.1 includes the end point.
+    let count = (((end - start) / step + 1e-4).floor() as usize)
+        .saturating_add(1)
+        .min(MAX_RANGE_SAMPLES);
+    let times: Vec<f32> = (0..count).map(|i| start + i as f32 * step).collect();
+
+    let mut state = SignalState::new();
+    sample_signal(
+        signal,
+        &times,
+        step,
+        input_signals,
+        band_signals,
+        stem_signals,
+        statistics,
+        &mut state,
+        musical_time,
+        0,
+    )
+    .values
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -562,6 +620,69 @@ mod tests {
         assert_eq!(chain[2].transform_type, TransformType::Normalise); // Normalise
     }
 
+    #[test]
+    fn test_sample_signal_range_sine() {
+        use crate::signal::GeneratorNode;
+
+        // One cycle per beat at the default 120 BPM: period 0.5 s.
+        let sine = Signal::generator(GeneratorNode::Sin {
+            freq_beats: 1.0,
+            phase: 0.0,
+        })
+        .scale(2.0);
+        let empty: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        let stats = StatisticsCache::new();
+
+        let values =
+            sample_signal_range(&sine, 0.0, 1.0, 0.125, &empty, &bands, &bands, &stats, None);
+
+        assert_eq!(values.len(), 9);
+        // t = 0.125 s is a quarter cycle: the peak.
+        assert!((values[1] - 2.0).abs() < 1e-4);
+        assert!((values[3] + 2.0).abs() < 1e-4);
+
+        assert!(
+            sample_signal_range(&sine, 0.0, 1.0, 0.0, &empty, &bands, &bands, &stats, None)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_sample_signal_range_rejects_non_finite_arguments() {
+        let signal = Signal::constant(1.0);
+        let empty: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        let stats = StatisticsCache::new();
+
+        for (start, end, step) in [
+            (f32::NAN, 1.0, 0.1),
+            (0.0, f32::INFINITY, 0.1),
+            (f32::NEG_INFINITY, 1.0, 0.1),
+            (0.0, 1.0, f32::INFINITY),
+            (0.0, 1.0, f32::NAN),
+        ] {
+            let values = sample_signal_range(
+                &signal, start, end, step, &empty, &bands, &bands, &stats, None,
+            );
+            assert!(values.is_empty(), "{start}..{end} step {step}");
+        }
+    }
+
+    #[test]
+    fn test_sample_signal_range_caps_the_sample_count() {
+        let signal = Signal::constant(1.0);
+        let empty: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        let stats = StatisticsCache::new();
+
+        let values = sample_signal_range(
+            &signal, 0.0, 1.0e9, 1.0e-9, &empty, &bands, &bands, &stats, None,
+        );
+        assert_eq!(values.len(), MAX_RANGE_SAMPLES);
+        assert!(values.iter().all(|&v| v == 1.0));
+    }
+
     #[test]
     fn test_transform_type_classification() {
         let input = Signal::input("test");
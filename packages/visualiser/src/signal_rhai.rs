@@ -4,15 +4,20 @@
 //! enabling scripts to use the Signal API with method chaining.
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 
 use rhai::{Dynamic, Engine, EvalAltResult, ImmutableString};
 
 use crate::event_rhai::{register_event_api, PickBuilder};
 use crate::input::{BandSignalMap, SignalMap};
+use crate::musical_time::MusicalTimeStructure;
 use crate::signal::{
-    GateBuilder, GeneratorNode, NoiseType, NormaliseBuilder, SelectBuilder, Signal, SignalNode,
-    SignalParam, SmoothBuilder,
+    EasingFunction, GateBuilder, GeneratorNode, NoiseType, NormaliseBuilder, SelectBuilder, Signal,
+    SignalId, SignalNode, SignalParam, SmoothBuilder,
 };
+use crate::signal_eval::EvalContext;
+use crate::signal_state::SignalState;
+use crate::signal_stats::StatisticsCache;
 
 // Thread-local storage for input signals during script execution.
 // Used by sample_at to access raw signal data at specific times.
@@ -51,6 +56,89 @@ pub fn set_current_composed_signals(composed_signals: SignalMap) {
     });
 }
 
+/// Everything `signal.value()` needs to evaluate a Signal during `update()`,
+/// beyond the input signal thread-locals.
+///
+/// The engine moves its signal state in for the duration of the update call and
+/// takes it back (with the values evaluated so far) before syncing, so stateful
+/// signals advance once per frame however they are read.
+#[derive(Default)]
+pub struct SignalProbe {
+    pub time: f32,
+    pub dt: f32,
+    pub frame_count: u64,
+    pub musical_time: Option<MusicalTimeStructure>,
+    pub stem_signals: BandSignalMap,
+    pub state: SignalState,
+    pub statistics: StatisticsCache,
+    /// Values evaluated this frame, keyed by signal.
+    pub cache: HashMap<SignalId, f32>,
+}
+
+thread_local! {
+    static SIGNAL_PROBE: RefCell<Option<SignalProbe>> = const { RefCell::new(None) };
+}
+
+/// Make `signal.value()` available for the current frame (call before `update()`).
+pub fn set_signal_probe(probe: SignalProbe) {
+    SIGNAL_PROBE.with(|cell| {
+        *cell.borrow_mut() = Some(probe);
+    });
+}
+
+/// Take back the probe set by `set_signal_probe` (call after `update()`).
+pub fn take_signal_probe() -> Option<SignalProbe> {
+    SIGNAL_PROBE.with(|cell| cell.borrow_mut().take())
+}
+
+/// Evaluate a signal at the current frame, for `signal.value()`.
+fn probe_signal_value(signal: &Signal) -> Result<f32, Box<EvalAltResult>> {
+    SIGNAL_PROBE.with(|cell| {
+        let mut probe = cell.borrow_mut();
+        let Some(probe) = probe.as_mut() else {
+            return Err("signal.value() is only available inside update()".into());
+        };
+        if let Some(v) = probe.cache.get(&signal.id) {
+            return Ok(*v);
+        }
+
+        let current = |cell: &RefCell<Option<SignalMap>>| cell.borrow().clone().unwrap_or_default();
+        let inputs = CURRENT_INPUT_SIGNALS.with(current);
+        let bands = CURRENT_BAND_SIGNALS.with(|cell| cell.borrow().clone().unwrap_or_default());
+        let custom_signals = CURRENT_CUSTOM_SIGNALS.with(current);
+        let composed_signals = CURRENT_COMPOSED_SIGNALS.with(current);
+
+        let mut ctx = EvalContext::new(
+            probe.time,
+            probe.dt,
+            probe.frame_count,
+            probe.musical_time.as_ref(),
+            &inputs,
+            &bands,
+            &probe.stem_signals,
+            &custom_signals,
+            &composed_signals,
+            &probe.statistics,
+            &mut probe.state,
+            None,
+        );
+        let value = signal.evaluate(&mut ctx);
+        probe.cache.insert(signal.id, value);
+        Ok(value)
+    })
+}
+
+/// Frame delta of the update in progress, from the same clock that drives
+/// signal evaluation. Outside `update()` there is no frame, so this is 0.
+fn current_frame_dt() -> f32 {
+    SIGNAL_PROBE
+        .with(|cell| cell.borrow().as_ref().map(|probe| probe.dt))
+        .unwrap_or_else(|| {
+            log::warn!("sample_at: time.dt is only available inside update()");
+            0.0
+        })
+}
+
 /// Clear the input signals for the current thread (call after script execution).
 pub fn clear_current_input_signals() {
     CURRENT_INPUT_SIGNALS.with(|cell| {
@@ -67,6 +155,20 @@ pub fn clear_current_input_signals() {
     });
 }
 
+/// Look up an easing by script name (e.g. "outCubic"), listing the valid
+/// names on failure.
+pub fn easing_by_name(name: &str) -> Result<EasingFunction, Box<EvalAltResult>> {
+    EasingFunction::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = EasingFunction::NAMED.iter().map(|(n, _)| *n).collect();
+        format!(
+            "Unknown easing '{}'. Expected one of: {}",
+            name,
+            names.join(", ")
+        )
+        .into()
+    })
+}
+
 /// Convert a Rhai Dynamic value to SignalParam.
 ///
 /// Accepts:
@@ -274,6 +376,12 @@ pub fn register_signal_api(engine: &mut Engine) {
         },
     );
     engine.register_fn("fract", |s: &mut Signal| s.fract());
+    engine.register_fn(
+        "ease",
+        |s: &mut Signal, name: &str| -> Result<Signal, Box<EvalAltResult>> {
+            Ok(s.ease(easing_by_name(name)?))
+        },
+    );
 
     // === Mapping / shaping ===
     engine.register_fn(
@@ -292,6 +400,12 @@ pub fn register_signal_api(engine: &mut Engine) {
             ))
         },
     );
+    engine.register_fn(
+        "step",
+        |s: &mut Signal, edge: Dynamic| -> Result<Signal, Box<EvalAltResult>> {
+            Ok(s.step(to_signal_param(edge)?))
+        },
+    );
     engine.register_fn(
         "smoothstep",
         |s: &mut Signal, edge0: Dynamic, edge1: Dynamic| -> Result<Signal, Box<EvalAltResult>> {
@@ -307,6 +421,24 @@ pub fn register_signal_api(engine: &mut Engine) {
 
     // === Rate and accumulation ===
     engine.register_fn("diff", |s: &mut Signal| s.diff());
+    engine.register_fn(
+        "windowMax",
+        |s: &mut Signal, seconds: Dynamic| -> Result<Signal, Box<EvalAltResult>> {
+            Ok(s.window_max(to_signal_param(seconds)?))
+        },
+    );
+    engine.register_fn(
+        "windowMin",
+        |s: &mut Signal, seconds: Dynamic| -> Result<Signal, Box<EvalAltResult>> {
+            Ok(s.window_min(to_signal_param(seconds)?))
+        },
+    );
+    engine.register_fn(
+        "windowAvg",
+        |s: &mut Signal, seconds: Dynamic| -> Result<Signal, Box<EvalAltResult>> {
+            Ok(s.window_avg(to_signal_param(seconds)?))
+        },
+    );
     engine.register_fn(
         "integrate",
         |s: &mut Signal, decay_beats: Dynamic| -> Result<Signal, Box<EvalAltResult>> {
@@ -384,6 +516,9 @@ pub fn register_signal_api(engine: &mut Engine) {
     engine.register_fn("and", |s: &mut Signal, other: Signal| s.and(other));
     engine.register_fn("or", |s: &mut Signal, other: Signal| s.or(other));
     engine.register_fn("not", |s: &mut Signal| s.not());
+    engine.register_fn("select", |s: &mut Signal, a: Signal, b: Signal| {
+        s.select(a, b)
+    });
 
     // === SelectBuilder ===
     engine.register_type_with_name::<SelectBuilder>("SelectBuilder");
@@ -406,9 +541,10 @@ pub fn register_signal_api(engine: &mut Engine) {
                 // Handle special time signals
                 match name.as_str() {
                     "time" | "time.seconds" => time,
-                    "time.dt" | "dt" => 0.016, // Default dt assumption
-                    "time.frames" => 0.0,      // Cannot determine frame count from time alone
-                    "time.beats" | "time.phase" | "time.bpm" => {
+                    "time.dt" | "dt" => current_frame_dt(),
+                    "time.frames" => 0.0, // Cannot determine frame count from time alone
+                    "time.beats" | "time.bars" | "time.barIndex" | "time.barPhase"
+                    | "time.phase" | "time.bpm" => {
                         log::warn!(
                             "sample_at: Cannot sample time.{} without musical context",
                             name
@@ -462,6 +598,10 @@ pub fn register_signal_api(engine: &mut Engine) {
         sample_at_impl(s, time as f32)
     });
 
+    // === Probing ===
+    // Current-frame value as a number, for branching inside update()
+    engine.register_fn("value", |s: &mut Signal| probe_signal_value(s));
+
     // === Sampling configuration ===
     // Change from default peak-preserving to linear interpolation
     engine.register_fn("interpolate", |s: &mut Signal| s.interpolate());
@@ -536,6 +676,13 @@ pub fn register_signal_api(engine: &mut Engine) {
     // === NormaliseBuilder methods ===
     engine.register_fn("global", |b: &mut NormaliseBuilder| b.clone().global());
     engine.register_fn("robust", |b: &mut NormaliseBuilder| b.clone().robust());
+    // signal.normalizeWindow(seconds): live AGC, no precomputed statistics
+    engine.register_fn("normalizeWindow", |s: &mut Signal, seconds: f32| {
+        NormaliseBuilder::new(s.clone()).window(seconds)
+    });
+    engine.register_fn("normalizeWindow", |s: &mut Signal, seconds: i64| {
+        NormaliseBuilder::new(s.clone()).window(seconds as f32)
+    });
     engine.register_fn(
         "to_range",
         |b: &mut NormaliseBuilder, min: f32, max: f32| b.clone().to_range(min, max),
@@ -753,6 +900,48 @@ pub fn register_signal_api(engine: &mut Engine) {
         })
     });
 
+    // gen.sequence([v0, v1, ...], #{ step_beats }) - options map is optional
+    engine.register_fn("__gen_sequence", |values: rhai::Array, options: Dynamic| {
+        let as_f32 = |v: &Dynamic| {
+            v.as_float()
+                .ok()
+                .or_else(|| v.as_int().ok().map(|i| i as f32))
+        };
+        let step_beats = options
+            .try_cast::<rhai::Map>()
+            .and_then(|opts| opts.get("step_beats").and_then(as_f32))
+            .filter(|beats| *beats > 0.0)
+            .unwrap_or(1.0);
+        Signal::generator(GeneratorNode::Sequence {
+            values: values.iter().map(|v| as_f32(v).unwrap_or(0.0)).collect(),
+            step_beats,
+        })
+    });
+
+    // gen.adsr(events, #{ attack, decay, sustain, release }) - times in beats, sustain is a level
+    engine.register_fn(
+        "__gen_adsr",
+        |events: crate::event_stream::EventStream, options: Dynamic| {
+            let opts = options.try_cast::<rhai::Map>().unwrap_or_default();
+            let get = |key: &str, default: f32| {
+                opts.get(key)
+                    .and_then(|v| {
+                        v.as_float()
+                            .ok()
+                            .or_else(|| v.as_int().ok().map(|i| i as f32))
+                    })
+                    .unwrap_or(default)
+            };
+            Signal::adsr_from_events(
+                events.events.clone(),
+                get("attack", 0.1),
+                get("decay", 0.5),
+                get("sustain", 0.7),
+                get("release", 0.3),
+            )
+        },
+    );
+
     // === Input signal accessor ===
     engine.register_fn("__signal_input", |name: ImmutableString| {
         Signal::input(name.as_str())
@@ -774,6 +963,14 @@ pub fn register_signal_api(engine: &mut Engine) {
         },
     );
 
+    // === Stem-scoped band input signal accessor ===
+    engine.register_fn(
+        "__stem_band_signal_input",
+        |stem_id: ImmutableString, band_key: ImmutableString, feature: ImmutableString| {
+            Signal::stem_band_input(stem_id.as_str(), band_key.as_str(), feature.as_str())
+        },
+    );
+
     // === Band events accessor ===
     // Returns pre-extracted EventStream for a band, or empty if not available.
     engine.register_fn("__band_events_get", |band_id: ImmutableString| {
@@ -857,6 +1054,8 @@ gen.triangle = |freq, phase| __gen_triangle(freq, phase);
 gen.saw = |freq, phase| __gen_saw(freq, phase);
 gen.noise = |noise_type, seed| __gen_noise(noise_type, seed);
 gen.perlin = |scale, seed| __gen_perlin(scale, seed);
+gen.sequence = |values, options| __gen_sequence(values, options);
+gen.adsr = |events, options| __gen_adsr(events, options);
 gen.constant = |value| __signal_constant(value);
 
 // === Timing Namespace (global) ===
@@ -869,6 +1068,9 @@ timing.dt = __signal_input("time.dt");
 timing.beatPosition = __signal_input("time.beats");
 timing.beatIndex = __signal_input("time.beatIndex");
 timing.beatPhase = __signal_input("time.phase");
+timing.barPosition = __signal_input("time.bars");
+timing.barIndex = __signal_input("time.barIndex");
+timing.barPhase = __signal_input("time.barPhase");
 timing.bpm = __signal_input("time.bpm");
 
 // === Legacy time namespace (for backwards compatibility during transition) ===
@@ -878,6 +1080,8 @@ time.__type = "time_namespace";
 time.seconds = timing.time;
 time.frames = __signal_input("time.frames");
 time.beats = timing.beatPosition;
+time.beat = timing.beatPosition;
+time.bar = timing.barPosition;
 time.phase = timing.beatPhase;
 time.bpm = timing.bpm;
 time.dt = timing.dt;
@@ -996,6 +1200,8 @@ inputs.mix.bands["{id}"].onsetPeaks = __band_events_get("{id}:onsetPeaks");
 /// inputs.stems = #{};
 /// inputs.stems["stem-abc123"] = #{};
 /// inputs.stems["stem-abc123"].energy = __stem_signal_input("stem-abc123", "energy");
+/// inputs.stems["stem-abc123"].amplitude = __stem_signal_input("stem-abc123", "amplitude");
+/// inputs.stems["stem-abc123"].loudness = __stem_signal_input("stem-abc123", "loudness");
 /// inputs.stems["stem-abc123"].flux = __stem_signal_input("stem-abc123", "flux");
 /// inputs.stems["stem-abc123"].centroid = __stem_signal_input("stem-abc123", "centroid");
 /// inputs.stems["stem-abc123"].onset = __stem_signal_input("stem-abc123", "onset");
@@ -1005,11 +1211,18 @@ inputs.mix.bands["{id}"].onsetPeaks = __band_events_get("{id}:onsetPeaks");
 /// inputs.stems["stem-abc123"].onsetPeaks = __event_stream_get("stem:stem-abc123:onsetPeaks");
 /// inputs.stems["stem-abc123"].label = "Drums";
 /// inputs.stems["stem-abc123"].bands = #{};
+/// inputs.stems["stem-abc123"].bands["band-1"] = #{};
+/// inputs.stems["stem-abc123"].bands["band-1"].energy = __stem_band_signal_input("stem-abc123", "band-1", "energy");
+/// inputs.stems["stem-abc123"].bands["Hi"] = inputs.stems["stem-abc123"].bands["band-1"];
 /// inputs.stems["Drums"] = inputs.stems["stem-abc123"];
 /// ```
 ///
+/// `bands` are the (id, label) pairs of the available frequency bands; each
+/// stem gets the same band accessors as `inputs.mix.bands`, resolved against
+/// that stem's per-band analysis.
+///
 /// Note: Uses consistent naming (centroid, flux, onset, energy, pitch, pitchConfidence) - no aliases.
-pub fn generate_stems_namespace(stems: &[(String, String)]) -> String {
+pub fn generate_stems_namespace(stems: &[(String, String)], bands: &[(String, String)]) -> String {
     let mut code =
         String::from("inputs.stems = #{};\ninputs.stems.__type = \"stems_namespace\";\n");
 
@@ -1020,6 +1233,8 @@ pub fn generate_stems_namespace(stems: &[(String, String)]) -> String {
             r#"inputs.stems["{id}"] = #{{}};
 inputs.stems["{id}"].__type = "stem_signals";
 inputs.stems["{id}"].energy = __stem_signal_input("{id}", "energy");
+inputs.stems["{id}"].amplitude = __stem_signal_input("{id}", "amplitude");
+inputs.stems["{id}"].loudness = __stem_signal_input("{id}", "loudness");
 inputs.stems["{id}"].flux = __stem_signal_input("{id}", "flux");
 inputs.stems["{id}"].centroid = __stem_signal_input("{id}", "centroid");
 inputs.stems["{id}"].onset = __stem_signal_input("{id}", "onset");
@@ -1035,6 +1250,30 @@ inputs.stems["{id}"].bands.__type = "bands_namespace";
             label = label
         ));
 
+        for (band_id, band_label) in bands {
+            code.push_str(&format!(
+                r#"inputs.stems["{id}"].bands["{band_id}"] = #{{}};
+inputs.stems["{id}"].bands["{band_id}"].__type = "band_signals";
+inputs.stems["{id}"].bands["{band_id}"].energy = __stem_band_signal_input("{id}", "{band_id}", "energy");
+inputs.stems["{id}"].bands["{band_id}"].onset = __stem_band_signal_input("{id}", "{band_id}", "onset");
+inputs.stems["{id}"].bands["{band_id}"].flux = __stem_band_signal_input("{id}", "{band_id}", "flux");
+inputs.stems["{id}"].bands["{band_id}"].centroid = __stem_band_signal_input("{id}", "{band_id}", "centroid");
+"#,
+                id = id,
+                band_id = band_id
+            ));
+
+            if band_label != band_id {
+                code.push_str(&format!(
+                    r#"inputs.stems["{id}"].bands["{band_label}"] = inputs.stems["{id}"].bands["{band_id}"];
+"#,
+                    id = id,
+                    band_label = band_label,
+                    band_id = band_id
+                ));
+            }
+        }
+
         // Also register by label if different from ID
         if label != id {
             code.push_str(&format!(
@@ -1168,6 +1407,49 @@ pub fn generate_composed_signals_namespace(signals: &[(String, String)]) -> Stri
     code
 }
 
+/// Generate Rhai code for the inputs.midi namespace.
+///
+/// `notes` are the played (channel, note) pairs, sorted. Each channel gets its
+/// note-on event stream, and each note its velocity signal (a composed signal)
+/// and note-on event stream, all named `midi:ch<channel>[:<note>]`.
+///
+/// This generates code like:
+/// ```rhai
+/// inputs.midi = #{};
+/// inputs.midi.__type = "midi_namespace";
+/// inputs.midi.ch1 = #{};
+/// inputs.midi.ch1.__type = "midi_channel";
+/// inputs.midi.ch1.events = __authored_events_get("midi:ch1");
+/// inputs.midi.ch1.note36 = __composed_signal_input("midi:ch1:36");
+/// inputs.midi.ch1.note36Events = __authored_events_get("midi:ch1:36");
+/// ```
+pub fn generate_midi_namespace(notes: &[(u8, u8)]) -> String {
+    let mut code = String::from("inputs.midi = #{};\ninputs.midi.__type = \"midi_namespace\";\n");
+
+    let mut current_channel = None;
+    for &(channel, key) in notes {
+        if current_channel != Some(channel) {
+            current_channel = Some(channel);
+            code.push_str(&format!(
+                r#"inputs.midi.ch{channel} = #{{}};
+inputs.midi.ch{channel}.__type = "midi_channel";
+inputs.midi.ch{channel}.events = __authored_events_get("midi:ch{channel}");
+"#,
+                channel = channel
+            ));
+        }
+        code.push_str(&format!(
+            r#"inputs.midi.ch{channel}.note{key} = __composed_signal_input("midi:ch{channel}:{key}");
+inputs.midi.ch{channel}.note{key}Events = __authored_events_get("midi:ch{channel}:{key}");
+"#,
+            channel = channel,
+            key = key
+        ));
+    }
+
+    code
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1195,6 +1477,37 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_scalar_on_left_of_signal_operators() {
+        let mut engine = Engine::new();
+        register_signal_api(&mut engine);
+        let eval = |expr: &str| {
+            engine
+                .eval::<Signal>(&format!("let s = __signal_constant(0.5); {}", expr))
+                .unwrap()
+        };
+        let constant = |signal: &Signal| match &*signal.node {
+            SignalNode::Constant(v) => *v,
+            _ => panic!("expected a constant, got {}", signal.describe()),
+        };
+
+        // Non-commutative operators keep the scalar as the left operand.
+        match &*eval("2.0 - s").node {
+            SignalNode::Sub(a, b) => assert_eq!((constant(a), constant(b)), (2.0, 0.5)),
+            _ => panic!("expected Sub"),
+        }
+        match &*eval("2.0 / s").node {
+            SignalNode::Div(a, b) => assert_eq!((constant(a), constant(b)), (2.0, 0.5)),
+            _ => panic!("expected Div"),
+        }
+        match &*eval("3 / s").node {
+            SignalNode::Div(a, b) => assert_eq!((constant(a), constant(b)), (3.0, 0.5)),
+            _ => panic!("expected Div"),
+        }
+        assert!(matches!(&*eval("2.0 + s").node, SignalNode::Add(..)));
+        assert!(matches!(&*eval("2 * s").node, SignalNode::Mul(..)));
+    }
+
     #[test]
     fn test_generate_bands_namespace() {
         let bands = vec![
@@ -1317,4 +1630,148 @@ mod tests {
             r#"inputs.customEvents["Snare Hits"] = __authored_events_get("Snare Hits");"#
         ));
     }
+
+    #[test]
+    fn test_time_beat_bar_bpm_follow_musical_time() {
+        use crate::musical_time::{
+            MusicalTimeProvenance, MusicalTimeSegment, MusicalTimeStructure,
+        };
+        use crate::signal_eval::EvalContext;
+        use crate::signal_state::SignalState;
+        use crate::signal_stats::StatisticsCache;
+        use std::collections::HashMap;
+
+        let mut engine = Engine::new();
+        register_signal_api(&mut engine);
+        let mut scope = rhai::Scope::new();
+        engine.run_with_scope(&mut scope, SIGNAL_API_RHAI).unwrap();
+        let beat: Signal = engine.eval_with_scope(&mut scope, "time.beat").unwrap();
+        let bar: Signal = engine.eval_with_scope(&mut scope, "time.bar").unwrap();
+        let bpm: Signal = engine.eval_with_scope(&mut scope, "time.bpm").unwrap();
+
+        // 90 BPM with the first beat at 1.0s: one beat every 2/3 second
+        let structure = MusicalTimeStructure {
+            version: 1,
+            segments: vec![MusicalTimeSegment {
+                id: "seg".to_string(),
+                bpm: 90.0,
+                phase_offset: 1.0,
+                start_time: 0.0,
+                end_time: 100.0,
+                confidence: None,
+                beats_per_bar: None,
+                provenance: MusicalTimeProvenance {
+                    source: "test".to_string(),
+                    source_hypothesis_id: None,
+                    promoted_at: "2024-01-01T00:00:00Z".to_string(),
+                    user_nudge: None,
+                },
+            }],
+            created_at: String::new(),
+            modified_at: String::new(),
+        };
+
+        let empty = HashMap::new();
+        let empty_bands = HashMap::new();
+        let stats = StatisticsCache::new();
+        let sample = |time: f32, musical_time: Option<&MusicalTimeStructure>| {
+            let mut state = SignalState::new();
+            let mut ctx = EvalContext::new(
+                time,
+                0.016,
+                0,
+                musical_time,
+                &empty,
+                &empty_bands,
+                &empty_bands,
+                &empty,
+                &empty,
+                &stats,
+                &mut state,
+                None,
+            );
+            (
+                beat.evaluate(&mut ctx),
+                bar.evaluate(&mut ctx),
+                bpm.evaluate(&mut ctx),
+            )
+        };
+
+        for (time, expected_beat) in [(1.0, 0.0), (1.0 + 2.0 / 3.0, 1.0), (1.0 + 8.0 / 3.0, 4.0)] {
+            let (b, bar_pos, tempo) = sample(time, Some(&structure));
+            assert!((b - expected_beat).abs() < 1e-3, "beat at {}: {}", time, b);
+            assert!((bar_pos - expected_beat / 4.0).abs() < 1e-3);
+            assert_eq!(tempo, 90.0);
+        }
+
+        // Without musical time, fall back to 120 BPM from zero
+        let (b, bar_pos, tempo) = sample(1.0, None);
+        assert!((b - 2.0).abs() < 1e-3);
+        assert!((bar_pos - 0.5).abs() < 1e-3);
+        assert_eq!(tempo, 120.0);
+    }
+
+    #[test]
+    fn test_bar_phase_resets_on_downbeats_in_three_four() {
+        use crate::musical_time::MusicalTimeStructure;
+        use crate::signal_eval::EvalContext;
+        use crate::signal_state::SignalState;
+        use crate::signal_stats::StatisticsCache;
+        use std::collections::HashMap;
+
+        let mut engine = Engine::new();
+        register_signal_api(&mut engine);
+        let mut scope = rhai::Scope::new();
+        engine.run_with_scope(&mut scope, SIGNAL_API_RHAI).unwrap();
+        let bar_index: Signal = engine
+            .eval_with_scope(&mut scope, "timing.barIndex")
+            .unwrap();
+        let bar_phase: Signal = engine
+            .eval_with_scope(&mut scope, "timing.barPhase")
+            .unwrap();
+
+        // 3/4 at 120 BPM, first downbeat at 0.7s: bars start at 0.7, 2.2, 3.7.
+        let structure = MusicalTimeStructure::constant(120.0, 0.7, Some(3.0), 100.0, "test");
+
+        let empty = HashMap::new();
+        let empty_bands = HashMap::new();
+        let stats = StatisticsCache::new();
+        let sample = |time: f32| {
+            let mut state = SignalState::new();
+            let mut ctx = EvalContext::new(
+                time,
+                0.016,
+                0,
+                Some(&structure),
+                &empty,
+                &empty_bands,
+                &empty_bands,
+                &empty,
+                &empty,
+                &stats,
+                &mut state,
+                None,
+            );
+            (bar_index.evaluate(&mut ctx), bar_phase.evaluate(&mut ctx))
+        };
+
+        for (time, expected_index, expected_phase) in [
+            (0.7, 0.0, 0.0),
+            (1.45, 0.0, 0.5),
+            (2.19, 0.0, 0.993),
+            (2.2, 1.0, 0.0),
+            (3.7, 2.0, 0.0),
+            // Before the first downbeat: the tail of bar -1, phase in 0-1.
+            (0.1, -1.0, 0.6),
+        ] {
+            let (index, phase) = sample(time);
+            assert_eq!(index, expected_index, "bar index at {}", time);
+            assert!(
+                (phase - expected_phase).abs() < 2e-3,
+                "bar phase at {}: {}",
+                time,
+                phase
+            );
+        }
+    }
 }
@@ -3,10 +3,21 @@
 //! Some signal operations (smoothing, hysteresis gates) require state
 //! that persists across frames. This module provides the state containers.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 
 use crate::signal::SignalId;
 
+/// The trigger an ADSR envelope is currently playing out.
+#[derive(Debug, Clone, Copy)]
+pub struct AdsrState {
+    /// Time (seconds) of the event that triggered the envelope.
+    pub trigger_time: f32,
+    /// Peak level reached at the end of the attack (the event weight).
+    pub peak: f32,
+    /// Level the attack ramps up from (non-zero when retriggered mid-envelope).
+    pub start_level: f32,
+}
+
 /// Runtime state for all stateful signal operations.
 #[derive(Default)]
 pub struct SignalState {
@@ -31,6 +42,12 @@ pub struct SignalState {
     /// Ring buffers for delay operation.
     pub delay_buffers: HashMap<SignalId, DelayBuffer>,
 
+    /// State for event-triggered ADSR envelopes (current trigger, if any).
+    pub adsr_state: HashMap<SignalId, AdsrState>,
+
+    /// Recent (time, value) samples for sliding-window statistics.
+    pub window_samples: HashMap<SignalId, VecDeque<(f32, f32)>>,
+
     /// Whether a "no musical time" warning has been logged.
     pub warned_no_musical_time: bool,
 
@@ -67,6 +84,8 @@ impl SignalState {
         self.diff_state.clear();
         self.integrate_state.clear();
         self.delay_buffers.clear();
+        self.adsr_state.clear();
+        self.window_samples.clear();
         self.warned_no_musical_time = false;
         self.warned_missing_bands.clear();
         self.warned_missing_stems.clear();
@@ -204,6 +223,11 @@ impl SignalState {
             .or_insert_with(|| DelayBuffer::new(capacity))
     }
 
+    /// Get or create the sample window for a sliding-window node.
+    pub fn get_window_samples(&mut self, id: SignalId) -> &mut VecDeque<(f32, f32)> {
+        self.window_samples.entry(id).or_default()
+    }
+
     /// Get collection sizes for profiling.
     /// Returns a tuple of (name, count) pairs.
     pub fn get_collection_sizes(&self) -> Vec<(&'static str, usize)> {
@@ -215,6 +239,7 @@ impl SignalState {
             ("diff_state", self.diff_state.len()),
             ("integrate_state", self.integrate_state.len()),
             ("delay_buffers", self.delay_buffers.len()),
+            ("window_samples", self.window_samples.len()),
             ("warned_missing_bands", self.warned_missing_bands.len()),
             ("warned_missing_stems", self.warned_missing_stems.len()),
             (
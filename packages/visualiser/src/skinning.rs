@@ -0,0 +1,433 @@
+//! Skeletal animation for skinned mesh assets.
+//!
+//! A `Skin` holds a joint hierarchy, per-vertex joint weights, and the
+//! animation clips imported with it. Clips are sampled on the CPU into one
+//! bone matrix per joint; the renderer skins vertices with them on the GPU,
+//! or with `skin_vertices` when the mesh goes through the CPU deformation path.
+
+use glam::{Mat4, Quat, Vec3, Vec4};
+
+use crate::gpu::mesh::{SkinVertex, Vertex};
+
+/// Maximum joints per skin (the size of the renderer's bone matrix array).
+pub const MAX_JOINTS: usize = 128;
+
+/// A joint in the skeleton with its rest pose.
+#[derive(Debug, Clone)]
+pub struct Joint {
+    /// Node name from the source file.
+    pub name: String,
+    /// Parent joint, or `None` for a root joint.
+    pub parent: Option<usize>,
+    /// Fixed transform above a root joint (its non-joint ancestors).
+    pub root_transform: Mat4,
+    /// Rest translation relative to the parent.
+    pub translation: Vec3,
+    /// Rest rotation relative to the parent.
+    pub rotation: Quat,
+    /// Rest scale relative to the parent.
+    pub scale: Vec3,
+    /// Maps mesh space into the joint's bind-pose space.
+    pub inverse_bind: Mat4,
+}
+
+/// How keyframes are interpolated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpolation {
+    /// Hold each keyframe until the next.
+    Step,
+    /// Lerp translations and scales, slerp rotations.
+    Linear,
+}
+
+/// Keyframe values for one joint property.
+#[derive(Debug, Clone)]
+pub enum ChannelValues {
+    Translation(Vec<Vec3>),
+    Rotation(Vec<Quat>),
+    Scale(Vec<Vec3>),
+}
+
+/// Keyframes animating one property of one joint.
+#[derive(Debug, Clone)]
+pub struct AnimationChannel {
+    /// Joint index in the skin.
+    pub joint: usize,
+    pub interpolation: Interpolation,
+    /// Keyframe times in seconds, ascending.
+    pub times: Vec<f32>,
+    /// One value per keyframe time.
+    pub values: ChannelValues,
+}
+
+/// A named animation clip.
+#[derive(Debug, Clone)]
+pub struct AnimationClip {
+    pub name: String,
+    /// Length in seconds (the last keyframe time).
+    pub duration: f32,
+    pub channels: Vec<AnimationChannel>,
+}
+
+impl AnimationClip {
+    /// Map a playback time onto the clip, wrapping when looping and holding
+    /// the first/last frame otherwise.
+    pub fn clip_time(&self, time: f32, looping: bool) -> f32 {
+        if self.duration <= 0.0 {
+            0.0
+        } else if looping {
+            time.rem_euclid(self.duration)
+        } else {
+            time.clamp(0.0, self.duration)
+        }
+    }
+}
+
+impl AnimationChannel {
+    /// Keyframe pair around `time` and the blend factor between them.
+    fn keyframes(&self, time: f32) -> (usize, usize, f32) {
+        let last = self.times.len().saturating_sub(1);
+        let next = self.times.partition_point(|&t| t <= time);
+        if next == 0 {
+            return (0, 0, 0.0);
+        }
+        if next > last {
+            return (last, last, 0.0);
+        }
+        let prev = next - 1;
+        if self.interpolation == Interpolation::Step {
+            return (prev, prev, 0.0);
+        }
+        let span = self.times[next] - self.times[prev];
+        let t = if span > 0.0 {
+            (time - self.times[prev]) / span
+        } else {
+            0.0
+        };
+        (prev, next, t)
+    }
+
+    /// Write this channel's value at `time` into a joint's local transform.
+    fn apply(&self, time: f32, pose: &mut (Vec3, Quat, Vec3)) {
+        if self.times.is_empty() {
+            return;
+        }
+        let (a, b, t) = self.keyframes(time);
+        match &self.values {
+            ChannelValues::Translation(values) => {
+                if let (Some(a), Some(b)) = (values.get(a), values.get(b)) {
+                    pose.0 = a.lerp(*b, t);
+                }
+            }
+            ChannelValues::Rotation(values) => {
+                if let (Some(a), Some(b)) = (values.get(a), values.get(b)) {
+                    pose.1 = a.slerp(*b, t).normalize();
+                }
+            }
+            ChannelValues::Scale(values) => {
+                if let (Some(a), Some(b)) = (values.get(a), values.get(b)) {
+                    pose.2 = a.lerp(*b, t);
+                }
+            }
+        }
+    }
+}
+
+/// Skeleton, joint weights, and animations of a skinned mesh asset.
+#[derive(Debug, Clone)]
+pub struct Skin {
+    pub joints: Vec<Joint>,
+    /// Joint influences, one per asset vertex.
+    pub vertices: Vec<SkinVertex>,
+    pub animations: Vec<AnimationClip>,
+}
+
+impl Skin {
+    /// Find an animation clip by name.
+    pub fn animation(&self, name: &str) -> Option<&AnimationClip> {
+        self.animations.iter().find(|clip| clip.name == name)
+    }
+
+    /// Bone matrices (joint world transform times inverse bind matrix) for a
+    /// clip at a time in seconds, or for the rest pose without a clip.
+    pub fn bone_matrices(&self, clip: Option<(&AnimationClip, f32)>) -> Vec<Mat4> {
+        let mut poses: Vec<(Vec3, Quat, Vec3)> = self
+            .joints
+            .iter()
+            .map(|joint| (joint.translation, joint.rotation, joint.scale))
+            .collect();
+        if let Some((clip, time)) = clip {
+            for channel in &clip.channels {
+                if let Some(pose) = poses.get_mut(channel.joint) {
+                    channel.apply(time, pose);
+                }
+            }
+        }
+
+        let mut globals: Vec<Option<Mat4>> = vec![None; self.joints.len()];
+        for index in 0..self.joints.len() {
+            self.joint_global(index, &poses, &mut globals);
+        }
+        globals
+            .iter()
+            .zip(&self.joints)
+            .map(|(global, joint)| global.unwrap_or(Mat4::IDENTITY) * joint.inverse_bind)
+            .collect()
+    }
+
+    /// World transform of a joint, resolving (and caching) its parents first.
+    fn joint_global(
+        &self,
+        index: usize,
+        poses: &[(Vec3, Quat, Vec3)],
+        globals: &mut [Option<Mat4>],
+    ) -> Mat4 {
+        if let Some(global) = globals[index] {
+            return global;
+        }
+        let joint = &self.joints[index];
+        let (translation, rotation, scale) = poses[index];
+        let local = Mat4::from_scale_rotation_translation(scale, rotation, translation);
+        // Parents are checked at import to form a tree
+        let parent = match joint.parent {
+            Some(parent) => self.joint_global(parent, poses, globals),
+            None => joint.root_transform,
+        };
+        let global = parent * local;
+        globals[index] = Some(global);
+        global
+    }
+}
+
+/// Skin vertices on the CPU with the same blend the skinned shader uses.
+pub fn skin_vertices(vertices: &[Vertex], skin: &[SkinVertex], bones: &[Mat4]) -> Vec<Vertex> {
+    vertices
+        .iter()
+        .zip(skin)
+        .map(|(vertex, influence)| {
+            let weights = Vec4::from(influence.weights);
+            if weights.element_sum() <= 0.0 {
+                return *vertex;
+            }
+            let mut matrix = Mat4::ZERO;
+            for (joint, weight) in influence.joints.iter().zip(influence.weights) {
+                if let Some(bone) = bones.get(*joint as usize) {
+                    matrix += *bone * weight;
+                }
+            }
+            let position = matrix.transform_point3(Vec3::from(vertex.position));
+            let normal = matrix
+                .transform_vector3(Vec3::from(vertex.normal))
+                .normalize_or(Vec3::from(vertex.normal));
+            Vertex::new(position.into(), normal.into(), vertex.color)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{headless_or_skip, step};
+    use crate::visualiser::VisualiserState;
+
+    /// A two-joint arm: the elbow sits one unit above the shoulder, and a clip
+    /// raises the shoulder by two units and turns the elbow a quarter turn.
+    fn arm() -> Skin {
+        let shoulder = Joint {
+            name: "shoulder".to_string(),
+            parent: None,
+            root_transform: Mat4::IDENTITY,
+            translation: Vec3::ZERO,
+            rotation: Quat::IDENTITY,
+            scale: Vec3::ONE,
+            inverse_bind: Mat4::IDENTITY,
+        };
+        let elbow = Joint {
+            name: "elbow".to_string(),
+            parent: Some(0),
+            translation: Vec3::Y,
+            inverse_bind: Mat4::from_translation(-Vec3::Y),
+            ..shoulder.clone()
+        };
+        let wave = AnimationClip {
+            name: "wave".to_string(),
+            duration: 1.0,
+            channels: vec![
+                AnimationChannel {
+                    joint: 0,
+                    interpolation: Interpolation::Linear,
+                    times: vec![0.0, 1.0],
+                    values: ChannelValues::Translation(vec![Vec3::ZERO, Vec3::new(0.0, 2.0, 0.0)]),
+                },
+                AnimationChannel {
+                    joint: 1,
+                    interpolation: Interpolation::Step,
+                    times: vec![0.0, 0.5],
+                    values: ChannelValues::Rotation(vec![
+                        Quat::IDENTITY,
+                        Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+                    ]),
+                },
+            ],
+        };
+        Skin {
+            joints: vec![shoulder, elbow],
+            vertices: Vec::new(),
+            animations: vec![wave],
+        }
+    }
+
+    fn assert_mat_eq(a: Mat4, b: Mat4) {
+        assert!(a.abs_diff_eq(b, 1e-5), "{a:?} != {b:?}");
+    }
+
+    #[test]
+    fn test_rest_pose_bones_are_identity() {
+        let skin = arm();
+        for bone in skin.bone_matrices(None) {
+            assert_mat_eq(bone, Mat4::IDENTITY);
+        }
+    }
+
+    #[test]
+    fn test_sampling_at_two_times_gives_different_bones() {
+        let skin = arm();
+        let clip = skin.animation("wave").unwrap();
+
+        let start = skin.bone_matrices(Some((clip, 0.0)));
+        let middle = skin.bone_matrices(Some((clip, 0.75)));
+        assert!(!start[0].abs_diff_eq(middle[0], 1e-3));
+        assert!(!start[1].abs_diff_eq(middle[1], 1e-3));
+
+        // A quarter in, the shoulder has risen 0.5 and the stepped rotation
+        // hasn't switched yet
+        let half = skin.bone_matrices(Some((clip, 0.25)));
+        assert_mat_eq(half[0], Mat4::from_translation(Vec3::new(0.0, 0.5, 0.0)));
+        assert_mat_eq(half[1], Mat4::from_translation(Vec3::new(0.0, 0.5, 0.0)));
+
+        // The elbow's rotation carries through its own bind pose
+        let tip = middle[1].transform_point3(Vec3::new(0.0, 2.0, 0.0));
+        assert!(tip.abs_diff_eq(Vec3::new(-1.0, 2.5, 0.0), 1e-5), "{tip:?}");
+    }
+
+    #[test]
+    fn test_clip_time_wraps_or_holds() {
+        let skin = arm();
+        let clip = skin.animation("wave").unwrap();
+        assert!((clip.clip_time(2.25, true) - 0.25).abs() < 1e-6);
+        assert!((clip.clip_time(-0.25, true) - 0.75).abs() < 1e-6);
+        assert_eq!(clip.clip_time(2.25, false), 1.0);
+        assert_eq!(clip.clip_time(-1.0, false), 0.0);
+    }
+
+    #[test]
+    fn test_skin_vertices_blends_weights() {
+        let vertices = [
+            Vertex::new([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0; 3]),
+            Vertex::new([1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [1.0; 3]),
+        ];
+        let skin = [
+            SkinVertex {
+                joints: [0, 1, 0, 0],
+                weights: [0.5, 0.5, 0.0, 0.0],
+            },
+            // Unweighted vertices stay put
+            SkinVertex::default(),
+        ];
+        let bones = [
+            Mat4::IDENTITY,
+            Mat4::from_translation(Vec3::new(0.0, 2.0, 0.0)),
+        ];
+
+        let skinned = skin_vertices(&vertices, &skin, &bones);
+        assert_eq!(skinned[0].position, [1.0, 1.0, 0.0]);
+        assert_eq!(skinned[1].position, [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_skinned_mesh_follows_animation_on_gpu_and_cpu() {
+        use crate::mesh_asset::MeshAsset;
+
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // A red quad bound to one joint that the "slide" clip moves one unit right
+        let vertices = [[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]]
+            .iter()
+            .map(|[x, y]| Vertex::new([*x, *y, 0.0], [0.0, 0.0, 1.0], [1.0, 0.0, 0.0]))
+            .collect();
+        let mut asset = MeshAsset::new("rig".to_string(), vertices, vec![0, 1, 2, 2, 3, 0]);
+        asset.skin = Some(Skin {
+            joints: vec![Joint {
+                name: "root".to_string(),
+                parent: None,
+                root_transform: Mat4::IDENTITY,
+                translation: Vec3::ZERO,
+                rotation: Quat::IDENTITY,
+                scale: Vec3::ONE,
+                inverse_bind: Mat4::IDENTITY,
+            }],
+            vertices: vec![
+                SkinVertex {
+                    joints: [0; 4],
+                    weights: [1.0, 0.0, 0.0, 0.0],
+                };
+                4
+            ],
+            animations: vec![AnimationClip {
+                name: "slide".to_string(),
+                duration: 1.0,
+                channels: vec![AnimationChannel {
+                    joint: 0,
+                    interpolation: Interpolation::Linear,
+                    times: vec![0.0, 1.0],
+                    values: ChannelValues::Translation(vec![Vec3::ZERO, Vec3::X]),
+                }],
+            }],
+        });
+
+        // Columns covered by the quad (no green, unlike the grey background)
+        let mut render = |time: f32, cpu: bool| {
+            let mut state = VisualiserState::new();
+            state.asset_registry.register(asset.clone());
+            // A morph weight sends the mesh down the CPU skinning path
+            let morph = if cpu {
+                r#"quad.morph("none", 0.0);"#
+            } else {
+                ""
+            };
+            let script = format!(
+                r#"
+                let quad = mesh.load("rig");
+                quad.playAnimation("slide", #{{ looping: false, time: gen.constant({time:?}) }});
+                {morph}
+
+                fn init(ctx) {{
+                    camera.position = #{{ x: 0.0, y: 0.0, z: 3.0 }};
+                    camera.lookAt(#{{ x: 0.0, y: 0.0, z: 0.0 }});
+                    scene.add(quad);
+                }}
+
+                fn update(dt, frame) {{}}
+            "#
+            );
+            assert!(state.load_script(&script));
+            step(&mut state, 1.0 / 60.0);
+            headless.render(&state);
+            let hdr = headless.read_scene_hdr();
+            let covered: Vec<usize> = (0..64).filter(|&x| hdr[32 * 64 + x][1] < 1e-3).collect();
+            assert!(!covered.is_empty(), "quad should be visible at {time}");
+            (covered[0], *covered.last().unwrap())
+        };
+
+        let rest = render(0.0, false);
+        let moved = render(0.5, false);
+        assert!(moved.0 > rest.0 + 4, "{rest:?} -> {moved:?}");
+        assert!(moved.1 > rest.1 + 4, "{rest:?} -> {moved:?}");
+
+        // CPU skinning lands in the same place
+        assert_eq!(render(0.0, true), rest);
+        assert_eq!(render(0.5, true), moved);
+    }
+}
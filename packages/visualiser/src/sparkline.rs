@@ -1,7 +1,11 @@
+use crate::bitmap_font::{text_strokes, GLYPH_HEIGHT};
+
 pub struct Sparkline {
     pub capacity: usize,
     pub data: Vec<f32>,
     pub cursor: usize,
+    /// Number of valid points (up to capacity).
+    pub count: usize,
     pub current_min: f32,
     pub current_max: f32,
     pub last_min: f32,
@@ -14,6 +18,7 @@ impl Sparkline {
             capacity,
             data: vec![0.0; capacity],
             cursor: 0,
+            count: 0,
             current_min: f32::INFINITY,
             current_max: f32::NEG_INFINITY,
             // Initialize with 0-1 range to avoid initial flickering before first loop
@@ -44,6 +49,7 @@ impl Sparkline {
 
         self.data[self.cursor] = value;
         self.cursor += 1;
+        self.count = (self.count + 1).min(self.capacity);
 
         // Clear a small gap ahead to visualize the "head"
         // But only if we are not at end
@@ -63,4 +69,84 @@ impl Sparkline {
             // Let's try just updating data for now.
         }
     }
+
+    /// Valid points as (index, value) pairs with values mapped to 0-1 using
+    /// the range of the previous and current sweeps, ready for the line pipeline.
+    pub fn to_gpu_data(&self) -> Vec<[f32; 2]> {
+        let min = self.last_min.min(self.current_min);
+        let max = self.last_max.max(self.current_max);
+        let range = max - min;
+        self.data[..self.count]
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| {
+                let y = if range > f32::EPSILON {
+                    (v - min) / range
+                } else {
+                    0.5
+                };
+                [i as f32, y]
+            })
+            .collect()
+    }
+}
+
+/// Number of samples kept per debug plot (about three seconds at 60fps).
+pub const DEBUG_PLOT_POINTS: usize = 180;
+
+/// Maximum number of debug plots. Six fill the top half of the screen,
+/// leaving the bottom for the waveform and spectrogram overlays.
+pub const MAX_DEBUG_PLOTS: usize = 6;
+
+/// Screen pixels per label font pixel.
+const PLOT_LABEL_SCALE: f32 = 2.0;
+
+/// A named sparkline created by `dbg.plot(name, signal)`.
+///
+/// The script engine samples the plotted signal once per frame; the renderer
+/// stacks plots in the top-right corner of the screen.
+pub struct DebugPlot {
+    pub name: String,
+    pub sparkline: Sparkline,
+}
+
+impl DebugPlot {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            sparkline: Sparkline::new(DEBUG_PLOT_POINTS),
+        }
+    }
+
+    /// Screen region (NDC offset, scale) reserved for the plot at `slot`,
+    /// stacked downward from the top-right corner.
+    pub fn screen_region(slot: usize) -> ([f32; 2], [f32; 2]) {
+        let height = 0.12;
+        let top = 0.95 - slot as f32 * (height + 0.04);
+        ([0.5, top - height], [0.45, height])
+    }
+
+    /// NDC line segments (`[start xyz, end xyz]`, z = 0) drawing the plot's
+    /// name just above its region, for the polyline pipeline with an identity
+    /// transform.
+    pub fn label_segments(&self, slot: usize, viewport: [f32; 2]) -> Vec<[f32; 6]> {
+        let (offset, scale) = Self::screen_region(slot);
+        let pixel_x = 2.0 * PLOT_LABEL_SCALE / viewport[0].max(1.0);
+        let pixel_y = 2.0 * PLOT_LABEL_SCALE / viewport[1].max(1.0);
+        let left = offset[0];
+        let top = offset[1] + scale[1] + (GLYPH_HEIGHT as f32 + 1.0) * pixel_y;
+        text_strokes(&self.name)
+            .into_iter()
+            .map(|[x0, y0, x1, y1]| {
+                [
+                    left + x0 * pixel_x,
+                    top - y0 * pixel_y,
+                    0.0,
+                    left + x1 * pixel_x,
+                    top - y1 * pixel_y,
+                    0.0,
+                ]
+            })
+            .collect()
+    }
 }
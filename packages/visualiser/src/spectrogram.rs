@@ -0,0 +1,257 @@
+//! Scrolling spectrogram overlay shown with `dbg.showSpectrogram(options)`.
+//!
+//! Each frame the raw track audio around the current time is FFT'd and the
+//! magnitudes, on a log-frequency axis, are written as one column into a
+//! fixed-width ring buffer of RGBA pixels. The renderer mirrors the ring
+//! buffer into a texture (one column per frame) and draws it as a quad,
+//! offsetting the texture lookup by the write cursor so it scrolls.
+//!
+//! The audio comes from `VisualiserState::set_spectrum_audio`: PCM such as
+//! the CLI's `--input`, not the low-rate envelopes an interpretation package
+//! carries.
+
+use crate::colormap::Colormap;
+use crate::frequency_band::fft_in_place;
+use crate::input::InputSignal;
+
+/// Number of columns (frames of history) in the spectrogram.
+pub const SPECTROGRAM_COLUMNS: u32 = 256;
+
+/// Number of frequency rows in the spectrogram.
+pub const SPECTROGRAM_ROWS: u32 = 128;
+
+/// Lowest sample rate treated as raw audio. Analysis envelopes, like a
+/// package's ~100 Hz amplitude signal, have no audible frequencies to show.
+pub const MIN_SPECTRUM_SAMPLE_RATE: f32 = 8000.0;
+
+/// FFT window length in samples.
+const SPECTRUM_WINDOW: usize = 1024;
+
+/// Magnitudes are shown over this range, in dB below full scale.
+const SPECTRUM_FLOOR_DB: f32 = -80.0;
+
+/// Options from `dbg.showSpectrogram(#{ rect, colormap })`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrogramOptions {
+    /// Screen region as `[x, y, w, h]` fractions of the screen, from the top-left.
+    pub rect: [f32; 4],
+    pub colormap: Colormap,
+}
+
+impl Default for SpectrogramOptions {
+    fn default() -> Self {
+        Self {
+            rect: [0.02, 0.02, 0.4, 0.2],
+            colormap: Colormap::Magma,
+        }
+    }
+}
+
+impl SpectrogramOptions {
+    /// Screen region as (NDC offset of the bottom-left corner, NDC size).
+    pub fn ndc_region(&self) -> ([f32; 2], [f32; 2]) {
+        let [x, y, w, h] = self.rect;
+        ([x * 2.0 - 1.0, 1.0 - (y + h) * 2.0], [w * 2.0, h * 2.0])
+    }
+}
+
+/// Ring buffer of spectrum columns, stored as RGBA8 rows (row 0 is the
+/// highest frequency, so the image is upright).
+pub struct Spectrogram {
+    pub options: SpectrogramOptions,
+    pixels: Vec<u8>,
+    /// Column the next spectrum is written to.
+    cursor: u32,
+    /// Total columns ever written; lets the renderer tell whether it has
+    /// fallen behind and needs a full upload.
+    columns_written: u64,
+}
+
+impl Spectrogram {
+    pub fn new(options: SpectrogramOptions) -> Self {
+        let background = options.colormap.rgba8(0.0);
+        Self {
+            options,
+            pixels: background.repeat((SPECTROGRAM_COLUMNS * SPECTROGRAM_ROWS) as usize),
+            cursor: 0,
+            columns_written: 0,
+        }
+    }
+
+    /// Write one column of magnitudes (0-1, lowest frequency first) at the
+    /// cursor and advance it, overwriting the oldest column once full.
+    pub fn push_column(&mut self, magnitudes: &[f32]) {
+        for row in 0..SPECTROGRAM_ROWS {
+            let value = magnitudes.get(row as usize).copied().unwrap_or(0.0);
+            let y = SPECTROGRAM_ROWS - 1 - row;
+            let offset = ((y * SPECTROGRAM_COLUMNS + self.cursor) * 4) as usize;
+            self.pixels[offset..offset + 4].copy_from_slice(&self.options.colormap.rgba8(value));
+        }
+        self.cursor = (self.cursor + 1) % SPECTROGRAM_COLUMNS;
+        self.columns_written += 1;
+    }
+
+    /// Write the spectrum of `audio` around `time`, or a silent column without audio.
+    pub fn push_from_audio(&mut self, audio: Option<&InputSignal>, time: f32) {
+        let column = match audio {
+            Some(audio) => spectrum_rows(
+                audio.samples(),
+                audio.sample_rate(),
+                time,
+                SPECTROGRAM_ROWS as usize,
+            ),
+            None => Vec::new(),
+        };
+        self.push_column(&column);
+    }
+
+    /// Number of columns holding data (at most `SPECTROGRAM_COLUMNS`).
+    pub fn populated_columns(&self) -> usize {
+        self.columns_written.min(SPECTROGRAM_COLUMNS as u64) as usize
+    }
+
+    /// Total columns written since creation.
+    pub fn columns_written(&self) -> u64 {
+        self.columns_written
+    }
+
+    /// Column the next spectrum goes to; the oldest column once the buffer is full.
+    pub fn cursor(&self) -> u32 {
+        self.cursor
+    }
+
+    /// The whole ring buffer as tightly packed RGBA8 rows.
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// One column as RGBA8, top row first.
+    pub fn column_rgba(&self, column: u32) -> Vec<u8> {
+        (0..SPECTROGRAM_ROWS)
+            .flat_map(|y| {
+                let offset = ((y * SPECTROGRAM_COLUMNS + column) * 4) as usize;
+                self.pixels[offset..offset + 4].iter().copied()
+            })
+            .collect()
+    }
+}
+
+/// Magnitude spectrum of the window of `samples` centred on `time`, reduced to
+/// `rows` log-spaced frequency rows (lowest first) and mapped from
+/// `SPECTRUM_FLOOR_DB`..0 dB to 0-1. A full-scale sine reads about 1.
+pub fn spectrum_rows(samples: &[f32], sample_rate: f32, time: f32, rows: usize) -> Vec<f32> {
+    let n = SPECTRUM_WINDOW;
+    let window: Vec<f32> = (0..n)
+        .map(|i| 0.5 - 0.5 * (std::f32::consts::TAU * i as f32 / n as f32).cos())
+        .collect();
+    let norm = 2.0 / window.iter().sum::<f32>();
+
+    let center = (time * sample_rate) as isize;
+    let start = center - (n / 2) as isize;
+    let mut re: Vec<f32> = (0..n)
+        .map(|i| {
+            let idx = start + i as isize;
+            if idx >= 0 && (idx as usize) < samples.len() {
+                samples[idx as usize] * window[i]
+            } else {
+                0.0
+            }
+        })
+        .collect();
+    let mut im = vec![0.0f32; n];
+    fft_in_place(&mut re, &mut im);
+
+    // Row r covers bins [bins^(r/rows), bins^((r+1)/rows)), skipping DC.
+    let bins = (n / 2) as f32;
+    (0..rows)
+        .map(|r| {
+            let lo = bins.powf(r as f32 / rows as f32) as usize;
+            let hi = (bins.powf((r + 1) as f32 / rows as f32) as usize).max(lo + 1);
+            let magnitude = (lo..hi.min(n / 2))
+                .map(|k| (re[k] * re[k] + im[k] * im[k]).sqrt() * norm)
+                .fold(0.0f32, f32::max);
+            let db = 20.0 * magnitude.max(1e-9).log10();
+            ((db - SPECTRUM_FLOOR_DB) / -SPECTRUM_FLOOR_DB).clamp(0.0, 1.0)
+        })
+        .collect()
+}
+
+/// Level of bar `bar` of `bars` bars spread evenly over `spectrum` (nearest
+/// row), or 0 without a spectrum.
+pub fn spectrum_level(spectrum: &[f32], bar: usize, bars: usize) -> f32 {
+    if spectrum.is_empty() || bars == 0 {
+        return 0.0;
+    }
+    let row = (bar * spectrum.len() / bars).min(spectrum.len() - 1);
+    spectrum[row]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_n_frames_populate_n_columns() {
+        let samples: Vec<f32> = (0..44100)
+            .map(|i| (i as f32 / 44100.0 * 440.0 * std::f32::consts::TAU).sin())
+            .collect();
+        let audio = InputSignal::new(samples, 44100.0);
+        let mut spectrogram = Spectrogram::new(SpectrogramOptions::default());
+        let background = Colormap::Magma.rgba8(0.0);
+
+        for frame in 0..10 {
+            spectrogram.push_from_audio(Some(&audio), 0.1 + frame as f32 / 60.0);
+        }
+
+        assert_eq!(spectrogram.populated_columns(), 10);
+        assert_eq!(spectrogram.cursor(), 10);
+        let populated = (0..SPECTROGRAM_COLUMNS)
+            .filter(|&c| {
+                spectrogram
+                    .column_rgba(c)
+                    .chunks(4)
+                    .any(|pixel| pixel != background)
+            })
+            .count();
+        assert_eq!(populated, 10);
+    }
+
+    #[test]
+    fn test_ring_buffer_wraps() {
+        let mut spectrogram = Spectrogram::new(SpectrogramOptions::default());
+        for _ in 0..SPECTROGRAM_COLUMNS + 3 {
+            spectrogram.push_column(&[1.0; SPECTROGRAM_ROWS as usize]);
+        }
+        assert_eq!(
+            spectrogram.populated_columns(),
+            SPECTROGRAM_COLUMNS as usize
+        );
+        assert_eq!(spectrogram.cursor(), 3);
+    }
+
+    #[test]
+    fn test_sine_peaks_in_its_row() {
+        let sample_rate = 44100.0;
+        let samples: Vec<f32> = (0..4096)
+            .map(|i| (i as f32 / sample_rate * 1000.0 * std::f32::consts::TAU).sin())
+            .collect();
+        let rows = spectrum_rows(&samples, sample_rate, 0.05, 64);
+        let peak = rows
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .unwrap();
+        // 1 kHz is bin ~23 of 512; row r starts at bin 512^(r/64)
+        assert_eq!(peak.0, (23f32.ln() / 512f32.ln() * 64.0) as usize);
+        assert!(*peak.1 > 0.95);
+    }
+
+    #[test]
+    fn test_options_region() {
+        let options = SpectrogramOptions {
+            rect: [0.0, 0.0, 0.5, 0.25],
+            ..Default::default()
+        };
+        assert_eq!(options.ndc_region(), ([-1.0, 0.5], [1.0, 0.5]));
+    }
+}
@@ -0,0 +1,134 @@
+//! Sprite textures for billboard particles.
+//!
+//! Sprites are plain RGBA8 images keyed by the id scripts pass to `sys.sprite()`.
+//! On native builds the id is a file path and missing sprites are decoded from disk
+//! on first use. In the browser the host decodes the image and registers raw RGBA
+//! bytes under the same id.
+
+use std::collections::{HashMap, HashSet};
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A decoded sprite image (RGBA8, row-major, top row first).
+#[derive(Debug, Clone)]
+pub struct SpriteAsset {
+    pub id: String,
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+impl SpriteAsset {
+    /// Create a sprite from raw RGBA8 bytes.
+    pub fn from_rgba(id: String, width: u32, height: u32, rgba: Vec<u8>) -> Result<Self, String> {
+        if width == 0 || height == 0 {
+            return Err(format!("Sprite '{}' has zero size", id));
+        }
+        let expected = width as usize * height as usize * 4;
+        if rgba.len() != expected {
+            return Err(format!(
+                "Sprite '{}' is {}x{} and needs {} RGBA bytes, got {}",
+                id,
+                width,
+                height,
+                expected,
+                rgba.len()
+            ));
+        }
+        Ok(Self {
+            id,
+            width,
+            height,
+            rgba,
+        })
+    }
+
+    /// Decode a sprite from an image file (PNG, JPEG, ...).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn from_file(id: String, path: &Path) -> Result<Self, String> {
+        let image = image::open(path)
+            .map_err(|e| format!("Failed to load sprite {:?}: {}", path, e))?
+            .to_rgba8();
+        let (width, height) = image.dimensions();
+        Self::from_rgba(id, width, height, image.into_raw())
+    }
+}
+
+/// Registry of sprite textures, shared by every particle system that references them.
+#[derive(Debug, Default)]
+pub struct SpriteAssetRegistry {
+    assets: HashMap<String, Arc<SpriteAsset>>,
+    /// Ids that failed to load from disk, so they are not retried every frame.
+    failed: HashSet<String>,
+    /// Directory relative sprite paths are resolved against (native only).
+    #[cfg(not(target_arch = "wasm32"))]
+    search_dir: Option<PathBuf>,
+}
+
+impl SpriteAssetRegistry {
+    /// Create a new empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a sprite from raw RGBA8 bytes, replacing any existing sprite with the same id.
+    pub fn register_rgba(
+        &mut self,
+        id: &str,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<(), String> {
+        let asset = SpriteAsset::from_rgba(id.to_string(), width, height, rgba)?;
+        self.failed.remove(id);
+        self.assets.insert(id.to_string(), Arc::new(asset));
+        Ok(())
+    }
+
+    /// Get a sprite by id.
+    pub fn get(&self, id: &str) -> Option<Arc<SpriteAsset>> {
+        self.assets.get(id).cloned()
+    }
+
+    /// Unregister a sprite.
+    pub fn unregister(&mut self, id: &str) -> bool {
+        self.assets.remove(id).is_some()
+    }
+
+    /// Clear all registered sprites.
+    pub fn clear(&mut self) {
+        self.assets.clear();
+        self.failed.clear();
+    }
+
+    /// Set the directory relative sprite paths are resolved against.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn set_search_dir(&mut self, dir: Option<PathBuf>) {
+        self.search_dir = dir;
+    }
+
+    /// Load any of `ids` that aren't registered yet, treating each id as a file path.
+    /// Failures are logged once and not retried.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load_missing<'a>(&mut self, ids: impl IntoIterator<Item = &'a str>) {
+        for id in ids {
+            if self.assets.contains_key(id) || self.failed.contains(id) {
+                continue;
+            }
+            let path = match &self.search_dir {
+                Some(dir) => dir.join(id),
+                None => PathBuf::from(id),
+            };
+            match SpriteAsset::from_file(id.to_string(), &path) {
+                Ok(asset) => {
+                    self.assets.insert(id.to_string(), Arc::new(asset));
+                }
+                Err(e) => {
+                    log::warn!("{}", e);
+                    self.failed.insert(id.to_string());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,437 @@
+//! Pre-separated stem loading for native renders (`--stems <dir>`).
+//!
+//! Each `.wav` file in the directory becomes one stem. Its id is the
+//! lowercased file name (`Drums.wav` -> `drums`) and its label is the file
+//! name as written, so scripts can use either `inputs.stems["drums"]` or
+//! `inputs.stems["Drums"]`. Every stem is downmixed to mono and analysed into
+//! `energy` (windowed RMS), `amplitude` (windowed peak) and `loudness`
+//! (short-term LUFS, see [`crate::loudness`]) envelopes. When
+//! custom bands are given (`--bands`), each stem is also analysed per band so
+//! scripts can read `inputs.stems["drums"].bands["Hi"].energy`.
+
+use std::path::Path;
+use std::rc::Rc;
+
+use crate::frequency_band::{
+    check_band_resolution, compute_band_energies, BandConfig, BAND_ENERGY_FEATURE,
+};
+use crate::input::InputSignal;
+use crate::loudness::{short_term_loudness, LOUDNESS_FEATURE};
+use crate::visualiser::VisualiserState;
+
+/// Sample rate (Hz) of the analysed stem envelopes.
+pub const STEM_FEATURE_RATE: f32 = 100.0;
+
+/// One analysed stem, ready to push into a `VisualiserState`.
+pub struct LoadedStem {
+    pub id: String,
+    pub label: String,
+    /// Windowed RMS of the stem audio, at `STEM_FEATURE_RATE`.
+    pub energy: Rc<InputSignal>,
+    /// Windowed peak absolute sample, at `STEM_FEATURE_RATE`.
+    pub amplitude: Rc<InputSignal>,
+    /// Short-term loudness in LUFS, at `STEM_FEATURE_RATE`. Empty for stems
+    /// below the loudness analysis sample rate.
+    pub loudness: Rc<InputSignal>,
+    /// Per-band energy of the stem audio as (band id, band label, energy),
+    /// at `STEM_FEATURE_RATE`. Empty when no bands were requested.
+    pub band_energy: Vec<(String, String, Rc<InputSignal>)>,
+}
+
+/// Load and analyse every `.wav` file in `dir`, sorted by file name.
+/// Each stem is additionally analysed into one energy envelope per band.
+pub fn load_stem_dir(dir: &Path, bands: &[BandConfig]) -> Result<Vec<LoadedStem>, String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read stems directory {:?}: {}", dir, e))?;
+
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("wav"))
+        })
+        .collect();
+    paths.sort();
+
+    if paths.is_empty() {
+        return Err(format!("No .wav stem files found in {:?}", dir));
+    }
+
+    paths
+        .iter()
+        .map(|path| load_stem_file(path, bands))
+        .collect()
+}
+
+/// Load and analyse a single stem file.
+fn load_stem_file(path: &Path, bands: &[BandConfig]) -> Result<LoadedStem, String> {
+    let bytes =
+        std::fs::read(path).map_err(|e| format!("Failed to read stem {:?}: {}", path, e))?;
+    let (samples, sample_rate) =
+        read_wav_mono(&bytes).map_err(|e| format!("Failed to decode stem {:?}: {}", path, e))?;
+
+    // Keep names safe to embed in the generated `inputs.stems["..."]` code.
+    let label: String = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || matches!(c, '-' | '_' | ' ') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let id = label.to_lowercase();
+
+    let (energy, amplitude) = stem_envelopes(&samples, sample_rate, STEM_FEATURE_RATE);
+    let loudness = short_term_loudness(&samples, sample_rate, STEM_FEATURE_RATE);
+    check_band_resolution(bands, sample_rate).map_err(|e| format!("Stem {:?}: {}", path, e))?;
+    let band_energy = bands
+        .iter()
+        .zip(compute_band_energies(
+            &samples,
+            sample_rate,
+            bands,
+            STEM_FEATURE_RATE,
+        ))
+        .map(|(band, envelope)| {
+            (
+                band.id.clone(),
+                band.label().to_string(),
+                Rc::new(InputSignal::new(envelope, STEM_FEATURE_RATE)),
+            )
+        })
+        .collect();
+    Ok(LoadedStem {
+        id,
+        label,
+        energy: Rc::new(InputSignal::new(energy, STEM_FEATURE_RATE)),
+        amplitude: Rc::new(InputSignal::new(amplitude, STEM_FEATURE_RATE)),
+        loudness: Rc::new(InputSignal::new(loudness, STEM_FEATURE_RATE)),
+        band_energy,
+    })
+}
+
+/// Push loaded stems into the state's stem signals and add them to the
+/// available stems. Existing stems (e.g. from a package) are kept; call
+/// before `load_script` so the `inputs.stems` accessors are generated.
+pub fn apply_stems(
+    stems: &[LoadedStem],
+    mut available: Vec<(String, String)>,
+    state: &mut VisualiserState,
+) {
+    for stem in stems {
+        state.push_stem_signal(&stem.id, &stem.label, "energy", Rc::clone(&stem.energy));
+        state.push_stem_signal(
+            &stem.id,
+            &stem.label,
+            "amplitude",
+            Rc::clone(&stem.amplitude),
+        );
+        state.push_stem_signal(
+            &stem.id,
+            &stem.label,
+            LOUDNESS_FEATURE,
+            Rc::clone(&stem.loudness),
+        );
+        for (band_id, band_label, energy) in &stem.band_energy {
+            state.push_stem_band_signal(
+                &stem.id,
+                &stem.label,
+                band_id,
+                band_label,
+                BAND_ENERGY_FEATURE,
+                Rc::clone(energy),
+            );
+        }
+        available.retain(|(id, _)| id != &stem.id);
+        available.push((stem.id.clone(), stem.label.clone()));
+    }
+    state.set_available_stems(available);
+}
+
+/// Compute `(energy, amplitude)` envelopes: RMS and peak absolute value over
+/// consecutive windows of `1 / frame_rate` seconds.
+pub fn stem_envelopes(samples: &[f32], sample_rate: f32, frame_rate: f32) -> (Vec<f32>, Vec<f32>) {
+    if samples.is_empty() || sample_rate <= 0.0 || frame_rate <= 0.0 {
+        return (Vec::new(), Vec::new());
+    }
+    let hop = ((sample_rate / frame_rate).round() as usize).max(1);
+    samples
+        .chunks(hop)
+        .map(|window| {
+            let sum_sq: f32 = window.iter().map(|s| s * s).sum();
+            let peak = window.iter().fold(0.0f32, |acc, s| acc.max(s.abs()));
+            ((sum_sq / window.len() as f32).sqrt(), peak)
+        })
+        .unzip()
+}
+
+/// Decode a RIFF/WAVE file to mono samples in [-1, 1] and its sample rate.
+///
+/// Supports integer PCM (8/16/24/32-bit) and 32/64-bit float, including
+/// `WAVE_FORMAT_EXTENSIBLE`. Multi-channel audio is averaged to mono.
+pub fn read_wav_mono(bytes: &[u8]) -> Result<(Vec<f32>, f32), String> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err("not a RIFF/WAVE file".to_string());
+    }
+
+    let u16_at = |at: usize| u16::from_le_bytes([bytes[at], bytes[at + 1]]);
+    let u32_at =
+        |at: usize| u32::from_le_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]]);
+
+    // (format tag, channels, sample rate, bits per sample)
+    let mut format: Option<(u16, usize, u32, usize)> = None;
+    let mut data: Option<&[u8]> = None;
+
+    let mut pos = 12;
+    while pos + 8 <= bytes.len() {
+        let chunk_id = &bytes[pos..pos + 4];
+        let size = u32_at(pos + 4) as usize;
+        let body = &bytes[pos + 8..(pos + 8 + size).min(bytes.len())];
+        match chunk_id {
+            b"fmt " if body.len() >= 16 => {
+                let mut tag = u16::from_le_bytes([body[0], body[1]]);
+                // WAVE_FORMAT_EXTENSIBLE: the real format is the sub-format GUID's first field.
+                if tag == 0xFFFE && body.len() >= 26 {
+                    tag = u16::from_le_bytes([body[24], body[25]]);
+                }
+                format = Some((
+                    tag,
+                    u16_at(pos + 10) as usize,
+                    u32_at(pos + 12),
+                    u16_at(pos + 22) as usize,
+                ));
+            }
+            b"data" => data = Some(body),
+            _ => {}
+        }
+        // Chunks are padded to an even size.
+        pos += 8 + size + (size & 1);
+    }
+
+    let (tag, channels, sample_rate, bits) = format.ok_or("missing fmt chunk")?;
+    let data = data.ok_or("missing data chunk")?;
+    if channels == 0 || sample_rate == 0 {
+        return Err("invalid channel count or sample rate".to_string());
+    }
+
+    let bytes_per_sample = bits / 8;
+    let decode: fn(&[u8]) -> f32 = match (tag, bits) {
+        (1, 8) => |b| (b[0] as f32 - 128.0) / 128.0,
+        (1, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+        (1, 24) => |b| (i32::from_le_bytes([0, b[0], b[1], b[2]]) >> 8) as f32 / 8_388_608.0,
+        (1, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0,
+        (3, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        (3, 64) => |b| f64::from_le_bytes([b[0], b[1], b[2], b[3], b[4], b[5], b[6], b[7]]) as f32,
+        _ => {
+            return Err(format!(
+                "unsupported sample format (format tag {}, {} bits)",
+                tag, bits
+            ))
+        }
+    };
+
+    let frame_bytes = bytes_per_sample * channels;
+    let samples = data
+        .chunks_exact(frame_bytes)
+        .map(|frame| {
+            let sum: f32 = frame.chunks_exact(bytes_per_sample).map(decode).sum();
+            sum / channels as f32
+        })
+        .collect();
+
+    Ok((samples, sample_rate as f32))
+}
+
+/// Encode interleaved samples as a 16-bit PCM wav.
+#[cfg(test)]
+pub(crate) fn wav_16bit(samples: &[f32], channels: u16, sample_rate: u32) -> Vec<u8> {
+    let data: Vec<u8> = samples
+        .iter()
+        .flat_map(|s| ((s * 32768.0).clamp(-32768.0, 32767.0) as i16).to_le_bytes())
+        .collect();
+    let mut wav = Vec::new();
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data.len() as u32).to_le_bytes());
+    wav.extend_from_slice(b"WAVEfmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&channels.to_le_bytes());
+    wav.extend_from_slice(&sample_rate.to_le_bytes());
+    wav.extend_from_slice(&(sample_rate * channels as u32 * 2).to_le_bytes());
+    wav.extend_from_slice(&(channels * 2).to_le_bytes());
+    wav.extend_from_slice(&16u16.to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    wav.extend_from_slice(&data);
+    wav
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::{BandSignalMap, SignalMap};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_read_wav_mono_downmixes_stereo() {
+        // Left 0.75, right 0.25 -> mono 0.5.
+        let wav = wav_16bit(&[0.75, 0.25, 0.75, 0.25], 2, 8000);
+        let (samples, rate) = read_wav_mono(&wav).unwrap();
+        assert_eq!(rate, 8000.0);
+        assert_eq!(samples.len(), 2);
+        assert!((samples[0] - 0.5).abs() < 1e-3);
+
+        assert!(read_wav_mono(b"not a wav file").is_err());
+    }
+
+    #[test]
+    fn test_stem_dir_populates_inputs_stems() {
+        let dir = std::env::temp_dir().join(format!(
+            "octoseq-stems-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Drums: silent for the first half second, then a constant 0.5.
+        let drums: Vec<f32> = (0..8000)
+            .map(|i| if i < 4000 { 0.0 } else { 0.5 })
+            .collect();
+        std::fs::write(dir.join("Drums.wav"), wav_16bit(&drums, 1, 8000)).unwrap();
+        // Bass: a constant -0.25 for the whole second.
+        std::fs::write(dir.join("bass.wav"), wav_16bit(&[-0.25; 8000], 1, 8000)).unwrap();
+        // Non-wav files are ignored.
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+        let stems = load_stem_dir(&dir, &[]).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+        assert_eq!(
+            stems
+                .iter()
+                .map(|s| (s.id.as_str(), s.label.as_str()))
+                .collect::<Vec<_>>(),
+            vec![("drums", "Drums"), ("bass", "bass")]
+        );
+
+        let mut state = VisualiserState::new();
+        apply_stems(&stems, Vec::new(), &mut state);
+        state
+            .try_load_script(
+                r#"
+                let cube;
+                fn init(ctx) {
+                    cube = mesh.cube();
+                    scene.add(cube);
+                }
+                fn update(dt, frame) {
+                    cube.position.x = inputs.stems["Drums"].energy;
+                    cube.position.y = inputs.stems["bass"].amplitude;
+                }
+            "#,
+            )
+            .unwrap();
+
+        let signals: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        let mut position_at = |time: f32| {
+            state.set_time(time - 0.05);
+            state.update(0.05, None, None, &signals, &bands, &signals, None);
+            let (_, cube) = state.scene_graph().scene_entities().next().unwrap();
+            cube.transform().position
+        };
+
+        let early = position_at(0.25);
+        assert!(early.x.abs() < 1e-3, "drums silent at 0.25s: {}", early.x);
+        assert!((early.y - 0.25).abs() < 1e-3);
+
+        let late = position_at(0.75);
+        assert!((late.x - 0.5).abs() < 1e-3, "drums at 0.75s: {}", late.x);
+        assert!((late.y - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_stem_dir_populates_stem_bands() {
+        let dir = std::env::temp_dir().join(format!(
+            "octoseq-stem-bands-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_nanos())
+                .unwrap_or(0)
+        ));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // Drums: silent, then a 3 kHz tone from 0.5s. Bass: a 100 Hz tone.
+        let tone = |i: usize, hz: f32| 0.5 * (std::f32::consts::TAU * hz * i as f32 / 8000.0).sin();
+        let drums: Vec<f32> = (0..8000)
+            .map(|i| if i < 4000 { 0.0 } else { tone(i, 3000.0) })
+            .collect();
+        let bass: Vec<f32> = (0..8000).map(|i| tone(i, 100.0)).collect();
+        std::fs::write(dir.join("drums.wav"), wav_16bit(&drums, 1, 8000)).unwrap();
+        std::fs::write(dir.join("bass.wav"), wav_16bit(&bass, 1, 8000)).unwrap();
+
+        let band = BandConfig {
+            id: "band-hi".to_string(),
+            label: Some("Hi".to_string()),
+            lo: 2000.0,
+            hi: 4000.0,
+        };
+        let stems = load_stem_dir(&dir, std::slice::from_ref(&band)).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let mut state = VisualiserState::new();
+        state.set_available_bands(vec![("band-hi".to_string(), "Hi".to_string())]);
+        apply_stems(&stems, Vec::new(), &mut state);
+        state
+            .try_load_script(
+                r#"
+                let cube;
+                fn init(ctx) {
+                    cube = mesh.cube();
+                    scene.add(cube);
+                }
+                fn update(dt, frame) {
+                    cube.position.x = inputs.stems["drums"].bands["Hi"].energy;
+                    cube.position.y = inputs.stems["bass"].bands["band-hi"].energy;
+                }
+            "#,
+            )
+            .unwrap();
+
+        let signals: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        let mut position_at = |time: f32| {
+            state.set_time(time - 0.05);
+            state.update(0.05, None, None, &signals, &bands, &signals, None);
+            let (_, cube) = state.scene_graph().scene_entities().next().unwrap();
+            cube.transform().position
+        };
+
+        let early = position_at(0.25);
+        assert!(
+            early.x.abs() < 0.02,
+            "drums Hi silent at 0.25s: {}",
+            early.x
+        );
+        assert!(early.y.abs() < 0.02, "bass has no Hi content: {}", early.y);
+
+        // A 0.5 sine inside the band reads its RMS (~0.35).
+        let late = position_at(0.75);
+        assert!(
+            (late.x - 0.35).abs() < 0.05,
+            "drums Hi at 0.75s: {}",
+            late.x
+        );
+        assert!(late.y.abs() < 0.02, "bass has no Hi content: {}", late.y);
+    }
+}
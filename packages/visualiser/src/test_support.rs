@@ -0,0 +1,193 @@
+//! Headless GPU harness for rendering tests.
+//!
+//! Creates a wgpu device without a window (falling back to a software adapter when
+//! no hardware one is available), renders a `VisualiserState` through the real
+//! `Renderer` into an offscreen texture, and reads the result back as tightly
+//! packed RGBA8 rows (or the HDR scene texture as floats).
+//!
+//! Only compiled for unit tests, so it never ships in release builds. Sandboxes
+//! without any adapter get `None` from the constructors; tests should skip with a
+//! note rather than fail, which [`headless_or_skip`] does for them. GPU tests live
+//! next to the feature they cover, using [`script_state`] or
+//! [`HeadlessRenderer::render_script`] to set up a scene.
+
+use crate::gpu::headless;
+pub use crate::gpu::headless::{read_texture_rgba16f, read_texture_rgba8};
+use crate::gpu::renderer::Renderer;
+use crate::input::{BandSignalMap, SignalMap};
+use crate::visualiser::VisualiserState;
+
+/// Texture format used for offscreen renders (matches the CLI's frame output).
+pub const HEADLESS_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Request a device and queue without a surface, or None if no adapter is available.
+pub fn request_headless_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    headless::request_device().ok()
+}
+
+/// A headless renderer of the given size, or None (after printing a skip note)
+/// when no adapter is available:
+///
+/// ```ignore
+/// let Some(mut headless) = headless_or_skip(64, 64) else {
+///     return;
+/// };
+/// ```
+pub fn headless_or_skip(width: u32, height: u32) -> Option<HeadlessRenderer> {
+    let headless = HeadlessRenderer::new(width, height);
+    if headless.is_none() {
+        eprintln!("Skipping headless render test: no GPU adapter available");
+    }
+    headless
+}
+
+/// Advance `state` by `dt` seconds with no audio or named inputs.
+pub fn step(state: &mut VisualiserState, dt: f32) {
+    state.update(
+        dt,
+        None,
+        None,
+        &SignalMap::new(),
+        &BandSignalMap::new(),
+        &SignalMap::new(),
+        None,
+    );
+}
+
+/// A fresh state running `script`, advanced by one 60 fps frame.
+///
+/// Panics with the script error if it fails to load.
+pub fn script_state(script: &str) -> VisualiserState {
+    let mut state = VisualiserState::new();
+    if let Err(e) = state.try_load_script(script) {
+        panic!("script failed to load: {}", e);
+    }
+    step(&mut state, 1.0 / 60.0);
+    state
+}
+
+/// A `Renderer` drawing into an offscreen texture.
+pub struct HeadlessRenderer {
+    pub renderer: Renderer,
+    texture: wgpu::Texture,
+    view: wgpu::TextureView,
+}
+
+impl HeadlessRenderer {
+    /// Create a renderer of the given size, or None if no adapter is available.
+    pub fn new(width: u32, height: u32) -> Option<Self> {
+        let (device, queue) = request_headless_device()?;
+
+        let texture = headless::create_target_texture(&device, HEADLESS_FORMAT, width, height);
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let renderer = Renderer::new(device, queue, HEADLESS_FORMAT, width, height);
+
+        Some(Self {
+            renderer,
+            texture,
+            view,
+        })
+    }
+
+    pub fn width(&self) -> u32 {
+        self.texture.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.texture.height()
+    }
+
+    /// Render one frame of `state` and return its pixels (RGBA8, top row first).
+    pub fn render(&mut self, state: &VisualiserState) -> RenderedFrame {
+        self.renderer.render(&self.view, state);
+        let pixels =
+            read_texture_rgba8(self.renderer.device(), self.renderer.queue(), &self.texture);
+        RenderedFrame {
+            width: self.width(),
+            height: self.height(),
+            pixels,
+        }
+    }
+
+    /// Run `script` for one frame in a fresh state and render it.
+    pub fn render_script(&mut self, script: &str) -> RenderedFrame {
+        self.render(&script_state(script))
+    }
+
+    /// The HDR scene of the last render, before post-processing and
+    /// tonemapping, as `[r, g, b, a]` per pixel (top row first).
+    pub fn read_scene_hdr(&self) -> Vec<[f32; 4]> {
+        read_texture_rgba16f(
+            self.renderer.device(),
+            self.renderer.queue(),
+            self.renderer.scene_texture(),
+        )
+    }
+}
+
+/// Pixels read back from a headless render.
+#[derive(Debug, Clone)]
+pub struct RenderedFrame {
+    pub width: u32,
+    pub height: u32,
+    /// Tightly packed RGBA8 rows, top row first.
+    pub pixels: Vec<u8>,
+}
+
+impl RenderedFrame {
+    /// The RGBA value at (x, y), with (0, 0) at the top left.
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [
+            self.pixels[i],
+            self.pixels[i + 1],
+            self.pixels[i + 2],
+            self.pixels[i + 3],
+        ]
+    }
+
+    /// The pixel at the center of the frame.
+    pub fn center(&self) -> [u8; 4] {
+        self.pixel(self.width / 2, self.height / 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_red_cube_renders_red_pixels() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        let script = r#"
+            let cube = mesh.cube();
+            cube.color.r = 1.0;
+            cube.color.g = 0.0;
+            cube.color.b = 0.0;
+
+            fn init(ctx) {
+                scene.add(cube);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        let frame = headless.render_script(script);
+        assert_eq!(frame.pixels.len(), 64 * 64 * 4);
+
+        // The cube sits at the origin, in the middle of the default camera's view
+        let [r, g, b, _] = frame.center();
+        assert!(r > 64, "center should be lit red, got {:?}", frame.center());
+        assert!(r > g.saturating_mul(2) && r > b.saturating_mul(2));
+
+        // The corners show the dark grey background
+        let [r, g, b, _] = frame.pixel(0, 0);
+        assert!(
+            r == g && g == b && r < 128,
+            "corner should be background, got {:?}",
+            frame.pixel(0, 0)
+        );
+    }
+}
@@ -0,0 +1,118 @@
+//! Pixel-space UI overlay (`ui.rect()`).
+//!
+//! UI entities are positioned in pixels, from (0, 0) at the top-left of the
+//! frame to (width, height) at the bottom-right, and drawn in their own pass
+//! after post-processing and tonemapping, so they stay crisp and aren't
+//! touched by bloom, feedback or other effects:
+//!
+//! ```rhai
+//! let panel = ui.rect(#{ x: 20.0, y: 20.0, width: 200.0, height: 40.0 });
+//! panel.color = #{ r: 0.0, g: 0.0, b: 0.0, a: 0.5 };
+//! scene.add(panel);
+//! ```
+//!
+//! UI entities are added to the scene like any other; they are drawn in the
+//! order they were added, over everything else.
+
+use crate::scene_graph::UiRect;
+
+/// Maximum number of UI rectangles drawn per frame.
+pub const MAX_UI_RECTS: usize = 256;
+
+/// Floats per UI vertex: pixel position (2) and RGBA colour (4).
+pub const UI_VERTEX_FLOATS: usize = 6;
+
+/// Orthographic projection from pixels (origin top-left, y down) to NDC.
+pub fn pixel_projection(width: f32, height: f32) -> glam::Mat4 {
+    glam::Mat4::orthographic_rh(0.0, width, height, 0.0, -1.0, 1.0)
+}
+
+/// The rectangle's corners in pixels, as two triangles.
+pub fn rect_corners(rect: &UiRect) -> [[f32; 2]; 6] {
+    let position = rect.transform.position;
+    let (left, top) = (position.x, position.y);
+    let right = left + rect.width * rect.transform.scale.x;
+    let bottom = top + rect.height * rect.transform.scale.y;
+    [
+        [left, top],
+        [left, bottom],
+        [right, bottom],
+        [left, top],
+        [right, bottom],
+        [right, top],
+    ]
+}
+
+/// The rectangle's vertices in the UI vertex layout.
+pub fn rect_vertices(rect: &UiRect) -> [[f32; UI_VERTEX_FLOATS]; 6] {
+    let [r, g, b, a] = rect.color;
+    rect_corners(rect).map(|[x, y]| [x, y, r, g, b, a])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{headless_or_skip, script_state};
+
+    #[test]
+    fn test_rect_at_pixel_lands_at_matching_ndc() {
+        let mut rect = UiRect::new(50.0, 20.0);
+        rect.transform.position.x = 100.0;
+        rect.transform.position.y = 100.0;
+        let projection = pixel_projection(800.0, 600.0);
+        let ndc = |[x, y]: [f32; 2]| projection.project_point3(glam::Vec3::new(x, y, 0.0));
+
+        // 100px in from the left of 800 and down from the top of 600
+        let corners = rect_corners(&rect);
+        let top_left = ndc(corners[0]);
+        assert!((top_left.x - -0.75).abs() < 1e-6, "{:?}", top_left);
+        assert!(
+            (top_left.y - (1.0 - 200.0 / 600.0)).abs() < 1e-6,
+            "{:?}",
+            top_left
+        );
+
+        // Scale grows the rect from its top-left corner
+        rect.transform.scale.x = 2.0;
+        let bottom_right = ndc(rect_corners(&rect)[2]);
+        assert!((bottom_right.x - -0.5).abs() < 1e-6, "{:?}", bottom_right);
+        assert!(
+            (bottom_right.y - (1.0 - 240.0 / 600.0)).abs() < 1e-6,
+            "{:?}",
+            bottom_right
+        );
+
+        // The frame's corners are the NDC corners
+        assert_eq!(ndc([0.0, 0.0]).truncate(), glam::Vec2::new(-1.0, 1.0));
+        assert_eq!(ndc([800.0, 600.0]).truncate(), glam::Vec2::new(1.0, -1.0));
+    }
+
+    #[test]
+    fn test_ui_rect_draws_after_post_processing() {
+        let Some(mut headless) = headless_or_skip(64, 64) else {
+            return;
+        };
+
+        let script = r#"
+            let panel = ui.rect(#{ x: 8.0, y: 4.0, width: 16.0, height: 8.0 });
+
+            fn init(ctx) {
+                scene.add(panel);
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        let mut state = script_state(script);
+
+        // Exposure darkens the scene but not the UI drawn over it
+        state.set_exposure(0.1);
+        let frame = headless.render(&state);
+        assert_eq!(frame.pixel(8, 4), [255, 255, 255, 255]);
+        assert_eq!(frame.pixel(23, 11), [255, 255, 255, 255]);
+        assert_ne!(frame.pixel(24, 11), [255, 255, 255, 255]);
+        assert_ne!(frame.pixel(8, 12), [255, 255, 255, 255]);
+        // Nothing is drawn into the HDR scene
+        let scene = headless.read_scene_hdr();
+        assert!(scene[4 * 64 + 8][0] < 0.5, "{:?}", scene[4 * 64 + 8]);
+    }
+}
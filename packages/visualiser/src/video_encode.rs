@@ -5,8 +5,11 @@
 
 #![cfg(not(target_arch = "wasm32"))]
 
-use std::path::Path;
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::str::FromStr;
 
 /// Result of checking FFmpeg availability.
 #[derive(Debug)]
@@ -39,7 +42,7 @@ pub fn check_ffmpeg() -> FfmpegStatus {
     }
 }
 
-/// Encode frames to video using FFmpeg.
+/// Encode frames to video using FFmpeg with the default options (H.264, yuv420p, CRF 18).
 ///
 /// # Arguments
 ///
@@ -66,6 +69,30 @@ pub fn encode_video_with_ffmpeg(
     output_path: &Path,
     fps: f32,
 ) -> Result<(), String> {
+    encode_video_with_options(
+        frames_dir,
+        output_path,
+        fps,
+        &VideoEncodingOptions::default(),
+    )
+}
+
+/// Encode frames to video with the given codec, quality and pixel format.
+///
+/// # Arguments
+///
+/// * `frames_dir` - Directory containing frames
+/// * `output_path` - Output video file path
+/// * `fps` - Frame rate
+/// * `options` - Encoding options, validated before FFmpeg runs
+pub fn encode_video_with_options(
+    frames_dir: &Path,
+    output_path: &Path,
+    fps: f32,
+    options: &VideoEncodingOptions,
+) -> Result<(), String> {
+    options.validate()?;
+
     // Check FFmpeg availability
     match check_ffmpeg() {
         FfmpegStatus::Available(version) => {
@@ -92,148 +119,427 @@ pub fn encode_video_with_ffmpeg(
             .map_err(|e| format!("Failed to create output directory: {}", e))?;
     }
 
-    // Build FFmpeg command
-    // -y: Overwrite output without asking
-    // -framerate: Input frame rate
-    // -i: Input pattern
-    // -c:v libx264: Use H.264 codec
-    // -pix_fmt yuv420p: Use widely compatible pixel format
-    // -crf 18: High quality (lower = better, 18-23 is good range)
-    let output = Command::new("ffmpeg")
-        .arg("-y")
-        .arg("-framerate")
-        .arg(fps.to_string())
-        .arg("-i")
-        .arg(&frame_pattern)
-        .arg("-c:v")
-        .arg("libx264")
-        .arg("-pix_fmt")
-        .arg("yuv420p")
-        .arg("-crf")
-        .arg("18")
-        .arg(output_path)
-        .output()
-        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("FFmpeg encoding failed:\n{}", stderr));
+    // Two-pass encodes write their stats to temporary log files
+    let pass_log = options.two_pass.then(two_pass_log_prefix);
+    let result = run_ffmpeg_invocations(&options.ffmpeg_invocations(
+        &frame_pattern,
+        output_path,
+        fps,
+        pass_log.as_deref(),
+    ));
+    if let Some(prefix) = &pass_log {
+        remove_pass_logs(prefix);
     }
+    result?;
 
     log::info!("Video encoded to {:?}", output_path);
     Ok(())
 }
 
-/// Encode frames to video with additional options.
-///
-/// # Arguments
-///
-/// * `frames_dir` - Directory containing frames
-/// * `output_path` - Output video file path
-/// * `fps` - Frame rate
-/// * `options` - Additional encoding options
-pub fn encode_video_with_options(
-    frames_dir: &Path,
-    output_path: &Path,
-    fps: f32,
-    options: &VideoEncodingOptions,
-) -> Result<(), String> {
-    // Check FFmpeg availability
-    match check_ffmpeg() {
-        FfmpegStatus::Available(_) => {}
-        FfmpegStatus::NotFound => {
-            return Err(
-                "FFmpeg not found. Install FFmpeg and ensure it's in your PATH.\n\
-                 See: https://ffmpeg.org/download.html"
-                    .to_string(),
-            );
+/// Run each FFmpeg invocation in turn, stopping at the first failure.
+fn run_ffmpeg_invocations(invocations: &[Vec<OsString>]) -> Result<(), String> {
+    for args in invocations {
+        let output = Command::new("ffmpeg")
+            .args(args)
+            .output()
+            .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("FFmpeg encoding failed:\n{}", stderr));
         }
-        FfmpegStatus::Unknown => {}
     }
+    Ok(())
+}
 
-    let frame_pattern = frames_dir.join("frame_%05d.png");
+/// A unique path prefix in the temp directory for two-pass stats files.
+fn two_pass_log_prefix() -> PathBuf {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!("octoseq-2pass-{}-{}", std::process::id(), nanos))
+}
 
-    if let Some(parent) = output_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+/// Delete the stats files the encoders wrote next to `prefix`
+/// (e.g. `prefix-0.log` and `prefix-0.log.mbtree` for x264).
+fn remove_pass_logs(prefix: &Path) {
+    let (Some(dir), Some(stem)) = (prefix.parent(), prefix.file_name()) else {
+        return;
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let stem = stem.to_string_lossy();
+    for entry in entries.flatten() {
+        if entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with(stem.as_ref())
+        {
+            if let Err(e) = std::fs::remove_file(entry.path()) {
+                log::warn!("Failed to remove two-pass log {:?}: {}", entry.path(), e);
+            }
+        }
     }
+}
 
-    let mut cmd = Command::new("ffmpeg");
-    cmd.arg("-y")
-        .arg("-framerate")
-        .arg(fps.to_string())
-        .arg("-i")
-        .arg(&frame_pattern);
+/// Video codec for encoded output.
+///
+/// H.264, H.265 and VP9 use external libraries that FFmpeg must be built with
+/// (`--enable-libx264`, `--enable-libx265`, `--enable-libvpx`). Most distro and
+/// Homebrew builds include all three; minimal or LGPL-only builds may lack the
+/// x264/x265 encoders. ProRes uses FFmpeg's built-in `prores_ks` encoder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoCodec {
+    /// H.264 via libx264. The most widely compatible choice.
+    #[default]
+    H264,
+    /// H.265/HEVC via libx265. Smaller files at the same quality.
+    H265,
+    /// VP9 via libvpx-vp9, for WebM output.
+    Vp9,
+    /// Apple ProRes 4444 via prores_ks. Near-lossless, for editing and grading.
+    ProRes,
+}
 
-    // Add codec
-    cmd.arg("-c:v").arg(&options.codec);
+impl VideoCodec {
+    /// The FFmpeg encoder name.
+    pub fn encoder(self) -> &'static str {
+        match self {
+            VideoCodec::H264 => "libx264",
+            VideoCodec::H265 => "libx265",
+            VideoCodec::Vp9 => "libvpx-vp9",
+            VideoCodec::ProRes => "prores_ks",
+        }
+    }
 
-    // Add pixel format
-    cmd.arg("-pix_fmt").arg(&options.pixel_format);
+    /// Container extension used for the default video path.
+    pub fn extension(self) -> &'static str {
+        match self {
+            VideoCodec::H264 | VideoCodec::H265 => "mp4",
+            VideoCodec::Vp9 => "webm",
+            VideoCodec::ProRes => "mov",
+        }
+    }
 
-    // Add quality setting
-    match options.codec.as_str() {
-        "libx264" | "libx265" => {
-            cmd.arg("-crf").arg(options.crf.to_string());
+    /// Pixel format used when none is given.
+    pub fn default_pixel_format(self) -> PixelFormat {
+        match self {
+            VideoCodec::ProRes => PixelFormat::Yuv444p,
+            _ => PixelFormat::Yuv420p,
         }
-        "libvpx-vp9" => {
-            cmd.arg("-crf").arg(options.crf.to_string());
-            cmd.arg("-b:v").arg("0"); // Constant quality mode
+    }
+
+    /// Highest accepted CRF value, or None if the codec has no CRF mode.
+    fn max_crf(self) -> Option<u32> {
+        match self {
+            VideoCodec::H264 | VideoCodec::H265 => Some(51),
+            VideoCodec::Vp9 => Some(63),
+            VideoCodec::ProRes => None,
         }
-        _ => {}
     }
+}
 
-    // Add preset if applicable
-    if let Some(ref preset) = options.preset {
-        cmd.arg("-preset").arg(preset);
+impl std::fmt::Display for VideoCodec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VideoCodec::H264 => write!(f, "h264"),
+            VideoCodec::H265 => write!(f, "h265"),
+            VideoCodec::Vp9 => write!(f, "vp9"),
+            VideoCodec::ProRes => write!(f, "prores"),
+        }
     }
+}
+
+impl FromStr for VideoCodec {
+    type Err = String;
 
-    // Add any extra arguments
-    for arg in &options.extra_args {
-        cmd.arg(arg);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "h264" | "avc" => Ok(VideoCodec::H264),
+            "h265" | "hevc" => Ok(VideoCodec::H265),
+            "vp9" => Ok(VideoCodec::Vp9),
+            "prores" => Ok(VideoCodec::ProRes),
+            _ => Err(format!(
+                "Unknown video codec '{}' (expected h264, h265, vp9 or prores)",
+                s
+            )),
+        }
     }
+}
 
-    cmd.arg(output_path);
+/// Chroma subsampling of the encoded video.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PixelFormat {
+    /// 4:2:0, playable everywhere.
+    Yuv420p,
+    /// 4:4:4, full chroma resolution. Needs High 4:4:4 (H.264) or Main 4:4:4
+    /// (H.265) decoder support, which many hardware players lack.
+    Yuv444p,
+}
 
-    let output = cmd
-        .output()
-        .map_err(|e| format!("Failed to run FFmpeg: {}", e))?;
+impl PixelFormat {
+    /// The FFmpeg `-pix_fmt` value for `codec`.
+    fn ffmpeg_name(self, codec: VideoCodec) -> &'static str {
+        match (self, codec) {
+            // ProRes 4444 is a 10-bit format
+            (PixelFormat::Yuv444p, VideoCodec::ProRes) => "yuv444p10le",
+            (PixelFormat::Yuv420p, _) => "yuv420p",
+            (PixelFormat::Yuv444p, _) => "yuv444p",
+        }
+    }
+}
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("FFmpeg encoding failed:\n{}", stderr));
+impl std::fmt::Display for PixelFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PixelFormat::Yuv420p => write!(f, "yuv420p"),
+            PixelFormat::Yuv444p => write!(f, "yuv444p"),
+        }
     }
+}
 
-    Ok(())
+impl FromStr for PixelFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "yuv420p" => Ok(PixelFormat::Yuv420p),
+            "yuv444p" => Ok(PixelFormat::Yuv444p),
+            _ => Err(format!(
+                "Unknown pixel format '{}' (expected yuv420p or yuv444p)",
+                s
+            )),
+        }
+    }
 }
 
 /// Options for video encoding.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
 pub struct VideoEncodingOptions {
-    /// Video codec (default: libx264)
-    pub codec: String,
-    /// Pixel format (default: yuv420p)
-    pub pixel_format: String,
-    /// Constant Rate Factor for quality (default: 18, lower = better)
-    pub crf: u32,
+    /// Video codec (default: h264)
+    pub codec: VideoCodec,
+    /// Pixel format (default: yuv420p, or yuv444p for ProRes)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pixel_format: Option<PixelFormat>,
+    /// Constant Rate Factor for quality (lower = better). Defaults to 18 for
+    /// H.264/H.265 and 31 for VP9 when no bitrate is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub crf: Option<u32>,
+    /// Target bitrate in FFmpeg syntax (e.g. "8M", "2500k"). Exclusive with `crf`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitrate: Option<String>,
+    /// Encode in two passes to hit `bitrate` more accurately. Requires `bitrate`.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub two_pass: bool,
+    /// Move the MP4/MOV index (moov atom) to the front so playback can start
+    /// before the whole file has downloaded.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub faststart: bool,
     /// Encoding preset (e.g., "slow", "medium", "fast")
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub preset: Option<String>,
     /// Additional FFmpeg arguments
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub extra_args: Vec<String>,
 }
 
 impl Default for VideoEncodingOptions {
     fn default() -> Self {
         Self {
-            codec: "libx264".to_string(),
-            pixel_format: "yuv420p".to_string(),
-            crf: 18,
+            codec: VideoCodec::H264,
+            pixel_format: None,
+            crf: None,
+            bitrate: None,
+            two_pass: false,
+            faststart: false,
             preset: None,
             extra_args: Vec::new(),
         }
     }
 }
 
+impl VideoEncodingOptions {
+    /// The pixel format that will be used: the explicit one or the codec default.
+    pub fn effective_pixel_format(&self) -> PixelFormat {
+        self.pixel_format
+            .unwrap_or_else(|| self.codec.default_pixel_format())
+    }
+
+    /// Check for option combinations the codec can't encode.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.crf.is_some() && self.bitrate.is_some() {
+            return Err("Set either a CRF (--crf) or a bitrate (--bitrate), not both".to_string());
+        }
+        if let Some(crf) = self.crf {
+            match self.codec.max_crf() {
+                Some(max) if crf > max => {
+                    return Err(format!(
+                        "CRF {} is out of range for {} (0-{})",
+                        crf, self.codec, max
+                    ));
+                }
+                None => {
+                    return Err(format!(
+                        "{} has no CRF mode; quality is set by the ProRes profile",
+                        self.codec
+                    ));
+                }
+                _ => {}
+            }
+        }
+        if let Some(bitrate) = &self.bitrate {
+            if self.codec == VideoCodec::ProRes {
+                return Err(format!(
+                    "{} does not take a target bitrate; quality is set by the ProRes profile",
+                    self.codec
+                ));
+            }
+            if !is_valid_bitrate(bitrate) {
+                return Err(format!(
+                    "Invalid bitrate '{}' (expected a number with an optional k, M or G suffix, e.g. 8M)",
+                    bitrate
+                ));
+            }
+        }
+        if self.codec == VideoCodec::ProRes && self.pixel_format == Some(PixelFormat::Yuv420p) {
+            return Err("ProRes does not support yuv420p; use yuv444p".to_string());
+        }
+        if self.two_pass && self.bitrate.is_none() {
+            return Err(
+                "Two-pass encoding (--two-pass) needs a target bitrate (--bitrate)".to_string(),
+            );
+        }
+        if self.faststart && self.codec.extension() == "webm" {
+            return Err(format!(
+                "Faststart only applies to MP4/MOV output; {} writes WebM",
+                self.codec
+            ));
+        }
+        Ok(())
+    }
+
+    /// FFmpeg output arguments for these options (everything between the input and output path).
+    pub fn ffmpeg_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "-c:v".to_string(),
+            self.codec.encoder().to_string(),
+            "-pix_fmt".to_string(),
+            self.effective_pixel_format()
+                .ffmpeg_name(self.codec)
+                .to_string(),
+        ];
+
+        match (self.codec, &self.bitrate, self.crf) {
+            (VideoCodec::ProRes, _, _) => {
+                // Profile 4 is ProRes 4444
+                args.extend(["-profile:v".to_string(), "4".to_string()]);
+            }
+            (_, Some(bitrate), _) => {
+                args.extend(["-b:v".to_string(), bitrate.clone()]);
+            }
+            (VideoCodec::Vp9, None, crf) => {
+                // -b:v 0 selects constant quality mode
+                args.extend([
+                    "-crf".to_string(),
+                    crf.unwrap_or(31).to_string(),
+                    "-b:v".to_string(),
+                    "0".to_string(),
+                ]);
+            }
+            (VideoCodec::H264 | VideoCodec::H265, None, crf) => {
+                args.extend(["-crf".to_string(), crf.unwrap_or(18).to_string()]);
+            }
+        }
+
+        if let Some(preset) = &self.preset {
+            args.extend(["-preset".to_string(), preset.clone()]);
+        }
+        if self.faststart {
+            args.extend(["-movflags".to_string(), "+faststart".to_string()]);
+        }
+        args.extend(self.extra_args.iter().cloned());
+        args
+    }
+
+    /// The full FFmpeg argument lists to run, in order: one for a single-pass
+    /// encode, or an analysis pass (discarding its output) plus the final pass
+    /// when `pass_log` is given. `pass_log` is the path prefix for the stats files.
+    pub fn ffmpeg_invocations(
+        &self,
+        frame_pattern: &Path,
+        output_path: &Path,
+        fps: f32,
+        pass_log: Option<&Path>,
+    ) -> Vec<Vec<OsString>> {
+        // -y: Overwrite output without asking
+        // -framerate: Input frame rate
+        // -i: Input pattern
+        let input: Vec<OsString> = vec![
+            "-y".into(),
+            "-framerate".into(),
+            fps.to_string().into(),
+            "-i".into(),
+            frame_pattern.into(),
+        ];
+        let encode = |extra: Vec<OsString>, output: OsString| {
+            let mut args = input.clone();
+            args.extend(self.ffmpeg_args().into_iter().map(OsString::from));
+            args.extend(extra);
+            args.push(output);
+            args
+        };
+
+        let Some(pass_log) = pass_log else {
+            return vec![encode(Vec::new(), output_path.into())];
+        };
+
+        let null_output = if cfg!(windows) { "NUL" } else { "/dev/null" };
+        vec![
+            encode(
+                [
+                    self.pass_args(1, pass_log),
+                    vec!["-an".into(), "-f".into(), "null".into()],
+                ]
+                .concat(),
+                null_output.into(),
+            ),
+            encode(self.pass_args(2, pass_log), output_path.into()),
+        ]
+    }
+
+    /// Arguments selecting pass `pass` of a two-pass encode.
+    fn pass_args(&self, pass: u8, pass_log: &Path) -> Vec<OsString> {
+        match self.codec {
+            // libx265 takes its pass settings through its own parameter string
+            VideoCodec::H265 => {
+                let mut params = OsString::from(format!("pass={}:stats=", pass));
+                params.push(pass_log);
+                params.push(".log");
+                vec!["-x265-params".into(), params]
+            }
+            _ => vec![
+                "-pass".into(),
+                pass.to_string().into(),
+                "-passlogfile".into(),
+                pass_log.into(),
+            ],
+        }
+    }
+}
+
+/// Whether `bitrate` looks like an FFmpeg bitrate: digits with an optional k/M/G suffix.
+fn is_valid_bitrate(bitrate: &str) -> bool {
+    let digits = bitrate.trim_end_matches(['k', 'K', 'm', 'M', 'g', 'G']);
+    bitrate.len() - digits.len() <= 1
+        && !digits.is_empty()
+        && digits.parse::<f64>().is_ok_and(|value| value > 0.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,8 +559,166 @@ mod tests {
     #[test]
     fn test_video_encoding_options_default() {
         let opts = VideoEncodingOptions::default();
-        assert_eq!(opts.codec, "libx264");
-        assert_eq!(opts.pixel_format, "yuv420p");
-        assert_eq!(opts.crf, 18);
+        assert_eq!(opts.codec, VideoCodec::H264);
+        assert_eq!(opts.effective_pixel_format(), PixelFormat::Yuv420p);
+        assert_eq!(
+            opts.ffmpeg_args(),
+            ["-c:v", "libx264", "-pix_fmt", "yuv420p", "-crf", "18"]
+        );
+    }
+
+    #[test]
+    fn test_ffmpeg_invocations_single_pass_with_faststart() {
+        let options = VideoEncodingOptions {
+            crf: Some(23),
+            faststart: true,
+            ..Default::default()
+        };
+        let invocations = options.ffmpeg_invocations(
+            Path::new("frames/frame_%05d.png"),
+            Path::new("out/render.mp4"),
+            30.0,
+            None,
+        );
+        assert_eq!(
+            invocations,
+            [[
+                "-y",
+                "-framerate",
+                "30",
+                "-i",
+                "frames/frame_%05d.png",
+                "-c:v",
+                "libx264",
+                "-pix_fmt",
+                "yuv420p",
+                "-crf",
+                "23",
+                "-movflags",
+                "+faststart",
+                "out/render.mp4",
+            ]
+            .map(OsString::from)]
+        );
+    }
+
+    #[test]
+    fn test_ffmpeg_invocations_two_pass() {
+        let options = VideoEncodingOptions {
+            bitrate: Some("8M".to_string()),
+            two_pass: true,
+            ..Default::default()
+        };
+        assert!(options.validate().is_ok());
+
+        let invocations = options.ffmpeg_invocations(
+            Path::new("frame_%05d.png"),
+            Path::new("render.mp4"),
+            60.0,
+            Some(Path::new("/tmp/log")),
+        );
+        assert_eq!(invocations.len(), 2);
+
+        let first: Vec<_> = invocations[0].iter().map(|a| a.to_str().unwrap()).collect();
+        assert!(first.windows(2).any(|w| w == ["-b:v", "8M"]));
+        assert!(first
+            .windows(4)
+            .any(|w| w == ["-pass", "1", "-passlogfile", "/tmp/log"]));
+        assert!(first.ends_with(&["-an", "-f", "null", "/dev/null"]) || cfg!(windows));
+
+        let second: Vec<_> = invocations[1].iter().map(|a| a.to_str().unwrap()).collect();
+        assert!(second
+            .windows(4)
+            .any(|w| w == ["-pass", "2", "-passlogfile", "/tmp/log"]));
+        assert_eq!(second.last(), Some(&"render.mp4"));
+
+        let h265 = VideoEncodingOptions {
+            codec: VideoCodec::H265,
+            ..options
+        };
+        let invocations = h265.ffmpeg_invocations(
+            Path::new("frame_%05d.png"),
+            Path::new("render.mp4"),
+            60.0,
+            Some(Path::new("/tmp/log")),
+        );
+        assert!(invocations[1]
+            .windows(2)
+            .any(|w| w == ["-x265-params", "pass=2:stats=/tmp/log.log"]));
+    }
+
+    #[test]
+    fn test_remove_pass_logs_only_removes_matching_files() {
+        let dir = std::env::temp_dir().join(format!("octoseq-pass-logs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["enc-0.log", "enc-0.log.mbtree", "other.txt"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        remove_pass_logs(&dir.join("enc"));
+
+        assert!(!dir.join("enc-0.log").exists());
+        assert!(!dir.join("enc-0.log.mbtree").exists());
+        assert!(dir.join("other.txt").exists());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_video_encoding_options_reject_unsupported_combinations() {
+        let prores = VideoEncodingOptions {
+            codec: VideoCodec::ProRes,
+            ..Default::default()
+        };
+        assert!(prores.validate().is_ok());
+        assert_eq!(
+            prores.ffmpeg_args(),
+            [
+                "-c:v",
+                "prores_ks",
+                "-pix_fmt",
+                "yuv444p10le",
+                "-profile:v",
+                "4"
+            ]
+        );
+
+        for invalid in [
+            VideoEncodingOptions {
+                crf: Some(18),
+                ..prores.clone()
+            },
+            VideoEncodingOptions {
+                pixel_format: Some(PixelFormat::Yuv420p),
+                ..prores.clone()
+            },
+            VideoEncodingOptions {
+                crf: Some(52),
+                ..Default::default()
+            },
+            VideoEncodingOptions {
+                crf: Some(20),
+                bitrate: Some("8M".to_string()),
+                ..Default::default()
+            },
+            VideoEncodingOptions {
+                bitrate: Some("fast".to_string()),
+                ..Default::default()
+            },
+            VideoEncodingOptions {
+                two_pass: true,
+                ..Default::default()
+            },
+            VideoEncodingOptions {
+                codec: VideoCodec::Vp9,
+                faststart: true,
+                ..Default::default()
+            },
+        ] {
+            assert!(
+                invalid.validate().is_err(),
+                "{:?} should be rejected",
+                invalid
+            );
+        }
     }
 }
@@ -5,15 +5,26 @@
 //! - Input signal processing
 //! - Frame updates
 
+use crate::axis_gizmo::{axis_segments, AxisSegment};
+use crate::background::SceneBackground;
 use crate::debug_markers::DebugMarkerLayer;
+use crate::error::VisualiserError;
 use crate::feedback::FeedbackConfig;
-use crate::input::{BandSignalMap, SharedSignal, SignalMap};
+use crate::fog::SceneFog;
+use crate::input::{BandSignalMap, InputSignal, SharedSignal, SignalMap};
 use crate::mesh_asset::MeshAssetRegistry;
 use crate::musical_time::MusicalTimeStructure;
-use crate::scene_graph::{EntityId, SceneGraph};
-use crate::script_diagnostics::ScriptDiagnostic;
-use crate::scripting::{get_script_debug_options, reset_script_debug_options, ScriptEngine};
+use crate::reference_grid::ReferenceGridOptions;
+use crate::scene_graph::{EntityId, MeshType, SceneGraph};
+use crate::script_diagnostics::{ScriptDiagnostic, ScriptSeverity};
+use crate::scripting::{
+    get_script_debug_options, reset_script_debug_options, take_replay_request, SandboxConfig,
+    SceneSnapshot, ScriptEngine,
+};
+use crate::signal::stem_band_feature;
 use crate::signal_explorer::{ScriptSignalInfo, SignalChainAnalysis};
+use crate::spectrogram::{spectrum_rows, Spectrogram, MIN_SPECTRUM_SAMPLE_RATE};
+use crate::sprite_asset::SpriteAssetRegistry;
 use std::collections::{HashMap, HashSet};
 
 /// Frame budget for limiting processing time in web preview.
@@ -65,6 +76,47 @@ pub struct DebugOptions {
     pub isolated_entity: Option<EntityId>,
     /// Per-entity debug bounding box toggles (via dbg.showBounds()).
     pub debug_bounds_entities: HashSet<u64>,
+    /// Show the full-track waveform overlay (via dbg.showWaveform()).
+    pub show_waveform: bool,
+    /// Only run this post effect from the chain (via dbg.soloEffect()).
+    pub solo_effect: Option<String>,
+    /// Draw an axis gizmo at the world origin (via dbg.showAxes(true)).
+    pub show_axes: bool,
+    /// Entities with an axis gizmo (via dbg.showAxes(entity)).
+    pub axes_entities: HashSet<u64>,
+}
+
+/// Seconds of playback replayed (without rendering) before the landing
+/// point of a seek or loop wrap.
+pub const SEEK_WARM_UP_SECS: f32 = 1.0;
+
+/// A playback range that time wraps around (loop/repeat mode).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LoopRange {
+    /// Loop start in seconds.
+    pub start: f32,
+    /// Loop end in seconds (exclusive).
+    pub end: f32,
+}
+
+impl LoopRange {
+    /// Create a loop range.
+    ///
+    /// An `end` past the track length is clamped to the track length, so the
+    /// loop wraps where the audio runs out rather than over silence.
+    pub fn new(start: f32, end: f32, track_duration: Option<f32>) -> Result<Self, String> {
+        let end = match track_duration {
+            Some(duration) => end.min(duration),
+            None => end,
+        };
+        if start < 0.0 || start >= end {
+            return Err(format!(
+                "Loop start ({:.3}s) must be non-negative and before loop end ({:.3}s)",
+                start, end
+            ));
+        }
+        Ok(Self { start, end })
+    }
 }
 
 pub struct VisualiserConfig {
@@ -87,13 +139,18 @@ impl Default for VisualiserConfig {
 
 pub struct VisualiserState {
     pub time: f32,
+    /// Frame delta of the last update. This is the authoritative frame time for
+    /// rendering (1/fps in offline renders); don't assume 60fps.
+    pub dt: f32,
     pub config: VisualiserConfig,
     /// Script engine manages scripts and the scene graph
     script_engine: ScriptEngine,
     /// Debug visualization options
     pub debug_options: DebugOptions,
-    /// Mesh asset registry for loaded OBJ meshes
+    /// Mesh asset registry for loaded OBJ and glTF meshes
     pub asset_registry: MeshAssetRegistry,
+    /// Sprite texture registry for textured billboard particles
+    pub sprite_registry: SpriteAssetRegistry,
     /// Debug marker layer for event visualization
     debug_marker_layer: DebugMarkerLayer,
     /// Current BPM for beat-based calculations
@@ -105,21 +162,63 @@ pub struct VisualiserState {
     stem_signals: BandSignalMap,
     /// Composed signals (name -> Rc<InputSignal>)
     composed_signals: SignalMap,
+    /// Active loop range, if looping is enabled.
+    loop_range: Option<LoopRange>,
+    /// Incremented on every seek (including loop wraps). The renderer clears
+    /// frame feedback when this changes.
+    seek_generation: u64,
+    /// Set by `seek`; the next update replays a pre-roll before rendering.
+    warm_up_pending: bool,
+    /// Global cap on rendered particle instances across all systems.
+    particle_budget: Option<usize>,
+    /// Track audio for the waveform overlay.
+    waveform_audio: Option<SharedSignal>,
+    /// Incremented whenever `waveform_audio` changes, so the renderer knows
+    /// to rebuild its cached envelope.
+    waveform_generation: u64,
+    /// Show the waveform overlay regardless of `dbg.showWaveform()` (CLI
+    /// `--show-waveform`).
+    always_show_waveform: bool,
+    /// Raw track audio analysed by the spectrogram and `scene.spectrumBars()`.
+    spectrum_audio: Option<SharedSignal>,
+    /// Whether the "no raw audio for the spectrum" warning has been logged.
+    warned_no_spectrum_audio: bool,
+    /// Scrolling spectrogram of the track audio (via dbg.showSpectrogram()).
+    spectrogram: Option<Spectrogram>,
+    /// Exposure multiplier applied on top of `scene.exposure` (CLI `--exposure`).
+    exposure: f32,
+    /// Whether `dbg.showGrid()` is drawn. Offline renders leave the grid out
+    /// unless `--show-grid` is passed.
+    reference_grid_allowed: bool,
 }
 
 impl VisualiserState {
     pub fn new() -> Self {
         Self {
             time: 0.0,
+            dt: 0.0,
             config: VisualiserConfig::default(),
             script_engine: ScriptEngine::new(),
             debug_options: DebugOptions::default(),
             asset_registry: MeshAssetRegistry::new(),
+            sprite_registry: SpriteAssetRegistry::new(),
             debug_marker_layer: DebugMarkerLayer::new(),
             current_bpm: 120.0,
             global_seed: 0,
             stem_signals: HashMap::new(),
             composed_signals: HashMap::new(),
+            loop_range: None,
+            seek_generation: 0,
+            warm_up_pending: false,
+            particle_budget: None,
+            waveform_audio: None,
+            waveform_generation: 0,
+            always_show_waveform: false,
+            spectrum_audio: None,
+            warned_no_spectrum_audio: false,
+            spectrogram: None,
+            exposure: 1.0,
+            reference_grid_allowed: true,
         }
     }
 
@@ -142,6 +241,17 @@ impl VisualiserState {
         self.asset_registry.register_from_obj(asset_id, obj_content)
     }
 
+    /// Register a mesh asset from glTF (GLB or embedded JSON) content.
+    /// Returns Ok(()) if successful, Err(message) if parsing failed.
+    pub fn register_mesh_asset_gltf(
+        &mut self,
+        asset_id: &str,
+        gltf_content: &[u8],
+    ) -> Result<(), String> {
+        self.asset_registry
+            .register_from_gltf(asset_id, gltf_content)
+    }
+
     /// Unregister a mesh asset.
     pub fn unregister_mesh_asset(&mut self, asset_id: &str) -> bool {
         self.asset_registry.unregister(asset_id)
@@ -152,11 +262,65 @@ impl VisualiserState {
         self.asset_registry.asset_ids()
     }
 
+    /// Register a sprite texture from raw RGBA8 bytes.
+    /// Returns Ok(()) if successful, Err(message) if the size doesn't match the data.
+    pub fn register_sprite_rgba(
+        &mut self,
+        sprite_id: &str,
+        width: u32,
+        height: u32,
+        rgba: Vec<u8>,
+    ) -> Result<(), String> {
+        self.sprite_registry
+            .register_rgba(sprite_id, width, height, rgba)
+    }
+
+    /// Load sprites referenced by particle systems and the skybox background
+    /// from disk (relative to the sprite registry's search directory) if they
+    /// aren't registered yet.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_sprites(&mut self) {
+        let skybox = match &self.script_engine.scene_background {
+            Some(SceneBackground::Skybox { asset_id }) => Some(asset_id.as_str()),
+            _ => None,
+        };
+        let ids: Vec<&str> = self
+            .script_engine
+            .particle_systems
+            .values()
+            .filter_map(|system| system.config.sprite.as_ref())
+            .map(|sprite| sprite.asset_id.as_str())
+            .chain(skybox)
+            .collect();
+        self.sprite_registry.load_missing(ids);
+    }
+
+    /// Start loading mesh assets referenced by `mesh.load()` if they aren't
+    /// registered yet: from disk (relative to the asset registry's search
+    /// directory) natively, or by URL in the browser.
+    fn load_mesh_assets(&mut self) {
+        let ids: Vec<&str> = self
+            .script_engine
+            .scene_graph
+            .meshes()
+            .filter_map(|(_, mesh)| match &mesh.mesh_type {
+                MeshType::Asset(asset_id) => Some(asset_id.as_str()),
+                _ => None,
+            })
+            .collect();
+        self.asset_registry.load_missing(ids);
+    }
+
     /// Load a Rhai script. Returns true if successful.
     pub fn load_script(&mut self, script: &str) -> bool {
+        self.try_load_script(script).is_ok()
+    }
+
+    /// Load a Rhai script, returning a `ScriptCompile` or `ScriptRuntime` error on failure.
+    pub fn try_load_script(&mut self, script: &str) -> Result<(), VisualiserError> {
         reset_script_debug_options();
         self.debug_options = DebugOptions::default();
-        self.script_engine.load_script(script)
+        self.script_engine.try_load_script(script)
     }
 
     /// Configure which input signal names should be available in the global `inputs` Signal namespace.
@@ -189,6 +353,25 @@ impl VisualiserState {
         self.script_engine.set_available_composed_signals(signals);
     }
 
+    /// The script sandbox limits in effect.
+    pub fn sandbox_config(&self) -> SandboxConfig {
+        self.script_engine.sandbox_config()
+    }
+
+    /// Replace the script sandbox limits (operations per call, call depth,
+    /// data sizes). Call before `load_script()` so init() is covered too.
+    pub fn set_sandbox_config(&mut self, config: SandboxConfig) {
+        self.script_engine.set_sandbox_config(config);
+    }
+
+    /// Configure which MIDI notes, as (channel, note) pairs, are available in
+    /// the global `inputs.midi` namespace. Their velocity signals are read from
+    /// the composed signals named `midi:ch<channel>:<note>`. This must be
+    /// called before `load_script()`.
+    pub fn set_available_midi_notes(&mut self, notes: Vec<(u8, u8)>) {
+        self.script_engine.set_available_midi_notes(notes);
+    }
+
     /// Push a stem-scoped signal.
     /// Stores under both stem_id and label for dual-access support.
     /// Takes SharedSignal (Rc<InputSignal>) for cheap cloning when storing under multiple keys.
@@ -214,6 +397,34 @@ impl VisualiserState {
         }
     }
 
+    /// Push a stem-scoped band signal (one band's feature analysed on one stem).
+    /// Stored in the stem's signals under [`stem_band_feature`], keyed by both
+    /// band id and band label, and by both stem id and stem label.
+    pub fn push_stem_band_signal(
+        &mut self,
+        stem_id: &str,
+        stem_label: &str,
+        band_id: &str,
+        band_label: &str,
+        feature: &str,
+        signal: SharedSignal,
+    ) {
+        self.push_stem_signal(
+            stem_id,
+            stem_label,
+            &stem_band_feature(band_id, feature),
+            std::rc::Rc::clone(&signal),
+        );
+        if band_label != band_id {
+            self.push_stem_signal(
+                stem_id,
+                stem_label,
+                &stem_band_feature(band_label, feature),
+                signal,
+            );
+        }
+    }
+
     /// Clear all stem signals.
     pub fn clear_stem_signals(&mut self) {
         self.stem_signals.clear();
@@ -251,6 +462,15 @@ impl VisualiserState {
         self.script_engine.take_diagnostics()
     }
 
+    /// Like [`Self::take_script_diagnostics`], keeping only diagnostics at or
+    /// above `min_severity`.
+    pub fn take_script_diagnostics_at_least(
+        &mut self,
+        min_severity: ScriptSeverity,
+    ) -> Vec<ScriptDiagnostic> {
+        self.script_engine.take_diagnostics_at_least(min_severity)
+    }
+
     /// Get a reference to the scene graph for rendering.
     pub fn scene_graph(&self) -> &SceneGraph {
         &self.script_engine.scene_graph
@@ -281,6 +501,36 @@ impl VisualiserState {
         &self.script_engine.camera_uniforms
     }
 
+    /// Set the exposure multiplier applied on top of the script's `scene.exposure`.
+    pub fn set_exposure(&mut self, exposure: f32) {
+        self.exposure = exposure;
+    }
+
+    /// Exposure applied before tonemapping: the CLI exposure times `scene.exposure`.
+    pub fn exposure(&self) -> f32 {
+        self.exposure * self.script_engine.scene_exposure
+    }
+
+    /// Get the tonemapping curve set via `scene.tonemap`.
+    pub fn scene_tonemap(&self) -> crate::scene_graph::SceneTonemap {
+        self.script_engine.scene_tonemap
+    }
+
+    /// Get the scene-wide blend mode set via `scene.blendMode`.
+    pub fn scene_blend_mode(&self) -> crate::scene_graph::SceneBlendMode {
+        self.script_engine.scene_blend_mode
+    }
+
+    /// Get the background set via `scene.background`, if any.
+    pub fn scene_background(&self) -> Option<&SceneBackground> {
+        self.script_engine.scene_background.as_ref()
+    }
+
+    /// Get the distance fog set via `scene.fog()`, if any.
+    pub fn scene_fog(&self) -> Option<&SceneFog> {
+        self.script_engine.scene_fog.as_ref()
+    }
+
     /// Get the evaluated lighting uniforms (signals resolved to f32 for renderer).
     pub fn lighting_uniforms(&self) -> &crate::lighting::LightingUniforms {
         &self.script_engine.lighting_uniforms
@@ -291,6 +541,23 @@ impl VisualiserState {
         &self.script_engine.post_chain
     }
 
+    /// Get the debug plots registered via `dbg.plot()`.
+    pub fn debug_plots(&self) -> &[crate::sparkline::DebugPlot] {
+        &self.script_engine.debug_plots
+    }
+
+    /// Freeze the current scene, post chain, feedback and particles as a
+    /// snapshot for A/B comparison.
+    pub fn capture_snapshot(&self) -> SceneSnapshot {
+        self.script_engine.capture_snapshot()
+    }
+
+    /// Restore a snapshot. Render without calling [`Self::update`] to
+    /// compare against it; the next update runs the script as usual.
+    pub fn restore_snapshot(&mut self, snapshot: &SceneSnapshot) {
+        self.script_engine.restore_snapshot(snapshot);
+    }
+
     /// Get a reference to the particle systems for rendering.
     pub fn particle_systems(
         &self,
@@ -310,9 +577,11 @@ impl VisualiserState {
 
     pub fn reset(&mut self) {
         self.time = 0.0;
+        self.dt = 0.0;
         self.script_engine = ScriptEngine::new();
         self.debug_options = DebugOptions::default();
         self.asset_registry.clear();
+        self.sprite_registry.clear();
         self.debug_marker_layer.clear();
         reset_script_debug_options();
         // Preserve global_seed across reset - if user set it, they want it to persist
@@ -323,24 +592,53 @@ impl VisualiserState {
         self.time = time;
     }
 
-    /// Set debug visualization options.
-    pub fn set_debug_options(&mut self, wireframe: bool, bounding_boxes: bool) {
-        self.debug_options.wireframe = wireframe;
-        self.debug_options.bounding_boxes = bounding_boxes;
+    /// Jump playback to `time`.
+    ///
+    /// Unlike `set_time`, this treats the jump as a discontinuity: stateful
+    /// signals and particles are reset, the next update replays up to
+    /// [`SEEK_WARM_UP_SECS`] of playback before the new position so they
+    /// settle as if it had been reached cleanly, and the renderer clears
+    /// frame feedback. The replayed frames add no `dbg.plot` points or script logs.
+    pub fn seek(&mut self, time: f32) {
+        self.time = time;
+        self.script_engine.reset_for_seek();
+        self.seek_generation += 1;
+        self.warm_up_pending = true;
     }
 
-    /// Isolate a single entity for rendering (useful for debugging).
-    pub fn isolate_entity(&mut self, entity_id: u64) {
-        self.debug_options.isolated_entity = Some(EntityId(entity_id));
+    /// Get the seek counter (see `seek`).
+    pub fn seek_generation(&self) -> u64 {
+        self.seek_generation
     }
 
-    /// Clear entity isolation, resume normal rendering.
-    pub fn clear_isolation(&mut self) {
-        self.debug_options.isolated_entity = None;
+    /// Loop playback over a range. Pass `None` to disable looping.
+    pub fn set_loop(&mut self, range: Option<LoopRange>) {
+        self.loop_range = range;
     }
 
-    /// Update the visualiser state for one frame.
-    pub fn update(
+    /// Get the active loop range.
+    pub fn loop_range(&self) -> Option<LoopRange> {
+        self.loop_range
+    }
+
+    /// Advance the clock by `dt`, wrapping to the loop start at the loop end.
+    /// Time past the loop end carries over into the next pass.
+    fn advance_time(&mut self, dt: f32) {
+        self.dt = dt;
+        self.time += dt;
+        if let Some(range) = self.loop_range {
+            if self.time >= range.end {
+                let length = range.end - range.start;
+                self.seek(range.start + (self.time - range.end) % length);
+            }
+        }
+    }
+
+    /// After a seek, run the script over the frames leading up to the current
+    /// time (without rendering) so smoothing, envelopes and particles carry
+    /// the state they would have had from normal playback.
+    #[allow(clippy::too_many_arguments)]
+    fn warm_up_after_seek(
         &mut self,
         dt: f32,
         rotation_signal: Option<&SharedSignal>,
@@ -350,56 +648,261 @@ impl VisualiserState {
         custom_signals: &SignalMap,
         musical_time: Option<&MusicalTimeStructure>,
     ) {
-        self.time += dt;
-
-        // Sample input signals
-        let amplitude = if let Some(sig) = rotation_signal {
-            let raw = sig.sample_window(self.time, dt);
-            if self.config.sigmoid_k > 0.0 {
-                sig.apply_sigmoid(raw, self.config.sigmoid_k)
-            } else {
-                raw
-            }
-        } else {
-            0.0
-        };
+        if !std::mem::take(&mut self.warm_up_pending) || dt <= 0.0 {
+            return;
+        }
+        let target = self.time;
+        let frames = (SEEK_WARM_UP_SECS.min(target.max(0.0)) / dt) as usize;
+        self.script_engine.set_warming_up(true);
+        for frame in (1..=frames).rev() {
+            self.time = target - frame as f32 * dt;
+            let sampled_signals =
+                self.sample_inputs(dt, rotation_signal, zoom_signal, named_signals);
+            self.script_engine.update(
+                self.time,
+                dt,
+                &sampled_signals,
+                named_signals,
+                band_signals,
+                &self.stem_signals,
+                custom_signals,
+                &self.composed_signals,
+                musical_time,
+            );
+        }
+        self.script_engine.set_warming_up(false);
+        self.time = target;
+    }
 
-        let flux = if let Some(sig) = zoom_signal {
+    /// Sample the input signals at the current time for the script's `frame` map.
+    fn sample_inputs(
+        &self,
+        dt: f32,
+        rotation_signal: Option<&SharedSignal>,
+        zoom_signal: Option<&SharedSignal>,
+        named_signals: &SignalMap,
+    ) -> HashMap<String, f32> {
+        let shape = |sig: &InputSignal| {
             let raw = sig.sample_window(self.time, dt);
             if self.config.sigmoid_k > 0.0 {
                 sig.apply_sigmoid(raw, self.config.sigmoid_k)
             } else {
                 raw
             }
-        } else {
-            0.0
         };
 
-        // Build signals map for script
-        let mut sampled_signals: HashMap<String, f32> = HashMap::new();
-
         // Sample all named signals
-        for (name, signal) in named_signals {
-            let raw = signal.sample_window(self.time, dt);
-            let val = if self.config.sigmoid_k > 0.0 {
-                signal.apply_sigmoid(raw, self.config.sigmoid_k)
-            } else {
-                raw
-            };
-            sampled_signals.insert(name.clone(), val);
-        }
+        let mut sampled_signals: HashMap<String, f32> = named_signals
+            .iter()
+            .map(|(name, signal)| (name.clone(), shape(signal)))
+            .collect();
 
-        // Add core signals (don't overwrite if already present from named_signals)
+        // Add core signals. The legacy rotation/zoom signals only fill
+        // amplitude/flux when named_signals doesn't already provide them.
         sampled_signals.insert("time".to_string(), self.time);
         sampled_signals.insert("dt".to_string(), dt);
-        // Only use legacy rotation_signal for amplitude if not already in named_signals
         if !sampled_signals.contains_key("amplitude") {
-            sampled_signals.insert("amplitude".to_string(), amplitude);
+            sampled_signals.insert(
+                "amplitude".to_string(),
+                rotation_signal.map(|sig| shape(sig)).unwrap_or(0.0),
+            );
         }
-        // Only use legacy zoom_signal for flux if not already in named_signals
         if !sampled_signals.contains_key("flux") {
-            sampled_signals.insert("flux".to_string(), flux);
+            sampled_signals.insert(
+                "flux".to_string(),
+                zoom_signal.map(|sig| shape(sig)).unwrap_or(0.0),
+            );
+        }
+        sampled_signals
+    }
+
+    /// Cap the total number of particle instances rendered per frame. When systems
+    /// want more than this in total, each is scaled down proportionally.
+    /// `None` removes the cap.
+    pub fn set_particle_budget(&mut self, budget: Option<usize>) {
+        self.particle_budget = budget;
+    }
+
+    /// Get the global particle instance budget.
+    pub fn particle_budget(&self) -> Option<usize> {
+        self.particle_budget
+    }
+
+    /// Set the track audio drawn by the waveform overlay.
+    pub fn set_waveform_audio(&mut self, audio: Option<SharedSignal>) {
+        self.waveform_audio = audio;
+        self.waveform_generation += 1;
+    }
+
+    /// Set the raw track audio (PCM) analysed by `dbg.showSpectrogram()` and
+    /// `scene.spectrumBars()`.
+    ///
+    /// Signals below [`MIN_SPECTRUM_SAMPLE_RATE`], such as a package's
+    /// amplitude envelope, are not used: both then stay silent and a warning
+    /// is logged the first time a script asks for a spectrum.
+    pub fn set_spectrum_audio(&mut self, audio: Option<SharedSignal>) {
+        self.spectrum_audio = audio;
+        self.warned_no_spectrum_audio = false;
+    }
+
+    /// Show the waveform overlay even if the script doesn't call `dbg.showWaveform()`.
+    pub fn set_always_show_waveform(&mut self, enabled: bool) {
+        self.always_show_waveform = enabled;
+    }
+
+    /// Whether the script called `dbg.captureReplay()` since the last call.
+    /// Hosts with an instant-replay buffer export it when this returns true.
+    pub fn take_replay_request(&mut self) -> bool {
+        take_replay_request()
+    }
+
+    /// Track audio to draw as the waveform overlay this frame, with its
+    /// generation, or None when the overlay is hidden or there is no audio.
+    pub fn waveform_overlay(&self) -> Option<(&InputSignal, u64)> {
+        if !(self.debug_options.show_waveform || self.always_show_waveform) {
+            return None;
+        }
+        self.waveform_audio
+            .as_deref()
+            .map(|audio| (audio, self.waveform_generation))
+    }
+
+    /// Allow or suppress the `dbg.showGrid()` reference grid.
+    pub fn set_reference_grid_allowed(&mut self, allowed: bool) {
+        self.reference_grid_allowed = allowed;
+    }
+
+    /// The reference grid to draw over the scene, if the script enabled one
+    /// and it isn't suppressed.
+    pub fn reference_grid(&self) -> Option<ReferenceGridOptions> {
+        self.script_engine
+            .reference_grid
+            .filter(|_| self.reference_grid_allowed)
+    }
+
+    /// World-space axis gizmo segments to draw: the origin gizmo first, then
+    /// one per `dbg.showAxes(entity)` entity (by ID) that still exists.
+    pub fn axis_gizmo_segments(&self) -> Vec<AxisSegment> {
+        let scene_graph = &self.script_engine.scene_graph;
+        let mut entities: Vec<u64> = self
+            .debug_options
+            .axes_entities
+            .iter()
+            .copied()
+            .filter(|id| scene_graph.get(EntityId(*id)).is_some())
+            .collect();
+        entities.sort_unstable();
+
+        let origin = self.debug_options.show_axes.then_some(glam::Mat4::IDENTITY);
+        origin
+            .into_iter()
+            .chain(
+                entities
+                    .into_iter()
+                    .map(|id| scene_graph.world_matrix(EntityId(id))),
+            )
+            .flat_map(axis_segments)
+            .collect()
+    }
+
+    /// Get the spectrogram overlay, if the script enabled one.
+    pub fn spectrogram(&self) -> Option<&Spectrogram> {
+        self.spectrogram.as_ref()
+    }
+
+    /// Append this frame's spectrum column while `dbg.showSpectrogram()` is on.
+    fn update_spectrogram(&mut self) {
+        let Some(options) = self.script_engine.spectrogram_options else {
+            self.spectrogram = None;
+            return;
+        };
+        let audio = self.spectrum_audio();
+        let spectrogram = self
+            .spectrogram
+            .get_or_insert_with(|| Spectrogram::new(options));
+        spectrogram.options = options;
+        spectrogram.push_from_audio(audio.as_deref(), self.time);
+    }
+
+    /// Analyse the track audio for `scene.spectrumBars()`, at the resolution
+    /// the bars asked for on the last sync.
+    fn update_spectrum_bars(&mut self) {
+        let bins = self.script_engine.spectrum_bins;
+        if bins == 0 {
+            self.script_engine.spectrum = Vec::new();
+            return;
+        }
+        self.script_engine.spectrum = match self.spectrum_audio() {
+            Some(audio) => spectrum_rows(audio.samples(), audio.sample_rate(), self.time, bins),
+            None => Vec::new(),
+        };
+    }
+
+    /// The audio to analyse for a spectrum, if it is raw audio. Warns once
+    /// when there is none, since the spectrum would otherwise be silently flat.
+    fn spectrum_audio(&mut self) -> Option<SharedSignal> {
+        let audio = self
+            .spectrum_audio
+            .clone()
+            .filter(|audio| audio.sample_rate() >= MIN_SPECTRUM_SAMPLE_RATE);
+        if audio.is_none() && !self.warned_no_spectrum_audio {
+            self.warned_no_spectrum_audio = true;
+            match &self.spectrum_audio {
+                Some(audio) => log::warn!(
+                    "Spectrum needs raw audio, but the track signal is only {} Hz; \
+                     the spectrogram and spectrum bars stay flat",
+                    audio.sample_rate()
+                ),
+                None => log::warn!(
+                    "Spectrum needs raw track audio and none was provided; \
+                     the spectrogram and spectrum bars stay flat"
+                ),
+            }
         }
+        audio
+    }
+
+    /// Set debug visualization options.
+    pub fn set_debug_options(&mut self, wireframe: bool, bounding_boxes: bool) {
+        self.debug_options.wireframe = wireframe;
+        self.debug_options.bounding_boxes = bounding_boxes;
+    }
+
+    /// Isolate a single entity for rendering (useful for debugging).
+    pub fn isolate_entity(&mut self, entity_id: u64) {
+        self.debug_options.isolated_entity = Some(EntityId(entity_id));
+    }
+
+    /// Clear entity isolation, resume normal rendering.
+    pub fn clear_isolation(&mut self) {
+        self.debug_options.isolated_entity = None;
+    }
+
+    /// Update the visualiser state for one frame.
+    pub fn update(
+        &mut self,
+        dt: f32,
+        rotation_signal: Option<&SharedSignal>,
+        zoom_signal: Option<&SharedSignal>,
+        named_signals: &SignalMap,
+        band_signals: &BandSignalMap,
+        custom_signals: &SignalMap,
+        musical_time: Option<&MusicalTimeStructure>,
+    ) {
+        self.advance_time(dt);
+        self.warm_up_after_seek(
+            dt,
+            rotation_signal,
+            zoom_signal,
+            named_signals,
+            band_signals,
+            custom_signals,
+            musical_time,
+        );
+
+        let sampled_signals = self.sample_inputs(dt, rotation_signal, zoom_signal, named_signals);
+
+        self.update_spectrum_bars();
 
         // Update script engine (this also syncs the scene graph)
         self.script_engine.update(
@@ -414,17 +917,26 @@ impl VisualiserState {
             musical_time,
         );
 
+        #[cfg(not(target_arch = "wasm32"))]
+        self.load_sprites();
+        self.load_mesh_assets();
+
         // Apply script debug options (these are set via dbg.wireframe(), dbg.isolate(), etc.)
         let script_debug = get_script_debug_options();
         self.debug_options.wireframe = script_debug.wireframe;
         self.debug_options.bounding_boxes = script_debug.bounding_boxes;
         self.debug_options.isolated_entity = script_debug.isolated_entity.map(EntityId);
         self.debug_options.debug_bounds_entities = script_debug.debug_bounds_entities;
+        self.debug_options.show_waveform = script_debug.show_waveform;
+        self.debug_options.solo_effect = script_debug.solo_effect;
+        self.debug_options.show_axes = script_debug.show_axes;
+        self.debug_options.axes_entities = script_debug.axes_entities;
+        self.update_spectrogram();
 
         // Update debug marker layer (processes pending marker requests from scripts)
         let current_beat = self.time * self.current_bpm / 60.0;
         self.debug_marker_layer
-            .update(current_beat, self.current_bpm);
+            .update(current_beat, self.current_bpm, musical_time);
     }
 
     /// Update the visualiser state with a frame budget.
@@ -446,54 +958,20 @@ impl VisualiserState {
     where
         F: Fn() -> f64,
     {
-        self.time += dt;
-
-        // Sample input signals
-        let amplitude = if let Some(sig) = rotation_signal {
-            let raw = sig.sample_window(self.time, dt);
-            if self.config.sigmoid_k > 0.0 {
-                sig.apply_sigmoid(raw, self.config.sigmoid_k)
-            } else {
-                raw
-            }
-        } else {
-            0.0
-        };
-
-        let flux = if let Some(sig) = zoom_signal {
-            let raw = sig.sample_window(self.time, dt);
-            if self.config.sigmoid_k > 0.0 {
-                sig.apply_sigmoid(raw, self.config.sigmoid_k)
-            } else {
-                raw
-            }
-        } else {
-            0.0
-        };
-
-        // Build signals map for script
-        let mut sampled_signals: HashMap<String, f32> = HashMap::new();
+        self.advance_time(dt);
+        self.warm_up_after_seek(
+            dt,
+            rotation_signal,
+            zoom_signal,
+            named_signals,
+            band_signals,
+            custom_signals,
+            musical_time,
+        );
 
-        // Sample all named signals
-        for (name, signal) in named_signals {
-            let raw = signal.sample_window(self.time, dt);
-            let val = if self.config.sigmoid_k > 0.0 {
-                signal.apply_sigmoid(raw, self.config.sigmoid_k)
-            } else {
-                raw
-            };
-            sampled_signals.insert(name.clone(), val);
-        }
+        let sampled_signals = self.sample_inputs(dt, rotation_signal, zoom_signal, named_signals);
 
-        // Add core signals
-        sampled_signals.insert("time".to_string(), self.time);
-        sampled_signals.insert("dt".to_string(), dt);
-        if !sampled_signals.contains_key("amplitude") {
-            sampled_signals.insert("amplitude".to_string(), amplitude);
-        }
-        if !sampled_signals.contains_key("flux") {
-            sampled_signals.insert("flux".to_string(), flux);
-        }
+        self.update_spectrum_bars();
 
         // Update script engine (this is the expensive part)
         self.script_engine.update(
@@ -526,11 +1004,16 @@ impl VisualiserState {
         self.debug_options.bounding_boxes = script_debug.bounding_boxes;
         self.debug_options.isolated_entity = script_debug.isolated_entity.map(EntityId);
         self.debug_options.debug_bounds_entities = script_debug.debug_bounds_entities;
+        self.debug_options.show_waveform = script_debug.show_waveform;
+        self.debug_options.solo_effect = script_debug.solo_effect;
+        self.debug_options.show_axes = script_debug.show_axes;
+        self.debug_options.axes_entities = script_debug.axes_entities;
+        self.update_spectrogram();
 
         // Update debug marker layer
         let current_beat = self.time * self.current_bpm / 60.0;
         self.debug_marker_layer
-            .update(current_beat, self.current_bpm);
+            .update(current_beat, self.current_bpm, musical_time);
 
         FrameResult::Completed
     }
@@ -557,7 +1040,7 @@ impl VisualiserState {
         input_signals: &SignalMap,
         band_signals: &BandSignalMap,
         musical_time: Option<&MusicalTimeStructure>,
-    ) -> Result<SignalChainAnalysis, String> {
+    ) -> Result<SignalChainAnalysis, VisualiserError> {
         self.script_engine.analyze_signal_chain(
             signal_name,
             center_time,
@@ -569,6 +1052,29 @@ impl VisualiserState {
             musical_time,
         )
     }
+
+    /// Densely sample a signal over `[start, end]` at `step` seconds (for editor previews).
+    pub fn sample_signal_range(
+        &mut self,
+        signal_name: &str,
+        start: f32,
+        end: f32,
+        step: f32,
+        input_signals: &SignalMap,
+        band_signals: &BandSignalMap,
+        musical_time: Option<&MusicalTimeStructure>,
+    ) -> Result<Vec<f32>, VisualiserError> {
+        self.script_engine.sample_signal_range(
+            signal_name,
+            start,
+            end,
+            step,
+            input_signals,
+            band_signals,
+            &self.stem_signals,
+            musical_time,
+        )
+    }
 }
 
 impl Default for VisualiserState {
@@ -576,3 +1082,335 @@ impl Default for VisualiserState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(state: &mut VisualiserState, dt: f32) {
+        let signals: SignalMap = HashMap::new();
+        let bands: BandSignalMap = HashMap::new();
+        state.update(dt, None, None, &signals, &bands, &signals, None);
+    }
+
+    #[test]
+    fn test_loop_wraps_to_start_and_requests_feedback_clear() {
+        let mut state = VisualiserState::new();
+        state.set_loop(Some(LoopRange::new(1.0, 2.0, None).unwrap()));
+        state.set_time(1.5);
+
+        step(&mut state, 0.25);
+        assert!((state.time - 1.75).abs() < 1e-6);
+        assert_eq!(state.seek_generation(), 0);
+
+        // Reaching the loop end seeks back to the start; the renderer clears
+        // feedback when it sees the seek generation change.
+        step(&mut state, 0.25);
+        assert_eq!(state.time, 1.0);
+        assert_eq!(state.seek_generation(), 1);
+
+        // Time past the loop end carries into the next pass
+        state.set_time(1.75);
+        step(&mut state, 0.5);
+        assert!((state.time - 1.25).abs() < 1e-6);
+        assert_eq!(state.seek_generation(), 2);
+
+        state.set_loop(None);
+        step(&mut state, 1.5);
+        assert!((state.time - 2.75).abs() < 1e-6);
+        assert_eq!(state.seek_generation(), 2);
+    }
+
+    #[test]
+    fn test_loop_wrap_rewarms_signal_state() {
+        let script = r#"
+            let level = gen.sin(1.0, 0.0).smooth.exponential(0.5, 0.5);
+            fn init(ctx) {}
+            fn update(dt, frame) {
+                dbg.plot("level", level);
+            }
+        "#;
+        let last_plotted = |state: &VisualiserState| {
+            let sparkline = &state.debug_plots()[0].sparkline;
+            (sparkline.count, sparkline.data[sparkline.cursor - 1])
+        };
+
+        // Play into t = 1.0 from the start of the track...
+        let mut clean = VisualiserState::new();
+        clean.try_load_script(script).unwrap();
+        clean.set_time(-0.25);
+        for _ in 0..5 {
+            step(&mut clean, 0.25);
+        }
+        assert!((clean.time - 1.0).abs() < 1e-6);
+
+        // ...and wrap onto it from the end of a loop, with smoothing state
+        // left over from the previous pass.
+        let mut looped = VisualiserState::new();
+        looped.try_load_script(script).unwrap();
+        looped.set_loop(Some(LoopRange::new(1.0, 2.0, None).unwrap()));
+        looped.set_time(1.0);
+        for _ in 0..3 {
+            step(&mut looped, 0.25);
+        }
+        let (count_before_wrap, _) = last_plotted(&looped);
+        step(&mut looped, 0.25);
+        assert_eq!(looped.time, 1.0);
+
+        // The wrap replays the second before the loop start (four frames)
+        // before the frame itself, so the smoothed value matches clean playback.
+        // Only the frame itself adds a plot point.
+        let (count, value) = last_plotted(&looped);
+        assert_eq!(count, count_before_wrap + 1);
+        let (_, expected) = last_plotted(&clean);
+        assert!((value - expected).abs() < 1e-5, "{} != {}", value, expected);
+    }
+
+    #[test]
+    fn test_loop_wrap_clears_feedback_on_gpu() {
+        let Some(mut headless) = crate::test_support::headless_or_skip(64, 64) else {
+            return;
+        };
+
+        // A red cube in the second half of the loop, echoed by full-strength feedback
+        let script = r#"
+            let cube = mesh.cube();
+            cube.color.r = 1.0;
+            cube.color.g = 0.0;
+            cube.color.b = 0.0;
+
+            fn init(ctx) {
+                scene.add(cube);
+                feedback.enable(feedback.builder().blend.max().opacity(1.0).build());
+            }
+
+            fn update(dt, frame) {
+                cube.position.x = if frame.time >= 1.5 { 0.0 } else { 1000.0 };
+            }
+        "#;
+        let is_red = |pixel: [u8; 4]| pixel[0] > pixel[1].saturating_add(64);
+        let run = |headless: &mut crate::test_support::HeadlessRenderer, looped: bool| {
+            let mut state = VisualiserState::new();
+            state.try_load_script(script).unwrap();
+            headless.renderer.request_feedback_clear();
+            if looped {
+                state.set_loop(Some(LoopRange::new(1.0, 2.0, None).unwrap()));
+            }
+            state.set_time(1.25);
+            for _ in 0..2 {
+                step(&mut state, 0.25);
+                assert!(is_red(headless.render(&state).center()));
+            }
+            // Looping wraps to t = 1.0; otherwise the same jump without a seek
+            step(&mut state, 0.25);
+            if !looped {
+                state.set_time(1.0);
+            }
+            headless.render(&state).center()
+        };
+
+        // Without a seek the cube's echo lingers once the cube has moved away...
+        assert!(is_red(run(&mut headless, false)));
+        // ...but the loop wrap clears it
+        let wrapped = run(&mut headless, true);
+        assert!(
+            !is_red(wrapped),
+            "feedback survived the wrap: {:?}",
+            wrapped
+        );
+    }
+
+    #[test]
+    fn test_spectrum_reads_raw_audio_but_not_envelopes() {
+        let script = r#"
+            let bars = scene.spectrumBars(#{ count: 4 });
+
+            fn init(ctx) {
+                dbg.showSpectrogram(#{});
+            }
+
+            fn update(dt, frame) {}
+        "#;
+        let sine = |rate: f32| -> SharedSignal {
+            let samples = (0..rate as usize)
+                .map(|i| (i as f32 / rate * 440.0 * std::f32::consts::TAU).sin())
+                .collect();
+            std::rc::Rc::new(InputSignal::new(samples, rate))
+        };
+        let run = |audio: Option<SharedSignal>| {
+            let mut state = VisualiserState::new();
+            state.set_spectrum_audio(audio);
+            state.try_load_script(script).unwrap();
+            state.set_time(0.5);
+            // The bars request their bins on the first sync
+            step(&mut state, 1.0 / 60.0);
+            step(&mut state, 1.0 / 60.0);
+            let spectrogram = state.spectrogram().expect("spectrogram enabled");
+            let silent = Spectrogram::new(spectrogram.options).column_rgba(0);
+            let column = spectrogram.column_rgba(1);
+            (state.script_engine.spectrum.clone(), column != silent)
+        };
+
+        // Raw audio drives both
+        let (spectrum, drawn) = run(Some(sine(44100.0)));
+        assert!(spectrum.iter().any(|&level| level > 0.5), "{:?}", spectrum);
+        assert!(drawn);
+
+        // An analysis envelope (like a package's amplitude signal) is ignored
+        // rather than FFT'd into a meaningless spectrum
+        let (spectrum, drawn) = run(Some(sine(100.0)));
+        assert!(spectrum.is_empty(), "{:?}", spectrum);
+        assert!(!drawn);
+        let (spectrum, drawn) = run(None);
+        assert!(spectrum.is_empty() && !drawn);
+    }
+
+    #[test]
+    fn test_fixed_frame_rate_advances_one_second() {
+        let mut state = VisualiserState::new();
+        let dt = 1.0 / 30.0;
+        for _ in 0..30 {
+            step(&mut state, dt);
+        }
+        assert_eq!(state.dt, dt);
+        assert!((state.time - 1.0).abs() < 1e-5, "time = {}", state.time);
+    }
+
+    #[test]
+    fn test_loop_end_clamped_to_track_length() {
+        let range = LoopRange::new(4.0, 12.0, Some(10.0)).unwrap();
+        assert_eq!(range.end, 10.0);
+
+        // A loop that starts past the end of the track is empty
+        assert!(LoopRange::new(11.0, 12.0, Some(10.0)).is_err());
+        assert!(LoopRange::new(2.0, 1.0, None).is_err());
+    }
+
+    #[test]
+    fn test_restore_snapshot_returns_scene_to_captured_state() {
+        let mut state = VisualiserState::new();
+        state
+            .try_load_script(
+                r#"
+                let cube;
+                let ball;
+                let glow;
+                fn init(ctx) {
+                    cube = mesh.cube();
+                    scene.add(cube);
+                }
+                fn update(dt, frame) {
+                    cube.position.x += 1.0;
+                    if cube.position.x > 2.5 && ball == () {
+                        ball = mesh.sphere();
+                        scene.add(ball);
+                        glow = fx.bloom(#{ threshold: 0.5, intensity: 0.8 });
+                        post.add(glow);
+                    }
+                }
+            "#,
+            )
+            .unwrap();
+        step(&mut state, 0.1);
+
+        let cube_x = |state: &VisualiserState| {
+            let (_, cube) = state.scene_graph().scene_entities().next().unwrap();
+            cube.transform().position.x
+        };
+        let snapshot = state.capture_snapshot();
+        assert_eq!(cube_x(&state), 1.0);
+
+        for _ in 0..3 {
+            step(&mut state, 0.1);
+        }
+        assert_eq!(cube_x(&state), 4.0);
+        assert_eq!(state.scene_graph().scene_entities().count(), 2);
+        assert_eq!(state.post_chain().effects.len(), 1);
+
+        state.restore_snapshot(&snapshot);
+        assert_eq!(cube_x(&state), 1.0);
+        assert_eq!(state.scene_graph().scene_entities().count(), 1);
+        assert!(state.post_chain().is_empty());
+    }
+
+    #[test]
+    fn test_show_grid_sets_reference_grid_for_renderer() {
+        let mut state = VisualiserState::new();
+        state
+            .try_load_script(
+                r#"
+                fn init(ctx) {}
+                fn update(dt, frame) {
+                    if frame.time < 0.15 {
+                        dbg.showGrid(#{ spacing: 0.25, color: #{ r: 1.0, g: 0.0, b: 0.0 }, checker: true });
+                    } else {
+                        dbg.hideGrid();
+                    }
+                }
+            "#,
+            )
+            .unwrap();
+        assert_eq!(state.reference_grid(), None);
+
+        step(&mut state, 0.1);
+        let grid = state.reference_grid().expect("grid enabled");
+        assert_eq!(grid.spacing, 0.25);
+        assert_eq!(grid.color, [1.0, 0.0, 0.0, 0.35]);
+        assert!(grid.checker);
+
+        // Offline renders suppress the grid unless --show-grid is passed.
+        state.set_reference_grid_allowed(false);
+        assert_eq!(state.reference_grid(), None);
+        state.set_reference_grid_allowed(true);
+
+        step(&mut state, 0.1);
+        assert_eq!(state.reference_grid(), None);
+    }
+
+    #[test]
+    fn test_show_axes_produces_axis_segments() {
+        let mut state = VisualiserState::new();
+        state
+            .try_load_script(
+                r#"
+                let cube;
+                fn init(ctx) {
+                    cube = mesh.cube();
+                    cube.position.x = 2.0;
+                    cube.rotation.z = 1.5707964;
+                    scene.add(cube);
+                }
+                fn update(dt, frame) {
+                    dbg.showAxes(true);
+                    dbg.showAxes(cube);
+                }
+            "#,
+            )
+            .unwrap();
+        assert!(state.axis_gizmo_segments().is_empty());
+
+        step(&mut state, 0.1);
+        let segments = state.axis_gizmo_segments();
+        assert_eq!(segments.len(), 6);
+
+        let close = |a: glam::Vec3, b: glam::Vec3| (a - b).length() < 1e-5;
+        // World origin: unit X, Y and Z in red, green and blue.
+        for (segment, end) in
+            segments[..3]
+                .iter()
+                .zip([glam::Vec3::X, glam::Vec3::Y, glam::Vec3::Z])
+        {
+            assert!(close(segment.start, glam::Vec3::ZERO));
+            assert!(close(segment.end, end), "{:?}", segment);
+        }
+        assert_eq!(segments[0].color, crate::axis_gizmo::AXIS_COLORS[0]);
+        assert_eq!(segments[2].color, crate::axis_gizmo::AXIS_COLORS[2]);
+
+        // Entity: follows its position and rotation (X now points along +Y).
+        let origin = glam::Vec3::new(2.0, 0.0, 0.0);
+        assert!(close(segments[3].start, origin));
+        assert!(close(segments[3].end, origin + glam::Vec3::Y));
+        assert!(close(segments[4].end, origin - glam::Vec3::X));
+        assert!(close(segments[5].end, origin + glam::Vec3::Z));
+    }
+}
@@ -14,9 +14,11 @@ use crate::gpu::renderer::Renderer;
 use crate::input::{BandSignalMap, InputSignal, SharedSignal, SignalMap};
 use crate::musical_time::MusicalTimeStructure;
 use crate::script_api::script_api_metadata_json;
+use crate::script_diagnostics::ScriptSeverity;
+use crate::scripting::{SandboxConfig, SceneSnapshot};
 // Note: ScriptSignalInfo and SignalChainAnalysis are used via state methods
 // but not directly referenced in this file (they're serialized to JSON)
-use crate::visualiser::{FrameBudget, FrameResult, VisualiserState};
+use crate::visualiser::{FrameBudget, FrameResult, LoopRange, VisualiserState};
 
 /// Debug struct for entity positions, serialized to JSON for debugging.
 #[derive(Serialize)]
@@ -89,6 +91,34 @@ struct VisualiserContext {
     musical_time: Option<MusicalTimeStructure>,
     /// Frequency band structure for band-aware processing
     frequency_bands: Option<FrequencyBandStructure>,
+    /// Scene captured with `capture_snapshot()` for A/B comparison.
+    snapshot: Option<SceneSnapshot>,
+}
+
+impl VisualiserContext {
+    /// Draw the current state to the canvas, reconfiguring a lost surface.
+    fn present(&mut self) {
+        match self.surface.get_current_texture() {
+            Ok(output) => {
+                let view = output
+                    .texture
+                    .create_view(&wgpu::TextureViewDescriptor::default());
+                self.renderer.render(&view, &self.state);
+                output.present();
+            }
+            Err(wgpu::SurfaceError::Lost) => {
+                self.renderer
+                    .resize(self.config.width, self.config.height, &self.state);
+                self.surface.configure(self.renderer.device(), &self.config);
+            }
+            Err(wgpu::SurfaceError::OutOfMemory) => {
+                log::error!("Surface out of memory");
+            }
+            Err(e) => {
+                log::warn!("Surface error: {:?}", e);
+            }
+        }
+    }
 }
 
 #[wasm_bindgen]
@@ -124,6 +154,29 @@ impl WasmVisualiser {
         inner.rotation_signal = Some(Rc::new(InputSignal::new(samples.to_vec(), sample_rate)));
     }
 
+    /// Push the track audio drawn by the `dbg.showWaveform()` overlay.
+    pub fn push_waveform_audio(&self, samples: &[f32], sample_rate: f32) {
+        let mut inner = self.inner.borrow_mut();
+        inner
+            .state
+            .set_waveform_audio(Some(Rc::new(InputSignal::new(
+                samples.to_vec(),
+                sample_rate,
+            ))));
+    }
+
+    /// Push the raw track audio (PCM) analysed by `dbg.showSpectrogram()` and
+    /// `scene.spectrumBars()`.
+    pub fn push_spectrum_audio(&self, samples: &[f32], sample_rate: f32) {
+        let mut inner = self.inner.borrow_mut();
+        inner
+            .state
+            .set_spectrum_audio(Some(Rc::new(InputSignal::new(
+                samples.to_vec(),
+                sample_rate,
+            ))));
+    }
+
     pub fn push_zoom_data(&self, samples: &[f32], sample_rate: f32) {
         log::info!(
             "Rust received zoom data: {} samples, rate {}",
@@ -354,6 +407,42 @@ impl WasmVisualiser {
             .push_stem_signal(stem_id, stem_label, feature, signal);
     }
 
+    /// Push a stem-scoped band signal (one band's feature analysed on one stem).
+    /// Exposed to scripts as `inputs.stems[stem].bands[band].<feature>`.
+    ///
+    /// - `stem_id` / `stem_label`: The stem the band was analysed on.
+    /// - `band_id` / `band_label`: The frequency band.
+    /// - `feature`: Signal type ("energy", "onset", "flux", "centroid").
+    /// - `samples`: Signal data.
+    /// - `sample_rate`: Sample rate of the signal.
+    pub fn push_stem_band_signal(
+        &self,
+        stem_id: &str,
+        stem_label: &str,
+        band_id: &str,
+        band_label: &str,
+        feature: &str,
+        samples: &[f32],
+        sample_rate: f32,
+    ) {
+        log::info!(
+            "Rust received stem band signal '{}' / '{}' / '{}': {} samples, rate {}",
+            stem_id,
+            band_id,
+            feature,
+            samples.len(),
+            sample_rate
+        );
+        let mut inner = self.inner.borrow_mut();
+        let signal = Rc::new(InputSignal::new(samples.to_vec(), sample_rate));
+        inner
+            .stem_id_to_label
+            .insert(stem_id.to_string(), stem_label.to_string());
+        inner
+            .state
+            .push_stem_band_signal(stem_id, stem_label, band_id, band_label, feature, signal);
+    }
+
     /// Clear all stem signals.
     pub fn clear_stem_signals(&self) {
         let mut inner = self.inner.borrow_mut();
@@ -826,6 +915,16 @@ impl WasmVisualiser {
         serde_json::to_string(&diags).unwrap_or_else(|_| "[]".to_string())
     }
 
+    /// Like `take_script_diagnostics_json`, keeping only diagnostics at or
+    /// above `min_severity` ("info", "warning" or "error"; unknown names keep
+    /// everything).
+    pub fn take_script_diagnostics_json_at_least(&self, min_severity: &str) -> String {
+        let min_severity = ScriptSeverity::from_name(min_severity).unwrap_or(ScriptSeverity::Info);
+        let mut inner = self.inner.borrow_mut();
+        let diags = inner.state.take_script_diagnostics_at_least(min_severity);
+        serde_json::to_string(&diags).unwrap_or_else(|_| "[]".to_string())
+    }
+
     pub fn resize(&self, width: u32, height: u32) {
         if width == 0 || height == 0 {
             return;
@@ -846,6 +945,54 @@ impl WasmVisualiser {
         inner.state.set_time(time);
     }
 
+    /// Jump playback to a time, resetting signal state and clearing feedback.
+    pub fn seek(&self, time: f32) {
+        let mut inner = self.inner.borrow_mut();
+        inner.state.seek(time);
+    }
+
+    /// Loop playback between `start` and `end` seconds.
+    ///
+    /// `track_duration` clamps an `end` past the end of the track; pass 0 if
+    /// unknown. Returns false if the range is invalid.
+    pub fn set_loop(&self, start: f32, end: f32, track_duration: f32) -> bool {
+        let duration = (track_duration > 0.0).then_some(track_duration);
+        match LoopRange::new(start, end, duration) {
+            Ok(range) => {
+                self.inner.borrow_mut().state.set_loop(Some(range));
+                true
+            }
+            Err(e) => {
+                log::error!("Invalid loop range: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Disable loop playback.
+    pub fn clear_loop(&self) {
+        self.inner.borrow_mut().state.set_loop(None);
+    }
+
+    /// Cap the total particle instances rendered per frame across all systems.
+    /// Pass 0 to remove the cap.
+    pub fn set_particle_budget(&self, budget: u32) {
+        let budget = (budget > 0).then_some(budget as usize);
+        self.inner.borrow_mut().state.set_particle_budget(budget);
+    }
+
+    /// Set the script's map size limit, which counts every property of every
+    /// entity held in arrays such as `scene.grid()` results. Call before
+    /// loading the script so init() runs under it too.
+    pub fn set_max_map_size(&self, max_map_size: u32) {
+        let mut inner = self.inner.borrow_mut();
+        let config = SandboxConfig {
+            max_map_size: max_map_size as usize,
+            ..inner.state.sandbox_config()
+        };
+        inner.state.set_sandbox_config(config);
+    }
+
     /// Set debug visualization options.
     pub fn set_debug_options(&self, wireframe: bool, bounding_boxes: bool) {
         let mut inner = self.inner.borrow_mut();
@@ -895,6 +1042,24 @@ impl WasmVisualiser {
         }
     }
 
+    /// Register a mesh asset from glTF content (GLB bytes, or a .gltf file
+    /// with embedded buffers). The asset will be available as
+    /// `mesh.load(asset_id)` in scripts.
+    /// Returns true if successful, false if parsing failed.
+    pub fn register_mesh_asset_gltf(&self, asset_id: &str, gltf_content: &[u8]) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        match inner.state.register_mesh_asset_gltf(asset_id, gltf_content) {
+            Ok(()) => {
+                log::info!("Registered glTF mesh asset '{}'", asset_id);
+                true
+            }
+            Err(e) => {
+                log::error!("Failed to register glTF mesh asset '{}': {}", asset_id, e);
+                false
+            }
+        }
+    }
+
     /// Unregister a mesh asset.
     /// Returns true if the asset was unregistered, false if it didn't exist.
     pub fn unregister_mesh_asset(&self, asset_id: &str) -> bool {
@@ -914,6 +1079,28 @@ impl WasmVisualiser {
         serde_json::to_string(&ids).unwrap_or_else(|_| "[]".to_string())
     }
 
+    // === Sprite Methods ===
+
+    /// Register a sprite texture for billboard particles from decoded RGBA8 bytes.
+    /// `sprite_id` is the path scripts pass to `sys.sprite()`.
+    /// Returns true if successful, false if the size doesn't match the data.
+    pub fn register_sprite(&self, sprite_id: &str, width: u32, height: u32, rgba: &[u8]) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        match inner
+            .state
+            .register_sprite_rgba(sprite_id, width, height, rgba.to_vec())
+        {
+            Ok(()) => {
+                log::info!("Registered sprite '{}'", sprite_id);
+                true
+            }
+            Err(e) => {
+                log::error!("Failed to register sprite '{}': {}", sprite_id, e);
+                false
+            }
+        }
+    }
+
     /// Get current state values for debugging.
     /// Returns [time, scene_entity_count, mesh_count, line_count]
     pub fn get_current_vals(&self) -> Vec<f32> {
@@ -1022,26 +1209,40 @@ impl WasmVisualiser {
         );
 
         // Render
-        match ctx.surface.get_current_texture() {
-            Ok(output) => {
-                let view = output
-                    .texture
-                    .create_view(&wgpu::TextureViewDescriptor::default());
-                ctx.renderer.render(&view, &ctx.state);
-                output.present();
-            }
-            Err(wgpu::SurfaceError::Lost) => {
-                ctx.renderer
-                    .resize(ctx.config.width, ctx.config.height, &ctx.state);
-                ctx.surface.configure(ctx.renderer.device(), &ctx.config);
-            }
-            Err(wgpu::SurfaceError::OutOfMemory) => {
-                log::error!("Surface out of memory");
-            }
-            Err(e) => {
-                log::warn!("Surface error: {:?}", e);
-            }
-        }
+        ctx.present();
+    }
+
+    /// Freeze the current scene, post chain, feedback and particles so it can be
+    /// shown again with `show_snapshot()` after editing the script. Replaces any
+    /// earlier snapshot.
+    pub fn capture_snapshot(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.snapshot = Some(inner.state.capture_snapshot());
+    }
+
+    /// Whether a snapshot has been captured.
+    pub fn has_snapshot(&self) -> bool {
+        self.inner.borrow().snapshot.is_some()
+    }
+
+    /// Restore the captured snapshot and draw it without running the script.
+    /// The next `render()` updates the script over it as usual.
+    ///
+    /// Returns false (drawing nothing) if no snapshot has been captured.
+    pub fn show_snapshot(&self) -> bool {
+        let mut inner = self.inner.borrow_mut();
+        let ctx = &mut *inner;
+        let Some(snapshot) = ctx.snapshot.as_ref() else {
+            return false;
+        };
+        ctx.state.restore_snapshot(snapshot);
+        ctx.present();
+        true
+    }
+
+    /// Discard the captured snapshot.
+    pub fn clear_snapshot(&self) {
+        self.inner.borrow_mut().snapshot = None;
     }
 
     /// Render with a frame budget timeout.
@@ -1084,26 +1285,7 @@ impl WasmVisualiser {
         }
 
         // Render
-        match ctx.surface.get_current_texture() {
-            Ok(output) => {
-                let view = output
-                    .texture
-                    .create_view(&wgpu::TextureViewDescriptor::default());
-                ctx.renderer.render(&view, &ctx.state);
-                output.present();
-            }
-            Err(wgpu::SurfaceError::Lost) => {
-                ctx.renderer
-                    .resize(ctx.config.width, ctx.config.height, &ctx.state);
-                ctx.surface.configure(ctx.renderer.device(), &ctx.config);
-            }
-            Err(wgpu::SurfaceError::OutOfMemory) => {
-                log::error!("Surface out of memory");
-            }
-            Err(e) => {
-                log::warn!("Surface error: {:?}", e);
-            }
-        }
+        ctx.present();
 
         true
     }
@@ -1155,6 +1337,7 @@ impl WasmVisualiser {
                     signals: wasm_signals,
                     step_count: result.step_count,
                     duration: result.duration,
+                    detected_bpm: result.detected_tempo.map(|t| t.bpm),
                 };
 
                 serde_json::to_string(&wasm_result).unwrap_or_else(|e| {
@@ -1171,6 +1354,7 @@ impl WasmVisualiser {
                     signals: vec![],
                     step_count: 0,
                     duration: 0.0,
+                    detected_bpm: None,
                 };
                 serde_json::to_string(&wasm_result).unwrap_or_else(|_| {
                     r#"{"success":false,"error":"Unknown error","signals":[],"step_count":0,"duration":0}"#.to_string()
@@ -1237,6 +1421,7 @@ impl WasmVisualiser {
                     event_streams: wasm_event_streams,
                     step_count: result.step_count,
                     duration: result.duration,
+                    detected_bpm: result.detected_tempo.map(|t| t.bpm),
                 };
 
                 serde_json::to_string(&wasm_result).unwrap_or_else(|e| {
@@ -1254,6 +1439,7 @@ impl WasmVisualiser {
                     event_streams: vec![],
                     step_count: 0,
                     duration: 0.0,
+                    detected_bpm: None,
                 };
                 serde_json::to_string(&wasm_result).unwrap_or_else(|_| {
                     r#"{"success":false,"error":"Unknown error","signals":[],"event_streams":[],"step_count":0,"duration":0}"#.to_string()
@@ -1317,10 +1503,47 @@ impl WasmVisualiser {
             Ok(analysis) => serde_json::to_string(&analysis)
                 .unwrap_or_else(|e| format!(r#"{{"error":"Serialization error: {}"}}"#, e)),
             Err(e) => {
-                format!(r#"{{"error":"{}"}}"#, e.replace('"', "'"))
+                format!(r#"{{"error":"{}"}}"#, e.to_string().replace('"', "'"))
             }
         }
     }
+
+    /// Densely sample a signal for drawing its shape in the editor.
+    ///
+    /// Returns a Float32Array of values at `start, start + step, ...` up to `end`
+    /// (seconds), evaluated with fresh state as if played from `start`, capped at
+    /// `MAX_RANGE_SAMPLES` values. Returns an empty array for non-finite arguments,
+    /// and (with a log) if the signal is not found.
+    pub fn sample_signal_range(
+        &self,
+        signal_name: &str,
+        start: f32,
+        end: f32,
+        step: f32,
+    ) -> Vec<f32> {
+        let mut inner = self.inner.borrow_mut();
+
+        // Clone the data we need to avoid borrow conflicts
+        let named_signals = inner.named_signals.clone();
+        let band_signals = inner.band_signals.clone();
+        let musical_time = inner.musical_time.clone();
+
+        inner
+            .state
+            .sample_signal_range(
+                signal_name,
+                start,
+                end,
+                step,
+                &named_signals,
+                &band_signals,
+                musical_time.as_ref(),
+            )
+            .unwrap_or_else(|e| {
+                log::warn!("sample_signal_range: {}", e);
+                Vec::new()
+            })
+    }
 }
 
 /// A debug signal serialized for JavaScript.
@@ -1339,6 +1562,8 @@ struct WasmAnalysisResult {
     signals: Vec<WasmDebugSignal>,
     step_count: usize,
     duration: f32,
+    /// BPM detected from the onset envelope when no musical time was set.
+    detected_bpm: Option<f32>,
 }
 
 /// An event serialized for JavaScript.
@@ -1379,6 +1604,8 @@ struct WasmExtendedAnalysisResult {
     event_streams: Vec<WasmEventStream>,
     step_count: usize,
     duration: f32,
+    /// BPM detected from the onset envelope when no musical time was set.
+    detected_bpm: Option<f32>,
 }
 
 #[wasm_bindgen]
@@ -1459,6 +1686,7 @@ pub async fn create_visualiser(canvas: HtmlCanvasElement) -> Result<WasmVisualis
             custom_signal_id_to_label: HashMap::new(),
             musical_time: None,
             frequency_bands: None,
+            snapshot: None,
         })),
     })
 }
@@ -0,0 +1,112 @@
+//! Full-track waveform overlay shown with `dbg.showWaveform()` or `--show-waveform`.
+//!
+//! The track's audio is downsampled to one min/max pair per screen column and
+//! drawn across the bottom of the screen with the line pipeline, with a
+//! vertical playhead at the current time.
+
+/// Maximum number of columns in a waveform envelope.
+pub const MAX_WAVEFORM_COLUMNS: usize = 4096;
+
+/// Screen region (NDC offset, scale) of the waveform strip along the bottom edge.
+pub const WAVEFORM_REGION: ([f32; 2], [f32; 2]) = ([-1.0, -1.0], [2.0, 0.15]);
+
+/// Per-column min/max of a track's samples.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveformEnvelope {
+    /// `[min, max]` of the samples that fall in each column, left to right.
+    pub columns: Vec<[f32; 2]>,
+}
+
+impl WaveformEnvelope {
+    /// Downsample `samples` to `columns` min/max pairs. Columns that cover no
+    /// samples (more columns than samples) repeat the nearest sample.
+    pub fn from_samples(samples: &[f32], columns: usize) -> Self {
+        if samples.is_empty() {
+            return Self {
+                columns: vec![[0.0, 0.0]; columns],
+            };
+        }
+        let columns = (0..columns)
+            .map(|c| {
+                let start = c * samples.len() / columns;
+                let end = ((c + 1) * samples.len() / columns).max(start + 1);
+                samples[start..end.min(samples.len())]
+                    .iter()
+                    .fold([f32::INFINITY, f32::NEG_INFINITY], |[lo, hi], &s| {
+                        [lo.min(s), hi.max(s)]
+                    })
+            })
+            .collect();
+        Self { columns }
+    }
+
+    /// Largest absolute sample value, used to scale the envelope to its strip.
+    pub fn peak(&self) -> f32 {
+        self.columns
+            .iter()
+            .fold(0.0f32, |peak, [lo, hi]| peak.max(lo.abs()).max(hi.abs()))
+    }
+
+    /// Line-strip points zig-zagging between each column's min and max, with
+    /// values mapped to 0-1 around a 0.5 centre line.
+    pub fn to_gpu_data(&self) -> Vec<[f32; 2]> {
+        let peak = self.peak();
+        let scale = if peak > f32::EPSILON { 0.5 / peak } else { 0.0 };
+        self.columns
+            .iter()
+            .enumerate()
+            .flat_map(|(i, [lo, hi])| {
+                let x = i as f32;
+                [[x, 0.5 + lo * scale], [x, 0.5 + hi * scale]]
+            })
+            .collect()
+    }
+}
+
+/// Playhead position across the strip (0-1) for `time` in a track of `duration` seconds.
+pub fn playhead_fraction(time: f32, duration: f32) -> f32 {
+    if duration > 0.0 {
+        (time / duration).clamp(0.0, 1.0)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_has_one_pair_per_column() {
+        // One second of a 5 Hz sine at 8 kHz
+        let samples: Vec<f32> = (0..8000)
+            .map(|i| (i as f32 / 8000.0 * 5.0 * std::f32::consts::TAU).sin())
+            .collect();
+        let envelope = WaveformEnvelope::from_samples(&samples, 640);
+
+        assert_eq!(envelope.columns.len(), 640);
+        for [lo, hi] in &envelope.columns {
+            assert!(lo <= hi);
+            assert!((-1.0..=1.0).contains(lo) && (-1.0..=1.0).contains(hi));
+        }
+        assert!((envelope.peak() - 1.0).abs() < 1e-3);
+        assert_eq!(envelope.to_gpu_data().len(), 2 * 640);
+    }
+
+    #[test]
+    fn test_envelope_with_more_columns_than_samples() {
+        let envelope = WaveformEnvelope::from_samples(&[-1.0, 0.5], 4);
+        assert_eq!(
+            envelope.columns,
+            [[-1.0, -1.0], [-1.0, -1.0], [0.5, 0.5], [0.5, 0.5]]
+        );
+        assert_eq!(WaveformEnvelope::from_samples(&[], 3).columns.len(), 3);
+    }
+
+    #[test]
+    fn test_playhead_fraction() {
+        assert_eq!(playhead_fraction(5.0, 10.0), 0.5);
+        assert_eq!(playhead_fraction(12.0, 10.0), 1.0);
+        assert_eq!(playhead_fraction(1.0, 0.0), 0.0);
+    }
+}
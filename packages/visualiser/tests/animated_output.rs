@@ -0,0 +1,67 @@
+//! Render straight to an animated GIF through the CLI render path.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use image::AnimationDecoder;
+use visualiser::cli::execute_render_job;
+use visualiser::error::VisualiserError;
+use visualiser::render_job::{RenderJobSpec, RenderPhase};
+
+const SCRIPT: &str = r#"
+    let cube = mesh.cube();
+
+    fn init(ctx) {
+        scene.add(cube);
+    }
+
+    fn update(dt, frame) {
+        cube.rotation.y = frame.time;
+    }
+"#;
+
+#[test]
+fn five_frame_render_to_gif_has_five_frames() {
+    let dir = std::env::temp_dir().join(format!("octoseq-animated-output-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).expect("create temp dir");
+    let input = dir.join("input.json");
+    let script = dir.join("scene.rhai");
+    std::fs::write(&input, "[0.0, 0.25, 0.5, 0.75, 1.0]").unwrap();
+    std::fs::write(&script, SCRIPT).unwrap();
+
+    let gif_path: PathBuf = dir.join("loop.gif");
+    let mut job = RenderJobSpec::new(input, script, dir.join("frames"));
+    job.width = 32;
+    job.height = 24;
+    job.fps = 10.0;
+    job.duration = Some(0.5);
+    job.output_video = true;
+    job.video_path = Some(gif_path.clone());
+
+    match pollster::block_on(execute_render_job(&job, false, true)) {
+        Ok(()) => {}
+        Err(VisualiserError::Render(e)) if e.phase == RenderPhase::GpuSetup => {
+            eprintln!("Skipping animated output test: {}", e);
+            let _ = std::fs::remove_dir_all(&dir);
+            return;
+        }
+        Err(e) => panic!("render failed: {}", e),
+    }
+
+    let decoder = image::codecs::gif::GifDecoder::new(BufReader::new(
+        File::open(&gif_path).expect("GIF was written"),
+    ))
+    .expect("valid GIF");
+    let frames = decoder
+        .into_frames()
+        .collect_frames()
+        .expect("GIF frames decode");
+    assert_eq!(frames.len(), 5);
+    for frame in &frames {
+        assert_eq!(frame.buffer().dimensions(), (32, 24));
+        assert_eq!(frame.delay().numer_denom_ms(), (100, 1));
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
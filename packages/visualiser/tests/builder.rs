@@ -0,0 +1,149 @@
+//! Embedding API: configure a `VisualiserBuilder` and render a few frames headless.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use visualiser::builder::{AudioSource, VisualiserBuilder};
+use visualiser::contact_sheet::ContactSheetGrid;
+use visualiser::error::VisualiserError;
+use visualiser::render_job::RenderPhase;
+
+const SCRIPT: &str = r#"
+    let cube = mesh.cube();
+    cube.color.r = 1.0;
+    cube.color.g = 0.0;
+    cube.color.b = 0.0;
+
+    fn init(ctx) {
+        scene.add(cube);
+    }
+
+    fn update(dt, frame) {}
+"#;
+
+#[test]
+fn renders_frames_headless_with_callbacks() {
+    let frames = Rc::new(RefCell::new(Vec::new()));
+    let progress = Rc::new(RefCell::new(Vec::new()));
+
+    let result = VisualiserBuilder::new()
+        .script(SCRIPT)
+        .audio(AudioSource::Samples {
+            samples: vec![0.0, 0.5, 1.0, 0.5],
+            sample_rate: 10.0,
+        })
+        .resolution(32, 32)
+        .fps(10.0)
+        .seed(7)
+        .duration(0.2)
+        .on_frame({
+            let frames = frames.clone();
+            move |frame| frames.borrow_mut().push((frame.index, frame.pixel(16, 16)))
+        })
+        .on_progress({
+            let progress = progress.clone();
+            move |p| progress.borrow_mut().push((p.frame, p.total_frames))
+        })
+        .run();
+
+    let summary = match result {
+        Ok(summary) => summary,
+        Err(VisualiserError::Render(e)) if e.phase == RenderPhase::GpuSetup => {
+            eprintln!("Skipping builder render test: {}", e);
+            return;
+        }
+        Err(e) => panic!("render failed: {}", e),
+    };
+
+    assert_eq!(summary.frame_count, 2);
+    assert_eq!(*progress.borrow(), vec![(0, 2), (1, 2)]);
+
+    let frames = frames.borrow();
+    assert_eq!(frames.len(), 2);
+    for (index, (frame_index, [r, g, b, _])) in frames.iter().enumerate() {
+        assert_eq!(*frame_index, index);
+        assert!(*r > 64 && *r > g.saturating_mul(2) && *r > b.saturating_mul(2));
+    }
+}
+
+#[test]
+fn invalid_settings_are_reported_before_rendering() {
+    let err = VisualiserBuilder::new()
+        .script(SCRIPT)
+        .resolution(0, 32)
+        .run()
+        .unwrap_err();
+    assert!(matches!(err, VisualiserError::InvalidConfig(_)));
+
+    let err = VisualiserBuilder::new().duration(1.0).run().unwrap_err();
+    assert!(matches!(err, VisualiserError::InvalidConfig(_)));
+
+    let err = VisualiserBuilder::new()
+        .script(SCRIPT)
+        .duration(1.0)
+        .contact_sheet(ContactSheetGrid::new(2, 1).unwrap())
+        .run()
+        .unwrap_err();
+    assert!(matches!(err, VisualiserError::InvalidConfig(_)));
+
+    let err = VisualiserBuilder::new()
+        .script(SCRIPT)
+        .duration(1.0)
+        .bands("mel:4")
+        .run()
+        .unwrap_err();
+    assert!(matches!(err, VisualiserError::InvalidConfig(_)));
+
+    let err = VisualiserBuilder::new()
+        .script("fn init(ctx) {")
+        .duration(1.0)
+        .run()
+        .unwrap_err();
+    assert!(matches!(err, VisualiserError::ScriptCompile(_)));
+}
+
+#[test]
+fn writes_cli_outputs_with_loop_range() {
+    let dir = std::env::temp_dir().join(format!("octoseq-builder-{}", std::process::id()));
+    let times = Rc::new(RefCell::new(Vec::new()));
+
+    let result = VisualiserBuilder::new()
+        .script(SCRIPT)
+        .audio(AudioSource::Samples {
+            samples: vec![0.5; 20],
+            sample_rate: 10.0,
+        })
+        .resolution(32, 32)
+        .fps(10.0)
+        .duration(0.6)
+        .loop_range(0.5, 0.8)
+        .max_particles(100)
+        .contact_sheet(ContactSheetGrid::new(2, 1).unwrap())
+        .manifest(true)
+        .output_dir(&dir)
+        .on_frame({
+            let times = times.clone();
+            move |frame| times.borrow_mut().push(frame.time)
+        })
+        .run();
+
+    let summary = match result {
+        Ok(summary) => summary,
+        Err(VisualiserError::Render(e)) if e.phase == RenderPhase::GpuSetup => {
+            eprintln!("Skipping builder output test: {}", e);
+            return;
+        }
+        Err(e) => panic!("render failed: {}", e),
+    };
+
+    assert_eq!(summary.frame_count, 6);
+    assert!(dir.join("contact_sheet.png").is_file());
+    assert!(dir.join("manifest.json").is_file());
+    assert!(!dir.join("frame_00000.png").exists());
+    // Only the sampled frames are drawn, all inside the loop
+    let times = times.borrow();
+    assert_eq!(times.len(), 2);
+    assert!(times.iter().all(|t| (0.5..0.8).contains(t)), "{:?}", times);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,67 @@
+//! Offline renders must be reproducible: running the same script twice with the
+//! same seed has to produce identical scene, particle and post-processing state
+//! on every frame.
+
+use visualiser::replay::{replay_frame_hashes, verify_deterministic_replay, ReplayConfig};
+
+const SCRIPT: &str = r#"
+let cube = mesh.cube();
+cube.position.x = gen.noise("white", 7);
+cube.rotation.y = gen.perlin(0.5, 3);
+cube.rotation.z = timing.time.scale(0.5);
+
+let sparks = particles.stream(gen.noise("white", 11), #{
+    mode: "threshold",
+    threshold: 0.5,
+    instances_per_burst: 4,
+    lifetime_beats: 1.0,
+    spread: #{ x: 0.5, y: 0.5, z: 0.5 },
+    rotation_variation: 1.0
+});
+sparks.rotation(#{ velocity: 2.0, randomize: 1.0 });
+
+let bloom = fx.bloom(#{ intensity: gen.noise("pink", 5) });
+
+fn init(ctx) {
+    scene.add(cube);
+    scene.add(sparks);
+    post.add(bloom);
+}
+
+fn update(dt, frame) {
+}
+"#;
+
+#[test]
+fn replay_with_particles_and_noise_is_deterministic() {
+    let config = ReplayConfig {
+        frames: 90,
+        seed: 42,
+        ..Default::default()
+    };
+
+    if let Err(e) = verify_deterministic_replay(SCRIPT, &config) {
+        panic!("{}", e);
+    }
+
+    // The state actually evolves, so matching hashes aren't trivially equal
+    let hashes = replay_frame_hashes(SCRIPT, &config).expect("script should run");
+    assert_ne!(hashes.first(), hashes.last());
+}
+
+#[test]
+fn replay_hash_depends_on_seed() {
+    let config = ReplayConfig {
+        frames: 30,
+        seed: 1,
+        ..Default::default()
+    };
+    let other_seed = ReplayConfig {
+        seed: 2,
+        ..config.clone()
+    };
+
+    let a = verify_deterministic_replay(SCRIPT, &config).expect("script should replay");
+    let b = verify_deterministic_replay(SCRIPT, &other_seed).expect("script should replay");
+    assert_ne!(a, b);
+}
@@ -68,6 +68,27 @@ fn renders_frames_from_golden_package() {
         preset_name: None,
         output_video: false,
         video_path: None,
+        video_options: Default::default(),
+        loop_count: 0,
+        contact_sheet: None,
+        manifest: false,
+        bands: None,
+        stems: None,
+        events: None,
+        midi: None,
+        bpm: None,
+        beat_offset: None,
+        time_signature: None,
+        loop_start: None,
+        loop_end: None,
+        max_particles: None,
+        max_operations: None,
+        max_init_operations: None,
+        show_waveform: false,
+        show_grid: false,
+        exposure: 1.0,
+        replay_seconds: None,
+        watch_audio: false,
     };
 
     // Same render path the CLI `render --package` command uses.